@@ -0,0 +1,697 @@
+//! Content-addressed artifact store for cached step outputs.
+//!
+//! Steps with a `cache:` block archive their `paths` after a successful run.
+//! Archives are keyed by a hash of the step, its command and the `key`
+//! files, so returning to an earlier lockfile (e.g. switching branches back) restores the matching
+//! `node_modules` instead of reinstalling. Archives are gzipped tarballs
+//! created with the system `tar`, stored as `<key>.tar.gz` next to a
+//! `<key>.meta.json` sidecar.
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use crate::checks::change::hash_target;
+use crate::checks::ChangeKind;
+use crate::config::ArtifactCacheConfig;
+
+/// Get the default artifact store directory (`~/.bivvy/artifacts/`).
+pub fn default_artifact_dir() -> PathBuf {
+    crate::sys::home_dir()
+        .unwrap_or_else(|| PathBuf::from("~"))
+        .join(".bivvy")
+        .join("artifacts")
+}
+
+/// Compute the cache key for a step's `cache:` block.
+///
+/// The key covers the step name, its command, the contents of every `key`
+/// entry (files, directories, or glob patterns) and the list of cached
+/// `paths`. Missing key files are part of the key, so creating one later
+/// produces a different key.
+pub fn compute_key(
+    step: &str,
+    command: &str,
+    cache: &ArtifactCacheConfig,
+    project_root: &Path,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("step:{}\n", step));
+    hasher.update(format!("command:{}\n", command));
+    for entry in &cache.key {
+        let kind = if entry.contains(['*', '?', '[']) {
+            ChangeKind::Glob
+        } else {
+            ChangeKind::File
+        };
+        let hash = if kind == ChangeKind::File && project_root.join(entry).is_dir() {
+            let pattern = format!("{}/**/*", entry.trim_end_matches('/'));
+            hash_target(&pattern, &ChangeKind::Glob, project_root)
+        } else {
+            hash_target(entry, &kind, project_root)
+        };
+        hasher.update(format!(
+            "key:{}={}\n",
+            entry,
+            hash.as_deref().unwrap_or("missing")
+        ));
+    }
+    for path in &cache.paths {
+        hasher.update(format!("path:{}\n", path));
+    }
+    hex::encode(&hasher.finalize()[..16])
+}
+
+/// Why a `cache.paths` entry can't be cached, if it can't.
+///
+/// Entries are deleted before a restore, so each must name something
+/// strictly inside the project root.
+pub fn invalid_cache_path(path: &str) -> Option<&'static str> {
+    let path = Path::new(path);
+    if path.has_root() || path.components().any(|c| matches!(c, Component::Prefix(_))) {
+        return Some("is absolute");
+    }
+    if path.components().any(|c| c == Component::ParentDir) {
+        return Some("contains '..'");
+    }
+    if !path.components().any(|c| matches!(c, Component::Normal(_))) {
+        return Some("is the project root");
+    }
+    None
+}
+
+/// Resolve a cached path inside `project_root`.
+///
+/// Fails for paths [`invalid_cache_path`] rejects and for paths whose
+/// nearest existing ancestor resolves (through symlinks) outside the
+/// project root.
+fn project_path(project_root: &Path, path: &str) -> Result<PathBuf> {
+    if let Some(problem) = invalid_cache_path(path) {
+        bail!("Refusing cache path '{}': it {}", path, problem);
+    }
+    let root = project_root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {:?}", project_root))?;
+    let target = project_root.join(path);
+
+    let mut existing = target.as_path();
+    while existing.symlink_metadata().is_err() {
+        existing = existing.parent().unwrap_or(project_root);
+    }
+    let resolved = existing
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {:?}", existing))?;
+    // A missing target may sit directly under the root; an existing one
+    // must not be the root itself
+    let inside = resolved.starts_with(&root) && (existing != target || resolved != root);
+    if !inside {
+        bail!(
+            "Refusing cache path '{}': it resolves outside the project",
+            path
+        );
+    }
+    Ok(target)
+}
+
+/// Remove a file, symlink, or directory tree if it exists.
+fn remove_path(path: &Path) -> Result<()> {
+    let Ok(metadata) = path.symlink_metadata() else {
+        return Ok(());
+    };
+    if metadata.is_dir() {
+        fs::remove_dir_all(path).with_context(|| format!("Failed to remove {:?}", path))
+    } else {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {:?}", path))
+    }
+}
+
+/// Metadata for a stored artifact.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArtifactEntry {
+    /// Content key (hash of the key files and paths).
+    pub key: String,
+    /// Project the artifact was saved from.
+    pub project: String,
+    /// Step that produced the artifact.
+    pub step: String,
+    /// Archived paths, relative to the project root.
+    pub paths: Vec<String>,
+    /// Archive size in bytes.
+    pub size_bytes: u64,
+    /// When the artifact was saved.
+    pub created_at: DateTime<Utc>,
+    /// When the artifact was last saved or restored.
+    pub last_used_at: DateTime<Utc>,
+}
+
+/// Storage for step artifacts with size-based LRU eviction.
+#[derive(Debug)]
+pub struct ArtifactStore {
+    /// Root directory for artifacts.
+    root: PathBuf,
+    /// Maximum total archive size in bytes.
+    max_bytes: u64,
+}
+
+impl ArtifactStore {
+    /// Create a new artifact store limited to `max_mb` megabytes.
+    pub fn new(root: impl Into<PathBuf>, max_mb: u64) -> Self {
+        Self {
+            root: root.into(),
+            max_bytes: max_mb.saturating_mul(1024 * 1024),
+        }
+    }
+
+    /// Get the store root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Get the size limit in bytes.
+    pub fn max_bytes(&self) -> u64 {
+        self.max_bytes
+    }
+
+    fn archive_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{}.tar.gz", key))
+    }
+
+    fn metadata_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{}.meta.json", key))
+    }
+
+    /// Load an artifact's metadata, if both the archive and metadata exist.
+    pub fn get(&self, key: &str) -> Option<ArtifactEntry> {
+        if !self.archive_path(key).is_file() {
+            return None;
+        }
+        let json = fs::read_to_string(self.metadata_path(key)).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn save_metadata(&self, entry: &ArtifactEntry) -> Result<()> {
+        let json = serde_json::to_string_pretty(entry)?;
+        fs::write(self.metadata_path(&entry.key), json)?;
+        Ok(())
+    }
+
+    /// Archive `paths` from `project_root` under `key`.
+    ///
+    /// Paths that don't exist are left out; returns `Ok(None)` when none
+    /// exist. Fails without archiving if a path is outside the project. Evicts least recently used artifacts afterwards to stay within
+    /// the size limit.
+    pub fn save(
+        &self,
+        key: &str,
+        project: &str,
+        step: &str,
+        paths: &[String],
+        project_root: &Path,
+    ) -> Result<Option<ArtifactEntry>> {
+        let mut existing: Vec<&String> = Vec::new();
+        for path in paths {
+            if project_path(project_root, path)?.exists() {
+                existing.push(path);
+            }
+        }
+        if existing.is_empty() {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(&self.root)
+            .with_context(|| format!("Failed to create artifact directory {:?}", self.root))?;
+
+        let partial = self.root.join(format!("{}.tar.gz.partial", key));
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&partial)
+            .arg("-C")
+            .arg(project_root)
+            .args(existing.iter().map(|p| p.as_str()))
+            .status()
+            .context("Failed to run tar")?;
+        if !status.success() {
+            let _ = fs::remove_file(&partial);
+            bail!("tar exited with {} while archiving {}", status, step);
+        }
+        fs::rename(&partial, self.archive_path(key))?;
+
+        let now = Utc::now();
+        let entry = ArtifactEntry {
+            key: key.to_string(),
+            project: project.to_string(),
+            step: step.to_string(),
+            paths: existing.into_iter().cloned().collect(),
+            size_bytes: fs::metadata(self.archive_path(key))?.len(),
+            created_at: now,
+            last_used_at: now,
+        };
+        self.save_metadata(&entry)?;
+        self.evict()?;
+
+        Ok(Some(entry))
+    }
+
+    /// Restore the artifact stored under `key` into `project_root`.
+    ///
+    /// The archive is extracted into a staging directory inside the project
+    /// first; existing copies of the archived paths are only replaced once
+    /// extraction succeeds, so a corrupt archive leaves them untouched.
+    /// Nothing is touched if any path is outside the project. Returns
+    /// `Ok(None)` on a cache miss.
+    pub fn restore(&self, key: &str, project_root: &Path) -> Result<Option<ArtifactEntry>> {
+        let Some(mut entry) = self.get(key) else {
+            return Ok(None);
+        };
+
+        let targets = entry
+            .paths
+            .iter()
+            .map(|path| project_path(project_root, path))
+            .collect::<Result<Vec<_>>>()?;
+
+        let staging = project_root.join(format!(".bivvy-restore-{}", key));
+        remove_path(&staging)?;
+        fs::create_dir_all(&staging).with_context(|| format!("Failed to create {:?}", staging))?;
+        let result = self.extract_into(&entry, &staging, &targets, project_root);
+        let _ = fs::remove_dir_all(&staging);
+        result?;
+
+        entry.last_used_at = Utc::now();
+        self.save_metadata(&entry)?;
+        Ok(Some(entry))
+    }
+
+    /// Extract the archive into `staging`, then move each archived path
+    /// over its target.
+    fn extract_into(
+        &self,
+        entry: &ArtifactEntry,
+        staging: &Path,
+        targets: &[PathBuf],
+        project_root: &Path,
+    ) -> Result<()> {
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(self.archive_path(&entry.key))
+            .arg("-C")
+            .arg(staging)
+            .status()
+            .context("Failed to run tar")?;
+        if !status.success() {
+            bail!("tar exited with {} while restoring {}", status, entry.step);
+        }
+
+        for (path, target) in entry.paths.iter().zip(targets) {
+            let staged = staging.join(path);
+            remove_path(target)?;
+            if staged.symlink_metadata().is_err() {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {:?}", parent))?;
+            }
+            fs::rename(&staged, target).with_context(|| {
+                format!(
+                    "Failed to move {:?} into {:?}",
+                    path,
+                    project_root.display()
+                )
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Remove an artifact.
+    pub fn remove(&self, key: &str) -> Result<bool> {
+        let mut removed = false;
+        for path in [self.archive_path(key), self.metadata_path(key)] {
+            if path.exists() {
+                fs::remove_file(&path)?;
+                removed = true;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// List stored artifacts, most recently used first.
+    pub fn list(&self) -> Result<Vec<ArtifactEntry>> {
+        if !self.root.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let Some(key) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".meta.json"))
+            else {
+                continue;
+            };
+            if let Some(artifact) = self.get(key) {
+                entries.push(artifact);
+            }
+        }
+
+        entries.sort_by_key(|e| std::cmp::Reverse(e.last_used_at));
+        Ok(entries)
+    }
+
+    /// Get total archive size in bytes.
+    pub fn total_size(&self) -> Result<u64> {
+        Ok(self.list()?.iter().map(|e| e.size_bytes).sum())
+    }
+
+    /// Evict least recently used artifacts until the store fits its limit.
+    ///
+    /// Also removes leftover metadata without an archive (and vice versa).
+    /// Returns the evicted entries.
+    pub fn evict(&self) -> Result<Vec<ArtifactEntry>> {
+        self.remove_orphans()?;
+
+        let mut entries = self.list()?;
+        let mut total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+        let mut evicted = Vec::new();
+        while total > self.max_bytes {
+            let Some(oldest) = entries.pop() else {
+                break;
+            };
+            self.remove(&oldest.key)?;
+            total -= oldest.size_bytes;
+            evicted.push(oldest);
+        }
+        Ok(evicted)
+    }
+
+    /// Remove every artifact. Returns the number removed.
+    pub fn clear(&self) -> Result<usize> {
+        let entries = self.list()?;
+        for entry in &entries {
+            self.remove(&entry.key)?;
+        }
+        self.remove_orphans()?;
+        Ok(entries.len())
+    }
+
+    fn remove_orphans(&self) -> Result<()> {
+        if !self.root.is_dir() {
+            return Ok(());
+        }
+        for entry in fs::read_dir(&self.root)? {
+            let path = entry?.path();
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let orphan = if let Some(key) = name.strip_suffix(".meta.json") {
+                !self.archive_path(key).exists()
+            } else if let Some(key) = name.strip_suffix(".tar.gz") {
+                !self.metadata_path(key).exists()
+            } else {
+                name.ends_with(".partial")
+            };
+            if orphan {
+                let _ = fs::remove_file(&path);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn cache_config(key: &[&str], paths: &[&str]) -> ArtifactCacheConfig {
+        ArtifactCacheConfig {
+            key: key.iter().map(|s| s.to_string()).collect(),
+            paths: paths.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn key_changes_with_key_file_contents() {
+        let project = TempDir::new().unwrap();
+        let cache = cache_config(&["yarn.lock"], &["node_modules"]);
+
+        fs::write(project.path().join("yarn.lock"), "a").unwrap();
+        let first = compute_key("install", "yarn", &cache, project.path());
+        fs::write(project.path().join("yarn.lock"), "b").unwrap();
+        let second = compute_key("install", "yarn", &cache, project.path());
+        fs::write(project.path().join("yarn.lock"), "a").unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            compute_key("install", "yarn", &cache, project.path())
+        );
+    }
+
+    #[test]
+    fn key_covers_directories_and_missing_files() {
+        let project = TempDir::new().unwrap();
+        let cache = cache_config(&["src", ".nvmrc"], &["dist"]);
+        let missing = compute_key("build", "yarn build", &cache, project.path());
+
+        fs::create_dir(project.path().join("src")).unwrap();
+        fs::write(project.path().join("src/app.js"), "1").unwrap();
+        let with_src = compute_key("build", "yarn build", &cache, project.path());
+        fs::write(project.path().join("src/app.js"), "2").unwrap();
+
+        assert_ne!(missing, with_src);
+        assert_ne!(
+            with_src,
+            compute_key("build", "yarn build", &cache, project.path())
+        );
+    }
+
+    #[test]
+    fn key_covers_step_and_command() {
+        let project = TempDir::new().unwrap();
+        let cache = cache_config(&["yarn.lock"], &["dist"]);
+        let key = compute_key("build", "yarn build", &cache, project.path());
+
+        assert_ne!(
+            key,
+            compute_key("build", "yarn build --prod", &cache, project.path())
+        );
+        assert_ne!(
+            key,
+            compute_key("bundle", "yarn build", &cache, project.path())
+        );
+    }
+
+    #[test]
+    fn save_and_restore_round_trip() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        let modules = project.path().join("node_modules").join("left-pad");
+        fs::create_dir_all(&modules).unwrap();
+        fs::write(modules.join("index.js"), "v1").unwrap();
+
+        let entry = store
+            .save(
+                "abc",
+                "app",
+                "install",
+                &["node_modules".to_string(), "missing".to_string()],
+                project.path(),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.paths, vec!["node_modules".to_string()]);
+        assert!(entry.size_bytes > 0);
+
+        fs::write(modules.join("index.js"), "v2").unwrap();
+        fs::write(modules.join("extra.js"), "x").unwrap();
+
+        assert!(store.restore("abc", project.path()).unwrap().is_some());
+        assert_eq!(fs::read_to_string(modules.join("index.js")).unwrap(), "v1");
+        assert!(!modules.join("extra.js").exists());
+    }
+
+    #[test]
+    fn restore_keeps_targets_when_extraction_fails() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        fs::create_dir(project.path().join("dist")).unwrap();
+        fs::write(project.path().join("dist/app.js"), "v1").unwrap();
+        store
+            .save("abc", "app", "build", &["dist".to_string()], project.path())
+            .unwrap()
+            .unwrap();
+        fs::write(store_dir.path().join("abc.tar.gz"), "not a tarball").unwrap();
+        fs::write(project.path().join("dist/app.js"), "v2").unwrap();
+
+        assert!(store.restore("abc", project.path()).is_err());
+        assert_eq!(
+            fs::read_to_string(project.path().join("dist/app.js")).unwrap(),
+            "v2"
+        );
+        assert!(!project.path().join(".bivvy-restore-abc").exists());
+    }
+
+    #[test]
+    fn restore_creates_missing_parent_directories() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        fs::create_dir_all(project.path().join("vendor/bundle")).unwrap();
+        fs::write(project.path().join("vendor/bundle/gem.rb"), "v1").unwrap();
+        store
+            .save(
+                "abc",
+                "app",
+                "bundle",
+                &["vendor/bundle".to_string()],
+                project.path(),
+            )
+            .unwrap()
+            .unwrap();
+        fs::remove_dir_all(project.path().join("vendor")).unwrap();
+
+        assert!(store.restore("abc", project.path()).unwrap().is_some());
+        assert_eq!(
+            fs::read_to_string(project.path().join("vendor/bundle/gem.rb")).unwrap(),
+            "v1"
+        );
+    }
+
+    #[test]
+    fn invalid_cache_paths() {
+        assert_eq!(invalid_cache_path("node_modules"), None);
+        assert_eq!(invalid_cache_path("./vendor/bundle"), None);
+        assert_eq!(invalid_cache_path("/etc"), Some("is absolute"));
+        assert_eq!(invalid_cache_path("../sibling"), Some("contains '..'"));
+        assert_eq!(invalid_cache_path("a/../../b"), Some("contains '..'"));
+        assert_eq!(invalid_cache_path("."), Some("is the project root"));
+        assert_eq!(invalid_cache_path(""), Some("is the project root"));
+    }
+
+    #[test]
+    fn save_refuses_paths_outside_project() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        let err = store
+            .save(
+                "abc",
+                "app",
+                "install",
+                &["../x".to_string()],
+                project.path(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Refusing cache path '../x': it contains '..'"
+        );
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn restore_refuses_symlinks_out_of_project_without_deleting() {
+        let project = TempDir::new().unwrap();
+        let outside = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        fs::create_dir(project.path().join("dist")).unwrap();
+        fs::write(project.path().join("dist/app.js"), "v1").unwrap();
+        store
+            .save("abc", "app", "build", &["dist".to_string()], project.path())
+            .unwrap()
+            .unwrap();
+
+        fs::remove_dir_all(project.path().join("dist")).unwrap();
+        fs::write(outside.path().join("keep.txt"), "mine").unwrap();
+        std::os::unix::fs::symlink(outside.path(), project.path().join("dist")).unwrap();
+
+        let err = store.restore("abc", project.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Refusing cache path 'dist': it resolves outside the project"
+        );
+        assert!(outside.path().join("keep.txt").exists());
+    }
+
+    #[test]
+    fn restore_miss_returns_none() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        assert!(store.restore("nope", project.path()).unwrap().is_none());
+        assert!(store.list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn save_without_existing_paths_is_skipped() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+
+        let saved = store
+            .save("k", "app", "build", &["dist".to_string()], project.path())
+            .unwrap();
+        assert!(saved.is_none());
+    }
+
+    #[test]
+    fn evict_removes_least_recently_used() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        fs::write(project.path().join("out"), "data").unwrap();
+
+        let unlimited = ArtifactStore::new(store_dir.path(), 100);
+        for key in ["old", "new"] {
+            unlimited
+                .save(key, "app", "build", &["out".to_string()], project.path())
+                .unwrap();
+        }
+        let mut old = unlimited.get("old").unwrap();
+        old.last_used_at = Utc::now() - chrono::Duration::days(1);
+        unlimited.save_metadata(&old).unwrap();
+
+        let size = unlimited.get("new").unwrap().size_bytes;
+        let limited = ArtifactStore {
+            root: store_dir.path().to_path_buf(),
+            max_bytes: size,
+        };
+        let evicted = limited.evict().unwrap();
+
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].key, "old");
+        assert!(limited.get("new").is_some());
+    }
+
+    #[test]
+    fn clear_removes_everything() {
+        let project = TempDir::new().unwrap();
+        let store_dir = TempDir::new().unwrap();
+        let store = ArtifactStore::new(store_dir.path(), 100);
+        fs::write(project.path().join("out"), "data").unwrap();
+        store
+            .save("k", "app", "build", &["out".to_string()], project.path())
+            .unwrap();
+        fs::write(store_dir.path().join("stray.meta.json"), "{}").unwrap();
+
+        assert_eq!(store.clear().unwrap(), 1);
+        assert_eq!(fs::read_dir(store_dir.path()).unwrap().count(), 0);
+    }
+}
@@ -1,14 +1,21 @@
-//! Template caching system.
+//! Template and artifact caching.
 //!
 //! This module provides disk-based caching for remote templates with
-//! TTL-based and content-based (ETag/git) invalidation strategies.
+//! TTL-based and content-based (ETag/git) invalidation strategies, the
+//! content-addressed [`ArtifactStore`] for step outputs declared with
+//! `cache:`, and [`offline`] mode, which serves remote content only from
+//! these caches.
 
+pub mod artifacts;
 pub mod entry;
+pub mod offline;
 pub mod revalidation;
 pub mod store;
 pub mod validation;
 
+pub use artifacts::{compute_key, default_artifact_dir, ArtifactEntry, ArtifactStore};
 pub use entry::{CacheEntry, CacheMetadata};
+pub use offline::{is_offline, RemoteResource, RemoteResources, RemoteStores};
 pub use revalidation::{needs_revalidation, CacheRevalidator, RevalidationResult};
 pub use store::CacheStore;
 pub use validation::{format_duration, parse_ttl, CacheValidator, ValidationResult};
@@ -0,0 +1,381 @@
+//! Offline mode and cache warming.
+//!
+//! `--offline` (or `BIVVY_OFFLINE=1`) makes every network consumer use only
+//! content already on disk: the remote-config cache for `extends`, the
+//! [`CacheStore`] for HTTP template sources and existing clones for Git
+//! sources. Fetchers capture the mode when they are constructed.
+//!
+//! [`RemoteResources`] enumerates everything a project's configs reference
+//! remotely, following `extends` chains. `bivvy cache warm` fetches each
+//! one, and offline runs check them up front so anything missing is
+//! reported in a single error.
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+
+use crate::cache::{default_cache_dir, CacheStore};
+use crate::config::schema::{TemplateSource as TemplateSourceConfig, TemplateSourceKind};
+use crate::config::{load_config_value, ConfigPaths, RemoteFetcher};
+use crate::error::BivvyError;
+use crate::registry::fetch::{GitFetcher, HttpFetcher};
+use crate::registry::remote::RemoteLoader;
+
+/// Environment variable that enables offline mode.
+pub const OFFLINE_ENV: &str = "BIVVY_OFFLINE";
+
+/// Whether offline mode is enabled for this process.
+pub fn is_offline() -> bool {
+    is_truthy(std::env::var(OFFLINE_ENV).ok().as_deref())
+}
+
+/// Enable offline mode for this process and any bivvy it spawns.
+pub fn enable() {
+    std::env::set_var(OFFLINE_ENV, "1");
+}
+
+fn is_truthy(value: Option<&str>) -> bool {
+    match value {
+        Some(v) => !matches!(
+            v.trim().to_ascii_lowercase().as_str(),
+            "" | "0" | "false" | "no" | "off"
+        ),
+        None => false,
+    }
+}
+
+/// Error for resources that offline mode can't reach.
+pub fn unavailable(missing: Vec<String>) -> BivvyError {
+    BivvyError::OfflineUnavailable { missing }
+}
+
+/// Error for a network operation attempted in offline mode.
+pub fn refuse(what: &str) -> anyhow::Error {
+    unavailable(vec![what.to_string()]).into()
+}
+
+/// A remote resource referenced by a project's configs.
+#[derive(Debug, Clone)]
+pub enum RemoteResource {
+    /// A config pulled in with `extends:`.
+    Extends {
+        /// URL of the base config.
+        url: String,
+    },
+    /// A `template_sources:` entry.
+    TemplateSource(TemplateSourceConfig),
+}
+
+impl fmt::Display for RemoteResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RemoteResource::Extends { url } => write!(f, "extends {}", url),
+            RemoteResource::TemplateSource(source) => match source.effective_kind() {
+                TemplateSourceKind::Http => write!(f, "template source {}", source.url),
+                TemplateSourceKind::Git => match source.git_ref {
+                    Some(ref r) => write!(f, "git template source {} ({})", source.url, r),
+                    None => write!(f, "git template source {}", source.url),
+                },
+            },
+        }
+    }
+}
+
+/// The fetchers and stores remote resources are read from.
+pub struct RemoteStores {
+    /// Fetcher (and cache) for `extends` configs.
+    pub configs: RemoteFetcher,
+    /// Fetcher for HTTP template sources.
+    pub http: HttpFetcher,
+    /// Clone directory for Git template sources.
+    pub git: GitFetcher,
+    /// Cache for HTTP template sources.
+    pub cache: CacheStore,
+}
+
+impl Default for RemoteStores {
+    /// The stores `extends` resolution and the template registry use.
+    fn default() -> Self {
+        let cache_dir = default_cache_dir();
+        Self {
+            configs: RemoteFetcher::default(),
+            http: HttpFetcher::new(),
+            git: GitFetcher::new(cache_dir.join("git")),
+            cache: CacheStore::new(cache_dir),
+        }
+    }
+}
+
+/// Remote resources referenced by a project, in discovery order.
+#[derive(Debug, Default)]
+pub struct RemoteResources {
+    /// Resources found, deduplicated.
+    pub resources: Vec<RemoteResource>,
+    /// `extends` configs that couldn't be read, with the reason. Their own
+    /// references are unknown.
+    pub failures: Vec<(RemoteResource, String)>,
+}
+
+impl RemoteResources {
+    /// Collect what the configs in `paths` reference, reading `extends`
+    /// configs through `load`.
+    ///
+    /// `load` returns the content of an `extends` URL, or `None` when it
+    /// isn't available (offline and uncached).
+    pub fn collect<F>(paths: &ConfigPaths, mut load: F) -> crate::error::Result<Self>
+    where
+        F: FnMut(&str) -> anyhow::Result<Option<String>>,
+    {
+        let mut found = Self::default();
+        let mut seen = HashSet::new();
+        let mut pending = VecDeque::new();
+
+        for path in [&paths.user_global, &paths.project, &paths.project_local]
+            .into_iter()
+            .flatten()
+        {
+            pending.push_back(load_config_value(path)?);
+        }
+
+        while let Some(value) = pending.pop_front() {
+            for source in template_sources(&value) {
+                let key = format!("source:{}#{:?}", source.url, source.git_ref);
+                if seen.insert(key) {
+                    found.resources.push(RemoteResource::TemplateSource(source));
+                }
+            }
+
+            for url in extends_urls(&value) {
+                if !seen.insert(format!("extends:{}", url)) {
+                    continue;
+                }
+                let resource = RemoteResource::Extends { url: url.clone() };
+                match load(&url) {
+                    Ok(Some(content)) => match serde_yaml::from_str(&content) {
+                        Ok(base) => pending.push_back(base),
+                        Err(e) => found.failures.push((resource.clone(), e.to_string())),
+                    },
+                    Ok(None) => {}
+                    Err(e) => found.failures.push((resource.clone(), format!("{:#}", e))),
+                }
+                found.resources.push(resource);
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Resources that aren't available locally.
+    pub fn missing(&self, stores: &RemoteStores) -> Vec<&RemoteResource> {
+        self.resources
+            .iter()
+            .filter(|r| match r {
+                RemoteResource::Extends { url } => {
+                    !matches!(stores.configs.cached(url), Ok(Some(_)))
+                }
+                RemoteResource::TemplateSource(source) => {
+                    !RemoteLoader::is_cached(source, &stores.git, &stores.cache)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Find remote resources that offline mode can't serve.
+///
+/// `extends` chains are followed through cached content only.
+pub fn missing_offline(
+    paths: &ConfigPaths,
+    stores: &RemoteStores,
+) -> crate::error::Result<Vec<String>> {
+    let found = RemoteResources::collect(paths, |url| stores.configs.cached(url))?;
+    Ok(found
+        .missing(stores)
+        .into_iter()
+        .map(ToString::to_string)
+        .collect())
+}
+
+/// Fetch everything the configs in `paths` reference into local stores.
+///
+/// `extends` configs are re-fetched (following their own `extends`) and
+/// template sources are refreshed even if cached. Returns each resource
+/// with the outcome of fetching it.
+pub fn warm(
+    paths: &ConfigPaths,
+    stores: &RemoteStores,
+) -> crate::error::Result<Vec<(RemoteResource, anyhow::Result<()>)>> {
+    let found = RemoteResources::collect(paths, |url| stores.configs.refresh(url).map(Some))?;
+    let mut failures = found.failures;
+
+    Ok(found
+        .resources
+        .into_iter()
+        .map(|resource| {
+            let outcome = match resource {
+                RemoteResource::Extends { .. } => {
+                    let key = resource.to_string();
+                    match failures.iter().position(|(r, _)| r.to_string() == key) {
+                        Some(i) => Err(anyhow::anyhow!(failures.remove(i).1)),
+                        None => Ok(()),
+                    }
+                }
+                RemoteResource::TemplateSource(ref source) => {
+                    RemoteLoader::refresh_source(source, &stores.http, &stores.git, &stores.cache)
+                }
+            };
+            (resource, outcome)
+        })
+        .collect())
+}
+
+fn extends_urls(value: &serde_yaml::Value) -> Vec<String> {
+    value
+        .get("extends")
+        .and_then(|e| e.as_sequence())
+        .map(|list| {
+            list.iter()
+                .filter_map(|e| e.get("url").and_then(|u| u.as_str()))
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn template_sources(value: &serde_yaml::Value) -> Vec<TemplateSourceConfig> {
+    value
+        .get("template_sources")
+        .and_then(|s| s.as_sequence())
+        .map(|list| {
+            list.iter()
+                .filter_map(|s| serde_yaml::from_value(s.clone()).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use std::path::Path;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn project(dir: &Path, config: &str) -> ConfigPaths {
+        let path = dir.join("config.yml");
+        std::fs::write(&path, config).unwrap();
+        ConfigPaths {
+            extends: Vec::new(),
+            user_global: None,
+            project: Some(path),
+            split_steps: Vec::new(),
+            split_workflows: Vec::new(),
+            project_local: None,
+        }
+    }
+
+    fn stores(dir: &Path, offline: bool) -> RemoteStores {
+        RemoteStores {
+            configs: RemoteFetcher::with_cache_dir(Duration::from_secs(5), dir.join("configs"))
+                .with_offline(offline),
+            http: HttpFetcher::new().with_offline(offline),
+            git: GitFetcher::new(dir.join("git")).with_offline(offline),
+            cache: CacheStore::new(dir.join("templates")),
+        }
+    }
+
+    #[test]
+    fn truthy_values() {
+        assert!(is_truthy(Some("1")));
+        assert!(is_truthy(Some("true")));
+        assert!(!is_truthy(Some("0")));
+        assert!(!is_truthy(Some("false")));
+        assert!(!is_truthy(Some("")));
+        assert!(!is_truthy(None));
+    }
+
+    #[test]
+    fn collect_follows_extends_chain() {
+        let temp = TempDir::new().unwrap();
+        let paths = project(
+            temp.path(),
+            "extends:\n  - url: https://example.com/base.yml\ntemplate_sources:\n  - url: https://example.com/a.yml\n",
+        );
+
+        let found = RemoteResources::collect(&paths, |url| {
+            Ok(match url {
+                "https://example.com/base.yml" => Some(
+                    "extends:\n  - url: https://example.com/root.yml\ntemplate_sources:\n  - url: https://github.com/example/t.git\n    ref: v1\n".to_string(),
+                ),
+                _ => None,
+            })
+        })
+        .unwrap();
+
+        let names: Vec<String> = found.resources.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            names,
+            [
+                "template source https://example.com/a.yml",
+                "extends https://example.com/base.yml",
+                "git template source https://github.com/example/t.git (v1)",
+                "extends https://example.com/root.yml",
+            ]
+        );
+    }
+
+    #[test]
+    fn warm_then_nothing_missing_offline() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/base.yml");
+            then.status(200).body(format!(
+                "template_sources:\n  - url: {}\n",
+                server.url("/templates.yml")
+            ));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/templates.yml");
+            then.status(200).body(
+                "name: remote-tool\ndescription: Remote\ncategory: tools\nstep:\n  command: x\n",
+            );
+        });
+
+        let temp = TempDir::new().unwrap();
+        let paths = project(
+            temp.path(),
+            &format!("extends:\n  - url: {}\n", server.url("/base.yml")),
+        );
+
+        let missing = missing_offline(&paths, &stores(temp.path(), true)).unwrap();
+        assert_eq!(missing, [format!("extends {}", server.url("/base.yml"))]);
+
+        let results = warm(&paths, &stores(temp.path(), false)).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results.iter().all(|(_, outcome)| outcome.is_ok()));
+
+        assert!(missing_offline(&paths, &stores(temp.path(), true))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn warm_reports_failures_per_resource() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/base.yml");
+            then.status(404);
+        });
+
+        let temp = TempDir::new().unwrap();
+        let paths = project(
+            temp.path(),
+            &format!("extends:\n  - url: {}\n", server.url("/base.yml")),
+        );
+
+        let results = warm(&paths, &stores(temp.path(), false)).unwrap();
+        assert_eq!(results.len(), 1);
+        let err = results[0].1.as_ref().unwrap_err();
+        assert!(err.to_string().contains("404"));
+    }
+}
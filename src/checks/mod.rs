@@ -179,6 +179,20 @@ impl Check {
         }
     }
 
+    /// Returns the target and kind of every change check in this check tree.
+    ///
+    /// Used to record what a step watches so staleness can be reported
+    /// without evaluating the step's checks.
+    pub fn change_targets(&self) -> Vec<(&str, ChangeKind)> {
+        match self {
+            Check::Change { target, kind, .. } => vec![(target.as_str(), *kind)],
+            Check::All { checks, .. } | Check::Any { checks, .. } => {
+                checks.iter().flat_map(|c| c.change_targets()).collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
     /// Compute a short hash of this check's configuration for snapshot key isolation.
     ///
     /// Two checks with different configs (different targets, on_change values, etc.)
@@ -1132,4 +1146,32 @@ mod tests {
         };
         assert!(!check.has_named_checks());
     }
+
+    #[test]
+    fn change_targets_recurses_into_combinators() {
+        let check: Check = serde_yaml::from_str(
+            r#"
+type: any
+checks:
+  - type: presence
+    target: node_modules
+  - type: all
+    checks:
+      - type: change
+        target: package-lock.json
+      - type: change
+        target: "src/**/*.ts"
+        kind: glob
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            check.change_targets(),
+            vec![
+                ("package-lock.json", ChangeKind::File),
+                ("src/**/*.ts", ChangeKind::Glob),
+            ]
+        );
+    }
 }
@@ -41,13 +41,22 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub trust: bool,
 
+    /// Use only cached remote content; never touch the network (also BIVVY_OFFLINE=1)
+    #[arg(long, global = true)]
+    pub offline: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
 
 use super::commands::cache::CacheArgs;
+use super::commands::config::ConfigSubcommand;
+use super::commands::diagnostics::DiagnosticsArgs;
+use super::commands::export::ExportArgs;
 use super::commands::feedback::FeedbackArgs;
+use super::commands::hook::HookArgs;
 use super::commands::snapshot::SnapshotArgs;
+use super::commands::templates::TemplatesSubcommand;
 use super::commands::update::UpdateArgs;
 
 /// Available subcommands.
@@ -62,6 +71,12 @@ pub enum Commands {
     /// Add a template step to the configuration
     Add(AddArgs),
 
+    /// Remove a step and every reference to it
+    Remove(RemoveArgs),
+
+    /// Replace a step's template reference with the template's definition
+    Eject(EjectArgs),
+
     /// Show current setup status
     Status(StatusArgs),
 
@@ -71,6 +86,9 @@ pub enum Commands {
     /// List available templates
     Templates(TemplatesArgs),
 
+    /// Show detected technologies, conflicts and proposed templates
+    Detect(DetectArgs),
+
     /// Show last run information
     Last(LastArgs),
 
@@ -83,15 +101,30 @@ pub enum Commands {
     /// Show resolved configuration
     Config(ConfigArgs),
 
+    /// Show effective environment variables and where they come from
+    Env(EnvArgs),
+
+    /// Show which steps changes since a git ref invalidate
+    Affected(AffectedArgs),
+
+    /// Generate CI configuration from a workflow
+    Export(ExportArgs),
+
     /// Manage template cache
     Cache(CacheArgs),
 
+    /// Manage learned recovery fixes
+    Diagnostics(DiagnosticsArgs),
+
     /// Capture and manage feedback
     Feedback(FeedbackArgs),
 
     /// Manage change check snapshots
     Snapshot(SnapshotArgs),
 
+    /// Install shell prompt and git hooks that report stale steps
+    Hook(HookArgs),
+
     /// Check for and install updates
     Update(UpdateArgs),
 
@@ -138,6 +171,18 @@ pub struct RunArgs {
     #[arg(long)]
     pub save_preferences: bool,
 
+    /// Read prompt answers from a YAML file (step -> prompt key -> value)
+    #[arg(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+
+    /// Answer a prompt: <step>.<prompt_key>=<value> (repeatable)
+    #[arg(long = "set", value_name = "STEP.KEY=VALUE")]
+    pub set: Vec<String>,
+
+    /// Print an answers-file template for the workflow's prompts and exit
+    #[arg(long)]
+    pub dump_prompts: bool,
+
     /// Preview commands without executing
     #[arg(long)]
     pub dry_run: bool,
@@ -166,6 +211,14 @@ pub struct RunArgs {
     #[arg(long, conflicts_with = "diagnostic_funnel")]
     pub no_diagnostic_funnel: bool,
 
+    /// Wait for another bivvy run in this project to finish
+    #[arg(long)]
+    pub wait: bool,
+
+    /// Fail immediately if another bivvy run holds the project lock
+    #[arg(long, conflicts_with = "wait")]
+    pub no_wait: bool,
+
     /// Suppress run header (used when chaining from init)
     #[arg(skip)]
     pub suppress_header: bool,
@@ -182,12 +235,17 @@ impl Default for RunArgs {
             force_all: false,
             resume: false,
             save_preferences: false,
+            answers: None,
+            set: Vec::new(),
+            dump_prompts: false,
             dry_run: false,
             non_interactive: false,
             ci: false,
             env: None,
             diagnostic_funnel: false,
             no_diagnostic_funnel: false,
+            wait: false,
+            no_wait: false,
             fresh: false,
             suppress_header: false,
         }
@@ -209,6 +267,11 @@ pub struct InitArgs {
     #[arg(long)]
     pub from: Option<String>,
 
+    /// Import steps from an existing setup script, Makefile, justfile,
+    /// package.json or devcontainer.json
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["template", "from"])]
+    pub import: Option<PathBuf>,
+
     /// Overwrite existing configuration
     #[arg(long)]
     pub force: bool,
@@ -232,6 +295,10 @@ pub struct StatusArgs {
     /// Target environment (e.g., development, ci, staging)
     #[arg(long, value_name = "ENV")]
     pub env: Option<String>,
+
+    /// Only report steps whose watched files changed since they last ran
+    #[arg(long)]
+    pub stale: bool,
 }
 
 /// Arguments for the `list` command.
@@ -369,6 +436,56 @@ pub struct ConfigArgs {
     /// Show fully merged config
     #[arg(long)]
     pub merged: bool,
+
+    /// Change a value instead of showing the config
+    #[command(subcommand)]
+    pub action: Option<ConfigSubcommand>,
+}
+
+/// Arguments for the `env` command.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct EnvArgs {
+    /// Include the env a step adds (template, step and environment overrides)
+    #[arg(long)]
+    pub step: Option<String>,
+
+    /// Workflow whose env to include (defaults to the environment's
+    /// default workflow, then `default`)
+    #[arg(short, long)]
+    pub workflow: Option<String>,
+
+    /// Target environment (e.g., development, ci, staging)
+    #[arg(long, value_name = "ENV")]
+    pub env: Option<String>,
+
+    /// Print unmasked values for `eval` or direnv: bash, fish, dotenv
+    #[arg(long, value_name = "FORMAT", value_parser = ["bash", "fish", "dotenv"])]
+    pub export: Option<String>,
+}
+
+/// Arguments for the `affected` command.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct AffectedArgs {
+    /// Git ref to compare against (e.g., origin/main)
+    #[arg(long, value_name = "REF")]
+    pub since: String,
+
+    /// Run the affected steps
+    #[arg(long, conflicts_with = "json")]
+    pub run: bool,
+
+    /// Workflow to select steps from (defaults to the environment's
+    /// default workflow, then `default`)
+    #[arg(short, long)]
+    pub workflow: Option<String>,
+
+    /// Target environment (e.g., development, ci, staging)
+    #[arg(long, value_name = "ENV")]
+    pub env: Option<String>,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Arguments for the `schema` command.
@@ -393,6 +510,18 @@ pub struct TemplatesArgs {
     /// Filter by category (e.g., ruby, node, python)
     #[arg(long)]
     pub category: Option<String>,
+
+    /// Check ejected steps instead of listing templates
+    #[command(subcommand)]
+    pub action: Option<TemplatesSubcommand>,
+}
+
+/// Arguments for the `detect` command.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct DetectArgs {
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
 }
 
 /// Arguments for the `add` command.
@@ -418,6 +547,24 @@ pub struct AddArgs {
     pub no_workflow: bool,
 }
 
+/// Arguments for the `eject` command.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct EjectArgs {
+    /// Step to eject
+    pub step: String,
+}
+
+/// Arguments for the `remove` command.
+#[derive(Debug, Clone, Default, clap::Args)]
+pub struct RemoveArgs {
+    /// Step to remove
+    pub step: String,
+
+    /// Keep the step's saved run state, satisfaction records and snapshots
+    #[arg(long)]
+    pub keep_state: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -450,6 +597,30 @@ mod tests {
         assert!(cli.args.force.is_empty());
     }
 
+    #[test]
+    fn run_args_set_is_repeatable() {
+        let cli = TestRun::parse_from([
+            "test",
+            "--set",
+            "deploy.region=eu",
+            "--set",
+            "deploy.migrate=true",
+        ]);
+        assert_eq!(
+            cli.args.set,
+            vec!["deploy.region=eu", "deploy.migrate=true"]
+        );
+        assert!(cli.args.answers.is_none());
+        assert!(!cli.args.dump_prompts);
+    }
+
+    #[test]
+    fn run_args_answers_parses_path() {
+        let cli = TestRun::parse_from(["test", "--answers", "answers.yml", "--dump-prompts"]);
+        assert_eq!(cli.args.answers, Some(PathBuf::from("answers.yml")));
+        assert!(cli.args.dump_prompts);
+    }
+
     #[test]
     fn run_args_force_and_force_all_can_coexist() {
         let cli = TestRun::parse_from(["test", "--force", "install", "--force-all"]);
@@ -1,14 +1,16 @@
 //! Add command implementation.
 //!
 //! The `bivvy add` command adds a template step to an existing
-//! `.bivvy/config.yml` file. It appends the step to the end of the
-//! steps section and optionally adds it to a workflow.
+//! `.bivvy/config.yml` file. It adds the step after the existing steps
+//! and optionally to a workflow, keeping the file's comments and layout.
 
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use serde_yaml::Value;
+
 use crate::cli::args::AddArgs;
-use crate::config::{load_config, load_merged_config, load_project_config};
+use crate::config::{load_config, load_merged_config, load_project_config, ConfigEditor};
 use crate::error::{BivvyError, Result};
 use crate::registry::resolver::Registry;
 use crate::registry::template::Template;
@@ -49,203 +51,54 @@ impl AddCommand {
         &self.args
     }
 
-    /// Format a step block for insertion into the config file.
+    /// Add a step referencing a template to the config document.
     ///
     /// Produces the same format as `bivvy init` — a template reference
     /// with commented-out details showing what the template provides.
-    fn format_step_block(step_name: &str, template_name: &str, template: &Template) -> String {
-        let mut block = format!("  {}:\n    template: {}\n", step_name, template_name);
+    /// A missing `steps:` section is created.
+    fn add_step(
+        editor: &mut ConfigEditor,
+        step_name: &str,
+        template_name: &str,
+        template: &Template,
+    ) -> Result<()> {
+        let template_value = Value::String(template_name.to_string());
+        editor.set(&["steps", step_name, "template"], &template_value)?;
 
         // Show command as comment
         if let Some(ref cmd) = template.step.command {
-            block.push_str(&format!("    # command: {}\n", cmd));
+            editor.append_comment(&["steps", step_name], &format!("command: {}", cmd))?;
         }
 
-        block
+        Ok(())
     }
 
-    /// Insert a step into the config file content (text-level editing).
+    /// Add a step name to a workflow's steps list, after `after` when it's
+    /// in the list and at the end otherwise.
     ///
-    /// Appends the step block after the last step entry in the `steps:` section.
-    fn insert_step_into_config(content: &str, step_block: &str) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-        let mut result_lines: Vec<String> = Vec::new();
-        let mut found_steps = false;
-        let mut insert_index = None;
-        let mut in_steps_section = false;
-
-        for line in lines.iter() {
-            let trimmed = line.trim();
-
-            // Detect the `steps:` top-level key
-            if trimmed == "steps:" && !line.starts_with(' ') && !line.starts_with('#') {
-                found_steps = true;
-                in_steps_section = true;
-                result_lines.push(line.to_string());
-                continue;
-            }
-
-            if in_steps_section {
-                // We're inside the steps section. A new top-level key
-                // (non-indented, non-empty, non-comment) ends it.
-                let is_top_level_key = !trimmed.is_empty()
-                    && !trimmed.starts_with('#')
-                    && !line.starts_with(' ')
-                    && !line.starts_with('\t');
-
-                if is_top_level_key {
-                    // Insert right before this line
-                    insert_index = Some(result_lines.len());
-                    in_steps_section = false;
-                }
-            }
-
-            result_lines.push(line.to_string());
-        }
-
-        if !found_steps {
-            return Err(BivvyError::ConfigValidationError {
-                message: "No 'steps:' section found in config file".to_string(),
-            });
-        }
-
-        // If we never left the steps section, append at the end
-        let idx = insert_index.unwrap_or(result_lines.len());
-
-        // Ensure there's a blank line before the new step block
-        // when inserting at the boundary
-        let step_block_with_newline = if idx < result_lines.len() {
-            // Inserting before a top-level key — add trailing newline
-            format!("{}\n", step_block)
-        } else {
-            // Appending at end
-            format!("\n{}", step_block)
-        };
-
-        result_lines.insert(idx, step_block_with_newline.trim_end().to_string());
-
-        let mut output = result_lines.join("\n");
-        // Ensure file ends with newline
-        if !output.ends_with('\n') {
-            output.push('\n');
-        }
-        Ok(output)
-    }
-
-    /// Add a step name to a workflow's steps list in the config file content.
+    /// Returns `false` if the workflow doesn't exist.
     fn add_to_workflow(
-        content: &str,
+        editor: &mut ConfigEditor,
         workflow_name: &str,
         step_name: &str,
         after: Option<&str>,
-    ) -> Result<String> {
-        let lines: Vec<&str> = content.lines().collect();
-
-        // Find the target steps line
-        let target = Self::find_workflow_steps_line(&lines, workflow_name);
-
-        if let Some((line_idx, prefix, current_steps)) = target {
-            let new_steps = Self::insert_step_name(&current_steps, step_name, after);
-            let mut result_lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
-            result_lines[line_idx] = format!("{}steps: [{}]", prefix, new_steps);
-            let mut output = result_lines.join("\n");
-            if !output.ends_with('\n') {
-                output.push('\n');
-            }
-            Ok(output)
-        } else {
-            // Workflow not found — return content unchanged
-            Ok(content.to_string())
-        }
-    }
-
-    /// Find the `steps: [...]` line for a given workflow name.
-    ///
-    /// Returns `(line_index, indentation_prefix, steps_content)` if found.
-    fn find_workflow_steps_line(
-        lines: &[&str],
-        workflow_name: &str,
-    ) -> Option<(usize, String, String)> {
-        // Find `workflows:` top-level key
-        let workflows_idx = lines.iter().enumerate().find_map(|(i, line)| {
-            let trimmed = line.trim();
-            if trimmed == "workflows:" && !line.starts_with(' ') && !line.starts_with('#') {
-                Some(i)
-            } else {
-                None
-            }
-        })?;
-
-        // Find the specific workflow by name
-        let workflow_pattern = format!("{}:", workflow_name);
-        let mut workflow_idx = None;
-        for (i, line) in lines.iter().enumerate().skip(workflows_idx + 1) {
-            let trimmed = line.trim();
-            if trimmed == workflow_pattern || trimmed.starts_with(&format!("{}: ", workflow_name)) {
-                workflow_idx = Some(i);
-                break;
-            }
-            // Stop at next top-level key
-            if !trimmed.is_empty()
-                && !trimmed.starts_with('#')
-                && !line.starts_with(' ')
-                && !line.starts_with('\t')
-            {
-                break;
-            }
+    ) -> Result<bool> {
+        if !editor.contains(&["workflows", workflow_name]) {
+            return Ok(false);
         }
 
-        let workflow_idx = workflow_idx?;
-        let workflow_indent = lines[workflow_idx].len() - lines[workflow_idx].trim_start().len();
-
-        // Find the `steps:` line under this workflow
-        for (i, line) in lines.iter().enumerate().skip(workflow_idx + 1) {
-            let trimmed = line.trim();
-            if trimmed.is_empty() || trimmed.starts_with('#') {
-                continue;
-            }
-
-            let current_indent = line.len() - trimmed.len();
-
-            // Stop if we hit a sibling or parent-level key
-            if current_indent <= workflow_indent {
-                break;
-            }
-
-            if trimmed.starts_with("steps:") {
-                let prefix = line[..line.len() - trimmed.len()].to_string();
-                if let (Some(start), Some(end)) = (trimmed.find('['), trimmed.find(']')) {
-                    let list_content = trimmed[start + 1..end].to_string();
-                    return Some((i, prefix, list_content));
-                }
-            }
-        }
-
-        None
-    }
-
-    /// Insert a step name into a comma-separated list string.
-    fn insert_step_name(current: &str, new_step: &str, after: Option<&str>) -> String {
-        let steps: Vec<&str> = current
-            .split(',')
-            .map(|s| s.trim())
-            .filter(|s| !s.is_empty())
-            .collect();
-
-        let mut new_steps: Vec<String> = steps.iter().map(|s| s.to_string()).collect();
-
-        if let Some(after_step) = after {
-            if let Some(pos) = new_steps.iter().position(|s| s == after_step) {
-                new_steps.insert(pos + 1, new_step.to_string());
-            } else {
-                // --after target not found, append
-                new_steps.push(new_step.to_string());
-            }
-        } else {
-            new_steps.push(new_step.to_string());
-        }
+        let path = ["workflows", workflow_name, "steps"];
+        let index = match (editor.get(&path), after) {
+            (Some(Value::Sequence(steps)), Some(after_step)) => steps
+                .iter()
+                .position(|s| s.as_str() == Some(after_step))
+                .map(|pos| pos + 1),
+            _ => None,
+        };
 
-        new_steps.join(", ")
+        let step_value = Value::String(step_name.to_string());
+        editor.insert_item(&path, index, &step_value)?;
+        Ok(true)
     }
 }
 
@@ -307,38 +160,32 @@ impl Command for AddCommand {
             return Ok(CommandResult::failure(1));
         }
 
-        // Read the raw config file
+        // Edit the raw config file, keeping its comments and layout
         let content = fs::read_to_string(&config_path)?;
+        let mut editor = ConfigEditor::new(content)?;
 
-        // Format the new step block
-        let step_block = Self::format_step_block(step_name, template_name, template);
-
-        // Insert the step into the config
-        let mut new_content = Self::insert_step_into_config(&content, &step_block)?;
+        Self::add_step(&mut editor, step_name, template_name, template)?;
 
         // Add to workflow unless --no-workflow
-        if !self.args.no_workflow {
-            let workflow = self.args.workflow.as_deref().unwrap_or("default");
-
-            new_content = Self::add_to_workflow(
-                &new_content,
-                workflow,
-                step_name,
-                self.args.after.as_deref(),
-            )?;
-        }
+        let workflow = self.args.workflow.as_deref().unwrap_or("default");
+        let added_to_workflow = !self.args.no_workflow
+            && Self::add_to_workflow(&mut editor, workflow, step_name, self.args.after.as_deref())?;
 
         // Write the updated config
-        fs::write(&config_path, &new_content)?;
+        fs::write(&config_path, editor.as_str())?;
 
         ui.success(&format!(
             "Added '{}' step using template '{}'",
             step_name, template_name
         ));
 
-        if !self.args.no_workflow {
-            let workflow = self.args.workflow.as_deref().unwrap_or("default");
+        if added_to_workflow {
             ui.message(&format!("  Added to '{}' workflow", workflow));
+        } else if !self.args.no_workflow {
+            ui.warning(&format!(
+                "Workflow '{}' not found; the step wasn't added to a workflow",
+                workflow
+            ));
         }
 
         ui.show_hint(&hints::after_add(step_name));
@@ -573,103 +420,134 @@ workflows:
         assert!(new_config.contains("# A custom comment"));
     }
 
-    // --- Unit tests for helper functions ---
+    #[test]
+    fn add_warns_for_missing_workflow() {
+        let config = "app_name: Test\n\nsteps:\n  hello:\n    command: echo hello\n\nworkflows:\n  default:\n    steps: [hello]\n";
+        let temp = setup_project(config);
+        let args = AddArgs {
+            template: "bundle-install".to_string(),
+            workflow: Some("ci".to_string()),
+            ..Default::default()
+        };
+        let cmd = AddCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui.has_warning("Workflow 'ci' not found"));
+        let new_config = fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap();
+        assert!(new_config.contains("steps: [hello]"));
+    }
 
     #[test]
-    fn format_step_block_basic() {
-        let loader = BuiltinLoader::new().unwrap();
-        let template = loader.get("bundle-install").unwrap();
+    fn add_keeps_block_style_workflow_list() {
+        let config = "app_name: Test\n\nsteps:\n  install:\n    command: npm install\n  build:\n    command: npm build\n\nworkflows:\n  default:\n    steps:\n      - install # deps first\n      - build\n";
+        let temp = setup_project(config);
+        let args = AddArgs {
+            template: "bundle-install".to_string(),
+            after: Some("install".to_string()),
+            ..Default::default()
+        };
+        let cmd = AddCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
 
-        let block = AddCommand::format_step_block("bundle-install", "bundle-install", template);
+        cmd.execute(&mut ui).unwrap();
 
-        assert!(block.contains("  bundle-install:\n    template: bundle-install\n"));
-        assert!(block.contains("# command: bundle install"));
+        let new_config = fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap();
+        assert!(new_config.contains(
+            "    steps:\n      - install # deps first\n      - bundle-install\n      - build\n"
+        ));
     }
 
+    // --- Unit tests for helper functions ---
+
     #[test]
-    fn format_step_block_custom_name() {
+    fn add_step_basic() {
         let loader = BuiltinLoader::new().unwrap();
         let template = loader.get("bundle-install").unwrap();
+        let mut editor = ConfigEditor::new("steps:\n  hello:\n    command: echo hello\n").unwrap();
 
-        let block = AddCommand::format_step_block("ruby_deps", "bundle-install", template);
+        AddCommand::add_step(&mut editor, "bundle-install", "bundle-install", template).unwrap();
 
-        assert!(block.starts_with("  ruby_deps:\n    template: bundle-install\n"));
+        assert_eq!(
+            editor.as_str(),
+            "steps:\n  hello:\n    command: echo hello\n  bundle-install:\n    template: bundle-install\n    # command: bundle install\n"
+        );
     }
 
     #[test]
-    fn insert_step_into_config_appends() {
+    fn add_step_places_step_before_next_section() {
+        let loader = BuiltinLoader::new().unwrap();
+        let template = loader.get("bundle-install").unwrap();
         let config = "app_name: Test\n\nsteps:\n  hello:\n    command: echo hello\n\nworkflows:\n  default:\n    steps: [hello]\n";
-        let step_block = "  world:\n    template: world\n";
+        let mut editor = ConfigEditor::new(config).unwrap();
 
-        let result = AddCommand::insert_step_into_config(config, step_block).unwrap();
+        AddCommand::add_step(&mut editor, "ruby_deps", "bundle-install", template).unwrap();
 
-        // Step should appear before workflows
-        let steps_pos = result.find("  world:").unwrap();
-        let workflows_pos = result.find("workflows:").unwrap();
+        let steps_pos = editor.as_str().find("  ruby_deps:").unwrap();
+        let workflows_pos = editor.as_str().find("workflows:").unwrap();
         assert!(steps_pos < workflows_pos);
     }
 
     #[test]
-    fn insert_step_into_config_no_steps_section() {
-        let config = "app_name: Test\n";
-        let step_block = "  hello:\n    command: echo hello\n";
+    fn add_step_creates_steps_section() {
+        let loader = BuiltinLoader::new().unwrap();
+        let template = loader.get("bundle-install").unwrap();
+        let mut editor = ConfigEditor::new("app_name: Test\n").unwrap();
 
-        let result = AddCommand::insert_step_into_config(config, step_block);
+        AddCommand::add_step(&mut editor, "bundle-install", "bundle-install", template).unwrap();
 
-        assert!(result.is_err());
+        assert!(editor.as_str().starts_with(
+            "app_name: Test\nsteps:\n  bundle-install:\n    template: bundle-install\n"
+        ));
     }
 
     #[test]
-    fn insert_step_at_end_when_no_following_section() {
-        let config = "app_name: Test\n\nsteps:\n  hello:\n    command: echo hello\n";
-        let step_block = "  world:\n    template: world\n";
+    fn add_to_workflow_updates_default() {
+        let mut editor = ConfigEditor::new("workflows:\n  default:\n    steps: [hello]\n").unwrap();
 
-        let result = AddCommand::insert_step_into_config(config, step_block).unwrap();
+        assert!(AddCommand::add_to_workflow(&mut editor, "default", "world", None).unwrap());
 
-        assert!(result.contains("  world:\n    template: world\n"));
+        assert!(editor.as_str().contains("steps: [hello, world]"));
     }
 
     #[test]
-    fn insert_step_name_appends() {
-        let result = AddCommand::insert_step_name("hello, world", "new", None);
-        assert_eq!(result, "hello, world, new");
-    }
+    fn add_to_workflow_with_after() {
+        let mut editor =
+            ConfigEditor::new("workflows:\n  default:\n    steps: [hello, goodbye]\n").unwrap();
 
-    #[test]
-    fn insert_step_name_after() {
-        let result = AddCommand::insert_step_name("hello, world", "new", Some("hello"));
-        assert_eq!(result, "hello, new, world");
-    }
+        AddCommand::add_to_workflow(&mut editor, "default", "world", Some("hello")).unwrap();
 
-    #[test]
-    fn insert_step_name_after_nonexistent_appends() {
-        let result = AddCommand::insert_step_name("hello, world", "new", Some("missing"));
-        assert_eq!(result, "hello, world, new");
+        assert!(editor.as_str().contains("steps: [hello, world, goodbye]"));
     }
 
     #[test]
-    fn insert_step_name_empty_list() {
-        let result = AddCommand::insert_step_name("", "new", None);
-        assert_eq!(result, "new");
+    fn add_to_workflow_after_nonexistent_appends() {
+        let mut editor =
+            ConfigEditor::new("workflows:\n  default:\n    steps: [hello, goodbye]\n").unwrap();
+
+        AddCommand::add_to_workflow(&mut editor, "default", "world", Some("missing")).unwrap();
+
+        assert!(editor.as_str().contains("steps: [hello, goodbye, world]"));
     }
 
     #[test]
-    fn add_to_workflow_updates_default() {
-        let config = "workflows:\n  default:\n    steps: [hello]\n";
+    fn add_to_workflow_creates_steps_list() {
+        let mut editor =
+            ConfigEditor::new("workflows:\n  default:\n    description: All\n").unwrap();
 
-        let result = AddCommand::add_to_workflow(config, "default", "world", None).unwrap();
+        AddCommand::add_to_workflow(&mut editor, "default", "world", None).unwrap();
 
-        assert!(result.contains("steps: [hello, world]"));
+        assert!(editor.as_str().contains("    steps: [world]\n"));
     }
 
     #[test]
-    fn add_to_workflow_with_after() {
-        let config = "workflows:\n  default:\n    steps: [hello, goodbye]\n";
-
-        let result =
-            AddCommand::add_to_workflow(config, "default", "world", Some("hello")).unwrap();
+    fn add_to_missing_workflow_is_false() {
+        let mut editor = ConfigEditor::new("workflows:\n  default:\n    steps: [a]\n").unwrap();
 
-        assert!(result.contains("steps: [hello, world, goodbye]"));
+        assert!(!AddCommand::add_to_workflow(&mut editor, "ci", "world", None).unwrap());
+        assert_eq!(editor.as_str(), "workflows:\n  default:\n    steps: [a]\n");
     }
 
     /// Adds a step from a template that lives in a remote HTTP source.
@@ -0,0 +1,430 @@
+//! Affected command implementation.
+//!
+//! The `bivvy affected --since <ref>` command lists the steps invalidated
+//! by files changed since a git ref, plus the steps that depend on them,
+//! and with `--run` runs just those steps.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde_json::json;
+
+use crate::checks::ChangeKind;
+use crate::cli::args::{AffectedArgs, RunArgs};
+use crate::config::{load_config, BivvyConfig, TrustPolicy};
+use crate::environment::resolver::ResolvedEnvironment;
+use crate::error::{BivvyError, Result};
+use crate::registry::Registry;
+use crate::runner::affected::{affected_steps, changed_files, Affected};
+use crate::runner::DependencyGraph;
+use crate::steps::ResolvedStep;
+use crate::ui::theme::BivvyTheme;
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+use super::run::{resolve_config_steps, step_registry, watched_targets, RunCommand};
+
+/// The affected command implementation.
+pub struct AffectedCommand {
+    project_root: PathBuf,
+    args: AffectedArgs,
+    config_override: Option<PathBuf>,
+    trust_policy: TrustPolicy,
+}
+
+impl AffectedCommand {
+    /// Create a new affected command.
+    pub fn new(project_root: &Path, args: AffectedArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+            config_override: None,
+            trust_policy: TrustPolicy::Prompt,
+        }
+    }
+
+    /// Set an override config path.
+    pub fn with_config_override(mut self, config_override: Option<PathBuf>) -> Self {
+        self.config_override = config_override;
+        self
+    }
+
+    /// Set the trust policy used when running the affected steps.
+    pub fn with_trust_policy(mut self, policy: TrustPolicy) -> Self {
+        self.trust_policy = policy;
+        self
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &AffectedArgs {
+        &self.args
+    }
+
+    /// The workflow to select from: `--workflow`, then the environment's
+    /// default workflow, then `default` (matching `bivvy run`).
+    fn workflow_name(&self, config: &BivvyConfig, env_name: &str) -> String {
+        self.args.workflow.clone().unwrap_or_else(|| {
+            config
+                .settings
+                .environment_profiles
+                .environments
+                .get(env_name)
+                .and_then(|env_config| env_config.default_workflow.clone())
+                .unwrap_or_else(|| "default".to_string())
+        })
+    }
+
+    fn run_steps(
+        &self,
+        workflow_name: &str,
+        steps: Vec<String>,
+        ui: &mut dyn UserInterface,
+    ) -> Result<CommandResult> {
+        let args = RunArgs {
+            workflow: workflow_name.to_string(),
+            only: steps.clone(),
+            force: steps,
+            env: self.args.env.clone(),
+            ..Default::default()
+        };
+        RunCommand::new(&self.project_root, args)
+            .with_trust_policy(self.trust_policy)
+            .with_config_override(self.config_override.clone())
+            .execute(ui)
+    }
+}
+
+/// Paths each step watches: file and glob change-check targets
+/// (including converted `watches`), plus the files its template's
+/// detection rules and detectors read.
+fn step_watches(
+    config: &BivvyConfig,
+    registry: &Registry,
+    steps: &std::collections::HashMap<String, ResolvedStep>,
+) -> Result<BTreeMap<String, Vec<String>>> {
+    let detectors = registry.detectors();
+    let mut watches = BTreeMap::new();
+    for (name, targets) in watched_targets(steps) {
+        let mut paths: Vec<String> = targets
+            .into_iter()
+            .filter(|(_, kind)| *kind != ChangeKind::Command)
+            .map(|(target, _)| target)
+            .collect();
+
+        if let Some(template_name) = config.steps.get(&name).and_then(|s| s.template.as_ref()) {
+            let (template, _source) = registry.resolve(template_name)?;
+            paths.extend(template.detects.iter().filter_map(|d| d.file.clone()));
+            for reference in &template.detectors {
+                paths.extend(detectors.watched_paths(reference));
+            }
+        }
+
+        paths.sort();
+        paths.dedup();
+        watches.insert(name, paths);
+    }
+    Ok(watches)
+}
+
+impl Command for AffectedCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config = match load_config(&self.project_root, self.config_override.as_deref()) {
+            Ok(c) => c,
+            Err(BivvyError::ConfigNotFound { .. }) => {
+                ui.error("No configuration found. Run 'bivvy init' first.");
+                return Ok(CommandResult::failure(2));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let resolved_env =
+            ResolvedEnvironment::resolve_from_config(self.args.env.as_deref(), &config.settings);
+        let workflow_name = self.workflow_name(&config, &resolved_env.name);
+        let Some(workflow) = config.workflows.get(&workflow_name) else {
+            ui.error(&format!("Unknown workflow: {}", workflow_name));
+            return Ok(CommandResult::failure(1));
+        };
+
+        let changed = match changed_files(&self.project_root, &self.args.since) {
+            Ok(files) => files,
+            Err(e) => {
+                ui.error(&e.to_string());
+                return Ok(CommandResult::failure(1));
+            }
+        };
+
+        let registry = step_registry(&self.project_root, &config)?;
+        let steps = resolve_config_steps(&registry, &config, Some(&resolved_env.name))?;
+
+        let mut builder = DependencyGraph::builder();
+        for name in &workflow.steps {
+            if let Some(step) = steps.get(name) {
+                builder = builder.add_step(name.clone(), step.depends_on.clone());
+            }
+        }
+        let graph = builder.build()?;
+
+        let watches = step_watches(&config, &registry, &steps)?;
+        let affected = affected_steps(&watches, &changed, &graph);
+
+        if self.args.json {
+            ui.message(
+                &serde_json::to_string_pretty(&affected_json(
+                    &self.args.since,
+                    &changed,
+                    &affected,
+                ))
+                .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?,
+            );
+            return Ok(CommandResult::success());
+        }
+
+        show_affected(ui, &self.args.since, &changed, &affected);
+
+        if self.args.run && !affected.is_empty() {
+            ui.message("");
+            return self.run_steps(&workflow_name, affected.step_names(), ui);
+        }
+        Ok(CommandResult::success())
+    }
+}
+
+fn affected_json(since: &str, changed: &[String], affected: &Affected) -> serde_json::Value {
+    json!({
+        "since": since,
+        "changed_files": changed,
+        "affected": affected
+            .direct
+            .iter()
+            .map(|s| json!({ "name": s.name, "files": s.files }))
+            .collect::<Vec<_>>(),
+        "dependents": affected.dependents,
+    })
+}
+
+fn show_affected(ui: &mut dyn UserInterface, since: &str, changed: &[String], affected: &Affected) {
+    let theme = BivvyTheme::new();
+    let files = if changed.len() == 1 { "file" } else { "files" };
+
+    if affected.is_empty() {
+        ui.message(&format!(
+            "No steps affected by {} changed {} since {}",
+            changed.len(),
+            files,
+            since
+        ));
+        return;
+    }
+
+    let count = affected.direct.len() + affected.dependents.len();
+    ui.message(&format!(
+        "{} {} affected by {} changed {} since {}",
+        count,
+        if count == 1 { "step" } else { "steps" },
+        changed.len(),
+        files,
+        since
+    ));
+    ui.message("");
+
+    let width = affected
+        .step_names()
+        .iter()
+        .map(|name| name.len())
+        .max()
+        .unwrap_or(0);
+    for step in &affected.direct {
+        ui.message(&format!(
+            "  {}{}  {}",
+            theme.highlight.apply_to(&step.name),
+            " ".repeat(width - step.name.len()),
+            theme.dim.apply_to(step.files.join(", ")),
+        ));
+    }
+    for name in &affected.dependents {
+        ui.message(&format!(
+            "  {}{}  {}",
+            theme.highlight.apply_to(name),
+            " ".repeat(width - name.len()),
+            theme.dim.apply_to("depends on an affected step"),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use std::fs;
+    use std::process::Command as Process;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"
+steps:
+  deps:
+    command: echo deps
+    check:
+      type: change
+      target: package-lock.json
+  db:
+    command: echo db
+    depends_on: [deps]
+    checks:
+      - type: change
+        target: "db/migrate/*.sql"
+        kind: glob
+  seed:
+    command: echo seed
+    depends_on: [db]
+  docs:
+    command: echo docs
+workflows:
+  default:
+    steps: [deps, db, seed, docs]
+"#;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Process::new("git")
+            .args([
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    fn setup_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        fs::create_dir_all(dir.join(".bivvy")).unwrap();
+        fs::create_dir_all(dir.join("db/migrate")).unwrap();
+        fs::write(dir.join(".bivvy/config.yml"), CONFIG).unwrap();
+        fs::write(dir.join("package-lock.json"), "{}").unwrap();
+        fs::write(dir.join("db/migrate/001.sql"), "create").unwrap();
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "init"]);
+        temp
+    }
+
+    fn affected_args(since: &str) -> AffectedArgs {
+        AffectedArgs {
+            since: since.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lists_affected_steps_and_dependents() {
+        let temp = setup_repo();
+        fs::write(temp.path().join("db/migrate/002.sql"), "alter").unwrap();
+        let mut ui = MockUI::new();
+
+        let result = AffectedCommand::new(temp.path(), affected_args("main"))
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(result.success);
+        assert!(ui.has_message("2 steps affected by 1 changed file since main"));
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m.contains("db") && m.contains("db/migrate/002.sql")));
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m.contains("seed") && m.contains("depends on an affected step")));
+        assert!(!ui.has_message("docs"));
+    }
+
+    #[test]
+    fn reports_nothing_affected() {
+        let temp = setup_repo();
+        fs::write(temp.path().join("README.md"), "hi").unwrap();
+        let mut ui = MockUI::new();
+
+        AffectedCommand::new(temp.path(), affected_args("main"))
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(ui.has_message("No steps affected by 1 changed file since main"));
+    }
+
+    #[test]
+    fn json_output() {
+        let temp = setup_repo();
+        fs::write(temp.path().join("package-lock.json"), "{\"v\":2}").unwrap();
+        let args = AffectedArgs {
+            json: true,
+            ..affected_args("main")
+        };
+        let mut ui = MockUI::new();
+
+        AffectedCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&ui.messages()[0]).unwrap();
+        assert_eq!(value["since"], "main");
+        assert_eq!(value["changed_files"], json!(["package-lock.json"]));
+        assert_eq!(
+            value["affected"],
+            json!([{ "name": "deps", "files": ["package-lock.json"] }])
+        );
+        assert_eq!(value["dependents"], json!(["db", "seed"]));
+    }
+
+    #[test]
+    fn unknown_ref_fails() {
+        let temp = setup_repo();
+        let mut ui = MockUI::new();
+
+        let result = AffectedCommand::new(temp.path(), affected_args("no-such-ref"))
+            .execute(&mut ui)
+            .unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("no-such-ref"));
+    }
+
+    #[test]
+    fn unknown_workflow_fails() {
+        let temp = setup_repo();
+        let args = AffectedArgs {
+            workflow: Some("nope".to_string()),
+            ..affected_args("main")
+        };
+        let mut ui = MockUI::new();
+
+        let result = AffectedCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Unknown workflow: nope"));
+    }
+
+    #[test]
+    fn no_config_fails() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = AffectedCommand::new(temp.path(), affected_args("main"))
+            .execute(&mut ui)
+            .unwrap();
+
+        assert_eq!(result.exit_code, 2);
+    }
+}
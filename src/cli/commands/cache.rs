@@ -1,11 +1,18 @@
 //! Cache command implementation.
 //!
-//! Provides `bivvy cache list`, `bivvy cache clear`, etc.
+//! Provides `bivvy cache list`, `bivvy cache clear`, `bivvy cache warm`,
+//! `bivvy cache artifacts`, etc.
 
 use anyhow::Result;
 use clap::{Args, Subcommand};
-
-use crate::cache::{default_cache_dir, format_duration, CacheStore, CacheValidator};
+use std::path::{Path, PathBuf};
+
+use crate::cache::offline::{self, is_offline, RemoteStores};
+use crate::cache::{
+    default_artifact_dir, default_cache_dir, format_duration, ArtifactStore, CacheStore,
+    CacheValidator,
+};
+use crate::config::{load_merged_config, ConfigPaths, ExecutionSettings};
 use crate::ui::{OutputWriter, Prompt, PromptResult, PromptType, UserInterface};
 
 use super::dispatcher::{Command, CommandResult};
@@ -40,17 +47,66 @@ pub enum CacheSubcommand {
     },
     /// Show cache statistics.
     Stats,
+    /// Prefetch every remote source the project's config references
+    /// (`extends` and `template_sources`) for use with `--offline`.
+    Warm,
+    /// Manage cached step artifacts (`cache:` on steps).
+    Artifacts {
+        #[command(subcommand)]
+        command: ArtifactsSubcommand,
+    },
+}
+
+/// Artifact cache subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ArtifactsSubcommand {
+    /// List cached artifacts, most recently used first.
+    List {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Evict artifacts beyond the size limit (`settings.artifact_cache_mb`).
+    Prune {
+        /// Remove every artifact instead.
+        #[arg(long)]
+        all: bool,
+        /// Don't prompt for confirmation.
+        #[arg(short, long)]
+        force: bool,
+    },
 }
 
 /// The cache command implementation.
 pub struct CacheCommand {
     args: CacheArgs,
+    project_root: Option<PathBuf>,
 }
 
 impl CacheCommand {
     /// Create a new cache command.
     pub fn new(args: CacheArgs) -> Self {
-        Self { args }
+        Self {
+            args,
+            project_root: None,
+        }
+    }
+
+    /// Read `settings.artifact_cache_mb` from this project's config.
+    pub fn with_project_root(mut self, project_root: &Path) -> Self {
+        self.project_root = Some(project_root.to_path_buf());
+        self
+    }
+
+    /// Open the artifact store with the configured size limit.
+    fn artifact_store(&self) -> ArtifactStore {
+        let max_mb = self
+            .project_root
+            .as_deref()
+            .and_then(|root| load_merged_config(root).ok())
+            .map(|config| config.settings.execution.artifact_cache_mb)
+            .unwrap_or_else(|| ExecutionSettings::default().artifact_cache_mb);
+        ArtifactStore::new(default_artifact_dir(), max_mb)
     }
 }
 
@@ -63,6 +119,19 @@ impl Command for CacheCommand {
             CacheSubcommand::List { verbose, json } => list_cache(&store, *verbose, *json, ui)?,
             CacheSubcommand::Clear { expired, force } => clear_cache(&store, *expired, *force, ui)?,
             CacheSubcommand::Stats => show_stats(&store, ui)?,
+            CacheSubcommand::Warm => {
+                let root = self.project_root.as_deref().unwrap_or(Path::new("."));
+                warm_cache(&ConfigPaths::discover(root), &RemoteStores::default(), ui)?
+            }
+            CacheSubcommand::Artifacts { command } => {
+                let artifacts = self.artifact_store();
+                match command {
+                    ArtifactsSubcommand::List { json } => list_artifacts(&artifacts, *json, ui)?,
+                    ArtifactsSubcommand::Prune { all, force } => {
+                        prune_artifacts(&artifacts, *all, *force, ui)?
+                    }
+                }
+            }
         };
 
         Ok(if exit_code == 0 {
@@ -190,6 +259,139 @@ fn show_stats(store: &CacheStore, ui: &mut dyn OutputWriter) -> Result<i32> {
     Ok(0)
 }
 
+/// Only requires `OutputWriter` — reports progress but does not prompt.
+fn warm_cache(
+    paths: &ConfigPaths,
+    stores: &RemoteStores,
+    ui: &mut dyn OutputWriter,
+) -> Result<i32> {
+    if is_offline() {
+        ui.error("Cannot warm the cache in offline mode");
+        return Ok(1);
+    }
+    if !paths.has_project_config() {
+        ui.error("No configuration found. Run 'bivvy init' first.");
+        return Ok(1);
+    }
+
+    let results = offline::warm(paths, stores)?;
+    if results.is_empty() {
+        ui.message("No remote sources to fetch");
+        return Ok(0);
+    }
+
+    let mut failed = 0;
+    for (resource, outcome) in &results {
+        match outcome {
+            Ok(()) => ui.success(&format!("Cached {}", resource)),
+            Err(e) => {
+                failed += 1;
+                ui.error(&format!("Failed to fetch {}: {:#}", resource, e));
+            }
+        }
+    }
+
+    if failed > 0 {
+        ui.warning(&format!(
+            "Fetched {} of {} remote sources",
+            results.len() - failed,
+            results.len()
+        ));
+        return Ok(1);
+    }
+    ui.message(&format!(
+        "Fetched {} remote sources; `--offline` runs can use them",
+        results.len()
+    ));
+    Ok(0)
+}
+
+/// Format a byte count as MB with one decimal.
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn list_artifacts(store: &ArtifactStore, json: bool, ui: &mut dyn OutputWriter) -> Result<i32> {
+    let entries = store.list()?;
+
+    if json {
+        ui.message(&serde_json::to_string_pretty(&entries)?);
+        return Ok(0);
+    }
+
+    if entries.is_empty() {
+        ui.message("No cached artifacts");
+        return Ok(0);
+    }
+
+    let total: u64 = entries.iter().map(|e| e.size_bytes).sum();
+    ui.message(&format!(
+        "{} cached artifacts ({} of {}):\n",
+        entries.len(),
+        format_mb(total),
+        format_mb(store.max_bytes())
+    ));
+    for entry in entries {
+        ui.message(&format!(
+            "  {} {}/{} [{}] {} — last used {}",
+            &entry.key[..entry.key.len().min(12)],
+            entry.project,
+            entry.step,
+            entry.paths.join(", "),
+            format_mb(entry.size_bytes),
+            crate::ui::progress::format_relative_time(entry.last_used_at)
+        ));
+    }
+
+    Ok(0)
+}
+
+fn prune_artifacts(
+    store: &ArtifactStore,
+    all: bool,
+    force: bool,
+    ui: &mut dyn UserInterface,
+) -> Result<i32> {
+    if !all {
+        let evicted = store.evict()?;
+        let freed: u64 = evicted.iter().map(|e| e.size_bytes).sum();
+        ui.success(&format!(
+            "Pruned {} artifacts ({} freed)",
+            evicted.len(),
+            format_mb(freed)
+        ));
+        return Ok(0);
+    }
+
+    let count = store.list()?.len();
+    if count == 0 {
+        ui.message("No cached artifacts");
+        return Ok(0);
+    }
+
+    if !force && ui.is_interactive() {
+        let prompt = Prompt {
+            key: "prune_artifacts".to_string(),
+            question: format!("Remove {} cached artifacts?", count),
+            prompt_type: PromptType::Confirm,
+            default: Some("false".to_string()),
+        };
+
+        match ui.prompt(&prompt)? {
+            PromptResult::Bool(true) => {}
+            _ => {
+                ui.message("Cancelled");
+                return Ok(0);
+            }
+        }
+    }
+
+    let removed = store.clear()?;
+    ui.success(&format!("Removed {} artifacts", removed));
+
+    Ok(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,6 +426,22 @@ mod tests {
         assert_eq!(entries.len(), 2);
     }
 
+    #[test]
+    fn warm_cache_without_config_fails() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = crate::ui::MockUI::new();
+
+        let code = warm_cache(
+            &ConfigPaths::discover(temp.path()),
+            &RemoteStores::default(),
+            &mut ui,
+        )
+        .unwrap();
+
+        assert_eq!(code, 1);
+        assert!(ui.errors().iter().any(|e| e.contains("bivvy init")));
+    }
+
     #[test]
     fn show_stats_empty() {
         let (_temp, store) = setup_test_store();
@@ -248,4 +466,22 @@ mod tests {
         assert_eq!(entries.len(), 2);
         assert_eq!(total, 8); // 5 + 3
     }
+
+    #[test]
+    fn prune_artifacts_all_clears_store() {
+        let temp = TempDir::new().unwrap();
+        let project = TempDir::new().unwrap();
+        std::fs::write(project.path().join("out"), "data").unwrap();
+        let store = ArtifactStore::new(temp.path(), 100);
+        store
+            .save("k", "app", "build", &["out".to_string()], project.path())
+            .unwrap();
+
+        let mut ui = crate::ui::MockUI::new();
+        list_artifacts(&store, false, &mut ui).unwrap();
+        assert!(ui.messages().iter().any(|m| m.contains("app/build [out]")));
+
+        prune_artifacts(&store, true, true, &mut ui).unwrap();
+        assert!(store.list().unwrap().is_empty());
+    }
 }
@@ -1,16 +1,42 @@
 //! Config command implementation.
 //!
 //! The `bivvy config` command shows resolved configuration.
+//! `bivvy config set` and `bivvy config unset` change single values in
+//! the project config, keeping its comments and layout.
 
+use std::fs;
 use std::path::{Path, PathBuf};
 
+use clap::Subcommand;
+
 use crate::cli::args::ConfigArgs;
-use crate::config::{load_config_file, load_merged_config, ConfigPaths};
+use crate::config::editor::parse_path;
+use crate::config::{
+    load_config_file, load_merged_config, parse_config, ConfigEditor, ConfigPaths,
+};
 use crate::error::{BivvyError, Result};
 use crate::ui::{OutputMode, UserInterface};
 
 use super::dispatcher::{Command, CommandResult};
 
+/// Config subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum ConfigSubcommand {
+    /// Set a value in the project config, keeping its formatting.
+    Set {
+        /// Dotted path to the value (`"` quotes a segment containing dots).
+        path: String,
+        /// New value, parsed as YAML (`true`, `3`, `[a, b]`, `{}`); `''`
+        /// sets an empty string.
+        value: String,
+    },
+    /// Remove a key or list item from the project config.
+    Unset {
+        /// Dotted path to the value (list items by index).
+        path: String,
+    },
+}
+
 /// The config command implementation.
 pub struct ConfigCommand {
     project_root: PathBuf,
@@ -43,10 +69,109 @@ impl ConfigCommand {
     pub fn args(&self) -> &ConfigArgs {
         &self.args
     }
+
+    /// Apply `set` or `unset` to the project config file.
+    fn execute_edit(
+        &self,
+        ui: &mut dyn UserInterface,
+        action: &ConfigSubcommand,
+    ) -> Result<CommandResult> {
+        let config_path = match self.config_override {
+            Some(ref override_path) => Some(override_path.clone()),
+            None => ConfigPaths::discover(&self.project_root).project,
+        };
+        let Some(config_path) = config_path.filter(|p| p.exists()) else {
+            ui.error("No configuration found. Run 'bivvy init' first.");
+            return Ok(CommandResult::failure(2));
+        };
+        let display_path = config_path
+            .strip_prefix(&self.project_root)
+            .unwrap_or(&config_path)
+            .display()
+            .to_string();
+
+        let mut editor = ConfigEditor::new(fs::read_to_string(&config_path)?)?;
+        let (message, checked) = match action {
+            ConfigSubcommand::Set { path, value } => {
+                let segments = parse_path(path);
+                let mut invalid = None;
+                for candidate in set_candidates(value) {
+                    let mut attempt = editor.clone();
+                    attempt.set(&segments, &candidate)?;
+                    match check_config(&attempt, &config_path) {
+                        Ok(()) => {
+                            editor = attempt;
+                            invalid = None;
+                            break;
+                        }
+                        // Report why the value as typed didn't fit
+                        Err(reason) => {
+                            invalid.get_or_insert(reason);
+                        }
+                    }
+                }
+                let checked = invalid.map_or(Ok(()), Err);
+                (format!("Set {} in {}", path, display_path), checked)
+            }
+            ConfigSubcommand::Unset { path } => {
+                if !editor.remove(&parse_path(path))? {
+                    ui.error(&format!("No value at '{}' in {}", path, display_path));
+                    return Ok(CommandResult::failure(1));
+                }
+                let checked = check_config(&editor, &config_path);
+                (format!("Removed {} from {}", path, display_path), checked)
+            }
+        };
+
+        // Don't write a config that bivvy can't load
+        if let Err(reason) = checked {
+            ui.error(&format!(
+                "The change would make the config invalid: {}",
+                reason
+            ));
+            return Ok(CommandResult::failure(1));
+        }
+
+        fs::write(&config_path, editor.as_str())?;
+        ui.success(&message);
+
+        Ok(CommandResult::success())
+    }
+}
+
+/// Values to try for `config set`, in order: the text read as YAML, then
+/// unless that is already a string the text itself, so `env.PORT 3000`
+/// and `env.URL "a: b"` fit fields that take strings. An empty value is
+/// the empty string.
+fn set_candidates(value: &str) -> Vec<serde_yaml::Value> {
+    let text = serde_yaml::Value::String(value.to_string());
+    if value.trim().is_empty() {
+        return vec![text];
+    }
+    // Anything that isn't valid YAML is taken as a plain string
+    match serde_yaml::from_str(value) {
+        Ok(parsed @ serde_yaml::Value::String(_)) => vec![parsed],
+        Ok(parsed) => vec![parsed, text],
+        Err(_) => vec![text],
+    }
+}
+
+/// Check that an edited config still loads, returning why it doesn't.
+fn check_config(editor: &ConfigEditor, config_path: &Path) -> std::result::Result<(), String> {
+    parse_config(editor.as_str(), config_path)
+        .map(|_| ())
+        .map_err(|e| match e {
+            BivvyError::ConfigParseError { message, .. } => message,
+            e => e.to_string(),
+        })
 }
 
 impl Command for ConfigCommand {
     fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        if let Some(ref action) = self.args.action {
+            return self.execute_edit(ui, action);
+        }
+
         let paths = ConfigPaths::discover(&self.project_root);
 
         // Load configuration: override path, merged (all sources), or project-only
@@ -110,7 +235,6 @@ impl Command for ConfigCommand {
 mod tests {
     use super::*;
     use crate::ui::{MockUI, UiState};
-    use std::fs;
     use tempfile::TempDir;
 
     fn setup_project(config: &str) -> TempDir {
@@ -232,4 +356,218 @@ workflows:
 
         assert!(result.success);
     }
+
+    fn edit(temp: &TempDir, action: ConfigSubcommand) -> (CommandResult, MockUI) {
+        let args = ConfigArgs {
+            action: Some(action),
+            ..Default::default()
+        };
+        let cmd = ConfigCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+        let result = cmd.execute(&mut ui).unwrap();
+        (result, ui)
+    }
+
+    fn read_config(temp: &TempDir) -> String {
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap()
+    }
+
+    const EDIT_CONFIG: &str = "# Project\napp_name: Test\n\nsteps:\n  # Says hello\n  hello:\n    command: echo hello\n    depends_on: [setup]\n  setup:\n    command: echo setup\n\nworkflows:\n  default:\n    steps: [setup, hello]\n";
+
+    #[test]
+    fn config_set_replaces_value_in_place() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, ui) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.command".to_string(),
+                value: "echo hi there".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(ui.has_success("Set steps.hello.command in .bivvy/config.yml"));
+        assert_eq!(
+            read_config(&temp),
+            EDIT_CONFIG.replace("echo hello", "echo hi there")
+        );
+    }
+
+    #[test]
+    fn config_set_parses_yaml_values() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.skippable".to_string(),
+                value: "false".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(read_config(&temp)
+            .contains("    depends_on: [setup]\n    skippable: false\n  setup:\n"));
+        let config = load_config_file(&temp.path().join(".bivvy/config.yml")).unwrap();
+        assert!(!config.steps["hello"].behavior.skippable);
+    }
+
+    #[test]
+    fn config_set_coerces_scalars_for_string_fields() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.env.PORT".to_string(),
+                value: "3000".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(
+            read_config(&temp).contains("    depends_on: [setup]\n    env:\n      PORT: '3000'\n")
+        );
+        let config = load_config_file(&temp.path().join(".bivvy/config.yml")).unwrap();
+        assert_eq!(config.steps["hello"].env_vars.env["PORT"], "3000");
+    }
+
+    #[test]
+    fn config_set_quotes_mapping_text_for_string_fields() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.env.FOO".to_string(),
+                value: "a: b".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(
+            read_config(&temp).contains("    depends_on: [setup]\n    env:\n      FOO: 'a: b'\n")
+        );
+        let config = load_config_file(&temp.path().join(".bivvy/config.yml")).unwrap();
+        assert_eq!(config.steps["hello"].env_vars.env["FOO"], "a: b");
+    }
+
+    #[test]
+    fn config_set_empty_value_is_empty_string() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.command".to_string(),
+                value: String::new(),
+            },
+        );
+
+        assert!(result.success);
+        assert_eq!(read_config(&temp), EDIT_CONFIG.replace("echo hello", "''"));
+    }
+
+    #[test]
+    fn config_set_inside_flow_mapping() {
+        let config = "app_name: Test\nsteps:\n  a: {command: x, title: y}\n";
+        let temp = setup_project(config);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.a.title".to_string(),
+                value: "new".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert_eq!(
+            read_config(&temp),
+            "app_name: Test\nsteps:\n  a: {command: x, title: new}\n"
+        );
+    }
+
+    #[test]
+    fn config_set_rejects_invalid_config() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, ui) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "steps.hello.depends_on".to_string(),
+                value: "{a: 1}".to_string(),
+            },
+        );
+
+        assert!(!result.success);
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("would make the config invalid"));
+        assert_eq!(read_config(&temp), EDIT_CONFIG);
+    }
+
+    #[test]
+    fn config_unset_removes_key_and_comment() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, ui) = edit(
+            &temp,
+            ConfigSubcommand::Unset {
+                path: "steps.hello".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(ui.has_success("Removed steps.hello"));
+        let config = read_config(&temp);
+        assert!(config.starts_with("# Project\napp_name: Test\n\nsteps:\n  setup:\n"));
+        assert!(!config.contains("Says hello"));
+    }
+
+    #[test]
+    fn config_unset_list_item() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Unset {
+                path: "workflows.default.steps.1".to_string(),
+            },
+        );
+
+        assert!(result.success);
+        assert!(read_config(&temp).contains("    steps: [setup]\n"));
+    }
+
+    #[test]
+    fn config_unset_missing_path_fails() {
+        let temp = setup_project(EDIT_CONFIG);
+
+        let (result, ui) = edit(
+            &temp,
+            ConfigSubcommand::Unset {
+                path: "steps.missing".to_string(),
+            },
+        );
+
+        assert!(!result.success);
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("No value at 'steps.missing'"));
+    }
+
+    #[test]
+    fn config_set_without_config_fails() {
+        let temp = TempDir::new().unwrap();
+
+        let (result, _) = edit(
+            &temp,
+            ConfigSubcommand::Set {
+                path: "app_name".to_string(),
+                value: "x".to_string(),
+            },
+        );
+
+        assert_eq!(result.exit_code, 2);
+    }
 }
@@ -0,0 +1,210 @@
+//! Detect command implementation.
+//!
+//! The `bivvy detect` command runs the same detection as `bivvy init` and
+//! prints what it found, how confident it is, the evidence, conflicts and
+//! the templates `init` would propose.
+
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::DetectArgs;
+use crate::config::load_merged_config;
+use crate::detection::{DetectedItem, DetectionReport};
+use crate::error::{BivvyError, Result};
+use crate::registry::resolver::Registry;
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+
+/// The detect command implementation.
+pub struct DetectCommand {
+    project_root: PathBuf,
+    args: DetectArgs,
+}
+
+impl DetectCommand {
+    /// Create a new detect command.
+    pub fn new(project_root: &Path, args: DetectArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+        }
+    }
+
+    /// Load the template registry, including remote sources when the
+    /// project config declares them.
+    fn template_registry(&self, ui: &mut dyn UserInterface) -> Option<Registry> {
+        let registry = match load_merged_config(&self.project_root) {
+            Ok(config) if !config.template_sources.is_empty() => {
+                Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)
+            }
+            _ => Registry::new(Some(&self.project_root)),
+        };
+        match registry {
+            Ok(registry) => Some(registry),
+            Err(e) => {
+                if !self.args.json {
+                    ui.warning(&format!("Failed to load templates: {}", e));
+                }
+                None
+            }
+        }
+    }
+}
+
+impl Command for DetectCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let registry = self.template_registry(ui);
+        let report = DetectionReport::build(&self.project_root, registry.as_ref());
+
+        if self.args.json {
+            let json =
+                serde_json::to_string_pretty(&report).map_err(|e| BivvyError::Other(e.into()))?;
+            ui.message(&json);
+            return Ok(CommandResult::success());
+        }
+
+        for warning in &report.warnings {
+            ui.warning(warning);
+        }
+
+        if report.technologies.is_empty() && report.package_managers.is_empty() {
+            ui.message("No technologies detected");
+        }
+
+        if !report.technologies.is_empty() {
+            ui.message("Detected technologies:");
+            for item in &report.technologies {
+                show_item(ui, item);
+            }
+            ui.message("");
+        }
+
+        if !report.package_managers.is_empty() {
+            ui.message("Package managers:");
+            for item in &report.package_managers {
+                show_item(ui, item);
+            }
+            ui.message("");
+        }
+
+        if !report.conflicts.is_empty() {
+            ui.message("Conflicts:");
+            for conflict in &report.conflicts {
+                ui.warning(&conflict.message);
+                ui.message(&format!("    Files:      {}", conflict.files.join(", ")));
+                ui.message(&format!("    Suggestion: {}", conflict.suggestion));
+            }
+            ui.message("");
+        }
+
+        if report.templates.is_empty() {
+            ui.message("No templates would be proposed");
+        } else {
+            ui.message("Proposed templates:");
+            for template in &report.templates {
+                ui.message(&format!(
+                    "  {} — {} ({})",
+                    template.name, template.reason, template.source
+                ));
+                if !template.detectors.is_empty() {
+                    ui.message(&format!(
+                        "    Detectors:  {}",
+                        template.detectors.join(", ")
+                    ));
+                }
+            }
+        }
+
+        Ok(CommandResult::success())
+    }
+}
+
+fn show_item(ui: &mut dyn UserInterface, item: &DetectedItem) {
+    ui.success(&format!(
+        "{} ({:.0}% confidence)",
+        item.name,
+        item.confidence * 100.0
+    ));
+    if !item.files.is_empty() {
+        ui.message(&format!("    Files:      {}", item.files.join(", ")));
+    }
+    if !item.commands.is_empty() {
+        ui.message(&format!("    Commands:   {}", item.commands.join(", ")));
+    }
+    if let Some(ref template) = item.template {
+        ui.message(&format!("    Template:   {}", template));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn node_project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+        fs::write(temp.path().join("package-lock.json"), "{}").unwrap();
+        temp
+    }
+
+    #[test]
+    fn detect_shows_evidence_conflicts_and_templates() {
+        let temp = node_project();
+        let cmd = DetectCommand::new(temp.path(), DetectArgs::default());
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui
+            .successes()
+            .iter()
+            .any(|m| m == "Node.js (100% confidence)"));
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m == "    Files:      package.json, yarn.lock"));
+        assert!(ui.has_warning("Multiple Node.js lockfiles"));
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m.starts_with("  yarn-install — ")));
+    }
+
+    #[test]
+    fn detect_json_output() {
+        let temp = node_project();
+        let cmd = DetectCommand::new(temp.path(), DetectArgs { json: true });
+        let mut ui = MockUI::new();
+
+        cmd.execute(&mut ui).unwrap();
+
+        let json: serde_json::Value = serde_json::from_str(&ui.messages()[0]).unwrap();
+        assert_eq!(json["technologies"][0]["name"], "Node.js");
+        assert_eq!(json["technologies"][0]["confidence"], 1.0);
+        assert_eq!(json["conflicts"][0]["kind"], "node_lockfiles");
+        assert!(json["templates"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|t| t["name"] == "yarn-install"));
+    }
+
+    #[test]
+    fn detect_empty_project() {
+        let temp = TempDir::new().unwrap();
+        let cmd = DetectCommand::new(temp.path(), DetectArgs::default());
+        let mut ui = MockUI::new();
+
+        cmd.execute(&mut ui).unwrap();
+
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m == "No technologies detected"));
+    }
+}
@@ -0,0 +1,295 @@
+//! Diagnostics command implementation.
+//!
+//! Provides `bivvy diagnostics learned list`, `export`, `import` and `clear`
+//! for the fixes learned from successful recoveries.
+
+use clap::{Args, Subcommand};
+use std::path::{Path, PathBuf};
+
+use crate::error::Result;
+use crate::runner::diagnostic::{default_learned_path, LearnedStore};
+use crate::ui::{Prompt, PromptResult, PromptType, UserInterface};
+
+use super::dispatcher::{Command, CommandResult};
+
+/// Arguments for the diagnostics command.
+#[derive(Debug, Clone, Args)]
+pub struct DiagnosticsArgs {
+    #[command(subcommand)]
+    pub command: DiagnosticsSubcommand,
+}
+
+/// Diagnostics subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum DiagnosticsSubcommand {
+    /// Manage fixes learned from successful recoveries.
+    Learned {
+        #[command(subcommand)]
+        command: LearnedSubcommand,
+    },
+}
+
+/// Learned fix subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum LearnedSubcommand {
+    /// List learned fixes, most successful first.
+    List {
+        /// Output as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write learned fixes to a file for sharing.
+    Export {
+        /// Destination file.
+        path: PathBuf,
+    },
+    /// Merge learned fixes from an exported file.
+    Import {
+        /// File written by `bivvy diagnostics learned export`.
+        path: PathBuf,
+    },
+    /// Forget every learned fix.
+    Clear {
+        /// Don't prompt for confirmation.
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+/// The diagnostics command implementation.
+pub struct DiagnosticsCommand {
+    args: DiagnosticsArgs,
+    store: LearnedStore,
+}
+
+impl DiagnosticsCommand {
+    /// Create a new diagnostics command using `~/.bivvy/learned-fixes.yml`.
+    pub fn new(args: DiagnosticsArgs) -> Self {
+        Self {
+            args,
+            store: LearnedStore::new(default_learned_path()),
+        }
+    }
+
+    /// Use a different learned fixes file.
+    pub fn with_learned_path(mut self, path: &Path) -> Self {
+        self.store = LearnedStore::new(path);
+        self
+    }
+}
+
+impl Command for DiagnosticsCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let exit_code = match &self.args.command {
+            DiagnosticsSubcommand::Learned { command } => match command {
+                LearnedSubcommand::List { json } => list_learned(&self.store, *json, ui)?,
+                LearnedSubcommand::Export { path } => export_learned(&self.store, path, ui)?,
+                LearnedSubcommand::Import { path } => import_learned(&self.store, path, ui)?,
+                LearnedSubcommand::Clear { force } => clear_learned(&self.store, *force, ui)?,
+            },
+        };
+
+        Ok(if exit_code == 0 {
+            CommandResult::success()
+        } else {
+            CommandResult::failure(exit_code)
+        })
+    }
+}
+
+fn list_learned(store: &LearnedStore, json: bool, ui: &mut dyn UserInterface) -> Result<i32> {
+    let fixes = store.list()?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&fixes)
+            .map_err(|e| crate::error::BivvyError::Other(e.into()))?;
+        ui.message(&output);
+        return Ok(0);
+    }
+
+    if fixes.is_empty() {
+        ui.message("No learned fixes yet");
+        return Ok(0);
+    }
+
+    ui.message(&format!("{} learned fixes:\n", fixes.len()));
+    for fix in fixes {
+        ui.message(&format!("  {}", fix.command));
+        ui.message(&format!("    Fixes:   {}", fix.signature));
+        ui.message(&format!(
+            "    Worked:  {} time{} (last {})",
+            fix.successes,
+            if fix.successes == 1 { "" } else { "s" },
+            fix.last_success.format("%Y-%m-%d")
+        ));
+        ui.message(&format!("    ID:      {}", fix.fingerprint));
+        ui.message("");
+    }
+
+    Ok(0)
+}
+
+fn export_learned(store: &LearnedStore, path: &Path, ui: &mut dyn UserInterface) -> Result<i32> {
+    let count = store.export(path)?;
+    ui.success(&format!(
+        "Exported {} learned fixes to {}",
+        count,
+        path.display()
+    ));
+    Ok(0)
+}
+
+fn import_learned(store: &LearnedStore, path: &Path, ui: &mut dyn UserInterface) -> Result<i32> {
+    if !path.exists() {
+        ui.error(&format!("File not found: {}", path.display()));
+        return Ok(1);
+    }
+    let summary = store.import(path)?;
+    ui.success(&format!(
+        "Imported learned fixes: {} added, {} updated",
+        summary.added, summary.updated
+    ));
+    Ok(0)
+}
+
+fn clear_learned(store: &LearnedStore, force: bool, ui: &mut dyn UserInterface) -> Result<i32> {
+    let count = store.list()?.len();
+    if count == 0 {
+        ui.message("No learned fixes");
+        return Ok(0);
+    }
+
+    if !force && ui.is_interactive() {
+        let prompt = Prompt {
+            key: "clear_learned".to_string(),
+            question: format!("Forget {} learned fixes?", count),
+            prompt_type: PromptType::Confirm,
+            default: Some("false".to_string()),
+        };
+
+        match ui.prompt(&prompt)? {
+            PromptResult::Bool(true) => {}
+            _ => {
+                ui.message("Cancelled");
+                return Ok(0);
+            }
+        }
+    }
+
+    let removed = store.clear()?;
+    ui.success(&format!("Forgot {} learned fixes", removed));
+    Ok(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use tempfile::TempDir;
+
+    fn write_export(path: &Path) {
+        std::fs::write(
+            path,
+            "fixes:\n\
+             - fingerprint: 0123456789abcdef\n  \
+               signature: 'bundle: An error occurred while installing pg (#.#.#)'\n  \
+               command: brew install libpq\n  \
+               source: shell\n  \
+               successes: 2\n  \
+               last_success: 2026-01-05T10:00:00Z\n",
+        )
+        .unwrap();
+    }
+
+    fn command(temp: &TempDir, sub: LearnedSubcommand) -> DiagnosticsCommand {
+        DiagnosticsCommand::new(DiagnosticsArgs {
+            command: DiagnosticsSubcommand::Learned { command: sub },
+        })
+        .with_learned_path(&temp.path().join("learned.yml"))
+    }
+
+    #[test]
+    fn list_empty() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+        command(&temp, LearnedSubcommand::List { json: false })
+            .execute(&mut ui)
+            .unwrap();
+        assert!(ui.messages().iter().any(|m| m == "No learned fixes yet"));
+    }
+
+    #[test]
+    fn import_then_list() {
+        let temp = TempDir::new().unwrap();
+        let export = temp.path().join("team.yml");
+        write_export(&export);
+
+        let mut ui = MockUI::new();
+        let result = command(
+            &temp,
+            LearnedSubcommand::Import {
+                path: export.clone(),
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+        assert!(result.success);
+
+        command(&temp, LearnedSubcommand::List { json: false })
+            .execute(&mut ui)
+            .unwrap();
+        assert!(ui.messages().iter().any(|m| m == "  brew install libpq"));
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m.contains("2 times (last 2026-01-05)")));
+    }
+
+    #[test]
+    fn export_round_trips() {
+        let temp = TempDir::new().unwrap();
+        let export = temp.path().join("team.yml");
+        write_export(&export);
+        let mut ui = MockUI::new();
+        command(&temp, LearnedSubcommand::Import { path: export })
+            .execute(&mut ui)
+            .unwrap();
+
+        let out = temp.path().join("out.yml");
+        command(&temp, LearnedSubcommand::Export { path: out.clone() })
+            .execute(&mut ui)
+            .unwrap();
+        let other = LearnedStore::new(temp.path().join("other.yml"));
+        assert_eq!(other.import(&out).unwrap().added, 1);
+    }
+
+    #[test]
+    fn import_missing_file_fails() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+        let result = command(
+            &temp,
+            LearnedSubcommand::Import {
+                path: temp.path().join("missing.yml"),
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+        assert!(!result.success);
+    }
+
+    #[test]
+    fn clear_with_force() {
+        let temp = TempDir::new().unwrap();
+        let export = temp.path().join("team.yml");
+        write_export(&export);
+        let mut ui = MockUI::new();
+        command(&temp, LearnedSubcommand::Import { path: export })
+            .execute(&mut ui)
+            .unwrap();
+        command(&temp, LearnedSubcommand::Clear { force: true })
+            .execute(&mut ui)
+            .unwrap();
+        assert!(ui.successes().iter().any(|m| m == "Forgot 1 learned fixes"));
+    }
+}
@@ -7,8 +7,9 @@
 
 use std::path::{Path, PathBuf};
 
+use crate::cache::offline::{self, is_offline, RemoteStores};
 use crate::cli::args::{Cli, Commands};
-use crate::config::TrustPolicy;
+use crate::config::{ConfigPaths, TrustPolicy};
 use crate::error::Result;
 use crate::ui::UserInterface;
 
@@ -82,6 +83,20 @@ impl CommandDispatcher {
         &self.project_root
     }
 
+    /// Fail if the project references remote content that isn't cached.
+    fn check_offline_resources(&self) -> Result<()> {
+        let paths = ConfigPaths::discover(&self.project_root);
+        if !paths.has_project_config() {
+            return Ok(());
+        }
+        let missing = offline::missing_offline(&paths, &RemoteStores::default())?;
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(offline::unavailable(missing))
+        }
+    }
+
     /// Dispatch and execute a command.
     ///
     /// Routes the CLI subcommand to the appropriate command implementation
@@ -98,6 +113,12 @@ impl CommandDispatcher {
 
         let config_override = self.config_override.clone().or_else(|| cli.config.clone());
 
+        // Offline, report every uncached remote resource up front rather
+        // than failing on whichever one is needed first
+        if is_offline() && uses_project_config(&cli.command) {
+            self.check_offline_resources()?;
+        }
+
         match &cli.command {
             Some(Commands::Run(args)) => {
                 let cmd = super::run::RunCommand::new(&self.project_root, args.clone())
@@ -114,10 +135,24 @@ impl CommandDispatcher {
                     .with_config_override(config_override);
                 cmd.execute(ui)
             }
+            Some(Commands::Remove(args)) => {
+                let cmd = super::remove::RemoveCommand::new(&self.project_root, args.clone())
+                    .with_config_override(config_override);
+                cmd.execute(ui)
+            }
+            Some(Commands::Eject(args)) => {
+                let cmd = super::eject::EjectCommand::new(&self.project_root, args.clone())
+                    .with_config_override(config_override);
+                cmd.execute(ui)
+            }
             Some(Commands::Templates(args)) => {
                 let cmd = super::templates::TemplatesCommand::new(&self.project_root, args.clone());
                 cmd.execute(ui)
             }
+            Some(Commands::Detect(args)) => {
+                let cmd = super::detect::DetectCommand::new(&self.project_root, args.clone());
+                cmd.execute(ui)
+            }
             Some(Commands::Status(args)) => {
                 let cmd = super::status::StatusCommand::new(&self.project_root, args.clone())
                     .with_config_override(config_override);
@@ -146,8 +181,18 @@ impl CommandDispatcher {
                     .with_config_override(config_override);
                 cmd.execute(ui)
             }
+            Some(Commands::Env(args)) => {
+                let cmd = super::env::EnvCommand::new(&self.project_root, args.clone())
+                    .with_config_override(config_override);
+                cmd.execute(ui)
+            }
             Some(Commands::Cache(args)) => {
-                let cmd = super::cache::CacheCommand::new(args.clone());
+                let cmd = super::cache::CacheCommand::new(args.clone())
+                    .with_project_root(&self.project_root);
+                cmd.execute(ui)
+            }
+            Some(Commands::Diagnostics(args)) => {
+                let cmd = super::diagnostics::DiagnosticsCommand::new(args.clone());
                 cmd.execute(ui)
             }
             Some(Commands::Feedback(args)) => {
@@ -163,6 +208,21 @@ impl CommandDispatcher {
                     .with_config_override(config_override);
                 cmd.execute(ui)
             }
+            Some(Commands::Affected(args)) => {
+                let cmd = super::affected::AffectedCommand::new(&self.project_root, args.clone())
+                    .with_trust_policy(trust_policy)
+                    .with_config_override(config_override);
+                cmd.execute(ui)
+            }
+            Some(Commands::Export(args)) => {
+                let cmd = super::export::ExportCommand::new(&self.project_root, args.clone())
+                    .with_config_override(config_override);
+                cmd.execute(ui)
+            }
+            Some(Commands::Hook(args)) => {
+                let cmd = super::hook::HookCommand::new(&self.project_root, args.clone());
+                cmd.execute(ui)
+            }
             Some(Commands::Completions(args)) => {
                 let cmd = super::completions::CompletionsCommand::new(args.clone());
                 cmd.execute(ui)
@@ -185,6 +245,24 @@ impl CommandDispatcher {
     }
 }
 
+/// Whether a command loads the project's config (and so its `extends`
+/// and template sources).
+fn uses_project_config(command: &Option<Commands>) -> bool {
+    !matches!(
+        command,
+        Some(
+            Commands::Init(_)
+                | Commands::Cache(_)
+                | Commands::Diagnostics(_)
+                | Commands::Feedback(_)
+                | Commands::Hook(_)
+                | Commands::Update(_)
+                | Commands::Completions(_)
+                | Commands::Schema(_)
+        )
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
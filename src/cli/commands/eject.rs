@@ -0,0 +1,485 @@
+//! Eject command implementation.
+//!
+//! The `bivvy eject` command replaces a step's `template:` reference with
+//! the template's full step definition, rendered with the step's inputs,
+//! so every field can be edited. The template name and version are
+//! recorded under `ejected_from` for `bivvy templates outdated`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_yaml::Value;
+
+use crate::cli::args::EjectArgs;
+use crate::config::{
+    load_config, load_merged_config, parse_config, ConfigEditor, Discovery, StepConfig,
+};
+use crate::error::{BivvyError, Result};
+use crate::registry::resolver::Registry;
+use crate::registry::EjectedStep;
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+
+/// The eject command implementation.
+pub struct EjectCommand {
+    project_root: PathBuf,
+    args: EjectArgs,
+    config_override: Option<PathBuf>,
+}
+
+impl EjectCommand {
+    /// Create a new eject command.
+    pub fn new(project_root: &Path, args: EjectArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+            config_override: None,
+        }
+    }
+
+    /// Set an override config path.
+    pub fn with_config_override(mut self, config_override: Option<PathBuf>) -> Self {
+        self.config_override = config_override;
+        self
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &EjectArgs {
+        &self.args
+    }
+
+    /// The file defining the step and the path to its fields there: the
+    /// main config, or the step's split file.
+    fn find_step(
+        &self,
+        config_path: &Path,
+        editor: &ConfigEditor,
+    ) -> Option<(PathBuf, Vec<String>)> {
+        let step = &self.args.step;
+        if editor.contains(&["steps", step.as_str()]) {
+            return Some((
+                config_path.to_path_buf(),
+                vec!["steps".to_string(), step.clone()],
+            ));
+        }
+        if self.config_override.is_some() {
+            return None;
+        }
+        Discovery::new(&self.project_root)
+            .step_path(step)
+            .map(|path| (path, Vec::new()))
+    }
+
+    /// Registry including the project's remote template sources.
+    fn registry(&self) -> Result<Registry> {
+        let config = match self.config_override {
+            Some(ref override_path) => load_config(&self.project_root, Some(override_path))?,
+            None => load_merged_config(&self.project_root)?,
+        };
+        if config.template_sources.is_empty() {
+            Registry::new(Some(&self.project_root))
+        } else {
+            Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)
+        }
+    }
+}
+
+/// Read a step definition from the file at `path`.
+fn parse_step(value: Option<Value>, path: &Path) -> Result<StepConfig> {
+    match value {
+        Some(Value::Null) | None => Ok(StepConfig::default()),
+        Some(value) => serde_yaml::from_value(value).map_err(|e| BivvyError::ConfigParseError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        }),
+    }
+}
+
+/// Path to a field of the step.
+fn field(base: &[String], rest: &[&str]) -> Vec<String> {
+    base.iter()
+        .cloned()
+        .chain(rest.iter().map(|s| s.to_string()))
+        .collect()
+}
+
+/// Replace the step's template reference with the rendered template
+/// fields. Fields the step already sets keep the step's value, as they
+/// did when the template was applied at run time.
+fn inline_template(
+    editor: &mut ConfigEditor,
+    base: &[String],
+    config: &StepConfig,
+    ejected: &EjectedStep,
+) -> Result<()> {
+    editor.remove(&field(base, &["template"]))?;
+    editor.remove(&field(base, &["inputs"]))?;
+
+    for (key, current, rendered) in [
+        ("title", &config.title, &ejected.title),
+        ("description", &config.description, &ejected.description),
+        ("command", &config.execution.command, &ejected.command),
+    ] {
+        if let (None, Some(value)) = (current, rendered) {
+            editor.set(&field(base, &[key]), &Value::String(value.clone()))?;
+        }
+    }
+
+    let mut tools = ejected.tools.clone();
+    for tool in &config.requires {
+        if !tools.contains(tool) {
+            tools.push(tool.clone());
+        }
+    }
+    if tools != config.requires {
+        // Keep the older `requires` spelling if that's what the step uses
+        let key = if editor.contains(&field(base, &["requires"])) {
+            "requires"
+        } else {
+            "tools"
+        };
+        let tools = tools.into_iter().map(Value::String).collect();
+        editor.set(&field(base, &[key]), &Value::Sequence(tools))?;
+    }
+
+    for (key, value) in &ejected.env {
+        if !config.env_vars.env.contains_key(key) {
+            editor.set(&field(base, &["env", key]), &Value::String(value.clone()))?;
+        }
+    }
+
+    if let Some(Value::Sequence(prompts)) = ejected.fields().get("prompts") {
+        for prompt in prompts {
+            let key = prompt.get("key").and_then(Value::as_str);
+            if !config
+                .output_settings
+                .prompts
+                .iter()
+                .any(|p| Some(p.key.as_str()) == key)
+            {
+                editor.insert_item(&field(base, &["prompts"]), None, prompt)?;
+            }
+        }
+    }
+
+    let ejected_from =
+        serde_yaml::to_value(&ejected.ejected_from).map_err(|e| BivvyError::ConfigEditError {
+            message: e.to_string(),
+        })?;
+    editor.set(&field(base, &["ejected_from"]), &ejected_from)
+}
+
+impl Command for EjectCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config_path = self
+            .config_override
+            .clone()
+            .unwrap_or_else(|| self.project_root.join(".bivvy/config.yml"));
+
+        // Validate config exists
+        if !config_path.exists() {
+            ui.error("No configuration found. Run 'bivvy init' first.");
+            return Ok(CommandResult::failure(2));
+        }
+
+        let step = &self.args.step;
+        let main = ConfigEditor::new(fs::read_to_string(&config_path)?)?;
+        let Some((path, base)) = self.find_step(&config_path, &main) else {
+            ui.error(&format!("Step '{}' not found in configuration.", step));
+            return Ok(CommandResult::failure(1));
+        };
+        let mut editor = if path == config_path {
+            main
+        } else {
+            ConfigEditor::new(fs::read_to_string(&path)?)?
+        };
+
+        let config = parse_step(editor.get(&base), &path)?;
+        let Some(ref template_name) = config.template else {
+            ui.error(&format!("Step '{}' doesn't use a template.", step));
+            return Ok(CommandResult::failure(1));
+        };
+
+        let ejected = EjectedStep::render(&self.registry()?, template_name, &config.inputs)?;
+        inline_template(&mut editor, &base, &config, &ejected)?;
+
+        // Don't write a config that bivvy can't load
+        let valid = if path == config_path {
+            parse_config(editor.as_str(), &config_path).map(|_| ())
+        } else {
+            let root: Vec<String> = Vec::new();
+            parse_step(editor.get(&root), &path).map(|_| ())
+        };
+        if let Err(e) = valid {
+            ui.error(&format!(
+                "Ejecting '{}' would make the config invalid: {}",
+                step, e
+            ));
+            return Ok(CommandResult::failure(1));
+        }
+
+        fs::write(&path, editor.as_str())?;
+
+        ui.success(&format!(
+            "Ejected '{}' from template '{}' ({})",
+            step, template_name, ejected.ejected_from.version
+        ));
+        if path != config_path {
+            let display = path.strip_prefix(&self.project_root).unwrap_or(&path);
+            ui.message(&format!("  Updated {}", display.display()));
+        }
+        ui.message("  Run 'bivvy templates outdated' to see later changes to the template");
+
+        Ok(CommandResult::success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use tempfile::TempDir;
+
+    fn setup_project(config: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(bivvy_dir.join("config.yml"), config).unwrap();
+        temp
+    }
+
+    fn read_config(temp: &TempDir) -> String {
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap()
+    }
+
+    fn eject(temp: &TempDir, step: &str, ui: &mut MockUI) -> CommandResult {
+        let args = EjectArgs {
+            step: step.to_string(),
+        };
+        EjectCommand::new(temp.path(), args).execute(ui).unwrap()
+    }
+
+    fn write_template(temp: &TempDir, name: &str, content: &str) {
+        let dir = temp.path().join(".bivvy/templates/steps");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(format!("{}.yml", name)), content).unwrap();
+    }
+
+    const DEPLOY_TEMPLATE: &str = r#"
+name: deploy
+description: "Deploy the app"
+category: custom
+version: "1.2.0"
+inputs:
+  region:
+    description: "Region"
+    type: string
+    required: true
+step:
+  title: "Deploy to ${region}"
+  command: "deploy --region ${region}"
+  requires: [node]
+  env:
+    DEPLOY_REGION: "${region}"
+"#;
+
+    #[test]
+    fn eject_command_creation() {
+        let temp = TempDir::new().unwrap();
+        let args = EjectArgs {
+            step: "deps".to_string(),
+        };
+        let cmd = EjectCommand::new(temp.path(), args);
+
+        assert_eq!(cmd.project_root(), temp.path());
+        assert_eq!(cmd.args().step, "deps");
+    }
+
+    #[test]
+    fn eject_fails_without_config() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "deps", &mut ui);
+
+        assert_eq!(result.exit_code, 2);
+    }
+
+    #[test]
+    fn eject_fails_for_unknown_step() {
+        let temp = setup_project("app_name: Test\nsteps:\n  deps:\n    template: bundle-install\n");
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "missing", &mut ui);
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Step 'missing' not found"));
+    }
+
+    #[test]
+    fn eject_fails_for_step_without_template() {
+        let config = "app_name: Test\nsteps:\n  hello:\n    command: echo hi\n";
+        let temp = setup_project(config);
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "hello", &mut ui);
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("doesn't use a template"));
+        assert_eq!(read_config(&temp), config);
+    }
+
+    #[test]
+    fn eject_inlines_builtin_template() {
+        let temp = setup_project(
+            "\
+app_name: Test
+
+steps:
+  # Ruby gems
+  deps:
+    template: bundle-install
+    depends_on: [tools]
+
+  tools:
+    command: mise install
+",
+        );
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "deps", &mut ui);
+
+        assert!(result.success);
+        assert_eq!(
+            read_config(&temp),
+            "\
+app_name: Test
+
+steps:
+  # Ruby gems
+  deps:
+    depends_on: [tools]
+    title: Install Ruby dependencies
+    description: Install gems from Gemfile using Bundler
+    command: bundle install
+    tools: [ruby]
+    ejected_from:
+      template: bundle-install
+      version: 1.0.0
+
+  tools:
+    command: mise install
+"
+        );
+        assert!(ui.has_success("Ejected 'deps' from template 'bundle-install' (1.0.0)"));
+    }
+
+    #[test]
+    fn eject_replaces_commented_template_step() {
+        // The format `bivvy add` and `bivvy init` write
+        let temp = setup_project(
+            "\
+app_name: Test
+
+steps:
+  bundle-install:
+    template: bundle-install
+    # command: bundle install
+
+workflows:
+  default:
+    steps: [bundle-install]
+",
+        );
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "bundle-install", &mut ui);
+
+        assert!(result.success);
+        assert_eq!(
+            read_config(&temp),
+            "\
+app_name: Test
+
+steps:
+  bundle-install:
+    title: Install Ruby dependencies
+    description: Install gems from Gemfile using Bundler
+    command: bundle install
+    tools: [ruby]
+    ejected_from:
+      template: bundle-install
+      version: 1.0.0
+
+workflows:
+  default:
+    steps: [bundle-install]
+"
+        );
+    }
+
+    #[test]
+    fn eject_substitutes_inputs_and_keeps_step_overrides() {
+        let temp = setup_project(
+            "\
+app_name: Test
+steps:
+  deploy:
+    template: deploy
+    inputs:
+      region: eu-west-1
+    title: Ship it
+    env:
+      DEBUG: \"1\"
+",
+        );
+        write_template(&temp, "deploy", DEPLOY_TEMPLATE);
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "deploy", &mut ui);
+
+        assert!(result.success);
+        let config = parse_config(&read_config(&temp), Path::new("config.yml")).unwrap();
+        let step = &config.steps["deploy"];
+        assert_eq!(step.template, None);
+        assert!(step.inputs.is_empty());
+        assert_eq!(step.title.as_deref(), Some("Ship it"));
+        assert_eq!(
+            step.execution.command.as_deref(),
+            Some("deploy --region eu-west-1")
+        );
+        assert_eq!(step.requires, vec!["node".to_string()]);
+        assert_eq!(step.env_vars.env["DEBUG"], "1");
+        assert_eq!(step.env_vars.env["DEPLOY_REGION"], "eu-west-1");
+
+        let ejected_from = step.ejected_from.as_ref().unwrap();
+        assert_eq!(ejected_from.template, "deploy");
+        assert_eq!(ejected_from.version, "1.2.0");
+        assert_eq!(ejected_from.inputs["region"], Value::from("eu-west-1"));
+    }
+
+    #[test]
+    fn eject_updates_split_step_file() {
+        let temp = setup_project("app_name: Test\n");
+        let steps_dir = temp.path().join(".bivvy/steps");
+        fs::create_dir_all(&steps_dir).unwrap();
+        fs::write(steps_dir.join("deps.yml"), "template: bundle-install\n").unwrap();
+        let mut ui = MockUI::new();
+
+        let result = eject(&temp, "deps", &mut ui);
+
+        assert!(result.success);
+        let content = fs::read_to_string(steps_dir.join("deps.yml")).unwrap();
+        let step: StepConfig = serde_yaml::from_str(&content).unwrap();
+        assert_eq!(step.execution.command.as_deref(), Some("bundle install"));
+        assert_eq!(step.ejected_from.unwrap().template, "bundle-install");
+        assert_eq!(read_config(&temp), "app_name: Test\n");
+        assert!(ui.has_message("Updated .bivvy/steps/deps.yml"));
+    }
+}
@@ -0,0 +1,534 @@
+//! Env command implementation.
+//!
+//! The `bivvy env` command shows the environment variables a workflow (and
+//! optionally one of its steps) runs with, which layer set each one and
+//! which layers it shadows.
+
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::EnvArgs;
+use crate::config::{
+    build_yaml_env_stack, load_config, load_env_file, load_env_file_optional, BivvyConfig,
+    EnvLayer, EnvLayerStack,
+};
+use crate::environment::resolver::ResolvedEnvironment;
+use crate::error::{BivvyError, Result};
+use crate::registry::Registry;
+use crate::secrets::SecretMatcher;
+use crate::ui::theme::BivvyTheme;
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+
+/// Source name for variables exported in the calling shell.
+const PROCESS_SOURCE: &str = "shell environment";
+
+/// Shown in place of secret values.
+const MASK: &str = "[REDACTED]";
+
+/// The env command implementation.
+pub struct EnvCommand {
+    project_root: PathBuf,
+    args: EnvArgs,
+    config_override: Option<PathBuf>,
+}
+
+impl EnvCommand {
+    /// Create a new env command.
+    pub fn new(project_root: &Path, args: EnvArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+            config_override: None,
+        }
+    }
+
+    /// Set an override config path.
+    pub fn with_config_override(mut self, config_override: Option<PathBuf>) -> Self {
+        self.config_override = config_override;
+        self
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &EnvArgs {
+        &self.args
+    }
+
+    /// Resolve the target environment using the priority chain.
+    fn resolve_environment(&self, config: &BivvyConfig) -> ResolvedEnvironment {
+        ResolvedEnvironment::resolve_from_config(self.args.env.as_deref(), &config.settings)
+    }
+
+    /// The workflow to show: `--workflow`, then the environment's
+    /// default workflow, then `default` (matching `bivvy run`).
+    fn workflow_name(&self, config: &BivvyConfig, env_name: &str) -> String {
+        self.args.workflow.clone().unwrap_or_else(|| {
+            config
+                .settings
+                .environment_profiles
+                .environments
+                .get(env_name)
+                .and_then(|env_config| env_config.default_workflow.clone())
+                .unwrap_or_else(|| "default".to_string())
+        })
+    }
+
+    /// Build the config-defined layers, lowest priority first.
+    ///
+    /// On top of the workflow layers from [`build_yaml_env_stack`], a step
+    /// adds its `env_file`, its template's `env`, its own `env` and the
+    /// `env` of its override for the target environment, in the order the
+    /// step executor applies them. Override entries set to `null` remove
+    /// the variable from the template and step layers.
+    fn build_stack(
+        &self,
+        config: &BivvyConfig,
+        workflow_name: &str,
+        env_name: &str,
+    ) -> Result<EnvLayerStack> {
+        let mut stack = build_yaml_env_stack(config, workflow_name, &self.project_root)?;
+
+        let Some(ref step_name) = self.args.step else {
+            return Ok(stack);
+        };
+        let step =
+            config
+                .steps
+                .get(step_name)
+                .ok_or_else(|| BivvyError::ConfigValidationError {
+                    message: format!("Unknown step: {}", step_name),
+                })?;
+
+        if let Some(ref env_file) = step.env_vars.env_file {
+            let resolved = self.project_root.join(env_file);
+            let file_env = if step.env_vars.env_file_optional {
+                load_env_file_optional(&resolved)
+            } else {
+                load_env_file(&resolved)?
+            };
+            let mut layer = EnvLayer::new(format!("steps.{}.env_file", step_name));
+            for (k, v) in file_env {
+                layer.set(k, v);
+            }
+            stack.push(layer);
+        }
+
+        let mut template_layer = EnvLayer::default();
+        if let Some(ref template_name) = step.template {
+            let registry = if config.template_sources.is_empty() {
+                Registry::new(Some(&self.project_root))?
+            } else {
+                Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)?
+            };
+            let (template, _source) = registry.resolve(template_name)?;
+            template_layer = EnvLayer::new(format!("templates.{}.env", template_name));
+            for (k, v) in &template.step.env {
+                template_layer.set(k, v);
+            }
+        }
+
+        let mut step_layer = EnvLayer::new(format!("steps.{}.env", step_name));
+        for (k, v) in &step.env_vars.env {
+            step_layer.set(k, v);
+        }
+
+        let mut override_layer =
+            EnvLayer::new(format!("steps.{}.environments.{}.env", step_name, env_name));
+        if let Some(overrides) = step.scoping.environments.get(env_name) {
+            for (k, v) in &overrides.env {
+                match v {
+                    Some(value) => override_layer.set(k, value),
+                    None => {
+                        template_layer.vars.remove(k);
+                        step_layer.vars.remove(k);
+                    }
+                }
+            }
+        }
+
+        for layer in [template_layer, step_layer, override_layer] {
+            if !layer.is_empty() {
+                stack.push(layer);
+            }
+        }
+
+        Ok(stack)
+    }
+}
+
+impl Command for EnvCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config = match load_config(&self.project_root, self.config_override.as_deref()) {
+            Ok(c) => c,
+            Err(BivvyError::ConfigNotFound { .. }) => {
+                ui.error("No configuration found. Run 'bivvy init' first.");
+                return Ok(CommandResult::failure(2));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let resolved_env = self.resolve_environment(&config);
+        let env_name = resolved_env.name.clone();
+        let workflow_name = self.workflow_name(&config, &env_name);
+
+        if self.args.workflow.is_some() && !config.workflows.contains_key(&workflow_name) {
+            ui.error(&format!("Unknown workflow: {}", workflow_name));
+            return Ok(CommandResult::failure(1));
+        }
+        if let Some(ref step_name) = self.args.step {
+            if !config.steps.contains_key(step_name) {
+                ui.error(&format!("Unknown step: {}", step_name));
+                return Ok(CommandResult::failure(1));
+            }
+        }
+
+        let mut stack = self.build_stack(&config, &workflow_name, &env_name)?;
+        let mut names: Vec<String> = stack.resolve().into_keys().collect();
+        names.sort();
+
+        // Exports carry the config's values only: the shell already has
+        // its own, and masked values would be useless to `eval`.
+        if let Some(ref format) = self.args.export {
+            for name in &names {
+                if let Some(value) = stack.get(name) {
+                    ui.message(&export_line(format, name, value));
+                }
+            }
+            return Ok(CommandResult::success());
+        }
+
+        // The process env wins last in the executor, so shell exports of a
+        // configured variable shadow every config layer.
+        let mut process = EnvLayer::new(PROCESS_SOURCE);
+        for name in &names {
+            if let Ok(value) = std::env::var(name) {
+                process.set(name, value);
+            }
+        }
+        stack.push(process);
+
+        let theme = BivvyTheme::new();
+        let matcher = SecretMatcher::with_builtins_and_custom(&config.settings.env_vars.secret_env);
+
+        ui.message(&format!(
+            "  {} {} ({})",
+            theme.key.apply_to("Environment:"),
+            theme.highlight.apply_to(&env_name),
+            theme.dim.apply_to(resolved_env.source.to_string()),
+        ));
+        ui.message(&format!(
+            "  {} {}",
+            theme.key.apply_to("Workflow:"),
+            theme.highlight.apply_to(&workflow_name),
+        ));
+        if let Some(ref step_name) = self.args.step {
+            ui.message(&format!(
+                "  {} {}",
+                theme.key.apply_to("Step:"),
+                theme.highlight.apply_to(step_name),
+            ));
+        }
+        ui.message("");
+
+        if names.is_empty() {
+            ui.message("  No environment variables configured.");
+            return Ok(CommandResult::success());
+        }
+
+        for name in &names {
+            let value = stack.get(name).unwrap_or_default();
+            let display = if matcher.is_secret(name) { MASK } else { value };
+            let sources = stack.sources_of(name);
+            ui.message(&format!(
+                "  {}={}  {}",
+                theme.key.apply_to(name),
+                display,
+                theme.dim.apply_to(format!("({})", sources[0])),
+            ));
+            if sources.len() > 1 {
+                ui.message(&format!(
+                    "    {}",
+                    theme
+                        .dim
+                        .apply_to(format!("shadows {}", sources[1..].join(", "))),
+                ));
+            }
+        }
+
+        Ok(CommandResult::success())
+    }
+}
+
+/// Format one variable for `--export`.
+///
+/// Values are never expanded by the reading shell or dotenv loader: bash
+/// and fish get single-quoted strings, and dotenv gets a bare value, a
+/// single-quoted one, or (when the value contains `'` or a line break) a
+/// double-quoted one with `\`, `"`, `$` and line breaks escaped.
+fn export_line(format: &str, name: &str, value: &str) -> String {
+    match format {
+        "fish" => format!(
+            "set -gx {} '{}'",
+            name,
+            value.replace('\\', "\\\\").replace('\'', "\\'")
+        ),
+        "dotenv" => {
+            let plain = !value.is_empty()
+                && value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_-./:@%+,".contains(c));
+            if plain {
+                format!("{}={}", name, value)
+            } else if !value.contains(['\'', '\n', '\r']) {
+                format!("{}='{}'", name, value)
+            } else {
+                let mut escaped = String::with_capacity(value.len());
+                for c in value.chars() {
+                    match c {
+                        '\\' => escaped.push_str("\\\\"),
+                        '"' => escaped.push_str("\\\""),
+                        '$' => escaped.push_str("\\$"),
+                        '\n' => escaped.push_str("\\n"),
+                        '\r' => escaped.push_str("\\r"),
+                        c => escaped.push(c),
+                    }
+                }
+                format!("{}=\"{}\"", name, escaped)
+            }
+        }
+        _ => format!("export {}='{}'", name, value.replace('\'', "'\\''")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn setup_project(config: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(bivvy_dir.join("config.yml"), config).unwrap();
+        temp
+    }
+
+    const CONFIG: &str = r#"
+app_name: test
+settings:
+  env:
+    BIVVY_TEST_MODE: settings
+    BIVVY_TEST_API_KEY: abc123
+workflows:
+  default:
+    steps: [hello]
+    env:
+      BIVVY_TEST_MODE: workflow
+steps:
+  hello:
+    command: echo hello
+    env:
+      BIVVY_TEST_MODE: step
+      BIVVY_TEST_STEP_ONLY: "it's here"
+    environments:
+      ci:
+        env:
+          BIVVY_TEST_MODE: ci
+          BIVVY_TEST_STEP_ONLY: null
+"#;
+
+    fn run(temp: &TempDir, args: EnvArgs) -> (CommandResult, MockUI) {
+        let cmd = EnvCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+        let result = cmd.execute(&mut ui).unwrap();
+        (result, ui)
+    }
+
+    #[test]
+    fn env_command_creation() {
+        let temp = TempDir::new().unwrap();
+        let cmd = EnvCommand::new(temp.path(), EnvArgs::default());
+
+        assert_eq!(cmd.project_root(), temp.path());
+        assert!(cmd.args().step.is_none());
+    }
+
+    #[test]
+    fn env_no_config() {
+        let temp = TempDir::new().unwrap();
+        let (result, ui) = run(&temp, EnvArgs::default());
+
+        assert_eq!(result.exit_code, 2);
+        assert!(ui.has_error("No configuration found"));
+    }
+
+    #[test]
+    fn env_shows_winning_layer_and_shadowed_layers() {
+        let temp = setup_project(CONFIG);
+        let (result, ui) = run(&temp, EnvArgs::default());
+
+        assert!(result.success);
+        let out = ui.messages().join("\n");
+        assert!(out.contains("BIVVY_TEST_MODE=workflow  (workflows.default.env)"));
+        assert!(out.contains("shadows settings.env_vars.env"));
+        assert!(!out.contains("BIVVY_TEST_STEP_ONLY"));
+    }
+
+    #[test]
+    fn env_masks_secrets() {
+        let temp = setup_project(CONFIG);
+        let (_, ui) = run(&temp, EnvArgs::default());
+
+        let out = ui.messages().join("\n");
+        assert!(out.contains("BIVVY_TEST_API_KEY=[REDACTED]"));
+        assert!(!out.contains("abc123"));
+    }
+
+    #[test]
+    fn env_includes_step_layers() {
+        let temp = setup_project(CONFIG);
+        let (_, ui) = run(
+            &temp,
+            EnvArgs {
+                step: Some("hello".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let out = ui.messages().join("\n");
+        assert!(out.contains("BIVVY_TEST_MODE=step  (steps.hello.env)"));
+        assert!(out.contains("shadows workflows.default.env, settings.env_vars.env"));
+        assert!(out.contains("BIVVY_TEST_STEP_ONLY=it's here"));
+    }
+
+    #[test]
+    fn env_applies_environment_overrides() {
+        let temp = setup_project(CONFIG);
+        let (_, ui) = run(
+            &temp,
+            EnvArgs {
+                step: Some("hello".to_string()),
+                env: Some("ci".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let out = ui.messages().join("\n");
+        assert!(out.contains("BIVVY_TEST_MODE=ci  (steps.hello.environments.ci.env)"));
+        assert!(!out.contains("BIVVY_TEST_STEP_ONLY"));
+    }
+
+    #[test]
+    fn env_unknown_step_fails() {
+        let temp = setup_project(CONFIG);
+        let (result, ui) = run(
+            &temp,
+            EnvArgs {
+                step: Some("missing".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Unknown step: missing"));
+    }
+
+    #[test]
+    fn env_unknown_workflow_fails() {
+        let temp = setup_project(CONFIG);
+        let (result, ui) = run(
+            &temp,
+            EnvArgs {
+                workflow: Some("missing".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Unknown workflow: missing"));
+    }
+
+    #[test]
+    fn env_export_prints_unmasked_values() {
+        let temp = setup_project(CONFIG);
+        let (result, ui) = run(
+            &temp,
+            EnvArgs {
+                step: Some("hello".to_string()),
+                export: Some("bash".to_string()),
+                ..Default::default()
+            },
+        );
+
+        assert!(result.success);
+        assert_eq!(
+            ui.messages(),
+            [
+                "export BIVVY_TEST_API_KEY='abc123'",
+                "export BIVVY_TEST_MODE='step'",
+                "export BIVVY_TEST_STEP_ONLY='it'\\''s here'",
+            ]
+        );
+    }
+
+    #[test]
+    fn export_line_formats() {
+        assert_eq!(export_line("bash", "A", "x y"), "export A='x y'");
+        assert_eq!(export_line("fish", "A", "it's"), "set -gx A 'it\\'s'");
+        assert_eq!(export_line("dotenv", "A", "plain"), "A=plain");
+        assert_eq!(export_line("dotenv", "A", "two words"), "A='two words'");
+        assert_eq!(export_line("dotenv", "A", "say \"hi\""), "A='say \"hi\"'");
+
+        // Mixed quotes
+        let mixed = r#"it's "quoted""#;
+        assert_eq!(
+            export_line("bash", "A", mixed),
+            r#"export A='it'\''s "quoted"'"#
+        );
+        assert_eq!(
+            export_line("fish", "A", mixed),
+            r#"set -gx A 'it\'s "quoted"'"#
+        );
+        assert_eq!(export_line("dotenv", "A", mixed), r#"A="it's \"quoted\"""#);
+
+        // Variable references and backslashes stay literal
+        assert_eq!(
+            export_line("bash", "A", r"$HOME\bin"),
+            r"export A='$HOME\bin'"
+        );
+        assert_eq!(
+            export_line("fish", "A", r"$HOME\bin"),
+            r"set -gx A '$HOME\\bin'"
+        );
+        assert_eq!(export_line("dotenv", "A", r"$HOME\bin"), r"A='$HOME\bin'");
+        assert_eq!(
+            export_line("dotenv", "A", r"it's $HOME\bin"),
+            r#"A="it's \$HOME\\bin""#
+        );
+
+        // Line breaks
+        assert_eq!(export_line("bash", "A", "a\nb"), "export A='a\nb'");
+        assert_eq!(export_line("fish", "A", "a\nb"), "set -gx A 'a\nb'");
+        assert_eq!(export_line("dotenv", "A", "a\nb"), r#"A="a\nb""#);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn bash_export_lines_round_trip() {
+        for value in [r#"it's "quoted""#, r"$HOME\bin `id`", "a\nb", ""] {
+            let script = format!("{}\nprintf %s \"$A\"", export_line("bash", "A", value));
+            let output = std::process::Command::new("sh")
+                .args(["-c", &script])
+                .output()
+                .unwrap();
+            assert_eq!(String::from_utf8(output.stdout).unwrap(), value);
+        }
+    }
+}
@@ -0,0 +1,720 @@
+//! Export command implementation.
+//!
+//! The `bivvy export` command generates other tools' configuration from a
+//! bivvy workflow.
+//!
+//! Usage:
+//!   bivvy export ci --provider github          # GitHub Actions workflow
+//!   bivvy export ci --provider gitlab --jobs groups
+//!   bivvy export dockerfile -o Dockerfile      # Image with setup built in
+//!   bivvy export devcontainer                  # .devcontainer/ for Codespaces
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::checks::ChangeKind;
+use crate::config::{load_config, BivvyConfig, PromptType};
+use crate::error::{BivvyError, Result};
+use crate::export::{CiPipeline, CiProvider, CiStep, ContainerImage, ContainerStep, JobLayout};
+use crate::requirements::registry::RequirementRegistry;
+use crate::secrets::SecretMatcher;
+use crate::steps::ResolvedStep;
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+use super::run::{resolve_config_steps, step_registry, watched_targets};
+
+/// Arguments for the `export` command.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportArgs {
+    #[command(subcommand)]
+    pub target: ExportTarget,
+}
+
+/// Export subcommands.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum ExportTarget {
+    /// Generate a CI pipeline that runs a workflow
+    Ci(ExportCiArgs),
+
+    /// Generate a Dockerfile that installs requirements and builds steps in
+    Dockerfile(ExportDockerfileArgs),
+
+    /// Generate .devcontainer/ with a Dockerfile and a postCreateCommand
+    /// that runs bivvy
+    Devcontainer(ExportDevcontainerArgs),
+}
+
+/// Arguments for `bivvy export ci`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportCiArgs {
+    /// CI service to generate a pipeline for
+    #[arg(long, value_enum)]
+    pub provider: ProviderArg,
+
+    /// Workflow to export (defaults to the environment's default
+    /// workflow, then `default`)
+    #[arg(short, long)]
+    pub workflow: Option<String>,
+
+    /// Environment the pipeline runs the workflow in
+    #[arg(long, value_name = "ENV", default_value = "ci")]
+    pub env: String,
+
+    /// One job for the whole workflow, or one job per parallel group
+    #[arg(long, value_enum, default_value = "single")]
+    pub jobs: JobsArg,
+
+    /// Write the pipeline to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for `bivvy export dockerfile`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportDockerfileArgs {
+    /// Workflow to export (defaults to the environment's default
+    /// workflow, then `default`)
+    #[arg(short, long)]
+    pub workflow: Option<String>,
+
+    /// Environment the image runs the workflow in
+    #[arg(long, value_name = "ENV", default_value = "docker")]
+    pub env: String,
+
+    /// Write the Dockerfile to a file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+}
+
+/// Arguments for `bivvy export devcontainer`.
+#[derive(Debug, Clone, clap::Args)]
+pub struct ExportDevcontainerArgs {
+    /// Workflow to export (defaults to the environment's default
+    /// workflow, then `default`)
+    #[arg(short, long)]
+    pub workflow: Option<String>,
+
+    /// Environment the container runs the workflow in
+    #[arg(long, value_name = "ENV", default_value = "codespace")]
+    pub env: String,
+
+    /// Directory to write devcontainer.json and Dockerfile to
+    #[arg(short, long, default_value = ".devcontainer")]
+    pub output: PathBuf,
+
+    /// Overwrite an existing devcontainer.json
+    #[arg(short, long)]
+    pub force: bool,
+}
+
+/// CI services `bivvy export ci` supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ProviderArg {
+    Github,
+    Gitlab,
+}
+
+impl From<ProviderArg> for CiProvider {
+    fn from(arg: ProviderArg) -> Self {
+        match arg {
+            ProviderArg::Github => Self::Github,
+            ProviderArg::Gitlab => Self::Gitlab,
+        }
+    }
+}
+
+/// Job layouts `bivvy export ci` supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum JobsArg {
+    Single,
+    Groups,
+}
+
+impl From<JobsArg> for JobLayout {
+    fn from(arg: JobsArg) -> Self {
+        match arg {
+            JobsArg::Single => Self::Single,
+            JobsArg::Groups => Self::Groups,
+        }
+    }
+}
+
+/// A workflow's steps, resolved for one environment.
+struct Selected {
+    workflow: String,
+    /// Every configured step, resolved for the environment.
+    steps: HashMap<String, ResolvedStep>,
+    /// Workflow steps that run in the environment, in workflow order.
+    names: Vec<String>,
+}
+
+/// The export command implementation.
+pub struct ExportCommand {
+    project_root: PathBuf,
+    args: ExportArgs,
+    config_override: Option<PathBuf>,
+}
+
+impl ExportCommand {
+    /// Create a new export command.
+    pub fn new(project_root: &Path, args: ExportArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+            config_override: None,
+        }
+    }
+
+    /// Set an override config path.
+    pub fn with_config_override(mut self, config_override: Option<PathBuf>) -> Self {
+        self.config_override = config_override;
+        self
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &ExportArgs {
+        &self.args
+    }
+
+    /// The workflow to export: `--workflow`, then the environment's
+    /// default workflow, then `default` (matching `bivvy run`).
+    fn workflow_name(&self, config: &BivvyConfig, workflow: Option<&str>, env: &str) -> String {
+        workflow.map(str::to_string).unwrap_or_else(|| {
+            config
+                .settings
+                .environment_profiles
+                .environments
+                .get(env)
+                .and_then(|env_config| env_config.default_workflow.clone())
+                .unwrap_or_else(|| "default".to_string())
+        })
+    }
+
+    /// Resolve a workflow's steps for `env`, leaving out steps whose
+    /// `only_environments` excludes it. `None` if the workflow is unknown.
+    fn select(
+        &self,
+        config: &BivvyConfig,
+        workflow_name: &str,
+        env: &str,
+    ) -> Result<Option<Selected>> {
+        let Some(workflow) = config.workflows.get(workflow_name) else {
+            return Ok(None);
+        };
+
+        let registry = step_registry(&self.project_root, config)?;
+        let steps = resolve_config_steps(&registry, config, Some(env))?;
+        let names = workflow
+            .steps
+            .iter()
+            .filter(|name| {
+                steps.get(*name).is_some_and(|step| {
+                    step.scoping.only_environments.is_empty()
+                        || step.scoping.only_environments.iter().any(|e| e == env)
+                })
+            })
+            .cloned()
+            .collect();
+
+        Ok(Some(Selected {
+            workflow: workflow_name.to_string(),
+            steps,
+            names,
+        }))
+    }
+
+    fn export_ci(
+        &self,
+        config: &BivvyConfig,
+        args: &ExportCiArgs,
+        ui: &mut dyn UserInterface,
+    ) -> Result<CommandResult> {
+        let workflow = self.workflow_name(config, args.workflow.as_deref(), &args.env);
+        let Some(selected) = self.select(config, &workflow, &args.env)? else {
+            ui.error(&format!("Unknown workflow: {}", workflow));
+            return Ok(CommandResult::failure(1));
+        };
+        let mut targets = watched_targets(&selected.steps);
+
+        let ci_steps = selected
+            .names
+            .iter()
+            .map(|name| {
+                let step = &selected.steps[name];
+                let mut cache_key: Vec<String> = targets
+                    .remove(name)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|(_, kind)| *kind != ChangeKind::Command)
+                    .map(|(target, _)| target)
+                    .collect();
+                let mut cache_paths = Vec::new();
+                if let Some(cache) = &step.execution.cache {
+                    cache_key.extend(cache.key.iter().cloned());
+                    cache_paths = cache.paths.clone();
+                }
+                CiStep {
+                    name: name.clone(),
+                    depends_on: step.depends_on.clone(),
+                    requires: step.requires.clone(),
+                    cache_key,
+                    cache_paths,
+                }
+            })
+            .collect();
+
+        let pipeline = CiPipeline::new(&selected.workflow, &args.env, ci_steps)?;
+        let rendered =
+            match pipeline.render(args.provider.into(), args.jobs.into(), &self.project_root) {
+                Ok(rendered) => rendered,
+                Err(e) => {
+                    ui.error(&e.to_string());
+                    return Ok(CommandResult::failure(1));
+                }
+            };
+        self.emit(ui, args.output.as_deref(), &rendered)
+    }
+
+    /// Plan the container image for a workflow, or report why there's none.
+    fn container_image(
+        &self,
+        config: &BivvyConfig,
+        workflow: Option<&str>,
+        env: &str,
+        ui: &mut dyn UserInterface,
+    ) -> Result<std::result::Result<ContainerImage, CommandResult>> {
+        let workflow = self.workflow_name(config, workflow, env);
+        let Some(selected) = self.select(config, &workflow, env)? else {
+            ui.error(&format!("Unknown workflow: {}", workflow));
+            return Ok(Err(CommandResult::failure(1)));
+        };
+
+        let templates = step_registry(&self.project_root, config)?;
+        // Only declared change-check targets: template detector files show
+        // a template applies, not everything its command reads
+        let mut targets = watched_targets(&selected.steps);
+        let matcher = SecretMatcher::with_builtins_and_custom(&config.settings.env_vars.secret_env);
+        let steps = selected
+            .names
+            .iter()
+            .map(|name| {
+                let step = &selected.steps[name];
+                ContainerStep {
+                    name: name.clone(),
+                    command: Some(step.execution.command.clone()),
+                    depends_on: step.depends_on.clone(),
+                    requires: step.requires.clone(),
+                    inputs: targets
+                        .remove(name)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|(_, kind)| *kind != ChangeKind::Command)
+                        .map(|(target, _)| target)
+                        .collect(),
+                    secrets: step_secrets(step, config, &matcher),
+                }
+            })
+            .collect();
+
+        let requirements = RequirementRegistry::new().with_custom(&config.requirements);
+        match ContainerImage::new(
+            &selected.workflow,
+            env,
+            steps,
+            &requirements,
+            &templates,
+            &self.project_root,
+        ) {
+            Ok(image) => Ok(Ok(image)),
+            Err(e) => {
+                ui.error(&e.to_string());
+                Ok(Err(CommandResult::failure(1)))
+            }
+        }
+    }
+
+    fn export_dockerfile(
+        &self,
+        config: &BivvyConfig,
+        args: &ExportDockerfileArgs,
+        ui: &mut dyn UserInterface,
+    ) -> Result<CommandResult> {
+        match self.container_image(config, args.workflow.as_deref(), &args.env, ui)? {
+            Ok(image) => self.emit(ui, args.output.as_deref(), &image.dockerfile()),
+            Err(result) => Ok(result),
+        }
+    }
+
+    fn export_devcontainer(
+        &self,
+        config: &BivvyConfig,
+        args: &ExportDevcontainerArgs,
+        ui: &mut dyn UserInterface,
+    ) -> Result<CommandResult> {
+        let dir = self.project_root.join(&args.output);
+        let json_path = dir.join("devcontainer.json");
+        if json_path.exists() && !args.force {
+            ui.error(&format!(
+                "{} already exists. Use --force to overwrite it.",
+                json_path.display()
+            ));
+            return Ok(CommandResult::failure(1));
+        }
+
+        let image = match self.container_image(config, args.workflow.as_deref(), &args.env, ui)? {
+            Ok(image) => image,
+            Err(result) => return Ok(result),
+        };
+        let name = self
+            .project_root
+            .canonicalize()
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+            .unwrap_or_else(|| "bivvy".to_string());
+
+        std::fs::create_dir_all(&dir)?;
+        std::fs::write(&json_path, image.devcontainer(&name)?)?;
+        std::fs::write(dir.join("Dockerfile"), image.dockerfile())?;
+        ui.success(&format!("Wrote {} and Dockerfile", json_path.display()));
+        Ok(CommandResult::success())
+    }
+
+    /// Print `content`, or write it to `output` under the project root.
+    fn emit(
+        &self,
+        ui: &mut dyn UserInterface,
+        output: Option<&Path>,
+        content: &str,
+    ) -> Result<CommandResult> {
+        match output {
+            Some(path) => {
+                let path = self.project_root.join(path);
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, content)?;
+                ui.success(&format!("Wrote {}", path.display()));
+            }
+            None => ui.message(content.trim_end()),
+        }
+        Ok(CommandResult::success())
+    }
+}
+
+/// Secrets a step uses: secret-looking env vars, secret prompts and
+/// `secrets:` entries it references.
+fn step_secrets(step: &ResolvedStep, config: &BivvyConfig, matcher: &SecretMatcher) -> Vec<String> {
+    let mut secrets: Vec<String> = matcher
+        .find_secrets(step.env_vars.env.keys().map(String::as_str))
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    secrets.extend(
+        step.output
+            .prompts
+            .iter()
+            .filter(|p| matches!(p.prompt_type, PromptType::Secret))
+            .map(|p| p.key.clone()),
+    );
+    secrets.extend(
+        step.referenced_variables()
+            .into_iter()
+            .filter(|name| config.secrets.contains_key(name)),
+    );
+    secrets.sort();
+    secrets.dedup();
+    if secrets.is_empty() && step.behavior.sensitive {
+        secrets.push("sensitive values".to_string());
+    }
+    secrets
+}
+
+impl Command for ExportCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config = match load_config(&self.project_root, self.config_override.as_deref()) {
+            Ok(c) => c,
+            Err(BivvyError::ConfigNotFound { .. }) => {
+                ui.error("No configuration found. Run 'bivvy init' first.");
+                return Ok(CommandResult::failure(2));
+            }
+            Err(e) => return Err(e),
+        };
+
+        match &self.args.target {
+            ExportTarget::Ci(args) => self.export_ci(&config, args, ui),
+            ExportTarget::Dockerfile(args) => self.export_dockerfile(&config, args, ui),
+            ExportTarget::Devcontainer(args) => self.export_devcontainer(&config, args, ui),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use std::fs;
+    use tempfile::TempDir;
+
+    const CONFIG: &str = r#"
+steps:
+  deps:
+    command: npm ci
+    requires: [node]
+    check:
+      type: change
+      target: package-lock.json
+    cache:
+      key: [package-lock.json]
+      paths: [node_modules]
+  db:
+    command: bin/setup-db
+    depends_on: [deps]
+    requires: [postgres-server]
+  seed:
+    command: bin/seed
+    depends_on: [db]
+    only_environments: [development]
+workflows:
+  default:
+    steps: [deps, db, seed]
+"#;
+
+    fn setup_project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".bivvy")).unwrap();
+        fs::write(temp.path().join(".bivvy/config.yml"), CONFIG).unwrap();
+        temp
+    }
+
+    fn ci_args(provider: ProviderArg) -> ExportArgs {
+        ExportArgs {
+            target: ExportTarget::Ci(ExportCiArgs {
+                provider,
+                workflow: None,
+                env: "ci".to_string(),
+                jobs: JobsArg::Single,
+                output: None,
+            }),
+        }
+    }
+
+    fn render(temp: &TempDir, args: ExportArgs) -> serde_yaml::Value {
+        let mut ui = MockUI::new();
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+        assert!(result.success);
+        serde_yaml::from_str(&ui.messages()[0]).unwrap()
+    }
+
+    #[test]
+    fn exports_github_workflow() {
+        let temp = setup_project();
+        let yaml = render(&temp, ci_args(ProviderArg::Github));
+
+        let job = &yaml["jobs"]["setup"];
+        assert_eq!(job["services"]["postgres"]["image"], "postgres:16");
+        let steps = job["steps"].as_sequence().unwrap();
+        assert!(steps.iter().any(|s| s["uses"] == "actions/setup-node@v4"));
+        let cache = steps.iter().find(|s| s["name"] == "Cache deps").unwrap();
+        assert_eq!(cache["with"]["path"], "node_modules");
+        assert_eq!(
+            cache["with"]["key"],
+            "bivvy-deps-${{ runner.os }}-${{ hashFiles('package-lock.json') }}"
+        );
+    }
+
+    #[test]
+    fn skips_steps_outside_the_environment() {
+        let temp = setup_project();
+        let args = ExportArgs {
+            target: ExportTarget::Ci(ExportCiArgs {
+                provider: ProviderArg::Gitlab,
+                workflow: None,
+                env: "ci".to_string(),
+                jobs: JobsArg::Groups,
+                output: None,
+            }),
+        };
+        let yaml = render(&temp, args);
+
+        assert_eq!(
+            yaml["stages"],
+            serde_yaml::from_str::<serde_yaml::Value>("[setup-1, setup-2]").unwrap()
+        );
+        assert_eq!(
+            yaml["setup-2"]["script"][0],
+            "bivvy run --non-interactive --env ci --only db"
+        );
+    }
+
+    #[test]
+    fn writes_output_file() {
+        let temp = setup_project();
+        let mut args = ci_args(ProviderArg::Github);
+        let ExportTarget::Ci(ci) = &mut args.target else {
+            unreachable!()
+        };
+        ci.output = Some(PathBuf::from(".github/workflows/bivvy.yml"));
+        let mut ui = MockUI::new();
+
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(result.success);
+        let written = fs::read_to_string(temp.path().join(".github/workflows/bivvy.yml")).unwrap();
+        assert!(written.starts_with("# Generated by `bivvy export ci`"));
+    }
+
+    #[test]
+    fn exports_dockerfile() {
+        let temp = setup_project();
+        fs::write(temp.path().join("package-lock.json"), "{}").unwrap();
+        let args = ExportArgs {
+            target: ExportTarget::Dockerfile(ExportDockerfileArgs {
+                workflow: None,
+                env: "docker".to_string(),
+                output: None,
+            }),
+        };
+        let mut ui = MockUI::new();
+
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(result.success);
+        let dockerfile = &ui.messages()[0];
+        assert!(dockerfile.contains("RUN mise install node\n"));
+        assert!(dockerfile.contains(
+            "COPY package-lock.json ./\n\
+             RUN bivvy run --non-interactive --env docker --only deps\n"
+        ));
+        assert!(dockerfile.contains("#   db: needs postgres-server\n"));
+        assert!(!dockerfile.contains("seed"));
+    }
+
+    #[test]
+    fn dockerfile_builds_template_steps_only_with_complete_inputs() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join(".bivvy")).unwrap();
+        for file in ["Gemfile", "Gemfile.lock", "package.json", "yarn.lock"] {
+            fs::write(temp.path().join(file), "").unwrap();
+        }
+        fs::write(
+            temp.path().join(".bivvy/config.yml"),
+            r#"
+steps:
+  gems:
+    template: bundle-install
+  lockfile-only:
+    template: yarn-install
+    check:
+      type: change
+      target: yarn.lock
+  node:
+    template: yarn-install
+    checks:
+      - type: change
+        target: package.json
+      - type: change
+        target: yarn.lock
+workflows:
+  default:
+    steps: [gems, lockfile-only, node]
+"#,
+        )
+        .unwrap();
+        let args = ExportArgs {
+            target: ExportTarget::Dockerfile(ExportDockerfileArgs {
+                workflow: None,
+                env: "docker".to_string(),
+                output: None,
+            }),
+        };
+        let mut ui = MockUI::new();
+
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(result.success);
+        let dockerfile = &ui.messages()[0];
+        assert!(dockerfile.contains(
+            "# node\nCOPY package.json yarn.lock ./\n\
+             RUN bivvy run --non-interactive --env docker --only node\n"
+        ));
+        assert!(dockerfile.contains("#   gems: has no file inputs to build on\n"));
+        assert!(dockerfile.contains("#   lockfile-only: reads package.json beyond its inputs"));
+        assert!(!dockerfile.contains("COPY Gemfile"));
+        assert!(!dockerfile.contains("--only gems"));
+    }
+
+    #[test]
+    fn devcontainer_refuses_to_overwrite() {
+        let temp = setup_project();
+        let args = ExportArgs {
+            target: ExportTarget::Devcontainer(ExportDevcontainerArgs {
+                workflow: None,
+                env: "codespace".to_string(),
+                output: PathBuf::from(".devcontainer"),
+                force: false,
+            }),
+        };
+
+        let mut ui = MockUI::new();
+        let result = ExportCommand::new(temp.path(), args.clone())
+            .execute(&mut ui)
+            .unwrap();
+        assert!(result.success);
+        let json = fs::read_to_string(temp.path().join(".devcontainer/devcontainer.json")).unwrap();
+        assert!(
+            json.contains("\"postCreateCommand\": \"bivvy run --non-interactive --env codespace\"")
+        );
+        assert!(temp.path().join(".devcontainer/Dockerfile").is_file());
+
+        let mut ui = MockUI::new();
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("already exists"));
+    }
+
+    #[test]
+    fn unknown_workflow_fails() {
+        let temp = setup_project();
+        let mut args = ci_args(ProviderArg::Github);
+        let ExportTarget::Ci(ci) = &mut args.target else {
+            unreachable!()
+        };
+        ci.workflow = Some("nope".to_string());
+        let mut ui = MockUI::new();
+
+        let result = ExportCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Unknown workflow: nope"));
+    }
+
+    #[test]
+    fn no_config_fails() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = ExportCommand::new(temp.path(), ci_args(ProviderArg::Github))
+            .execute(&mut ui)
+            .unwrap();
+
+        assert_eq!(result.exit_code, 2);
+    }
+}
@@ -0,0 +1,686 @@
+//! Hook command implementation.
+//!
+//! The `bivvy hook` command installs prompt and git hooks that report
+//! stale setup steps via `bivvy status --stale --quiet`.
+//!
+//! Usage:
+//!   bivvy hook print <shell>        # Print the prompt hook script
+//!   bivvy hook install <target>     # Add the hook to a shell rc file or git
+//!   bivvy hook uninstall <target>   # Remove a previously installed hook
+
+use std::path::{Path, PathBuf};
+
+use crate::error::{BivvyError, Result};
+use crate::ui::UserInterface;
+
+use super::dispatcher::{Command, CommandResult};
+
+/// Arguments for the `hook` command.
+#[derive(Debug, Clone, clap::Args)]
+pub struct HookArgs {
+    #[command(subcommand)]
+    pub action: HookAction,
+}
+
+/// Hook subcommands.
+#[derive(Debug, Clone, clap::Subcommand)]
+pub enum HookAction {
+    /// Print the prompt hook script for a shell
+    Print {
+        /// Shell to print the hook for
+        #[arg(value_enum)]
+        shell: HookShell,
+    },
+
+    /// Install the prompt hook into a shell rc file, or git hooks
+    Install {
+        /// Shell rc file or `git` for post-checkout/post-merge hooks
+        #[arg(value_enum)]
+        target: HookTarget,
+    },
+
+    /// Remove a previously installed hook
+    Uninstall {
+        /// Shell rc file or `git` for post-checkout/post-merge hooks
+        #[arg(value_enum)]
+        target: HookTarget,
+    },
+}
+
+/// Shells with a prompt hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HookShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Where a hook can be installed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HookTarget {
+    Bash,
+    Zsh,
+    Fish,
+    Git,
+}
+
+impl HookTarget {
+    fn shell(self) -> Option<HookShell> {
+        match self {
+            Self::Bash => Some(HookShell::Bash),
+            Self::Zsh => Some(HookShell::Zsh),
+            Self::Fish => Some(HookShell::Fish),
+            Self::Git => None,
+        }
+    }
+}
+
+const BLOCK_START: &str = "# >>> bivvy hook >>>";
+const BLOCK_END: &str = "# <<< bivvy hook <<<";
+
+/// Git hooks that trigger a stale check.
+const GIT_HOOKS: [&str; 2] = ["post-checkout", "post-merge"];
+
+const BASH_HOOK: &str = r#"_bivvy_prompt_hook() {
+  if [ ! -f .bivvy/config.yml ]; then
+    _BIVVY_LAST=
+    return
+  fi
+  local head=
+  [ -f .git/HEAD ] && read -r head < .git/HEAD
+  [ "$PWD:$head" = "${_BIVVY_LAST-}" ] && return
+  _BIVVY_LAST="$PWD:$head"
+  bivvy status --stale --quiet
+}
+case ";${PROMPT_COMMAND:-};" in
+  *";_bivvy_prompt_hook;"*) ;;
+  *) PROMPT_COMMAND="_bivvy_prompt_hook${PROMPT_COMMAND:+;$PROMPT_COMMAND}" ;;
+esac
+"#;
+
+const ZSH_HOOK: &str = r#"_bivvy_prompt_hook() {
+  if [[ ! -f .bivvy/config.yml ]]; then
+    _BIVVY_LAST=
+    return
+  fi
+  local head=
+  [[ -f .git/HEAD ]] && read -r head < .git/HEAD
+  [[ "$PWD:$head" == "${_BIVVY_LAST-}" ]] && return
+  _BIVVY_LAST="$PWD:$head"
+  bivvy status --stale --quiet
+}
+autoload -Uz add-zsh-hook
+add-zsh-hook precmd _bivvy_prompt_hook
+"#;
+
+const FISH_HOOK: &str = r#"function _bivvy_prompt_hook --on-event fish_prompt
+    if not test -f .bivvy/config.yml
+        set -g _bivvy_last
+        return
+    end
+    set -l head
+    test -f .git/HEAD; and read head < .git/HEAD
+    test "$PWD:$head" = "$_bivvy_last"; and return
+    set -g _bivvy_last "$PWD:$head"
+    bivvy status --stale --quiet
+end
+"#;
+
+/// The prompt hook script for a shell.
+///
+/// The hook runs the stale check when the prompt is shown in a project
+/// with a bivvy config, but only after changing directory or branch so
+/// the notice isn't repeated on every prompt.
+pub fn hook_script(shell: HookShell) -> &'static str {
+    match shell {
+        HookShell::Bash => BASH_HOOK,
+        HookShell::Zsh => ZSH_HOOK,
+        HookShell::Fish => FISH_HOOK,
+    }
+}
+
+/// The rc file a shell's prompt hook is installed into.
+pub fn rc_path(shell: HookShell, home: &Path) -> PathBuf {
+    match shell {
+        HookShell::Bash => home.join(".bashrc"),
+        HookShell::Zsh => std::env::var_os("ZDOTDIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| home.to_path_buf())
+            .join(".zshrc"),
+        HookShell::Fish => home.join(".config").join("fish").join("config.fish"),
+    }
+}
+
+/// The rc file line that loads the prompt hook.
+fn rc_line(shell: HookShell) -> &'static str {
+    match shell {
+        HookShell::Bash => r#"eval "$(bivvy hook print bash)""#,
+        HookShell::Zsh => r#"eval "$(bivvy hook print zsh)""#,
+        HookShell::Fish => "bivvy hook print fish | source",
+    }
+}
+
+/// The stale check run by a git hook.
+fn git_hook_body(hook: &str) -> &'static str {
+    if hook == "post-checkout" {
+        // $3 is 1 for branch checkouts and 0 for file checkouts.
+        "if [ \"$3\" = \"1\" ] && command -v bivvy >/dev/null 2>&1; then\n  bivvy status --stale --quiet || true\nfi"
+    } else {
+        "if command -v bivvy >/dev/null 2>&1; then\n  bivvy status --stale --quiet || true\nfi"
+    }
+}
+
+/// A marked block holding `body`.
+fn block(body: &str) -> String {
+    format!("{BLOCK_START}\n{body}\n{BLOCK_END}\n")
+}
+
+/// Append a marked block to `content`.
+///
+/// Returns `None` if a block is already present.
+pub fn add_block(content: &str, body: &str) -> Option<String> {
+    if content.contains(BLOCK_START) {
+        return None;
+    }
+    let mut out = content.to_string();
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    out.push_str(&block(body));
+    Some(out)
+}
+
+/// Why a block can't be appended to an existing git hook, if it can't.
+///
+/// Only `sh` and `bash` scripts are appended to, and only when their last
+/// command isn't `exit` or `exec`, which would skip anything after it.
+fn unappendable_hook(content: &str) -> Option<&'static str> {
+    let shebang = content.lines().next().unwrap_or_default();
+    let interpreter = shebang
+        .strip_prefix("#!")
+        .map(|s| s.split_whitespace().collect::<Vec<_>>())
+        .unwrap_or_default();
+    let program = match interpreter.as_slice() {
+        [env, program, ..] if env.ends_with("/env") => *program,
+        [program, ..] => program.rsplit('/').next().unwrap_or_default(),
+        [] => return Some("it has no #! line"),
+    };
+    if !matches!(program, "sh" | "bash") {
+        return Some("it isn't a sh or bash script");
+    }
+
+    let last = content
+        .lines()
+        .map(str::trim)
+        .rfind(|line| !line.is_empty() && !line.starts_with('#'))
+        .unwrap_or_default();
+    let first_word = last.split_whitespace().next().unwrap_or_default();
+    if matches!(first_word, "exit" | "exec") {
+        return Some("it ends with exit or exec");
+    }
+    None
+}
+
+/// Read a file to add a block to; `None` if it doesn't exist.
+fn read_existing(path: &Path) -> Result<Option<String>> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(BivvyError::Other(anyhow::anyhow!(
+            "Failed to read {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+/// Remove the marked block from `content`.
+///
+/// Returns `None` if no block is present.
+pub fn remove_block(content: &str) -> Option<String> {
+    let start = content.find(BLOCK_START)?;
+    let end = content[start..].find(BLOCK_END)? + start + BLOCK_END.len();
+    let before = content[..start].trim_end_matches('\n');
+    let after = content[end..].trim_start_matches('\n');
+    let mut out = before.to_string();
+    if !out.is_empty() {
+        out.push('\n');
+    }
+    if !after.is_empty() {
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(after);
+    }
+    Some(out)
+}
+
+/// The hook command implementation.
+pub struct HookCommand {
+    project_root: PathBuf,
+    args: HookArgs,
+}
+
+impl HookCommand {
+    /// Create a new hook command.
+    pub fn new(project_root: &Path, args: HookArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+        }
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &HookArgs {
+        &self.args
+    }
+
+    fn home() -> Result<PathBuf> {
+        crate::sys::home_dir()
+            .ok_or_else(|| BivvyError::Other(anyhow::anyhow!("Could not determine home directory")))
+    }
+
+    fn install_shell(&self, shell: HookShell, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let path = rc_path(shell, &Self::home()?);
+        let content = read_existing(&path)?.unwrap_or_default();
+        match add_block(&content, rc_line(shell)) {
+            Some(updated) => {
+                if let Some(parent) = path.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&path, updated)?;
+                ui.success(&format!("Installed prompt hook in {}", path.display()));
+                ui.message("  Open a new shell to start seeing stale step notices.");
+            }
+            None => ui.message(&format!(
+                "Prompt hook is already installed in {}",
+                path.display()
+            )),
+        }
+        Ok(CommandResult::success())
+    }
+
+    fn uninstall_shell(
+        &self,
+        shell: HookShell,
+        ui: &mut dyn UserInterface,
+    ) -> Result<CommandResult> {
+        let path = rc_path(shell, &Self::home()?);
+        let content = std::fs::read_to_string(&path).unwrap_or_default();
+        match remove_block(&content) {
+            Some(updated) => {
+                std::fs::write(&path, updated)?;
+                ui.success(&format!("Removed prompt hook from {}", path.display()));
+            }
+            None => ui.message(&format!("No prompt hook found in {}", path.display())),
+        }
+        Ok(CommandResult::success())
+    }
+
+    /// Resolve the git hooks directory, honoring `core.hooksPath`.
+    fn git_hooks_dir(&self) -> Option<PathBuf> {
+        let output = std::process::Command::new("git")
+            .args(["rev-parse", "--git-path", "hooks"])
+            .current_dir(&self.project_root)
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let dir = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Some(self.project_root.join(dir))
+    }
+
+    fn install_git(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let Some(hooks_dir) = self.git_hooks_dir() else {
+            ui.error("Not a git repository.");
+            return Ok(CommandResult::failure(1));
+        };
+        std::fs::create_dir_all(&hooks_dir)?;
+
+        let mut refused = false;
+        for hook in GIT_HOOKS {
+            let path = hooks_dir.join(hook);
+            let existing = read_existing(&path)?;
+            let content = existing.as_deref().unwrap_or("#!/bin/sh\n");
+            let Some(updated) = add_block(content, git_hook_body(hook)) else {
+                ui.message(&format!("{} hook is already installed", hook));
+                continue;
+            };
+            if let Some(reason) = existing.as_deref().and_then(unappendable_hook) {
+                ui.warning(&format!(
+                    "Not changing {} because {}. Add this to it by hand:",
+                    path.display(),
+                    reason
+                ));
+                ui.message(block(git_hook_body(hook)).trim_end());
+                refused = true;
+                continue;
+            }
+            std::fs::write(&path, updated)?;
+            make_executable(&path)?;
+            ui.success(&format!("Installed {} hook", hook));
+        }
+        if refused {
+            return Ok(CommandResult::failure(1));
+        }
+        Ok(CommandResult::success())
+    }
+
+    fn uninstall_git(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let Some(hooks_dir) = self.git_hooks_dir() else {
+            ui.error("Not a git repository.");
+            return Ok(CommandResult::failure(1));
+        };
+
+        for hook in GIT_HOOKS {
+            let path = hooks_dir.join(hook);
+            let content = std::fs::read_to_string(&path).unwrap_or_default();
+            let Some(updated) = remove_block(&content) else {
+                ui.message(&format!("No bivvy {} hook found", hook));
+                continue;
+            };
+            // Delete hooks that only held our block.
+            if updated.trim() == "#!/bin/sh" || updated.trim().is_empty() {
+                std::fs::remove_file(&path)?;
+            } else {
+                std::fs::write(&path, updated)?;
+            }
+            ui.success(&format!("Removed {} hook", hook));
+        }
+        Ok(CommandResult::success())
+    }
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+impl Command for HookCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        match self.args.action {
+            HookAction::Print { shell } => {
+                print!("{}", hook_script(shell));
+                Ok(CommandResult::success())
+            }
+            HookAction::Install { target } => match target.shell() {
+                Some(shell) => self.install_shell(shell, ui),
+                None => self.install_git(ui),
+            },
+            HookAction::Uninstall { target } => match target.shell() {
+                Some(shell) => self.uninstall_shell(shell, ui),
+                None => self.uninstall_git(ui),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::MockUI;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn git_init(path: &Path) {
+        let status = std::process::Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    fn hook_command(path: &Path, action: HookAction) -> HookCommand {
+        HookCommand::new(path, HookArgs { action })
+    }
+
+    #[test]
+    fn hook_scripts_run_quiet_stale_check() {
+        for shell in [HookShell::Bash, HookShell::Zsh, HookShell::Fish] {
+            let script = hook_script(shell);
+            assert!(script.contains("bivvy status --stale --quiet"));
+            assert!(script.contains(".bivvy/config.yml"));
+        }
+        assert!(hook_script(HookShell::Bash).contains("PROMPT_COMMAND"));
+        assert!(hook_script(HookShell::Zsh).contains("add-zsh-hook precmd"));
+        assert!(hook_script(HookShell::Fish).contains("--on-event fish_prompt"));
+    }
+
+    #[test]
+    fn add_block_appends_once() {
+        let added = add_block("export PATH=/bin", "eval x").unwrap();
+        assert_eq!(
+            added,
+            format!("export PATH=/bin\n\n{BLOCK_START}\neval x\n{BLOCK_END}\n")
+        );
+        assert!(add_block(&added, "eval x").is_none());
+    }
+
+    #[test]
+    fn remove_block_restores_surrounding_content() {
+        let added = add_block("alias ll='ls -l'\n", "eval x").unwrap();
+        assert_eq!(remove_block(&added).unwrap(), "alias ll='ls -l'\n");
+        assert!(remove_block("alias ll='ls -l'\n").is_none());
+    }
+
+    #[test]
+    fn remove_block_keeps_content_after_block() {
+        let content = format!("a\n\n{BLOCK_START}\neval x\n{BLOCK_END}\n\nb\n");
+        assert_eq!(remove_block(&content).unwrap(), "a\n\nb\n");
+    }
+
+    #[test]
+    fn rc_paths_per_shell() {
+        let home = Path::new("/home/dev");
+        assert_eq!(rc_path(HookShell::Bash, home), home.join(".bashrc"));
+        assert_eq!(
+            rc_path(HookShell::Fish, home),
+            home.join(".config/fish/config.fish")
+        );
+    }
+
+    #[test]
+    fn install_git_writes_executable_hooks() {
+        let temp = TempDir::new().unwrap();
+        git_init(temp.path());
+        let mut ui = MockUI::new();
+
+        let result = hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+
+        assert!(result.success);
+        let checkout = fs::read_to_string(temp.path().join(".git/hooks/post-checkout")).unwrap();
+        assert!(checkout.starts_with("#!/bin/sh\n"));
+        assert!(checkout.contains("[ \"$3\" = \"1\" ]"));
+        assert!(checkout.contains("bivvy status --stale --quiet"));
+        let merge = fs::read_to_string(temp.path().join(".git/hooks/post-merge")).unwrap();
+        assert!(merge.contains("bivvy status --stale --quiet"));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let meta = fs::metadata(temp.path().join(".git/hooks/post-merge")).unwrap();
+            assert_eq!(meta.permissions().mode() & 0o111, 0o111);
+        }
+    }
+
+    #[test]
+    fn install_git_preserves_existing_hook() {
+        let temp = TempDir::new().unwrap();
+        git_init(temp.path());
+        let hook = temp.path().join(".git/hooks/post-merge");
+        fs::write(&hook, "#!/bin/sh\nnpm install\n").unwrap();
+        let mut ui = MockUI::new();
+
+        hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+        hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+
+        let content = fs::read_to_string(&hook).unwrap();
+        assert!(content.starts_with("#!/bin/sh\nnpm install\n"));
+        assert_eq!(content.matches(BLOCK_START).count(), 1);
+        assert!(ui.has_message("already installed"));
+    }
+
+    #[test]
+    fn unappendable_hooks() {
+        assert_eq!(unappendable_hook("#!/bin/sh\nnpm install\n"), None);
+        assert_eq!(
+            unappendable_hook("#!/usr/bin/env bash\nset -e\nmake # done\n"),
+            None
+        );
+        assert_eq!(
+            unappendable_hook("#!/bin/sh\nexec lefthook run post-merge \"$@\"\n"),
+            Some("it ends with exit or exec")
+        );
+        assert_eq!(
+            unappendable_hook("#!/bin/bash\nnpm install\nexit 0\n\n"),
+            Some("it ends with exit or exec")
+        );
+        assert_eq!(
+            unappendable_hook("#!/usr/bin/env python3\nprint('hi')\n"),
+            Some("it isn't a sh or bash script")
+        );
+        assert_eq!(
+            unappendable_hook("npm install\n"),
+            Some("it has no #! line")
+        );
+    }
+
+    #[test]
+    fn install_git_refuses_hooks_it_cant_append_to() {
+        let temp = TempDir::new().unwrap();
+        git_init(temp.path());
+        let hook = temp.path().join(".git/hooks/post-merge");
+        let original = "#!/bin/sh\nexec husky post-merge\n";
+        fs::write(&hook, original).unwrap();
+        let mut ui = MockUI::new();
+
+        let result = hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert_eq!(fs::read_to_string(&hook).unwrap(), original);
+        assert!(ui.has_warning("because it ends with exit or exec. Add this to it by hand:"));
+        assert!(ui.has_message(&format!(
+            "{BLOCK_START}\n{}\n{BLOCK_END}",
+            git_hook_body("post-merge")
+        )));
+        // The other hook is still installed
+        let checkout = fs::read_to_string(temp.path().join(".git/hooks/post-checkout")).unwrap();
+        assert!(checkout.contains(BLOCK_START));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn install_git_fails_on_unreadable_hook() {
+        let temp = TempDir::new().unwrap();
+        git_init(temp.path());
+        // A directory can't be read as a file
+        fs::create_dir(temp.path().join(".git/hooks/post-checkout")).unwrap();
+        let mut ui = MockUI::new();
+
+        let err = hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap_err();
+
+        assert!(err.to_string().starts_with("Failed to read "));
+        assert!(temp.path().join(".git/hooks/post-checkout").is_dir());
+    }
+
+    #[test]
+    fn uninstall_git_removes_only_bivvy_hooks() {
+        let temp = TempDir::new().unwrap();
+        git_init(temp.path());
+        let merge = temp.path().join(".git/hooks/post-merge");
+        fs::write(&merge, "#!/bin/sh\nnpm install\n").unwrap();
+        let mut ui = MockUI::new();
+
+        hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+        hook_command(
+            temp.path(),
+            HookAction::Uninstall {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+
+        assert!(!temp.path().join(".git/hooks/post-checkout").exists());
+        assert_eq!(
+            fs::read_to_string(&merge).unwrap(),
+            "#!/bin/sh\nnpm install\n"
+        );
+    }
+
+    #[test]
+    fn install_git_outside_repository_fails() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = hook_command(
+            temp.path(),
+            HookAction::Install {
+                target: HookTarget::Git,
+            },
+        )
+        .execute(&mut ui)
+        .unwrap();
+
+        assert!(!result.success);
+        assert!(ui.has_error("Not a git repository"));
+    }
+}
@@ -2,16 +2,22 @@
 //!
 //! The `bivvy init` command initializes project configuration.
 
+use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
 use crate::cli::args::{InitArgs, RunArgs};
-use crate::detection::DetectionRunner;
+use crate::config::{load_merged_config, ConfigEditor};
+use crate::detection::{proposed_templates, DetectionRunner, FullDetection};
 use crate::error::Result;
+use crate::import::{self, SourceKind};
 use crate::registry::builtin::BuiltinLoader;
+use crate::registry::resolver::Registry;
 use crate::registry::template::Template;
+use crate::ui::tui::screens::init::{InitResult, InitScreen, Selection, StepTemplate};
 use crate::ui::{
-    hints, OutputWriter, Prompt, PromptOption, PromptResult, PromptType, UserInterface,
+    hints, line_diff, render_diff, tui, BivvyTheme, OutputWriter, Prompt, PromptOption,
+    PromptResult, PromptType, UserInterface,
 };
 
 use super::dispatcher::{Command, CommandResult};
@@ -47,6 +53,35 @@ impl InitCommand {
         self.project_root.join(".bivvy/config.yml").exists()
     }
 
+    /// Project name used in generated config.
+    fn project_name(&self) -> &str {
+        self.project_root
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("MyApp")
+    }
+
+    /// Load the template registry used to detect and describe steps.
+    ///
+    /// Includes remote `template_sources` when an existing or user-level
+    /// config declares them. Load failures are reported and detection
+    /// falls back to the built-in checks.
+    fn template_registry(&self, ui: &mut dyn UserInterface) -> Option<Registry> {
+        let registry = match load_merged_config(&self.project_root) {
+            Ok(config) if !config.template_sources.is_empty() => {
+                Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)
+            }
+            _ => Registry::new(Some(&self.project_root)),
+        };
+        match registry {
+            Ok(registry) => Some(registry),
+            Err(e) => {
+                ui.warning(&format!("Failed to load templates: {}", e));
+                None
+            }
+        }
+    }
+
     /// Resolve the absolute path to the user's local schema file.
     ///
     /// Returns `~/.bivvy/schema.json` if the home directory is discoverable.
@@ -66,11 +101,59 @@ impl InitCommand {
         steps: &[(&str, Option<&Template>)],
         schema_path: Option<&Path>,
     ) -> String {
-        let project_name = self
-            .project_root
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("MyApp");
+        self.create_config_with_inputs(steps, &HashMap::new(), schema_path)
+    }
+
+    /// Create configuration content, setting `inputs:` on steps that have
+    /// entries in `inputs`.
+    fn create_config_with_inputs(
+        &self,
+        steps: &[(&str, Option<&Template>)],
+        inputs: &HashMap<&str, &[(String, serde_yaml::Value)]>,
+        schema_path: Option<&Path>,
+    ) -> String {
+        let mut config = self.config_header(schema_path);
+
+        if !steps.is_empty() {
+            config.push_str("\nsteps:\n");
+
+            let step_names: Vec<&str> = steps.iter().map(|(name, _)| *name).collect();
+
+            for (name, template) in steps {
+                config.push_str(&format!("  {}:\n    template: {}\n", name, name));
+
+                let step_inputs = inputs.get(name).copied().unwrap_or_default();
+                if !step_inputs.is_empty() {
+                    config.push_str("    inputs:\n");
+                    for (input, value) in step_inputs {
+                        let value = serde_yaml::to_string(value).unwrap_or_default();
+                        config.push_str(&format!("      {}: {}\n", input, value.trim_end()));
+                    }
+                }
+
+                if let Some(tmpl) = template {
+                    // Show command
+                    if let Some(ref cmd) = tmpl.step.command {
+                        config.push_str(&format!("    # command: {}\n", cmd));
+                    }
+
+                    // Template details are shown as command comment above
+                }
+
+                config.push('\n');
+            }
+
+            config.push_str("workflows:\n  default:\n    steps: ");
+            config.push_str(&format!("[{}]\n", step_names.join(", ")));
+        }
+
+        config
+    }
+
+    /// Configuration header: schema directive, usage comments, app name
+    /// and default settings.
+    fn config_header(&self, schema_path: Option<&Path>) -> String {
+        let project_name = self.project_name();
 
         let mut config = String::new();
 
@@ -116,31 +199,105 @@ impl InitCommand {
              \x20   output: verbose  # verbose | quiet | silent\n"
         ));
 
-        if !steps.is_empty() {
-            config.push_str("\nsteps:\n");
-
-            let step_names: Vec<&str> = steps.iter().map(|(name, _)| *name).collect();
+        config
+    }
 
-            for (name, template) in steps {
-                config.push_str(&format!("  {}:\n    template: {}\n", name, name));
+    /// Create configuration content for selected templates.
+    fn config_for(
+        &self,
+        selections: &[Selection],
+        registry: Option<&Registry>,
+        schema_path: Option<&Path>,
+    ) -> String {
+        let steps: Vec<(&str, Option<&Template>)> = selections
+            .iter()
+            .map(|s| (s.name.as_str(), registry.and_then(|r| r.get(&s.name))))
+            .collect();
+        let inputs = selections
+            .iter()
+            .map(|s| (s.name.as_str(), s.inputs.as_slice()))
+            .collect();
+        self.create_config_with_inputs(&steps, &inputs, schema_path)
+    }
 
-                if let Some(tmpl) = template {
-                    // Show command
-                    if let Some(ref cmd) = tmpl.step.command {
-                        config.push_str(&format!("    # command: {}\n", cmd));
-                    }
+    /// Pick templates and set their inputs on the full-screen init screen.
+    ///
+    /// Returns `None` if the user cancelled.
+    fn select_fullscreen(
+        &self,
+        detection: &FullDetection,
+        suggestions: &[(String, String)],
+        registry: Option<&Registry>,
+    ) -> std::io::Result<Option<Vec<Selection>>> {
+        let schema = Self::schema_path();
+        let mut screen = InitScreen::new()
+            .project_name(self.project_name())
+            .preview(|selections| self.config_for(selections, registry, schema.as_deref()));
 
-                    // Template details are shown as command comment above
-                }
+        for result in detection
+            .project
+            .details
+            .iter()
+            .chain(&detection.package_managers.details)
+        {
+            screen.add_detection(result.into());
+        }
+        for (name, reason) in suggestions {
+            let template = StepTemplate::new_selected(name, reason);
+            screen.add_step_template(match registry.and_then(|r| r.get(name)) {
+                Some(t) => template.with_inputs(&t.inputs),
+                None => template,
+            });
+        }
 
-                config.push('\n');
-            }
+        tui::run(&mut screen)?;
+        Ok(match screen.result() {
+            InitResult::Confirmed(_) => Some(screen.selections()),
+            InitResult::Cancelled => None,
+        })
+    }
 
-            config.push_str("workflows:\n  default:\n    steps: ");
-            config.push_str(&format!("[{}]\n", step_names.join(", ")));
+    /// Pick templates with a multi-select prompt.
+    ///
+    /// Selects every suggestion with `--minimal` or without a terminal.
+    fn select_with_prompts(
+        &self,
+        ui: &mut dyn UserInterface,
+        suggestions: &[(String, String)],
+    ) -> Vec<String> {
+        if self.args.minimal || !ui.is_interactive() {
+            return suggestions.iter().map(|(name, _)| name.clone()).collect();
+        }
+        if suggestions.is_empty() {
+            return Vec::new();
         }
 
-        config
+        // Interactive multi-select checklist
+        let options: Vec<PromptOption> = suggestions
+            .iter()
+            .map(|(name, reason)| PromptOption {
+                label: format!("{} — {}", name, reason),
+                value: name.clone(),
+            })
+            .collect();
+
+        let all_values = suggestions
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let prompt = Prompt {
+            key: "init_steps".to_string(),
+            question: "Select steps to include".to_string(),
+            prompt_type: PromptType::MultiSelect { options },
+            default: Some(all_values),
+        };
+
+        match ui.prompt(&prompt) {
+            Ok(PromptResult::Strings(selected)) => selected,
+            _ => Vec::new(),
+        }
     }
 
     /// Execute `--from`: copy config from another project.
@@ -169,6 +326,101 @@ impl InitCommand {
         Ok(CommandResult::success())
     }
 
+    /// Execute `--import`: build steps from an existing setup source.
+    ///
+    /// Shows the generated config as a diff against the current one and,
+    /// when interactive, asks before writing it.
+    fn execute_import(&self, ui: &mut dyn UserInterface, path: &Path) -> Result<CommandResult> {
+        let source = self.project_root.join(path);
+        let content = match fs::read_to_string(&source) {
+            Ok(content) => content,
+            Err(e) => {
+                ui.error(&format!("Failed to read {}: {}", path.display(), e));
+                return Ok(CommandResult::failure(1));
+            }
+        };
+
+        let kind = SourceKind::detect(&source, &content);
+        let parsed = match import::parse(kind, &content) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                ui.error(&e.to_string());
+                return Ok(CommandResult::failure(1));
+            }
+        };
+        if parsed.commands.is_empty() {
+            ui.error(&format!("No setup commands found in {}", path.display()));
+            return Ok(CommandResult::failure(1));
+        }
+
+        ui.message(&format!("Importing {} ({})", path.display(), kind.label()));
+        ui.message("");
+
+        let registry = self.template_registry(ui);
+        let steps = import::build_steps(&parsed.commands, registry.as_ref());
+        let schema = Self::schema_path();
+        let mut editor = ConfigEditor::new(self.config_header(schema.as_deref()))?;
+        import::write_steps(&mut editor, &steps)?;
+        let config = editor.into_string();
+
+        let config_path = self.project_root.join(".bivvy/config.yml");
+        let current = fs::read_to_string(&config_path).unwrap_or_default();
+        ui.message(&render_diff(
+            &line_diff(&current, &config),
+            &BivvyTheme::new(),
+        ));
+
+        if !parsed.skipped.is_empty() {
+            ui.warning(&format!(
+                "{} line(s) weren't imported; move them into steps by hand:",
+                parsed.skipped.len()
+            ));
+            for line in &parsed.skipped {
+                ui.message(&format!("  {}", line.lines().next().unwrap_or_default()));
+            }
+            ui.message("");
+        }
+
+        if ui.is_interactive() {
+            let prompt = Prompt {
+                key: "write_import".to_string(),
+                question: "Write .bivvy/config.yml?".to_string(),
+                prompt_type: PromptType::Confirm,
+                default: Some("true".to_string()),
+            };
+
+            match ui.prompt(&prompt)? {
+                PromptResult::Bool(true) => {}
+                _ => {
+                    ui.message("Cancelled");
+                    return Ok(CommandResult::success());
+                }
+            }
+        }
+
+        fs::create_dir_all(self.project_root.join(".bivvy"))?;
+        fs::write(&config_path, &config)?;
+
+        self.update_gitignore(ui)?;
+
+        ui.message("");
+        ui.success("Created .bivvy/config.yml");
+
+        let step_names: Vec<&str> = steps.iter().map(|s| s.name.as_str()).collect();
+        let templated = steps.iter().filter(|s| s.template.is_some()).count();
+        ui.message("Workflow: default");
+        ui.message(&format!(
+            "Steps: {} ({})",
+            steps.len(),
+            step_names.join(", ")
+        ));
+        ui.message(&format!("Matched to templates: {}", templated));
+        ui.message("");
+
+        ui.show_hint(hints::after_init());
+        Ok(CommandResult::success())
+    }
+
     /// Execute `--template`: generate config from a specific template or category.
     fn execute_template(
         &self,
@@ -295,6 +547,11 @@ impl Command for InitCommand {
             return self.execute_from(ui, from_path);
         }
 
+        // Handle --import: turn an existing setup script into steps
+        if let Some(ref import_path) = self.args.import {
+            return self.execute_import(ui, import_path);
+        }
+
         // Handle --template: use a specific template instead of detection
         if let Some(ref template_name) = self.args.template {
             return self.execute_template(ui, template_name);
@@ -302,9 +559,40 @@ impl Command for InitCommand {
 
         // Run detection
         let detection = DetectionRunner::run(&self.project_root);
+        let registry = self.template_registry(ui);
+
+        if let Some(ref registry) = registry {
+            for warning in registry.detector_warnings() {
+                ui.warning(warning);
+            }
+        }
+
+        // Built-in suggestions, then project, user and remote templates
+        // whose detectors match
+        let suggestions: Vec<(String, String)> =
+            proposed_templates(&detection, &self.project_root, registry.as_ref())
+                .into_iter()
+                .map(|p| (p.name, p.reason))
+                .collect();
+
+        // Full-screen picker when the terminal supports it
+        let mut selections = None;
+        if !self.args.minimal && ui.supports_fullscreen() {
+            match self.select_fullscreen(&detection, &suggestions, registry.as_ref()) {
+                Ok(Some(chosen)) => selections = Some(chosen),
+                Ok(None) => {
+                    ui.message("Cancelled");
+                    return Ok(CommandResult::success());
+                }
+                Err(e) => ui.warning(&format!(
+                    "Full-screen init unavailable ({}); using prompts",
+                    e
+                )),
+            }
+        }
 
-        // Show detected technologies
-        if !detection.project.details.is_empty() {
+        // Show detected technologies (the full-screen picker already did)
+        if selections.is_none() && !detection.project.details.is_empty() {
             ui.message("Detected technologies:");
             for detail in &detection.project.details {
                 ui.success(&format!("{} - {}", detail.name, detail.details.join(", ")));
@@ -320,56 +608,22 @@ impl Command for InitCommand {
         }
 
         // Collect steps to include
-        let mut steps = Vec::new();
-
-        if self.args.minimal || !ui.is_interactive() {
-            // Just use detected templates
-            for suggestion in &detection.suggested_templates {
-                steps.push(suggestion.name.to_string());
-            }
-        } else if !detection.suggested_templates.is_empty() {
-            // Interactive multi-select checklist
-            let options: Vec<PromptOption> = detection
-                .suggested_templates
-                .iter()
-                .map(|s| PromptOption {
-                    label: format!("{} — {}", s.name, s.reason),
-                    value: s.name.to_string(),
+        let selections = match selections {
+            Some(selections) => selections,
+            None => self
+                .select_with_prompts(ui, &suggestions)
+                .into_iter()
+                .map(|name| Selection {
+                    name,
+                    inputs: Vec::new(),
                 })
-                .collect();
-
-            let all_values = detection
-                .suggested_templates
-                .iter()
-                .map(|s| s.name.as_str())
-                .collect::<Vec<_>>()
-                .join(",");
-
-            let prompt = Prompt {
-                key: "init_steps".to_string(),
-                question: "Select steps to include".to_string(),
-                prompt_type: PromptType::MultiSelect { options },
-                default: Some(all_values),
-            };
-
-            if let Ok(PromptResult::Strings(selected)) = ui.prompt(&prompt) {
-                steps = selected;
-            }
-        }
-
-        // Load templates to enrich config output
-        let loader = BuiltinLoader::new().ok();
-        let steps_with_templates: Vec<(&str, Option<&Template>)> = steps
-            .iter()
-            .map(|name| {
-                let template = loader.as_ref().and_then(|l| l.get(name));
-                (name.as_str(), template)
-            })
-            .collect();
+                .collect(),
+        };
+        let steps: Vec<String> = selections.iter().map(|s| s.name.clone()).collect();
 
-        // Generate config
+        // Generate config, using the loaded templates to enrich it
         let schema = Self::schema_path();
-        let config = self.create_config(&steps_with_templates, schema.as_deref());
+        let config = self.config_for(&selections, registry.as_ref(), schema.as_deref());
 
         // Write config
         let bivvy_dir = self.project_root.join(".bivvy");
@@ -493,6 +747,36 @@ mod tests {
         assert!(config.contains("# Override any template field per-step:"));
     }
 
+    #[test]
+    fn config_for_sets_edited_inputs() {
+        let temp = TempDir::new().unwrap();
+        let cmd = InitCommand::new(temp.path(), InitArgs::default());
+
+        let selections = vec![
+            Selection {
+                name: "node-version-bump".to_string(),
+                inputs: vec![("bump".to_string(), serde_yaml::Value::from("minor"))],
+            },
+            Selection {
+                name: "yarn-install".to_string(),
+                inputs: Vec::new(),
+            },
+        ];
+        let config = cmd.config_for(&selections, None, None);
+
+        assert!(config.contains(
+            "  node-version-bump:\n    template: node-version-bump\n    inputs:\n      bump: minor\n"
+        ));
+        assert!(config.contains("  yarn-install:\n    template: yarn-install\n\n"));
+
+        let parsed: crate::config::BivvyConfig = serde_yaml::from_str(&config).unwrap();
+        let step = &parsed.steps["node-version-bump"];
+        assert_eq!(
+            step.inputs.get("bump"),
+            Some(&serde_yaml::Value::from("minor"))
+        );
+    }
+
     #[test]
     fn create_config_with_template_check() {
         let temp = TempDir::new().unwrap();
@@ -715,6 +999,64 @@ mod tests {
         assert_eq!(count, 1);
     }
 
+    #[test]
+    fn init_suggests_project_template_with_project_detector() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(bivvy_dir.join("templates/steps")).unwrap();
+        fs::write(
+            bivvy_dir.join("detectors.yml"),
+            "detectors:\n  platform-config:\n    file: platform.yml\n",
+        )
+        .unwrap();
+        fs::write(
+            bivvy_dir.join("templates/steps/platform-lint.yml"),
+            "name: platform-lint\n\
+             description: Lint platform config\n\
+             category: platform\n\
+             detectors: [platform-config.file]\n\
+             step:\n  command: platform lint\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("platform.yml"), "team: core\n").unwrap();
+
+        let cmd = InitCommand::new(temp.path(), InitArgs::default());
+        let mut ui = MockUI::new();
+        cmd.execute(&mut ui).unwrap();
+
+        let config = fs::read_to_string(bivvy_dir.join("config.yml")).unwrap();
+        assert!(config.contains("template: platform-lint"));
+        assert!(config.contains("# command: platform lint"));
+    }
+
+    #[test]
+    fn init_skips_project_template_when_detector_fails() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(bivvy_dir.join("templates/steps")).unwrap();
+        fs::write(
+            bivvy_dir.join("detectors.yml"),
+            "detectors:\n  platform-config:\n    file: platform.yml\n",
+        )
+        .unwrap();
+        fs::write(
+            bivvy_dir.join("templates/steps/platform-lint.yml"),
+            "name: platform-lint\n\
+             description: Lint platform config\n\
+             category: platform\n\
+             detectors: [platform-config.file]\n\
+             step:\n  command: platform lint\n",
+        )
+        .unwrap();
+
+        let cmd = InitCommand::new(temp.path(), InitArgs::default());
+        let mut ui = MockUI::new();
+        cmd.execute(&mut ui).unwrap();
+
+        let config = fs::read_to_string(bivvy_dir.join("config.yml")).unwrap();
+        assert!(!config.contains("platform-lint"));
+    }
+
     #[test]
     fn init_interactive_uses_multiselect() {
         let temp = TempDir::new().unwrap();
@@ -860,4 +1202,145 @@ mod tests {
         let _result = cmd.execute(&mut ui);
         assert!(ui.prompts_shown().contains(&"run_after_init".to_string()));
     }
+
+    #[test]
+    fn init_import_writes_steps_from_setup_script() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("bin")).unwrap();
+        fs::write(
+            temp.path().join("bin/setup"),
+            "#!/bin/bash\nset -e\n\n# Install dependencies\nbundle install\n\n# Seed data\nbin/seed --demo\n",
+        )
+        .unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("bin/setup")),
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui.has_message("Importing bin/setup (shell script)"));
+        assert!(ui.has_message("+   bundle-install:"));
+
+        let config = fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap();
+        assert!(config.contains("app_name:"));
+        assert!(config.contains("  bundle-install:\n    template: bundle-install\n"));
+        assert!(
+            config.contains("  seed-data:\n    title: Seed data\n    command: bin/seed --demo\n")
+        );
+        assert!(config.contains("    depends_on: [bundle-install]\n"));
+        assert!(config.contains("steps: [bundle-install, seed-data]"));
+
+        let parsed: crate::config::BivvyConfig = serde_yaml::from_str(&config).unwrap();
+        assert_eq!(parsed.steps.len(), 2);
+    }
+
+    #[test]
+    fn init_import_reports_skipped_lines() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("setup.sh"),
+            "export RAILS_ENV=development\nbin/rails db:prepare\n",
+        )
+        .unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("setup.sh")),
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        cmd.execute(&mut ui).unwrap();
+
+        assert!(ui.has_warning("1 line(s) weren't imported"));
+        assert!(ui.has_message("  export RAILS_ENV=development"));
+    }
+
+    #[test]
+    fn init_import_declined_writes_nothing() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"scripts": {"setup": "npm install"}}"#,
+        )
+        .unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("package.json")),
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+        ui.set_interactive(true);
+        ui.set_prompt_response("write_import", "false");
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui.prompts_shown().contains(&"write_import".to_string()));
+        assert!(!temp.path().join(".bivvy/config.yml").exists());
+    }
+
+    #[test]
+    fn init_import_diffs_against_existing_config() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(bivvy_dir.join("config.yml"), "app_name: old\n").unwrap();
+        fs::write(temp.path().join("Makefile"), "setup:\n\tnpm install\n").unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("Makefile")),
+            force: true,
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui.has_message("- app_name: old"));
+    }
+
+    #[test]
+    fn init_import_missing_file_fails() {
+        let temp = TempDir::new().unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("bin/setup")),
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Failed to read bin/setup"));
+        assert!(!temp.path().join(".bivvy/config.yml").exists());
+    }
+
+    #[test]
+    fn init_import_without_setup_target_fails() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Makefile"), "build:\n\tcargo build\n").unwrap();
+
+        let args = InitArgs {
+            import: Some(PathBuf::from("Makefile")),
+            ..Default::default()
+        };
+        let cmd = InitCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("No setup, bootstrap, install target found"));
+    }
 }
@@ -8,14 +8,14 @@ use std::path::{Path, PathBuf};
 use crate::cli::args::LintArgs;
 use crate::config::{
     load_config, load_merged_config, load_project_config, load_single_step_file,
-    load_single_workflow_file, BivvyConfig, ConfigPaths, Discovery,
+    load_single_workflow_file, BivvyConfig, ConfigEditor, ConfigPaths, Discovery,
 };
 use crate::error::{BivvyError, Result};
 use crate::lint::{
     CircularRequirementDepRule, Fix, FixEngine, HumanFormatter, InstallTemplateMissingRule,
     JsonFormatter, LintDiagnostic, LintFormatter, RuleRegistry, SarifFormatter,
-    ServiceRequirementWithoutHintRule, Severity, TemplateInputsRule, UndefinedTemplateRule,
-    UnknownRequirementRule,
+    ServiceRequirementWithoutHintRule, Severity, TemplateInputsRule, UndefinedDetectorRule,
+    UndefinedTemplateRule, UnknownRequirementRule, UnknownVariableRule,
 };
 use crate::registry::Registry;
 use crate::requirements::registry::RequirementRegistry;
@@ -122,6 +122,19 @@ impl LintCommand {
     }
 
     /// Build the [`BivvyConfig`] view to lint plus the source paths it draws from.
+    /// The first of `paths` whose YAML has a value at `path`.
+    fn file_containing(paths: &[PathBuf], path: &[String]) -> Option<PathBuf> {
+        paths
+            .iter()
+            .find(|file| {
+                std::fs::read_to_string(file)
+                    .ok()
+                    .and_then(|content| ConfigEditor::new(content).ok())
+                    .is_some_and(|editor| editor.contains(path))
+            })
+            .cloned()
+    }
+
     fn build_target_config(&self, target: &LintTarget) -> Result<(BivvyConfig, Vec<PathBuf>)> {
         if let Some(ref override_path) = self.config_override {
             // Explicit override: just load that file in isolation.
@@ -326,7 +339,7 @@ impl Command for LintCommand {
 
         // Build the BivvyConfig view to lint along with the file paths
         // we actually consulted (used for raw-YAML deprecation scanning).
-        let (config, lint_file_paths) = match self.build_target_config(&target) {
+        let (mut config, lint_file_paths) = match self.build_target_config(&target) {
             Ok(pair) => pair,
             Err(BivvyError::ConfigParseError { path, message }) => {
                 ui.error(&format!("Parse error in {}: {}", path.display(), message));
@@ -391,7 +404,14 @@ impl Command for LintCommand {
             rule_registry.register(Box::new(UndefinedTemplateRule::new(
                 template_registry.clone(),
             )));
-            rule_registry.register(Box::new(TemplateInputsRule::new(template_registry)));
+            rule_registry.register(Box::new(TemplateInputsRule::new(template_registry.clone())));
+            rule_registry.register(Box::new(UndefinedDetectorRule::new(
+                template_registry.clone(),
+            )));
+            rule_registry.register(Box::new(UnknownVariableRule::new(
+                template_registry,
+                &self.project_root,
+            )));
         }
 
         // Add requirement-related rules
@@ -415,26 +435,29 @@ impl Command for LintCommand {
         if self.args.fix {
             let fixes: Vec<Fix> = diagnostics
                 .iter()
-                .filter_map(|d| {
-                    // Only create fixes for diagnostics that have suggestions and spans
-                    match (&d.suggestion, &d.span) {
-                        (Some(suggestion), Some(span)) => Some(Fix {
-                            file: span.file.clone(),
-                            start: 0, // Would need actual byte offsets from marked_yaml
-                            end: 0,
-                            replacement: suggestion.clone(),
-                        }),
-                        _ => None,
-                    }
+                .filter_map(|d| d.fix.as_ref())
+                .filter_map(|edit| {
+                    // Fix the first linted file that has the edited path
+                    let file = Self::file_containing(&lint_file_paths, edit.path())?;
+                    Some(Fix {
+                        file,
+                        edit: edit.clone(),
+                    })
                 })
                 .collect();
 
             if !fixes.is_empty() {
                 let engine = FixEngine::new();
                 let result = engine.apply_fixes(&diagnostics, &fixes);
+                for error in &result.errors {
+                    ui.error(&format!("Couldn't apply fixes to {}", error));
+                }
                 if result.applied > 0 {
-                    ui.success(&format!("Applied {} fix(es)", result.applied));
-                    // Re-run rules after fixes
+                    if self.args.format == "human" {
+                        ui.success(&format!("Applied {} fix(es)", result.applied));
+                    }
+                    // Re-run rules against the fixed files
+                    (config, _) = self.build_target_config(&target)?;
                     diagnostics = self.run_rules(&rule_registry, &config);
                 }
             }
@@ -646,6 +669,26 @@ workflows:
         assert!(!result.success);
     }
 
+    #[test]
+    fn lint_fix_removes_self_dependency() {
+        let config = "app_name: test-app\nsteps:\n  a:\n    command: echo a\n    # keep this\n    depends_on: [a]\nworkflows:\n  default:\n    steps: [a]\n";
+        let temp = setup_project(config);
+        let args = LintArgs {
+            fix: true,
+            format: "human".to_string(),
+            ..Default::default()
+        };
+        let cmd = LintCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui.successes().iter().any(|m| m == "Applied 1 fix(es)"));
+        let fixed = std::fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap();
+        assert!(fixed.contains("    # keep this\n    depends_on: []\n"));
+    }
+
     #[test]
     fn lint_detects_undefined_dependency() {
         let config = r#"
@@ -12,17 +12,25 @@
 //! - Consistent global flag handling
 
 pub mod add;
+pub mod affected;
 pub mod cache;
 pub mod completions;
 pub mod config;
+pub mod detect;
+pub mod diagnostics;
 pub mod dispatcher;
 pub mod display;
+pub mod eject;
+pub mod env;
+pub mod export;
 pub mod feedback;
 pub mod history;
+pub mod hook;
 pub mod init;
 pub mod last;
 pub mod lint;
 pub mod list;
+pub mod remove;
 pub mod run;
 pub mod schema;
 pub mod snapshot;
@@ -0,0 +1,807 @@
+//! Remove command implementation.
+//!
+//! The `bivvy remove` command deletes a step and every reference to it:
+//! other steps' `depends_on` (including per-environment overrides) and
+//! `satisfied_when` refs, and workflow `steps`, `force` and `overrides`.
+//! Steps that depended on the removed step can be reconnected to its
+//! dependencies. The step's saved state is cleared unless `--keep-state`
+//! is passed.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde_yaml::Value;
+
+use crate::cli::args::RemoveArgs;
+use crate::config::{parse_config, ConfigEditor, Discovery};
+use crate::error::Result;
+use crate::snapshots::SnapshotStore;
+use crate::state::{
+    FreshnessIndex, ProjectId, ProjectLock, SatisfactionCache, StateStore, WaitPolicy,
+};
+use crate::ui::{Prompt, PromptResult, PromptType, UserInterface};
+
+use super::dispatcher::{Command, CommandResult};
+
+/// The remove command implementation.
+pub struct RemoveCommand {
+    project_root: PathBuf,
+    args: RemoveArgs,
+    config_override: Option<PathBuf>,
+}
+
+/// How a config file lays out steps and workflows.
+#[derive(Debug, Clone, PartialEq)]
+enum DocumentKind {
+    /// `.bivvy/config.yml` or `.bivvy/config.local.yml`: `steps:` and
+    /// `workflows:` maps.
+    Config,
+    /// `.bivvy/workflows/<name>.yml`: `steps:` and a single `workflow:`.
+    WorkflowFile,
+    /// `.bivvy/steps/<name>.yml`: one step's fields at the root.
+    StepFile(String),
+}
+
+/// A config file being edited.
+struct Document {
+    path: PathBuf,
+    kind: DocumentKind,
+    original: String,
+    editor: ConfigEditor,
+}
+
+impl Document {
+    fn load(path: PathBuf, kind: DocumentKind) -> Result<Self> {
+        let original = fs::read_to_string(&path)?;
+        let editor = ConfigEditor::new(original.clone())?;
+        Ok(Self {
+            path,
+            kind,
+            original,
+            editor,
+        })
+    }
+
+    /// Steps defined in the file, with the path to each step's fields.
+    fn steps(&self) -> Vec<(String, Vec<String>)> {
+        match self.kind {
+            DocumentKind::StepFile(ref name) => vec![(name.clone(), Vec::new())],
+            _ => keys(&self.editor, &["steps"])
+                .into_iter()
+                .map(|name| (name.clone(), vec!["steps".to_string(), name]))
+                .collect(),
+        }
+    }
+
+    /// Paths to the workflows declared in the file.
+    fn workflows(&self) -> Vec<Vec<String>> {
+        match self.kind {
+            DocumentKind::Config => keys(&self.editor, &["workflows"])
+                .into_iter()
+                .map(|name| vec!["workflows".to_string(), name])
+                .collect(),
+            DocumentKind::WorkflowFile if self.editor.contains(&["workflow"]) => {
+                vec![vec!["workflow".to_string()]]
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the file defines (or overrides) the step.
+    fn defines(&self, step: &str) -> bool {
+        self.steps().iter().any(|(name, _)| name == step)
+    }
+
+    /// The step's own dependencies, if the file defines them.
+    fn dependencies_of(&self, step: &str) -> Option<Vec<String>> {
+        let (_, base) = self.steps().into_iter().find(|(name, _)| name == step)?;
+        match self.editor.get(&join(&base, &["depends_on"]))? {
+            Value::Sequence(items) => Some(strings(&items)),
+            _ => None,
+        }
+    }
+
+    /// Steps whose dependencies include `step`.
+    fn dependents_of(&self, step: &str) -> Vec<String> {
+        self.steps()
+            .into_iter()
+            .filter(|(name, base)| {
+                name != step
+                    && dependency_lists(&self.editor, base)
+                        .iter()
+                        .any(|list| list_contains(&self.editor, list, step))
+            })
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Remove the step's definition and every reference to it, making
+    /// dependents depend on `reconnect_to` instead. Returns the paths that
+    /// changed.
+    fn remove_step(&mut self, step: &str, reconnect_to: &[String]) -> Result<Vec<String>> {
+        let mut changed = Vec::new();
+
+        if !matches!(self.kind, DocumentKind::StepFile(_)) {
+            self.editor.remove(&["steps", step])?;
+        }
+
+        for (name, base) in self.steps() {
+            if name == step {
+                continue;
+            }
+            let replacement: Vec<String> = reconnect_to
+                .iter()
+                .filter(|dep| **dep != name)
+                .cloned()
+                .collect();
+            for list in dependency_lists(&self.editor, &base) {
+                if replace_in_list(&mut self.editor, &list, step, &replacement)? {
+                    changed.push(list.join("."));
+                }
+            }
+
+            let satisfied_when = join(&base, &["satisfied_when"]);
+            if remove_satisfaction_refs(&mut self.editor, &satisfied_when, step)? {
+                changed.push(satisfied_when.join("."));
+            }
+        }
+
+        for workflow in self.workflows() {
+            // An empty `steps` list stays; empty `force` lists are dropped
+            let steps = join(&workflow, &["steps"]);
+            if remove_from_list(&mut self.editor, &steps, step, false)? {
+                changed.push(steps.join("."));
+            }
+            let force = join(&workflow, &["force"]);
+            if remove_from_list(&mut self.editor, &force, step, true)? {
+                changed.push(force.join("."));
+            }
+
+            let overrides = join(&workflow, &["overrides"]);
+            if self.editor.remove(&join(&overrides, &[step]))? {
+                if keys(&self.editor, &overrides).is_empty() {
+                    self.editor.remove(&overrides)?;
+                }
+                changed.push(overrides.join("."));
+            }
+        }
+
+        Ok(changed)
+    }
+
+    fn is_changed(&self) -> bool {
+        self.editor.as_str() != self.original
+    }
+}
+
+/// Keys of the mapping at `path`.
+fn keys<S: AsRef<str>>(editor: &ConfigEditor, path: &[S]) -> Vec<String> {
+    match editor.get(path) {
+        Some(Value::Mapping(mapping)) => mapping
+            .keys()
+            .filter_map(|k| k.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn join(base: &[String], rest: &[&str]) -> Vec<String> {
+    base.iter()
+        .cloned()
+        .chain(rest.iter().map(|s| s.to_string()))
+        .collect()
+}
+
+fn strings(items: &[Value]) -> Vec<String> {
+    items
+        .iter()
+        .filter_map(|v| v.as_str().map(str::to_string))
+        .collect()
+}
+
+/// A step's `depends_on` and its per-environment `depends_on` overrides.
+fn dependency_lists(editor: &ConfigEditor, base: &[String]) -> Vec<Vec<String>> {
+    let environments = join(base, &["environments"]);
+    let mut lists = vec![join(base, &["depends_on"])];
+    lists.extend(
+        keys(editor, &environments)
+            .iter()
+            .map(|env| join(&environments, &[env, "depends_on"])),
+    );
+    lists
+}
+
+fn list_contains(editor: &ConfigEditor, path: &[String], item: &str) -> bool {
+    matches!(editor.get(path), Some(Value::Sequence(items)) if strings(&items).iter().any(|s| s == item))
+}
+
+/// Remove `item` from the list at `path`. With `drop_empty`, a list left
+/// empty is removed entirely.
+fn remove_from_list(
+    editor: &mut ConfigEditor,
+    path: &[String],
+    item: &str,
+    drop_empty: bool,
+) -> Result<bool> {
+    let Some(Value::Sequence(items)) = editor.get(path) else {
+        return Ok(false);
+    };
+    if !strings(&items).iter().any(|s| s == item) {
+        return Ok(false);
+    }
+    if drop_empty && items.iter().all(|v| v.as_str() == Some(item)) {
+        editor.remove(path)?;
+    } else {
+        editor.remove_item(path, item)?;
+    }
+    Ok(true)
+}
+
+/// Replace `item` in the dependency list at `path` with `replacement`
+/// (skipping entries already in the list). A list left empty is removed.
+fn replace_in_list(
+    editor: &mut ConfigEditor,
+    path: &[String],
+    item: &str,
+    replacement: &[String],
+) -> Result<bool> {
+    let Some(Value::Sequence(items)) = editor.get(path) else {
+        return Ok(false);
+    };
+    let existing = strings(&items);
+    let Some(index) = existing.iter().position(|s| s == item) else {
+        return Ok(false);
+    };
+    let additions: Vec<&String> = replacement
+        .iter()
+        .filter(|dep| !existing.contains(dep))
+        .collect();
+
+    if additions.is_empty() {
+        return remove_from_list(editor, path, item, true);
+    }
+    // Insert before removing so a one-item list keeps its style
+    for (offset, dep) in additions.into_iter().enumerate() {
+        editor.insert_item(path, Some(index + offset), &Value::String(dep.clone()))?;
+    }
+    editor.remove_item(path, item)?;
+    Ok(true)
+}
+
+/// Remove `satisfied_when` conditions that reference one of `step`'s
+/// checks (`ref: <step>.<check>`). A list left empty is removed.
+fn remove_satisfaction_refs(
+    editor: &mut ConfigEditor,
+    path: &[String],
+    step: &str,
+) -> Result<bool> {
+    let Some(Value::Sequence(items)) = editor.get(path) else {
+        return Ok(false);
+    };
+    let prefix = format!("{}.", step);
+    let indices: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            item.get("ref")
+                .and_then(Value::as_str)
+                .is_some_and(|r| r.starts_with(&prefix))
+        })
+        .map(|(i, _)| i)
+        .collect();
+    if indices.is_empty() {
+        return Ok(false);
+    }
+    if indices.len() == items.len() {
+        editor.remove(path)?;
+        return Ok(true);
+    }
+    for index in indices.into_iter().rev() {
+        editor.remove(&join(path, &[&index.to_string()]))?;
+    }
+    Ok(true)
+}
+
+impl RemoveCommand {
+    /// Create a new remove command.
+    pub fn new(project_root: &Path, args: RemoveArgs) -> Self {
+        Self {
+            project_root: project_root.to_path_buf(),
+            args,
+            config_override: None,
+        }
+    }
+
+    /// Set an override config path.
+    pub fn with_config_override(mut self, config_override: Option<PathBuf>) -> Self {
+        self.config_override = config_override;
+        self
+    }
+
+    /// Get the project root path.
+    pub fn project_root(&self) -> &Path {
+        &self.project_root
+    }
+
+    /// Get the command arguments.
+    pub fn args(&self) -> &RemoveArgs {
+        &self.args
+    }
+
+    /// Load every file that can define or reference steps. The main
+    /// config comes first. With a config override, only that file is
+    /// edited.
+    fn load_documents(&self, config_path: &Path) -> Result<Vec<Document>> {
+        let mut documents = vec![Document::load(
+            config_path.to_path_buf(),
+            DocumentKind::Config,
+        )?];
+        if self.config_override.is_some() {
+            return Ok(documents);
+        }
+
+        let discovery = Discovery::new(&self.project_root);
+        if let Some(local) = discovery.local_config_path() {
+            documents.push(Document::load(local, DocumentKind::Config)?);
+        }
+        for path in discovery.workflow_files() {
+            documents.push(Document::load(path, DocumentKind::WorkflowFile)?);
+        }
+        for name in discovery.step_file_names() {
+            if let Some(path) = discovery.step_path(&name) {
+                documents.push(Document::load(path, DocumentKind::StepFile(name))?);
+            }
+        }
+        Ok(documents)
+    }
+
+    fn display_path<'a>(&self, path: &'a Path) -> std::path::Display<'a> {
+        path.strip_prefix(&self.project_root)
+            .unwrap_or(path)
+            .display()
+    }
+
+    /// Ask whether dependents should depend on the removed step's
+    /// dependencies instead. Non-interactive runs reconnect.
+    fn confirm_reconnect(
+        &self,
+        ui: &mut dyn UserInterface,
+        dependents: &[String],
+        dependencies: &[String],
+    ) -> Result<bool> {
+        if !ui.is_interactive() {
+            return Ok(true);
+        }
+        let prompt = Prompt {
+            key: "reconnect_dependents".to_string(),
+            question: format!(
+                "Make {} depend on {} instead?",
+                dependents.join(", "),
+                dependencies.join(", ")
+            ),
+            prompt_type: PromptType::Confirm,
+            default: Some("true".to_string()),
+        };
+        Ok(matches!(ui.prompt(&prompt)?, PromptResult::Bool(true)))
+    }
+
+    /// Clear the step's run state, satisfaction records, change-check
+    /// index entry and snapshots.
+    fn clear_state(&self, step: &str, ui: &mut dyn UserInterface) -> Result<bool> {
+        let project_id = ProjectId::from_path(&self.project_root)?;
+        if !StateStore::state_dir(&project_id).exists() {
+            return Ok(false);
+        }
+
+        // Don't interleave with a run writing the same files
+        let policy = WaitPolicy::from_flags(false, false, ui.is_interactive());
+        let _lock = ProjectLock::acquire(&project_id, "remove", policy, |holder| {
+            ui.message(&format!("Waiting for {} to finish...", holder.describe()));
+        })?;
+
+        let (mut state, _) = StateStore::load(&project_id)?;
+        state.clear_step(step);
+        state.save(&project_id)?;
+
+        let mut satisfaction = SatisfactionCache::load(project_id.satisfaction_path());
+        satisfaction.invalidate(step);
+        satisfaction.flush()?;
+
+        let mut freshness = FreshnessIndex::load(project_id.freshness_path());
+        freshness.retain(|name| name != step);
+        freshness.save()?;
+
+        SnapshotStore::load_for_project(&project_id).delete_step(step)?;
+        Ok(true)
+    }
+}
+
+impl Command for RemoveCommand {
+    fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config_path = self
+            .config_override
+            .clone()
+            .unwrap_or_else(|| self.project_root.join(".bivvy/config.yml"));
+
+        // Validate config exists
+        if !config_path.exists() {
+            ui.error("No configuration found. Run 'bivvy init' first.");
+            return Ok(CommandResult::failure(2));
+        }
+
+        let step = self.args.step.as_str();
+        let mut documents = self.load_documents(&config_path)?;
+
+        if !documents.iter().any(|d| d.defines(step)) {
+            ui.error(&format!("Step '{}' not found in configuration.", step));
+            return Ok(CommandResult::failure(1));
+        }
+
+        // Offer to reconnect dependents to what the step depended on
+        let dependencies = documents
+            .iter()
+            .find_map(|d| d.dependencies_of(step))
+            .unwrap_or_default();
+        let mut dependents: Vec<String> = Vec::new();
+        for name in documents.iter().flat_map(|d| d.dependents_of(step)) {
+            if !dependents.contains(&name) {
+                dependents.push(name);
+            }
+        }
+        let reconnect = !dependents.is_empty()
+            && !dependencies.is_empty()
+            && self.confirm_reconnect(ui, &dependents, &dependencies)?;
+        let reconnect_to = if reconnect { dependencies } else { Vec::new() };
+
+        let mut changes = Vec::new();
+        for document in &mut documents {
+            for path in document.remove_step(step, &reconnect_to)? {
+                changes.push((document.path.clone(), path));
+            }
+        }
+
+        // Don't write a config that bivvy can't load
+        if let Err(e) = parse_config(documents[0].editor.as_str(), &config_path) {
+            ui.error(&format!(
+                "Removing '{}' would make the config invalid: {}",
+                step, e
+            ));
+            return Ok(CommandResult::failure(1));
+        }
+
+        let mut deleted = Vec::new();
+        for document in &documents {
+            if document.kind == DocumentKind::StepFile(step.to_string()) {
+                fs::remove_file(&document.path)?;
+                deleted.push(document.path.clone());
+            } else if document.is_changed() {
+                fs::write(&document.path, document.editor.as_str())?;
+            }
+        }
+
+        ui.success(&format!("Removed step '{}'", step));
+        for path in &deleted {
+            ui.message(&format!("  Deleted {}", self.display_path(path)));
+        }
+        for (file, path) in &changes {
+            if *file == config_path {
+                ui.message(&format!("  Updated {}", path));
+            } else {
+                ui.message(&format!(
+                    "  Updated {} in {}",
+                    path,
+                    self.display_path(file)
+                ));
+            }
+        }
+        if reconnect {
+            ui.message(&format!(
+                "  {} now depend(s) on {}",
+                dependents.join(", "),
+                reconnect_to.join(", ")
+            ));
+        }
+
+        if !self.args.keep_state {
+            match self.clear_state(step, ui) {
+                Ok(true) => ui.message(&format!("  Cleared saved state for '{}'", step)),
+                Ok(false) => {}
+                Err(e) => ui.warning(&format!("Couldn't clear saved state for '{}': {}", step, e)),
+            }
+        }
+
+        Ok(CommandResult::success())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::StepStatus;
+    use crate::ui::MockUI;
+    use tempfile::TempDir;
+
+    fn setup_project(config: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(bivvy_dir.join("config.yml"), config).unwrap();
+        temp
+    }
+
+    fn read_config(temp: &TempDir) -> String {
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap()
+    }
+
+    fn remove(temp: &TempDir, step: &str, ui: &mut MockUI) -> CommandResult {
+        let args = RemoveArgs {
+            step: step.to_string(),
+            keep_state: true,
+        };
+        RemoveCommand::new(temp.path(), args).execute(ui).unwrap()
+    }
+
+    const CONFIG: &str = "\
+app_name: Test
+
+steps:
+  tools:
+    command: mise install
+
+  # Ruby gems
+  deps:
+    command: bundle install
+    depends_on: [tools]
+    check:
+      name: gems
+      type: execution
+      command: bundle check
+
+  db:
+    command: bin/rails db:prepare
+    depends_on:
+      - deps
+    satisfied_when:
+      - ref: deps.gems
+      - type: presence
+        target: db/schema.rb
+    environments:
+      ci:
+        depends_on: [deps]
+
+workflows:
+  default:
+    steps: [tools, deps, db]
+    force: [deps]
+    overrides:
+      deps:
+        skip_prompt: true
+  ci:
+    steps: [deps, db]
+";
+
+    #[test]
+    fn remove_command_creation() {
+        let temp = TempDir::new().unwrap();
+        let args = RemoveArgs {
+            step: "deps".to_string(),
+            keep_state: false,
+        };
+        let cmd = RemoveCommand::new(temp.path(), args);
+
+        assert_eq!(cmd.project_root(), temp.path());
+        assert_eq!(cmd.args().step, "deps");
+    }
+
+    #[test]
+    fn remove_fails_without_config() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = remove(&temp, "deps", &mut ui);
+
+        assert_eq!(result.exit_code, 2);
+    }
+
+    #[test]
+    fn remove_fails_for_unknown_step() {
+        let temp = setup_project(CONFIG);
+        let mut ui = MockUI::new();
+
+        let result = remove(&temp, "missing", &mut ui);
+
+        assert!(!result.success);
+        assert_eq!(result.exit_code, 1);
+        assert!(ui.has_error("Step 'missing' not found"));
+        assert_eq!(read_config(&temp), CONFIG);
+    }
+
+    #[test]
+    fn remove_rewrites_every_reference() {
+        let temp = setup_project(CONFIG);
+        let mut ui = MockUI::new();
+
+        let result = remove(&temp, "deps", &mut ui);
+
+        assert!(result.success);
+        assert_eq!(
+            read_config(&temp),
+            "\
+app_name: Test
+
+steps:
+  tools:
+    command: mise install
+
+  db:
+    command: bin/rails db:prepare
+    depends_on:
+      - tools
+    satisfied_when:
+      - type: presence
+        target: db/schema.rb
+    environments:
+      ci:
+        depends_on: [tools]
+
+workflows:
+  default:
+    steps: [tools, db]
+  ci:
+    steps: [db]
+"
+        );
+        assert!(ui.has_success("Removed step 'deps'"));
+        assert!(ui.has_message("Updated steps.db.depends_on"));
+        assert!(ui.has_message("Updated workflows.default.overrides"));
+        assert!(ui.has_message("db now depend(s) on tools"));
+
+        let config = parse_config(&read_config(&temp), Path::new("config.yml")).unwrap();
+        assert_eq!(config.steps["db"].depends_on, vec!["tools".to_string()]);
+    }
+
+    #[test]
+    fn remove_without_reconnecting_drops_dependency() {
+        let temp = setup_project(CONFIG);
+        let mut ui = MockUI::new();
+        ui.set_interactive(true);
+        ui.set_prompt_response("reconnect_dependents", "false");
+
+        let result = remove(&temp, "deps", &mut ui);
+
+        assert!(result.success);
+        assert!(ui
+            .prompts_shown()
+            .contains(&"reconnect_dependents".to_string()));
+        let config = read_config(&temp);
+        assert!(config.contains("  db:\n    command: bin/rails db:prepare\n    satisfied_when:\n"));
+        assert!(config.contains("    environments:\n      ci: {}\n"));
+    }
+
+    #[test]
+    fn remove_step_without_dependents_does_not_prompt() {
+        let temp = setup_project(CONFIG);
+        let mut ui = MockUI::new();
+        ui.set_interactive(true);
+
+        let result = remove(&temp, "db", &mut ui);
+
+        assert!(result.success);
+        assert!(ui.prompts_shown().is_empty());
+        let config = read_config(&temp);
+        assert!(config.contains("    steps: [tools, deps]\n"));
+        assert!(config.contains("    steps: [deps]\n"));
+    }
+
+    #[test]
+    fn remove_updates_split_files() {
+        let temp = setup_project(
+            "app_name: Test\nsteps:\n  tools:\n    command: mise install\nworkflows:\n  default:\n    steps: [tools, deps]\n",
+        );
+        let bivvy = temp.path().join(".bivvy");
+        fs::create_dir_all(bivvy.join("steps")).unwrap();
+        fs::create_dir_all(bivvy.join("workflows")).unwrap();
+        fs::write(
+            bivvy.join("steps/deps.yml"),
+            "command: bundle install\ndepends_on: [tools]\n",
+        )
+        .unwrap();
+        fs::write(
+            bivvy.join("steps/db.yml"),
+            "command: bin/rails db:prepare\ndepends_on: [deps]\n",
+        )
+        .unwrap();
+        fs::write(
+            bivvy.join("workflows/ci.yml"),
+            "workflow:\n  steps: [deps, db]\n",
+        )
+        .unwrap();
+        let mut ui = MockUI::new();
+
+        let result = remove(&temp, "deps", &mut ui);
+
+        assert!(result.success);
+        assert!(!bivvy.join("steps/deps.yml").exists());
+        assert_eq!(
+            fs::read_to_string(bivvy.join("steps/db.yml")).unwrap(),
+            "command: bin/rails db:prepare\ndepends_on: [tools]\n"
+        );
+        assert_eq!(
+            fs::read_to_string(bivvy.join("workflows/ci.yml")).unwrap(),
+            "workflow:\n  steps: [db]\n"
+        );
+        assert!(read_config(&temp).contains("    steps: [tools]\n"));
+        assert!(ui.has_message("Deleted .bivvy/steps/deps.yml"));
+        assert!(ui.has_message("Updated workflow.steps in .bivvy/workflows/ci.yml"));
+    }
+
+    #[test]
+    fn remove_clears_saved_state() {
+        let temp = setup_project(CONFIG);
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let mut state = StateStore::new(&project_id);
+        state.record_step_result("deps", StepStatus::Success, std::time::Duration::ZERO);
+        state.record_step_result("tools", StepStatus::Success, std::time::Duration::ZERO);
+        state.save(&project_id).unwrap();
+
+        let args = RemoveArgs {
+            step: "deps".to_string(),
+            keep_state: false,
+        };
+        let mut ui = MockUI::new();
+        let result = RemoveCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        let (state, _) = StateStore::load(&project_id).unwrap();
+        let _ = fs::remove_dir_all(StateStore::state_dir(&project_id));
+        assert!(result.success);
+        assert!(state.get_step("deps").is_none());
+        assert!(state.get_step("tools").is_some());
+        assert!(ui.has_message("Cleared saved state for 'deps'"));
+    }
+
+    #[test]
+    fn remove_leaves_state_while_project_is_locked() {
+        let temp = setup_project(CONFIG);
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let mut state = StateStore::new(&project_id);
+        state.record_step_result("deps", StepStatus::Success, std::time::Duration::ZERO);
+        state.save(&project_id).unwrap();
+        let lock = ProjectLock::acquire(&project_id, "run", WaitPolicy::NoWait, |_| {}).unwrap();
+
+        let args = RemoveArgs {
+            step: "deps".to_string(),
+            keep_state: false,
+        };
+        let mut ui = MockUI::new();
+        let result = RemoveCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        drop(lock);
+        let (state, _) = StateStore::load(&project_id).unwrap();
+        let _ = fs::remove_dir_all(StateStore::state_dir(&project_id));
+        assert!(result.success);
+        assert!(!read_config(&temp).contains("deps:"));
+        assert!(state.get_step("deps").is_some());
+        assert!(ui.has_warning("Couldn't clear saved state for 'deps'"));
+    }
+
+    #[test]
+    fn remove_keep_state_leaves_state() {
+        let temp = setup_project(CONFIG);
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let mut state = StateStore::new(&project_id);
+        state.record_step_result("deps", StepStatus::Success, std::time::Duration::ZERO);
+        state.save(&project_id).unwrap();
+
+        let mut ui = MockUI::new();
+        let result = remove(&temp, "deps", &mut ui);
+
+        let (state, _) = StateStore::load(&project_id).unwrap();
+        let _ = fs::remove_dir_all(StateStore::state_dir(&project_id));
+        assert!(result.success);
+        assert!(state.get_step("deps").is_some());
+    }
+}
@@ -5,12 +5,14 @@
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
+use crate::cache::{default_artifact_dir, ArtifactStore};
+use crate::checks::{ChangeKind, Check, SatisfactionCondition};
 use crate::cli::args::RunArgs;
 #[cfg(test)]
 use crate::config::load_merged_config;
 use crate::config::{
-    evaluate_vars, load_for_run_with_trust, load_project_config, ConfigPaths, Discovery,
-    ExtendsResolver, InterpolationContext, TrustPolicy, TrustStore,
+    load_for_run_with_trust, load_project_config, ConfigPaths, Discovery, ExtendsResolver,
+    InterpolationContext, PromptAnswers, TrustPolicy, TrustStore, VarEvaluator,
 };
 use crate::environment::resolver::ResolvedEnvironment;
 use crate::error::{BivvyError, Result};
@@ -18,12 +20,13 @@ use crate::registry::Registry;
 use crate::requirements::checker::GapChecker;
 use crate::requirements::probe::EnvironmentProbe;
 use crate::requirements::registry::RequirementRegistry;
+use crate::runner::diagnostic::{default_learned_path, LearnedStore};
 use crate::runner::display::{
     NonInteractiveWorkflowDisplay, RunHeader, RunSummary, StepSummary, TerminalWorkflowDisplay,
     WorkflowDisplay,
 };
 use crate::runner::{RunOptions, SkipBehavior, WorkflowRunner};
-use crate::state::{ProjectId, StateStore};
+use crate::state::{ProjectId, ProjectLock, StateStore, WaitPolicy};
 use crate::steps::ResolvedStep;
 use crate::ui::surface::TerminalSurface;
 use crate::ui::{hints, OutputMode, StatusKind, UserInterface};
@@ -104,7 +107,27 @@ impl RunCommand {
             active_environment: None,
             diagnostic_funnel,
             fresh: self.args.fresh,
+            answers: PromptAnswers::default(),
+            artifacts: Some(ArtifactStore::new(
+                default_artifact_dir(),
+                config.settings.execution.artifact_cache_mb,
+            )),
+            learned: Some(LearnedStore::new(default_learned_path())),
+        }
+    }
+
+    /// Collect prompt answers from `--answers` and `--set`.
+    ///
+    /// `--set` values are applied after the file so they win on conflict.
+    fn load_answers(&self) -> Result<PromptAnswers> {
+        let mut answers = match &self.args.answers {
+            Some(path) => PromptAnswers::load(path)?,
+            None => PromptAnswers::new(),
+        };
+        for assignment in &self.args.set {
+            answers.apply_set(assignment)?;
         }
+        Ok(answers)
     }
 
     /// Resolve the target environment using the priority chain.
@@ -122,55 +145,71 @@ impl RunCommand {
         config: &crate::config::BivvyConfig,
         environment: Option<&str>,
     ) -> Result<HashMap<String, ResolvedStep>> {
-        let registry = if config.template_sources.is_empty() {
-            Registry::new(Some(&self.project_root))?
+        let registry = step_registry(&self.project_root, config)?;
+        resolve_config_steps(&registry, config, environment)
+    }
+}
+
+/// Build the template registry for a project's configuration.
+pub(crate) fn step_registry(
+    project_root: &Path,
+    config: &crate::config::BivvyConfig,
+) -> Result<Registry> {
+    if config.template_sources.is_empty() {
+        Registry::new(Some(project_root))
+    } else {
+        Registry::with_remote_sources(Some(project_root), &config.template_sources)
+    }
+}
+
+/// Resolve every configured step, applying templates and global defaults.
+pub(crate) fn resolve_config_steps(
+    registry: &Registry,
+    config: &crate::config::BivvyConfig,
+    environment: Option<&str>,
+) -> Result<HashMap<String, ResolvedStep>> {
+    let defaults = &config.settings.defaults;
+    // Resolve the effective global rerun window: prefer defaults.rerun_window,
+    // fall back to execution.default_rerun_window for backward compatibility.
+    let global_rerun_window = defaults.rerun_window.as_deref().or(config
+        .settings
+        .execution
+        .default_rerun_window
+        .as_deref());
+
+    let mut steps = HashMap::new();
+    for (name, step_config) in &config.steps {
+        let mut resolved = if let Some(template_name) = &step_config.template {
+            let (template, _source) = registry.resolve(template_name)?;
+            ResolvedStep::from_template(
+                name,
+                template,
+                step_config,
+                &step_config.inputs,
+                environment,
+            )
         } else {
-            Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)?
+            ResolvedStep::from_config(name, step_config, environment)
         };
 
-        let defaults = &config.settings.defaults;
-        // Resolve the effective global rerun window: prefer defaults.rerun_window,
-        // fall back to execution.default_rerun_window for backward compatibility.
-        let global_rerun_window = defaults.rerun_window.as_deref().or(config
-            .settings
-            .execution
-            .default_rerun_window
-            .as_deref());
-
-        let mut steps = HashMap::new();
-        for (name, step_config) in &config.steps {
-            let mut resolved = if let Some(template_name) = &step_config.template {
-                let (template, _source) = registry.resolve(template_name)?;
-                ResolvedStep::from_template(
-                    name,
-                    template,
-                    step_config,
-                    &step_config.inputs,
-                    environment,
-                )
-            } else {
-                ResolvedStep::from_config(name, step_config, environment)
-            };
-
-            // Apply global defaults for fields that weren't explicitly set at step level
-            if step_config.behavior.auto_run.is_none() {
-                resolved.behavior.auto_run = defaults.auto_run;
-            }
-            if step_config.behavior.prompt_on_rerun.is_none() {
-                resolved.behavior.prompt_on_rerun = defaults.prompt_on_rerun;
-            }
-            if step_config.behavior.rerun_window.is_none() {
-                if let Some(window_str) = global_rerun_window {
-                    if let Ok(w) = window_str.parse() {
-                        resolved.behavior.rerun_window = w;
-                    }
+        // Apply global defaults for fields that weren't explicitly set at step level
+        if step_config.behavior.auto_run.is_none() {
+            resolved.behavior.auto_run = defaults.auto_run;
+        }
+        if step_config.behavior.prompt_on_rerun.is_none() {
+            resolved.behavior.prompt_on_rerun = defaults.prompt_on_rerun;
+        }
+        if step_config.behavior.rerun_window.is_none() {
+            if let Some(window_str) = global_rerun_window {
+                if let Ok(w) = window_str.parse() {
+                    resolved.behavior.rerun_window = w;
                 }
             }
-
-            steps.insert(name.clone(), resolved);
         }
-        Ok(steps)
+
+        steps.insert(name.clone(), resolved);
     }
+    Ok(steps)
 }
 
 /// Combine CLI force flags with the matching workflow's force directives.
@@ -357,6 +396,28 @@ impl Command for RunCommand {
             self.args.workflow.clone()
         };
 
+        // Prompt answers are parsed up front so a malformed answers file
+        // fails before anything is printed or executed.
+        let mut answers = self.load_answers()?;
+
+        if self.args.dump_prompts {
+            if !config.workflows.contains_key(&workflow_name) {
+                ui.error(&format!("Unknown workflow: {}", workflow_name));
+                return Ok(CommandResult::failure(1));
+            }
+            // Only the steps this run would execute, so --only/--skip and
+            // environment filters shape the template like the real run.
+            let steps = self.resolve_steps(&config, Some(&env_name))?;
+            let mut options = self.build_options(&config);
+            options.workflow = Some(workflow_name.clone());
+            options.provided_requirements = provided_requirements.clone();
+            options.active_environment = Some(env_name.clone());
+            let planned = WorkflowRunner::new(&config, steps.clone()).planned_steps(&options)?;
+            let template = crate::config::answers::dump_prompts(&planned, &steps);
+            ui.message(template.trim_end());
+            return Ok(CommandResult::success());
+        }
+
         // Build the run header (header rendering happens through the
         // workflow display so it stays coordinated with the pinned bar).
         let app_name = config.app_name.as_deref().unwrap_or("project").to_string();
@@ -422,11 +483,29 @@ impl Command for RunCommand {
         // Get project identity
         let project_id = ProjectId::from_path(&self.project_root)?;
 
+        // Take the project lock before reading state so a concurrent run
+        // cannot overwrite our records (dry runs write nothing)
+        let _lock = if self.args.dry_run {
+            None
+        } else {
+            let policy =
+                WaitPolicy::from_flags(self.args.wait, self.args.no_wait, ui.is_interactive());
+            Some(ProjectLock::acquire(
+                &project_id,
+                "run",
+                policy,
+                |holder| {
+                    ui.message(&format!("Waiting for {} to finish...", holder.describe()));
+                },
+            )?)
+        };
+
         // Load state (with any v1→v2 baseline migrations)
         let (mut state, baseline_migrations) = StateStore::load(&project_id)?;
 
         // Resolve steps with environment
         let steps = self.resolve_steps(&config, Some(&env_name))?;
+        answers.validate(&steps)?;
 
         // Check if workflow exists
         if !config.workflows.contains_key(&workflow_name) {
@@ -464,6 +543,7 @@ impl Command for RunCommand {
         options.workflow = Some(workflow_name.clone());
         options.provided_requirements = provided_requirements;
         options.active_environment = Some(env_name.clone());
+        options.answers = answers;
 
         // Create runner with project-backed snapshot store for change check baselines
         let mut snapshot_store = crate::snapshots::SnapshotStore::load_for_project(&project_id);
@@ -483,6 +563,10 @@ impl Command for RunCommand {
             );
         }
 
+        // Capture change-check targets before the runner takes the steps, so
+        // finished steps can refresh the freshness index afterwards.
+        let watched_targets = watched_targets(&steps);
+
         let mut runner = WorkflowRunner::with_snapshot_store(&config, steps, snapshot_store);
 
         // Create gap checker for requirement detection
@@ -490,11 +574,26 @@ impl Command for RunCommand {
         let req_registry = RequirementRegistry::new().with_custom(&config.requirements);
         let mut gap_checker = GapChecker::new(&req_registry, &probe, &self.project_root);
 
-        // Evaluate user-defined vars
-        let resolved_vars = evaluate_vars(&config.vars, &self.project_root)?;
+        // Evaluate only the user-defined vars the planned steps reference,
+        // so unused computed vars never run and prompt vars never ask.
+        let referenced = runner.referenced_variables(&options)?;
+        let mut var_evaluator = VarEvaluator::new(&config.vars, &self.project_root);
+        let resolved_vars = var_evaluator.evaluate(referenced.iter().map(String::as_str), ui)?;
 
         // Create interpolation context
-        let ctx = InterpolationContext::new().with_vars(resolved_vars);
+        let mut ctx = InterpolationContext::new().with_vars(resolved_vars);
+        for secret in var_evaluator.secrets() {
+            ctx.add_secret(secret.clone());
+        }
+
+        // Without a user to ask, report every prompt nothing can answer
+        // before running anything rather than failing midway through.
+        if !ui.is_interactive() || workflow_non_interactive {
+            let missing = runner.unanswered_prompts(&options, &ctx)?;
+            if !missing.is_empty() {
+                return Err(crate::config::answers::unanswered_error(&missing));
+            }
+        }
 
         // Build the YAML-defined base env (settings.env_vars + workflow env),
         // then snapshot the parent process env. The base env is layered first
@@ -605,6 +704,21 @@ impl Command for RunCommand {
             if let Err(e) = runner.snapshot_store_mut().save() {
                 tracing::warn!("Failed to save snapshot store: {}", e);
             }
+
+            // Refresh cached target hashes for `bivvy status --stale`. Steps
+            // that finished, by running or by passing their check, are
+            // up to date as of now.
+            let mut freshness = crate::state::FreshnessIndex::load(project_id.freshness_path());
+            for step_result in result.steps.iter().filter(|s| s.success) {
+                if let Some(targets) = watched_targets.get(&step_result.name) {
+                    let targets: Vec<_> = targets.iter().map(|(t, k)| (t.as_str(), *k)).collect();
+                    freshness.record(&step_result.name, &targets, &self.project_root);
+                }
+            }
+            freshness.retain(|name| config.steps.contains_key(name));
+            if let Err(e) = freshness.save() {
+                tracing::warn!("Failed to save freshness index: {}", e);
+            }
         }
 
         // Build and show run summary
@@ -690,6 +804,31 @@ impl Command for RunCommand {
     }
 }
 
+/// Change-check targets watched by each step.
+///
+/// Includes targets from the step's `check`/`checks` and from inline
+/// `satisfied_when` conditions.
+pub(crate) fn watched_targets(
+    steps: &HashMap<String, ResolvedStep>,
+) -> HashMap<String, Vec<(String, ChangeKind)>> {
+    steps
+        .iter()
+        .map(|(name, step)| {
+            let mut checks: Vec<Check> = step.execution.effective_check().into_iter().collect();
+            checks.extend(step.satisfied_when.iter().filter_map(|c| match c {
+                SatisfactionCondition::Check(check) => Some(check.clone()),
+                SatisfactionCondition::Ref { .. } => None,
+            }));
+            let targets = checks
+                .iter()
+                .flat_map(|c| c.change_targets())
+                .map(|(target, kind)| (target.to_string(), kind))
+                .collect();
+            (name.clone(), targets)
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -857,6 +996,40 @@ workflows:
         assert!(result.success);
     }
 
+    #[test]
+    fn execute_fails_when_project_is_locked() {
+        let config = r#"
+steps:
+  hello:
+    command: echo hello
+workflows:
+  default:
+    steps: [hello]
+"#;
+        let temp = setup_project(config);
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let _lock = ProjectLock::acquire(&project_id, "run", WaitPolicy::NoWait, |_| {}).unwrap();
+
+        let args = RunArgs {
+            no_wait: true,
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let err = cmd.execute(&mut ui).unwrap_err();
+        assert!(matches!(err, BivvyError::ProjectLocked { .. }));
+
+        // Dry runs write nothing and do not need the lock
+        let args = RunArgs {
+            dry_run: true,
+            no_wait: true,
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        assert!(cmd.execute(&mut MockUI::new()).unwrap().success);
+    }
+
     #[test]
     fn execute_real_workflow() {
         let config = r#"
@@ -1617,6 +1790,38 @@ workflows:
         assert!(result.success);
     }
 
+    #[test]
+    fn unreferenced_computed_vars_are_not_evaluated() {
+        let config = r#"
+app_name: Test
+vars:
+  version:
+    json: package.json
+    key: version
+  broken:
+    command: "touch broken-ran && exit 1"
+steps:
+  stamp:
+    command: "echo ${version} > version.txt"
+workflows:
+  default:
+    steps: [stamp]
+"#;
+        let temp = setup_project(config);
+        fs::write(temp.path().join("package.json"), r#"{"version": "3.1.4"}"#).unwrap();
+        let cmd = RunCommand::new(temp.path(), RunArgs::default());
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(!temp.path().join("broken-ran").exists());
+        assert_eq!(
+            fs::read_to_string(temp.path().join("version.txt")).unwrap(),
+            "3.1.4\n"
+        );
+    }
+
     #[test]
     fn execute_unknown_workflow_does_not_duplicate_message() {
         let config = r#"
@@ -1772,4 +1977,156 @@ workflows:
         let config: crate::config::BivvyConfig = serde_yaml::from_str(yaml_default).unwrap();
         assert!(config.settings.execution.diagnostic_funnel);
     }
+
+    const PROMPT_CONFIG: &str = r#"
+app_name: Test
+steps:
+  deploy:
+    command: "echo ${region} > region.txt"
+    prompts:
+      - key: region
+        question: Which region?
+        type: select
+        options:
+          - label: US
+            value: us
+          - label: EU
+            value: eu
+      - key: migrate
+        question: Run migrations?
+        type: confirm
+workflows:
+  default:
+    steps: [deploy]
+"#;
+
+    #[test]
+    fn execute_uses_set_answers_in_non_interactive_mode() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let args = RunArgs {
+            set: vec![
+                "deploy.region=eu".to_string(),
+                "deploy.migrate=yes".to_string(),
+            ],
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        let written = fs::read_to_string(temp.path().join("region.txt")).unwrap();
+        assert_eq!(written.trim(), "eu");
+    }
+
+    #[test]
+    fn execute_reads_answers_file() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let answers_path = temp.path().join("answers.yml");
+        fs::write(&answers_path, "deploy:\n  region: us\n  migrate: false\n").unwrap();
+        let args = RunArgs {
+            answers: Some(answers_path),
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        let written = fs::read_to_string(temp.path().join("region.txt")).unwrap();
+        assert_eq!(written.trim(), "us");
+    }
+
+    #[test]
+    fn execute_lists_every_unanswered_prompt() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let cmd = RunCommand::new(temp.path(), RunArgs::default());
+        let mut ui = MockUI::new();
+
+        let err = cmd.execute(&mut ui).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: 2 prompts unanswered in non-interactive mode:\n\
+             \x20 - deploy.region (Which region?)\n\
+             \x20 - deploy.migrate (Run migrations?)\n\
+             Provide answers with --answers <file> or --set <step>.<key>=<value>. \
+             Run 'bivvy run --dump-prompts' to generate an answers template."
+        );
+        assert!(!temp.path().join("region.txt").exists());
+    }
+
+    #[test]
+    fn execute_rejects_answer_outside_options() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let args = RunArgs {
+            set: vec![
+                "deploy.region=ap".to_string(),
+                "deploy.migrate=true".to_string(),
+            ],
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let err = cmd.execute(&mut ui).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid prompt answers:\n\
+             \x20 - deploy.region: 'ap' is not one of the options (us, eu)"
+        );
+    }
+
+    #[test]
+    fn execute_dump_prompts_prints_template_without_running() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let args = RunArgs {
+            dump_prompts: true,
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            ui.messages(),
+            &["# Prompt answers for `bivvy run --answers <file>`\n\
+               deploy:\n\
+               \x20 # Which region?\n\
+               \x20 # type: select (us, eu)\n\
+               \x20 region: ~\n\
+               \x20 # Run migrations?\n\
+               \x20 # type: confirm\n\
+               \x20 migrate: ~"
+                .to_string()]
+        );
+        assert!(!temp.path().join("region.txt").exists());
+    }
+
+    #[test]
+    fn execute_dump_prompts_covers_only_planned_steps() {
+        let temp = setup_project(PROMPT_CONFIG);
+        let args = RunArgs {
+            dump_prompts: true,
+            skip: vec!["deploy".to_string()],
+            ..Default::default()
+        };
+        let cmd = RunCommand::new(temp.path(), args);
+        let mut ui = MockUI::new();
+
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            ui.messages(),
+            &["# Prompt answers for `bivvy run --answers <file>`\n\
+               # No steps in this run declare prompts."
+                .to_string()]
+        );
+    }
 }
@@ -15,7 +15,7 @@ use crate::checks::{change, Check};
 use crate::config::{load_config, load_for_run, load_project_config};
 use crate::error::{BivvyError, Result};
 use crate::snapshots::{SnapshotKey, SnapshotStore};
-use crate::state::ProjectId;
+use crate::state::{ProjectId, ProjectLock, WaitPolicy};
 use crate::ui::UserInterface;
 
 use super::dispatcher::{Command, CommandResult};
@@ -141,6 +141,14 @@ impl Command for SnapshotCommand {
     }
 }
 
+/// Take the project lock before writing snapshot baselines.
+fn lock_project(project_id: &ProjectId, ui: &mut dyn UserInterface) -> Result<ProjectLock> {
+    let policy = WaitPolicy::from_flags(false, false, ui.is_interactive());
+    ProjectLock::acquire(project_id, "snapshot", policy, |holder| {
+        ui.message(&format!("Waiting for {} to finish...", holder.describe()));
+    })
+}
+
 impl SnapshotCommand {
     fn execute_capture(
         &self,
@@ -151,6 +159,7 @@ impl SnapshotCommand {
         event_bus: &mut crate::logging::EventBus,
     ) -> Result<()> {
         let project_id = ProjectId::from_path(&self.project_root)?;
+        let _lock = lock_project(&project_id, ui)?;
         let mut store = SnapshotStore::load_for_project(&project_id);
 
         // Load only what's needed for this snapshot. With --workflow we use
@@ -295,6 +304,7 @@ impl SnapshotCommand {
         _event_bus: &mut crate::logging::EventBus,
     ) -> Result<()> {
         let project_id = ProjectId::from_path(&self.project_root)?;
+        let _lock = lock_project(&project_id, ui)?;
         let mut store = SnapshotStore::load_for_project(&project_id);
 
         if store.delete_named(&args.slug) {
@@ -16,7 +16,7 @@ use crate::requirements::checker::GapChecker;
 use crate::requirements::probe::EnvironmentProbe;
 use crate::requirements::registry::RequirementRegistry;
 use crate::requirements::status::RequirementStatus;
-use crate::state::{ProjectId, StateStore, StepStatus};
+use crate::state::{FreshnessIndex, LockInfo, ProjectId, ProjectLock, StateStore, StepStatus};
 use crate::ui::theme::BivvyTheme;
 use crate::ui::{format_relative_time, hints, OutputMode, StatusKind, UserInterface};
 
@@ -62,6 +62,60 @@ impl StatusCommand {
 }
 
 impl StatusCommand {
+    /// Report steps whose watched files changed since they last finished.
+    ///
+    /// Reads only the cached freshness index, never the configuration or
+    /// run history, so it stays fast enough for shell prompt hooks. In
+    /// quiet mode it prints a single notice line, or nothing when every
+    /// step is up to date.
+    fn execute_stale(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let project_id = ProjectId::from_path(&self.project_root)?;
+        let index = FreshnessIndex::load(project_id.freshness_path());
+        let stale = index.stale_steps(&self.project_root);
+
+        if self.args.json {
+            let steps: Vec<serde_json::Value> = stale
+                .iter()
+                .map(|s| json!({ "name": s.name, "changed": s.changed }))
+                .collect();
+            ui.message(
+                &serde_json::to_string_pretty(&json!({ "stale": steps }))
+                    .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?,
+            );
+            return Ok(CommandResult::success());
+        }
+
+        if ui.output_mode() == OutputMode::Quiet {
+            if !stale.is_empty() {
+                ui.message(&stale_notice(stale.len()));
+            }
+            return Ok(CommandResult::success());
+        }
+
+        let theme = BivvyTheme::new();
+        if stale.is_empty() {
+            ui.message(&format!(
+                "  {} All steps are up to date",
+                StatusKind::Success.styled(&theme)
+            ));
+            return Ok(CommandResult::success());
+        }
+
+        for step in &stale {
+            ui.message(&format!(
+                "  {} {} {}",
+                StatusKind::Warning.styled(&theme),
+                theme.highlight.apply_to(&step.name),
+                theme
+                    .dim
+                    .apply_to(format!("({} changed)", step.changed.join(", "))),
+            ));
+        }
+        ui.message("");
+        ui.message(&stale_notice(stale.len()));
+        Ok(CommandResult::success())
+    }
+
     /// Produce JSON output for the status command.
     fn execute_json(
         &self,
@@ -69,6 +123,7 @@ impl StatusCommand {
         config: &crate::config::BivvyConfig,
         state: &StateStore,
         resolved_env: &ResolvedEnvironment,
+        running: Option<&LockInfo>,
     ) -> Result<CommandResult> {
         let app_name = config.app_name.as_deref().unwrap_or("Bivvy Setup");
 
@@ -186,6 +241,15 @@ impl StatusCommand {
             output["requirements"] = json!(requirements);
         }
 
+        if let Some(holder) = running {
+            output["running"] = json!({
+                "command": holder.command,
+                "pid": holder.pid,
+                "host": holder.host,
+                "started_at": holder.acquired_at.to_rfc3339(),
+            });
+        }
+
         let json_str = serde_json::to_string_pretty(&output)
             .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
         ui.message(&json_str);
@@ -196,6 +260,12 @@ impl StatusCommand {
 
 impl Command for StatusCommand {
     fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        // The stale check runs from shell prompts, so it skips logging and
+        // config loading entirely.
+        if self.args.stale {
+            return self.execute_stale(ui);
+        }
+
         // Create event bus for structured logging
         let mut event_bus = crate::logging::EventBus::new();
         if let Ok(logger) = crate::logging::EventLogger::new(
@@ -270,12 +340,15 @@ impl Command for StatusCommand {
         // Load state (baseline migrations not needed for status)
         let (state, _) = StateStore::load(&project_id)?;
 
+        // Status never takes the project lock, but reports who holds it
+        let running = ProjectLock::holder(&project_id);
+
         // Resolve environment
         let resolved_env = self.resolve_environment(&config);
 
         // JSON output mode
         if self.args.json {
-            let result = self.execute_json(ui, &config, &state, &resolved_env)?;
+            let result = self.execute_json(ui, &config, &state, &resolved_env, running.as_ref())?;
             event_bus.emit(&crate::logging::BivvyEvent::SessionEnded {
                 exit_code: result.exit_code,
                 duration_ms: start.elapsed().as_millis() as u64,
@@ -315,6 +388,15 @@ impl Command for StatusCommand {
             ui.message("");
         }
 
+        if let Some(ref holder) = running {
+            ui.message(&format!(
+                "  {} {}",
+                theme.key.apply_to("Running:"),
+                theme.dim.apply_to(holder.describe()),
+            ));
+            ui.message("");
+        }
+
         // Show step status
         ui.message(&format!("  {}", theme.key.apply_to("Steps:")));
 
@@ -527,6 +609,15 @@ fn format_requirement_status(theme: &BivvyTheme, status: &RequirementStatus) ->
     }
 }
 
+/// One-line notice for a number of stale steps.
+fn stale_notice(count: usize) -> String {
+    if count == 1 {
+        "1 setup step is stale, run `bivvy run`".to_string()
+    } else {
+        format!("{count} setup steps are stale, run `bivvy run`")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -636,6 +727,33 @@ workflows:
         assert!(!ui.warnings().iter().any(|m| m.contains("hello")));
     }
 
+    #[test]
+    fn status_reports_running_lock_holder() {
+        let config = r#"
+steps:
+  hello:
+    command: echo hello
+workflows:
+  default:
+    steps: [hello]
+"#;
+        let temp = setup_project(config);
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let _lock =
+            ProjectLock::acquire(&project_id, "run", crate::state::WaitPolicy::NoWait, |_| {})
+                .unwrap();
+
+        let cmd = StatusCommand::new(temp.path(), StatusArgs::default());
+        let mut ui = MockUI::new();
+        let result = cmd.execute(&mut ui).unwrap();
+
+        assert!(result.success);
+        assert!(ui
+            .messages()
+            .iter()
+            .any(|m| m.contains("Running:") && m.contains("bivvy run")));
+    }
+
     #[test]
     fn status_shows_header_with_app_name() {
         let config = r#"
@@ -951,4 +1069,85 @@ workflows:
             ui.messages()
         );
     }
+
+    fn record_lockfile_step(temp: &TempDir) {
+        fs::write(temp.path().join("Gemfile.lock"), "gems").unwrap();
+        let project_id = ProjectId::from_path(temp.path()).unwrap();
+        let mut index = FreshnessIndex::load(project_id.freshness_path());
+        index.record(
+            "bundle",
+            &[("Gemfile.lock", crate::checks::ChangeKind::File)],
+            temp.path(),
+        );
+        index.save().unwrap();
+    }
+
+    #[test]
+    fn stale_quiet_prints_one_line_notice() {
+        let temp = setup_project("steps:\n  bundle:\n    command: bundle install\n");
+        record_lockfile_step(&temp);
+        fs::write(temp.path().join("Gemfile.lock"), "more gems").unwrap();
+
+        let args = StatusArgs {
+            stale: true,
+            ..Default::default()
+        };
+        let mut ui = MockUI::new();
+        ui.set_output_mode(OutputMode::Quiet);
+
+        let result = StatusCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(
+            ui.messages(),
+            &["1 setup step is stale, run `bivvy run`".to_string()]
+        );
+    }
+
+    #[test]
+    fn stale_quiet_is_silent_when_up_to_date() {
+        let temp = setup_project("steps:\n  bundle:\n    command: bundle install\n");
+        record_lockfile_step(&temp);
+
+        let args = StatusArgs {
+            stale: true,
+            ..Default::default()
+        };
+        let mut ui = MockUI::new();
+        ui.set_output_mode(OutputMode::Quiet);
+
+        StatusCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(ui.messages().is_empty());
+    }
+
+    #[test]
+    fn stale_lists_changed_targets() {
+        let temp = setup_project("steps:\n  bundle:\n    command: bundle install\n");
+        record_lockfile_step(&temp);
+        fs::write(temp.path().join("Gemfile.lock"), "more gems").unwrap();
+
+        let args = StatusArgs {
+            stale: true,
+            ..Default::default()
+        };
+        let mut ui = MockUI::new();
+
+        StatusCommand::new(temp.path(), args)
+            .execute(&mut ui)
+            .unwrap();
+
+        assert!(ui.has_message("bundle"));
+        assert!(ui.has_message("Gemfile.lock changed"));
+    }
+
+    #[test]
+    fn stale_notice_pluralizes() {
+        assert_eq!(stale_notice(1), "1 setup step is stale, run `bivvy run`");
+        assert_eq!(stale_notice(3), "3 setup steps are stale, run `bivvy run`");
+    }
 }
@@ -2,18 +2,30 @@
 //!
 //! The `bivvy templates` command lists all available templates from
 //! all sources (built-in, local, remote), organized by category.
+//! `bivvy templates outdated` compares steps written by `bivvy eject`
+//! with the current version of their template.
 
 use std::path::{Path, PathBuf};
 
+use clap::Subcommand;
+
 use crate::cli::args::TemplatesArgs;
 use crate::config::load_merged_config;
 use crate::error::{BivvyError, Result};
+use crate::registry::eject::{step_fields, EjectedStep};
 use crate::registry::resolver::Registry;
 use crate::ui::theme::BivvyTheme;
-use crate::ui::UserInterface;
+use crate::ui::{line_diff, render_diff, UserInterface};
 
 use super::dispatcher::{Command, CommandResult};
 
+/// Templates subcommands.
+#[derive(Debug, Clone, Subcommand)]
+pub enum TemplatesSubcommand {
+    /// Show ejected steps whose template has changed since they were ejected.
+    Outdated,
+}
+
 /// The templates command implementation.
 pub struct TemplatesCommand {
     project_root: PathBuf,
@@ -38,10 +50,97 @@ impl TemplatesCommand {
     pub fn args(&self) -> &TemplatesArgs {
         &self.args
     }
+
+    /// Report ejected steps whose template version has moved on, with a
+    /// diff from the step's fields to what the template renders now.
+    fn execute_outdated(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        let config = match load_merged_config(&self.project_root) {
+            Ok(config) => config,
+            Err(BivvyError::ConfigNotFound { .. }) => {
+                ui.error("No configuration found. Run 'bivvy init' first.");
+                return Ok(CommandResult::failure(2));
+            }
+            Err(e) => return Err(e),
+        };
+        let registry = if config.template_sources.is_empty() {
+            Registry::new(Some(&self.project_root))?
+        } else {
+            Registry::with_remote_sources(Some(&self.project_root), &config.template_sources)?
+        };
+        let theme = BivvyTheme::new();
+
+        let mut ejected: Vec<_> = config
+            .steps
+            .iter()
+            .filter_map(|(name, step)| step.ejected_from.as_ref().map(|from| (name, step, from)))
+            .collect();
+        if ejected.is_empty() {
+            ui.message("No ejected steps. Use `bivvy eject <step>` to inline a template.");
+            return Ok(CommandResult::success());
+        }
+        ejected.sort_by_key(|(name, _, _)| *name);
+
+        let mut outdated = 0;
+        for (name, step, from) in &ejected {
+            let current = match EjectedStep::render(&registry, &from.template, &from.inputs) {
+                Ok(current) => current,
+                Err(BivvyError::UnknownTemplate { .. }) => {
+                    ui.warning(&format!(
+                        "Step '{}' was ejected from template '{}', which is no longer available",
+                        name, from.template
+                    ));
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+            if current.ejected_from.version == from.version {
+                continue;
+            }
+
+            outdated += 1;
+            ui.message(&format!(
+                "  {}  {} {} → {}",
+                theme.highlight.apply_to(name.as_str()),
+                from.template,
+                theme.dim.apply_to(&from.version),
+                current.ejected_from.version,
+            ));
+            let ours = serde_yaml::to_string(&step_fields(step)).unwrap_or_default();
+            let theirs = serde_yaml::to_string(&current.fields()).unwrap_or_default();
+            if ours == theirs {
+                ui.message(&format!(
+                    "    {}",
+                    theme.dim.apply_to("No changes to the step's fields")
+                ));
+            } else {
+                ui.message(render_diff(&line_diff(&ours, &theirs), &theme).trim_end());
+            }
+            ui.message("");
+        }
+
+        if outdated == 0 {
+            ui.success(&format!(
+                "All {} ejected step(s) are up to date",
+                ejected.len()
+            ));
+        } else {
+            ui.message(&format!(
+                "  {} ejected step(s) behind their template. Copy over the changes you want, \
+                 then set `ejected_from.version` to the new version.",
+                outdated
+            ));
+        }
+
+        Ok(CommandResult::success())
+    }
 }
 
 impl Command for TemplatesCommand {
     fn execute(&self, ui: &mut dyn UserInterface) -> Result<CommandResult> {
+        if let Some(TemplatesSubcommand::Outdated) = self.args.action {
+            return self.execute_outdated(ui);
+        }
+
         // If the project has a config with template_sources, surface those
         // remote templates too. When there's no config (e.g. a brand-new
         // project), fall back to the local + built-in registry.
@@ -54,15 +153,19 @@ impl Command for TemplatesCommand {
             }
             Err(e) => return Err(e),
         };
-        let manifest = registry.builtin().manifest();
+        let categories = registry.categories();
         let theme = BivvyTheme::new();
 
+        for warning in registry.detector_warnings() {
+            ui.warning(warning);
+        }
+
         ui.show_header("Available Templates");
         ui.message("");
 
         let mut shown_count = 0;
 
-        for category in &manifest.categories {
+        for category in categories {
             // Filter by category if specified
             if let Some(ref filter) = self.args.category {
                 if category.name != *filter {
@@ -111,7 +214,10 @@ impl Command for TemplatesCommand {
         // Registry keys are qualified (category/name), manifest uses unqualified names,
         // so extract the unqualified name for comparison.
         let all_names = registry.all_template_names();
-        let manifest_names: Vec<&str> = manifest.all_template_names().into_iter().collect();
+        let manifest_names: Vec<&str> = categories
+            .iter()
+            .flat_map(|c| c.templates.iter().map(|s| s.as_str()))
+            .collect();
         let extra: Vec<_> = all_names
             .iter()
             .filter(|n| {
@@ -199,6 +305,7 @@ mod tests {
         let temp = TempDir::new().unwrap();
         let args = TemplatesArgs {
             category: Some("ruby".to_string()),
+            ..Default::default()
         };
         let cmd = TemplatesCommand::new(temp.path(), args);
         let mut ui = MockUI::new();
@@ -274,6 +381,7 @@ step:
         let temp = TempDir::new().unwrap();
         let args = TemplatesArgs {
             category: Some("nonexistent".to_string()),
+            ..Default::default()
         };
         let cmd = TemplatesCommand::new(temp.path(), args);
         let mut ui = MockUI::new();
@@ -343,4 +451,130 @@ steps:
             ui.messages()
         );
     }
+
+    #[test]
+    fn templates_lists_project_categories() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        std::fs::create_dir_all(bivvy_dir.join("templates/steps")).unwrap();
+        std::fs::write(
+            bivvy_dir.join("detectors.yml"),
+            "categories:\n  - name: platform\n    description: Platform team tooling\n    templates: [platform-lint]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            bivvy_dir.join("templates/steps/platform-lint.yml"),
+            "name: platform-lint\ndescription: Lint platform config\ncategory: platform\nstep:\n  command: platform lint\n",
+        )
+        .unwrap();
+
+        let cmd = TemplatesCommand::new(temp.path(), TemplatesArgs::default());
+        let mut ui = MockUI::new();
+        cmd.execute(&mut ui).unwrap();
+
+        let messages = ui.messages();
+        assert!(messages.iter().any(|m| m.contains("Platform team tooling")));
+        assert!(!messages
+            .iter()
+            .any(|m| m.contains("Project and user templates")));
+    }
+
+    fn outdated(temp: &TempDir, ui: &mut MockUI) -> CommandResult {
+        let args = TemplatesArgs {
+            action: Some(TemplatesSubcommand::Outdated),
+            ..Default::default()
+        };
+        TemplatesCommand::new(temp.path(), args)
+            .execute(ui)
+            .unwrap()
+    }
+
+    fn setup_ejected_project(template_version: &str, template_command: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let bivvy = temp.path().join(".bivvy");
+        std::fs::create_dir_all(bivvy.join("templates/steps")).unwrap();
+        std::fs::write(
+            bivvy.join("templates/steps/deploy.yml"),
+            format!(
+                "name: deploy\ndescription: Deploy\ncategory: custom\nversion: \"{}\"\n\
+                 inputs:\n  region:\n    description: Region\n    type: string\n\
+                 step:\n  command: \"{}\"\n",
+                template_version, template_command
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            bivvy.join("config.yml"),
+            "app_name: Test\nsteps:\n  deploy:\n    command: deploy --region eu-west-1 --verbose\n\
+             \x20   ejected_from:\n      template: deploy\n      version: \"1.0.0\"\n\
+             \x20     inputs:\n        region: eu-west-1\n",
+        )
+        .unwrap();
+        temp
+    }
+
+    #[test]
+    fn outdated_fails_without_config() {
+        let temp = TempDir::new().unwrap();
+        let mut ui = MockUI::new();
+
+        let result = outdated(&temp, &mut ui);
+
+        assert_eq!(result.exit_code, 2);
+    }
+
+    #[test]
+    fn outdated_reports_no_ejected_steps() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join(".bivvy")).unwrap();
+        std::fs::write(
+            temp.path().join(".bivvy/config.yml"),
+            "app_name: Test\nsteps:\n  deps:\n    template: bundle-install\n",
+        )
+        .unwrap();
+        let mut ui = MockUI::new();
+
+        let result = outdated(&temp, &mut ui);
+
+        assert!(result.success);
+        assert!(ui.has_message("No ejected steps"));
+    }
+
+    #[test]
+    fn outdated_shows_diff_for_new_template_version() {
+        let temp = setup_ejected_project("1.1.0", "deploy --region ${region} --wait");
+        let mut ui = MockUI::new();
+
+        let result = outdated(&temp, &mut ui);
+
+        assert!(result.success);
+        assert!(ui.has_message("1.0.0 → 1.1.0"));
+        assert!(ui.has_message("- command: deploy --region eu-west-1 --verbose"));
+        assert!(ui.has_message("+ command: deploy --region eu-west-1 --wait"));
+        assert!(ui.has_message("1 ejected step(s) behind their template"));
+    }
+
+    #[test]
+    fn outdated_skips_steps_on_current_version() {
+        let temp = setup_ejected_project("1.0.0", "deploy --region ${region}");
+        let mut ui = MockUI::new();
+
+        let result = outdated(&temp, &mut ui);
+
+        assert!(result.success);
+        assert!(ui.has_success("All 1 ejected step(s) are up to date"));
+        assert!(!ui.has_message("command:"));
+    }
+
+    #[test]
+    fn outdated_warns_when_template_is_gone() {
+        let temp = setup_ejected_project("1.1.0", "deploy");
+        std::fs::remove_file(temp.path().join(".bivvy/templates/steps/deploy.yml")).unwrap();
+        let mut ui = MockUI::new();
+
+        let result = outdated(&temp, &mut ui);
+
+        assert!(result.success);
+        assert!(ui.has_warning("no longer available"));
+    }
 }
@@ -1,12 +1,14 @@
 //! Update command implementation.
 //!
 //! The `bivvy update` command checks for and installs updates.
-//! It also provides flags to enable or disable automatic background updates.
+//! It also provides flags to enable or disable automatic background updates
+//! and to roll back to the binary replaced by the last update.
 
 use crate::error::Result;
 use crate::ui::{OutputWriter, UserInterface};
 use crate::updates::{
-    auto_update::is_auto_update_enabled, check_for_updates_fresh, detect_install_method, VERSION,
+    auto_update::is_auto_update_enabled, check_for_updates_fresh, detect_install_method,
+    install_verified_update, previous_binary, rollback, InstallMethod, UpdatePreferences, VERSION,
 };
 
 use super::dispatcher::{Command, CommandResult};
@@ -25,6 +27,10 @@ pub struct UpdateArgs {
     /// Disable automatic background updates
     #[arg(long, conflicts_with = "enable_auto_update")]
     pub disable_auto_update: bool,
+
+    /// Restore the binary replaced by the last update
+    #[arg(long, conflicts_with_all = ["check", "enable_auto_update", "disable_auto_update"])]
+    pub rollback: bool,
 }
 
 /// The update command implementation.
@@ -48,6 +54,9 @@ impl Command for UpdateCommand {
         if self.args.disable_auto_update {
             return set_auto_update(ui, false);
         }
+        if self.args.rollback {
+            return rollback_update(ui);
+        }
 
         ui.message(&format!("Current version: {}", VERSION));
 
@@ -58,6 +67,16 @@ impl Command for UpdateCommand {
             ui.message("Auto-update: disabled");
         }
 
+        let prefs = UpdatePreferences::load();
+        ui.message(&format!("Update channel: {}", prefs.describe()));
+
+        if let Some(previous) = previous_binary() {
+            ui.message(&format!(
+                "Rollback available: v{} (bivvy update --rollback)",
+                previous.version
+            ));
+        }
+
         ui.message("Checking for updates...");
 
         let info = match check_for_updates_fresh() {
@@ -87,6 +106,30 @@ impl Command for UpdateCommand {
 
         let method = detect_install_method();
 
+        if matches!(method, InstallMethod::Manual { .. }) {
+            ui.message("Downloading and verifying release binary...");
+            if matches!(crate::updates::verify::release_public_key(), Ok(None)) {
+                ui.warning(
+                    "This build has no release signing key; the download is checked against the release's SHA256SUMS only.",
+                );
+            }
+            return match install_verified_update(&info) {
+                Ok(Some(version)) => {
+                    ui.success(&format!("Updated to v{}", version));
+                    ui.message("Run `bivvy update --rollback` to restore the previous version.");
+                    Ok(CommandResult::success())
+                }
+                Ok(None) => {
+                    ui.error("Update was downloaded but could not be installed.");
+                    Ok(CommandResult::failure(1))
+                }
+                Err(e) => {
+                    ui.error(&format!("Update failed: {:#}", e));
+                    Ok(CommandResult::failure(1))
+                }
+            };
+        }
+
         if !method.supports_auto_update() {
             ui.message(&format!(
                 "Auto-update is not supported for {} installs.",
@@ -98,7 +141,19 @@ impl Command for UpdateCommand {
             return Ok(CommandResult::success());
         }
 
-        let update_cmd = method.update_command().unwrap();
+        let Some(update_cmd) =
+            method.update_command_for(&info.latest, prefs.follows_latest_stable())
+        else {
+            ui.message(&format!(
+                "{} installs only receive stable releases; cannot install v{}.",
+                method.name(),
+                info.latest
+            ));
+            if let Some(url) = &info.release_url {
+                ui.message(&format!("Download it from: {}", url));
+            }
+            return Ok(CommandResult::success());
+        };
         ui.message(&format!("Updating via: {}", update_cmd));
 
         let parts: Vec<&str> = update_cmd.split_whitespace().collect();
@@ -116,6 +171,24 @@ impl Command for UpdateCommand {
     }
 }
 
+/// Restore the binary kept by the last self-update.
+fn rollback_update(ui: &mut dyn OutputWriter) -> Result<CommandResult> {
+    match rollback() {
+        Ok(Some(version)) => {
+            ui.message(&format!("Rolled back to v{}.", version));
+            Ok(CommandResult::success())
+        }
+        Ok(None) => {
+            ui.error("No previous binary to roll back to.");
+            Ok(CommandResult::failure(1))
+        }
+        Err(e) => {
+            ui.error(&format!("Rollback failed: {:#}", e));
+            Ok(CommandResult::failure(1))
+        }
+    }
+}
+
 /// Write the auto_update setting to the system config at `~/.bivvy/config.yml`.
 ///
 /// Only requires `OutputWriter` — displays confirmation messages but does not prompt.
@@ -194,6 +267,7 @@ mod tests {
         assert!(!cmd.args.check);
         assert!(!cmd.args.enable_auto_update);
         assert!(!cmd.args.disable_auto_update);
+        assert!(!cmd.args.rollback);
     }
 
     #[test]
@@ -0,0 +1,653 @@
+//! Pre-supplied prompt answers for non-interactive runs.
+//!
+//! CI and provisioning scripts answer step prompts ahead of time with an
+//! answers file (`bivvy run --answers answers.yml`) and/or repeatable
+//! `--set step.prompt_key=value` flags. Answers are keyed by step name and
+//! prompt key:
+//!
+//! ```yaml
+//! deploy:
+//!   region: eu-west-1
+//!   migrate: true
+//!   features: [api, worker]
+//! ```
+//!
+//! Every answer is validated against the prompt it targets (its
+//! [`PromptType`] and declared options) before any step runs.
+//!
+//! # Example
+//!
+//! ```
+//! use bivvy::config::answers::PromptAnswers;
+//!
+//! let mut answers = PromptAnswers::parse_yaml("deploy:\n  region: eu-west-1\n").unwrap();
+//! answers.apply_set("deploy.migrate=true").unwrap();
+//!
+//! assert_eq!(answers.get("deploy", "region"), Some("eu-west-1"));
+//! assert_eq!(answers.get("deploy", "migrate"), Some("true"));
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::config::schema::{PromptConfig, PromptType};
+use crate::error::{BivvyError, Result};
+use crate::steps::ResolvedStep;
+
+/// Prompt answers keyed by step name, then prompt key.
+///
+/// Values are stored in their interpolation form: booleans as
+/// `true`/`false`, multiselect lists comma-joined.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromptAnswers {
+    answers: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+/// A prompt that has no answer, default, or environment override.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnansweredPrompt {
+    /// Step that declares the prompt.
+    pub step: String,
+    /// Prompt key.
+    pub key: String,
+    /// Question text shown when prompting interactively.
+    pub question: String,
+}
+
+impl PromptAnswers {
+    /// Create an empty answer set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load answers from a YAML file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| BivvyError::ConfigParseError {
+            path: path.to_path_buf(),
+            message: format!("Failed to read answers file: {}", e),
+        })?;
+        Self::parse_yaml(&content).map_err(|e| BivvyError::ConfigParseError {
+            path: path.to_path_buf(),
+            message: match e {
+                BivvyError::ConfigValidationError { message } => message,
+                other => other.to_string(),
+            },
+        })
+    }
+
+    /// Parse answers from YAML content.
+    ///
+    /// The document must be a mapping of step names to mappings of prompt
+    /// keys to scalar or list values. `null` values are treated as
+    /// "not answered" so a template from `--dump-prompts` can be filled in
+    /// incrementally.
+    pub fn parse_yaml(content: &str) -> Result<Self> {
+        let mut answers = Self::new();
+        if content.trim().is_empty() {
+            return Ok(answers);
+        }
+
+        let doc: serde_yaml::Value =
+            serde_yaml::from_str(content).map_err(|e| BivvyError::ConfigValidationError {
+                message: format!("Invalid answers YAML: {}", e),
+            })?;
+
+        let steps = match doc {
+            serde_yaml::Value::Null => return Ok(answers),
+            serde_yaml::Value::Mapping(m) => m,
+            _ => {
+                return Err(BivvyError::ConfigValidationError {
+                    message: "Answers file must be a mapping of step names to prompt answers"
+                        .to_string(),
+                })
+            }
+        };
+
+        for (step, prompts) in steps {
+            let step = yaml_key(&step)?;
+            let prompts = match prompts {
+                serde_yaml::Value::Null => continue,
+                serde_yaml::Value::Mapping(m) => m,
+                _ => {
+                    return Err(BivvyError::ConfigValidationError {
+                        message: format!(
+                            "Answers for step '{}' must be a mapping of prompt keys to values",
+                            step
+                        ),
+                    })
+                }
+            };
+            for (key, value) in prompts {
+                let key = yaml_key(&key)?;
+                if let Some(value) = answer_value(&step, &key, &value)? {
+                    answers.insert(&step, &key, &value);
+                }
+            }
+        }
+
+        Ok(answers)
+    }
+
+    /// Apply a `--set step.prompt_key=value` override.
+    ///
+    /// Later calls win over earlier ones and over answers loaded from a file.
+    pub fn apply_set(&mut self, assignment: &str) -> Result<()> {
+        let invalid = || BivvyError::ConfigValidationError {
+            message: format!(
+                "Invalid --set '{}'. Expected <step>.<prompt_key>=<value>",
+                assignment
+            ),
+        };
+
+        let (path, value) = assignment.split_once('=').ok_or_else(invalid)?;
+        let (step, key) = path.trim().split_once('.').ok_or_else(invalid)?;
+        if step.is_empty() || key.is_empty() {
+            return Err(invalid());
+        }
+
+        self.insert(step, key, value);
+        Ok(())
+    }
+
+    /// Record an answer, replacing any previous value.
+    pub fn insert(&mut self, step: &str, key: &str, value: &str) {
+        self.answers
+            .entry(step.to_string())
+            .or_default()
+            .insert(key.to_string(), value.to_string());
+    }
+
+    /// Look up the answer for a step's prompt.
+    pub fn get(&self, step: &str, key: &str) -> Option<&str> {
+        self.answers
+            .get(step)
+            .and_then(|prompts| prompts.get(key))
+            .map(|s| s.as_str())
+    }
+
+    /// Whether no answers have been supplied.
+    pub fn is_empty(&self) -> bool {
+        self.answers.values().all(|prompts| prompts.is_empty())
+    }
+
+    /// Validate every answer against the prompts declared by `steps`.
+    ///
+    /// Answers are normalized in place (e.g. `yes` becomes `true` for a
+    /// confirm prompt). All problems are collected and reported together.
+    pub fn validate(&mut self, steps: &HashMap<String, ResolvedStep>) -> Result<()> {
+        let mut problems = Vec::new();
+
+        for (step_name, prompts) in &mut self.answers {
+            let Some(step) = steps.get(step_name) else {
+                problems.push(format!("{}: unknown step", step_name));
+                continue;
+            };
+            for (key, value) in prompts.iter_mut() {
+                let Some(prompt) = step.output.prompts.iter().find(|p| &p.key == key) else {
+                    problems.push(format!(
+                        "{}.{}: step '{}' has no prompt '{}'",
+                        step_name, key, step_name, key
+                    ));
+                    continue;
+                };
+                match normalize_answer(prompt, value) {
+                    Ok(normalized) => *value = normalized,
+                    Err(message) => problems.push(format!("{}.{}: {}", step_name, key, message)),
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(BivvyError::ConfigValidationError {
+                message: format!(
+                    "Invalid prompt answers:\n{}",
+                    problems
+                        .iter()
+                        .map(|p| format!("  - {}", p))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ),
+            })
+        }
+    }
+
+    /// Collect prompts in `step_names` that nothing will answer.
+    ///
+    /// A prompt counts as answered when it has an entry in this answer
+    /// set, a config default, an environment override (`KEY=value`), or
+    /// when `is_resolvable` reports the key is already known (e.g. from
+    /// `vars:`).
+    pub fn unanswered(
+        &self,
+        step_names: &[String],
+        steps: &HashMap<String, ResolvedStep>,
+        is_resolvable: impl Fn(&str) -> bool,
+    ) -> Vec<UnansweredPrompt> {
+        let mut missing = Vec::new();
+        for step_name in step_names {
+            let Some(step) = steps.get(step_name) else {
+                continue;
+            };
+            for prompt in &step.output.prompts {
+                let answered = self.get(step_name, &prompt.key).is_some()
+                    || prompt.default.is_some()
+                    || std::env::var(prompt.key.to_uppercase()).is_ok()
+                    || is_resolvable(&prompt.key);
+                if !answered {
+                    missing.push(UnansweredPrompt {
+                        step: step_name.clone(),
+                        key: prompt.key.clone(),
+                        question: prompt.question.clone(),
+                    });
+                }
+            }
+        }
+        missing
+    }
+}
+
+/// Build the error reported when prompts are left unanswered.
+pub fn unanswered_error(missing: &[UnansweredPrompt]) -> BivvyError {
+    let noun = if missing.len() == 1 {
+        "prompt"
+    } else {
+        "prompts"
+    };
+    let lines: Vec<String> = missing
+        .iter()
+        .map(|p| format!("  - {}.{} ({})", p.step, p.key, p.question))
+        .collect();
+    BivvyError::ConfigValidationError {
+        message: format!(
+            "{} {} unanswered in non-interactive mode:\n{}\n\
+             Provide answers with --answers <file> or --set <step>.<key>=<value>. \
+             Run 'bivvy run --dump-prompts' to generate an answers template.",
+            missing.len(),
+            noun,
+            lines.join("\n")
+        ),
+    }
+}
+
+/// Render an answers-file template for the prompts in `step_names`.
+///
+/// Each prompt is written with its question, type, and options as a
+/// comment, and its default (or `~` when there is none) as the value.
+pub fn dump_prompts(step_names: &[String], steps: &HashMap<String, ResolvedStep>) -> String {
+    let mut out = String::from("# Prompt answers for `bivvy run --answers <file>`\n");
+    let mut any = false;
+
+    for step_name in step_names {
+        let Some(step) = steps.get(step_name) else {
+            continue;
+        };
+        if step.output.prompts.is_empty() {
+            continue;
+        }
+        any = true;
+        out.push_str(&format!("{}:\n", step_name));
+        for prompt in &step.output.prompts {
+            out.push_str(&format!("  # {}\n", prompt.question));
+            out.push_str(&format!(
+                "  # type: {}",
+                prompt_type_name(&prompt.prompt_type)
+            ));
+            if !prompt.options.is_empty() {
+                let values: Vec<&str> = prompt.options.iter().map(|o| o.value.as_str()).collect();
+                out.push_str(&format!(" ({})", values.join(", ")));
+            }
+            out.push('\n');
+            out.push_str(&format!("  {}: {}\n", prompt.key, template_value(prompt)));
+        }
+    }
+
+    if !any {
+        out.push_str("# No steps in this run declare prompts.\n");
+    }
+    out
+}
+
+fn prompt_type_name(prompt_type: &PromptType) -> &'static str {
+    match prompt_type {
+        PromptType::Select => "select",
+        PromptType::Multiselect => "multiselect",
+        PromptType::Confirm => "confirm",
+        PromptType::Input => "input",
+        PromptType::Secret => "secret",
+        PromptType::Number => "number",
+        PromptType::Path => "path",
+    }
+}
+
+fn template_value(prompt: &PromptConfig) -> String {
+    match &prompt.default {
+        None | Some(serde_yaml::Value::Null) => "~".to_string(),
+        Some(value) => serde_yaml::to_string(value)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_else(|_| "~".to_string()),
+    }
+}
+
+fn yaml_key(key: &serde_yaml::Value) -> Result<String> {
+    match key {
+        serde_yaml::Value::String(s) => Ok(s.clone()),
+        serde_yaml::Value::Number(n) => Ok(n.to_string()),
+        serde_yaml::Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(BivvyError::ConfigValidationError {
+            message: format!("Invalid key in answers file: {:?}", key),
+        }),
+    }
+}
+
+/// Convert a YAML answer into its interpolation string, or `None` for null.
+fn answer_value(step: &str, key: &str, value: &serde_yaml::Value) -> Result<Option<String>> {
+    let scalar = |v: &serde_yaml::Value| match v {
+        serde_yaml::Value::String(s) => Some(s.clone()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    };
+
+    match value {
+        serde_yaml::Value::Null => Ok(None),
+        serde_yaml::Value::Sequence(items) => {
+            let values: Option<Vec<String>> = items.iter().map(scalar).collect();
+            values
+                .map(|v| Some(v.join(",")))
+                .ok_or_else(|| BivvyError::ConfigValidationError {
+                    message: format!("Answer for {}.{} must be a list of scalars", step, key),
+                })
+        }
+        other => scalar(other)
+            .map(Some)
+            .ok_or_else(|| BivvyError::ConfigValidationError {
+                message: format!(
+                    "Answer for {}.{} must be a string, number, boolean, or list",
+                    step, key
+                ),
+            }),
+    }
+}
+
+/// Check an answer against its prompt, returning the normalized value.
+fn normalize_answer(prompt: &PromptConfig, value: &str) -> std::result::Result<String, String> {
+    let allowed = || {
+        prompt
+            .options
+            .iter()
+            .map(|o| o.value.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match prompt.prompt_type {
+        PromptType::Confirm => match value.trim().to_lowercase().as_str() {
+            "true" | "yes" | "y" => Ok("true".to_string()),
+            "false" | "no" | "n" => Ok("false".to_string()),
+            _ => Err(format!(
+                "'{}' is not a valid confirm answer (expected true or false)",
+                value
+            )),
+        },
+        PromptType::Select => {
+            if prompt.options.is_empty() || prompt.options.iter().any(|o| o.value == value) {
+                Ok(value.to_string())
+            } else {
+                Err(format!(
+                    "'{}' is not one of the options ({})",
+                    value,
+                    allowed()
+                ))
+            }
+        }
+        PromptType::Multiselect => {
+            let values: Vec<&str> = value
+                .split(',')
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .collect();
+            if let Some(bad) = values.iter().find(|v| {
+                !prompt.options.is_empty() && !prompt.options.iter().any(|o| o.value == **v)
+            }) {
+                return Err(format!(
+                    "'{}' is not one of the options ({})",
+                    bad,
+                    allowed()
+                ));
+            }
+            Ok(values.join(","))
+        }
+        PromptType::Input | PromptType::Secret | PromptType::Number | PromptType::Path => {
+            crate::ui::Prompt::from(prompt)
+                .prompt_type
+                .validate(value)?;
+            Ok(value.to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::{PromptOption, StepConfig, StepOutputSettings};
+
+    fn prompt(key: &str, prompt_type: PromptType, options: &[&str]) -> PromptConfig {
+        PromptConfig {
+            key: key.to_string(),
+            question: format!("{}?", key),
+            prompt_type,
+            options: options
+                .iter()
+                .map(|v| PromptOption {
+                    label: v.to_string(),
+                    value: v.to_string(),
+                })
+                .collect(),
+            default: None,
+            min: None,
+            max: None,
+            pattern: None,
+            pattern_message: None,
+            must_be: None,
+        }
+    }
+
+    fn steps_with(prompts: Vec<PromptConfig>) -> HashMap<String, ResolvedStep> {
+        let config = StepConfig {
+            output_settings: StepOutputSettings {
+                prompts,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let mut steps = HashMap::new();
+        steps.insert(
+            "deploy".to_string(),
+            ResolvedStep::from_config("deploy", &config, None),
+        );
+        steps
+    }
+
+    #[test]
+    fn parse_yaml_flattens_scalars_and_lists() {
+        let answers = PromptAnswers::parse_yaml(
+            "deploy:\n  region: eu\n  migrate: true\n  replicas: 3\n  features: [api, worker]\n",
+        )
+        .unwrap();
+        assert_eq!(answers.get("deploy", "region"), Some("eu"));
+        assert_eq!(answers.get("deploy", "migrate"), Some("true"));
+        assert_eq!(answers.get("deploy", "replicas"), Some("3"));
+        assert_eq!(answers.get("deploy", "features"), Some("api,worker"));
+    }
+
+    #[test]
+    fn parse_yaml_skips_null_answers() {
+        let answers = PromptAnswers::parse_yaml("deploy:\n  region: ~\n").unwrap();
+        assert_eq!(answers.get("deploy", "region"), None);
+        assert!(answers.is_empty());
+    }
+
+    #[test]
+    fn parse_yaml_rejects_non_mapping_step() {
+        let err = PromptAnswers::parse_yaml("deploy: eu\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Answers for step 'deploy' must be a mapping of prompt keys to values"
+        );
+    }
+
+    #[test]
+    fn apply_set_overrides_file_answer() {
+        let mut answers = PromptAnswers::parse_yaml("deploy:\n  region: eu\n").unwrap();
+        answers.apply_set("deploy.region=us").unwrap();
+        assert_eq!(answers.get("deploy", "region"), Some("us"));
+    }
+
+    #[test]
+    fn apply_set_keeps_equals_in_value() {
+        let mut answers = PromptAnswers::new();
+        answers.apply_set("deploy.flags=a=b").unwrap();
+        assert_eq!(answers.get("deploy", "flags"), Some("a=b"));
+    }
+
+    #[test]
+    fn apply_set_rejects_missing_step() {
+        let mut answers = PromptAnswers::new();
+        let err = answers.apply_set("region=eu").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid --set 'region=eu'. Expected <step>.<prompt_key>=<value>"
+        );
+    }
+
+    #[test]
+    fn validate_normalizes_confirm() {
+        let steps = steps_with(vec![prompt("migrate", PromptType::Confirm, &[])]);
+        let mut answers = PromptAnswers::new();
+        answers.insert("deploy", "migrate", "yes");
+        answers.validate(&steps).unwrap();
+        assert_eq!(answers.get("deploy", "migrate"), Some("true"));
+    }
+
+    #[test]
+    fn validate_reports_every_problem() {
+        let steps = steps_with(vec![
+            prompt("region", PromptType::Select, &["us", "eu"]),
+            prompt("features", PromptType::Multiselect, &["api", "worker"]),
+        ]);
+        let mut answers = PromptAnswers::new();
+        answers.insert("deploy", "region", "ap");
+        answers.insert("deploy", "features", "api,cron");
+        answers.insert("deploy", "typo", "x");
+        answers.insert("missing", "key", "x");
+
+        let err = answers.validate(&steps).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid prompt answers:\n\
+             \x20 - deploy.features: 'cron' is not one of the options (api, worker)\n\
+             \x20 - deploy.region: 'ap' is not one of the options (us, eu)\n\
+             \x20 - deploy.typo: step 'deploy' has no prompt 'typo'\n\
+             \x20 - missing: unknown step"
+        );
+    }
+
+    #[test]
+    fn validate_checks_number_and_pattern_constraints() {
+        let mut replicas = prompt("replicas", PromptType::Number, &[]);
+        replicas.min = Some(1.0);
+        replicas.max = Some(5.0);
+        let mut slug = prompt("slug", PromptType::Input, &[]);
+        slug.pattern = Some("[a-z-]+".to_string());
+        slug.pattern_message = Some("use lowercase letters and dashes".to_string());
+        let steps = steps_with(vec![replicas, slug]);
+
+        let mut answers = PromptAnswers::new();
+        answers.insert("deploy", "replicas", "9");
+        answers.insert("deploy", "slug", "My App");
+
+        let err = answers.validate(&steps).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid prompt answers:\n\
+             \x20 - deploy.replicas: must be at most 5\n\
+             \x20 - deploy.slug: use lowercase letters and dashes"
+        );
+    }
+
+    #[test]
+    fn unanswered_lists_prompts_without_values() {
+        let mut with_default = prompt("mode", PromptType::Input, &[]);
+        with_default.default = Some(serde_yaml::Value::String("dev".to_string()));
+        let steps = steps_with(vec![
+            prompt("bivvy_test_answers_region", PromptType::Input, &[]),
+            prompt("bivvy_test_answers_known", PromptType::Input, &[]),
+            prompt("bivvy_test_answers_set", PromptType::Input, &[]),
+            with_default,
+        ]);
+        let mut answers = PromptAnswers::new();
+        answers.insert("deploy", "bivvy_test_answers_set", "x");
+
+        let missing = answers.unanswered(&["deploy".to_string()], &steps, |key| {
+            key == "bivvy_test_answers_known"
+        });
+        assert_eq!(
+            missing,
+            vec![UnansweredPrompt {
+                step: "deploy".to_string(),
+                key: "bivvy_test_answers_region".to_string(),
+                question: "bivvy_test_answers_region?".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn unanswered_error_lists_prompts() {
+        let err = unanswered_error(&[
+            UnansweredPrompt {
+                step: "deploy".to_string(),
+                key: "region".to_string(),
+                question: "Which region?".to_string(),
+            },
+            UnansweredPrompt {
+                step: "seed".to_string(),
+                key: "size".to_string(),
+                question: "How much data?".to_string(),
+            },
+        ]);
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: 2 prompts unanswered in non-interactive mode:\n\
+             \x20 - deploy.region (Which region?)\n\
+             \x20 - seed.size (How much data?)\n\
+             Provide answers with --answers <file> or --set <step>.<key>=<value>. \
+             Run 'bivvy run --dump-prompts' to generate an answers template."
+        );
+    }
+
+    #[test]
+    fn dump_prompts_round_trips_through_parse() {
+        let mut region = prompt("region", PromptType::Select, &["us", "eu"]);
+        region.default = Some(serde_yaml::Value::String("us".to_string()));
+        let steps = steps_with(vec![region, prompt("name", PromptType::Input, &[])]);
+
+        let template = dump_prompts(&["deploy".to_string()], &steps);
+        assert_eq!(
+            template,
+            "# Prompt answers for `bivvy run --answers <file>`\n\
+             deploy:\n\
+             \x20 # region?\n\
+             \x20 # type: select (us, eu)\n\
+             \x20 region: us\n\
+             \x20 # name?\n\
+             \x20 # type: input\n\
+             \x20 name: ~\n"
+        );
+
+        let parsed = PromptAnswers::parse_yaml(&template).unwrap();
+        assert_eq!(parsed.get("deploy", "region"), Some("us"));
+        assert_eq!(parsed.get("deploy", "name"), None);
+    }
+}
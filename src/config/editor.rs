@@ -0,0 +1,1668 @@
+//! Format-preserving config editing.
+//!
+//! [`ConfigEditor`] changes a YAML document in place. It finds keys and
+//! list items from the positions `marked-yaml` reports and rewrites only
+//! the text they occupy, so comments, blank lines, quoting and the layout
+//! of everything else are kept. New entries follow the indentation and
+//! blank-line spacing of their siblings, and flow lists (`[a, b]`) stay
+//! flow lists.
+//!
+//! Paths are lists of mapping keys and list indices, such as
+//! `["steps", "build", "depends_on", "0"]`.
+
+use std::fmt;
+
+use marked_yaml::types::{MarkedMappingNode, MarkedScalarNode, MarkedSequenceNode};
+use marked_yaml::{parse_yaml_with_options, LoaderOptions, Node};
+use serde_yaml::{Mapping, Value};
+
+use crate::error::{BivvyError, Result};
+
+/// Indentation used when the document doesn't show its own.
+const DEFAULT_INDENT: usize = 2;
+
+/// A change to a config document, addressed by path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConfigEdit {
+    /// Set the value at a path, creating any missing mappings.
+    Set {
+        /// Path to the key or list item.
+        path: Vec<String>,
+        /// New value.
+        value: Value,
+    },
+    /// Remove a key or list item.
+    Remove {
+        /// Path to the key or list item.
+        path: Vec<String>,
+    },
+    /// Remove the items of a list that equal a string.
+    RemoveItem {
+        /// Path to the list.
+        path: Vec<String>,
+        /// Item to remove.
+        item: String,
+    },
+    /// Rename a mapping key, keeping its value.
+    RenameKey {
+        /// Path to the key.
+        path: Vec<String>,
+        /// New key.
+        to: String,
+    },
+}
+
+impl ConfigEdit {
+    /// The path the edit changes.
+    pub fn path(&self) -> &[String] {
+        match self {
+            Self::Set { path, .. }
+            | Self::Remove { path }
+            | Self::RemoveItem { path, .. }
+            | Self::RenameKey { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for ConfigEdit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Set { path, value } => {
+                let value = inline_value(value, false).unwrap_or_else(|| "{...}".to_string());
+                write!(f, "set {} to {}", path.join("."), value)
+            }
+            Self::Remove { path } => write!(f, "remove {}", path.join(".")),
+            Self::RemoveItem { path, item } => {
+                write!(f, "remove '{}' from {}", item, path.join("."))
+            }
+            Self::RenameKey { path, to } => write!(f, "rename {} to '{}'", path.join("."), to),
+        }
+    }
+}
+
+/// Split a dotted path (`steps.build.command`) into segments.
+///
+/// Segments containing dots can be quoted: `steps."db.setup".command`.
+pub fn parse_path(path: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    for c in path.chars() {
+        match c {
+            '"' => quoted = !quoted,
+            '.' if !quoted => segments.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    segments.push(current);
+    segments
+}
+
+/// A YAML config document being edited.
+#[derive(Debug, Clone)]
+pub struct ConfigEditor {
+    text: String,
+}
+
+impl ConfigEditor {
+    /// Start editing a document.
+    ///
+    /// Fails if the document isn't a YAML mapping the editor can follow.
+    /// Anchors, aliases and tags aren't supported.
+    pub fn new(text: impl Into<String>) -> Result<Self> {
+        let editor = Self { text: text.into() };
+        editor.root()?;
+        Ok(editor)
+    }
+
+    /// The document's current text.
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// The document's text, consuming the editor.
+    pub fn into_string(self) -> String {
+        self.text
+    }
+
+    /// Whether a key or list item exists at a path.
+    pub fn contains<S: AsRef<str>>(&self, path: &[S]) -> bool {
+        self.get(path).is_some()
+    }
+
+    /// The value at a path.
+    pub fn get<S: AsRef<str>>(&self, path: &[S]) -> Option<Value> {
+        let root = self.root().ok()?;
+        let walk = walk(&root, path);
+        (walk.found == path.len()).then(|| to_value(walk.node))
+    }
+
+    /// Apply an edit. Returns `false` if its path doesn't exist (for
+    /// removals and renames).
+    pub fn apply(&mut self, edit: &ConfigEdit) -> Result<bool> {
+        match edit {
+            ConfigEdit::Set { path, value } => self.set(path, value).map(|_| true),
+            ConfigEdit::Remove { path } => self.remove(path),
+            ConfigEdit::RemoveItem { path, item } => self.remove_item(path, item),
+            ConfigEdit::RenameKey { path, to } => self.rename_key(path, to),
+        }
+    }
+
+    /// Set the value at a path, creating any missing mappings.
+    ///
+    /// An existing value is replaced in place; a scalar replaced by a
+    /// scalar keeps its trailing comment. A new key is added after its
+    /// siblings.
+    pub fn set<S: AsRef<str>>(&mut self, path: &[S], value: &Value) -> Result<()> {
+        if path.is_empty() {
+            return Err(edit_error("can't replace the whole document"));
+        }
+        let root = self.root()?;
+        let walk = walk(&root, path);
+        let lines = Lines::new(&self.text);
+
+        if walk.found == path.len() {
+            let edit = match walk.target.as_ref().expect("path is not empty") {
+                Target::Entry { key, .. } if walk.parent.is_some_and(|p| is_flow(&lines, p)) => {
+                    // A value inside `{...}` ends at a comma or brace, not
+                    // the end of the line: rebuild the flow mapping
+                    let Value::Mapping(mut mapping) = to_value(walk.parent.expect("checked"))
+                    else {
+                        unreachable!("flow mapping");
+                    };
+                    mapping.insert(Value::String(key.as_str().to_string()), value.clone());
+                    return self.set(&path[..path.len() - 1], &Value::Mapping(mapping));
+                }
+                Target::Entry { key, .. } => self.replace_entry_value(&lines, key, value)?,
+                Target::Item { parent, index, .. } => {
+                    self.replace_item(&lines, parent, *index, value)?
+                }
+            };
+            return self.commit(edit);
+        }
+
+        // The path ends (or continues) below an existing node
+        let missing = &path[walk.found..];
+        let nested = nest(&missing[1..], value.clone());
+        match walk.node {
+            Node::Mapping(mapping) if !is_flow(&lines, walk.node) && !mapping.is_empty() => {
+                let key = missing[0].as_ref();
+                let edit = self.insert_entry(&lines, &root, &path[..walk.found], key, &nested)?;
+                self.commit(edit)
+            }
+            Node::Sequence(seq) if missing[0].as_ref().parse::<usize>() == Ok(seq.len()) => {
+                self.insert_item(&path[..walk.found], None, &nested)
+            }
+            Node::Sequence(_) => Err(edit_error(format!(
+                "{} is a list; use an index below its length",
+                display_path(&path[..walk.found])
+            ))),
+            _ if walk.found == 0 => {
+                // Empty document
+                let key = missing[0].as_ref();
+                let edit = self.insert_entry(&lines, &root, &path[..0], key, &nested)?;
+                self.commit(edit)
+            }
+            node => {
+                // An empty value or a flow mapping: rebuild it with the new key
+                let mut merged = match to_value(node) {
+                    Value::Mapping(m) => m,
+                    Value::Null => Mapping::new(),
+                    _ => {
+                        return Err(edit_error(format!(
+                            "{} is not a mapping",
+                            display_path(&path[..walk.found])
+                        )))
+                    }
+                };
+                merged.insert(Value::String(missing[0].as_ref().to_string()), nested);
+                self.set(&path[..walk.found], &Value::Mapping(merged))
+            }
+        }
+    }
+
+    /// Remove a key or list item, with the comments directly above it.
+    ///
+    /// Returns `false` if nothing exists at the path. Removing the last
+    /// entry of a mapping or list leaves `{}` or `[]`.
+    pub fn remove<S: AsRef<str>>(&mut self, path: &[S]) -> Result<bool> {
+        if path.is_empty() {
+            return Err(edit_error("can't remove the whole document"));
+        }
+        let root = self.root()?;
+        let walk = walk(&root, path);
+        if walk.found < path.len() {
+            return Ok(false);
+        }
+        let lines = Lines::new(&self.text);
+        let parent_path = &path[..path.len() - 1];
+
+        match walk.target.expect("path is not empty") {
+            Target::Entry { key, parent } => {
+                let parent_node = walk.parent.expect("entry has a parent");
+                if is_flow(&lines, parent_node) {
+                    let mut value = match to_value(parent_node) {
+                        Value::Mapping(m) => m,
+                        _ => unreachable!("flow mapping"),
+                    };
+                    value.remove(key.as_str());
+                    self.set(parent_path, &Value::Mapping(value))?;
+                    return Ok(true);
+                }
+                if parent.len() == 1 && !parent_path.is_empty() {
+                    self.set(parent_path, &Value::Mapping(Mapping::new()))?;
+                    return Ok(true);
+                }
+                let edit = self.remove_entry(&lines, parent, key)?;
+                self.commit(edit)?;
+                Ok(true)
+            }
+            Target::Item { parent, index } => {
+                let parent_node = walk.parent.expect("item has a parent");
+                if is_flow(&lines, parent_node) {
+                    let edit = flow_list_edit(&lines, parent_node, |items| {
+                        items.remove(index);
+                    })?;
+                    self.commit(edit)?;
+                    return Ok(true);
+                }
+                if parent.len() == 1 {
+                    self.set(parent_path, &Value::Sequence(Vec::new()))?;
+                    return Ok(true);
+                }
+                let extent = item_extent(&lines, parent.get_node(index).expect("index"))?;
+                let edit = remove_lines(&lines, extent.first_line, extent.end_line, index == 0);
+                self.commit(edit)?;
+                Ok(true)
+            }
+        }
+    }
+
+    /// Remove the items of the list at `path` that equal `item`.
+    ///
+    /// Returns `false` if there is no such list or item.
+    pub fn remove_item<S: AsRef<str>>(&mut self, path: &[S], item: &str) -> Result<bool> {
+        let mut removed = false;
+        while let Some(Value::Sequence(items)) = self.get(path) {
+            let Some(index) = items.iter().position(|v| value_matches(v, item)) else {
+                break;
+            };
+            let mut item_path: Vec<String> = path.iter().map(|s| s.as_ref().to_string()).collect();
+            item_path.push(index.to_string());
+            self.remove(&item_path)?;
+            removed = true;
+        }
+        Ok(removed)
+    }
+
+    /// Rename the key at `path`, keeping its value and position.
+    ///
+    /// Returns `false` if the key doesn't exist.
+    pub fn rename_key<S: AsRef<str>>(&mut self, path: &[S], to: &str) -> Result<bool> {
+        let root = self.root()?;
+        let walk = walk(&root, path);
+        if path.is_empty() || walk.found < path.len() {
+            return Ok(false);
+        }
+        let Some(Target::Entry { key, parent }) = walk.target else {
+            return Err(edit_error(format!(
+                "{} is a list item, not a key",
+                display_path(path)
+            )));
+        };
+        if key.as_str() == to {
+            return Ok(true);
+        }
+        if parent.get_node(to).is_some() {
+            return Err(edit_error(format!(
+                "{} already has a key '{}'",
+                display_path(&path[..path.len() - 1]),
+                to
+            )));
+        }
+        let lines = Lines::new(&self.text);
+        let start = lines.marker_offset(key)?;
+        let end = scalar_end(&self.text, start, Context::Key);
+        self.commit(Edit::replace(start, end, render_key(to)))?;
+        Ok(true)
+    }
+
+    /// Insert an item into the list at `path`, before `index` or at the
+    /// end. A missing or empty list is created.
+    pub fn insert_item<S: AsRef<str>>(
+        &mut self,
+        path: &[S],
+        index: Option<usize>,
+        value: &Value,
+    ) -> Result<()> {
+        let root = self.root()?;
+        let walk = walk(&root, path);
+        let lines = Lines::new(&self.text);
+
+        let seq = match walk.node {
+            Node::Sequence(seq) if walk.found == path.len() => seq,
+            node if walk.found < path.len() || to_value(node).is_null() => {
+                return self.set(path, &Value::Sequence(vec![value.clone()]));
+            }
+            _ => {
+                return Err(edit_error(format!("{} is not a list", display_path(path))));
+            }
+        };
+
+        if is_flow(&lines, walk.node) || seq.is_empty() {
+            let rendered = inline_value(value, true).ok_or_else(|| {
+                edit_error(format!(
+                    "can't add a nested value to the flow list at {}",
+                    display_path(path)
+                ))
+            })?;
+            let edit = flow_list_edit(&lines, walk.node, |items| {
+                let at = index.unwrap_or(items.len()).min(items.len());
+                items.insert(at, rendered);
+            })?;
+            return self.commit(edit);
+        }
+
+        let index = index.filter(|i| *i < seq.len());
+        let (line, dash) = match index {
+            Some(i) => {
+                let extent = item_extent(&lines, seq.get_node(i).expect("index"))?;
+                (extent.first_line, extent.indent)
+            }
+            None => {
+                let last = item_extent(&lines, seq.get_node(seq.len() - 1).expect("not empty"))?;
+                (last.end_line, last.indent)
+            }
+        };
+        let text = format!(
+            "{}-{}",
+            " ".repeat(dash),
+            item_value_text(value, dash, self.indent_unit())
+        );
+        self.commit(insert_lines(&lines, line, &text, false))
+    }
+
+    /// Add a comment line at the end of the mapping at `path`.
+    pub fn append_comment<S: AsRef<str>>(&mut self, path: &[S], comment: &str) -> Result<()> {
+        let root = self.root()?;
+        let walk = walk(&root, path);
+        let lines = Lines::new(&self.text);
+        let Node::Mapping(mapping) = walk.node else {
+            return Err(edit_error(format!(
+                "{} is not a mapping",
+                display_path(path)
+            )));
+        };
+        if walk.found < path.len() || mapping.is_empty() || is_flow(&lines, walk.node) {
+            return Err(edit_error(format!(
+                "{} is not a block mapping",
+                display_path(path)
+            )));
+        }
+        let (indent, end_line) = mapping_layout(&lines, mapping)?;
+        let text = format!("{}# {}", " ".repeat(indent), comment);
+        self.commit(insert_lines(&lines, end_line, &text, false))
+    }
+
+    fn root(&self) -> Result<Node> {
+        let options = LoaderOptions::default().prevent_coercion(true);
+        parse_yaml_with_options(0, &self.text, options).map_err(|e| edit_error(e.to_string()))
+    }
+
+    /// Apply a text edit, checking the result still parses.
+    fn commit(&mut self, edit: Edit) -> Result<()> {
+        let mut text = self.text.clone();
+        text.replace_range(edit.start..edit.end, &edit.text);
+        let options = LoaderOptions::default().prevent_coercion(true);
+        parse_yaml_with_options(0, &text, options)
+            .map_err(|e| edit_error(format!("the edit would produce invalid YAML ({e})")))?;
+        self.text = text;
+        Ok(())
+    }
+
+    /// Indentation step used by the document.
+    fn indent_unit(&self) -> usize {
+        self.text
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+            .map(|l| l.len() - l.trim_start_matches(' ').len())
+            .filter(|i| *i > 0)
+            .min()
+            .unwrap_or(DEFAULT_INDENT)
+    }
+
+    fn replace_entry_value(
+        &self,
+        lines: &Lines,
+        key: &MarkedScalarNode,
+        value: &Value,
+    ) -> Result<Edit> {
+        let key_start = lines.marker_offset(key)?;
+        let region = value_region(&self.text, key_start)?;
+        let (indent, _, end_line) = entry_layout(lines, key)?;
+
+        if let ValueRegion::Inline { start, end, .. } = region {
+            // Non-empty flow collections stay flow; `{}` grows into a block
+            let flow = matches!(self.text.as_bytes()[start], b'[' | b'{')
+                && &self.text[start..end] != "{}";
+            let rendered = if flow {
+                flow_value(value)
+            } else {
+                inline_value(value, false)
+            };
+            if let Some(rendered) = rendered {
+                return Ok(Edit::replace(start, end, rendered));
+            }
+        }
+        let colon = region.after_colon();
+        let end = lines.end(end_line - 1).max(colon);
+        Ok(Edit::replace(
+            colon,
+            end,
+            entry_value_text(value, indent, self.indent_unit()),
+        ))
+    }
+
+    fn replace_item(
+        &self,
+        lines: &Lines,
+        parent: &MarkedSequenceNode,
+        index: usize,
+        value: &Value,
+    ) -> Result<Edit> {
+        let node = parent.get_node(index).expect("index");
+        let start = lines.marker_offset(node)?;
+        let text = self.text.as_bytes();
+        let in_flow = matches!(enclosing_open(&self.text, start), Some(b'['));
+        if in_flow {
+            let rendered = flow_value(value)
+                .ok_or_else(|| edit_error("can't put a nested value in a flow list"))?;
+            let end = scalar_end(&self.text, start, Context::Flow);
+            return Ok(Edit::replace(start, end, rendered));
+        }
+        if let (Node::Scalar(_), Some(rendered)) = (node, inline_value(value, false)) {
+            if !matches!(text[start], b'|' | b'>') && !is_block_scalar_item(&self.text, start) {
+                let end = scalar_end(&self.text, start, Context::Block);
+                return Ok(Edit::replace(start, end, rendered));
+            }
+        }
+        let extent = item_extent(lines, node)?;
+        let dash = lines.start(extent.line) + extent.indent;
+        Ok(Edit::replace(
+            dash + 1,
+            lines.end(extent.end_line - 1),
+            item_value_text(value, extent.indent, self.indent_unit()),
+        ))
+    }
+
+    /// Add `key: value` after the last entry of the block mapping at
+    /// `path` (or the root).
+    fn insert_entry<S: AsRef<str>>(
+        &self,
+        lines: &Lines,
+        root: &Node,
+        path: &[S],
+        key: &str,
+        value: &Value,
+    ) -> Result<Edit> {
+        let walk = walk(root, path);
+        let Node::Mapping(mapping) = walk.node else {
+            unreachable!("insert_entry on a mapping");
+        };
+        let unit = self.indent_unit();
+        let entry = format!("{}:", render_key(key));
+
+        if mapping.is_empty() {
+            let text = format!("{}{}", entry, entry_value_text(value, 0, unit));
+            return Ok(insert_lines(lines, lines.count(), &text, false));
+        }
+
+        let (indent, end_line) = mapping_layout(lines, mapping)?;
+        let text = format!(
+            "{}{}{}",
+            " ".repeat(indent),
+            entry,
+            entry_value_text(value, indent, unit)
+        );
+        let multiline = text.contains('\n');
+        let spaced = self.spaced(lines, root, path, multiline)?;
+        Ok(insert_lines(lines, end_line, &text, spaced))
+    }
+
+    /// Whether a new entry in the mapping at `path` should be preceded
+    /// by a blank line, following how its siblings are separated.
+    ///
+    /// With a single sibling there's nothing to copy, so a multi-line
+    /// entry after a multi-line sibling follows the nearest ancestor
+    /// mapping that has several entries.
+    fn spaced<S: AsRef<str>>(
+        &self,
+        lines: &Lines,
+        root: &Node,
+        path: &[S],
+        multiline: bool,
+    ) -> Result<bool> {
+        let mut inherit = multiline;
+        for depth in (0..=path.len()).rev() {
+            let Node::Mapping(mapping) = walk(root, &path[..depth]).node else {
+                continue;
+            };
+            let keys: Vec<&MarkedScalarNode> = mapping.keys().collect();
+            let last = *keys
+                .iter()
+                .max_by_key(|k| marker_line(k))
+                .expect("not empty");
+            let (_, first_line, end_line) = entry_layout(lines, last)?;
+            if keys.len() >= 2 {
+                return Ok(first_line > 0 && lines.is_blank(first_line - 1));
+            }
+            // A single sibling: only multi-line entries after a multi-line
+            // sibling look further up
+            inherit = inherit && end_line - first_line > 1;
+            if !inherit {
+                return Ok(false);
+            }
+        }
+        Ok(false)
+    }
+
+    fn remove_entry(
+        &self,
+        lines: &Lines,
+        parent: &MarkedMappingNode,
+        key: &MarkedScalarNode,
+    ) -> Result<Edit> {
+        let key_start = lines.marker_offset(key)?;
+        let (_, first_line, end_line) = entry_layout(lines, key)?;
+        let line = marker_line(key);
+
+        if lines.start(line) + lines.indent(line) != key_start {
+            // The first key of a list item (`- key: value`): pull the next
+            // key up onto the dash line
+            let next = parent
+                .keys()
+                .filter(|k| marker_line(k) > line)
+                .min_by_key(|k| marker_line(k))
+                .expect("mapping has another key");
+            let next_start = lines.marker_offset(next)?;
+            return Ok(Edit::replace(key_start, next_start, String::new()));
+        }
+
+        let first_in_mapping = parent.keys().all(|k| marker_line(k) >= line);
+        Ok(remove_lines(lines, first_line, end_line, first_in_mapping))
+    }
+}
+
+/// Whether a list item equals `item` (scalars compared as text).
+fn value_matches(value: &Value, item: &str) -> bool {
+    match value {
+        Value::String(s) => s == item,
+        Value::Bool(b) => b.to_string() == item,
+        Value::Number(n) => n.to_string() == item,
+        _ => false,
+    }
+}
+
+fn edit_error(message: impl Into<String>) -> BivvyError {
+    BivvyError::ConfigEditError {
+        message: message.into(),
+    }
+}
+
+fn display_path<S: AsRef<str>>(path: &[S]) -> String {
+    if path.is_empty() {
+        return "the document".to_string();
+    }
+    path.iter()
+        .map(|s| s.as_ref())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// `value` nested under the keys of `path`.
+fn nest<S: AsRef<str>>(path: &[S], value: Value) -> Value {
+    path.iter().rev().fold(value, |value, key| {
+        let mut mapping = Mapping::new();
+        mapping.insert(Value::String(key.as_ref().to_string()), value);
+        Value::Mapping(mapping)
+    })
+}
+
+// === Locating nodes ===
+
+/// How a node hangs off its parent.
+enum Target<'n> {
+    /// A mapping entry.
+    Entry {
+        key: &'n MarkedScalarNode,
+        parent: &'n MarkedMappingNode,
+    },
+    /// A list item.
+    Item {
+        parent: &'n MarkedSequenceNode,
+        index: usize,
+    },
+}
+
+/// How far a path resolves.
+struct Walk<'n> {
+    /// Number of segments found.
+    found: usize,
+    /// Node at the found prefix.
+    node: &'n Node,
+    /// How `node` hangs off its parent (`None` for the root).
+    target: Option<Target<'n>>,
+    /// The parent of `node`.
+    parent: Option<&'n Node>,
+}
+
+fn walk<'n, S: AsRef<str>>(root: &'n Node, path: &[S]) -> Walk<'n> {
+    let mut walk = Walk {
+        found: 0,
+        node: root,
+        target: None,
+        parent: None,
+    };
+    for segment in path {
+        let segment = segment.as_ref();
+        let next = match walk.node {
+            Node::Mapping(mapping) => {
+                mapping
+                    .iter()
+                    .find(|(k, _)| k.as_str() == segment)
+                    .map(|(key, value)| {
+                        (
+                            value,
+                            Target::Entry {
+                                key,
+                                parent: mapping,
+                            },
+                        )
+                    })
+            }
+            Node::Sequence(seq) => segment.parse::<usize>().ok().and_then(|index| {
+                seq.get_node(index)
+                    .map(|value| (value, Target::Item { parent: seq, index }))
+            }),
+            Node::Scalar(_) => None,
+        };
+        let Some((node, target)) = next else {
+            break;
+        };
+        walk.parent = Some(walk.node);
+        walk.node = node;
+        walk.target = Some(target);
+        walk.found += 1;
+    }
+    walk
+}
+
+/// Convert a parsed node into a YAML value.
+fn to_value(node: &Node) -> Value {
+    match node {
+        Node::Scalar(scalar) if scalar.may_coerce() => {
+            if scalar.as_str().is_empty() {
+                Value::Null
+            } else {
+                serde_yaml::from_str(scalar.as_str())
+                    .unwrap_or_else(|_| Value::String(scalar.as_str().to_string()))
+            }
+        }
+        Node::Scalar(scalar) => Value::String(scalar.as_str().to_string()),
+        Node::Sequence(seq) => Value::Sequence(seq.iter().map(to_value).collect()),
+        Node::Mapping(mapping) => Value::Mapping(
+            mapping
+                .iter()
+                .map(|(k, v)| (Value::String(k.as_str().to_string()), to_value(v)))
+                .collect(),
+        ),
+    }
+}
+
+fn marker_line(node: &MarkedScalarNode) -> usize {
+    node.span().start().map(|m| m.line() - 1).unwrap_or(0)
+}
+
+/// Whether a collection is written in flow style (`[...]` or `{...}`).
+fn is_flow(lines: &Lines, node: &Node) -> bool {
+    match node {
+        Node::Scalar(_) => false,
+        _ => lines
+            .marker_offset(node)
+            .ok()
+            .and_then(|offset| lines.text.as_bytes().get(offset))
+            .is_some_and(|c| matches!(c, b'[' | b'{')),
+    }
+}
+
+/// The bracket enclosing `offset` on its line, if it's inside one.
+fn enclosing_open(text: &str, offset: usize) -> Option<u8> {
+    let line_start = text[..offset].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let mut depth: Vec<u8> = Vec::new();
+    let bytes = text.as_bytes();
+    let mut i = line_start;
+    while i < offset {
+        match bytes[i] {
+            b'[' | b'{' => depth.push(bytes[i]),
+            b']' | b'}' => {
+                depth.pop();
+            }
+            b'"' | b'\'' => i = scalar_end(text, i, Context::Flow) - 1,
+            _ => {}
+        }
+        i += 1;
+    }
+    depth.last().copied()
+}
+
+fn is_block_scalar_item(text: &str, start: usize) -> bool {
+    let line_start = text[..start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let prefix = text[line_start..start].trim();
+    prefix.ends_with('|') || prefix.ends_with('>')
+}
+
+// === Text geometry ===
+
+/// Line offsets of a document.
+struct Lines<'a> {
+    text: &'a str,
+    starts: Vec<usize>,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Self {
+        let mut starts = vec![0];
+        starts.extend(text.match_indices('\n').map(|(i, _)| i + 1));
+        if starts.last() == Some(&text.len()) {
+            starts.pop();
+        }
+        Self { text, starts }
+    }
+
+    fn count(&self) -> usize {
+        self.starts.len()
+    }
+
+    fn start(&self, line: usize) -> usize {
+        self.starts.get(line).copied().unwrap_or(self.text.len())
+    }
+
+    /// Offset of the end of a line's content (before its newline).
+    fn end(&self, line: usize) -> usize {
+        let next = self.start(line + 1);
+        if next > 0 && self.text.as_bytes().get(next - 1) == Some(&b'\n') && next > self.start(line)
+        {
+            next - 1
+        } else {
+            next
+        }
+    }
+
+    fn line(&self, line: usize) -> &'a str {
+        &self.text[self.start(line)..self.end(line)]
+    }
+
+    fn indent(&self, line: usize) -> usize {
+        let l = self.line(line);
+        l.len() - l.trim_start_matches(' ').len()
+    }
+
+    fn is_blank(&self, line: usize) -> bool {
+        self.line(line).trim().is_empty()
+    }
+
+    fn is_comment(&self, line: usize) -> bool {
+        self.line(line).trim_start().starts_with('#')
+    }
+
+    /// Byte offset of a node's start marker.
+    fn marker_offset(&self, node: &impl Spanned) -> Result<usize> {
+        let marker = node
+            .start_marker()
+            .ok_or_else(|| edit_error("missing source position"))?;
+        let (line, column) = marker;
+        let start = self.start(line - 1);
+        let chars = self.text[start..]
+            .char_indices()
+            .nth(column - 1)
+            .map(|(i, _)| i)
+            .unwrap_or(self.text.len() - start);
+        Ok(start + chars)
+    }
+}
+
+/// Nodes with a start marker.
+trait Spanned {
+    fn start_marker(&self) -> Option<(usize, usize)>;
+}
+
+impl Spanned for Node {
+    fn start_marker(&self) -> Option<(usize, usize)> {
+        self.span().start().map(|m| (m.line(), m.column()))
+    }
+}
+
+impl Spanned for MarkedScalarNode {
+    fn start_marker(&self) -> Option<(usize, usize)> {
+        self.span().start().map(|m| (m.line(), m.column()))
+    }
+}
+
+/// End (exclusive) of the block that starts on `line` at `indent`: the
+/// last following line indented deeper, skipping blank lines and
+/// comments. A `- ` item at the same indent continues the block when
+/// `dash_continues` (a key whose value is a list written at its own
+/// indentation).
+fn block_end(lines: &Lines, line: usize, indent: usize, dash_continues: bool) -> usize {
+    let mut end = line + 1;
+    for i in line + 1..lines.count() {
+        if lines.is_blank(i) {
+            continue;
+        }
+        let content = lines.line(i).trim_start();
+        let line_indent = lines.indent(i);
+        let dash = content == "-" || content.starts_with("- ");
+        if line_indent > indent || (dash_continues && line_indent == indent && dash) {
+            end = i + 1;
+        } else if !lines.is_comment(i) {
+            break;
+        }
+    }
+    end
+}
+
+/// First line of the comments directly above `line` at `indent`.
+fn leading_comments(lines: &Lines, line: usize, indent: usize) -> usize {
+    let mut first = line;
+    while first > 0 && lines.is_comment(first - 1) && lines.indent(first - 1) == indent {
+        first -= 1;
+    }
+    first
+}
+
+/// Indentation, first line (with leading comments) and end line of the
+/// entry for `key`.
+fn entry_layout(lines: &Lines, key: &MarkedScalarNode) -> Result<(usize, usize, usize)> {
+    let start = lines.marker_offset(key)?;
+    let line = marker_line(key);
+    let indent = start - lines.start(line);
+    let region = value_region(lines.text, start)?;
+    let dash_continues = matches!(region, ValueRegion::Block { .. });
+    let mut end = block_end(lines, line, indent, dash_continues);
+    if let ValueRegion::Inline { end: value_end, .. } = region {
+        // Multi-line quoted or flow values
+        while end < lines.count() && lines.start(end) < value_end {
+            end += 1;
+        }
+    }
+    let line_leading = lines.indent(line) == indent;
+    let first = if line_leading {
+        leading_comments(lines, line, indent)
+    } else {
+        line
+    };
+    Ok((indent, first, end))
+}
+
+/// Indentation of a block mapping's keys and the end line of its last
+/// entry.
+fn mapping_layout(lines: &Lines, mapping: &MarkedMappingNode) -> Result<(usize, usize)> {
+    let mut indent = None;
+    let mut end_line = 0;
+    for key in mapping.keys() {
+        let (key_indent, _, end) = entry_layout(lines, key)?;
+        indent.get_or_insert(key_indent);
+        end_line = end_line.max(end);
+    }
+    Ok((indent.unwrap_or(0), end_line))
+}
+
+/// Layout of a block list item.
+struct ItemExtent {
+    /// Line of the item's dash.
+    line: usize,
+    /// Column of the dash.
+    indent: usize,
+    /// First line, including comments directly above.
+    first_line: usize,
+    /// End line (exclusive).
+    end_line: usize,
+}
+
+fn item_extent(lines: &Lines, node: &Node) -> Result<ItemExtent> {
+    let start = lines.marker_offset(node)?;
+    let mut line = lines.text[..start].matches('\n').count();
+    let mut prefix = &lines.text[lines.start(line)..start];
+    if prefix.trim().is_empty() {
+        // Content on the line after a lone dash
+        while line > 0 {
+            line -= 1;
+            if lines.line(line).trim() == "-" {
+                break;
+            }
+        }
+        prefix = lines.line(line);
+    }
+    let trimmed = prefix.trim();
+    if !trimmed.starts_with('-') || trimmed.trim_start_matches('-').trim().contains('-') {
+        return Err(edit_error("nested lists aren't supported"));
+    }
+    let indent = lines.indent(line);
+    Ok(ItemExtent {
+        line,
+        indent,
+        first_line: leading_comments(lines, line, indent),
+        end_line: block_end(lines, line, indent, false),
+    })
+}
+
+/// Where an entry's value is written.
+enum ValueRegion {
+    /// On the key's line (scalar or flow collection).
+    Inline {
+        colon: usize,
+        start: usize,
+        end: usize,
+    },
+    /// On the following lines, as a block scalar, or empty.
+    Block { colon: usize },
+}
+
+impl ValueRegion {
+    /// Offset just after the key's colon.
+    fn after_colon(&self) -> usize {
+        match self {
+            Self::Inline { colon, .. } | Self::Block { colon } => colon + 1,
+        }
+    }
+}
+
+fn value_region(text: &str, key_start: usize) -> Result<ValueRegion> {
+    let key_end = scalar_end(text, key_start, Context::Key);
+    let bytes = text.as_bytes();
+    let mut colon = key_end;
+    while colon < bytes.len() && bytes[colon] == b' ' {
+        colon += 1;
+    }
+    if bytes.get(colon) != Some(&b':') {
+        return Err(edit_error("couldn't find the key's colon"));
+    }
+    let mut start = colon + 1;
+    while start < bytes.len() && matches!(bytes[start], b' ' | b'\t') {
+        start += 1;
+    }
+    match bytes.get(start) {
+        None | Some(b'\n') | Some(b'\r') | Some(b'#') | Some(b'|') | Some(b'>') => {
+            Ok(ValueRegion::Block { colon })
+        }
+        Some(_) => Ok(ValueRegion::Inline {
+            colon,
+            start,
+            end: scalar_end(text, start, Context::Block),
+        }),
+    }
+}
+
+/// Where a scalar appears, which decides what ends a plain scalar.
+#[derive(Clone, Copy, PartialEq)]
+enum Context {
+    /// A mapping key.
+    Key,
+    /// A block value.
+    Block,
+    /// Inside `[...]` or `{...}`.
+    Flow,
+}
+
+/// End offset of the scalar or flow collection starting at `start`.
+fn scalar_end(text: &str, start: usize, context: Context) -> usize {
+    let bytes = text.as_bytes();
+    match bytes.get(start) {
+        Some(b'"') => {
+            let mut i = start + 1;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'\\' => i += 1,
+                    b'"' => return i + 1,
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        Some(b'\'') => {
+            let mut i = start + 1;
+            while i < bytes.len() {
+                if bytes[i] == b'\'' {
+                    if bytes.get(i + 1) == Some(&b'\'') {
+                        i += 1;
+                    } else {
+                        return i + 1;
+                    }
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        Some(b'[') | Some(b'{') => {
+            let mut depth = 0;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'[' | b'{' => depth += 1,
+                    b']' | b'}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return i + 1;
+                        }
+                    }
+                    b'"' | b'\'' => i = scalar_end(text, i, Context::Flow) - 1,
+                    _ => {}
+                }
+                i += 1;
+            }
+            bytes.len()
+        }
+        _ => {
+            let mut end = start;
+            let mut i = start;
+            while i < bytes.len() {
+                let c = bytes[i];
+                let next_is_space = matches!(bytes.get(i + 1), None | Some(b' ' | b'\n' | b'\t'));
+                let stop = c == b'\n'
+                    || (c == b'#' && i > start && matches!(bytes[i - 1], b' ' | b'\t'))
+                    || (context == Context::Flow && matches!(c, b',' | b']' | b'}'))
+                    || (context != Context::Block && c == b':' && next_is_space);
+                if stop {
+                    break;
+                }
+                if !matches!(c, b' ' | b'\t' | b'\r') {
+                    end = i + 1;
+                }
+                i += 1;
+            }
+            end
+        }
+    }
+}
+
+// === Text edits ===
+
+/// Replace `start..end` with `text`.
+struct Edit {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl Edit {
+    fn replace(start: usize, end: usize, text: String) -> Self {
+        Self { start, end, text }
+    }
+}
+
+/// Insert `text` as whole lines before `line`, optionally after a blank
+/// line.
+fn insert_lines(lines: &Lines, line: usize, text: &str, spaced: bool) -> Edit {
+    let at = lines.start(line);
+    let mut insert = String::new();
+    if at == lines.text.len() && !lines.text.is_empty() && !lines.text.ends_with('\n') {
+        insert.push('\n');
+    }
+    if spaced {
+        insert.push('\n');
+    }
+    insert.push_str(text);
+    insert.push('\n');
+    Edit::replace(at, at, insert)
+}
+
+/// Remove lines `first..end`. When the block is followed by a blank line
+/// that would leave two blank lines, or a blank line right after its
+/// parent key, that blank line goes too.
+fn remove_lines(lines: &Lines, first: usize, end: usize, first_in_parent: bool) -> Edit {
+    let mut end = end;
+    let preceded_by_blank = first > 0 && lines.is_blank(first - 1);
+    if end < lines.count() && lines.is_blank(end) && (preceded_by_blank || first_in_parent) {
+        while end < lines.count() && lines.is_blank(end) {
+            end += 1;
+        }
+    }
+    Edit::replace(lines.start(first), lines.start(end), String::new())
+}
+
+/// Rewrite the items of a flow list.
+fn flow_list_edit(
+    lines: &Lines,
+    node: &Node,
+    change: impl FnOnce(&mut Vec<String>),
+) -> Result<Edit> {
+    let text = lines.text;
+    let start = lines.marker_offset(node)?;
+    if text.as_bytes().get(start) != Some(&b'[') {
+        // An empty block value: write a new flow list after the colon
+        let mut items = Vec::new();
+        change(&mut items);
+        return Ok(Edit::replace(
+            start,
+            start,
+            format!("[{}]", items.join(", ")),
+        ));
+    }
+    let end = scalar_end(text, start, Context::Flow);
+    let inner = &text[start + 1..end - 1];
+    let mut items = Vec::new();
+    let mut i = 0;
+    let bytes = inner.as_bytes();
+    while i < bytes.len() {
+        while i < bytes.len() && matches!(bytes[i], b' ' | b'\n' | b'\t' | b',') {
+            i += 1;
+        }
+        if i >= bytes.len() {
+            break;
+        }
+        let item_end = scalar_end(inner, i, Context::Flow);
+        items.push(inner[i..item_end].to_string());
+        i = item_end;
+        while i < bytes.len() && bytes[i] != b',' {
+            i += 1;
+        }
+    }
+    change(&mut items);
+    Ok(Edit::replace(start, end, format!("[{}]", items.join(", "))))
+}
+
+// === Rendering ===
+
+/// A key, quoted if it needs to be.
+fn render_key(key: &str) -> String {
+    let plain = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | '/'))
+        && !key.starts_with(['-', '.']);
+    if plain {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).unwrap_or_else(|_| key.to_string())
+    }
+}
+
+/// A single-line scalar, quoted as serde_yaml would.
+fn render_scalar(value: &Value) -> String {
+    serde_yaml::to_string(value)
+        .unwrap_or_default()
+        .trim_end()
+        .to_string()
+}
+
+/// A value on one line: scalars, lists of scalars as `[a, b]` and empty
+/// mappings as `{}`. `flow` quotes strings that would break a flow list.
+fn inline_value(value: &Value, flow: bool) -> Option<String> {
+    match value {
+        Value::Sequence(items) => {
+            let items: Option<Vec<String>> = items.iter().map(|v| inline_value(v, true)).collect();
+            Some(format!("[{}]", items?.join(", ")))
+        }
+        Value::Mapping(mapping) if mapping.is_empty() => Some("{}".to_string()),
+        Value::Mapping(_) | Value::Tagged(_) => None,
+        Value::String(s) => {
+            let rendered = render_scalar(value);
+            if rendered.contains('\n') {
+                return None;
+            }
+            let breaks_flow = !rendered.starts_with(['"', '\''])
+                && s.contains([',', '[', ']', '{', '}', '#', ':']);
+            if flow && breaks_flow {
+                serde_json::to_string(s).ok()
+            } else {
+                Some(rendered)
+            }
+        }
+        _ => Some(render_scalar(value)),
+    }
+}
+
+/// A value in flow style, including non-empty mappings as `{k: v}`.
+fn flow_value(value: &Value) -> Option<String> {
+    match value {
+        Value::Mapping(mapping) => {
+            let entries: Option<Vec<String>> = mapping
+                .iter()
+                .map(|(k, v)| {
+                    let key = k.as_str().map(render_key)?;
+                    Some(format!("{}: {}", key, flow_value(v)?))
+                })
+                .collect();
+            Some(format!("{{{}}}", entries?.join(", ")))
+        }
+        Value::Sequence(items) => {
+            let items: Option<Vec<String>> = items.iter().map(flow_value).collect();
+            Some(format!("[{}]", items?.join(", ")))
+        }
+        _ => inline_value(value, true),
+    }
+}
+
+/// Text following `key:` for a value: ` value` on the same line, a block
+/// scalar, or a nested block indented one step deeper than `indent`.
+fn entry_value_text(value: &Value, indent: usize, unit: usize) -> String {
+    if let Some(inline) = inline_value(value, false) {
+        return format!(" {}", inline);
+    }
+    if let Value::String(_) = value {
+        return format!(" {}", block_scalar(value, indent + unit));
+    }
+    let lines = block_lines(value, indent + unit, unit);
+    format!("\n{}", lines.join("\n"))
+}
+
+/// Text following `-` for a list item at `indent`.
+fn item_value_text(value: &Value, indent: usize, unit: usize) -> String {
+    if let Some(inline) = inline_value(value, false) {
+        return format!(" {}", inline);
+    }
+    if let Value::String(_) = value {
+        return format!(" {}", block_scalar(value, indent + 2));
+    }
+    let lines = block_lines(value, indent + 2, unit);
+    let first = lines.first().map(|l| l.trim_start()).unwrap_or_default();
+    let mut text = format!(" {}", first);
+    for line in lines.iter().skip(1) {
+        text.push('\n');
+        text.push_str(line);
+    }
+    text
+}
+
+/// A multi-line string as a block scalar whose content is at `indent`.
+fn block_scalar(value: &Value, indent: usize) -> String {
+    let rendered = render_scalar(value);
+    let mut lines = rendered.lines();
+    let mut text = lines.next().unwrap_or("|-").to_string();
+    for line in lines {
+        text.push('\n');
+        if !line.trim().is_empty() {
+            text.push_str(&" ".repeat(indent));
+            text.push_str(line.strip_prefix("  ").unwrap_or(line));
+        }
+    }
+    text
+}
+
+/// A mapping or list as block lines at `indent`.
+fn block_lines(value: &Value, indent: usize, unit: usize) -> Vec<String> {
+    let pad = " ".repeat(indent);
+    let mut lines = Vec::new();
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping {
+                let key = key
+                    .as_str()
+                    .map(render_key)
+                    .unwrap_or_else(|| render_scalar(key));
+                let text = format!("{}{}:{}", pad, key, entry_value_text(value, indent, unit));
+                lines.extend(text.lines().map(str::to_string));
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                let text = format!("{}-{}", pad, item_value_text(item, indent, unit));
+                lines.extend(text.lines().map(str::to_string));
+            }
+        }
+        _ => lines.push(format!("{}{}", pad, render_scalar(value))),
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn editor(text: &str) -> ConfigEditor {
+        ConfigEditor::new(text).unwrap()
+    }
+
+    fn string(s: &str) -> Value {
+        Value::String(s.to_string())
+    }
+
+    fn list(items: &[&str]) -> Value {
+        Value::Sequence(items.iter().map(|s| string(s)).collect())
+    }
+
+    const CONFIG: &str = "\
+# Project config
+app_name: demo
+
+settings:
+  defaults:
+    output: verbose  # verbose | quiet | silent
+
+steps:
+  # Ruby gems
+  install:
+    command: bundle install
+    depends_on: [tools]
+
+  build:
+    command: |
+      make
+      make install
+    depends_on:
+      - install
+
+workflows:
+  default:
+    steps: [install, build]
+";
+
+    #[test]
+    fn get_reads_values() {
+        let e = editor(CONFIG);
+        assert_eq!(e.get(&["app_name"]), Some(string("demo")));
+        assert_eq!(
+            e.get(&["steps", "build", "command"]),
+            Some(string("make\nmake install\n"))
+        );
+        assert_eq!(
+            e.get(&["workflows", "default", "steps"]),
+            Some(list(&["install", "build"]))
+        );
+        assert!(e.contains(&["steps", "build", "depends_on", "0"]));
+        assert!(!e.contains(&["steps", "missing"]));
+    }
+
+    #[test]
+    fn set_scalar_keeps_trailing_comment() {
+        let mut e = editor(CONFIG);
+        e.set(&["settings", "defaults", "output"], &string("quiet"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("    output: quiet  # verbose | quiet | silent\n"));
+        assert_eq!(e.as_str().len(), CONFIG.len() - 2);
+    }
+
+    #[test]
+    fn set_replaces_block_scalar() {
+        let mut e = editor(CONFIG);
+        e.set(&["steps", "build", "command"], &string("make all"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("  build:\n    command: make all\n    depends_on:\n"));
+    }
+
+    #[test]
+    fn set_multiline_string_writes_block_scalar() {
+        let mut e = editor(CONFIG);
+        e.set(&["steps", "install", "command"], &string("a\nb"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("    command: |-\n      a\n      b\n    depends_on: [tools]\n"));
+        assert_eq!(
+            e.get(&["steps", "install", "command"]),
+            Some(string("a\nb"))
+        );
+    }
+
+    #[test]
+    fn set_adds_key_after_siblings() {
+        let mut e = editor(CONFIG);
+        e.set(&["steps", "install", "title"], &string("Install gems"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("    depends_on: [tools]\n    title: Install gems\n\n  build:"));
+    }
+
+    #[test]
+    fn set_creates_missing_mappings() {
+        let mut e = editor(CONFIG);
+        e.set(&["steps", "test", "command"], &string("make test"))
+            .unwrap();
+        // Steps are separated by blank lines, so the new one is too
+        assert!(e
+            .as_str()
+            .contains("      - install\n\n  test:\n    command: make test\n\nworkflows:"));
+    }
+
+    #[test]
+    fn set_new_top_level_key_follows_spacing() {
+        let mut e = editor(CONFIG);
+        e.set(&["vars", "region"], &string("us-east-1")).unwrap();
+        assert!(e
+            .as_str()
+            .ends_with("    steps: [install, build]\n\nvars:\n  region: us-east-1\n"));
+    }
+
+    #[test]
+    fn set_in_compact_mapping_stays_compact() {
+        let mut e = editor("steps:\n  a:\n    command: x\n  b:\n    command: y\n");
+        e.set(&["steps", "c", "command"], &string("z")).unwrap();
+        assert_eq!(
+            e.as_str(),
+            "steps:\n  a:\n    command: x\n  b:\n    command: y\n  c:\n    command: z\n"
+        );
+    }
+
+    #[test]
+    fn set_into_empty_value_and_flow_mapping() {
+        let mut e = editor("app_name: x\noverrides:\nvars: {a: 1}\n");
+        e.set(&["overrides", "ci"], &string("yes")).unwrap();
+        e.set(&["vars", "b"], &Value::from(2)).unwrap();
+        assert_eq!(
+            e.as_str(),
+            "app_name: x\noverrides:\n  ci: yes\nvars: {a: 1, b: 2}\n"
+        );
+    }
+
+    #[test]
+    fn set_replaces_value_inside_flow_mapping() {
+        let mut e = editor("steps:\n  a: {command: x, title: y}  # inline\n  b: [{command: z}]\n");
+        e.set(&["steps", "a", "title"], &string("new")).unwrap();
+        e.set(&["steps", "a", "command"], &string("make all"))
+            .unwrap();
+        e.set(&["steps", "b", "0", "command"], &string("w"))
+            .unwrap();
+        assert_eq!(
+            e.as_str(),
+            "steps:\n  a: {command: make all, title: new}  # inline\n  b: [{command: w}]\n"
+        );
+    }
+
+    #[test]
+    fn set_into_empty_flow_mapping_writes_block() {
+        let mut e = editor("app_name: x\nsteps: {}\n");
+        e.set(&["steps", "a", "command"], &string("run")).unwrap();
+        assert_eq!(e.as_str(), "app_name: x\nsteps:\n  a:\n    command: run\n");
+    }
+
+    #[test]
+    fn set_into_empty_document() {
+        let mut e = editor("# nothing yet\n");
+        e.set(&["app_name"], &string("demo")).unwrap();
+        assert_eq!(e.as_str(), "# nothing yet\napp_name: demo\n");
+    }
+
+    #[test]
+    fn set_list_item() {
+        let mut e = editor(CONFIG);
+        e.set(&["workflows", "default", "steps", "1"], &string("compile"))
+            .unwrap();
+        e.set(&["steps", "build", "depends_on", "0"], &string("setup"))
+            .unwrap();
+        assert!(e.as_str().contains("steps: [install, compile]"));
+        assert!(e.as_str().contains("    depends_on:\n      - setup\n"));
+    }
+
+    #[test]
+    fn set_nested_mapping_value() {
+        let mut e = editor("app_name: x\n");
+        let value: Value = serde_yaml::from_str("command: run\nenv:\n  A: '1'\n").unwrap();
+        e.set(&["steps", "a"], &value).unwrap();
+        assert_eq!(
+            e.as_str(),
+            "app_name: x\nsteps:\n  a:\n    command: run\n    env:\n      A: '1'\n"
+        );
+    }
+
+    #[test]
+    fn remove_entry_with_leading_comment() {
+        let mut e = editor(CONFIG);
+        assert!(e.remove(&["steps", "install"]).unwrap());
+        assert!(e.as_str().contains("steps:\n  build:\n"));
+        assert!(!e.as_str().contains("Ruby gems"));
+    }
+
+    #[test]
+    fn remove_last_entry_collapses_blank_lines() {
+        let mut e = editor(CONFIG);
+        assert!(e.remove(&["steps", "build"]).unwrap());
+        assert!(e.as_str().contains("    depends_on: [tools]\n\nworkflows:"));
+    }
+
+    #[test]
+    fn remove_only_entry_leaves_empty_mapping() {
+        let mut e = editor("steps:\n  a:\n    command: x\nworkflows: {}\n");
+        assert!(e.remove(&["steps", "a"]).unwrap());
+        assert_eq!(e.as_str(), "steps: {}\nworkflows: {}\n");
+    }
+
+    #[test]
+    fn remove_missing_path_is_false() {
+        let mut e = editor(CONFIG);
+        assert!(!e.remove(&["steps", "nope"]).unwrap());
+        assert_eq!(e.as_str(), CONFIG);
+    }
+
+    #[test]
+    fn remove_flow_and_block_list_items() {
+        let mut e = editor(CONFIG);
+        assert!(e
+            .remove_item(&["workflows", "default", "steps"], "install")
+            .unwrap());
+        assert!(e.as_str().contains("steps: [build]"));
+
+        assert!(e
+            .remove_item(&["steps", "build", "depends_on"], "install")
+            .unwrap());
+        assert!(e.as_str().contains("    depends_on: []\n"));
+
+        assert!(!e
+            .remove_item(&["steps", "build", "depends_on"], "install")
+            .unwrap());
+    }
+
+    #[test]
+    fn remove_block_list_item_keeps_others() {
+        let mut e = editor("list:\n  - a\n  # about b\n  - b\n  - c\n");
+        assert!(e.remove(&["list", "1"]).unwrap());
+        assert_eq!(e.as_str(), "list:\n  - a\n  - c\n");
+    }
+
+    #[test]
+    fn remove_first_key_of_list_item() {
+        let mut e = editor("checks:\n  - type: execution\n    command: x\n");
+        assert!(e.remove(&["checks", "0", "type"]).unwrap());
+        assert_eq!(e.as_str(), "checks:\n  - command: x\n");
+    }
+
+    #[test]
+    fn rename_key_keeps_value_and_comments() {
+        let mut e = editor(CONFIG);
+        assert!(e.rename_key(&["steps", "install"], "gems").unwrap());
+        assert!(e
+            .as_str()
+            .contains("  # Ruby gems\n  gems:\n    command: bundle install\n"));
+        assert!(e.rename_key(&["steps", "gems"], "build").is_err());
+        assert!(!e.rename_key(&["steps", "nope"], "x").unwrap());
+    }
+
+    #[test]
+    fn insert_item_into_flow_list() {
+        let mut e = editor(CONFIG);
+        e.insert_item(&["workflows", "default", "steps"], Some(1), &string("test"))
+            .unwrap();
+        e.insert_item(&["workflows", "default", "steps"], None, &string("a, b"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("steps: [install, test, build, \"a, b\"]"));
+    }
+
+    #[test]
+    fn insert_item_into_block_list() {
+        let mut e = editor(CONFIG);
+        e.insert_item(&["steps", "build", "depends_on"], None, &string("tools"))
+            .unwrap();
+        e.insert_item(&["steps", "build", "depends_on"], Some(0), &string("first"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .contains("    depends_on:\n      - first\n      - install\n      - tools\n"));
+    }
+
+    #[test]
+    fn insert_item_creates_missing_list() {
+        let mut e = editor(CONFIG);
+        e.insert_item(&["workflows", "default", "force"], None, &string("build"))
+            .unwrap();
+        assert!(e
+            .as_str()
+            .ends_with("    steps: [install, build]\n    force: [build]\n"));
+    }
+
+    #[test]
+    fn append_comment_to_mapping() {
+        let mut e = editor("steps:\n  a:\n    template: x\n");
+        e.append_comment(&["steps", "a"], "command: run x").unwrap();
+        assert_eq!(
+            e.as_str(),
+            "steps:\n  a:\n    template: x\n    # command: run x\n"
+        );
+    }
+
+    #[test]
+    fn follows_document_indentation() {
+        let mut e = editor("steps:\n    a:\n        command: x\n");
+        e.set(&["steps", "a", "env", "A"], &string("1")).unwrap();
+        assert_eq!(
+            e.as_str(),
+            "steps:\n    a:\n        command: x\n        env:\n            A: '1'\n"
+        );
+    }
+
+    #[test]
+    fn rejects_anchors() {
+        let err = ConfigEditor::new("a: &x 1\nb: *x\n").unwrap_err();
+        assert!(err.to_string().contains("Can't edit config"));
+    }
+
+    #[test]
+    fn apply_edits() {
+        let mut e = editor(CONFIG);
+        let edit = ConfigEdit::RemoveItem {
+            path: vec!["steps".into(), "install".into(), "depends_on".into()],
+            item: "tools".into(),
+        };
+        assert!(e.apply(&edit).unwrap());
+        assert_eq!(
+            edit.to_string(),
+            "remove 'tools' from steps.install.depends_on"
+        );
+        assert!(e.as_str().contains("    depends_on: []\n"));
+    }
+
+    #[test]
+    fn parse_path_splits_on_dots() {
+        assert_eq!(parse_path("steps.a.command"), vec!["steps", "a", "command"]);
+        assert_eq!(
+            parse_path("steps.\"db.setup\".command"),
+            vec!["steps", "db.setup", "command"]
+        );
+    }
+}
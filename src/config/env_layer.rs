@@ -142,6 +142,20 @@ impl EnvLayerStack {
         None
     }
 
+    /// Get every source that sets a variable.
+    ///
+    /// Sources are ordered from highest to lowest priority, so the first
+    /// entry is the one [`source_of`](Self::source_of) reports and the rest
+    /// are the layers it shadows.
+    pub fn sources_of(&self, key: &str) -> Vec<&str> {
+        self.layers
+            .iter()
+            .rev()
+            .filter(|layer| layer.vars.contains_key(key))
+            .map(|layer| layer.source.as_str())
+            .collect()
+    }
+
     /// Get all layers for inspection.
     pub fn layers(&self) -> &[EnvLayer] {
         &self.layers
@@ -273,6 +287,22 @@ mod tests {
         assert_eq!(resolved.get("B"), Some(&"2".to_string()));
     }
 
+    #[test]
+    fn sources_of_lists_shadowed_layers() {
+        let mut stack = EnvLayerStack::new();
+
+        for source in ["base", "middle", "top"] {
+            let mut layer = EnvLayer::new(source);
+            if source != "middle" {
+                layer.set("KEY", source);
+            }
+            stack.push(layer);
+        }
+
+        assert_eq!(stack.sources_of("KEY"), vec!["top", "base"]);
+        assert!(stack.sources_of("MISSING").is_empty());
+    }
+
     #[test]
     fn missing_key_returns_none() {
         let stack = EnvLayerStack::new();
@@ -108,8 +108,9 @@ impl ExtendsResolver {
         // Convert current config to Value for merging
         let config_value = serde_yaml::to_value(config)?;
 
-        // Environment override blocks should replace entirely, not field-merge
-        let leaf_keys = std::collections::HashSet::from(["environments"]);
+        // Environment override blocks and diagnostic rules should replace
+        // entirely, not field-merge
+        let leaf_keys = std::collections::HashSet::from(["environments", "diagnostics"]);
 
         // Start with empty Value
         let mut merged = serde_yaml::Value::Mapping(serde_yaml::Mapping::new());
@@ -1145,4 +1146,49 @@ steps:
             vec!["dev".to_string()]
         );
     }
+
+    #[test]
+    fn extends_merges_diagnostic_rules_by_name() {
+        let server = MockServer::start();
+
+        let base_yaml = r#"
+diagnostics:
+  vpn:
+    pattern: "VPN not connected"
+    explanation: "Connect to the corporate VPN"
+    fix: "vpn up"
+  artifactory:
+    pattern: "401 Unauthorized"
+    explanation: "Token expired"
+    fix: "jf login"
+"#;
+
+        server.mock(|when, then| {
+            when.method(GET).path("/base.yml");
+            then.status(200).body(base_yaml);
+        });
+
+        let overlay_yaml = format!(
+            r#"
+extends:
+  - url: {}
+diagnostics:
+  artifactory:
+    pattern: "token expired"
+    explanation: "Refresh the Artifactory token"
+"#,
+            server.url("/base.yml")
+        );
+        let config: BivvyConfig = serde_yaml::from_str(&overlay_yaml).unwrap();
+
+        let resolver = resolver_with_mock(&server);
+        let resolved = resolver.resolve(&config).unwrap();
+
+        // Base rule not mentioned by the overlay is kept
+        assert_eq!(resolved.diagnostics["vpn"].fix.as_deref(), Some("vpn up"));
+        // Overlay rule replaces the base rule entirely
+        let artifactory = &resolved.diagnostics["artifactory"];
+        assert_eq!(artifactory.pattern, "token expired");
+        assert!(artifactory.fix.is_none());
+    }
 }
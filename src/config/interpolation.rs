@@ -5,6 +5,9 @@
 //! # Syntax
 //!
 //! - `${variable_name}` - replaced with variable value
+//! - `${name:-default}` - `default` when `name` is unset or empty
+//! - `${name:?message}` - fails with `message` when `name` is unset or empty
+//! - `${name | lower}` - value passed through one or more filters (see [`FILTERS`])
 //! - `$${escaped}` - produces literal `${escaped}` in output
 //!
 //! # Example
@@ -24,12 +27,155 @@ pub enum Segment {
     Literal(String),
     /// Variable reference: ${name}
     Variable(String),
+    /// Variable reference with a fallback or filters: ${name:-default | lower}
+    Expression(Expression),
+}
+
+/// Filters that can be applied with `${name | filter}`.
+pub const FILTERS: &[&str] = &["lower", "upper", "slug", "trim", "basename", "quote"];
+
+/// A variable reference with a fallback and/or filters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    /// Variable name
+    pub name: String,
+    /// What to do when the variable is unset or empty
+    pub fallback: Option<Fallback>,
+    /// Filter names, applied left to right
+    pub filters: Vec<String>,
+}
+
+/// Fallback behavior for an unset or empty variable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Fallback {
+    /// `${name:-default}` - use the default text
+    Default(String),
+    /// `${name:?message}` - fail with the message
+    Error(String),
+}
+
+impl Expression {
+    /// Parse the text between `${` and `}`.
+    ///
+    /// Every unescaped `|` starts a filter, so a fallback that needs a
+    /// literal pipe writes it as `\|`.
+    fn parse(body: &str) -> Segment {
+        let mut parts = split_filters(body).into_iter();
+        let head = parts.next().unwrap_or_default();
+        let filters: Vec<String> = parts.map(|f| f.trim().to_string()).collect();
+        let head = if filters.is_empty() {
+            head.as_str()
+        } else {
+            head.trim_end()
+        };
+
+        let (name, fallback) = if let Some((name, default)) = head.split_once(":-") {
+            (name, Some(Fallback::Default(default.to_string())))
+        } else if let Some((name, message)) = head.split_once(":?") {
+            (name, Some(Fallback::Error(message.to_string())))
+        } else {
+            (head, None)
+        };
+
+        if fallback.is_none() && filters.is_empty() {
+            return Segment::Variable(name.to_string());
+        }
+        Segment::Expression(Expression {
+            name: name.trim().to_string(),
+            fallback,
+            filters,
+        })
+    }
+
+    /// Compute the final value from the variable's resolved value.
+    ///
+    /// Unknown filters are reported as errors.
+    fn evaluate(&self, value: Option<String>) -> Result<String> {
+        let mut value = match (value, &self.fallback) {
+            (Some(v), _) if !v.is_empty() => v,
+            (_, Some(Fallback::Default(default))) => default.clone(),
+            (_, Some(Fallback::Error(message))) => {
+                let message = if message.trim().is_empty() {
+                    "variable is not set".to_string()
+                } else {
+                    message.trim().to_string()
+                };
+                return Err(BivvyError::ConfigValidationError {
+                    message: format!("{}: {}", self.name, message),
+                });
+            }
+            (Some(v), None) => v,
+            (None, None) => {
+                return Err(BivvyError::ConfigValidationError {
+                    message: format!("Unresolved variable: ${{{}}}", self.name),
+                })
+            }
+        };
+
+        for filter in &self.filters {
+            value =
+                apply_filter(filter, &value).ok_or_else(|| BivvyError::ConfigValidationError {
+                    message: format!(
+                        "Unknown filter '{}' for variable '{}' (expected one of: {})",
+                        filter,
+                        self.name,
+                        FILTERS.join(", ")
+                    ),
+                })?;
+        }
+        Ok(value)
+    }
+}
+
+/// Split an expression body on unescaped `|`, unescaping `\|` to `|`.
+fn split_filters(body: &str) -> Vec<String> {
+    let mut parts = vec![String::new()];
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'|') => {
+                chars.next();
+                parts.last_mut().unwrap().push('|');
+            }
+            '|' => parts.push(String::new()),
+            _ => parts.last_mut().unwrap().push(c),
+        }
+    }
+    parts
+}
+
+/// Apply a named filter, or `None` if the filter is unknown.
+pub fn apply_filter(filter: &str, value: &str) -> Option<String> {
+    let result = match filter {
+        "lower" => value.to_lowercase(),
+        "upper" => value.to_uppercase(),
+        "trim" => value.trim().to_string(),
+        "slug" => {
+            let mut slug = String::new();
+            for c in value.chars() {
+                if c.is_alphanumeric() {
+                    slug.extend(c.to_lowercase());
+                } else if !slug.is_empty() && !slug.ends_with('-') {
+                    slug.push('-');
+                }
+            }
+            slug.trim_end_matches('-').to_string()
+        }
+        "basename" => std::path::Path::new(value)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        "quote" => format!("'{}'", value.replace('\'', "'\\''")),
+        _ => return None,
+    };
+    Some(result)
 }
 
 /// Parse a string containing ${var} interpolations.
 ///
 /// Supports:
 /// - `${variable_name}` - variable interpolation
+/// - `${name:-default}`, `${name:?message}` and `${name | filter}` - expressions
 /// - `$${escaped}` - literal `${escaped}` in output
 ///
 /// # Returns
@@ -72,17 +218,17 @@ pub fn parse_interpolation(input: &str) -> Vec<Segment> {
                         segments.push(Segment::Literal(std::mem::take(&mut current_literal)));
                     }
 
-                    // Read variable name until }
-                    let mut var_name = String::new();
+                    // Read expression until }
+                    let mut body = String::new();
                     while let Some(&c) = chars.peek() {
                         if c == '}' {
                             chars.next();
                             break;
                         }
-                        var_name.push(chars.next().unwrap());
+                        body.push(chars.next().unwrap());
                     }
 
-                    segments.push(Segment::Variable(var_name));
+                    segments.push(Expression::parse(&body));
                 }
                 _ => {
                     current_literal.push(c);
@@ -109,7 +255,8 @@ pub fn extract_variables(input: &str) -> HashSet<String> {
         .into_iter()
         .filter_map(|seg| match seg {
             Segment::Variable(name) => Some(name),
-            _ => None,
+            Segment::Expression(expr) => Some(expr.name),
+            Segment::Literal(_) => None,
         })
         .collect()
 }
@@ -118,7 +265,55 @@ pub fn extract_variables(input: &str) -> HashSet<String> {
 pub fn has_interpolation(input: &str) -> bool {
     parse_interpolation(input)
         .iter()
-        .any(|seg| matches!(seg, Segment::Variable(_)))
+        .any(|seg| !matches!(seg, Segment::Literal(_)))
+}
+
+/// Resolve only the variables named in `values`.
+///
+/// References to other variables and `$$` escapes are left as written, so
+/// the result can still be interpolated later.
+///
+/// # Errors
+///
+/// Returns `ConfigValidationError` if an expression on a known variable
+/// fails (a `:?` message on an empty value, or an unknown filter).
+pub fn substitute_known(input: &str, values: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::new();
+    let mut rest = input;
+
+    while let Some(start) = rest.find('$') {
+        result.push_str(&rest[..start]);
+        let tail = &rest[start..];
+
+        if let Some(after) = tail.strip_prefix("$$") {
+            result.push_str("$$");
+            rest = after;
+            continue;
+        }
+        let Some(end) = tail.strip_prefix("${").and_then(|body| body.find('}')) else {
+            result.push('$');
+            rest = &tail[1..];
+            continue;
+        };
+
+        let body = &tail[2..2 + end];
+        let resolved = match Expression::parse(body) {
+            Segment::Variable(name) => values.get(&name).cloned(),
+            Segment::Expression(expr) => match values.get(&expr.name) {
+                Some(value) => Some(expr.evaluate(Some(value.clone()))?),
+                None => None,
+            },
+            Segment::Literal(_) => None,
+        };
+        match resolved {
+            Some(value) => result.push_str(&value),
+            None => result.push_str(&tail[..end + 3]),
+        }
+        rest = &tail[end + 3..];
+    }
+
+    result.push_str(rest);
+    Ok(result)
 }
 
 /// Context for variable resolution.
@@ -149,6 +344,9 @@ pub struct InterpolationContext {
 
     /// Built-in variables (project_name, project_root, bivvy_version)
     pub builtins: HashMap<String, String>,
+
+    /// Answers to secret prompts, masked wherever step output is shown
+    pub secrets: Vec<String>,
 }
 
 impl InterpolationContext {
@@ -203,6 +401,24 @@ impl InterpolationContext {
         ctx
     }
 
+    /// Register a secret value so it is masked in step output.
+    pub fn add_secret(&mut self, value: impl Into<String>) {
+        let value = value.into();
+        if !value.is_empty() && !self.secrets.contains(&value) {
+            self.secrets.push(value);
+        }
+    }
+
+    /// Replace every registered secret in `text` with `[REDACTED]`.
+    pub fn mask_secrets(&self, text: &str) -> String {
+        if self.secrets.is_empty() {
+            return text.to_string();
+        }
+        let mut masker = crate::secrets::OutputMasker::new();
+        masker.add_secrets(self.secrets.iter().cloned());
+        masker.mask(text)
+    }
+
     /// Resolve a variable name to its value.
     ///
     /// Resolution order: prompts > preferences > vars > inputs > env > builtins
@@ -222,7 +438,9 @@ impl InterpolationContext {
 ///
 /// # Errors
 ///
-/// Returns `ConfigValidationError` if any variable is not found in the context.
+/// Returns `ConfigValidationError` if any variable without a fallback is not
+/// found in the context, if a `${name:?message}` variable is unset or empty,
+/// or if an expression uses an unknown filter.
 pub fn resolve_string(input: &str, context: &InterpolationContext) -> Result<String> {
     let segments = parse_interpolation(input);
     let mut result = String::new();
@@ -239,6 +457,9 @@ pub fn resolve_string(input: &str, context: &InterpolationContext) -> Result<Str
                         })?;
                 result.push_str(&value);
             }
+            Segment::Expression(expr) => {
+                result.push_str(&expr.evaluate(context.resolve(&expr.name))?);
+            }
         }
     }
 
@@ -248,7 +469,8 @@ pub fn resolve_string(input: &str, context: &InterpolationContext) -> Result<Str
 /// Resolve string with optional default value for missing variables.
 ///
 /// Unlike `resolve_string`, this never fails - missing variables
-/// are replaced with the provided default.
+/// are replaced with the provided default. Expressions with their own
+/// `:-` fallback use it instead, and unknown filters are skipped.
 pub fn resolve_string_with_default(
     input: &str,
     context: &InterpolationContext,
@@ -266,6 +488,19 @@ pub fn resolve_string_with_default(
                     .unwrap_or_else(|| default.to_string());
                 result.push_str(&value);
             }
+            Segment::Expression(expr) => {
+                let mut value = match (context.resolve(&expr.name), &expr.fallback) {
+                    (Some(v), _) if !v.is_empty() => v,
+                    (_, Some(Fallback::Default(fallback))) => fallback.clone(),
+                    (value, _) => value.unwrap_or_else(|| default.to_string()),
+                };
+                for filter in &expr.filters {
+                    if let Some(filtered) = apply_filter(filter, &value) {
+                        value = filtered;
+                    }
+                }
+                result.push_str(&value);
+            }
         }
     }
 
@@ -373,6 +608,31 @@ mod tests {
         assert!(vars.is_empty());
     }
 
+    #[test]
+    fn substitute_known_leaves_other_references() {
+        let values = HashMap::from([("bump".to_string(), "minor".to_string())]);
+
+        let result = substitute_known(
+            "npm version ${bump} && echo ${APP} $${bump} $HOME $$",
+            &values,
+        )
+        .unwrap();
+
+        assert_eq!(result, "npm version minor && echo ${APP} $${bump} $HOME $$");
+    }
+
+    #[test]
+    fn substitute_known_evaluates_expressions() {
+        let values = HashMap::from([
+            ("name".to_string(), "My App".to_string()),
+            ("empty".to_string(), String::new()),
+        ]);
+
+        let result = substitute_known("${name | slug}-${empty:-x}-${other:-y}", &values).unwrap();
+
+        assert_eq!(result, "my-app-x-${other:-y}");
+    }
+
     #[test]
     fn has_interpolation_returns_true_for_variables() {
         assert!(has_interpolation("hello ${name}"));
@@ -577,4 +837,159 @@ mod tests {
         // Original context is unchanged
         assert!(ctx.inputs.is_empty());
     }
+
+    #[test]
+    fn mask_secrets_redacts_registered_values() {
+        let mut ctx = InterpolationContext::new();
+        ctx.add_secret("s3cr3t");
+        ctx.add_secret("");
+        assert_eq!(ctx.secrets, vec!["s3cr3t".to_string()]);
+        assert_eq!(ctx.mask_secrets("token=s3cr3t"), "token=[REDACTED]");
+    }
+
+    #[test]
+    fn mask_secrets_without_secrets_is_identity() {
+        let ctx = InterpolationContext::new();
+        assert_eq!(ctx.mask_secrets("token=abc"), "token=abc");
+    }
+
+    #[test]
+    fn parse_default_expression() {
+        let result = parse_interpolation("${DB_HOST:-localhost}");
+        assert_eq!(
+            result,
+            vec![Segment::Expression(Expression {
+                name: "DB_HOST".to_string(),
+                fallback: Some(Fallback::Default("localhost".to_string())),
+                filters: vec![],
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_error_expression_with_filters() {
+        let result = parse_interpolation("${app:?app is required | slug | upper}");
+        assert_eq!(
+            result,
+            vec![Segment::Expression(Expression {
+                name: "app".to_string(),
+                fallback: Some(Fallback::Error("app is required".to_string())),
+                filters: vec!["slug".to_string(), "upper".to_string()],
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_default_with_escaped_pipe() {
+        let result = parse_interpolation(r"${mode:-a\|b | upper}");
+        assert_eq!(
+            result,
+            vec![Segment::Expression(Expression {
+                name: "mode".to_string(),
+                fallback: Some(Fallback::Default("a|b".to_string())),
+                filters: vec!["upper".to_string()],
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_default_with_unescaped_pipe_starts_filter() {
+        let result = parse_interpolation("${mode:-a|b}");
+        assert_eq!(
+            result,
+            vec![Segment::Expression(Expression {
+                name: "mode".to_string(),
+                fallback: Some(Fallback::Default("a".to_string())),
+                filters: vec!["b".to_string()],
+            })]
+        );
+    }
+
+    #[test]
+    fn parse_keeps_backslash_not_before_pipe() {
+        let result = parse_interpolation(r"${dir:-C:\tmp}");
+        assert_eq!(
+            result,
+            vec![Segment::Expression(Expression {
+                name: "dir".to_string(),
+                fallback: Some(Fallback::Default(r"C:\tmp".to_string())),
+                filters: vec![],
+            })]
+        );
+    }
+
+    #[test]
+    fn extract_variables_includes_expressions() {
+        let vars = extract_variables("${a:-x} ${b | lower}");
+        assert!(vars.contains("a"));
+        assert!(vars.contains("b"));
+        assert!(has_interpolation("${a:-x}"));
+    }
+
+    #[test]
+    fn resolve_default_when_unset_or_empty() {
+        let mut ctx = InterpolationContext::new();
+        assert_eq!(
+            resolve_string("${DB_HOST:-localhost}", &ctx).unwrap(),
+            "localhost"
+        );
+        ctx.env.insert("DB_HOST".to_string(), String::new());
+        assert_eq!(
+            resolve_string("${DB_HOST:-localhost}", &ctx).unwrap(),
+            "localhost"
+        );
+        ctx.env.insert("DB_HOST".to_string(), "db".to_string());
+        assert_eq!(resolve_string("${DB_HOST:-localhost}", &ctx).unwrap(), "db");
+    }
+
+    #[test]
+    fn resolve_error_expression_reports_message() {
+        let ctx = InterpolationContext::new();
+        let err = resolve_string("${token:?set a token first}", &ctx).unwrap_err();
+        assert!(err.to_string().contains("token: set a token first"));
+    }
+
+    #[test]
+    fn resolve_applies_filters_in_order() {
+        let mut ctx = InterpolationContext::new();
+        ctx.vars
+            .insert("app".to_string(), "  My Cool_App! ".to_string());
+        assert_eq!(
+            resolve_string("${app | slug}", &ctx).unwrap(),
+            "my-cool-app"
+        );
+        assert_eq!(
+            resolve_string("${app | trim | upper}", &ctx).unwrap(),
+            "MY COOL_APP!"
+        );
+        assert_eq!(
+            resolve_string("${missing:-Some Name | lower}", &ctx).unwrap(),
+            "some name"
+        );
+    }
+
+    #[test]
+    fn resolve_fails_on_unknown_filter() {
+        let mut ctx = InterpolationContext::new();
+        ctx.vars.insert("app".to_string(), "x".to_string());
+        let err = resolve_string("${app | shout}", &ctx).unwrap_err();
+        assert!(err.to_string().contains("Unknown filter 'shout'"));
+    }
+
+    #[test]
+    fn filters_basename_and_quote() {
+        assert_eq!(
+            apply_filter("basename", "/srv/apps/demo").as_deref(),
+            Some("demo")
+        );
+        assert_eq!(apply_filter("quote", "it's").as_deref(), Some("'it'\\''s'"));
+        assert_eq!(apply_filter("nope", "x"), None);
+    }
+
+    #[test]
+    fn resolve_string_with_default_honors_expression_fallback() {
+        let ctx = InterpolationContext::new();
+        let result = resolve_string_with_default("${a:-own} ${b | upper}", &ctx, "dflt");
+        assert_eq!(result, "own DFLT");
+    }
 }
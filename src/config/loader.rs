@@ -10,6 +10,7 @@ use crate::config::schema::{BivvyConfig, StepConfig, WorkflowConfig, WorkflowFil
 use crate::config::trust::{TrustPolicy, TrustStore};
 use crate::error::{BivvyError, Result};
 use crate::ui::{Prompt, PromptResult, PromptType, Prompter};
+use crate::updates::version::{VersionConstraint, VERSION};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -281,10 +282,46 @@ pub fn load_config_file(path: &Path) -> Result<BivvyConfig> {
 /// * `content` - The YAML content to parse
 /// * `source_path` - Path for error reporting
 pub fn parse_config(content: &str, source_path: &Path) -> Result<BivvyConfig> {
-    serde_yaml::from_str(content).map_err(|e| BivvyError::ConfigParseError {
+    let to_parse_error = |e: serde_yaml::Error| BivvyError::ConfigParseError {
         path: source_path.to_path_buf(),
         message: e.to_string(),
-    })
+    };
+    let value: serde_yaml::Value = serde_yaml::from_str(content).map_err(to_parse_error)?;
+    check_bivvy_version(&value, source_path)?;
+    serde_yaml::from_value(value).map_err(to_parse_error)
+}
+
+/// Check a raw config's `bivvy_version` against the running binary.
+///
+/// Runs before typed parsing so that a config written for a newer bivvy
+/// fails with an upgrade message rather than an unknown-field error.
+pub fn check_bivvy_version(value: &serde_yaml::Value, source_path: &Path) -> Result<()> {
+    let Some(required) = value.get("bivvy_version") else {
+        return Ok(());
+    };
+    let required = match required {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        _ => {
+            return Err(BivvyError::ConfigParseError {
+                path: source_path.to_path_buf(),
+                message: "bivvy_version must be a string such as \">=1.10\"".to_string(),
+            })
+        }
+    };
+    let constraint =
+        VersionConstraint::parse(&required).map_err(|e| BivvyError::ConfigParseError {
+            path: source_path.to_path_buf(),
+            message: format!("Invalid bivvy_version: {}", e),
+        })?;
+    if !constraint.matches(VERSION) {
+        return Err(BivvyError::UnsupportedBivvyVersion {
+            path: source_path.to_path_buf(),
+            required: constraint.to_string(),
+            current: VERSION.to_string(),
+        });
+    }
+    Ok(())
 }
 
 /// Load a config file as raw YAML Value (for merging).
@@ -302,10 +339,12 @@ pub fn load_config_value(path: &Path) -> Result<serde_yaml::Value> {
         }
     })?;
 
-    serde_yaml::from_str(&content).map_err(|e| BivvyError::ConfigParseError {
+    let value = serde_yaml::from_str(&content).map_err(|e| BivvyError::ConfigParseError {
         path: path.to_path_buf(),
         message: e.to_string(),
-    })
+    })?;
+    check_bivvy_version(&value, path)?;
+    Ok(value)
 }
 
 /// Load split-file definitions and return a synthetic YAML value.
@@ -1061,6 +1100,45 @@ mod tests {
         assert!(matches!(result, Err(BivvyError::ConfigParseError { .. })));
     }
 
+    #[test]
+    fn parse_config_accepts_satisfied_bivvy_version() {
+        let config = parse_config("bivvy_version: \">=0.1\"\n", Path::new("test.yml")).unwrap();
+        assert_eq!(config.bivvy_version.as_deref(), Some(">=0.1"));
+    }
+
+    #[test]
+    fn parse_config_checks_bivvy_version_before_fields() {
+        // A newer config with fields this binary doesn't know about should
+        // fail on the version, not on the unknown field
+        let content = "bivvy_version: \">=999.0\"\nfuture_feature: true\n";
+        let err = parse_config(content, Path::new("test.yml")).unwrap_err();
+        assert!(matches!(err, BivvyError::UnsupportedBivvyVersion { .. }));
+        assert!(err.to_string().contains(">=999.0"));
+        assert!(err.to_string().contains("bivvy update"));
+    }
+
+    #[test]
+    fn parse_config_rejects_invalid_bivvy_version() {
+        let err = parse_config("bivvy_version: newest\n", Path::new("test.yml")).unwrap_err();
+        assert!(matches!(err, BivvyError::ConfigParseError { .. }));
+        assert!(err.to_string().contains("bivvy_version"));
+    }
+
+    #[test]
+    fn load_merged_config_checks_bivvy_version() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(
+            bivvy_dir.join("config.yml"),
+            "bivvy_version: \">=999.0\"\nsteps: {}\n",
+        )
+        .unwrap();
+
+        let err = load_merged_config(temp.path()).unwrap_err();
+        assert!(matches!(err, BivvyError::UnsupportedBivvyVersion { .. }));
+    }
+
     #[test]
     fn load_config_value_returns_raw_value() {
         let temp = TempDir::new().unwrap();
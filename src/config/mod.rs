@@ -7,6 +7,8 @@
 //! - Validation in [`validator`]
 //! - Variable interpolation in [`interpolation`]
 //! - Environment variable handling in [`environment`]
+//! - Pre-supplied prompt answers in [`answers`]
+//! - Format-preserving edits in [`editor`]
 //!
 //! # Example
 //!
@@ -35,7 +37,9 @@
 //! 5. Split-file workflows (`.bivvy/workflows/*.yml`)
 //! 6. Local overrides (`.bivvy/config.local.yml`)
 
+pub mod answers;
 pub mod discovery;
+pub mod editor;
 pub mod env_file;
 pub mod env_layer;
 pub mod environment;
@@ -51,13 +55,20 @@ pub mod vars;
 
 // Schema re-exports
 pub use schema::{
-    BehaviorConfig, BivvyConfig, CustomRequirement, CustomRequirementCheck, EnvVarSettings,
+    ArtifactCacheConfig, BehaviorConfig, BivvyConfig, CustomRequirement, CustomRequirementCheck,
+    DiagnosticCategory, DiagnosticPlatform, DiagnosticRule, EjectedFrom, EnvVarSettings,
     EnvironmentProfileSettings, EnvironmentScopingConfig, EnvironmentVarsConfig, ExecutionConfig,
     ExecutionSettings, HookConfig, OutputMode, PromptConfig, PromptType, SecretConfig, Settings,
     StepConfig, StepEnvironmentOverride, StepOutputConfig, StepOutputSettings, StepOverride,
-    TemplateSource, VarDefinition, WorkflowConfig, WorkflowFile, WorkflowSettings,
+    TemplateSource, UpdateChannel, VarDefinition, WorkflowConfig, WorkflowFile, WorkflowSettings,
 };
 
+// Answers re-exports
+pub use answers::PromptAnswers;
+
+// Editor re-exports
+pub use editor::{ConfigEdit, ConfigEditor};
+
 // Discovery re-exports
 pub use discovery::{Discovery, WorkflowHeader};
 
@@ -68,15 +79,15 @@ pub use crate::checks::{
 };
 
 // Vars re-exports
-pub use vars::evaluate_vars;
+pub use vars::VarEvaluator;
 
 // Loader re-exports
 pub use loader::{
-    ensure_global_config, find_project_root, load_config, load_config_file, load_config_value,
-    load_for_run, load_for_run_with_resolver, load_for_run_with_trust, load_merged_config,
-    load_merged_config_with_resolver, load_merged_config_with_trust, load_project_config,
-    load_single_step_file, load_single_workflow_file, parse_config, parse_workflow_file_value,
-    ConfigPaths,
+    check_bivvy_version, ensure_global_config, find_project_root, load_config, load_config_file,
+    load_config_value, load_for_run, load_for_run_with_resolver, load_for_run_with_trust,
+    load_merged_config, load_merged_config_with_resolver, load_merged_config_with_trust,
+    load_project_config, load_single_step_file, load_single_workflow_file, parse_config,
+    parse_workflow_file_value, ConfigPaths,
 };
 
 // Merger re-exports
@@ -88,7 +99,8 @@ pub use validator::{validate, validate_config, ValidationError};
 // Interpolation re-exports
 pub use interpolation::{
     extract_variables, has_interpolation, parse_interpolation, resolve_string,
-    resolve_string_with_default, InterpolationContext, Segment,
+    resolve_string_with_default, substitute_known, Expression, Fallback, InterpolationContext,
+    Segment, FILTERS,
 };
 
 // Environment re-exports
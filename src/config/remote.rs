@@ -1,7 +1,8 @@
 //! Remote configuration fetching.
 //!
 //! This module provides functionality for fetching configuration files
-//! from remote URLs.
+//! from remote URLs. In [offline mode](crate::cache::offline) only cached
+//! configs are served.
 
 use anyhow::{anyhow, Context, Result};
 use std::collections::HashMap;
@@ -28,6 +29,8 @@ pub struct RemoteFetcher {
     cache_dir: PathBuf,
     /// HTTP client.
     client: reqwest::blocking::Client,
+    /// Serve only cached configs.
+    offline: bool,
 }
 
 impl RemoteFetcher {
@@ -45,6 +48,7 @@ impl RemoteFetcher {
                 .timeout(timeout)
                 .build()
                 .expect("Failed to create HTTP client"),
+            offline: crate::cache::is_offline(),
         }
     }
 
@@ -57,15 +61,25 @@ impl RemoteFetcher {
                 .timeout(timeout)
                 .build()
                 .expect("Failed to create HTTP client"),
+            offline: crate::cache::is_offline(),
         }
     }
 
+    /// Serve only cached configs, never touching the network.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
     /// Fetch a configuration file from a URL.
     pub fn fetch(&self, url: &str) -> Result<String> {
         // Check cache first
         if let Some(cached) = self.check_cache(url)? {
             return Ok(cached);
         }
+        if self.offline {
+            return Err(crate::cache::offline::refuse(&format!("extends {}", url)));
+        }
 
         // Fetch from remote
         let content = self.fetch_remote(url)?;
@@ -81,11 +95,26 @@ impl RemoteFetcher {
         self.fetch_remote(url)
     }
 
+    /// Fetch a configuration file, bypassing and then updating the cache.
+    pub fn refresh(&self, url: &str) -> Result<String> {
+        let content = self.fetch_remote(url)?;
+        self.save_cache(url, &content)?;
+        Ok(content)
+    }
+
+    /// The cached copy of a URL, if any.
+    pub fn cached(&self, url: &str) -> Result<Option<String>> {
+        self.check_cache(url)
+    }
+
     /// Fetch with authentication header.
     ///
     /// Requires HTTPS to prevent credential leakage over plain HTTP.
     /// Returns an error if the URL scheme is not `https://`.
     pub fn fetch_with_auth(&self, url: &str, auth: &AuthHeader) -> Result<String> {
+        if self.offline {
+            return Err(crate::cache::offline::refuse(url));
+        }
         if !url.starts_with("https://") {
             return Err(anyhow!(
                 "Refusing to send auth headers over insecure HTTP: {}. Use HTTPS instead",
@@ -110,6 +139,9 @@ impl RemoteFetcher {
     }
 
     fn fetch_remote(&self, url: &str) -> Result<String> {
+        if self.offline {
+            return Err(crate::cache::offline::refuse(url));
+        }
         let response = self
             .client
             .get(url)
@@ -489,6 +521,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn offline_fetch_serves_cache_only() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/config.yml");
+            then.status(200).body("app_name: cached\n");
+        });
+        let temp = TempDir::new().unwrap();
+        let url = server.url("/config.yml");
+
+        let online = RemoteFetcher::with_cache_dir(Duration::from_secs(5), temp.path().into());
+        online.refresh(&url).unwrap();
+
+        let offline = RemoteFetcher::with_cache_dir(Duration::from_secs(5), temp.path().into())
+            .with_offline(true);
+        assert_eq!(offline.fetch(&url).unwrap(), "app_name: cached\n");
+        mock.assert_calls(1);
+
+        let err = offline.fetch(&server.url("/other.yml")).unwrap_err();
+        assert!(err.to_string().contains("Offline mode"));
+        assert!(offline.fetch_fresh(&url).is_err());
+    }
+
     #[test]
     fn fetch_uses_cache_on_second_call() {
         let server = MockServer::start();
@@ -32,6 +32,13 @@ fn any_value_map_schema(_: &mut schemars::generate::SchemaGenerator) -> schemars
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default, deny_unknown_fields)]
 pub struct BivvyConfig {
+    /// Required bivvy version, e.g. `">=1.10"`.
+    ///
+    /// Checked before the rest of the config is parsed, so older binaries
+    /// report an upgrade message instead of unknown-field errors.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bivvy_version: Option<String>,
+
     /// Application name (for display purposes)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub app_name: Option<String>,
@@ -66,6 +73,10 @@ pub struct BivvyConfig {
     /// User-defined variables for interpolation.
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub vars: HashMap<String, VarDefinition>,
+
+    /// Project-defined diagnostic rules for the error-recovery funnel
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub diagnostics: HashMap<String, DiagnosticRule>,
 }
 
 /// JSONL event logging settings.
@@ -170,12 +181,30 @@ pub struct ExecutionSettings {
     #[serde(default = "default_auto_update")]
     pub auto_update: bool,
 
+    /// Release channel that `bivvy update` and auto-update follow.
+    /// Read from the user config (`~/.bivvy/config.yml`). Default: `stable`.
+    #[serde(default, skip_serializing_if = "UpdateChannel::is_stable")]
+    pub update_channel: UpdateChannel,
+
+    /// Stay on this exact version instead of following a channel.
+    /// Read from the user config (`~/.bivvy/config.yml`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_pin: Option<String>,
+
     /// Global default rerun window for all steps.
     /// Steps can override this with their own `rerun_window` field.
     /// Accepts duration strings: `"4h"`, `"30m"`, `"7d"`, `"0"`/`"never"`, `"forever"`.
     /// Default: `"4h"`.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_rerun_window: Option<String>,
+
+    /// Maximum total size of the artifact cache (`~/.bivvy/artifacts/`) in MB.
+    /// Least recently used artifacts are evicted past this limit. Default: 5120.
+    #[serde(
+        default = "default_artifact_cache_mb",
+        skip_serializing_if = "is_default_artifact_cache_mb"
+    )]
+    pub artifact_cache_mb: u64,
 }
 
 impl Default for ExecutionSettings {
@@ -186,7 +215,10 @@ impl Default for ExecutionSettings {
             history_retention: default_history_retention(),
             diagnostic_funnel: true,
             auto_update: default_auto_update(),
+            update_channel: UpdateChannel::default(),
+            update_pin: None,
             default_rerun_window: None,
+            artifact_cache_mb: default_artifact_cache_mb(),
         }
     }
 }
@@ -326,6 +358,14 @@ fn is_default_history_retention(v: &usize) -> bool {
     *v == default_history_retention()
 }
 
+fn default_artifact_cache_mb() -> u64 {
+    5120
+}
+
+fn is_default_artifact_cache_mb(v: &u64) -> bool {
+    *v == default_artifact_cache_mb()
+}
+
 fn default_auto_update() -> bool {
     true
 }
@@ -352,6 +392,32 @@ pub enum OutputMode {
     Silent,
 }
 
+/// Release channel for bivvy updates
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    /// Full releases only
+    #[default]
+    Stable,
+    /// Pre-releases as well as full releases
+    Beta,
+}
+
+impl UpdateChannel {
+    fn is_stable(&self) -> bool {
+        *self == UpdateChannel::Stable
+    }
+}
+
+impl std::fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateChannel::Stable => f.write_str("stable"),
+            UpdateChannel::Beta => f.write_str("beta"),
+        }
+    }
+}
+
 /// Fields related to what the step actually runs and how.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
@@ -386,6 +452,29 @@ pub struct ExecutionConfig {
     /// Step requires sudo/elevated permissions
     #[serde(default, skip_serializing_if = "is_false")]
     pub requires_sudo: bool,
+
+    /// Artifact cache: restore `paths` from a previous run with the same key
+    /// files instead of running the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache: Option<ArtifactCacheConfig>,
+}
+
+/// Artifact cache settings for an expensive step.
+///
+/// ```yaml
+/// cache:
+///   key: [yarn.lock, .nvmrc]
+///   paths: [node_modules]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ArtifactCacheConfig {
+    /// Files, directories, or glob patterns whose contents key the cache
+    pub key: Vec<String>,
+
+    /// Paths (relative to the project root) to archive after a successful
+    /// run and restore on a cache hit
+    pub paths: Vec<String>,
 }
 
 /// Fields related to environment variable management.
@@ -524,6 +613,26 @@ pub struct EnvironmentScopingConfig {
     pub only_environments: Vec<String>,
 }
 
+/// The template an ejected step was copied from.
+///
+/// Written by `bivvy eject` so `bivvy templates outdated` can compare the
+/// step with later versions of the template.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct EjectedFrom {
+    /// Template name, as the step referenced it
+    pub template: String,
+
+    /// Template version at the time of ejecting
+    pub version: String,
+
+    /// Inputs the template was rendered with
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[schemars(schema_with = "any_value_map_schema")]
+    pub inputs: HashMap<String, serde_yaml::Value>,
+}
+
 /// Configuration for a single setup step
 ///
 /// `deny_unknown_fields` is applied via the `schemars`-only attribute because
@@ -543,6 +652,10 @@ pub struct StepConfig {
     #[schemars(schema_with = "any_value_map_schema")]
     pub inputs: HashMap<String, serde_yaml::Value>,
 
+    /// Template this step was ejected from (written by `bivvy eject`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ejected_from: Option<EjectedFrom>,
+
     /// Step title (for display)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
@@ -617,7 +730,7 @@ pub struct PromptConfig {
     /// Question to display
     pub question: String,
 
-    /// Prompt type: select, multiselect, confirm, input
+    /// Prompt type: select, multiselect, confirm, input, secret, number, path
     #[serde(rename = "type")]
     pub prompt_type: PromptType,
 
@@ -628,6 +741,26 @@ pub struct PromptConfig {
     /// Default value
     #[schemars(schema_with = "any_value_schema")]
     pub default: Option<serde_yaml::Value>,
+
+    /// Smallest accepted value (number prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    /// Largest accepted value (number prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+
+    /// Regex the whole answer must match (input prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// Message shown when the answer doesn't match `pattern`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_message: Option<String>,
+
+    /// What the answer must point at (path prompts): exists, file, dir
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_be: Option<PathRequirement>,
 }
 
 /// Type of interactive prompt
@@ -638,6 +771,24 @@ pub enum PromptType {
     Multiselect,
     Confirm,
     Input,
+    /// Masked input; the value is redacted from output and logs
+    Secret,
+    /// Numeric input, bounded by `min`/`max`
+    Number,
+    /// Filesystem path input with tab completion
+    Path,
+}
+
+/// Constraint on the answer to a `path` prompt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum PathRequirement {
+    /// The path must exist (file or directory)
+    Exists,
+    /// The path must be an existing file
+    File,
+    /// The path must be an existing directory
+    Dir,
 }
 
 /// Option for select/multiselect prompts
@@ -907,17 +1058,31 @@ pub struct SecretConfig {
     pub command: String,
 }
 
-/// A variable definition: either a static value or a shell-computed value.
+/// A variable definition: a static value or a value computed at run time.
 ///
-/// In YAML, static values are plain strings and computed values use
-/// `{ command: "..." }` syntax:
+/// In YAML, static values are plain strings; every other source is a
+/// mapping keyed by the source kind:
 ///
 /// ```yaml
 /// vars:
 ///   app_name: "bivvy"                       # static
 ///   version:
 ///     command: "cat VERSION"                 # computed
+///   release:
+///     file: VERSION                          # trimmed file contents
+///   node_version:
+///     json: package.json                     # also yaml: / toml:
+///     key: engines.node
+///   port:
+///     env: PORT
+///     default: "3000"
+///   region:
+///     prompt: "Which region?"
+///     default: us-east-1
 /// ```
+///
+/// Non-static vars are evaluated lazily (only when a planned step
+/// references them) and at most once per run.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(untagged)]
 pub enum VarDefinition {
@@ -926,6 +1091,51 @@ pub enum VarDefinition {
         /// Shell command to run
         command: String,
     },
+    /// Trimmed contents of a file.
+    File {
+        /// Path to the file, relative to the project root
+        file: String,
+    },
+    /// A value read from a JSON document.
+    Json {
+        /// Path to the JSON file, relative to the project root
+        json: String,
+        /// Dotted key path (e.g. `engines.node`, `items.0.name`)
+        key: String,
+    },
+    /// A value read from a YAML document.
+    Yaml {
+        /// Path to the YAML file, relative to the project root
+        yaml: String,
+        /// Dotted key path (e.g. `development.port`)
+        key: String,
+    },
+    /// A value read from a TOML document.
+    Toml {
+        /// Path to the TOML file, relative to the project root
+        toml: String,
+        /// Dotted key path (e.g. `package.version`)
+        key: String,
+    },
+    /// An environment variable, with an optional fallback.
+    Env {
+        /// Environment variable name
+        env: String,
+        /// Value to use when the variable is unset
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+    },
+    /// Asked interactively the first time it is needed.
+    Prompt {
+        /// Question to display
+        prompt: String,
+        /// Default answer (also used in non-interactive runs)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        default: Option<String>,
+        /// Mask input and redact the value from output
+        #[serde(default, skip_serializing_if = "is_false")]
+        secret: bool,
+    },
     /// A static string value.
     Static(String),
 }
@@ -969,6 +1179,77 @@ pub enum CustomRequirementCheck {
     },
 }
 
+/// A project-defined diagnostic rule.
+///
+/// When a failed step's output matches `pattern`, the rule contributes its
+/// category and a resolution candidate to the recovery menu. Capture groups
+/// in `pattern` can be referenced from `fix` as `{1}`, `{2}`, etc.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct DiagnosticRule {
+    /// Regex matched against the step's error output
+    pub pattern: String,
+
+    /// Error category this rule identifies
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<DiagnosticCategory>,
+
+    /// Why the failure happened and what the fix does
+    pub explanation: String,
+
+    /// Command that resolves the failure (supports `{N}` capture groups)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+
+    /// Label shown in the recovery menu (defaults to the fix command)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+
+    /// Only offer this rule on the given platform
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub platform: Option<DiagnosticPlatform>,
+
+    /// Confidence in the resolution, from 0.0 to 1.0
+    #[serde(
+        default = "default_diagnostic_confidence",
+        skip_serializing_if = "is_default_diagnostic_confidence"
+    )]
+    pub confidence: f32,
+}
+
+fn default_diagnostic_confidence() -> f32 {
+    0.8
+}
+
+fn is_default_diagnostic_confidence(v: &f32) -> bool {
+    *v == default_diagnostic_confidence()
+}
+
+/// Error category for a project-defined diagnostic rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DiagnosticCategory {
+    NotFound,
+    ConnectionRefused,
+    VersionMismatch,
+    SyncIssue,
+    PermissionDenied,
+    PortConflict,
+    BuildFailure,
+    ResourceLimit,
+    AuthFailure,
+    SystemConstraint,
+}
+
+/// Platform constraint for a project-defined diagnostic rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticPlatform {
+    Macos,
+    Linux,
+    Windows,
+}
+
 /// Configuration for a named environment.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(default, deny_unknown_fields)]
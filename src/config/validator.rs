@@ -8,13 +8,14 @@
 //! - Var names must be valid identifiers
 //! - Var names must not collide with builtin variables
 //! - Computed vars must have non-empty commands
+//! - Artifact caches must declare at least one key entry
 
 use crate::config::schema::{BivvyConfig, VarDefinition};
 use crate::error::{BivvyError, Result};
 use std::collections::HashSet;
 
 /// Built-in interpolation variable names that user vars must not shadow.
-const BUILTIN_VAR_NAMES: &[&str] = &["bivvy_version", "project_name", "project_root"];
+pub const BUILTIN_VAR_NAMES: &[&str] = &["bivvy_version", "project_name", "project_root"];
 
 /// Validation error with context.
 #[derive(Debug, Clone)]
@@ -105,6 +106,38 @@ fn validate_vars(config: &BivvyConfig) -> Vec<ValidationError> {
                 });
             }
         }
+
+        // File-backed and env vars must name their source
+        let source = match def {
+            VarDefinition::File { file } => Some(("file", file)),
+            VarDefinition::Json { json, .. } => Some(("json", json)),
+            VarDefinition::Yaml { yaml, .. } => Some(("yaml", yaml)),
+            VarDefinition::Toml { toml, .. } => Some(("toml", toml)),
+            VarDefinition::Env { env, .. } => Some(("env", env)),
+            _ => None,
+        };
+        let key = match def {
+            VarDefinition::Json { key, .. }
+            | VarDefinition::Yaml { key, .. }
+            | VarDefinition::Toml { key, .. } => Some(key),
+            _ => None,
+        };
+        if let Some((field, _)) = source.filter(|(_, v)| v.trim().is_empty()) {
+            errors.push(ValidationError {
+                rule: "empty-var-source".to_string(),
+                message: format!("Variable '{}' has an empty '{}' field.", name, field),
+                step: None,
+                workflow: None,
+            });
+        }
+        if key.is_some_and(|k| k.trim().is_empty()) {
+            errors.push(ValidationError {
+                rule: "empty-var-source".to_string(),
+                message: format!("Variable '{}' has an empty 'key' field.", name),
+                step: None,
+                workflow: None,
+            });
+        }
     }
 
     errors
@@ -125,6 +158,21 @@ fn validate_steps(config: &BivvyConfig) -> Vec<ValidationError> {
             });
         }
 
+        // An empty cache key would let unrelated runs share one artifact
+        if let Some(cache) = &step.execution.cache {
+            if cache.key.iter().all(|entry| entry.trim().is_empty()) {
+                errors.push(ValidationError {
+                    rule: "empty-cache-key".to_string(),
+                    message: format!(
+                        "Step '{}' caches artifacts without a key; list the files that determine its outputs under cache.key",
+                        name
+                    ),
+                    step: Some(name.clone()),
+                    workflow: None,
+                });
+            }
+        }
+
         // Validate depends_on references
         for dep in &step.depends_on {
             if !config.steps.contains_key(dep) {
@@ -261,7 +309,8 @@ pub fn validate(config: &BivvyConfig) -> Result<()> {
 mod tests {
     use super::*;
     use crate::config::schema::{
-        ExecutionConfig, StepConfig, StepOverride, VarDefinition, WorkflowConfig,
+        ArtifactCacheConfig, ExecutionConfig, StepConfig, StepOverride, VarDefinition,
+        WorkflowConfig,
     };
 
     #[test]
@@ -275,6 +324,27 @@ mod tests {
         assert!(errors.iter().any(|e| e.rule == "missing-command"));
     }
 
+    #[test]
+    fn validates_cache_key_not_empty() {
+        let mut config = BivvyConfig::default();
+        let mut step = StepConfig::default();
+        step.execution.command = Some("yarn build".to_string());
+        step.execution.cache = Some(ArtifactCacheConfig {
+            key: vec![" ".to_string()],
+            paths: vec!["dist".to_string()],
+        });
+        config.steps.insert("build".to_string(), step);
+
+        let errors = validate_config(&config);
+        assert!(errors.iter().any(|e| e.rule == "empty-cache-key"));
+
+        config.steps.get_mut("build").unwrap().execution.cache = Some(ArtifactCacheConfig {
+            key: vec!["yarn.lock".to_string()],
+            paths: vec!["dist".to_string()],
+        });
+        assert!(validate_config(&config).is_empty());
+    }
+
     #[test]
     fn validates_depends_on_exists() {
         let mut config = BivvyConfig::default();
@@ -556,6 +626,40 @@ mod tests {
         assert!(!errors.iter().any(|e| e.rule == "builtin-var-collision"));
     }
 
+    #[test]
+    fn validates_empty_var_sources() {
+        let mut config = BivvyConfig::default();
+        config.vars.insert(
+            "version".to_string(),
+            VarDefinition::Json {
+                json: "package.json".to_string(),
+                key: " ".to_string(),
+            },
+        );
+        config.vars.insert(
+            "port".to_string(),
+            VarDefinition::Env {
+                env: "".to_string(),
+                default: None,
+            },
+        );
+
+        let errors = validate_config(&config);
+        let mut messages: Vec<_> = errors
+            .iter()
+            .filter(|e| e.rule == "empty-var-source")
+            .map(|e| e.message.as_str())
+            .collect();
+        messages.sort();
+        assert_eq!(
+            messages,
+            vec![
+                "Variable 'port' has an empty 'env' field.",
+                "Variable 'version' has an empty 'key' field.",
+            ]
+        );
+    }
+
     #[test]
     fn no_var_errors_for_empty_vars() {
         let config = BivvyConfig::default();
@@ -2,29 +2,86 @@
 //!
 //! Evaluates `vars:` definitions from config into resolved string values
 //! that can be injected into [`InterpolationContext`](super::InterpolationContext).
+//!
+//! [`VarEvaluator`] evaluates lazily: a var is only computed when it is
+//! asked for, and each value is computed at most once per evaluator, so a
+//! run that never references a computed var never runs its command.
 
 use crate::config::schema::VarDefinition;
 use crate::error::{BivvyError, Result};
 use crate::shell::execute_quiet;
+use crate::ui::{Prompt, PromptType, UserInterface};
 use std::collections::HashMap;
 use std::path::Path;
 
-/// Evaluate all variable definitions into resolved string values.
-///
-/// Static vars pass through unchanged. Computed vars run the command via
-/// `shell::execute_quiet` and use trimmed stdout. Fails if any computed
-/// var's command returns non-zero.
-pub fn evaluate_vars(
-    vars: &HashMap<String, VarDefinition>,
-    project_root: &Path,
-) -> Result<HashMap<String, String>> {
-    let mut resolved = HashMap::new();
-
-    for (name, def) in vars {
-        let value = match def {
-            VarDefinition::Static(s) => s.clone(),
+/// Lazily evaluates `vars:` definitions, caching each value for the run.
+pub struct VarEvaluator<'a> {
+    vars: &'a HashMap<String, VarDefinition>,
+    project_root: &'a Path,
+    cache: HashMap<String, String>,
+    secrets: Vec<String>,
+}
+
+impl<'a> VarEvaluator<'a> {
+    /// Create an evaluator over the given definitions.
+    pub fn new(vars: &'a HashMap<String, VarDefinition>, project_root: &'a Path) -> Self {
+        Self {
+            vars,
+            project_root,
+            cache: HashMap::new(),
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Evaluate the named vars, skipping names that aren't defined.
+    ///
+    /// Returns every value evaluated so far, including earlier calls.
+    pub fn evaluate<'n>(
+        &mut self,
+        names: impl IntoIterator<Item = &'n str>,
+        ui: &mut dyn UserInterface,
+    ) -> Result<HashMap<String, String>> {
+        let mut names: Vec<&str> = names.into_iter().collect();
+        names.sort_unstable();
+        for name in names {
+            self.get(name, ui)?;
+        }
+        Ok(self.cache.clone())
+    }
+
+    /// Evaluate a single var, returning `None` if it isn't defined.
+    pub fn get(&mut self, name: &str, ui: &mut dyn UserInterface) -> Result<Option<String>> {
+        if let Some(value) = self.cache.get(name) {
+            return Ok(Some(value.clone()));
+        }
+        let Some(def) = self.vars.get(name) else {
+            return Ok(None);
+        };
+
+        let value = self.compute(name, def, ui)?;
+        self.cache.insert(name.to_string(), value.clone());
+        Ok(Some(value))
+    }
+
+    /// Values of secret prompt vars evaluated so far.
+    pub fn secrets(&self) -> &[String] {
+        &self.secrets
+    }
+
+    fn compute(
+        &mut self,
+        name: &str,
+        def: &VarDefinition,
+        ui: &mut dyn UserInterface,
+    ) -> Result<String> {
+        let err = |message: String| BivvyError::ConfigValidationError {
+            message: format!("Variable '{}': {}", name, message),
+        };
+
+        match def {
+            VarDefinition::Static(s) => Ok(s.clone()),
             VarDefinition::Computed { command } => {
-                let result = execute_quiet(command, Some(project_root))?;
+                let result = execute_quiet(command, Some(self.project_root))?;
                 if !result.success {
                     return Err(BivvyError::ConfigValidationError {
                         message: format!(
@@ -35,18 +92,109 @@ pub fn evaluate_vars(
                         ),
                     });
                 }
-                result.stdout.trim().to_string()
+                Ok(result.stdout.trim().to_string())
+            }
+            VarDefinition::File { file } => Ok(self.read(file).map_err(err)?.trim().to_string()),
+            VarDefinition::Json { json, key } => {
+                let content = self.read(json).map_err(err)?;
+                let doc: serde_json::Value = serde_json::from_str(&content)
+                    .map_err(|e| err(format!("{} is not valid JSON: {}", json, e)))?;
+                lookup_key(&doc, key, json).map_err(err)
+            }
+            VarDefinition::Yaml { yaml, key } => {
+                let content = self.read(yaml).map_err(err)?;
+                let doc: serde_json::Value = serde_yaml::from_str(&content)
+                    .map_err(|e| err(format!("{} is not valid YAML: {}", yaml, e)))?;
+                lookup_key(&doc, key, yaml).map_err(err)
+            }
+            VarDefinition::Toml { toml, key } => {
+                let content = self.read(toml).map_err(err)?;
+                let doc: serde_json::Value = ::toml::from_str(&content)
+                    .map_err(|e| err(format!("{} is not valid TOML: {}", toml, e)))?;
+                lookup_key(&doc, key, toml).map_err(err)
+            }
+            VarDefinition::Env { env, default } => match std::env::var(env) {
+                Ok(value) => Ok(value),
+                Err(_) => default
+                    .clone()
+                    .ok_or_else(|| err(format!("environment variable {} is not set", env))),
+            },
+            VarDefinition::Prompt {
+                prompt,
+                default,
+                secret,
+            } => {
+                let value = ui
+                    .prompt(&Prompt {
+                        key: name.to_string(),
+                        question: prompt.clone(),
+                        prompt_type: if *secret {
+                            PromptType::Secret
+                        } else {
+                            PromptType::Input
+                        },
+                        default: default.clone(),
+                    })?
+                    .as_string();
+                if *secret && !value.is_empty() {
+                    self.secrets.push(value.clone());
+                }
+                Ok(value)
+            }
+        }
+    }
+
+    fn read(&self, file: &str) -> std::result::Result<String, String> {
+        std::fs::read_to_string(self.project_root.join(file))
+            .map_err(|e| format!("cannot read {}: {}", file, e))
+    }
+}
+
+/// Follow a dotted key path (`a.b.0.c`) through a parsed document.
+///
+/// Numeric segments index into arrays. The target must be a scalar.
+fn lookup_key(
+    doc: &serde_json::Value,
+    key: &str,
+    file: &str,
+) -> std::result::Result<String, String> {
+    let mut current = doc;
+    for segment in key.split('.') {
+        let next = match current {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(items) => {
+                segment.parse::<usize>().ok().and_then(|i| items.get(i))
             }
+            _ => None,
         };
-        resolved.insert(name.clone(), value);
+        current = next.ok_or_else(|| format!("key '{}' not found in {}", key, file))?;
     }
 
-    Ok(resolved)
+    match current {
+        serde_json::Value::String(s) => Ok(s.clone()),
+        serde_json::Value::Number(n) => Ok(n.to_string()),
+        serde_json::Value::Bool(b) => Ok(b.to_string()),
+        serde_json::Value::Null => Ok(String::new()),
+        _ => Err(format!(
+            "key '{}' in {} is not a string, number, or boolean",
+            key, file
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ui::{NonInteractiveUI, OutputMode};
+
+    /// Evaluate every definition without a terminal.
+    fn evaluate_vars(
+        vars: &HashMap<String, VarDefinition>,
+        project_root: &Path,
+    ) -> Result<HashMap<String, String>> {
+        let mut ui = NonInteractiveUI::new(OutputMode::Quiet);
+        VarEvaluator::new(vars, project_root).evaluate(vars.keys().map(String::as_str), &mut ui)
+    }
 
     #[test]
     fn evaluate_static_var() {
@@ -145,4 +293,197 @@ mod tests {
         assert_eq!(result.get("static_var"), Some(&"hello".to_string()));
         assert_eq!(result.get("computed_var"), Some(&"world".to_string()));
     }
+
+    fn write(temp: &tempfile::TempDir, name: &str, content: &str) {
+        std::fs::write(temp.path().join(name), content).unwrap();
+    }
+
+    #[test]
+    fn evaluate_file_var_trims_contents() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(&temp, "VERSION", "1.4.2\n");
+        let mut vars = HashMap::new();
+        vars.insert(
+            "version".to_string(),
+            VarDefinition::File {
+                file: "VERSION".to_string(),
+            },
+        );
+
+        let result = evaluate_vars(&vars, temp.path()).unwrap();
+        assert_eq!(result.get("version"), Some(&"1.4.2".to_string()));
+    }
+
+    #[test]
+    fn evaluate_structured_key_paths() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(
+            &temp,
+            "package.json",
+            r#"{"version": "2.0.0", "engines": {"node": ">=20"}, "files": ["dist", "lib"]}"#,
+        );
+        write(
+            &temp,
+            "database.yml",
+            "development:\n  port: 5432\n  ssl: false\n",
+        );
+        write(
+            &temp,
+            "Cargo.toml",
+            "[package]\nname = \"app\"\nversion = \"0.3.1\"\n",
+        );
+
+        let yaml: HashMap<String, VarDefinition> = serde_yaml::from_str(
+            r#"
+            node: { json: package.json, key: engines.node }
+            first_file: { json: package.json, key: files.1 }
+            port: { yaml: database.yml, key: development.port }
+            ssl: { yaml: database.yml, key: development.ssl }
+            crate_version: { toml: Cargo.toml, key: package.version }
+            "#,
+        )
+        .unwrap();
+
+        let result = evaluate_vars(&yaml, temp.path()).unwrap();
+        assert_eq!(result["node"], ">=20");
+        assert_eq!(result["first_file"], "lib");
+        assert_eq!(result["port"], "5432");
+        assert_eq!(result["ssl"], "false");
+        assert_eq!(result["crate_version"], "0.3.1");
+    }
+
+    #[test]
+    fn missing_key_reports_var_and_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(&temp, "package.json", r#"{"version": "2.0.0"}"#);
+        let vars: HashMap<String, VarDefinition> =
+            serde_yaml::from_str("node: { json: package.json, key: engines.node }").unwrap();
+
+        let err = evaluate_vars(&vars, temp.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Variable 'node': key 'engines.node' not found in package.json"
+        );
+    }
+
+    #[test]
+    fn non_scalar_key_is_rejected() {
+        let temp = tempfile::TempDir::new().unwrap();
+        write(&temp, "package.json", r#"{"engines": {"node": "20"}}"#);
+        let vars: HashMap<String, VarDefinition> =
+            serde_yaml::from_str("engines: { json: package.json, key: engines }").unwrap();
+
+        let err = evaluate_vars(&vars, temp.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Variable 'engines': key 'engines' in package.json is not a string, number, or boolean"
+        );
+    }
+
+    #[test]
+    fn env_var_falls_back_to_default() {
+        let vars: HashMap<String, VarDefinition> = serde_yaml::from_str(
+            r#"
+            set: { env: BIVVY_VARS_TEST_SET }
+            unset: { env: BIVVY_VARS_TEST_UNSET, default: "3000" }
+            "#,
+        )
+        .unwrap();
+        std::env::set_var("BIVVY_VARS_TEST_SET", "from-env");
+        std::env::remove_var("BIVVY_VARS_TEST_UNSET");
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let result = evaluate_vars(&vars, temp.path()).unwrap();
+        std::env::remove_var("BIVVY_VARS_TEST_SET");
+
+        assert_eq!(result["set"], "from-env");
+        assert_eq!(result["unset"], "3000");
+    }
+
+    #[test]
+    fn env_var_without_default_errors_when_unset() {
+        let vars: HashMap<String, VarDefinition> =
+            serde_yaml::from_str("token: { env: BIVVY_VARS_TEST_MISSING }").unwrap();
+        std::env::remove_var("BIVVY_VARS_TEST_MISSING");
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let err = evaluate_vars(&vars, temp.path()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Variable 'token': environment variable BIVVY_VARS_TEST_MISSING is not set"
+        );
+    }
+
+    #[test]
+    fn prompt_var_asks_once_and_records_secrets() {
+        let vars: HashMap<String, VarDefinition> = serde_yaml::from_str(
+            r#"
+            region: { prompt: "Region?" }
+            api_token: { prompt: "Token?", secret: true }
+            "#,
+        )
+        .unwrap();
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut ui = crate::ui::MockUI::new();
+        ui.set_prompt_response("region", "eu");
+        ui.set_prompt_response("api_token", "t0ken");
+
+        let mut evaluator = VarEvaluator::new(&vars, temp.path());
+        assert_eq!(
+            evaluator.get("region", &mut ui).unwrap(),
+            Some("eu".to_string())
+        );
+        assert_eq!(
+            evaluator.get("region", &mut ui).unwrap(),
+            Some("eu".to_string())
+        );
+        evaluator.get("api_token", &mut ui).unwrap();
+
+        assert_eq!(ui.prompts_shown(), ["region", "api_token"]);
+        assert_eq!(evaluator.secrets(), ["t0ken"]);
+    }
+
+    #[test]
+    fn evaluator_skips_unreferenced_vars() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let marker = temp.path().join("ran");
+        let mut vars = HashMap::new();
+        vars.insert("used".to_string(), VarDefinition::Static("yes".to_string()));
+        vars.insert(
+            "unused".to_string(),
+            VarDefinition::Computed {
+                command: format!("touch {}", marker.display()),
+            },
+        );
+
+        let mut ui = crate::ui::MockUI::new();
+        let result = VarEvaluator::new(&vars, temp.path())
+            .evaluate(["used", "not_a_var"], &mut ui)
+            .unwrap();
+
+        assert_eq!(result.len(), 1);
+        assert_eq!(result["used"], "yes");
+        assert!(!marker.exists(), "unreferenced computed var must not run");
+    }
+
+    #[test]
+    fn evaluator_caches_computed_values() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let counter = temp.path().join("count");
+        let mut vars = HashMap::new();
+        vars.insert(
+            "stamp".to_string(),
+            VarDefinition::Computed {
+                command: format!("echo x >> {0} && wc -l < {0}", counter.display()),
+            },
+        );
+
+        let mut ui = crate::ui::MockUI::new();
+        let mut evaluator = VarEvaluator::new(&vars, temp.path());
+        let first = evaluator.get("stamp", &mut ui).unwrap();
+        let second = evaluator.get("stamp", &mut ui).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(std::fs::read_to_string(&counter).unwrap(), "x\n");
+    }
 }
@@ -2,10 +2,12 @@
 
 use std::path::Path;
 
+use serde::Serialize;
+
 use super::file_detection::file_exists;
 
 /// A detected conflict.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Conflict {
     pub kind: ConflictKind,
     pub message: String,
@@ -14,7 +16,8 @@ pub struct Conflict {
 }
 
 /// Type of conflict.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConflictKind {
     /// Multiple Node package manager lockfiles.
     NodeLockfiles,
@@ -91,6 +91,30 @@ pub fn any_file_exists(project_root: &Path, files: &[&str]) -> Option<String> {
         .map(|f| f.to_string())
 }
 
+/// Return the files that exist, in the order given.
+pub fn existing_files(project_root: &Path, files: &[&str]) -> Vec<String> {
+    files
+        .iter()
+        .filter(|f| project_root.join(f).exists())
+        .map(|f| f.to_string())
+        .collect()
+}
+
+/// Names of the top-level entries ending in any of `suffixes`, sorted.
+pub fn files_with_suffix(project_root: &Path, suffixes: &[&str]) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(project_root)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.file_name().to_string_lossy().into_owned())
+                .filter(|name| suffixes.iter().any(|s| name.ends_with(s)))
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
 /// Check if all files exist.
 pub fn all_files_exist(project_root: &Path, files: &[&str]) -> bool {
     files.iter().all(|f| project_root.join(f).exists())
@@ -184,4 +208,25 @@ mod tests {
         assert!(all_files_exist(temp.path(), &["a.txt", "b.txt"]));
         assert!(!all_files_exist(temp.path(), &["a.txt", "c.txt"]));
     }
+
+    #[test]
+    fn existing_files_keeps_order() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("b.txt"), "").unwrap();
+        fs::write(temp.path().join("a.txt"), "").unwrap();
+
+        let found = existing_files(temp.path(), &["b.txt", "missing.txt", "a.txt"]);
+        assert_eq!(found, vec!["b.txt", "a.txt"]);
+    }
+
+    #[test]
+    fn files_with_suffix_sorted() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Web.csproj"), "").unwrap();
+        fs::write(temp.path().join("App.sln"), "").unwrap();
+        fs::write(temp.path().join("README.md"), "").unwrap();
+
+        let found = files_with_suffix(temp.path(), &[".sln", ".csproj"]);
+        assert_eq!(found, vec!["App.sln", "Web.csproj"]);
+    }
 }
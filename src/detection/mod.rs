@@ -6,6 +6,7 @@ pub mod environment;
 pub mod file_detection;
 pub mod package_manager;
 pub mod project;
+pub mod report;
 pub mod runner;
 pub mod types;
 
@@ -13,5 +14,6 @@ pub use conflicts::ConflictDetector;
 pub use environment::EnvironmentDetector;
 pub use package_manager::PackageManagerDetector;
 pub use project::ProjectDetector;
+pub use report::{proposed_templates, DetectedItem, DetectionReport, ProposedTemplate};
 pub use runner::{DetectionRunner, FullDetection, SuggestedTemplate};
 pub use types::{Detection, DetectionKind, DetectionResult};
@@ -3,7 +3,8 @@
 use std::path::Path;
 
 use super::command_detection::command_succeeds;
-use super::file_detection::file_exists;
+use super::file_detection::{any_file_exists, existing_files, file_exists, files_with_suffix};
+use super::types::DetectionResult;
 
 /// Detected package manager.
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +54,9 @@ pub struct PackageManagerDetection {
     pub system: Option<PackageManager>,
     pub version_manager: Option<PackageManager>,
     pub language_managers: Vec<PackageManager>,
+    /// Evidence and confidence for each detected manager, in the order
+    /// system, version manager, language managers.
+    pub details: Vec<DetectionResult>,
 }
 
 /// Detects installed package managers.
@@ -61,19 +65,39 @@ pub struct PackageManagerDetector;
 impl PackageManagerDetector {
     /// Detect package managers for a project.
     pub fn detect(project_root: &Path) -> PackageManagerDetection {
+        let system = Self::detect_system_package_manager(project_root);
+        let version_manager = Self::detect_version_manager(project_root);
+        let language_managers = Self::detect_language_managers(project_root);
+
+        let mut details: Vec<DetectionResult> = Vec::new();
+        details.extend(system.iter().map(|(_, d)| d.clone()));
+        details.extend(version_manager.iter().map(|(_, d)| d.clone()));
+        details.extend(language_managers.iter().map(|(_, d)| d.clone()));
+
         PackageManagerDetection {
-            system: Self::detect_system_package_manager(project_root),
-            version_manager: Self::detect_version_manager(project_root),
-            language_managers: Self::detect_language_managers(project_root),
+            system: system.map(|(pm, _)| pm),
+            version_manager: version_manager.map(|(pm, _)| pm),
+            language_managers: language_managers.into_iter().map(|(pm, _)| pm).collect(),
+            details,
         }
     }
 
-    fn detect_system_package_manager(project_root: &Path) -> Option<PackageManager> {
+    fn found(pm: &PackageManager) -> DetectionResult {
+        DetectionResult::found(&format!("{:?}", pm))
+    }
+
+    fn detect_system_package_manager(
+        project_root: &Path,
+    ) -> Option<(PackageManager, DetectionResult)> {
         // Only suggest a system package manager step if the project has a
         // config file for it (e.g. Brewfile). A tool being installed on the
         // system alone is not enough — the step would have nothing to do.
         if file_exists(project_root, "Brewfile") && command_succeeds("brew --version") {
-            return Some(PackageManager::Homebrew);
+            let pm = PackageManager::Homebrew;
+            let detail = Self::found(&pm)
+                .with_files(&["Brewfile"])
+                .with_command("brew --version");
+            return Some((pm, detail));
         }
 
         // Windows package managers don't have a standard project-level config
@@ -82,36 +106,55 @@ impl PackageManagerDetector {
         None
     }
 
-    fn detect_version_manager(project_root: &Path) -> Option<PackageManager> {
+    fn detect_version_manager(project_root: &Path) -> Option<(PackageManager, DetectionResult)> {
+        let with_file = |pm: PackageManager, file: &str| {
+            let detail = Self::found(&pm).with_files(&[file]);
+            (pm, detail)
+        };
+
         // Check for config files first (cheap)
-        if file_exists(project_root, ".mise.toml") || file_exists(project_root, "mise.toml") {
-            return Some(PackageManager::Mise);
+        if let Some(file) = any_file_exists(project_root, &[".mise.toml", "mise.toml"]) {
+            return Some(with_file(PackageManager::Mise, &file));
         }
         if file_exists(project_root, ".tool-versions") {
-            return Some(PackageManager::Asdf);
+            return Some(with_file(PackageManager::Asdf, ".tool-versions"));
         }
         if file_exists(project_root, ".nvmrc") {
             // Could be nvm or fnm — check if fnm is installed (it reads .nvmrc too)
             if command_succeeds("fnm --version") {
-                return Some(PackageManager::Fnm);
+                let (pm, detail) = with_file(PackageManager::Fnm, ".nvmrc");
+                return Some((pm, detail.with_command("fnm --version")));
             }
-            return Some(PackageManager::Nvm);
+            let (pm, detail) = with_file(PackageManager::Nvm, ".nvmrc");
+            return Some((pm, detail.with_confidence(0.8)));
         }
         if file_exists(project_root, ".node-version") {
             // fnm, volta, and nodenv all read .node-version
             if command_succeeds("fnm --version") {
-                return Some(PackageManager::Fnm);
+                let (pm, detail) = with_file(PackageManager::Fnm, ".node-version");
+                return Some((
+                    pm,
+                    detail.with_command("fnm --version").with_confidence(0.8),
+                ));
             }
             if command_succeeds("volta --version") {
-                return Some(PackageManager::Volta);
+                let (pm, detail) = with_file(PackageManager::Volta, ".node-version");
+                return Some((
+                    pm,
+                    detail.with_command("volta --version").with_confidence(0.8),
+                ));
             }
-            return Some(PackageManager::Nvm);
+            let (pm, detail) = with_file(PackageManager::Nvm, ".node-version");
+            return Some((pm, detail.with_confidence(0.5)));
         }
         if file_exists(project_root, ".ruby-version") {
-            return Some(PackageManager::Rbenv);
+            // Also read by chruby, rvm and mise
+            let (pm, detail) = with_file(PackageManager::Rbenv, ".ruby-version");
+            return Some((pm, detail.with_confidence(0.7)));
         }
         if file_exists(project_root, ".python-version") {
-            return Some(PackageManager::Pyenv);
+            let (pm, detail) = with_file(PackageManager::Pyenv, ".python-version");
+            return Some((pm, detail.with_confidence(0.8)));
         }
 
         // Only suggest version managers when the project has a config file.
@@ -121,92 +164,106 @@ impl PackageManagerDetector {
         None
     }
 
-    fn detect_language_managers(project_root: &Path) -> Vec<PackageManager> {
+    fn detect_language_managers(project_root: &Path) -> Vec<(PackageManager, DetectionResult)> {
+        let dotnet_files = files_with_suffix(project_root, &[".sln", ".csproj"]);
+
         let mut managers = Vec::new();
+        let mut push = |pm: PackageManager, files: &[&str], confidence: f32| {
+            let detail = Self::found(&pm)
+                .with_files(&existing_files(project_root, files))
+                .with_confidence(confidence);
+            managers.push((pm, detail));
+        };
 
         // Ruby
         if file_exists(project_root, "Gemfile") {
-            managers.push(PackageManager::Bundler);
+            push(PackageManager::Bundler, &["Gemfile", "Gemfile.lock"], 1.0);
         }
 
         // Node
         if file_exists(project_root, "yarn.lock") {
-            managers.push(PackageManager::Yarn);
+            push(PackageManager::Yarn, &["yarn.lock"], 1.0);
         } else if file_exists(project_root, "pnpm-lock.yaml") {
-            managers.push(PackageManager::Pnpm);
+            push(PackageManager::Pnpm, &["pnpm-lock.yaml"], 1.0);
         } else if file_exists(project_root, "bun.lockb") {
-            managers.push(PackageManager::Bun);
-        } else if file_exists(project_root, "package-lock.json")
-            || file_exists(project_root, "package.json")
-        {
-            managers.push(PackageManager::Npm);
+            push(PackageManager::Bun, &["bun.lockb"], 1.0);
+        } else if file_exists(project_root, "package-lock.json") {
+            push(PackageManager::Npm, &["package-lock.json"], 1.0);
+        } else if file_exists(project_root, "package.json") {
+            // No lockfile: npm is the default, not a certainty
+            push(PackageManager::Npm, &["package.json"], 0.7);
         }
 
         // Python
         if file_exists(project_root, "poetry.lock") {
-            managers.push(PackageManager::Poetry);
+            push(PackageManager::Poetry, &["poetry.lock"], 1.0);
         } else if file_exists(project_root, "uv.lock") {
-            managers.push(PackageManager::Uv);
-        } else if file_exists(project_root, "requirements.txt")
-            || file_exists(project_root, "pyproject.toml")
-        {
-            managers.push(PackageManager::Pip);
+            push(PackageManager::Uv, &["uv.lock"], 1.0);
+        } else if file_exists(project_root, "requirements.txt") {
+            push(
+                PackageManager::Pip,
+                &["requirements.txt", "pyproject.toml"],
+                1.0,
+            );
+        } else if file_exists(project_root, "pyproject.toml") {
+            // pyproject.toml alone may belong to hatch, pdm or similar
+            push(PackageManager::Pip, &["pyproject.toml"], 0.6);
         }
 
         // PHP
         if file_exists(project_root, "composer.json") {
-            managers.push(PackageManager::Composer);
+            push(
+                PackageManager::Composer,
+                &["composer.json", "composer.lock"],
+                1.0,
+            );
         }
 
         // Kotlin/JVM
         if file_exists(project_root, "build.gradle.kts")
             || file_exists(project_root, "build.gradle")
         {
-            managers.push(PackageManager::Gradle);
+            push(
+                PackageManager::Gradle,
+                &["build.gradle.kts", "build.gradle"],
+                1.0,
+            );
         }
 
         // Elixir
         if file_exists(project_root, "mix.exs") {
-            managers.push(PackageManager::Mix);
+            push(PackageManager::Mix, &["mix.exs", "mix.lock"], 1.0);
         }
 
         // Rust
         if file_exists(project_root, "Cargo.toml") {
-            managers.push(PackageManager::Cargo);
+            push(PackageManager::Cargo, &["Cargo.toml", "Cargo.lock"], 1.0);
         }
 
         // Go
         if file_exists(project_root, "go.mod") {
-            managers.push(PackageManager::Go);
+            push(PackageManager::Go, &["go.mod", "go.sum"], 1.0);
         }
 
         // Maven (Java)
         if file_exists(project_root, "pom.xml") {
-            managers.push(PackageManager::Maven);
+            push(PackageManager::Maven, &["pom.xml"], 1.0);
         }
 
         // .NET
-        if std::fs::read_dir(project_root)
-            .map(|entries| {
-                entries.filter_map(|e| e.ok()).any(|e| {
-                    let name = e.file_name();
-                    let name = name.to_string_lossy();
-                    name.ends_with(".sln") || name.ends_with(".csproj")
-                })
-            })
-            .unwrap_or(false)
-        {
-            managers.push(PackageManager::Dotnet);
+        if !dotnet_files.is_empty() {
+            let files: Vec<&str> = dotnet_files.iter().map(String::as_str).collect();
+            push(PackageManager::Dotnet, &files, 1.0);
         }
 
         // Dart/Flutter
         if file_exists(project_root, "pubspec.yaml") {
-            managers.push(PackageManager::Dart);
+            push(PackageManager::Dart, &["pubspec.yaml", "pubspec.lock"], 1.0);
         }
 
         // Deno
         if file_exists(project_root, "deno.json") || file_exists(project_root, "deno.jsonc") {
-            managers.push(PackageManager::Deno);
+            push(PackageManager::Deno, &["deno.json", "deno.jsonc"], 1.0);
         }
 
         managers
@@ -384,4 +441,26 @@ mod tests {
 
         assert!(detection.language_managers.contains(&PackageManager::Deno));
     }
+
+    #[test]
+    fn details_record_evidence_and_confidence() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("Cargo.toml"), "").unwrap();
+        fs::write(temp.path().join("Cargo.lock"), "").unwrap();
+
+        let detection = PackageManagerDetector::detect(temp.path());
+
+        let npm = detection.details.iter().find(|d| d.name == "Npm").unwrap();
+        assert_eq!(npm.matched_files(), vec!["package.json"]);
+        assert!(npm.confidence < 1.0);
+
+        let cargo = detection
+            .details
+            .iter()
+            .find(|d| d.name == "Cargo")
+            .unwrap();
+        assert_eq!(cargo.matched_files(), vec!["Cargo.toml", "Cargo.lock"]);
+        assert_eq!(cargo.confidence, 1.0);
+    }
 }
@@ -4,7 +4,7 @@ use std::path::Path;
 
 use crate::registry::TemplateName;
 
-use super::file_detection::{any_file_exists, file_exists};
+use super::file_detection::{any_file_exists, existing_files, file_exists, files_with_suffix};
 use super::types::DetectionResult;
 
 /// Detected project type.
@@ -50,16 +50,19 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Ruby")
                     .with_detail("Gemfile found")
+                    .with_files(&["Gemfile"])
                     .with_template(TemplateName::BundleInstall),
             );
 
             // Rails (detected alongside Ruby)
-            if file_exists(project_root, "config/routes.rb")
-                || file_exists(project_root, "config/application.rb")
-            {
+            let rails_files =
+                existing_files(project_root, &["config/application.rb", "config/routes.rb"]);
+            if !rails_files.is_empty() {
                 details.push(
                     DetectionResult::found("Rails")
                         .with_detail("Rails application detected")
+                        .with_files(&rails_files)
+                        .with_confidence(if rails_files.len() == 2 { 1.0 } else { 0.8 })
                         .with_template(TemplateName::RailsDb),
                 );
             }
@@ -89,77 +92,87 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Node.js")
                     .with_detail("package.json found")
+                    .with_files(&["package.json"])
+                    .with_files(lockfile.as_slice())
                     .with_template(template),
             );
 
             // Next.js (detected alongside Node.js)
-            if any_file_exists(
+            let next_files = existing_files(
                 project_root,
                 &["next.config.js", "next.config.mjs", "next.config.ts"],
-            )
-            .is_some()
-            {
+            );
+            if !next_files.is_empty() {
                 details.push(
                     DetectionResult::found("Next.js")
                         .with_detail("Next.js config found")
+                        .with_files(&next_files)
                         .with_template(TemplateName::NextjsBuild),
                 );
             }
 
             // Vite (detected alongside Node.js)
-            if any_file_exists(
+            let vite_files = existing_files(
                 project_root,
                 &["vite.config.js", "vite.config.ts", "vite.config.mjs"],
-            )
-            .is_some()
-            {
+            );
+            if !vite_files.is_empty() {
                 details.push(
                     DetectionResult::found("Vite")
                         .with_detail("Vite config found")
+                        .with_files(&vite_files)
                         .with_template(TemplateName::ViteBuild),
                 );
             }
 
             // Remix (detected alongside Node.js)
-            if any_file_exists(project_root, &["remix.config.js", "remix.config.ts"]).is_some()
-                || file_exists(project_root, "app/root.tsx")
-            {
+            let remix_config =
+                existing_files(project_root, &["remix.config.js", "remix.config.ts"]);
+            let remix_root = existing_files(project_root, &["app/root.tsx"]);
+            if !remix_config.is_empty() || !remix_root.is_empty() {
+                // app/root.tsx alone is shared with other React Router setups
                 details.push(
                     DetectionResult::found("Remix")
                         .with_detail("Remix application detected")
+                        .with_files(&remix_config)
+                        .with_files(&remix_root)
+                        .with_confidence(if remix_config.is_empty() { 0.6 } else { 1.0 })
                         .with_template(TemplateName::RemixBuild),
                 );
             }
         }
 
         // Python
-        if file_exists(project_root, "pyproject.toml")
-            || file_exists(project_root, "requirements.txt")
-            || file_exists(project_root, "setup.py")
-        {
+        let python_files = existing_files(
+            project_root,
+            &["pyproject.toml", "requirements.txt", "setup.py"],
+        );
+        if !python_files.is_empty() {
             all_types.push(ProjectType::Python);
 
-            let template = if file_exists(project_root, "poetry.lock") {
-                TemplateName::PoetryInstall
+            let (template, lockfile) = if file_exists(project_root, "poetry.lock") {
+                (TemplateName::PoetryInstall, Some("poetry.lock"))
             } else if file_exists(project_root, "uv.lock") {
-                TemplateName::UvSync
+                (TemplateName::UvSync, Some("uv.lock"))
             } else {
-                TemplateName::PipInstall
+                (TemplateName::PipInstall, None)
             };
 
             details.push(
                 DetectionResult::found("Python")
                     .with_detail("Python project detected")
+                    .with_files(&python_files)
+                    .with_files(lockfile.as_slice())
                     .with_template(template),
             );
 
             // Alembic (detected alongside Python)
-            if file_exists(project_root, "alembic.ini")
-                || file_exists(project_root, "alembic/env.py")
-            {
+            let alembic_files = existing_files(project_root, &["alembic.ini", "alembic/env.py"]);
+            if !alembic_files.is_empty() {
                 details.push(
                     DetectionResult::found("Alembic")
                         .with_detail("Alembic configuration found")
+                        .with_files(&alembic_files)
                         .with_template(TemplateName::AlembicMigrate),
                 );
             }
@@ -169,6 +182,8 @@ impl ProjectDetector {
                 details.push(
                     DetectionResult::found("Django")
                         .with_detail("manage.py found")
+                        .with_files(&["manage.py"])
+                        .with_confidence(0.9)
                         .with_template(TemplateName::DjangoMigrate),
                 );
             }
@@ -180,6 +195,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Rust")
                     .with_detail("Cargo.toml found")
+                    .with_files(&["Cargo.toml"])
                     .with_template(TemplateName::CargoBuild),
             );
 
@@ -188,6 +204,7 @@ impl ProjectDetector {
                 details.push(
                     DetectionResult::found("Diesel")
                         .with_detail("diesel.toml found")
+                        .with_files(&["diesel.toml"])
                         .with_template(TemplateName::DieselMigrate),
                 );
             }
@@ -199,6 +216,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Go")
                     .with_detail("go.mod found")
+                    .with_files(&["go.mod"])
                     .with_template(TemplateName::GoModDownload),
             );
         }
@@ -209,6 +227,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("PHP")
                     .with_detail("composer.json found")
+                    .with_files(&["composer.json"])
                     .with_template(TemplateName::ComposerInstall),
             );
 
@@ -217,29 +236,38 @@ impl ProjectDetector {
                 details.push(
                     DetectionResult::found("Laravel")
                         .with_detail("artisan found")
+                        .with_files(&["artisan"])
                         .with_template(TemplateName::LaravelSetup),
                 );
             }
         }
 
         // Kotlin/JVM
-        if file_exists(project_root, "build.gradle.kts")
-            || file_exists(project_root, "build.gradle")
-        {
+        let gradle_files = existing_files(project_root, &["build.gradle.kts", "build.gradle"]);
+        if !gradle_files.is_empty() {
             all_types.push(ProjectType::Gradle);
             details.push(
                 DetectionResult::found("Kotlin/JVM")
                     .with_detail("Gradle build file found")
+                    .with_files(&gradle_files)
                     .with_template(TemplateName::GradleDeps),
             );
 
             // Spring Boot (detected alongside Gradle)
-            if file_exists(project_root, "src/main/resources/application.properties")
-                || file_exists(project_root, "src/main/resources/application.yml")
-            {
+            let spring_files = existing_files(
+                project_root,
+                &[
+                    "src/main/resources/application.properties",
+                    "src/main/resources/application.yml",
+                ],
+            );
+            if !spring_files.is_empty() {
+                // Plain JVM apps use the same resource names
                 details.push(
                     DetectionResult::found("Spring Boot")
                         .with_detail("Spring Boot application detected")
+                        .with_files(&spring_files)
+                        .with_confidence(0.7)
                         .with_template(TemplateName::SpringBootBuild),
                 );
             }
@@ -251,6 +279,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Elixir")
                     .with_detail("mix.exs found")
+                    .with_files(&["mix.exs"])
                     .with_template(TemplateName::MixDepsGet),
             );
         }
@@ -261,16 +290,20 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Swift")
                     .with_detail("Package.swift found")
+                    .with_files(&["Package.swift"])
                     .with_template(TemplateName::SwiftResolve),
             );
         }
 
         // Terraform
-        if any_file_exists(project_root, &["main.tf", "terraform.tf", "versions.tf"]).is_some() {
+        let terraform_files =
+            existing_files(project_root, &["main.tf", "terraform.tf", "versions.tf"]);
+        if !terraform_files.is_empty() {
             all_types.push(ProjectType::Terraform);
             details.push(
                 DetectionResult::found("Terraform")
                     .with_detail("Terraform files found")
+                    .with_files(&terraform_files)
                     .with_template(TemplateName::TerraformInit),
             );
         }
@@ -280,6 +313,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("AWS CDK")
                     .with_detail("cdk.json found")
+                    .with_files(&["cdk.json"])
                     .with_template(TemplateName::CdkSynth),
             );
         }
@@ -290,25 +324,19 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Maven (Java)")
                     .with_detail("pom.xml found")
+                    .with_files(&["pom.xml"])
                     .with_template(TemplateName::MavenResolve),
             );
         }
 
         // .NET (C#)
-        if std::fs::read_dir(project_root)
-            .map(|entries| {
-                entries.filter_map(|e| e.ok()).any(|e| {
-                    let name = e.file_name();
-                    let name = name.to_string_lossy();
-                    name.ends_with(".sln") || name.ends_with(".csproj")
-                })
-            })
-            .unwrap_or(false)
-        {
+        let dotnet_files = files_with_suffix(project_root, &[".sln", ".csproj"]);
+        if !dotnet_files.is_empty() {
             all_types.push(ProjectType::Dotnet);
             details.push(
                 DetectionResult::found(".NET")
                     .with_detail(".NET solution or project found")
+                    .with_files(&dotnet_files)
                     .with_template(TemplateName::DotnetRestore),
             );
         }
@@ -317,41 +345,46 @@ impl ProjectDetector {
         if file_exists(project_root, "pubspec.yaml") {
             all_types.push(ProjectType::Dart);
 
-            let flutter_dirs = ["android", "ios", "web", "macos", "linux", "windows"];
-            let is_flutter = flutter_dirs
+            let flutter_dirs: Vec<String> = ["android", "ios", "web", "macos", "linux", "windows"]
                 .iter()
-                .any(|dir| project_root.join(dir).is_dir());
+                .filter(|dir| project_root.join(dir).is_dir())
+                .map(|dir| format!("{}/", dir))
+                .collect();
 
-            if is_flutter {
+            // Flutter is inferred from platform directories, not a manifest
+            if !flutter_dirs.is_empty() {
                 details.push(
                     DetectionResult::found("Flutter")
                         .with_detail("pubspec.yaml found")
+                        .with_files(&["pubspec.yaml"])
+                        .with_files(&flutter_dirs)
+                        .with_confidence(0.8)
                         .with_template(TemplateName::FlutterPubGet),
                 );
             } else {
                 details.push(
                     DetectionResult::found("Dart")
                         .with_detail("pubspec.yaml found")
+                        .with_files(&["pubspec.yaml"])
                         .with_template(TemplateName::DartPubGet),
                 );
             }
         }
 
         // Deno
-        if file_exists(project_root, "deno.json")
-            || file_exists(project_root, "deno.jsonc")
-            || file_exists(project_root, "deno.lock")
-        {
+        let deno_files = existing_files(project_root, &["deno.json", "deno.jsonc", "deno.lock"]);
+        if !deno_files.is_empty() {
             all_types.push(ProjectType::Deno);
             details.push(
                 DetectionResult::found("Deno")
                     .with_detail("Deno configuration found")
+                    .with_files(&deno_files)
                     .with_template(TemplateName::DenoInstall),
             );
         }
 
         // Docker Compose (detected alongside other project types)
-        if any_file_exists(
+        let compose_files = existing_files(
             project_root,
             &[
                 "docker-compose.yml",
@@ -359,12 +392,12 @@ impl ProjectDetector {
                 "compose.yml",
                 "compose.yaml",
             ],
-        )
-        .is_some()
-        {
+        );
+        if !compose_files.is_empty() {
             details.push(
                 DetectionResult::found("Docker Compose")
                     .with_detail("Docker Compose file found")
+                    .with_files(&compose_files)
                     .with_template(TemplateName::DockerComposeUp),
             );
         }
@@ -374,6 +407,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Helm")
                     .with_detail("Chart.yaml found")
+                    .with_files(&["Chart.yaml"])
                     .with_template(TemplateName::HelmDeps),
             );
         }
@@ -383,21 +417,25 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Pulumi")
                     .with_detail("Pulumi.yaml found")
+                    .with_files(&["Pulumi.yaml"])
                     .with_template(TemplateName::PulumiInstall),
             );
         }
 
         // Ansible (detected alongside other project types)
-        if file_exists(project_root, "ansible.cfg")
-            || any_file_exists(
-                project_root,
-                &["playbook.yml", "playbook.yaml", "site.yml", "site.yaml"],
-            )
-            .is_some()
-        {
+        let ansible_cfg = existing_files(project_root, &["ansible.cfg"]);
+        let playbooks = existing_files(
+            project_root,
+            &["playbook.yml", "playbook.yaml", "site.yml", "site.yaml"],
+        );
+        if !ansible_cfg.is_empty() || !playbooks.is_empty() {
+            // Playbook names alone are generic YAML file names
             details.push(
                 DetectionResult::found("Ansible")
                     .with_detail("Ansible configuration found")
+                    .with_files(&ansible_cfg)
+                    .with_files(&playbooks)
+                    .with_confidence(if ansible_cfg.is_empty() { 0.6 } else { 1.0 })
                     .with_template(TemplateName::AnsibleInstall),
             );
         }
@@ -407,6 +445,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Prisma")
                     .with_detail("Prisma schema found")
+                    .with_files(&["prisma/schema.prisma"])
                     .with_template(TemplateName::PrismaMigrate),
             );
         }
@@ -414,16 +453,15 @@ impl ProjectDetector {
         // --- Cross-cutting sidebar detections ---
 
         // Environment file setup (detected alongside any project type)
-        if any_file_exists(
+        let env_templates = existing_files(
             project_root,
             &[".env.example", ".env.sample", ".env.template"],
-        )
-        .is_some()
-            && !file_exists(project_root, ".env")
-        {
+        );
+        if !env_templates.is_empty() && !file_exists(project_root, ".env") {
             details.push(
                 DetectionResult::found("Environment setup")
                     .with_detail("Environment template file found")
+                    .with_files(&env_templates)
                     .with_template(TemplateName::EnvCopy),
             );
         }
@@ -433,6 +471,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("pre-commit")
                     .with_detail(".pre-commit-config.yaml found")
+                    .with_files(&[".pre-commit-config.yaml"])
                     .with_template(TemplateName::PreCommitInstall),
             );
         }
@@ -442,6 +481,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Nx")
                     .with_detail("Nx workspace detected")
+                    .with_files(&["nx.json"])
                     .with_template(TemplateName::NxBuild),
             );
         }
@@ -450,6 +490,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Turborepo")
                     .with_detail("Turborepo workspace detected")
+                    .with_files(&["turbo.json"])
                     .with_template(TemplateName::TurboBuild),
             );
         }
@@ -458,6 +499,7 @@ impl ProjectDetector {
             details.push(
                 DetectionResult::found("Lerna")
                     .with_detail("Lerna monorepo detected")
+                    .with_files(&["lerna.json"])
                     .with_template(TemplateName::LernaBootstrap),
             );
         }
@@ -1078,4 +1120,29 @@ mod tests {
             .iter()
             .any(|d| d.suggested_template == Some(TemplateName::LernaBootstrap)));
     }
+
+    #[test]
+    fn details_record_matched_files() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+
+        let result = ProjectDetector::detect(temp.path());
+
+        let node = result.details.iter().find(|d| d.name == "Node.js").unwrap();
+        assert_eq!(node.matched_files(), vec!["package.json", "yarn.lock"]);
+        assert_eq!(node.confidence, 1.0);
+    }
+
+    #[test]
+    fn weak_markers_lower_confidence() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("site.yml"), "").unwrap();
+
+        let result = ProjectDetector::detect(temp.path());
+
+        let ansible = result.details.iter().find(|d| d.name == "Ansible").unwrap();
+        assert_eq!(ansible.matched_files(), vec!["site.yml"]);
+        assert!(ansible.confidence < 1.0);
+    }
 }
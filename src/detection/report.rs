@@ -0,0 +1,223 @@
+//! Detection report for `bivvy detect`.
+//!
+//! Flattens a [`FullDetection`] into technologies and package managers with
+//! confidence and evidence, conflicts, and the templates `bivvy init` would
+//! propose, including project, user and remote templates whose detectors
+//! pass.
+
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::registry::template::TemplateSource;
+use crate::registry::Registry;
+
+use super::conflicts::Conflict;
+use super::runner::{DetectionRunner, FullDetection};
+use super::types::DetectionResult;
+
+/// Everything detection found in a project.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectionReport {
+    /// Detected languages, frameworks and tools.
+    pub technologies: Vec<DetectedItem>,
+    /// Detected system, version and language package managers.
+    pub package_managers: Vec<DetectedItem>,
+    /// Conflicting configuration with suggested resolutions.
+    pub conflicts: Vec<Conflict>,
+    /// Templates `bivvy init` would propose, in order.
+    pub templates: Vec<ProposedTemplate>,
+    /// Detector shadowing warnings from the template registry.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+/// A single detected technology or package manager.
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedItem {
+    /// Display name.
+    pub name: String,
+    /// Confidence from 0.0 to 1.0.
+    pub confidence: f32,
+    /// Files that matched.
+    pub files: Vec<String>,
+    /// Commands that succeeded.
+    pub commands: Vec<String>,
+    /// Human-readable notes.
+    pub details: Vec<String>,
+    /// Template suggested for this detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl From<&DetectionResult> for DetectedItem {
+    fn from(result: &DetectionResult) -> Self {
+        Self {
+            name: result.name.clone(),
+            confidence: result.confidence,
+            files: result.matched_files(),
+            commands: result.matched_commands(),
+            details: result.details.clone(),
+            template: result.suggested_template.map(|t| t.to_string()),
+        }
+    }
+}
+
+/// A template `bivvy init` would propose.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProposedTemplate {
+    /// Template name, used as the step name.
+    pub name: String,
+    /// Why it was proposed.
+    pub reason: String,
+    /// Where the template comes from: builtin, project, user or remote.
+    pub source: String,
+    /// Detector references that passed, for non-built-in templates.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub detectors: Vec<String>,
+}
+
+impl DetectionReport {
+    /// Run detection on a project and build the report.
+    pub fn build(project_root: &Path, registry: Option<&Registry>) -> Self {
+        let detection = DetectionRunner::run(project_root);
+        Self::from_detection(&detection, project_root, registry)
+    }
+
+    /// Build the report from an existing detection run.
+    pub fn from_detection(
+        detection: &FullDetection,
+        project_root: &Path,
+        registry: Option<&Registry>,
+    ) -> Self {
+        Self {
+            technologies: detection.project.details.iter().map(Into::into).collect(),
+            package_managers: detection
+                .package_managers
+                .details
+                .iter()
+                .map(Into::into)
+                .collect(),
+            conflicts: detection.conflicts.clone(),
+            templates: proposed_templates(detection, project_root, registry),
+            warnings: registry
+                .map(|r| r.detector_warnings().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Templates to propose: built-in suggestions first, then project, user
+/// and remote templates whose detectors all pass.
+pub fn proposed_templates(
+    detection: &FullDetection,
+    project_root: &Path,
+    registry: Option<&Registry>,
+) -> Vec<ProposedTemplate> {
+    let mut proposed: Vec<ProposedTemplate> = detection
+        .suggested_templates
+        .iter()
+        .map(|s| ProposedTemplate {
+            name: s.name.to_string(),
+            reason: s.reason.clone(),
+            source: "builtin".to_string(),
+            detectors: Vec::new(),
+        })
+        .collect();
+
+    let Some(registry) = registry else {
+        return proposed;
+    };
+    for template in registry.detected_custom_templates(project_root) {
+        if proposed.iter().any(|p| p.name == template.name) {
+            continue;
+        }
+        let source = match registry.resolve(&template.name).map(|(_, source)| source) {
+            Ok(TemplateSource::Project) => "project",
+            Ok(TemplateSource::User) => "user",
+            Ok(TemplateSource::Remote { .. }) => "remote",
+            Ok(TemplateSource::Builtin) | Err(_) => "builtin",
+        };
+        proposed.push(ProposedTemplate {
+            name: template.name.clone(),
+            reason: template.description.clone(),
+            source: source.to_string(),
+            detectors: template.detectors.clone(),
+        });
+    }
+    proposed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn report_includes_evidence_and_templates() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+        fs::write(temp.path().join("package-lock.json"), "{}").unwrap();
+
+        let report = DetectionReport::build(temp.path(), None);
+
+        let node = report
+            .technologies
+            .iter()
+            .find(|t| t.name == "Node.js")
+            .unwrap();
+        assert_eq!(node.files, vec!["package.json", "yarn.lock"]);
+        assert_eq!(node.template.as_deref(), Some("yarn-install"));
+        assert!(!report.conflicts.is_empty());
+        assert!(report.templates.iter().any(|t| t.name == "yarn-install"));
+    }
+
+    #[test]
+    fn report_proposes_custom_templates() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(bivvy_dir.join("templates/steps")).unwrap();
+        fs::write(
+            bivvy_dir.join("detectors.yml"),
+            "detectors:\n  platform-config:\n    file: platform.yml\n",
+        )
+        .unwrap();
+        fs::write(
+            bivvy_dir.join("templates/steps/platform-lint.yml"),
+            "name: platform-lint\n\
+             description: Lint platform config\n\
+             category: platform\n\
+             detectors: [platform-config.file]\n\
+             step:\n  command: platform lint\n",
+        )
+        .unwrap();
+        fs::write(temp.path().join("platform.yml"), "").unwrap();
+        let registry = Registry::new(Some(temp.path())).unwrap();
+
+        let report = DetectionReport::build(temp.path(), Some(&registry));
+
+        let proposed = report
+            .templates
+            .iter()
+            .find(|t| t.name == "platform-lint")
+            .unwrap();
+        assert_eq!(proposed.source, "project");
+        assert_eq!(proposed.detectors, vec!["platform-config.file"]);
+    }
+
+    #[test]
+    fn report_serializes_conflict_kind() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "{}").unwrap();
+        fs::write(temp.path().join("yarn.lock"), "").unwrap();
+        fs::write(temp.path().join("package-lock.json"), "{}").unwrap();
+
+        let report = DetectionReport::build(temp.path(), None);
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["conflicts"][0]["kind"], "node_lockfiles");
+        assert!(json.get("warnings").is_none());
+    }
+}
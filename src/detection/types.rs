@@ -83,6 +83,46 @@ impl DetectionResult {
         self.suggested_template = Some(template);
         self
     }
+
+    /// Record files that matched, as evidence.
+    pub fn with_files<S: AsRef<str>>(self, files: &[S]) -> Self {
+        files.iter().fold(self, |result, file| {
+            result.add_kind(DetectionKind::FileExists(file.as_ref().to_string()))
+        })
+    }
+
+    /// Record a command that succeeded, as evidence.
+    pub fn with_command(self, command: &str) -> Self {
+        self.add_kind(DetectionKind::CommandSucceeds(command.to_string()))
+    }
+
+    fn add_kind(mut self, kind: DetectionKind) -> Self {
+        self.kind = Some(match self.kind.take() {
+            None => kind,
+            Some(DetectionKind::Multiple(mut kinds)) => {
+                kinds.push(kind);
+                DetectionKind::Multiple(kinds)
+            }
+            Some(existing) => DetectionKind::Multiple(vec![existing, kind]),
+        });
+        self
+    }
+
+    /// Files that matched, in the order they were recorded.
+    pub fn matched_files(&self) -> Vec<String> {
+        self.kind
+            .as_ref()
+            .map(|k| k.flatten(|k| matches!(k, DetectionKind::FileExists(_))))
+            .unwrap_or_default()
+    }
+
+    /// Commands that succeeded, in the order they were recorded.
+    pub fn matched_commands(&self) -> Vec<String> {
+        self.kind
+            .as_ref()
+            .map(|k| k.flatten(|k| matches!(k, DetectionKind::CommandSucceeds(_))))
+            .unwrap_or_default()
+    }
 }
 
 /// Kind of detection that matched.
@@ -98,6 +138,21 @@ pub enum DetectionKind {
     Multiple(Vec<DetectionKind>),
 }
 
+impl DetectionKind {
+    /// Collect the file or command values of the leaves selected by `keep`.
+    fn flatten(&self, keep: fn(&DetectionKind) -> bool) -> Vec<String> {
+        match self {
+            DetectionKind::Multiple(kinds) => kinds.iter().flat_map(|k| k.flatten(keep)).collect(),
+            DetectionKind::FileExists(value) | DetectionKind::CommandSucceeds(value)
+                if keep(self) =>
+            {
+                vec![value.clone()]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +195,21 @@ mod tests {
 
         assert!(matches!(kind, DetectionKind::Multiple(_)));
     }
+
+    #[test]
+    fn evidence_accumulates() {
+        let result = DetectionResult::found("Node.js")
+            .with_files(&["package.json", "yarn.lock"])
+            .with_command("yarn --version");
+
+        assert_eq!(result.matched_files(), vec!["package.json", "yarn.lock"]);
+        assert_eq!(result.matched_commands(), vec!["yarn --version"]);
+    }
+
+    #[test]
+    fn evidence_empty_without_kind() {
+        let result = DetectionResult::found("ruby");
+        assert!(result.matched_files().is_empty());
+        assert!(result.matched_commands().is_empty());
+    }
 }
@@ -27,6 +27,10 @@ pub enum BivvyError {
     #[error("Invalid configuration: {message}")]
     ConfigValidationError { message: String },
 
+    /// A config document couldn't be edited in place.
+    #[error("Can't edit config: {message}")]
+    ConfigEditError { message: String },
+
     /// Referenced template does not exist.
     #[error("Unknown template: {name}")]
     UnknownTemplate { name: String },
@@ -65,6 +69,22 @@ pub enum BivvyError {
     #[error("Shell error: {message}")]
     ShellError { message: String },
 
+    /// A config requires a bivvy version this binary doesn't satisfy.
+    #[error("{path} requires bivvy {required}, but this is bivvy {current}. Run `bivvy update` to upgrade.")]
+    UnsupportedBivvyVersion {
+        path: PathBuf,
+        required: String,
+        current: String,
+    },
+
+    /// Offline mode needs remote content that isn't available locally.
+    #[error("Offline mode: not available locally:\n  - {}\nRun `bivvy cache warm` while online to fetch them.", .missing.join("\n  - "))]
+    OfflineUnavailable { missing: Vec<String> },
+
+    /// Another bivvy process holds the project lock.
+    #[error("Another bivvy process is already running in this project ({holder}). Use --wait to wait for it to finish.")]
+    ProjectLocked { holder: String },
+
     /// IO error wrapper.
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -108,6 +128,17 @@ mod tests {
         assert!(err.to_string().contains("missing required field"));
     }
 
+    #[test]
+    fn config_edit_error_displays_message() {
+        let err = BivvyError::ConfigEditError {
+            message: "anchors aren't supported".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Can't edit config: anchors aren't supported"
+        );
+    }
+
     #[test]
     fn unknown_template_displays_name() {
         let err = BivvyError::UnknownTemplate {
@@ -178,6 +209,42 @@ mod tests {
         assert!(msg.contains("--trust"));
     }
 
+    #[test]
+    fn unsupported_bivvy_version_displays_upgrade_hint() {
+        let err = BivvyError::UnsupportedBivvyVersion {
+            path: PathBuf::from(".bivvy/config.yml"),
+            required: ">=1.10".into(),
+            current: "1.9.0".into(),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("requires bivvy >=1.10, but this is bivvy 1.9.0"));
+        assert!(msg.contains("bivvy update"));
+    }
+
+    #[test]
+    fn offline_unavailable_lists_every_resource() {
+        let err = BivvyError::OfflineUnavailable {
+            missing: vec![
+                "extends https://example.com/base.yml".into(),
+                "template source https://example.com/templates.yml".into(),
+            ],
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("  - extends https://example.com/base.yml\n"));
+        assert!(msg.contains("  - template source https://example.com/templates.yml\n"));
+        assert!(msg.contains("bivvy cache warm"));
+    }
+
+    #[test]
+    fn project_locked_displays_holder_and_hint() {
+        let err = BivvyError::ProjectLocked {
+            holder: "bivvy run, pid 42 on laptop".into(),
+        };
+        let msg = err.to_string();
+        assert!(msg.contains("pid 42 on laptop"));
+        assert!(msg.contains("--wait"));
+    }
+
     #[test]
     fn io_error_converts_from_std() {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file missing");
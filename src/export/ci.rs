@@ -0,0 +1,643 @@
+//! CI pipeline generation.
+//!
+//! Turns a workflow's resolved steps into a GitHub Actions workflow or a
+//! GitLab CI pipeline that installs bivvy and runs the workflow with
+//! `bivvy run --non-interactive --env <env>`, either as one job or as
+//! one job per parallel group of steps.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::error::{BivvyError, Result};
+use crate::runner::DependencyGraph;
+
+use super::toolchains::{provide, ProvidedRequirements, Service};
+
+/// Command that installs bivvy into `$HOME/.local/bin`.
+pub const INSTALL_COMMAND: &str = "curl -fsSL https://bivvy.dev/install | sh";
+
+/// Image used by GitLab jobs that need no known toolchain.
+const GITLAB_DEFAULT_IMAGE: &str = "buildpack-deps:bookworm";
+
+/// CI service to generate a pipeline for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiProvider {
+    /// GitHub Actions.
+    Github,
+    /// GitLab CI/CD.
+    Gitlab,
+}
+
+/// How a workflow's steps are split into CI jobs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum JobLayout {
+    /// One job running the whole workflow.
+    #[default]
+    Single,
+    /// One job per parallel group, each needing the previous one.
+    Groups,
+}
+
+/// A workflow step as far as CI is concerned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CiStep {
+    /// Step name.
+    pub name: String,
+    /// Steps this one depends on.
+    pub depends_on: Vec<String>,
+    /// System-level prerequisites.
+    pub requires: Vec<String>,
+    /// Files, directories or globs whose contents key the step's cache.
+    pub cache_key: Vec<String>,
+    /// Paths to cache between pipeline runs.
+    pub cache_paths: Vec<String>,
+}
+
+/// A workflow ready to render as a CI pipeline.
+#[derive(Debug)]
+pub struct CiPipeline {
+    workflow: String,
+    environment: String,
+    steps: BTreeMap<String, CiStep>,
+    groups: Vec<Vec<String>>,
+}
+
+/// One CI job: the steps it runs and what it needs.
+struct Job {
+    id: String,
+    steps: Vec<String>,
+    needs: Option<String>,
+    caches: Vec<String>,
+}
+
+impl CiPipeline {
+    /// Build a pipeline from the steps a workflow runs in `environment`.
+    ///
+    /// Dependencies on steps outside `steps` are ignored, since those
+    /// steps don't run in this environment.
+    pub fn new(workflow: &str, environment: &str, steps: Vec<CiStep>) -> Result<Self> {
+        let steps: BTreeMap<String, CiStep> =
+            steps.into_iter().map(|s| (s.name.clone(), s)).collect();
+
+        let mut builder = DependencyGraph::builder();
+        for step in steps.values() {
+            let deps = step
+                .depends_on
+                .iter()
+                .filter(|dep| steps.contains_key(*dep))
+                .cloned()
+                .collect();
+            builder = builder.add_step(step.name.clone(), deps);
+        }
+        let groups = builder.build()?.parallel_groups()?;
+
+        Ok(Self {
+            workflow: workflow.to_string(),
+            environment: environment.to_string(),
+            steps,
+            groups,
+        })
+    }
+
+    /// Parallel groups of step names, in execution order.
+    pub fn groups(&self) -> &[Vec<String>] {
+        &self.groups
+    }
+
+    /// Render the pipeline as YAML.
+    ///
+    /// `project_root` is used to find toolchain version files and to tell
+    /// cache key directories from files.
+    pub fn render(
+        &self,
+        provider: CiProvider,
+        layout: JobLayout,
+        project_root: &Path,
+    ) -> Result<String> {
+        if self.steps.is_empty() {
+            return Err(BivvyError::Other(anyhow::anyhow!(
+                "Workflow '{}' has no steps in the '{}' environment",
+                self.workflow,
+                self.environment
+            )));
+        }
+
+        let jobs = self.jobs(layout);
+        let provided = self.provided(self.steps.keys());
+        let body = match provider {
+            CiProvider::Github => self.github(&jobs, project_root),
+            CiProvider::Gitlab => self.gitlab(&jobs, project_root),
+        };
+        let yaml = serde_yaml::to_string(&body)
+            .map_err(|e| BivvyError::Other(anyhow::anyhow!("YAML serialization failed: {e}")))?;
+
+        let mut out = format!(
+            "# Generated by `bivvy export ci` from the '{}' workflow.\n\
+             # Regenerate it after changing the bivvy configuration.\n",
+            self.workflow
+        );
+        if !provided.unknown.is_empty() {
+            out.push_str(&format!(
+                "# Not installed by this pipeline: {}\n",
+                provided.unknown.join(", ")
+            ));
+        }
+        if provider == CiProvider::Gitlab {
+            for job in &jobs {
+                let extra: Vec<&str> = self
+                    .provided(job.steps.iter())
+                    .toolchains
+                    .iter()
+                    .skip(1)
+                    .map(|t| t.requirement)
+                    .collect();
+                if !extra.is_empty() {
+                    out.push_str(&format!(
+                        "# Job '{}' also requires {}, which its image lacks.\n",
+                        job.id,
+                        extra.join(", ")
+                    ));
+                }
+            }
+        }
+        out.push('\n');
+        out.push_str(&yaml);
+        Ok(out)
+    }
+
+    fn jobs(&self, layout: JobLayout) -> Vec<Job> {
+        let cached = |steps: &[String]| -> Vec<String> {
+            steps
+                .iter()
+                .filter(|name| !self.steps[*name].cache_paths.is_empty())
+                .cloned()
+                .collect()
+        };
+
+        match layout {
+            JobLayout::Single => {
+                let steps: Vec<String> = self.groups.iter().flatten().cloned().collect();
+                vec![Job {
+                    id: "setup".to_string(),
+                    caches: cached(&steps),
+                    steps,
+                    needs: None,
+                }]
+            }
+            JobLayout::Groups => {
+                let mut seen: Vec<String> = Vec::new();
+                let mut jobs: Vec<Job> = Vec::new();
+                for (i, group) in self.groups.iter().enumerate() {
+                    seen.extend(group.iter().cloned());
+                    jobs.push(Job {
+                        id: format!("setup-{}", i + 1),
+                        steps: group.clone(),
+                        needs: jobs.last().map(|j| j.id.clone()),
+                        // Restore earlier groups' caches: each job starts
+                        // from a clean checkout.
+                        caches: cached(&seen),
+                    });
+                }
+                jobs
+            }
+        }
+    }
+
+    fn provided<'a>(&'a self, names: impl Iterator<Item = &'a String>) -> ProvidedRequirements {
+        let steps: Vec<&CiStep> = names.filter_map(|n| self.steps.get(n)).collect();
+        provide(
+            steps
+                .iter()
+                .flat_map(|s| s.requires.iter().map(String::as_str)),
+        )
+    }
+
+    fn run_command(&self, job: &Job, layout_steps: usize) -> String {
+        let mut command = format!("bivvy run --non-interactive --env {}", self.environment);
+        if self.workflow != "default" {
+            command.push_str(&format!(" --workflow {}", self.workflow));
+        }
+        if job.steps.len() < layout_steps {
+            command.push_str(&format!(" --only {}", job.steps.join(",")));
+        }
+        command
+    }
+
+    fn github(&self, jobs: &[Job], project_root: &Path) -> Value {
+        let mut jobs_map = Mapping::new();
+        for job in jobs {
+            let provided = self.provided(job.steps.iter());
+            let mut steps = vec![map([("uses", "actions/checkout@v4".into())])];
+
+            for toolchain in &provided.toolchains {
+                let mut step = map([("uses", toolchain.action.into())]);
+                let inputs = toolchain.action_inputs(project_root);
+                if !inputs.is_empty() {
+                    step.insert(
+                        "with".into(),
+                        Value::Mapping(
+                            inputs
+                                .into_iter()
+                                .map(|(k, v)| (k.into(), v.into()))
+                                .collect(),
+                        ),
+                    );
+                }
+                steps.push(step);
+            }
+
+            steps.push(map([
+                ("name", "Install bivvy".into()),
+                (
+                    "run",
+                    format!("{INSTALL_COMMAND}\necho \"$HOME/.local/bin\" >> \"$GITHUB_PATH\"\n")
+                        .into(),
+                ),
+            ]));
+
+            for name in &job.caches {
+                let step = &self.steps[name];
+                let prefix = format!("bivvy-{}-${{{{ runner.os }}}}-", name);
+                let files = key_patterns(&step.cache_key, project_root);
+                let key = if files.is_empty() {
+                    format!("{prefix}${{{{ github.sha }}}}")
+                } else {
+                    let quoted: Vec<String> = files.iter().map(|f| format!("'{f}'")).collect();
+                    format!("{prefix}${{{{ hashFiles({}) }}}}", quoted.join(", "))
+                };
+                steps.push(map([
+                    ("name", format!("Cache {name}").into()),
+                    ("uses", "actions/cache@v4".into()),
+                    (
+                        "with",
+                        Value::Mapping(map([
+                            ("path", step.cache_paths.join("\n").into()),
+                            ("key", key.into()),
+                            ("restore-keys", prefix.into()),
+                        ])),
+                    ),
+                ]));
+            }
+
+            steps.push(map([
+                ("name", "Run bivvy".into()),
+                ("run", self.run_command(job, self.steps.len()).into()),
+            ]));
+
+            let mut job_map = Mapping::new();
+            if job.steps.len() < self.steps.len() {
+                job_map.insert("name".into(), job.steps.join(", ").into());
+            }
+            if let Some(needs) = &job.needs {
+                job_map.insert("needs".into(), needs.as_str().into());
+            }
+            job_map.insert("runs-on".into(), "ubuntu-latest".into());
+            if !provided.services.is_empty() {
+                job_map.insert(
+                    "services".into(),
+                    Value::Mapping(
+                        provided
+                            .services
+                            .iter()
+                            .map(|s| (s.name.into(), Value::Mapping(github_service(s))))
+                            .collect(),
+                    ),
+                );
+            }
+            job_map.insert(
+                "steps".into(),
+                Value::Sequence(steps.into_iter().map(Value::Mapping).collect()),
+            );
+            jobs_map.insert(job.id.as_str().into(), Value::Mapping(job_map));
+        }
+
+        Value::Mapping(map([
+            ("name", "bivvy".into()),
+            (
+                "on",
+                Value::Sequence(vec!["push".into(), "pull_request".into()]),
+            ),
+            ("jobs", Value::Mapping(jobs_map)),
+        ]))
+    }
+
+    fn gitlab(&self, jobs: &[Job], project_root: &Path) -> Value {
+        let mut root = Mapping::new();
+        root.insert(
+            "stages".into(),
+            Value::Sequence(jobs.iter().map(|j| j.id.as_str().into()).collect()),
+        );
+
+        for job in jobs {
+            let provided = self.provided(job.steps.iter());
+            let mut job_map = Mapping::new();
+            job_map.insert("stage".into(), job.id.as_str().into());
+            let image = provided
+                .toolchains
+                .first()
+                .map(|t| t.image(project_root))
+                .unwrap_or_else(|| GITLAB_DEFAULT_IMAGE.to_string());
+            job_map.insert("image".into(), image.into());
+
+            if !provided.services.is_empty() {
+                job_map.insert(
+                    "services".into(),
+                    Value::Sequence(
+                        provided
+                            .services
+                            .iter()
+                            .map(|s| {
+                                Value::Mapping(map([
+                                    ("name", s.image.into()),
+                                    ("alias", s.name.into()),
+                                ]))
+                            })
+                            .collect(),
+                    ),
+                );
+                let variables: Mapping = provided
+                    .services
+                    .iter()
+                    .flat_map(|s| s.env.iter())
+                    .map(|(k, v)| ((*k).into(), (*v).into()))
+                    .collect();
+                if !variables.is_empty() {
+                    job_map.insert("variables".into(), Value::Mapping(variables));
+                }
+            }
+
+            if !job.caches.is_empty() {
+                let caches = job
+                    .caches
+                    .iter()
+                    .map(|name| {
+                        let step = &self.steps[name];
+                        let paths = step.cache_paths.iter().map(|p| p.as_str().into()).collect();
+                        Value::Mapping(map([
+                            ("key", gitlab_cache_key(name, &step.cache_key, project_root)),
+                            ("paths", Value::Sequence(paths)),
+                        ]))
+                    })
+                    .collect();
+                job_map.insert("cache".into(), Value::Sequence(caches));
+            }
+
+            job_map.insert(
+                "before_script".into(),
+                Value::Sequence(vec![
+                    INSTALL_COMMAND.into(),
+                    "export PATH=\"$HOME/.local/bin:$PATH\"".into(),
+                ]),
+            );
+            job_map.insert(
+                "script".into(),
+                Value::Sequence(vec![self.run_command(job, self.steps.len()).into()]),
+            );
+            if let Some(needs) = &job.needs {
+                job_map.insert("needs".into(), Value::Sequence(vec![needs.as_str().into()]));
+            }
+            root.insert(job.id.as_str().into(), Value::Mapping(job_map));
+        }
+        Value::Mapping(root)
+    }
+}
+
+fn map<const N: usize>(entries: [(&str, Value); N]) -> Mapping {
+    entries.into_iter().map(|(k, v)| (k.into(), v)).collect()
+}
+
+fn github_service(service: &Service) -> Mapping {
+    let mut out = map([("image", service.image.into())]);
+    if !service.env.is_empty() {
+        out.insert(
+            "env".into(),
+            Value::Mapping(
+                service
+                    .env
+                    .iter()
+                    .map(|(k, v)| ((*k).into(), (*v).into()))
+                    .collect(),
+            ),
+        );
+    }
+    out.insert(
+        "ports".into(),
+        Value::Sequence(vec![format!("{0}:{0}", service.port).into()]),
+    );
+    out.insert(
+        "options".into(),
+        format!(
+            "--health-cmd \"{}\" --health-interval 10s --health-timeout 5s --health-retries 5",
+            service.health_cmd
+        )
+        .into(),
+    );
+    out
+}
+
+/// Cache key entries as file patterns: directories match their contents.
+fn key_patterns(key: &[String], project_root: &Path) -> Vec<String> {
+    let mut patterns: Vec<String> = key
+        .iter()
+        .map(|entry| {
+            let entry = entry.trim_start_matches("./").trim_end_matches('/');
+            if project_root.join(entry).is_dir() {
+                format!("{entry}/**")
+            } else {
+                entry.to_string()
+            }
+        })
+        .collect();
+    patterns.sort();
+    patterns.dedup();
+    patterns
+}
+
+/// GitLab keys caches on at most two plain files; anything else gets a
+/// per-branch key, and bivvy's own change checks catch stale contents.
+fn gitlab_cache_key(step: &str, key: &[String], project_root: &Path) -> Value {
+    let patterns = key_patterns(key, project_root);
+    let plain = patterns.iter().all(|p| !p.contains(['*', '?', '[']));
+    if plain && (1..=2).contains(&patterns.len()) {
+        Value::Mapping(map([
+            (
+                "files",
+                Value::Sequence(patterns.into_iter().map(Value::from).collect()),
+            ),
+            ("prefix", format!("bivvy-{step}").into()),
+        ]))
+    } else {
+        format!("bivvy-{step}-$CI_COMMIT_REF_SLUG").into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn step(name: &str, depends_on: &[&str], requires: &[&str]) -> CiStep {
+        CiStep {
+            name: name.to_string(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            requires: requires.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn pipeline() -> CiPipeline {
+        let deps = CiStep {
+            cache_key: vec!["package-lock.json".to_string()],
+            cache_paths: vec!["node_modules".to_string()],
+            ..step("deps", &[], &["node"])
+        };
+        CiPipeline::new(
+            "default",
+            "ci",
+            vec![
+                deps,
+                step("db", &["deps", "docker"], &["postgres-server", "brew"]),
+                step("lint", &["deps"], &["node"]),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn parse(rendered: &str) -> Value {
+        serde_yaml::from_str(rendered).unwrap()
+    }
+
+    #[test]
+    fn groups_ignore_steps_outside_the_environment() {
+        let pipeline = pipeline();
+        assert_eq!(
+            pipeline.groups(),
+            &[
+                vec!["deps".to_string()],
+                vec!["db".to_string(), "lint".to_string()]
+            ]
+        );
+    }
+
+    #[test]
+    fn github_single_job() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join(".nvmrc"), "20\n").unwrap();
+
+        let rendered = pipeline()
+            .render(CiProvider::Github, JobLayout::Single, temp.path())
+            .unwrap();
+        assert!(rendered.starts_with("# Generated by `bivvy export ci`"));
+        assert!(rendered.contains("# Not installed by this pipeline: brew\n"));
+
+        let yaml = parse(&rendered);
+        let job = &yaml["jobs"]["setup"];
+        assert_eq!(job["services"]["postgres"]["image"], "postgres:16");
+        let steps = job["steps"].as_sequence().unwrap();
+        assert_eq!(steps[1]["uses"], "actions/setup-node@v4");
+        assert_eq!(steps[1]["with"]["node-version-file"], ".nvmrc");
+        assert_eq!(steps[3]["uses"], "actions/cache@v4");
+        assert_eq!(steps[3]["with"]["path"], "node_modules");
+        assert_eq!(
+            steps[3]["with"]["key"],
+            "bivvy-deps-${{ runner.os }}-${{ hashFiles('package-lock.json') }}"
+        );
+        assert_eq!(
+            steps.last().unwrap()["run"],
+            "bivvy run --non-interactive --env ci"
+        );
+    }
+
+    #[test]
+    fn github_job_per_group() {
+        let temp = TempDir::new().unwrap();
+        let rendered = pipeline()
+            .render(CiProvider::Github, JobLayout::Groups, temp.path())
+            .unwrap();
+        let yaml = parse(&rendered);
+
+        let first = &yaml["jobs"]["setup-1"];
+        assert_eq!(first["name"], "deps");
+        assert!(first.get("needs").is_none());
+        assert!(first.get("services").is_none());
+
+        let second = &yaml["jobs"]["setup-2"];
+        assert_eq!(second["needs"], "setup-1");
+        assert_eq!(second["services"]["postgres"]["ports"][0], "5432:5432");
+        let steps = second["steps"].as_sequence().unwrap();
+        assert!(steps.iter().any(|s| s["name"] == "Cache deps"));
+        assert_eq!(
+            steps.last().unwrap()["run"],
+            "bivvy run --non-interactive --env ci --only db,lint"
+        );
+    }
+
+    #[test]
+    fn gitlab_job_per_group() {
+        let temp = TempDir::new().unwrap();
+        let rendered = pipeline()
+            .render(CiProvider::Gitlab, JobLayout::Groups, temp.path())
+            .unwrap();
+        let yaml = parse(&rendered);
+
+        assert_eq!(yaml["stages"], parse("[setup-1, setup-2]"));
+        let first = &yaml["setup-1"];
+        assert_eq!(first["image"], "node:lts");
+        assert_eq!(
+            first["cache"][0]["key"],
+            parse("{files: [package-lock.json], prefix: bivvy-deps}")
+        );
+        let second = &yaml["setup-2"];
+        assert_eq!(second["needs"], parse("[setup-1]"));
+        assert_eq!(second["services"][0]["alias"], "postgres");
+        assert_eq!(second["variables"]["POSTGRES_PASSWORD"], "postgres");
+        assert_eq!(
+            second["script"][0],
+            "bivvy run --non-interactive --env ci --only db,lint"
+        );
+    }
+
+    #[test]
+    fn gitlab_falls_back_for_glob_keys() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("vendor")).unwrap();
+
+        assert_eq!(
+            gitlab_cache_key("deps", &["vendor".to_string()], temp.path()),
+            Value::from("bivvy-deps-$CI_COMMIT_REF_SLUG")
+        );
+        assert_eq!(
+            key_patterns(&["./vendor/".to_string()], temp.path()),
+            vec!["vendor/**"]
+        );
+    }
+
+    #[test]
+    fn named_workflow_is_passed_to_run() {
+        let temp = TempDir::new().unwrap();
+        let pipeline = CiPipeline::new("ci", "ci", vec![step("test", &[], &[])]).unwrap();
+        let rendered = pipeline
+            .render(CiProvider::Gitlab, JobLayout::Single, temp.path())
+            .unwrap();
+        let yaml = parse(&rendered);
+
+        assert_eq!(yaml["setup"]["image"], GITLAB_DEFAULT_IMAGE);
+        assert_eq!(
+            yaml["setup"]["script"][0],
+            "bivvy run --non-interactive --env ci --workflow ci"
+        );
+    }
+
+    #[test]
+    fn empty_pipeline_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let pipeline = CiPipeline::new("default", "ci", vec![]).unwrap();
+        let err = pipeline
+            .render(CiProvider::Github, JobLayout::Single, temp.path())
+            .unwrap_err();
+        assert!(err.to_string().contains("no steps"));
+    }
+}
@@ -0,0 +1,769 @@
+//! Container image generation.
+//!
+//! Turns a workflow's resolved steps into a Dockerfile, and into a
+//! devcontainer that builds it and finishes setup with `bivvy run`.
+//! Requirements become install layers, steps whose file inputs cover what
+//! their command reads become `RUN` layers that only rebuild when those
+//! inputs change, and the rest are left to run in the container.
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+use serde_json::json;
+
+use crate::error::{BivvyError, Result};
+use crate::registry::template::Platform as TemplatePlatform;
+use crate::registry::Registry;
+use crate::requirements::registry::{
+    InstallContext, Platform, Requirement, RequirementCheck, RequirementRegistry,
+};
+use crate::runner::DependencyGraph;
+
+use super::ci::INSTALL_COMMAND;
+
+/// Base image: Debian with curl, git and build tools.
+const BASE_IMAGE: &str = "buildpack-deps:bookworm";
+
+/// Directory the project is copied into.
+const WORKDIR: &str = "/app";
+
+/// Where requirement installers put binaries: `~/.local/bin` (mise,
+/// bivvy), mise shims and `~/.cargo/bin` (rustup).
+const PATH: &str = "/root/.local/bin:/root/.local/share/mise/shims:/root/.cargo/bin:$PATH";
+
+/// Requirements that need a running daemon but aren't checked as services.
+const DAEMON_REQUIREMENTS: &[&str] = &["docker"];
+
+/// Files dependency installers read, by command. A step running one of
+/// these is only built as a layer if its inputs cover each of the files
+/// that exist in the project.
+const COMMAND_INPUTS: &[(&str, &[&str])] = &[
+    ("bundle install", &["Gemfile", "Gemfile.lock"]),
+    ("yarn", &["package.json", "yarn.lock", ".yarnrc.yml"]),
+    (
+        "yarn install",
+        &["package.json", "yarn.lock", ".yarnrc.yml"],
+    ),
+    ("npm ci", &["package.json", "package-lock.json"]),
+    ("npm install", &["package.json", "package-lock.json"]),
+    ("pnpm install", &["package.json", "pnpm-lock.yaml"]),
+    ("bun install", &["package.json", "bun.lockb", "bun.lock"]),
+    ("poetry install", &["pyproject.toml", "poetry.lock"]),
+    ("uv sync", &["pyproject.toml", "uv.lock"]),
+    ("composer install", &["composer.json", "composer.lock"]),
+    ("go mod download", &["go.mod", "go.sum"]),
+    ("cargo fetch", &["Cargo.toml", "Cargo.lock"]),
+    ("mix deps.get", &["mix.exs", "mix.lock"]),
+];
+
+/// A workflow step as far as a container image is concerned.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContainerStep {
+    /// Step name.
+    pub name: String,
+    /// Command the step runs.
+    pub command: Option<String>,
+    /// Steps this one depends on.
+    pub depends_on: Vec<String>,
+    /// System-level prerequisites.
+    pub requires: Vec<String>,
+    /// Files, directories or globs the step declares it reads and re-runs
+    /// on (its change-check targets).
+    pub inputs: Vec<String>,
+    /// Secrets the step uses: env vars, prompts or variables.
+    pub secrets: Vec<String>,
+}
+
+/// How the image provides one requirement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementInstall {
+    /// Requirement name.
+    pub requirement: String,
+    /// Install command from the requirement's install template.
+    pub command: Option<String>,
+    /// Version files the install command reads.
+    pub copy: Vec<String>,
+    /// Instructions shown when there's no command.
+    pub hint: Option<String>,
+}
+
+/// A step built into the image.
+#[derive(Debug)]
+struct StepLayer {
+    name: String,
+    /// Sources to copy, keyed by destination directory.
+    copy: BTreeMap<String, Vec<String>>,
+}
+
+/// A workflow ready to render as a Dockerfile or devcontainer.
+#[derive(Debug)]
+pub struct ContainerImage {
+    workflow: String,
+    environment: String,
+    installs: Vec<RequirementInstall>,
+    services: Vec<String>,
+    layers: Vec<StepLayer>,
+    deferred: Vec<(String, String)>,
+}
+
+impl ContainerImage {
+    /// Plan an image from the steps a workflow runs in `environment`.
+    ///
+    /// `project_root` is used to drop inputs and version files the project
+    /// doesn't have and to expand globs `COPY` can't match.
+    pub fn new(
+        workflow: &str,
+        environment: &str,
+        steps: Vec<ContainerStep>,
+        requirements: &RequirementRegistry,
+        templates: &Registry,
+        project_root: &Path,
+    ) -> Result<Self> {
+        if steps.is_empty() {
+            return Err(BivvyError::Other(anyhow::anyhow!(
+                "Workflow '{}' has no steps in the '{}' environment",
+                workflow,
+                environment
+            )));
+        }
+
+        let steps: BTreeMap<String, ContainerStep> =
+            steps.into_iter().map(|s| (s.name.clone(), s)).collect();
+        let mut builder = DependencyGraph::builder();
+        for step in steps.values() {
+            let deps = step
+                .depends_on
+                .iter()
+                .filter(|dep| steps.contains_key(*dep))
+                .cloned()
+                .collect();
+            builder = builder.add_step(step.name.clone(), deps);
+        }
+        let order: Vec<String> = builder
+            .build()?
+            .parallel_groups()?
+            .into_iter()
+            .flatten()
+            .collect();
+
+        let mut image = Self {
+            workflow: workflow.to_string(),
+            environment: environment.to_string(),
+            installs: Vec::new(),
+            services: Vec::new(),
+            layers: Vec::new(),
+            deferred: Vec::new(),
+        };
+
+        let mut seen = HashSet::new();
+        for name in &order {
+            for requirement in &steps[name].requires {
+                image.add_requirement(
+                    requirement,
+                    requirements,
+                    templates,
+                    project_root,
+                    &mut seen,
+                );
+            }
+        }
+
+        let mut deferred: HashSet<&str> = HashSet::new();
+        for name in &order {
+            let step = &steps[name];
+            let reason = step
+                .requires
+                .iter()
+                .find(|r| image.services.contains(r))
+                .map(|service| format!("needs {service}"))
+                .or_else(|| {
+                    (!step.secrets.is_empty()).then(|| format!("uses {}", step.secrets.join(", ")))
+                })
+                .or_else(|| {
+                    step.depends_on
+                        .iter()
+                        .find(|d| deferred.contains(d.as_str()))
+                        .map(|dep| format!("depends on {dep}"))
+                });
+
+            let copy = copy_sources(&step.inputs, project_root);
+            let reason = reason
+                .or_else(|| {
+                    copy.is_empty()
+                        .then(|| "has no file inputs to build on".to_string())
+                })
+                .or_else(|| {
+                    let missing = uncovered_inputs(step.command.as_deref(), &copy, project_root);
+                    (!missing.is_empty())
+                        .then(|| format!("reads {} beyond its inputs", missing.join(", ")))
+                });
+            match reason {
+                Some(reason) => {
+                    deferred.insert(name);
+                    image.deferred.push((name.clone(), reason));
+                }
+                None => image.layers.push(StepLayer {
+                    name: name.clone(),
+                    copy,
+                }),
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Requirement installs, dependencies first.
+    pub fn installs(&self) -> &[RequirementInstall] {
+        &self.installs
+    }
+
+    /// Required services, which the image doesn't run.
+    pub fn services(&self) -> &[String] {
+        &self.services
+    }
+
+    /// Steps built into the image as `RUN` layers, in order.
+    pub fn built_steps(&self) -> Vec<&str> {
+        self.layers.iter().map(|l| l.name.as_str()).collect()
+    }
+
+    /// Steps left to run in the container, with the reason for each.
+    pub fn deferred_steps(&self) -> &[(String, String)] {
+        &self.deferred
+    }
+
+    /// The `bivvy run` command that finishes setup in the container.
+    pub fn run_command(&self) -> String {
+        let mut command = format!("bivvy run --non-interactive --env {}", self.environment);
+        if self.workflow != "default" {
+            command.push_str(&format!(" --workflow {}", self.workflow));
+        }
+        command
+    }
+
+    /// Render the Dockerfile.
+    pub fn dockerfile(&self) -> String {
+        let mut out = format!(
+            "# syntax=docker/dockerfile:1\n\
+             # Generated by `bivvy export` from the '{}' workflow.\n\
+             # Regenerate it after changing the bivvy configuration.\n\
+             FROM {BASE_IMAGE}\n\n\
+             ENV PATH=\"{PATH}\"\n\
+             WORKDIR {WORKDIR}\n",
+            self.workflow
+        );
+
+        for install in &self.installs {
+            out.push('\n');
+            match &install.command {
+                Some(command) => {
+                    out.push_str(&format!("# {}\n", install.requirement));
+                    if !install.copy.is_empty() {
+                        out.push_str(&format!("COPY {} ./\n", install.copy.join(" ")));
+                    }
+                    out.push_str(&run_instruction(command));
+                }
+                None => out.push_str(&format!(
+                    "# {}: not installed by this image.{}\n",
+                    install.requirement,
+                    install
+                        .hint
+                        .as_deref()
+                        .map(|h| format!(" {h}"))
+                        .unwrap_or_default()
+                )),
+            }
+        }
+
+        out.push_str(&format!(
+            "\n# bivvy\nRUN {INSTALL_COMMAND}\nCOPY .bivvy/ .bivvy/\n"
+        ));
+
+        for layer in &self.layers {
+            out.push_str(&format!("\n# {}\n", layer.name));
+            for (dest, sources) in &layer.copy {
+                out.push_str(&format!("COPY {} {}\n", sources.join(" "), dest));
+            }
+            out.push_str(&format!(
+                "RUN {} --only {}\n",
+                self.run_command(),
+                layer.name
+            ));
+        }
+
+        out.push_str("\nCOPY . .\n");
+
+        if !self.deferred.is_empty() {
+            out.push_str(&format!(
+                "\n# Run in the container with `{}`:\n",
+                self.run_command()
+            ));
+            for (name, reason) in &self.deferred {
+                out.push_str(&format!("#   {name}: {reason}\n"));
+            }
+        }
+        if !self.services.is_empty() {
+            out.push_str(&format!(
+                "# Services not provided by this image: {}\n",
+                self.services.join(", ")
+            ));
+        }
+        out
+    }
+
+    /// Render `devcontainer.json` for a devcontainer that builds the
+    /// Dockerfile from the same directory.
+    pub fn devcontainer(&self, name: &str) -> Result<String> {
+        let config = json!({
+            "name": name,
+            "build": {
+                "dockerfile": "Dockerfile",
+                "context": "..",
+            },
+            "postCreateCommand": self.run_command(),
+        });
+        let body = serde_json::to_string_pretty(&config)
+            .map_err(|e| anyhow::anyhow!("JSON serialization failed: {e}"))?;
+
+        let mut out = format!(
+            "// Generated by `bivvy export devcontainer` from the '{}' workflow.\n\
+             // Regenerate it after changing the bivvy configuration.\n",
+            self.workflow
+        );
+        if !self.services.is_empty() {
+            out.push_str(&format!(
+                "// Services not provided by this container: {}\n",
+                self.services.join(", ")
+            ));
+        }
+        out.push_str(&body);
+        out.push('\n');
+        Ok(out)
+    }
+
+    /// Add a requirement and, first, what installing it needs.
+    fn add_requirement(
+        &mut self,
+        name: &str,
+        requirements: &RequirementRegistry,
+        templates: &Registry,
+        project_root: &Path,
+        seen: &mut HashSet<String>,
+    ) {
+        if !seen.insert(name.to_string()) {
+            return;
+        }
+
+        let Some(requirement) = requirements.get(name) else {
+            self.installs.push(RequirementInstall {
+                requirement: name.to_string(),
+                command: None,
+                copy: Vec::new(),
+                hint: None,
+            });
+            return;
+        };
+        if is_service(requirement) {
+            self.services.push(name.to_string());
+            return;
+        }
+
+        let template = requirement
+            .install_template
+            .as_deref()
+            .and_then(|t| templates.resolve(t).ok())
+            .map(|(template, _source)| template)
+            .filter(|t| t.platforms.contains(&TemplatePlatform::Linux));
+
+        let mut needs = requirement.depends_on.clone();
+        if template.is_some() {
+            if let Some(install_requires) = requirement.install_requires {
+                needs.extend(install_requires(&InstallContext {
+                    detected_managers: Vec::new(),
+                    platform: Platform::Linux,
+                }));
+            }
+        }
+        if let Some(template) = template {
+            needs.extend(template.step.requires.iter().cloned());
+        }
+        for need in &needs {
+            self.add_requirement(need, requirements, templates, project_root, seen);
+        }
+
+        let mut copy: Vec<String> = version_files(&requirement.checks)
+            .into_iter()
+            .filter(|file| project_root.join(file).is_file())
+            .collect();
+        copy.sort();
+        copy.dedup();
+
+        self.installs.push(RequirementInstall {
+            requirement: name.to_string(),
+            command: template.and_then(|t| t.step.command.clone()),
+            copy,
+            hint: requirement.install_hint.clone(),
+        });
+    }
+}
+
+/// Whether a requirement is a running service rather than a tool.
+fn is_service(requirement: &Requirement) -> bool {
+    fn reachable(check: &RequirementCheck) -> bool {
+        match check {
+            RequirementCheck::ServiceReachable(_) => true,
+            RequirementCheck::Any(checks) => checks.iter().any(reachable),
+            _ => false,
+        }
+    }
+    DAEMON_REQUIREMENTS.contains(&requirement.name.as_str())
+        || requirement.checks.iter().any(reachable)
+}
+
+fn version_files(checks: &[RequirementCheck]) -> Vec<String> {
+    checks
+        .iter()
+        .flat_map(|check| match check {
+            RequirementCheck::ManagedCommand {
+                version_file: Some(file),
+                ..
+            } => vec![file.clone()],
+            RequirementCheck::Any(checks) => version_files(checks),
+            _ => Vec::new(),
+        })
+        .collect()
+}
+
+/// `RUN` for a command, as a heredoc when it spans lines.
+fn run_instruction(command: &str) -> String {
+    let command = command.trim_end();
+    if command.contains('\n') {
+        format!("RUN <<'EOF'\n{command}\nEOF\n")
+    } else {
+        format!("RUN {command}\n")
+    }
+}
+
+/// Files a step's command is known to read that exist in the project but
+/// aren't among its `COPY` sources.
+///
+/// A layer that misses one would run the command without it, e.g. `yarn
+/// install` with a lockfile but no `package.json`.
+fn uncovered_inputs(
+    command: Option<&str>,
+    copy: &BTreeMap<String, Vec<String>>,
+    project_root: &Path,
+) -> Vec<String> {
+    let Some(command) = command else {
+        return Vec::new();
+    };
+    let sources: Vec<&String> = copy.values().flatten().collect();
+    let covered = |file: &str| {
+        sources.iter().any(|source| {
+            source.as_str() == file
+                || file.starts_with(&format!("{source}/"))
+                || crate::sys::glob_match_path(source, file)
+        })
+    };
+
+    let mut missing = Vec::new();
+    for part in command
+        .lines()
+        .flat_map(|line| line.split("&&"))
+        .map(str::trim)
+    {
+        for (prefix, files) in COMMAND_INPUTS {
+            let runs = part == *prefix || part.starts_with(&format!("{prefix} "));
+            if !runs {
+                continue;
+            }
+            for file in *files {
+                if project_root.join(file).is_file()
+                    && !covered(file)
+                    && !missing.iter().any(|m| m == file)
+                {
+                    missing.push(file.to_string());
+                }
+            }
+        }
+    }
+    missing
+}
+
+/// Group a step's inputs into `COPY` sources by destination directory.
+///
+/// Missing files and directories are dropped. Globs with wildcards only
+/// in their last component are kept for `COPY` to match; others are
+/// expanded to the files they match now.
+fn copy_sources(inputs: &[String], project_root: &Path) -> BTreeMap<String, Vec<String>> {
+    let mut copy: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut add = |source: String, dir: bool| {
+        let parent = match source.trim_end_matches('/').rsplit_once('/') {
+            Some((parent, _)) => format!("{parent}/"),
+            None => "./".to_string(),
+        };
+        let dest = if dir { format!("{}/", source) } else { parent };
+        let sources = copy.entry(dest).or_default();
+        if !sources.contains(&source) {
+            sources.push(source);
+        }
+    };
+
+    for input in inputs {
+        let input = input.trim_start_matches("./").trim_end_matches('/');
+        if !input.contains(['*', '?', '[']) {
+            let path = project_root.join(input);
+            if path.exists() {
+                add(input.to_string(), path.is_dir());
+            }
+            continue;
+        }
+
+        let (dir, last) = input.rsplit_once('/').unwrap_or(("", input));
+        let matches =
+            crate::sys::glob(&project_root.join(input).to_string_lossy()).unwrap_or_default();
+        if matches.is_empty() {
+            continue;
+        }
+        if !input.contains("**") && !dir.contains(['*', '?', '[']) && !last.is_empty() {
+            add(input.to_string(), false);
+        } else {
+            for path in matches.iter().filter(|p| p.is_file()) {
+                if let Ok(relative) = path.strip_prefix(project_root) {
+                    add(relative.to_string_lossy().replace('\\', "/"), false);
+                }
+            }
+        }
+    }
+
+    for sources in copy.values_mut() {
+        sources.sort();
+    }
+    copy
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn step(name: &str, depends_on: &[&str], requires: &[&str], inputs: &[&str]) -> ContainerStep {
+        ContainerStep {
+            name: name.to_string(),
+            depends_on: depends_on.iter().map(|s| s.to_string()).collect(),
+            requires: requires.iter().map(|s| s.to_string()).collect(),
+            inputs: inputs.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn project() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Gemfile"), "").unwrap();
+        fs::write(temp.path().join("Gemfile.lock"), "").unwrap();
+        fs::write(temp.path().join(".ruby-version"), "3.3.0\n").unwrap();
+        fs::create_dir_all(temp.path().join("db/migrate")).unwrap();
+        fs::write(temp.path().join("db/migrate/001.sql"), "").unwrap();
+        temp
+    }
+
+    fn image(temp: &TempDir, steps: Vec<ContainerStep>) -> ContainerImage {
+        let templates = Registry::new(None).unwrap();
+        ContainerImage::new(
+            "default",
+            "docker",
+            steps,
+            &RequirementRegistry::new(),
+            &templates,
+            temp.path(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn requirements_install_dependencies_first() {
+        let temp = project();
+        let image = image(
+            &temp,
+            vec![step(
+                "bundle",
+                &[],
+                &["bundler", "postgres-server"],
+                &["Gemfile"],
+            )],
+        );
+
+        let names: Vec<&str> = image
+            .installs()
+            .iter()
+            .map(|i| i.requirement.as_str())
+            .collect();
+        assert_eq!(names, vec!["mise", "ruby", "bundler"]);
+        assert_eq!(
+            image.installs()[1].command.as_deref(),
+            Some("mise install ruby")
+        );
+        assert_eq!(image.installs()[1].copy, vec![".ruby-version"]);
+        assert!(image.installs()[2].command.is_none());
+        assert_eq!(image.services(), &["postgres-server".to_string()]);
+    }
+
+    #[test]
+    fn steps_needing_services_or_secrets_are_deferred() {
+        let temp = project();
+        let seed = ContainerStep {
+            secrets: vec!["API_TOKEN".to_string()],
+            ..step("seed", &[], &[], &["Gemfile"])
+        };
+        let image = image(
+            &temp,
+            vec![
+                step(
+                    "bundle",
+                    &[],
+                    &["ruby"],
+                    &["Gemfile", "Gemfile.lock", "yarn.lock"],
+                ),
+                step(
+                    "db",
+                    &["bundle"],
+                    &["postgres-server"],
+                    &["db/migrate/*.sql"],
+                ),
+                step("migrate", &["db"], &[], &["db/migrate/*.sql"]),
+                step("hello", &[], &[], &[]),
+                seed,
+            ],
+        );
+
+        assert_eq!(image.built_steps(), vec!["bundle"]);
+        let deferred: BTreeMap<&str, &str> = image
+            .deferred_steps()
+            .iter()
+            .map(|(n, r)| (n.as_str(), r.as_str()))
+            .collect();
+        assert_eq!(deferred["db"], "needs postgres-server");
+        assert_eq!(deferred["migrate"], "depends on db");
+        assert_eq!(deferred["seed"], "uses API_TOKEN");
+        assert_eq!(deferred["hello"], "has no file inputs to build on");
+    }
+
+    #[test]
+    fn dockerfile_layers_copy_inputs_before_running() {
+        let temp = project();
+        let dockerfile = image(
+            &temp,
+            vec![
+                step("bundle", &[], &["ruby"], &["Gemfile.lock", "Gemfile"]),
+                step("migrate", &["bundle"], &[], &["db/migrate/*.sql"]),
+                step("db", &[], &["postgres-server"], &[]),
+            ],
+        )
+        .dockerfile();
+
+        assert!(dockerfile.starts_with("# syntax=docker/dockerfile:1\n"));
+        assert!(dockerfile.contains("RUN curl https://mise.run | sh\n"));
+        assert!(dockerfile.contains("COPY .ruby-version ./\nRUN mise install ruby\n"));
+        assert!(dockerfile.contains(
+            "# bundle\nCOPY Gemfile Gemfile.lock ./\n\
+             RUN bivvy run --non-interactive --env docker --only bundle\n"
+        ));
+        assert!(dockerfile.contains("COPY db/migrate/*.sql db/migrate/\n"));
+        assert!(dockerfile.contains("#   db: needs postgres-server\n"));
+        let bundle = dockerfile.find("# bundle").unwrap();
+        let migrate = dockerfile.find("# migrate").unwrap();
+        assert!(bundle < migrate);
+    }
+
+    #[test]
+    fn installers_need_every_file_they_read() {
+        let temp = project();
+        let bundle = |inputs: &[&str]| ContainerStep {
+            command: Some("bundle install --jobs 4".to_string()),
+            ..step("bundle", &[], &["ruby"], inputs)
+        };
+        let partial = image(&temp, vec![bundle(&["Gemfile"])]);
+        assert!(partial.built_steps().is_empty());
+        assert_eq!(
+            partial.deferred_steps(),
+            &[(
+                "bundle".to_string(),
+                "reads Gemfile.lock beyond its inputs".to_string()
+            )]
+        );
+
+        let full = image(&temp, vec![bundle(&["Gemfile", "Gemfile.lock"])]);
+        assert_eq!(full.built_steps(), vec!["bundle"]);
+
+        // Files the project doesn't have aren't needed
+        let npm = ContainerStep {
+            command: Some("npm ci".to_string()),
+            ..step("npm", &[], &[], &["Gemfile"])
+        };
+        assert_eq!(image(&temp, vec![npm]).built_steps(), vec!["npm"]);
+    }
+
+    #[test]
+    fn multiline_installs_use_heredocs() {
+        assert_eq!(run_instruction("echo hi\n"), "RUN echo hi\n");
+        assert_eq!(
+            run_instruction("if true; then\n  echo hi\nfi\n"),
+            "RUN <<'EOF'\nif true; then\n  echo hi\nfi\nEOF\n"
+        );
+    }
+
+    #[test]
+    fn recursive_globs_expand_to_files() {
+        let temp = project();
+        let copy = copy_sources(
+            &[
+                "**/*.sql".to_string(),
+                "./db/".to_string(),
+                "missing".to_string(),
+            ],
+            temp.path(),
+        );
+
+        assert_eq!(copy["db/migrate/"], vec!["db/migrate/001.sql"]);
+        assert_eq!(copy["db/"], vec!["db"]);
+        assert_eq!(copy.len(), 2);
+    }
+
+    #[test]
+    fn devcontainer_runs_bivvy_after_create() {
+        let temp = project();
+        let rendered = image(&temp, vec![step("db", &[], &["redis-server"], &[])])
+            .devcontainer("app")
+            .unwrap();
+
+        assert!(rendered.contains("// Services not provided by this container: redis-server\n"));
+        let json: String = rendered
+            .lines()
+            .filter(|l| !l.starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["build"]["dockerfile"], "Dockerfile");
+        assert_eq!(
+            value["postCreateCommand"],
+            "bivvy run --non-interactive --env docker"
+        );
+    }
+
+    #[test]
+    fn empty_workflow_is_an_error() {
+        let temp = TempDir::new().unwrap();
+        let templates = Registry::new(None).unwrap();
+        let err = ContainerImage::new(
+            "default",
+            "docker",
+            vec![],
+            &RequirementRegistry::new(),
+            &templates,
+            temp.path(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("no steps"));
+    }
+}
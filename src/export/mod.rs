@@ -0,0 +1,12 @@
+//! Generating other tools' configuration from a bivvy workflow.
+//!
+//! - [`ci`] - GitHub Actions and GitLab CI pipelines
+//! - [`container`] - Dockerfiles and devcontainers
+//! - [`toolchains`] - Setup actions, images and services for `requires`
+
+pub mod ci;
+pub mod container;
+pub mod toolchains;
+
+pub use ci::{CiPipeline, CiProvider, CiStep, JobLayout};
+pub use container::{ContainerImage, ContainerStep, RequirementInstall};
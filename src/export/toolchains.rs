@@ -0,0 +1,304 @@
+//! Known ways to provide `requires` entries outside bivvy.
+//!
+//! Maps requirement names to CI setup actions, container images and
+//! service containers, so exported configs install the same toolchains
+//! bivvy would check for.
+
+use std::path::Path;
+
+/// A language toolchain that can be provided by a setup action or image.
+#[derive(Debug)]
+pub struct Toolchain {
+    /// Requirement name, as used in `requires`.
+    pub requirement: &'static str,
+    /// GitHub Actions setup action.
+    pub action: &'static str,
+    /// Action inputs used when the project has no version file.
+    pub default_inputs: &'static [(&'static str, &'static str)],
+    /// Action input that reads a version file, if the action needs one.
+    pub version_file_input: Option<&'static str>,
+    /// Version files, in order of preference.
+    pub version_files: &'static [&'static str],
+    /// Container image repository with the toolchain installed.
+    pub image: &'static str,
+    /// Image tag used when no version file pins one.
+    pub default_tag: &'static str,
+}
+
+impl Toolchain {
+    /// The first of this toolchain's version files present in the project.
+    pub fn version_file(&self, project_root: &Path) -> Option<&'static str> {
+        self.version_files
+            .iter()
+            .copied()
+            .find(|file| project_root.join(file).is_file())
+    }
+
+    /// Action inputs: the version file if there is one, else the defaults.
+    pub fn action_inputs(&self, project_root: &Path) -> Vec<(&'static str, String)> {
+        match (self.version_file_input, self.version_file(project_root)) {
+            (Some(input), Some(file)) => vec![(input, file.to_string())],
+            (None, Some(_)) => Vec::new(),
+            _ => self
+                .default_inputs
+                .iter()
+                .map(|(k, v)| (*k, v.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Container image, tagged with the pinned version when a plain
+    /// version file (such as `.ruby-version`) exists.
+    pub fn image(&self, project_root: &Path) -> String {
+        let pinned = self
+            .version_file(project_root)
+            .filter(|file| file.starts_with('.'))
+            .and_then(|file| std::fs::read_to_string(project_root.join(file)).ok())
+            .and_then(|content| {
+                let version = content.lines().next()?.trim();
+                let version = version.strip_prefix('v').unwrap_or(version);
+                let plain =
+                    !version.is_empty() && version.chars().all(|c| c.is_ascii_digit() || c == '.');
+                plain.then(|| version.to_string())
+            });
+        format!(
+            "{}:{}",
+            self.image,
+            pinned.as_deref().unwrap_or(self.default_tag)
+        )
+    }
+}
+
+/// A backing service that runs as a sidecar container.
+#[derive(Debug)]
+pub struct Service {
+    /// Requirement name, as used in `requires`.
+    pub requirement: &'static str,
+    /// Short name used as the service key and hostname.
+    pub name: &'static str,
+    /// Container image.
+    pub image: &'static str,
+    /// Port the service listens on.
+    pub port: u16,
+    /// Environment the container needs to start.
+    pub env: &'static [(&'static str, &'static str)],
+    /// Command that succeeds once the service is ready.
+    pub health_cmd: &'static str,
+}
+
+/// Language toolchains with a setup action and an image.
+pub const TOOLCHAINS: &[Toolchain] = &[
+    Toolchain {
+        requirement: "ruby",
+        action: "ruby/setup-ruby@v1",
+        default_inputs: &[("ruby-version", "3.3")],
+        version_file_input: None,
+        version_files: &[".ruby-version", ".tool-versions"],
+        image: "ruby",
+        default_tag: "3.3",
+    },
+    Toolchain {
+        requirement: "node",
+        action: "actions/setup-node@v4",
+        default_inputs: &[("node-version", "lts/*")],
+        version_file_input: Some("node-version-file"),
+        version_files: &[".nvmrc", ".node-version"],
+        image: "node",
+        default_tag: "lts",
+    },
+    Toolchain {
+        requirement: "python",
+        action: "actions/setup-python@v5",
+        default_inputs: &[("python-version", "3.x")],
+        version_file_input: Some("python-version-file"),
+        version_files: &[".python-version"],
+        image: "python",
+        default_tag: "3.12",
+    },
+    Toolchain {
+        requirement: "go",
+        action: "actions/setup-go@v5",
+        default_inputs: &[("go-version", "stable")],
+        version_file_input: Some("go-version-file"),
+        version_files: &["go.mod"],
+        image: "golang",
+        default_tag: "1.22",
+    },
+    Toolchain {
+        requirement: "rust",
+        action: "dtolnay/rust-toolchain@stable",
+        default_inputs: &[],
+        version_file_input: None,
+        version_files: &[],
+        image: "rust",
+        default_tag: "1",
+    },
+    Toolchain {
+        requirement: "java",
+        action: "actions/setup-java@v4",
+        default_inputs: &[("distribution", "temurin"), ("java-version", "21")],
+        version_file_input: None,
+        version_files: &[],
+        image: "eclipse-temurin",
+        default_tag: "21",
+    },
+    Toolchain {
+        requirement: "php",
+        action: "shivammathur/setup-php@v2",
+        default_inputs: &[("php-version", "8.3")],
+        version_file_input: None,
+        version_files: &[],
+        image: "php",
+        default_tag: "8.3-cli",
+    },
+    Toolchain {
+        requirement: "elixir",
+        action: "erlef/setup-beam@v1",
+        default_inputs: &[("elixir-version", "1.16"), ("otp-version", "26")],
+        version_file_input: None,
+        version_files: &[],
+        image: "elixir",
+        default_tag: "1.16",
+    },
+    Toolchain {
+        requirement: "dotnet",
+        action: "actions/setup-dotnet@v4",
+        default_inputs: &[("dotnet-version", "8.0.x")],
+        version_file_input: None,
+        version_files: &[],
+        image: "mcr.microsoft.com/dotnet/sdk",
+        default_tag: "8.0",
+    },
+    Toolchain {
+        requirement: "deno",
+        action: "denoland/setup-deno@v2",
+        default_inputs: &[],
+        version_file_input: None,
+        version_files: &[],
+        image: "denoland/deno",
+        default_tag: "latest",
+    },
+];
+
+/// Services that run as sidecar containers.
+pub const SERVICES: &[Service] = &[
+    Service {
+        requirement: "postgres-server",
+        name: "postgres",
+        image: "postgres:16",
+        port: 5432,
+        env: &[("POSTGRES_PASSWORD", "postgres")],
+        health_cmd: "pg_isready",
+    },
+    Service {
+        requirement: "redis-server",
+        name: "redis",
+        image: "redis:7",
+        port: 6379,
+        env: &[],
+        health_cmd: "redis-cli ping",
+    },
+];
+
+/// Requirements that come with another toolchain or service, or with
+/// standard CI runners and images, and need no setup of their own.
+const BUNDLED: &[(&str, &str)] = &[("bundler", "ruby"), ("postgres", "postgres-server")];
+
+/// How a workflow's `requires` entries are provided.
+#[derive(Debug, Default)]
+pub struct ProvidedRequirements {
+    /// Toolchains to install, in first-required order.
+    pub toolchains: Vec<&'static Toolchain>,
+    /// Services to run, in first-required order.
+    pub services: Vec<&'static Service>,
+    /// Requirements with no known setup, sorted.
+    pub unknown: Vec<String>,
+}
+
+/// Sort requirement names into toolchains, services and unknowns.
+pub fn provide<'a>(requirements: impl IntoIterator<Item = &'a str>) -> ProvidedRequirements {
+    let mut provided = ProvidedRequirements::default();
+    let mut pending: Vec<&str> = Vec::new();
+    for requirement in requirements {
+        if !pending.contains(&requirement) {
+            pending.push(requirement);
+        }
+    }
+
+    for requirement in &pending {
+        let requirement = BUNDLED
+            .iter()
+            .find(|(name, _)| name == requirement)
+            .map(|(_, with)| *with)
+            .unwrap_or(requirement);
+        if let Some(toolchain) = TOOLCHAINS.iter().find(|t| t.requirement == requirement) {
+            if !provided
+                .toolchains
+                .iter()
+                .any(|t| t.requirement == requirement)
+            {
+                provided.toolchains.push(toolchain);
+            }
+        } else if let Some(service) = SERVICES.iter().find(|s| s.requirement == requirement) {
+            if !provided
+                .services
+                .iter()
+                .any(|s| s.requirement == requirement)
+            {
+                provided.services.push(service);
+            }
+        } else {
+            provided.unknown.push(requirement.to_string());
+        }
+    }
+    provided.unknown.sort();
+    provided.unknown.dedup();
+    provided
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn provide_sorts_requirements() {
+        let provided = provide(["node", "postgres-server", "brew", "ruby", "bundler", "node"]);
+
+        let toolchains: Vec<_> = provided.toolchains.iter().map(|t| t.requirement).collect();
+        assert_eq!(toolchains, vec!["node", "ruby"]);
+        assert_eq!(provided.services.len(), 1);
+        assert_eq!(provided.services[0].name, "postgres");
+        assert_eq!(provided.unknown, vec!["brew"]);
+    }
+
+    #[test]
+    fn action_inputs_prefer_version_file() {
+        let temp = TempDir::new().unwrap();
+        let node = TOOLCHAINS.iter().find(|t| t.requirement == "node").unwrap();
+        assert_eq!(
+            node.action_inputs(temp.path()),
+            vec![("node-version", "lts/*".to_string())]
+        );
+
+        fs::write(temp.path().join(".nvmrc"), "20\n").unwrap();
+        assert_eq!(
+            node.action_inputs(temp.path()),
+            vec![("node-version-file", ".nvmrc".to_string())]
+        );
+    }
+
+    #[test]
+    fn image_uses_pinned_version() {
+        let temp = TempDir::new().unwrap();
+        let ruby = TOOLCHAINS.iter().find(|t| t.requirement == "ruby").unwrap();
+        assert_eq!(ruby.image(temp.path()), "ruby:3.3");
+
+        fs::write(temp.path().join(".ruby-version"), "3.2.2\n").unwrap();
+        assert_eq!(ruby.image(temp.path()), "ruby:3.2.2");
+
+        fs::write(temp.path().join(".ruby-version"), "ruby-3.2.2\n").unwrap();
+        assert_eq!(ruby.image(temp.path()), "ruby:3.3");
+    }
+}
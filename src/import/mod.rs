@@ -0,0 +1,11 @@
+//! Importing existing setup scripts into bivvy config.
+//!
+//! - [`sources`] - `bin/setup` scripts, Makefiles, justfiles, package.json
+//!   scripts and devcontainer.json
+//! - [`steps`] - Template and custom steps built from what they run
+
+pub mod sources;
+pub mod steps;
+
+pub use sources::{parse, ImportedCommand, ParsedSource, SourceKind};
+pub use steps::{build_steps, write_steps, ImportedStep};
@@ -0,0 +1,1047 @@
+//! Parsers for existing setup sources.
+//!
+//! Each parser turns a source into the commands it runs, in order, with
+//! the heading each command appeared under: a comment or echoed banner in
+//! a script, a Make target or just recipe, a package.json script name or a
+//! devcontainer command name.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use crate::error::{BivvyError, Result};
+
+/// Make targets tried, in order, when importing a Makefile.
+const MAKE_TARGETS: &[&str] = &["setup", "bootstrap", "install"];
+
+/// package.json scripts tried, in order.
+const NPM_SCRIPTS: &[&str] = &["setup", "bootstrap"];
+
+/// devcontainer.json lifecycle commands, in the order they run.
+const DEVCONTAINER_COMMANDS: &[&str] = &[
+    "onCreateCommand",
+    "updateContentCommand",
+    "postCreateCommand",
+];
+
+/// Kind of setup source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// A shell script such as `bin/setup` or `script/bootstrap`.
+    Shell,
+    /// A Ruby script, like the `bin/setup` Rails generates.
+    Ruby,
+    /// A Makefile `setup:` target.
+    Makefile,
+    /// A justfile `setup` recipe.
+    Justfile,
+    /// package.json `setup` script.
+    PackageJson,
+    /// devcontainer.json lifecycle commands.
+    Devcontainer,
+}
+
+impl SourceKind {
+    /// Identify a source from its file name and, for scripts, its shebang.
+    pub fn detect(path: &Path, content: &str) -> Self {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match name.as_str() {
+            "makefile" | "gnumakefile" => Self::Makefile,
+            "justfile" | ".justfile" => Self::Justfile,
+            "package.json" => Self::PackageJson,
+            "devcontainer.json" | ".devcontainer.json" => Self::Devcontainer,
+            _ if name.ends_with(".mk") => Self::Makefile,
+            _ if name.ends_with(".rb") => Self::Ruby,
+            _ if content
+                .lines()
+                .next()
+                .is_some_and(|l| l.starts_with("#!") && l.contains("ruby")) =>
+            {
+                Self::Ruby
+            }
+            _ => Self::Shell,
+        }
+    }
+
+    /// Short description used in messages.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Shell => "shell script",
+            Self::Ruby => "Ruby script",
+            Self::Makefile => "Makefile",
+            Self::Justfile => "justfile",
+            Self::PackageJson => "package.json scripts",
+            Self::Devcontainer => "devcontainer.json",
+        }
+    }
+}
+
+/// A command found in a setup source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedCommand {
+    /// The shell command.
+    pub command: String,
+    /// Heading the command appeared under, if any.
+    pub section: Option<String>,
+}
+
+/// What a setup source runs.
+#[derive(Debug, Default)]
+pub struct ParsedSource {
+    /// Commands, in the order they run.
+    pub commands: Vec<ImportedCommand>,
+    /// Lines that weren't imported and need a look by hand.
+    pub skipped: Vec<String>,
+}
+
+impl ParsedSource {
+    fn push(&mut self, command: impl Into<String>, section: Option<&str>) {
+        let command = command.into();
+        let command = command.trim();
+        if command.is_empty() {
+            return;
+        }
+        self.commands.push(ImportedCommand {
+            command: command.to_string(),
+            section: section.map(str::to_string),
+        });
+    }
+}
+
+/// Parse a setup source of the given kind.
+pub fn parse(kind: SourceKind, content: &str) -> Result<ParsedSource> {
+    match kind {
+        SourceKind::Shell => Ok(parse_shell(content)),
+        SourceKind::Ruby => Ok(parse_ruby(content)),
+        SourceKind::Makefile => parse_makefile(content),
+        SourceKind::Justfile => parse_justfile(content),
+        SourceKind::PackageJson => parse_package_json(content),
+        SourceKind::Devcontainer => parse_devcontainer(content),
+    }
+}
+
+fn import_error(message: String) -> BivvyError {
+    BivvyError::Other(anyhow::anyhow!(message))
+}
+
+// === Shell ===
+
+/// Parse a shell script.
+///
+/// Comments directly above a command title it and the commands after it
+/// until the next blank line; echoed banners title every command until
+/// the next banner. `if`/`for`/`while`/`case` blocks are kept whole.
+/// Function definitions, `export`, `source` and `alias` lines are
+/// reported as skipped.
+pub fn parse_shell(content: &str) -> ParsedSource {
+    let mut parsed = ParsedSource::default();
+    let lines = logical_lines(content);
+    let mut banner: Option<String> = None;
+    let mut comment: Option<String> = None;
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i].trim();
+        i += 1;
+
+        if line.is_empty() {
+            comment = None;
+            continue;
+        }
+        if line.starts_with("#!") {
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('#') {
+            if !is_directive(text) {
+                comment = heading(text);
+            }
+            continue;
+        }
+        if is_shell_boilerplate(line) {
+            continue;
+        }
+        if let Some(text) = echo_text(line) {
+            if let Some(text) = heading(&text) {
+                banner = Some(text);
+                comment = None;
+            }
+            continue;
+        }
+
+        let mut block = vec![line.to_string()];
+        let mut depth = block_depth(line);
+        while depth > 0 && i < lines.len() {
+            block.push(lines[i].trim_end().to_string());
+            depth += block_depth(lines[i].trim());
+            i += 1;
+        }
+
+        if is_function_definition(line) || is_skipped_shell(line) {
+            parsed.skipped.push(block.join("\n"));
+            continue;
+        }
+        let section = comment.as_deref().or(banner.as_deref());
+        parsed.push(block.join("\n"), section);
+    }
+    parsed
+}
+
+/// Join backslash-continued lines.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for line in content.lines() {
+        match line.strip_suffix('\\') {
+            Some(start) => {
+                current.push_str(start.trim_end());
+                current.push(' ');
+            }
+            None => {
+                current.push_str(if current.is_empty() {
+                    line
+                } else {
+                    line.trim_start()
+                });
+                lines.push(std::mem::take(&mut current));
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Change in block nesting a line makes.
+fn block_depth(line: &str) -> i32 {
+    let mut depth = 0;
+    for segment in line.split(';') {
+        let mut words = segment.split_whitespace();
+        if let Some(first) = words.next() {
+            if matches!(first, "if" | "for" | "while" | "until" | "case") {
+                depth += 1;
+            }
+        }
+        for word in segment.split_whitespace() {
+            if matches!(word, "fi" | "done" | "esac" | "}") {
+                depth -= 1;
+            }
+        }
+    }
+    if line.trim_end().ends_with('{') {
+        depth += 1;
+    }
+    depth
+}
+
+fn is_function_definition(line: &str) -> bool {
+    line.starts_with("function ")
+        || line
+            .split_once("()")
+            .is_some_and(|(name, _)| !name.is_empty() && !name.contains(char::is_whitespace))
+}
+
+fn is_directive(comment: &str) -> bool {
+    let comment = comment.trim_start();
+    comment.starts_with("shellcheck") || comment.starts_with("-*-") || comment.starts_with('!')
+}
+
+/// Lines every setup script has that aren't setup steps.
+fn is_shell_boilerplate(line: &str) -> bool {
+    let first = line.split_whitespace().next().unwrap_or("");
+    matches!(
+        first,
+        "set" | "exit" | "trap" | "shopt" | "readonly" | "local"
+    ) || line.starts_with("IFS=")
+        || (first == "cd"
+            && (line.contains("dirname") || line.contains("rev-parse") || line.contains("$0")))
+}
+
+/// Lines that change the script's environment, which steps don't share.
+fn is_skipped_shell(line: &str) -> bool {
+    let first = line.split_whitespace().next().unwrap_or("");
+    matches!(first, "export" | "source" | "." | "alias" | "unset")
+        || (first.contains('=') && !first.starts_with('-') && line.split_whitespace().count() == 1)
+}
+
+/// The text an `echo` or `printf` line prints.
+fn echo_text(line: &str) -> Option<String> {
+    let rest = line
+        .strip_prefix("echo ")
+        .or_else(|| line.strip_prefix("printf "))
+        .or_else(|| (line == "echo").then_some(""))?;
+    if rest.contains(['>', '|']) {
+        return None;
+    }
+    let words: Vec<&str> = rest
+        .split_whitespace()
+        .skip_while(|w| w.starts_with('-'))
+        .collect();
+    Some(words.join(" ").replace("\\n", " ").replace(['"', '\''], ""))
+}
+
+/// A heading from a comment or banner, without decoration.
+fn heading(text: &str) -> Option<String> {
+    let decoration = |c: char| {
+        c.is_whitespace() || matches!(c, '=' | '-' | '*' | '#' | '>' | ':' | '.' | '~' | '_')
+    };
+    let text = text.trim_matches(decoration);
+    (!text.is_empty() && text.chars().any(char::is_alphabetic)).then(|| text.to_string())
+}
+
+// === Ruby ===
+
+/// Parse a Ruby setup script such as Rails' `bin/setup`.
+///
+/// `system`/`system!` calls become commands, `puts` banners title them,
+/// and `FileUtils.cp` becomes `cp -n`. Other statements that aren't
+/// structure (`require`, `def`, `end`, blocks) are reported as skipped.
+pub fn parse_ruby(content: &str) -> ParsedSource {
+    let mut parsed = ParsedSource::default();
+    let mut banner: Option<String> = None;
+    let mut in_def = 0;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line.starts_with("def ") {
+            in_def += 1;
+            continue;
+        }
+        if in_def > 0 {
+            if line == "end" {
+                in_def -= 1;
+            }
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("puts") {
+            if let Some(text) = string_literals(rest).first().and_then(|t| heading(t)) {
+                banner = Some(text);
+            }
+            continue;
+        }
+
+        let calls = system_calls(line);
+        if !calls.is_empty() {
+            let joiner = if line.contains("||") { " || " } else { " && " };
+            parsed.push(calls.join(joiner), banner.as_deref());
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("FileUtils.cp") {
+            let args = string_literals(rest);
+            if args.len() == 2 {
+                parsed.push(format!("cp -n {} {}", args[0], args[1]), banner.as_deref());
+                continue;
+            }
+        }
+        if let Some(rest) = line.strip_prefix("FileUtils.mkdir_p") {
+            let args = string_literals(rest);
+            if !args.is_empty() {
+                parsed.push(format!("mkdir -p {}", args.join(" ")), banner.as_deref());
+                continue;
+            }
+        }
+
+        let structural = ["require", "include", "end", "APP_ROOT", "FileUtils.chdir"]
+            .iter()
+            .any(|p| line.starts_with(p))
+            || line.ends_with(" do")
+            || line.starts_with("unless ")
+            || line.starts_with("if ");
+        if !structural {
+            parsed.skipped.push(line.to_string());
+        }
+    }
+    parsed
+}
+
+/// Commands run by `system`/`system!` calls on a line, in order.
+fn system_calls(line: &str) -> Vec<String> {
+    let mut calls = Vec::new();
+    let mut rest = line;
+    while let Some(pos) = rest.find("system") {
+        let after = rest[pos + "system".len()..].trim_start_matches('!');
+        let end = after.find(['|', '&']).unwrap_or(after.len());
+        let args = string_literals(&after[..end]);
+        if !args.is_empty() {
+            calls.push(args.join(" "));
+        }
+        rest = &after[end..];
+    }
+    calls
+}
+
+/// The contents of the quoted string literals in `text`, in order.
+fn string_literals(text: &str) -> Vec<String> {
+    let mut literals = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' && c != '\'' {
+            continue;
+        }
+        let mut literal = String::new();
+        while let Some(next) = chars.next() {
+            match next {
+                '\\' => {
+                    if let Some(escaped) = chars.next() {
+                        literal.push(if escaped == 'n' { '\n' } else { escaped });
+                    }
+                }
+                _ if next == c => break,
+                _ => literal.push(next),
+            }
+        }
+        literals.push(literal);
+    }
+    literals
+}
+
+// === Make ===
+
+#[derive(Debug, Default)]
+struct Rule {
+    prerequisites: Vec<String>,
+    recipe: Vec<String>,
+}
+
+/// Parse a Makefile's setup target, with its prerequisites first.
+///
+/// Each target's recipe lines are titled with the target name. Recipe
+/// prefixes (`@`, `-`, `+`) are dropped, `$(MAKE) <target>` runs that
+/// target's recipe in place, and simple variables are substituted.
+pub fn parse_makefile(content: &str) -> Result<ParsedSource> {
+    let mut rules: HashMap<String, Rule> = HashMap::new();
+    let mut variables: HashMap<String, String> = HashMap::new();
+    let mut current: Vec<String> = Vec::new();
+
+    for line in logical_lines(content) {
+        if let Some(recipe) = line.strip_prefix('\t') {
+            for target in &current {
+                rules
+                    .entry(target.clone())
+                    .or_default()
+                    .recipe
+                    .push(recipe.to_string());
+            }
+            continue;
+        }
+        let line = line.split('#').next().unwrap_or("").trim_end();
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some((name, value)) = make_assignment(line) {
+            variables.insert(name, value);
+            current.clear();
+            continue;
+        }
+        if let Some((targets, prerequisites)) = line.split_once(':') {
+            let prerequisites: Vec<String> = prerequisites
+                .split(';')
+                .next()
+                .unwrap_or("")
+                .split_whitespace()
+                .map(str::to_string)
+                .collect();
+            current = targets
+                .split_whitespace()
+                .filter(|t| !t.starts_with('.'))
+                .map(str::to_string)
+                .collect();
+            for target in &current {
+                rules
+                    .entry(target.clone())
+                    .or_default()
+                    .prerequisites
+                    .extend(prerequisites.iter().cloned());
+            }
+            continue;
+        }
+        current.clear();
+    }
+
+    let Some(target) = MAKE_TARGETS.iter().find(|t| rules.contains_key(**t)) else {
+        return Err(import_error(format!(
+            "No {} target found in the Makefile",
+            MAKE_TARGETS.join(", ")
+        )));
+    };
+
+    let mut parsed = ParsedSource::default();
+    let mut seen = HashSet::new();
+    expand_make(target, &rules, &variables, &mut seen, &mut parsed);
+    Ok(parsed)
+}
+
+fn make_assignment(line: &str) -> Option<(String, String)> {
+    for op in [":=", "?=", "+=", "::=", "="] {
+        if let Some((name, value)) = line.split_once(op) {
+            let name = name.trim();
+            if !name.is_empty() && !name.contains(char::is_whitespace) && !name.contains(':') {
+                return Some((name.to_string(), value.trim().to_string()));
+            }
+        }
+    }
+    None
+}
+
+fn expand_make(
+    target: &str,
+    rules: &HashMap<String, Rule>,
+    variables: &HashMap<String, String>,
+    seen: &mut HashSet<String>,
+    parsed: &mut ParsedSource,
+) {
+    if !seen.insert(target.to_string()) {
+        return;
+    }
+    let Some(rule) = rules.get(target) else {
+        return;
+    };
+    for prerequisite in &rule.prerequisites {
+        expand_make(prerequisite, rules, variables, seen, parsed);
+    }
+
+    let mut section = target.to_string();
+    for line in &rule.recipe {
+        let line = substitute_make(line.trim().trim_start_matches(['@', '-', '+']), variables);
+        let line = line.trim();
+        if let Some(sub) = line
+            .strip_prefix("$(MAKE) ")
+            .or_else(|| line.strip_prefix("make "))
+            .filter(|t| rules.contains_key(t.trim()))
+        {
+            expand_make(sub.trim(), rules, variables, seen, parsed);
+            continue;
+        }
+        if let Some(text) = echo_text(line) {
+            if let Some(text) = heading(&text) {
+                section = text;
+            }
+            continue;
+        }
+        parsed.push(line, Some(&section));
+    }
+}
+
+/// Substitute `$(VAR)`/`${VAR}` for known variables and unescape `$$`.
+fn substitute_make(line: &str, variables: &HashMap<String, String>) -> String {
+    let mut out = line.to_string();
+    for (name, value) in variables {
+        out = out
+            .replace(&format!("$({name})"), value)
+            .replace(&format!("${{{name}}}"), value);
+    }
+    out.replace("$$", "$")
+}
+
+// === just ===
+
+/// Parse a justfile's `setup` recipe, with its dependencies first.
+///
+/// Recipes are titled with their names. `just <recipe>` runs that recipe
+/// in place, and shebang recipes are kept as one multi-line command.
+pub fn parse_justfile(content: &str) -> Result<ParsedSource> {
+    let mut rules: HashMap<String, Rule> = HashMap::new();
+    let mut current: Option<String> = None;
+
+    for line in content.lines() {
+        if line.starts_with(' ') || line.starts_with('\t') {
+            if let Some(name) = &current {
+                if !line.trim().is_empty() {
+                    rules
+                        .entry(name.clone())
+                        .or_default()
+                        .recipe
+                        .push(line.trim().to_string());
+                }
+            }
+            continue;
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        current = None;
+        if trimmed.starts_with("set ") || trimmed.starts_with("export ") || trimmed.contains(":=") {
+            continue;
+        }
+        if let Some((head, deps)) = trimmed.split_once(':') {
+            let name = head
+                .trim_start_matches('@')
+                .split_whitespace()
+                .next()
+                .unwrap_or("")
+                .to_string();
+            let deps = deps
+                .split_whitespace()
+                .map(|d| d.trim_start_matches('(').to_string())
+                .filter(|d| {
+                    d.chars()
+                        .all(|c| c.is_alphanumeric() || c == '-' || c == '_')
+                })
+                .collect();
+            rules.insert(
+                name.clone(),
+                Rule {
+                    prerequisites: deps,
+                    recipe: Vec::new(),
+                },
+            );
+            current = Some(name);
+        }
+    }
+
+    if !rules.contains_key("setup") {
+        return Err(import_error(
+            "No setup recipe found in the justfile".to_string(),
+        ));
+    }
+
+    let mut parsed = ParsedSource::default();
+    let mut seen = HashSet::new();
+    expand_just("setup", &rules, &mut seen, &mut parsed);
+    Ok(parsed)
+}
+
+fn expand_just(
+    recipe: &str,
+    rules: &HashMap<String, Rule>,
+    seen: &mut HashSet<String>,
+    parsed: &mut ParsedSource,
+) {
+    if !seen.insert(recipe.to_string()) {
+        return;
+    }
+    let Some(rule) = rules.get(recipe) else {
+        return;
+    };
+    for dep in &rule.prerequisites {
+        expand_just(dep, rules, seen, parsed);
+    }
+
+    if rule.recipe.first().is_some_and(|l| l.starts_with("#!")) {
+        parsed.push(rule.recipe[1..].join("\n"), Some(recipe));
+        return;
+    }
+    for line in &rule.recipe {
+        let line = line.trim_start_matches(['@', '-']);
+        if let Some(sub) = line
+            .strip_prefix("just ")
+            .filter(|t| rules.contains_key(t.trim()))
+        {
+            expand_just(sub.trim(), rules, seen, parsed);
+            continue;
+        }
+        if line.starts_with('#') || echo_text(line).is_some() {
+            continue;
+        }
+        parsed.push(line, Some(recipe));
+    }
+}
+
+// === package.json ===
+
+/// Parse package.json's `setup` (or `bootstrap`) script.
+///
+/// Scripts are split on `&&`, and `npm run`/`yarn`/`pnpm`/`bun run`
+/// calls to other scripts run them in place, titled with their names.
+/// `pre` and `post` scripts run around the script, as npm does.
+pub fn parse_package_json(content: &str) -> Result<ParsedSource> {
+    let json: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| import_error(format!("Invalid package.json: {e}")))?;
+    let scripts: HashMap<String, String> = json
+        .get("scripts")
+        .and_then(|s| s.as_object())
+        .map(|s| {
+            s.iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let Some(entry) = NPM_SCRIPTS.iter().find(|s| scripts.contains_key(**s)) else {
+        return Err(import_error(format!(
+            "No {} script found in package.json",
+            NPM_SCRIPTS.join(" or ")
+        )));
+    };
+
+    let mut parsed = ParsedSource::default();
+    let mut seen = HashSet::new();
+    expand_script(entry, &scripts, &mut seen, &mut parsed);
+    Ok(parsed)
+}
+
+fn expand_script(
+    name: &str,
+    scripts: &HashMap<String, String>,
+    seen: &mut HashSet<String>,
+    parsed: &mut ParsedSource,
+) {
+    if !seen.insert(name.to_string()) {
+        return;
+    }
+    let Some(script) = scripts.get(name) else {
+        return;
+    };
+    let pre = format!("pre{name}");
+    if scripts.contains_key(&pre) {
+        expand_script(&pre, scripts, seen, parsed);
+    }
+    for part in script.split("&&") {
+        let part = part.trim();
+        match script_reference(part).filter(|s| scripts.contains_key(*s)) {
+            Some(other) => expand_script(other, scripts, seen, parsed),
+            None => parsed.push(part, Some(name)),
+        }
+    }
+    let post = format!("post{name}");
+    if scripts.contains_key(&post) {
+        expand_script(&post, scripts, seen, parsed);
+    }
+}
+
+/// The script an `npm run x`-style command runs.
+fn script_reference(command: &str) -> Option<&str> {
+    let words: Vec<&str> = command.split_whitespace().collect();
+    match words.as_slice() {
+        ["npm", "run" | "run-script", script]
+        | ["yarn" | "pnpm" | "bun", "run", script]
+        | ["yarn" | "pnpm", script] => Some(script),
+        _ => None,
+    }
+}
+
+// === devcontainer.json ===
+
+/// Parse a devcontainer's lifecycle commands, in the order they run.
+///
+/// String commands are split on `&&`, array commands are quoted and
+/// joined, and the entries of object commands are titled with their keys.
+pub fn parse_devcontainer(content: &str) -> Result<ParsedSource> {
+    let json: serde_json::Value = serde_json::from_str(&strip_jsonc(content))
+        .map_err(|e| import_error(format!("Invalid devcontainer.json: {e}")))?;
+
+    let mut parsed = ParsedSource::default();
+    for key in DEVCONTAINER_COMMANDS {
+        match json.get(*key) {
+            Some(serde_json::Value::Object(commands)) => {
+                for (name, command) in commands {
+                    push_devcontainer_command(&mut parsed, command, Some(name));
+                }
+            }
+            Some(command) => push_devcontainer_command(&mut parsed, command, None),
+            None => {}
+        }
+    }
+    if parsed.commands.is_empty() {
+        return Err(import_error(format!(
+            "No {} found in devcontainer.json",
+            DEVCONTAINER_COMMANDS.join(", ")
+        )));
+    }
+    Ok(parsed)
+}
+
+fn push_devcontainer_command(
+    parsed: &mut ParsedSource,
+    command: &serde_json::Value,
+    section: Option<&str>,
+) {
+    match command {
+        serde_json::Value::String(command) => {
+            for part in command.split("&&") {
+                parsed.push(part, section);
+            }
+        }
+        serde_json::Value::Array(argv) => {
+            let words: Vec<String> = argv
+                .iter()
+                .filter_map(|a| a.as_str())
+                .map(shell_quote)
+                .collect();
+            parsed.push(words.join(" "), section);
+        }
+        _ => {}
+    }
+}
+
+fn shell_quote(word: &str) -> String {
+    if !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || "-_./=:@%+,".contains(c))
+    {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+/// Remove JSONC comments and trailing commas.
+fn strip_jsonc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => {
+                    if let Some(next) = chars.next() {
+                        out.push(next);
+                    }
+                }
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                for next in chars.by_ref() {
+                    if next == '\n' {
+                        out.push('\n');
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                let mut prev = ' ';
+                for next in chars.by_ref() {
+                    if prev == '*' && next == '/' {
+                        break;
+                    }
+                    prev = next;
+                }
+            }
+            (',', _) => {
+                let rest: String = chars.clone().collect();
+                let next = rest.trim_start().chars().next();
+                if !matches!(next, Some('}') | Some(']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(parsed: &ParsedSource) -> Vec<(&str, Option<&str>)> {
+        parsed
+            .commands
+            .iter()
+            .map(|c| (c.command.as_str(), c.section.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn detects_source_kinds() {
+        let kind = |name: &str, content: &str| SourceKind::detect(Path::new(name), content);
+        assert_eq!(kind("Makefile", ""), SourceKind::Makefile);
+        assert_eq!(kind("justfile", ""), SourceKind::Justfile);
+        assert_eq!(kind("package.json", ""), SourceKind::PackageJson);
+        assert_eq!(
+            kind(".devcontainer/devcontainer.json", ""),
+            SourceKind::Devcontainer
+        );
+        assert_eq!(kind("bin/setup", "#!/usr/bin/env ruby\n"), SourceKind::Ruby);
+        assert_eq!(kind("bin/setup", "#!/bin/bash\n"), SourceKind::Shell);
+    }
+
+    #[test]
+    fn shell_titles_commands_from_comments_and_banners() {
+        let parsed = parse_shell(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+cd "$(dirname "$0")/.."
+
+echo "== Installing dependencies =="
+bundle install
+yarn
+
+# Prepare the database
+bin/rails db:prepare \
+  --trace
+
+export RAILS_ENV=development
+if [ ! -f .env ]; then
+  cp .env.example .env
+fi
+"#,
+        );
+
+        assert_eq!(
+            commands(&parsed),
+            vec![
+                ("bundle install", Some("Installing dependencies")),
+                ("yarn", Some("Installing dependencies")),
+                ("bin/rails db:prepare --trace", Some("Prepare the database")),
+                (
+                    "if [ ! -f .env ]; then\n  cp .env.example .env\nfi",
+                    Some("Installing dependencies")
+                ),
+            ]
+        );
+        assert_eq!(parsed.skipped, vec!["export RAILS_ENV=development"]);
+    }
+
+    #[test]
+    fn shell_skips_function_definitions() {
+        let parsed = parse_shell("log() {\n  echo \"$1\"\n}\nnpm ci\n");
+        assert_eq!(commands(&parsed), vec![("npm ci", None)]);
+        assert_eq!(parsed.skipped.len(), 1);
+    }
+
+    #[test]
+    fn ruby_bin_setup() {
+        let parsed = parse_ruby(
+            r#"#!/usr/bin/env ruby
+require "fileutils"
+
+APP_ROOT = File.expand_path("..", __dir__)
+
+def system!(*args)
+  system(*args, exception: true)
+end
+
+FileUtils.chdir APP_ROOT do
+  puts "== Installing dependencies =="
+  system("bundle check") || system!("bundle install")
+
+  puts "\n== Copying sample files =="
+  unless File.exist?("config/database.yml")
+    FileUtils.cp "config/database.yml.sample", "config/database.yml"
+  end
+
+  puts "\n== Preparing database =="
+  system! "bin/rails", "db:prepare"
+  exec "bin/dev"
+end
+"#,
+        );
+
+        assert_eq!(
+            commands(&parsed),
+            vec![
+                (
+                    "bundle check || bundle install",
+                    Some("Installing dependencies")
+                ),
+                (
+                    "cp -n config/database.yml.sample config/database.yml",
+                    Some("Copying sample files")
+                ),
+                ("bin/rails db:prepare", Some("Preparing database")),
+            ]
+        );
+        assert_eq!(parsed.skipped, vec!["exec \"bin/dev\""]);
+    }
+
+    #[test]
+    fn makefile_expands_prerequisites_and_submakes() {
+        let parsed = parse_makefile(
+            "NPM := npm\n\
+             .PHONY: setup deps db\n\
+             setup: deps\n\
+             \t@echo \"Setting up the database\"\n\
+             \t$(MAKE) db\n\
+             deps:\n\
+             \t$(NPM) ci\n\
+             db:\n\
+             \t-bin/migrate $$HOME\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            commands(&parsed),
+            vec![("npm ci", Some("deps")), ("bin/migrate $HOME", Some("db"))]
+        );
+    }
+
+    #[test]
+    fn makefile_without_setup_target_fails() {
+        let err = parse_makefile("build:\n\tcargo build\n").unwrap_err();
+        assert!(err.to_string().contains("No setup"));
+    }
+
+    #[test]
+    fn justfile_recipe_with_dependencies() {
+        let parsed = parse_justfile(
+            "set dotenv-load\n\n\
+             setup: deps\n    @just db\n\n\
+             deps:\n    bundle install\n\n\
+             db:\n    #!/usr/bin/env bash\n    bin/rails db:create\n    bin/rails db:migrate\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            commands(&parsed),
+            vec![
+                ("bundle install", Some("deps")),
+                ("bin/rails db:create\nbin/rails db:migrate", Some("db")),
+            ]
+        );
+    }
+
+    #[test]
+    fn package_json_follows_script_references() {
+        let parsed = parse_package_json(
+            r#"{"scripts": {
+                "presetup": "node scripts/check.js",
+                "setup": "yarn && npm run db:setup",
+                "db:setup": "prisma migrate dev"
+            }}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            commands(&parsed),
+            vec![
+                ("node scripts/check.js", Some("presetup")),
+                ("yarn", Some("setup")),
+                ("prisma migrate dev", Some("db:setup")),
+            ]
+        );
+    }
+
+    #[test]
+    fn devcontainer_lifecycle_commands() {
+        let parsed = parse_devcontainer(
+            r#"{
+                // Comments are allowed
+                "name": "app",
+                "onCreateCommand": ["bash", "-c", "echo hi there"],
+                "postCreateCommand": {
+                    "deps": "bundle install && yarn",
+                },
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            commands(&parsed),
+            vec![
+                ("bash -c 'echo hi there'", None),
+                ("bundle install", Some("deps")),
+                ("yarn", Some("deps")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,398 @@
+//! Turning imported commands into config steps.
+//!
+//! Commands the template registry recognises become template steps;
+//! the rest become custom steps, grouped by the heading they appeared
+//! under. Each step depends on the one before it, preserving the order
+//! the original source ran them in.
+
+use std::collections::HashSet;
+
+use serde_yaml::{Mapping, Value};
+
+use super::sources::ImportedCommand;
+use crate::config::ConfigEditor;
+use crate::error::Result;
+use crate::registry::resolver::Registry;
+
+/// Longest title inferred from a command.
+const MAX_TITLE_LEN: usize = 50;
+
+/// Longest step name inferred from a title.
+const MAX_NAME_LEN: usize = 40;
+
+/// Commands that mean the same as a template's command.
+const ALIASES: &[(&str, &str)] = &[
+    ("yarn", "yarn install"),
+    ("bundle", "bundle install"),
+    ("npm i", "npm install"),
+    ("pnpm i", "pnpm install"),
+    ("bun i", "bun install"),
+    ("rails db:setup", "rails db:prepare"),
+];
+
+/// A step built from imported commands.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportedStep {
+    /// Step name.
+    pub name: String,
+    /// Title, for custom steps.
+    pub title: Option<String>,
+    /// Template the step uses, if its command matched one.
+    pub template: Option<String>,
+    /// Command, for custom steps and templates run with extra options.
+    pub command: Option<String>,
+    /// The step that ran before this one.
+    pub depends_on: Vec<String>,
+}
+
+/// Template commands, for recognising imported commands.
+struct TemplateMatcher {
+    /// Normalised command and the name to reference the template by.
+    commands: Vec<(String, String)>,
+}
+
+impl TemplateMatcher {
+    fn new(registry: &Registry) -> Self {
+        let mut commands: Vec<(String, String)> = Vec::new();
+        for qualified in registry.all_template_names() {
+            let Some(template) = registry.get(&qualified) else {
+                continue;
+            };
+            let Some(command) = template.step.command.as_deref() else {
+                continue;
+            };
+            // Templates that need inputs or run scripts can't be matched
+            // against a single command
+            if command.contains("${") || command.trim().contains('\n') {
+                continue;
+            }
+            let command = normalize(command);
+            if commands.iter().any(|(c, _)| *c == command) {
+                continue;
+            }
+            // Prefer the short name when it resolves to the same template
+            let unambiguous = registry
+                .get(&template.name)
+                .is_some_and(|t| t.category == template.category);
+            let name = if unambiguous {
+                template.name.clone()
+            } else {
+                qualified
+            };
+            commands.push((command, name));
+        }
+        Self { commands }
+    }
+
+    /// The template a command runs and, when it adds options to the
+    /// template's command, the command to run instead.
+    fn find(&self, command: &str) -> Option<(String, Option<String>)> {
+        // `check || install` runs the install; templates have their own checks
+        let segment = command.rsplit(" || ").next().unwrap_or(command).trim();
+        let normalized = normalize(segment);
+
+        if let Some((_, name)) = self.commands.iter().find(|(c, _)| *c == normalized) {
+            return Some((name.clone(), None));
+        }
+        self.commands.iter().find_map(|(c, name)| {
+            let flags = normalized.strip_prefix(c.as_str())?.strip_prefix(' ')?;
+            flags
+                .starts_with('-')
+                .then(|| (name.clone(), Some(segment.to_string())))
+        })
+    }
+}
+
+/// Normalise a command for comparison: collapse whitespace and spell
+/// equivalent invocations the same way.
+fn normalize(command: &str) -> String {
+    let mut words: Vec<&str> = command.split_whitespace().collect();
+    match words.as_slice() {
+        ["bundle", "exec", "rails" | "bin/rails", ..] => {
+            words.splice(..3, ["rails"]);
+        }
+        ["bin/rails" | "./bin/rails", ..] => words[0] = "rails",
+        ["pip3", ..] => words[0] = "pip",
+        ["python3", ..] => words[0] = "python",
+        _ => {}
+    }
+    let command = words.join(" ");
+    ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == command)
+        .map(|(_, canonical)| canonical.to_string())
+        .unwrap_or(command)
+}
+
+/// Build steps from imported commands.
+///
+/// Commands are matched against the registry's templates when one is
+/// given. Consecutive unmatched commands under the same heading share a
+/// step titled with the heading; other custom steps are titled with
+/// their command.
+pub fn build_steps(commands: &[ImportedCommand], registry: Option<&Registry>) -> Vec<ImportedStep> {
+    let matcher = registry.map(TemplateMatcher::new);
+    let mut steps: Vec<ImportedStep> = Vec::new();
+    let mut names = HashSet::new();
+    // Heading of the last step, if it's a custom step with one
+    let mut open_section: Option<&str> = None;
+
+    for imported in commands {
+        let section = imported.section.as_deref();
+        let matched = matcher.as_ref().and_then(|m| m.find(&imported.command));
+
+        if let Some((template, command)) = matched {
+            let short = template.rsplit('/').next().unwrap_or(&template);
+            let name = unique_name(short, &mut names);
+            steps.push(ImportedStep {
+                depends_on: steps
+                    .last()
+                    .map(|s| vec![s.name.clone()])
+                    .unwrap_or_default(),
+                name,
+                title: None,
+                template: Some(template),
+                command,
+            });
+            open_section = None;
+            continue;
+        }
+
+        if let (Some(open), Some(section)) = (open_section, section) {
+            if open == section {
+                if let Some(step) = steps.last_mut() {
+                    let command = step.command.get_or_insert_with(String::new);
+                    command.push_str(" && ");
+                    command.push_str(&imported.command);
+                    continue;
+                }
+            }
+        }
+
+        let title = match section {
+            Some(section) => section.to_string(),
+            None => title_for(&imported.command),
+        };
+        let name = unique_name(&slug(&title), &mut names);
+        steps.push(ImportedStep {
+            depends_on: steps
+                .last()
+                .map(|s| vec![s.name.clone()])
+                .unwrap_or_default(),
+            name,
+            title: Some(title),
+            template: None,
+            command: Some(imported.command.clone()),
+        });
+        open_section = section;
+    }
+    steps
+}
+
+/// A title from a command's first line.
+fn title_for(command: &str) -> String {
+    let line = command.lines().next().unwrap_or("").trim();
+    if line.chars().count() <= MAX_TITLE_LEN {
+        return line.to_string();
+    }
+    let truncated: String = line.chars().take(MAX_TITLE_LEN - 3).collect();
+    format!("{}...", truncated.trim_end())
+}
+
+/// A step name from a title: lowercase words joined with dashes.
+fn slug(title: &str) -> String {
+    let mut slug = String::new();
+    for word in title
+        .to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        if !slug.is_empty() && slug.len() + word.len() + 1 > MAX_NAME_LEN {
+            break;
+        }
+        if !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push_str(word);
+    }
+    slug.truncate(MAX_NAME_LEN);
+    if slug.is_empty() {
+        slug.push_str("step");
+    }
+    slug
+}
+
+fn unique_name(base: &str, names: &mut HashSet<String>) -> String {
+    let mut name = base.to_string();
+    let mut n = 2;
+    while names.contains(&name) {
+        name = format!("{base}-{n}");
+        n += 1;
+    }
+    names.insert(name.clone());
+    name
+}
+
+/// Add steps and a `default` workflow running them to a config document.
+pub fn write_steps(editor: &mut ConfigEditor, steps: &[ImportedStep]) -> Result<()> {
+    for step in steps {
+        let mut fields = Mapping::new();
+        if let Some(ref template) = step.template {
+            fields.insert("template".into(), template.as_str().into());
+        }
+        if let Some(ref title) = step.title {
+            fields.insert("title".into(), title.as_str().into());
+        }
+        if let Some(ref command) = step.command {
+            fields.insert("command".into(), command.as_str().into());
+        }
+        if !step.depends_on.is_empty() {
+            let depends_on = step.depends_on.iter().map(|d| d.as_str().into()).collect();
+            fields.insert("depends_on".into(), Value::Sequence(depends_on));
+        }
+        editor.set(&["steps", step.name.as_str()], &Value::Mapping(fields))?;
+    }
+
+    let names = steps.iter().map(|s| s.name.as_str().into()).collect();
+    editor.set(&["workflows", "default", "steps"], &Value::Sequence(names))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn command(command: &str, section: Option<&str>) -> ImportedCommand {
+        ImportedCommand {
+            command: command.to_string(),
+            section: section.map(str::to_string),
+        }
+    }
+
+    fn registry() -> Registry {
+        Registry::new(None).unwrap()
+    }
+
+    #[test]
+    fn maps_known_commands_to_templates() {
+        let registry = registry();
+        let steps = build_steps(
+            &[
+                command("bundle check || bundle install", None),
+                command("yarn", None),
+                command("bin/rails db:prepare", None),
+            ],
+            Some(&registry),
+        );
+
+        let templates: Vec<_> = steps.iter().map(|s| s.template.as_deref()).collect();
+        assert_eq!(
+            templates,
+            vec![
+                Some("bundle-install"),
+                Some("yarn-install"),
+                Some("rails-db")
+            ]
+        );
+        assert!(steps.iter().all(|s| s.command.is_none()));
+    }
+
+    #[test]
+    fn extra_flags_override_the_template_command() {
+        let registry = registry();
+        let steps = build_steps(&[command("bundle install --jobs 4", None)], Some(&registry));
+
+        assert_eq!(steps[0].template.as_deref(), Some("bundle-install"));
+        assert_eq!(steps[0].command.as_deref(), Some("bundle install --jobs 4"));
+    }
+
+    #[test]
+    fn extra_arguments_are_not_a_template_match() {
+        let registry = registry();
+        let steps = build_steps(&[command("brew install go jq", None)], Some(&registry));
+
+        assert_eq!(steps[0].template, None);
+    }
+
+    #[test]
+    fn groups_unmatched_commands_by_section() {
+        let steps = build_steps(
+            &[
+                command("cp .env.example .env", Some("Copy sample files")),
+                command(
+                    "cp config/db.yml.sample config/db.yml",
+                    Some("Copy sample files"),
+                ),
+                command("bin/seed", None),
+            ],
+            None,
+        );
+
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].name, "copy-sample-files");
+        assert_eq!(steps[0].title.as_deref(), Some("Copy sample files"));
+        assert_eq!(
+            steps[0].command.as_deref(),
+            Some("cp .env.example .env && cp config/db.yml.sample config/db.yml")
+        );
+        assert_eq!(steps[1].name, "bin-seed");
+        assert_eq!(steps[1].title.as_deref(), Some("bin/seed"));
+    }
+
+    #[test]
+    fn steps_depend_on_the_previous_step() {
+        let steps = build_steps(&[command("a", None), command("b", None)], None);
+
+        assert!(steps[0].depends_on.is_empty());
+        assert_eq!(steps[1].depends_on, vec!["a"]);
+    }
+
+    #[test]
+    fn names_are_unique() {
+        let steps = build_steps(
+            &[
+                command("make", Some("Build")),
+                command("ls", None),
+                command("make", Some("Build")),
+            ],
+            None,
+        );
+
+        let names: Vec<_> = steps.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["build", "ls", "build-2"]);
+    }
+
+    #[test]
+    fn long_commands_get_truncated_titles_and_names() {
+        let long = "curl -fsSL https://example.com/a/very/long/path/to/some/install/script.sh | sh";
+        let steps = build_steps(&[command(long, None)], None);
+
+        let title = steps[0].title.as_deref().unwrap();
+        assert!(title.ends_with("..."));
+        assert!(title.chars().count() <= MAX_TITLE_LEN);
+        assert!(steps[0].name.len() <= MAX_NAME_LEN);
+    }
+
+    #[test]
+    fn renders_steps_and_workflow() {
+        let steps = build_steps(
+            &[
+                command("bundle install", None),
+                command("if true; then\n  echo: hi\nfi", Some("Check")),
+            ],
+            Some(&registry()),
+        );
+        let mut editor = ConfigEditor::new("app_name: demo\n").unwrap();
+        write_steps(&mut editor, &steps).unwrap();
+        let yaml = editor.into_string();
+
+        assert!(yaml.contains("  bundle-install:\n    template: bundle-install\n"));
+        assert!(yaml.contains("    depends_on: [bundle-install]\n"));
+        assert!(yaml.ends_with("workflows:\n  default:\n    steps: [bundle-install, check]\n"));
+
+        let parsed: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(
+            parsed["steps"]["check"]["command"].as_str(),
+            Some("if true; then\n  echo: hi\nfi")
+        );
+    }
+}
@@ -9,6 +9,8 @@
 //! - [`config`] - Configuration loading, parsing, and validation
 //! - [`environment`] - Environment detection and resolution
 //! - [`error`] - Error types and result aliases
+//! - [`export`] - CI pipeline, Dockerfile and devcontainer generation
+//! - [`import`] - Importing existing setup scripts into config
 //! - [`lint`] - Configuration validation and linting
 //! - [`logging`] - Structured event logging (JSONL)
 //! - [`registry`] - Template registry and resolution
@@ -40,7 +42,9 @@ pub mod config;
 pub mod detection;
 pub mod environment;
 pub mod error;
+pub mod export;
 pub mod feedback;
+pub mod import;
 pub mod lint;
 pub mod logging;
 pub mod registry;
@@ -6,6 +6,7 @@
 
 use super::rule::{RuleId, Severity};
 use super::span::Span;
+use crate::config::ConfigEdit;
 
 /// A diagnostic message produced by a lint rule.
 #[derive(Debug, Clone)]
@@ -20,6 +21,8 @@ pub struct LintDiagnostic {
     pub span: Option<Span>,
     /// Optional suggestion for fixing the issue.
     pub suggestion: Option<String>,
+    /// Optional config edit that fixes the issue (applied by `--fix`).
+    pub fix: Option<ConfigEdit>,
     /// Additional related locations.
     pub related: Vec<RelatedInfo>,
 }
@@ -42,6 +45,7 @@ impl LintDiagnostic {
             message: message.into(),
             span: None,
             suggestion: None,
+            fix: None,
             related: vec![],
         }
     }
@@ -58,6 +62,12 @@ impl LintDiagnostic {
         self
     }
 
+    /// Add an automatic fix.
+    pub fn with_fix(mut self, fix: ConfigEdit) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
     /// Add related information.
     pub fn with_related(mut self, span: Span, message: impl Into<String>) -> Self {
         self.related.push(RelatedInfo {
@@ -80,6 +90,7 @@ mod tests {
         assert_eq!(diag.severity, Severity::Error);
         assert_eq!(diag.message, "Test message");
         assert!(diag.suggestion.is_none());
+        assert!(diag.fix.is_none());
         assert!(diag.span.is_none());
         assert!(diag.related.is_empty());
     }
@@ -93,6 +104,17 @@ mod tests {
         assert_eq!(diag.suggestion.unwrap(), "Fix it like this");
     }
 
+    #[test]
+    fn diagnostic_with_fix() {
+        let fix = ConfigEdit::Remove {
+            path: vec!["steps".to_string(), "a".to_string()],
+        };
+        let diag = LintDiagnostic::new(RuleId::new("test-rule"), Severity::Warning, "Test warning")
+            .with_fix(fix.clone());
+
+        assert_eq!(diag.fix, Some(fix));
+    }
+
     #[test]
     fn diagnostic_builder_pattern() {
         let diag = LintDiagnostic::new(RuleId::new("test"), Severity::Error, "Test message")
@@ -1,8 +1,11 @@
 //! Automatic fix application.
 //!
 //! This module provides functionality for automatically fixing
-//! configuration issues detected by lint rules.
+//! configuration issues detected by lint rules. Fixes are
+//! [`ConfigEdit`]s applied with the [`ConfigEditor`], so comments and
+//! formatting in the fixed files are kept.
 
+use crate::config::{ConfigEdit, ConfigEditor};
 use crate::lint::LintDiagnostic;
 use std::collections::HashMap;
 use std::fs;
@@ -13,12 +16,8 @@ use std::path::{Path, PathBuf};
 pub struct Fix {
     /// File to modify.
     pub file: PathBuf,
-    /// Start byte offset.
-    pub start: usize,
-    /// End byte offset.
-    pub end: usize,
-    /// Replacement text.
-    pub replacement: String,
+    /// Edit to apply to the file.
+    pub edit: ConfigEdit,
 }
 
 /// Result of attempting to apply fixes.
@@ -66,7 +65,7 @@ impl FixEngine {
         // Collect unfixable diagnostics
         let unfixable = diagnostics
             .iter()
-            .filter(|d| d.suggestion.is_none())
+            .filter(|d| d.fix.is_none())
             .cloned()
             .collect();
 
@@ -81,41 +80,26 @@ impl FixEngine {
     pub fn preview_fixes(&self, fixes: &[Fix]) -> Vec<String> {
         fixes
             .iter()
-            .map(|f| {
-                format!(
-                    "{}:{}-{}: Replace with '{}'",
-                    f.file.display(),
-                    f.start,
-                    f.end,
-                    f.replacement
-                )
-            })
+            .map(|f| format!("{}: {}", f.file.display(), f.edit))
             .collect()
     }
 
     fn apply_fixes_to_file(&self, file: &Path, fixes: &[&Fix]) -> Result<usize, String> {
         let content =
             fs::read_to_string(file).map_err(|e| format!("Failed to read file: {}", e))?;
+        let mut editor = ConfigEditor::new(content).map_err(|e| e.to_string())?;
 
-        // Sort fixes by start position (reverse order for safe replacement)
-        let mut sorted_fixes = fixes.to_vec();
-        sorted_fixes.sort_by(|a, b| b.start.cmp(&a.start));
-
-        let mut new_content = content;
-        for fix in &sorted_fixes {
-            if fix.start <= new_content.len() && fix.end <= new_content.len() {
-                new_content = format!(
-                    "{}{}{}",
-                    &new_content[..fix.start],
-                    &fix.replacement,
-                    &new_content[fix.end..]
-                );
+        // Edits are addressed by path, so they apply in any order
+        let mut count = 0;
+        for fix in fixes {
+            if editor.apply(&fix.edit).map_err(|e| e.to_string())? {
+                count += 1;
             }
         }
 
-        fs::write(file, new_content).map_err(|e| format!("Failed to write file: {}", e))?;
+        fs::write(file, editor.as_str()).map_err(|e| format!("Failed to write file: {}", e))?;
 
-        Ok(sorted_fixes.len())
+        Ok(count)
     }
 }
 
@@ -130,17 +114,22 @@ mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    fn path(path: &str) -> Vec<String> {
+        path.split('.').map(str::to_string).collect()
+    }
+
     #[test]
     fn applies_simple_fix() {
         let temp = TempDir::new().unwrap();
         let config_path = temp.path().join("config.yml");
-        fs::write(&config_path, "app_name: \"My App\"\n").unwrap();
+        fs::write(&config_path, "app_name: \"My App\"  # shown in output\n").unwrap();
 
         let fix = Fix {
             file: config_path.clone(),
-            start: 10,
-            end: 18,
-            replacement: "\"my-app\"".to_string(),
+            edit: ConfigEdit::Set {
+                path: path("app_name"),
+                value: "my-app".into(),
+            },
         };
 
         let engine = FixEngine::new();
@@ -149,27 +138,33 @@ mod tests {
         assert_eq!(result.applied, 1);
 
         let content = fs::read_to_string(&config_path).unwrap();
-        assert!(content.contains("my-app"));
+        assert_eq!(content, "app_name: my-app  # shown in output\n");
     }
 
     #[test]
     fn applies_multiple_fixes_to_same_file() {
         let temp = TempDir::new().unwrap();
         let config_path = temp.path().join("config.yml");
-        fs::write(&config_path, "aaa bbb ccc").unwrap();
+        fs::write(
+            &config_path,
+            "steps:\n  a:\n    # itself?\n    depends_on: [a, b]\n\nworkflows:\n  default:\n    force: [ghost]\n",
+        )
+        .unwrap();
 
         let fixes = vec![
             Fix {
                 file: config_path.clone(),
-                start: 0,
-                end: 3,
-                replacement: "AAA".to_string(),
+                edit: ConfigEdit::RemoveItem {
+                    path: path("steps.a.depends_on"),
+                    item: "a".to_string(),
+                },
             },
             Fix {
                 file: config_path.clone(),
-                start: 8,
-                end: 11,
-                replacement: "CCC".to_string(),
+                edit: ConfigEdit::RemoveItem {
+                    path: path("workflows.default.force"),
+                    item: "ghost".to_string(),
+                },
             },
         ];
 
@@ -179,7 +174,30 @@ mod tests {
         assert_eq!(result.applied, 2);
 
         let content = fs::read_to_string(&config_path).unwrap();
-        assert_eq!(content, "AAA bbb CCC");
+        assert_eq!(
+            content,
+            "steps:\n  a:\n    # itself?\n    depends_on: [b]\n\nworkflows:\n  default:\n    force: []\n"
+        );
+    }
+
+    #[test]
+    fn skips_fixes_whose_path_is_gone() {
+        let temp = TempDir::new().unwrap();
+        let config_path = temp.path().join("config.yml");
+        fs::write(&config_path, "steps: {}\n").unwrap();
+
+        let fix = Fix {
+            file: config_path.clone(),
+            edit: ConfigEdit::Remove {
+                path: path("steps.missing"),
+            },
+        };
+
+        let engine = FixEngine::new();
+        let result = engine.apply_fixes(&[], &[fix]);
+
+        assert_eq!(result.applied, 0);
+        assert!(result.errors.is_empty());
     }
 
     #[test]
@@ -203,26 +221,26 @@ mod tests {
     fn preview_fixes_returns_descriptions() {
         let fix = Fix {
             file: PathBuf::from("config.yml"),
-            start: 10,
-            end: 20,
-            replacement: "new_value".to_string(),
+            edit: ConfigEdit::Set {
+                path: path("app_name"),
+                value: "new_value".into(),
+            },
         };
 
         let engine = FixEngine::new();
         let previews = engine.preview_fixes(&[fix]);
 
         assert_eq!(previews.len(), 1);
-        assert!(previews[0].contains("config.yml:10-20"));
-        assert!(previews[0].contains("new_value"));
+        assert_eq!(previews[0], "config.yml: set app_name to new_value");
     }
 
     #[test]
     fn handles_file_not_found() {
         let fix = Fix {
             file: PathBuf::from("/nonexistent/path/config.yml"),
-            start: 0,
-            end: 5,
-            replacement: "test".to_string(),
+            edit: ConfigEdit::Remove {
+                path: path("app_name"),
+            },
         };
 
         let engine = FixEngine::new();
@@ -43,9 +43,9 @@ pub use registry::RuleRegistry;
 pub use rule::{LintRule, RuleId, Severity};
 pub use rules::{
     AppNameRule, CircularDependencyRule, CircularRequirementDepRule, DeprecatedFieldsRule,
-    InstallTemplateMissingRule, RequiredFieldsRule, SelfDependencyRule,
+    InstallTemplateMissingRule, InterpolationFiltersRule, RequiredFieldsRule, SelfDependencyRule,
     ServiceRequirementWithoutHintRule, TemplateInputsRule, UndefinedDependencyRule,
-    UndefinedTemplateRule, UnknownRequirementRule,
+    UndefinedDetectorRule, UndefinedTemplateRule, UnknownRequirementRule, UnknownVariableRule,
 };
 pub use schema::{schema_json, schema_value, SchemaGenerator};
 pub use span::Span;
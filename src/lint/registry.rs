@@ -7,13 +7,14 @@ use std::collections::HashMap;
 
 use super::rule::{LintRule, RuleId};
 use super::rules::{
-    AppNameRule, CheckFieldsMutualExclusivityRule, CircularDependencyRule,
+    AppNameRule, CachePathsRule, CheckFieldsMutualExclusivityRule, CircularDependencyRule,
     CircularRequirementDepRule, CustomEnvironmentShadowsBuiltinRule, DeprecatedFieldsRule,
     EnvironmentCircularDependencyRule, EnvironmentDefaultWorkflowMissingRule,
-    InstallTemplateMissingRule, RedundantEnvNullRule, RedundantEnvironmentOverrideRule,
-    RequiredFieldsRule, SelfDependencyRule, ServiceRequirementWithoutHintRule,
-    UndefinedDependencyRule, UndefinedWorkflowForceRule, UnknownEnvironmentInOnlyRule,
-    UnknownEnvironmentInStepRule, UnknownRequirementRule, UnreachableEnvironmentOverrideRule,
+    InstallTemplateMissingRule, InterpolationFiltersRule, RedundantEnvNullRule,
+    RedundantEnvironmentOverrideRule, RequiredFieldsRule, SelfDependencyRule,
+    ServiceRequirementWithoutHintRule, UndefinedDependencyRule, UndefinedWorkflowForceRule,
+    UnknownEnvironmentInOnlyRule, UnknownEnvironmentInStepRule, UnknownRequirementRule,
+    UnreachableEnvironmentOverrideRule, ValidDiagnosticsRule, ValidPromptsRule,
 };
 use crate::requirements::registry::RequirementRegistry;
 
@@ -36,7 +37,8 @@ impl RuleRegistry {
     /// Requirement rules are registered with a default `RequirementRegistry`
     /// (built-ins only); the lint command re-registers them with config-aware
     /// custom requirements.
-    /// Note: Template-related rules (UndefinedTemplateRule, TemplateInputsRule)
+    /// Note: Template-related rules (UndefinedTemplateRule, TemplateInputsRule,
+    /// UnknownVariableRule)
     /// require a Registry and must be registered separately.
     pub fn with_builtins() -> Self {
         let mut registry = Self::new();
@@ -55,7 +57,11 @@ impl RuleRegistry {
         registry.register(Box::new(RedundantEnvNullRule));
         registry.register(Box::new(EnvironmentCircularDependencyRule));
         registry.register(Box::new(CheckFieldsMutualExclusivityRule));
+        registry.register(Box::new(CachePathsRule));
         registry.register(Box::new(DeprecatedFieldsRule));
+        registry.register(Box::new(ValidPromptsRule));
+        registry.register(Box::new(InterpolationFiltersRule));
+        registry.register(Box::new(ValidDiagnosticsRule));
 
         // Requirement rules (registered with default RequirementRegistry;
         // the lint command re-registers with config-aware custom requirements)
@@ -2,7 +2,7 @@
 //!
 //! This rule validates the format and conventions of the app_name field.
 
-use crate::config::BivvyConfig;
+use crate::config::{BivvyConfig, ConfigEdit};
 use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
 
 /// Validates app_name format and conventions.
@@ -36,16 +36,18 @@ impl LintRule for AppNameRule {
                     "app_name cannot be empty",
                 ));
             } else if name.contains(' ') {
+                let kebab = name.to_lowercase().replace(' ', "-");
                 diagnostics.push(
                     LintDiagnostic::new(
                         self.id(),
                         self.default_severity(),
                         "app_name contains spaces; consider using kebab-case",
                     )
-                    .with_suggestion(format!(
-                        "Use \"{}\" instead",
-                        name.to_lowercase().replace(' ', "-")
-                    )),
+                    .with_suggestion(format!("Use \"{}\" instead", kebab))
+                    .with_fix(ConfigEdit::Set {
+                        path: vec!["app_name".to_string()],
+                        value: kebab.into(),
+                    }),
                 );
             }
         }
@@ -81,6 +83,25 @@ mod tests {
             .contains("my-app-name"));
     }
 
+    #[test]
+    fn fix_sets_kebab_case_name() {
+        let rule = AppNameRule;
+        let config = BivvyConfig {
+            app_name: Some("My App".to_string()),
+            ..Default::default()
+        };
+
+        let diagnostics = rule.check(&config);
+
+        assert_eq!(
+            diagnostics[0].fix,
+            Some(ConfigEdit::Set {
+                path: vec!["app_name".to_string()],
+                value: "my-app".into(),
+            })
+        );
+    }
+
     #[test]
     fn errors_on_empty_name() {
         let rule = AppNameRule;
@@ -0,0 +1,96 @@
+//! Validates artifact cache paths.
+//!
+//! Cached `paths` are deleted and replaced when an artifact is restored, so
+//! each must be a relative path inside the project root.
+
+use crate::cache::artifacts::invalid_cache_path;
+use crate::config::BivvyConfig;
+use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
+
+/// Ensures `cache.paths` entries stay inside the project.
+pub struct CachePathsRule;
+
+impl LintRule for CachePathsRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("cache-paths-in-project")
+    }
+
+    fn name(&self) -> &str {
+        "Cache Paths In Project"
+    }
+
+    fn description(&self) -> &str {
+        "Ensures artifact cache paths are relative and inside the project"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let mut names: Vec<_> = config.steps.keys().collect();
+        names.sort();
+        for name in names {
+            let Some(cache) = &config.steps[name].execution.cache else {
+                continue;
+            };
+            for path in &cache.paths {
+                if let Some(problem) = invalid_cache_path(path) {
+                    diagnostics.push(LintDiagnostic::new(
+                        self.id(),
+                        self.default_severity(),
+                        format!(
+                            "Step '{}' caches '{}', which {}. Cache paths must be relative to the project root.",
+                            name, path, problem
+                        ),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ArtifactCacheConfig, StepConfig};
+    use std::collections::HashMap;
+
+    fn messages(paths: &[&str]) -> Vec<String> {
+        let mut step = StepConfig::default();
+        step.execution.cache = Some(ArtifactCacheConfig {
+            key: vec!["yarn.lock".to_string()],
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        });
+        let config = BivvyConfig {
+            steps: HashMap::from([("install".to_string(), step)]),
+            ..Default::default()
+        };
+        CachePathsRule
+            .check(&config)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn relative_paths_pass() {
+        assert!(messages(&["node_modules", "vendor/bundle"]).is_empty());
+    }
+
+    #[test]
+    fn paths_outside_project_are_errors() {
+        assert_eq!(
+            messages(&["/usr/local", "../shared", "."]),
+            vec![
+                "Step 'install' caches '/usr/local', which is absolute. Cache paths must be relative to the project root.",
+                "Step 'install' caches '../shared', which contains '..'. Cache paths must be relative to the project root.",
+                "Step 'install' caches '.', which is the project root. Cache paths must be relative to the project root.",
+            ]
+        );
+    }
+}
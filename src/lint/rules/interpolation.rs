@@ -0,0 +1,418 @@
+//! Interpolation validation.
+//!
+//! Checks `${...}` expressions in step commands, hooks and checks: filter
+//! names must be known, and variables without a `:-` or `:?` fallback should
+//! be defined somewhere in the project rather than left to the environment.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use crate::config::interpolation::{parse_interpolation, Segment, FILTERS};
+use crate::config::load_env_file_optional;
+use crate::config::validator::BUILTIN_VAR_NAMES;
+use crate::config::{BivvyConfig, StepConfig};
+use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
+use crate::registry::Registry;
+
+/// Collect every interpolated string of a step.
+fn step_strings(step: &StepConfig) -> Vec<String> {
+    let mut strings: Vec<String> = step
+        .execution
+        .command
+        .iter()
+        .chain(&step.hooks.before)
+        .chain(&step.hooks.after)
+        .cloned()
+        .collect();
+    strings.extend(
+        step.scoping
+            .environments
+            .values()
+            .filter_map(|o| o.command.clone()),
+    );
+
+    let checks = step
+        .execution
+        .check
+        .iter()
+        .chain(&step.execution.checks)
+        .chain(&step.execution.precondition)
+        .chain(
+            step.scoping
+                .environments
+                .values()
+                .flat_map(|o| o.check.iter().chain(&o.precondition)),
+        )
+        .filter_map(|c| serde_yaml::to_value(c).ok())
+        .chain(
+            step.satisfied_when
+                .iter()
+                .filter_map(|c| serde_yaml::to_value(c).ok()),
+        );
+    for value in checks {
+        collect_yaml_strings(&value, &mut strings);
+    }
+    strings
+}
+
+fn collect_yaml_strings(value: &serde_yaml::Value, strings: &mut Vec<String>) {
+    match value {
+        serde_yaml::Value::String(s) => strings.push(s.clone()),
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                collect_yaml_strings(item, strings);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for item in map.values() {
+                collect_yaml_strings(item, strings);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Iterate steps in name order.
+fn sorted_steps(config: &BivvyConfig) -> Vec<(&String, &StepConfig)> {
+    let mut steps: Vec<_> = config.steps.iter().collect();
+    steps.sort_by(|a, b| a.0.cmp(b.0));
+    steps
+}
+
+/// Ensures interpolation filters are known.
+pub struct InterpolationFiltersRule;
+
+impl LintRule for InterpolationFiltersRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("interpolation-filters")
+    }
+
+    fn name(&self) -> &str {
+        "Interpolation Filters"
+    }
+
+    fn description(&self) -> &str {
+        "Ensures ${name | filter} expressions use known filters"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for (name, step) in sorted_steps(config) {
+            for text in step_strings(step) {
+                for segment in parse_interpolation(&text) {
+                    let Segment::Expression(expr) = segment else {
+                        continue;
+                    };
+                    for filter in expr
+                        .filters
+                        .iter()
+                        .filter(|f| !FILTERS.contains(&f.as_str()))
+                    {
+                        let mut suggestion = format!("Known filters: {}", FILTERS.join(", "));
+                        if expr.fallback.is_some() {
+                            suggestion.push_str(". Write a literal '|' in a fallback as '\\|'");
+                        }
+                        diagnostics.push(
+                            LintDiagnostic::new(
+                                self.id(),
+                                self.default_severity(),
+                                format!(
+                                    "Step '{}' uses unknown filter '{}' on variable '{}'",
+                                    name, filter, expr.name
+                                ),
+                            )
+                            .with_suggestion(suggestion),
+                        );
+                    }
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+/// Warns about interpolated variables the project doesn't define.
+///
+/// A variable is defined when it is a built-in, a `vars:` entry, a prompt
+/// key, a template input of the step, an env var declared in config, or a
+/// key of an `env_file` the step loads. Anything else can only come from
+/// the environment bivvy runs in, which the rule deliberately ignores so
+/// results don't depend on the machine running `bivvy lint`.
+pub struct UnknownVariableRule {
+    registry: Registry,
+    project_root: PathBuf,
+}
+
+impl UnknownVariableRule {
+    /// Create a new unknown variable rule with the given registry, reading
+    /// env files relative to `project_root`.
+    pub fn new(registry: Registry, project_root: &Path) -> Self {
+        Self {
+            registry,
+            project_root: project_root.to_path_buf(),
+        }
+    }
+
+    /// Keys of an env file, or none when it can't be read.
+    fn env_file_keys(&self, path: &Path) -> impl Iterator<Item = String> {
+        load_env_file_optional(&self.project_root.join(path)).into_keys()
+    }
+
+    /// Names every step can reference.
+    fn global_names(&self, config: &BivvyConfig) -> HashSet<String> {
+        let mut names: HashSet<String> = BUILTIN_VAR_NAMES.iter().map(|s| s.to_string()).collect();
+        names.extend(config.vars.keys().cloned());
+        names.extend(config.settings.env_vars.env.keys().cloned());
+        if let Some(path) = &config.settings.env_vars.env_file {
+            names.extend(self.env_file_keys(path));
+        }
+        for workflow in config.workflows.values() {
+            names.extend(workflow.env.keys().cloned());
+            if let Some(path) = &workflow.env_file {
+                names.extend(self.env_file_keys(path));
+            }
+        }
+        for step in config.steps.values() {
+            names.extend(step.output_settings.prompts.iter().map(|p| p.key.clone()));
+        }
+        names
+    }
+
+    /// Names only the given step can reference.
+    fn step_names(&self, step: &StepConfig) -> HashSet<String> {
+        let mut names: HashSet<String> = step.inputs.keys().cloned().collect();
+        names.extend(step.env_vars.env.keys().cloned());
+        if let Some(path) = &step.env_vars.env_file {
+            names.extend(self.env_file_keys(path));
+        }
+        for env_override in step.scoping.environments.values() {
+            names.extend(env_override.env.keys().cloned());
+        }
+        if let Some(template) = step.template.as_deref().and_then(|t| self.registry.get(t)) {
+            names.extend(template.inputs.keys().cloned());
+        }
+        names
+    }
+}
+
+impl LintRule for UnknownVariableRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("unknown-variable")
+    }
+
+    fn name(&self) -> &str {
+        "Unknown Variable"
+    }
+
+    fn description(&self) -> &str {
+        "Warns about interpolated variables left to the environment"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let global = self.global_names(config);
+        for (name, step) in sorted_steps(config) {
+            let local = self.step_names(step);
+
+            let mut reported = HashSet::new();
+            for text in step_strings(step) {
+                for segment in parse_interpolation(&text) {
+                    let var = match segment {
+                        Segment::Variable(var) => var,
+                        Segment::Expression(expr) if expr.fallback.is_none() => expr.name,
+                        _ => continue,
+                    };
+                    if global.contains(&var)
+                        || local.contains(&var)
+                        || !reported.insert(var.clone())
+                    {
+                        continue;
+                    }
+                    diagnostics.push(
+                        LintDiagnostic::new(
+                            self.id(),
+                            self.default_severity(),
+                            format!(
+                                "Step '{}' references '{}', which only the environment can provide",
+                                name, var
+                            ),
+                        )
+                        .with_suggestion(format!(
+                            "Define '{}' under vars or env, or use ${{{}:-default}} or ${{{}:?message}}",
+                            var, var, var
+                        )),
+                    );
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(yaml: &str) -> BivvyConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn unknown_variables_in(project: &Path, config: &BivvyConfig) -> Vec<String> {
+        let registry = Registry::new(Some(project)).unwrap();
+        UnknownVariableRule::new(registry, project)
+            .check(config)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    fn unknown_variables(config: &BivvyConfig) -> Vec<String> {
+        let temp = tempfile::TempDir::new().unwrap();
+        unknown_variables_in(temp.path(), config)
+    }
+
+    #[test]
+    fn unknown_filter_is_reported() {
+        let config = config(
+            r#"
+steps:
+  db:
+    command: "createdb ${project_name | slug | shout}"
+"#,
+        );
+        let diagnostics = InterpolationFiltersRule.check(&config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].message,
+            "Step 'db' uses unknown filter 'shout' on variable 'project_name'"
+        );
+    }
+
+    #[test]
+    fn unknown_filter_after_fallback_suggests_escaping() {
+        let config = config(
+            r#"
+steps:
+  build:
+    command: "make ${mode:-debug|release}"
+"#,
+        );
+        let diagnostics = InterpolationFiltersRule.check(&config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].suggestion.as_deref(),
+            Some(
+                "Known filters: lower, upper, slug, trim, basename, quote. \
+                 Write a literal '|' in a fallback as '\\|'"
+            )
+        );
+    }
+
+    #[test]
+    fn known_filters_pass() {
+        let config = config(
+            r#"
+steps:
+  db:
+    command: "createdb ${project_name | lower | slug}"
+"#,
+        );
+        assert!(InterpolationFiltersRule.check(&config).is_empty());
+    }
+
+    #[test]
+    fn unknown_variable_is_reported_once() {
+        let config = config(
+            r#"
+steps:
+  deploy:
+    command: "deploy ${region} ${region}"
+"#,
+        );
+        assert_eq!(
+            unknown_variables(&config),
+            vec!["Step 'deploy' references 'region', which only the environment can provide"]
+        );
+    }
+
+    #[test]
+    fn process_env_does_not_define_variables() {
+        let config = config(
+            r#"
+steps:
+  build:
+    command: "ls ${PATH}"
+"#,
+        );
+        let temp = tempfile::TempDir::new().unwrap();
+        let registry = Registry::new(Some(temp.path())).unwrap();
+        let diagnostics = UnknownVariableRule::new(registry, temp.path()).check(&config);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn defined_and_fallback_variables_pass() {
+        let config = config(
+            r#"
+vars:
+  region: us-east-1
+settings:
+  env:
+    APP_ENV: dev
+steps:
+  deploy:
+    command: "deploy ${region} ${APP_ENV} ${project_name} ${version}"
+    env:
+      STEP_ONLY: "1"
+    before: ["echo ${STEP_ONLY} ${host:-localhost}"]
+    prompts:
+      - key: version
+        question: Version?
+        type: input
+    check:
+      type: presence
+      target: "${dir:?set the output dir}/app"
+"#,
+        );
+        assert!(unknown_variables(&config).is_empty());
+    }
+
+    #[test]
+    fn env_file_keys_define_variables() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join(".env"), "APP_HOST=localhost\n").unwrap();
+        std::fs::write(temp.path().join(".env.deploy"), "TOKEN=abc\n").unwrap();
+        let config = config(
+            r#"
+settings:
+  env_file: .env
+steps:
+  deploy:
+    command: "deploy ${TOKEN} ${APP_HOST} ${REGION}"
+    env_file: .env.deploy
+  build:
+    command: "build ${TOKEN}"
+"#,
+        );
+        assert_eq!(
+            unknown_variables_in(temp.path(), &config),
+            vec![
+                "Step 'build' references 'TOKEN', which only the environment can provide",
+                "Step 'deploy' references 'REGION', which only the environment can provide",
+            ]
+        );
+    }
+}
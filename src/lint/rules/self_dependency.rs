@@ -2,7 +2,7 @@
 //!
 //! This rule detects steps that depend on themselves.
 
-use crate::config::BivvyConfig;
+use crate::config::{BivvyConfig, ConfigEdit};
 use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
 
 /// Detects steps that depend on themselves.
@@ -30,16 +30,30 @@ impl LintRule for SelfDependencyRule {
 
         for (step_name, step_config) in &config.steps {
             if step_config.depends_on.contains(step_name) {
-                diagnostics.push(LintDiagnostic::new(
-                    self.id(),
-                    self.default_severity(),
-                    format!("Step '{}' depends on itself", step_name),
-                ));
+                diagnostics.push(
+                    LintDiagnostic::new(
+                        self.id(),
+                        self.default_severity(),
+                        format!("Step '{}' depends on itself", step_name),
+                    )
+                    .with_fix(ConfigEdit::RemoveItem {
+                        path: vec![
+                            "steps".to_string(),
+                            step_name.clone(),
+                            "depends_on".to_string(),
+                        ],
+                        item: step_name.clone(),
+                    }),
+                );
             }
         }
 
         diagnostics
     }
+
+    fn supports_fix(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
@@ -73,6 +87,13 @@ mod tests {
 
         assert_eq!(diagnostics.len(), 1);
         assert!(diagnostics[0].message.contains("depends on itself"));
+        assert_eq!(
+            diagnostics[0].fix,
+            Some(ConfigEdit::RemoveItem {
+                path: vec!["steps".into(), "a".into(), "depends_on".into()],
+                item: "a".into(),
+            })
+        );
     }
 
     #[test]
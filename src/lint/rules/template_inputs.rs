@@ -307,6 +307,11 @@ step:
                         prompt_type: PromptType::Input,
                         options: vec![],
                         default: None,
+                        min: None,
+                        max: None,
+                        pattern: None,
+                        pattern_message: None,
+                        must_be: None,
                     }],
                     ..Default::default()
                 },
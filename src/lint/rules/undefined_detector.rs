@@ -0,0 +1,165 @@
+//! Undefined detector validation.
+//!
+//! This rule validates that the detectors referenced by a step's template
+//! resolve against the merged project, user, remote and built-in detectors,
+//! and warns when one detector definition shadows another.
+
+use crate::config::BivvyConfig;
+use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
+use crate::registry::Registry;
+
+/// Validates that template detector references resolve.
+pub struct UndefinedDetectorRule {
+    registry: Registry,
+}
+
+impl UndefinedDetectorRule {
+    /// Create a new undefined detector rule with the given registry.
+    pub fn new(registry: Registry) -> Self {
+        Self { registry }
+    }
+}
+
+impl LintRule for UndefinedDetectorRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("undefined-detector")
+    }
+
+    fn name(&self) -> &str {
+        "Undefined Detector"
+    }
+
+    fn description(&self) -> &str {
+        "Ensures template detector references exist in the merged detectors"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+        let detectors = self.registry.detectors();
+
+        let mut step_names: Vec<&String> = config.steps.keys().collect();
+        step_names.sort();
+        for step_name in step_names {
+            let Some(ref template_name) = config.steps[step_name].template else {
+                continue;
+            };
+            let Some(template) = self.registry.get(template_name) else {
+                continue;
+            };
+            for reference in &template.detectors {
+                if let Err(e) = detectors.check(reference) {
+                    diagnostics.push(LintDiagnostic::new(
+                        self.id(),
+                        self.default_severity(),
+                        format!(
+                            "Step '{}' uses template '{}' whose detector '{}' does not resolve: {}",
+                            step_name, template_name, reference, e
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for warning in self.registry.detector_warnings() {
+            diagnostics.push(LintDiagnostic::new(
+                self.id(),
+                Severity::Warning,
+                warning.clone(),
+            ));
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StepConfig;
+    use std::collections::HashMap;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn project(detectors_yaml: &str, template_detectors: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(bivvy_dir.join("templates/steps")).unwrap();
+        fs::write(bivvy_dir.join("detectors.yml"), detectors_yaml).unwrap();
+        fs::write(
+            bivvy_dir.join("templates/steps/platform-lint.yml"),
+            format!(
+                "name: platform-lint\n\
+                 description: Lint\n\
+                 category: platform\n\
+                 detectors: {}\n\
+                 step:\n  command: platform lint\n",
+                template_detectors
+            ),
+        )
+        .unwrap();
+        temp
+    }
+
+    fn config() -> BivvyConfig {
+        let mut steps = HashMap::new();
+        steps.insert(
+            "lint".to_string(),
+            StepConfig {
+                template: Some("platform-lint".to_string()),
+                ..Default::default()
+            },
+        );
+        BivvyConfig {
+            steps,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn passes_with_project_detector() {
+        let temp = project(
+            "detectors:\n  platform-config:\n    file: platform.yml\n",
+            "[platform-config.file]",
+        );
+        let rule = UndefinedDetectorRule::new(Registry::new(Some(temp.path())).unwrap());
+
+        assert!(rule.check(&config()).is_empty());
+    }
+
+    #[test]
+    fn passes_with_builtin_detector() {
+        let temp = project("detectors: {}\n", "[cargo-toml-present]");
+        let rule = UndefinedDetectorRule::new(Registry::new(Some(temp.path())).unwrap());
+
+        assert!(rule.check(&config()).is_empty());
+    }
+
+    #[test]
+    fn detects_undefined_detector() {
+        let temp = project("detectors: {}\n", "[platform-config.file]");
+        let rule = UndefinedDetectorRule::new(Registry::new(Some(temp.path())).unwrap());
+
+        let diagnostics = rule.check(&config());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].message.contains("platform-config.file"));
+    }
+
+    #[test]
+    fn warns_when_project_detector_overrides_builtin() {
+        let temp = project(
+            "detectors:\n  cargo-toml-present:\n    file: Cargo.toml\n",
+            "[cargo-toml-present]",
+        );
+        let rule = UndefinedDetectorRule::new(Registry::new(Some(temp.path())).unwrap());
+
+        let diagnostics = rule.check(&config());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+        assert!(diagnostics[0].message.contains("'cargo-toml-present'"));
+    }
+}
@@ -3,7 +3,7 @@
 //! This rule detects references to undefined steps in a workflow's
 //! `force` list.
 
-use crate::config::BivvyConfig;
+use crate::config::{BivvyConfig, ConfigEdit};
 use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
 
 /// Detects references to undefined steps in workflow `force` lists.
@@ -32,20 +32,34 @@ impl LintRule for UndefinedWorkflowForceRule {
         for (workflow_name, workflow) in &config.workflows {
             for step_name in &workflow.force {
                 if !config.steps.contains_key(step_name) {
-                    diagnostics.push(LintDiagnostic::new(
-                        self.id(),
-                        self.default_severity(),
-                        format!(
-                            "Workflow '{}' force list references undefined step '{}'",
-                            workflow_name, step_name
-                        ),
-                    ));
+                    diagnostics.push(
+                        LintDiagnostic::new(
+                            self.id(),
+                            self.default_severity(),
+                            format!(
+                                "Workflow '{}' force list references undefined step '{}'",
+                                workflow_name, step_name
+                            ),
+                        )
+                        .with_fix(ConfigEdit::RemoveItem {
+                            path: vec![
+                                "workflows".to_string(),
+                                workflow_name.clone(),
+                                "force".to_string(),
+                            ],
+                            item: step_name.clone(),
+                        }),
+                    );
                 }
             }
         }
 
         diagnostics
     }
+
+    fn supports_fix(&self) -> bool {
+        true
+    }
 }
 
 #[cfg(test)]
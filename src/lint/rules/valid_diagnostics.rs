@@ -0,0 +1,142 @@
+//! Validates project-defined diagnostic rules.
+//!
+//! Checks that each rule's `pattern` is a valid regex, that `confidence` is
+//! between 0.0 and 1.0, and that `{N}` placeholders in `fix` and `label`
+//! refer to capture groups the pattern actually defines.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::config::{BivvyConfig, DiagnosticRule};
+use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
+
+static PLACEHOLDER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{(\d+)\}").unwrap());
+
+/// Ensures diagnostic rules compile and reference valid capture groups.
+pub struct ValidDiagnosticsRule;
+
+impl ValidDiagnosticsRule {
+    fn problems(rule: &DiagnosticRule) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if !(0.0..=1.0).contains(&rule.confidence) {
+            problems.push(format!(
+                "has confidence {} outside the range 0.0 to 1.0",
+                rule.confidence
+            ));
+        }
+
+        let regex = match Regex::new(&rule.pattern) {
+            Ok(regex) => regex,
+            Err(e) => {
+                problems.push(format!("has an invalid pattern: {}", e));
+                return problems;
+            }
+        };
+
+        // captures_len includes the implicit whole-match group 0
+        let groups = regex.captures_len() - 1;
+        for (field, value) in [("fix", &rule.fix), ("label", &rule.label)] {
+            let Some(value) = value else { continue };
+            for caps in PLACEHOLDER.captures_iter(value) {
+                let index: usize = caps[1].parse().unwrap_or(usize::MAX);
+                if index == 0 || index > groups {
+                    problems.push(format!(
+                        "references {{{}}} in {} but the pattern has {} capture group(s)",
+                        &caps[1], field, groups
+                    ));
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+impl LintRule for ValidDiagnosticsRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("valid-diagnostics")
+    }
+
+    fn name(&self) -> &str {
+        "Valid Diagnostics"
+    }
+
+    fn description(&self) -> &str {
+        "Ensures diagnostic rule patterns compile and reference valid capture groups"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let mut names: Vec<_> = config.diagnostics.keys().collect();
+        names.sort();
+        for name in names {
+            for problem in Self::problems(&config.diagnostics[name]) {
+                diagnostics.push(LintDiagnostic::new(
+                    self.id(),
+                    self.default_severity(),
+                    format!("Diagnostic rule '{}' {}", name, problem),
+                ));
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn messages(yaml: &str) -> Vec<String> {
+        let mut diagnostics = HashMap::new();
+        diagnostics.insert("vpn".to_string(), serde_yaml::from_str(yaml).unwrap());
+        let config = BivvyConfig {
+            diagnostics,
+            ..Default::default()
+        };
+        ValidDiagnosticsRule
+            .check(&config)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_rule_passes() {
+        let messages = messages(
+            "pattern: 'host (\\S+) unreachable'\nexplanation: Connect the VPN\nfix: vpn up {1}\n",
+        );
+        assert!(messages.is_empty());
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let messages = messages("pattern: '[a-'\nexplanation: x\n");
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("Diagnostic rule 'vpn' has an invalid pattern"));
+    }
+
+    #[test]
+    fn missing_capture_group_is_reported() {
+        assert_eq!(
+            messages("pattern: 'VPN (down)'\nexplanation: x\nfix: vpn up {2}\n"),
+            vec!["Diagnostic rule 'vpn' references {2} in fix but the pattern has 1 capture group(s)"]
+        );
+    }
+
+    #[test]
+    fn out_of_range_confidence_is_reported() {
+        assert_eq!(
+            messages("pattern: 'VPN'\nexplanation: x\nconfidence: 1.5\n"),
+            vec!["Diagnostic rule 'vpn' has confidence 1.5 outside the range 0.0 to 1.0"]
+        );
+    }
+}
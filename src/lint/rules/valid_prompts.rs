@@ -0,0 +1,139 @@
+//! Validates prompt constraints on steps.
+//!
+//! Checks that `pattern` is a valid regex, that `min` does not exceed `max`,
+//! and that constraint fields are only used with prompt types that honor them.
+
+use crate::config::schema::{PromptConfig, PromptType};
+use crate::config::BivvyConfig;
+use crate::lint::{LintDiagnostic, LintRule, RuleId, Severity};
+
+/// Ensures prompt constraints are well-formed and match the prompt type.
+pub struct ValidPromptsRule;
+
+impl ValidPromptsRule {
+    fn problems(prompt: &PromptConfig) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if let Some(pattern) = &prompt.pattern {
+            if let Err(e) = regex::Regex::new(pattern) {
+                problems.push(format!("has an invalid pattern: {}", e));
+            }
+        }
+        if let (Some(min), Some(max)) = (prompt.min, prompt.max) {
+            if min > max {
+                problems.push(format!("has min ({}) greater than max ({})", min, max));
+            }
+        }
+
+        let kind = &prompt.prompt_type;
+        if (prompt.min.is_some() || prompt.max.is_some()) && !matches!(kind, PromptType::Number) {
+            problems.push("sets min/max but is not a number prompt".to_string());
+        }
+        if prompt.pattern.is_some() && !matches!(kind, PromptType::Input) {
+            problems.push("sets pattern but is not an input prompt".to_string());
+        }
+        if prompt.must_be.is_some() && !matches!(kind, PromptType::Path) {
+            problems.push("sets must_be but is not a path prompt".to_string());
+        }
+
+        problems
+    }
+}
+
+impl LintRule for ValidPromptsRule {
+    fn id(&self) -> RuleId {
+        RuleId::new("valid-prompts")
+    }
+
+    fn name(&self) -> &str {
+        "Valid Prompts"
+    }
+
+    fn description(&self) -> &str {
+        "Ensures prompt constraints are well-formed and match the prompt type"
+    }
+
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+
+    fn check(&self, config: &BivvyConfig) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        let mut names: Vec<_> = config.steps.keys().collect();
+        names.sort();
+        for name in names {
+            for prompt in &config.steps[name].output_settings.prompts {
+                for problem in Self::problems(prompt) {
+                    diagnostics.push(LintDiagnostic::new(
+                        self.id(),
+                        self.default_severity(),
+                        format!("Prompt '{}' in step '{}' {}", prompt.key, name, problem),
+                    ));
+                }
+            }
+        }
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StepConfig;
+    use std::collections::HashMap;
+
+    fn config_with_prompt(yaml: &str) -> BivvyConfig {
+        let mut step = StepConfig::default();
+        step.output_settings.prompts = vec![serde_yaml::from_str(yaml).unwrap()];
+        let mut steps = HashMap::new();
+        steps.insert("setup".to_string(), step);
+        BivvyConfig {
+            steps,
+            ..Default::default()
+        }
+    }
+
+    fn messages(config: &BivvyConfig) -> Vec<String> {
+        ValidPromptsRule
+            .check(config)
+            .into_iter()
+            .map(|d| d.message)
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_prompts_pass() {
+        let config =
+            config_with_prompt("key: port\nquestion: Port?\ntype: number\nmin: 1\nmax: 65535\n");
+        assert!(messages(&config).is_empty());
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let config =
+            config_with_prompt("key: slug\nquestion: Slug?\ntype: input\npattern: '[a-'\n");
+        let messages = messages(&config);
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].starts_with("Prompt 'slug' in step 'setup' has an invalid pattern"));
+    }
+
+    #[test]
+    fn min_above_max_is_reported() {
+        let config = config_with_prompt("key: n\nquestion: N?\ntype: number\nmin: 5\nmax: 1\n");
+        assert_eq!(
+            messages(&config),
+            vec!["Prompt 'n' in step 'setup' has min (5) greater than max (1)"]
+        );
+    }
+
+    #[test]
+    fn constraint_on_wrong_type_is_reported() {
+        let config = config_with_prompt("key: dir\nquestion: Dir?\ntype: input\nmust_be: dir\n");
+        assert_eq!(
+            messages(&config),
+            vec!["Prompt 'dir' in step 'setup' sets must_be but is not a path prompt"]
+        );
+    }
+}
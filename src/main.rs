@@ -84,6 +84,11 @@ fn main() -> ExitCode {
         std::env::set_var("NO_COLOR", "1");
     }
 
+    // Handle --offline (exported so background processes inherit it)
+    if cli.offline {
+        bivvy::cache::offline::enable();
+    }
+
     // Determine project root
     let project_root = cli
         .project
@@ -91,9 +96,11 @@ fn main() -> ExitCode {
         .cloned()
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
-    // Check if non-interactive (CI mode or explicit flag)
+    // Check if non-interactive (CI mode or explicit flag). Stale checks
+    // run from shell prompt hooks, so they never prompt or spawn updates.
     let is_interactive = match &cli.command {
         Some(Commands::Run(args)) => !args.non_interactive && !args.ci && !is_ci(),
+        Some(Commands::Status(args)) if args.stale => false,
         _ => !is_ci(),
     };
 
@@ -497,6 +497,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn all_template_detector_references_resolve() {
+        let detectors = load_detectors().unwrap();
+        let templates = load_templates().unwrap();
+        for (name, template) in &templates {
+            for reference in &template.detectors {
+                let parsed = super::super::DetectorRef::parse(reference).unwrap_or_else(|e| {
+                    panic!("Template '{}' has invalid detector ref: {}", name, e)
+                });
+                let def = detectors
+                    .detectors
+                    .get(&parsed.detector)
+                    .unwrap_or_else(|| {
+                        panic!(
+                            "Template '{}' references unknown detector '{}'",
+                            name, reference
+                        )
+                    });
+                if let Some(group) = &parsed.group {
+                    assert!(
+                        !def.group_names(group).is_empty(),
+                        "Template '{}' references empty detector group '{}'",
+                        name,
+                        reference
+                    );
+                }
+            }
+        }
+    }
+
     #[test]
     fn all_template_name_variants_resolve_in_registry() {
         let loader = BuiltinLoader::new().unwrap();
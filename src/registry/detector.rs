@@ -14,15 +14,23 @@
 //! - `rails-project.files.application` — check a specific named file
 //! - `gemfile-present.command` — singular command shorthand
 //! - `gemfile-present.file` — singular file shorthand
+//! - `rails-project.regex.gemfile` — a named content check; `contains`,
+//!   `regex`, `json_path` and `globs` groups work like `files`
 //!
 //! # Evaluation Semantics
 //!
 //! - **commands group** (plural): ANY must succeed (OR)
 //! - **files group** (plural): ANY must exist (OR)
+//! - **contains group**: ANY file must contain its text (OR)
+//! - **regex group**: ANY file must match its pattern (OR)
+//! - **json_path group**: ANY `file#key.path` must exist (OR). JSON, TOML
+//!   and YAML files are parsed based on their extension
+//! - **globs group**: ANY pattern must match at least `min` paths (OR)
 //! - **single command**: must succeed
 //! - **single file**: must exist
 //! - **bare detector name**: ANY check across all groups passes (OR)
 
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -31,14 +39,22 @@ use std::path::Path;
 use crate::detection::command_detection::command_succeeds;
 use crate::detection::file_detection::file_exists;
 use crate::error::{BivvyError, Result};
+use crate::registry::manifest::Category;
 
 /// Top-level detectors file structure.
 ///
-/// Corresponds to the `templates/detectors.yml` file format.
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// Corresponds to the `templates/detectors.yml` file format. Project, user
+/// and remote detector files may also carry `registry.yml`-style
+/// `categories`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct DetectorFile {
     /// Map of detector name to definition.
+    #[serde(default)]
     pub detectors: HashMap<String, DetectorDef>,
+
+    /// Template categories contributed alongside the detectors.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<Category>,
 }
 
 /// A single detector definition.
@@ -46,8 +62,9 @@ pub struct DetectorFile {
 /// Supports both singular (`command`/`file`) and plural (`commands`/`files`)
 /// forms. Singular values are merged into the plural maps with the key
 /// `"default"` when accessed via [`all_commands`](DetectorDef::all_commands)
-/// or [`all_files`](DetectorDef::all_files).
-#[derive(Debug, Clone, Deserialize, Serialize)]
+/// or [`all_files`](DetectorDef::all_files). Content checks (`contains`,
+/// `regex`, `json_path`, `globs`) only have a named, plural form.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct DetectorDef {
     /// Singular command shorthand.
     #[serde(default)]
@@ -64,6 +81,54 @@ pub struct DetectorDef {
     /// Named files map.
     #[serde(default)]
     pub files: HashMap<String, String>,
+
+    /// Named substring checks against file contents.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub contains: HashMap<String, ContainsCheck>,
+
+    /// Named regex checks against file contents.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub regex: HashMap<String, RegexCheck>,
+
+    /// Named key path checks, as `file#dotted.key.path`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub json_path: HashMap<String, String>,
+
+    /// Named glob checks with a minimum match count.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub globs: HashMap<String, GlobCheck>,
+}
+
+/// A file that must contain a substring.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ContainsCheck {
+    /// File path, relative to the project root.
+    pub file: String,
+    /// Text the file must contain.
+    pub text: String,
+}
+
+/// A file whose contents must match a regex.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct RegexCheck {
+    /// File path, relative to the project root.
+    pub file: String,
+    /// Regex matched against the whole file, with multi-line `^` and `$`.
+    pub pattern: String,
+}
+
+/// A glob that must match a minimum number of paths.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct GlobCheck {
+    /// Glob pattern, relative to the project root.
+    pub pattern: String,
+    /// Minimum number of matching paths (default 1).
+    #[serde(default = "default_glob_min")]
+    pub min: usize,
+}
+
+fn default_glob_min() -> usize {
+    1
 }
 
 impl DetectorDef {
@@ -96,6 +161,28 @@ impl DetectorDef {
             || !self.commands.is_empty()
             || self.file.is_some()
             || !self.files.is_empty()
+            || !self.contains.is_empty()
+            || !self.regex.is_empty()
+            || !self.json_path.is_empty()
+            || !self.globs.is_empty()
+    }
+
+    /// Names of the entries in a plural group, sorted.
+    ///
+    /// Singular `command`/`file` values appear as `"default"`.
+    pub fn group_names(&self, group: &DetectorGroup) -> Vec<String> {
+        let mut names: Vec<String> = match group {
+            DetectorGroup::Commands | DetectorGroup::Command => {
+                self.all_commands().into_keys().collect()
+            }
+            DetectorGroup::Files | DetectorGroup::File => self.all_files().into_keys().collect(),
+            DetectorGroup::Contains => self.contains.keys().cloned().collect(),
+            DetectorGroup::Regex => self.regex.keys().cloned().collect(),
+            DetectorGroup::JsonPath => self.json_path.keys().cloned().collect(),
+            DetectorGroup::Globs => self.globs.keys().cloned().collect(),
+        };
+        names.sort();
+        names
     }
 }
 
@@ -123,6 +210,43 @@ pub enum DetectorGroup {
     Command,
     /// Singular file shorthand.
     File,
+    /// File substring checks (OR semantics).
+    Contains,
+    /// File regex checks (OR semantics).
+    Regex,
+    /// Structured key path checks (OR semantics).
+    JsonPath,
+    /// Glob match-count checks (OR semantics).
+    Globs,
+}
+
+impl DetectorGroup {
+    /// The group name as written in references.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DetectorGroup::Commands => "commands",
+            DetectorGroup::Files => "files",
+            DetectorGroup::Command => "command",
+            DetectorGroup::File => "file",
+            DetectorGroup::Contains => "contains",
+            DetectorGroup::Regex => "regex",
+            DetectorGroup::JsonPath => "json_path",
+            DetectorGroup::Globs => "globs",
+        }
+    }
+
+    /// Parse a plural group name, which may be followed by a specific name.
+    fn parse_plural(s: &str) -> Option<Self> {
+        match s {
+            "commands" => Some(DetectorGroup::Commands),
+            "files" => Some(DetectorGroup::Files),
+            "contains" => Some(DetectorGroup::Contains),
+            "regex" => Some(DetectorGroup::Regex),
+            "json_path" => Some(DetectorGroup::JsonPath),
+            "globs" => Some(DetectorGroup::Globs),
+            _ => None,
+        }
+    }
 }
 
 impl DetectorRef {
@@ -142,18 +266,16 @@ impl DetectorRef {
             }),
             2 => {
                 let group = match parts[1] {
-                    "commands" => DetectorGroup::Commands,
-                    "files" => DetectorGroup::Files,
                     "command" => DetectorGroup::Command,
                     "file" => DetectorGroup::File,
-                    other => {
-                        return Err(BivvyError::ConfigValidationError {
+                    other => DetectorGroup::parse_plural(other).ok_or_else(|| {
+                        BivvyError::ConfigValidationError {
                             message: format!(
-                                "Invalid detector group '{}' in '{}'. Expected: commands, files, command, file",
+                                "Invalid detector group '{}' in '{}'. Expected: commands, files, command, file, contains, regex, json_path, globs",
                                 other, s
                             ),
-                        })
-                    }
+                        }
+                    })?,
                 };
                 Ok(DetectorRef {
                     detector: parts[0].to_string(),
@@ -162,18 +284,14 @@ impl DetectorRef {
                 })
             }
             3 => {
-                let group = match parts[1] {
-                    "commands" => DetectorGroup::Commands,
-                    "files" => DetectorGroup::Files,
-                    other => {
-                        return Err(BivvyError::ConfigValidationError {
-                            message: format!(
-                                "Invalid detector group '{}' in '{}'. Specific references require 'commands' or 'files'",
-                                other, s
-                            ),
-                        })
+                let group = DetectorGroup::parse_plural(parts[1]).ok_or_else(|| {
+                    BivvyError::ConfigValidationError {
+                        message: format!(
+                            "Invalid detector group '{}' in '{}'. Specific references require a plural group (commands, files, contains, regex, json_path, globs)",
+                            parts[1], s
+                        ),
                     }
-                };
+                })?;
                 Ok(DetectorRef {
                     detector: parts[0].to_string(),
                     group: Some(group),
@@ -247,12 +365,29 @@ impl DetectorRegistry {
         })?;
 
         let (passed, details) = match (&parsed.group, &parsed.specific) {
-            // Bare name: ANY check across all groups passes (OR)
+            // Bare name: ANY check across all non-empty groups passes (OR)
             (None, None) => {
-                let (cmds_pass, mut details) = self.eval_commands_group(def);
-                let (files_pass, file_details) = self.eval_files_group(def, project_root);
-                details.extend(file_details);
-                (cmds_pass || files_pass, details)
+                let groups = [
+                    DetectorGroup::Commands,
+                    DetectorGroup::Files,
+                    DetectorGroup::Contains,
+                    DetectorGroup::Regex,
+                    DetectorGroup::JsonPath,
+                    DetectorGroup::Globs,
+                ];
+                let mut details = Vec::new();
+                let mut any_pass = false;
+                let mut any_checks = false;
+                for group in &groups {
+                    if def.group_names(group).is_empty() {
+                        continue;
+                    }
+                    any_checks = true;
+                    let (pass, group_details) = self.eval_group(def, group, project_root);
+                    any_pass |= pass;
+                    details.extend(group_details);
+                }
+                (any_pass || !any_checks, details)
             }
 
             // commands group (any must pass)
@@ -261,6 +396,17 @@ impl DetectorRegistry {
             // files group (any must exist)
             (Some(DetectorGroup::Files), None) => self.eval_files_group(def, project_root),
 
+            // content groups (any must match)
+            (
+                Some(
+                    group @ (DetectorGroup::Contains
+                    | DetectorGroup::Regex
+                    | DetectorGroup::JsonPath
+                    | DetectorGroup::Globs),
+                ),
+                None,
+            ) => self.eval_group(def, group, project_root),
+
             // command singular shorthand
             (Some(DetectorGroup::Command), None) => {
                 self.eval_command_singular(def, &parsed.detector)?
@@ -281,6 +427,29 @@ impl DetectorRegistry {
                 self.eval_specific_file(def, &parsed.detector, name, project_root)?
             }
 
+            // specific content check
+            (
+                Some(
+                    group @ (DetectorGroup::Contains
+                    | DetectorGroup::Regex
+                    | DetectorGroup::JsonPath
+                    | DetectorGroup::Globs),
+                ),
+                Some(name),
+            ) => {
+                let (pass, detail) = self
+                    .eval_content_check(def, group, name, project_root)
+                    .ok_or_else(|| BivvyError::ConfigValidationError {
+                        message: format!(
+                            "Detector '{}' has no {} check named '{}'",
+                            parsed.detector,
+                            group.as_str(),
+                            name
+                        ),
+                    })?;
+                (pass, vec![detail])
+            }
+
             // singular shorthands can't have specifics
             (Some(DetectorGroup::Command), Some(_)) | (Some(DetectorGroup::File), Some(_)) => {
                 return Err(BivvyError::ConfigValidationError {
@@ -306,6 +475,109 @@ impl DetectorRegistry {
         })
     }
 
+    /// Check that a reference resolves to a non-empty detector facet,
+    /// without running any checks.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference is malformed or names an unknown
+    /// detector, group or check.
+    pub fn check(&self, reference: &str) -> Result<()> {
+        let parsed = DetectorRef::parse(reference)?;
+        let def = self.detectors.get(&parsed.detector).ok_or_else(|| {
+            BivvyError::ConfigValidationError {
+                message: format!("Unknown detector: '{}'", parsed.detector),
+            }
+        })?;
+
+        let Some(group) = &parsed.group else {
+            return Ok(());
+        };
+        let missing = match (group, &parsed.specific) {
+            (DetectorGroup::Command, _) => def.command.is_none(),
+            (DetectorGroup::File, _) => def.file.is_none(),
+            (_, Some(name)) => !def.group_names(group).contains(name),
+            (_, None) => def.group_names(group).is_empty(),
+        };
+        if missing {
+            return Err(BivvyError::ConfigValidationError {
+                message: format!(
+                    "Detector '{}' has no check at '{}'",
+                    parsed.detector, reference
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Files and glob patterns a detector reference reads.
+    ///
+    /// Command checks read no files and are left out. Malformed or
+    /// unknown references yield no paths.
+    pub fn watched_paths(&self, reference: &str) -> Vec<String> {
+        let Ok(parsed) = DetectorRef::parse(reference) else {
+            return Vec::new();
+        };
+        let Some(def) = self.detectors.get(&parsed.detector) else {
+            return Vec::new();
+        };
+        let groups = match parsed.group {
+            Some(group) => vec![group],
+            None => vec![
+                DetectorGroup::Files,
+                DetectorGroup::Contains,
+                DetectorGroup::Regex,
+                DetectorGroup::JsonPath,
+                DetectorGroup::Globs,
+            ],
+        };
+        let specific = parsed.specific.as_deref();
+        let wanted = |name: &String| specific.is_none_or(|s| s == name);
+
+        let mut paths: Vec<String> = groups
+            .iter()
+            .flat_map(|group| -> Vec<String> {
+                match group {
+                    DetectorGroup::Commands | DetectorGroup::Command => Vec::new(),
+                    DetectorGroup::File => def.file.iter().cloned().collect(),
+                    DetectorGroup::Files => def
+                        .all_files()
+                        .into_iter()
+                        .filter(|(name, _)| wanted(name))
+                        .map(|(_, file)| file)
+                        .collect(),
+                    DetectorGroup::Contains => def
+                        .contains
+                        .iter()
+                        .filter(|(name, _)| wanted(name))
+                        .map(|(_, check)| check.file.clone())
+                        .collect(),
+                    DetectorGroup::Regex => def
+                        .regex
+                        .iter()
+                        .filter(|(name, _)| wanted(name))
+                        .map(|(_, check)| check.file.clone())
+                        .collect(),
+                    DetectorGroup::JsonPath => def
+                        .json_path
+                        .iter()
+                        .filter(|(name, _)| wanted(name))
+                        .map(|(_, spec)| spec.split('#').next().unwrap_or(spec).to_string())
+                        .collect(),
+                    DetectorGroup::Globs => def
+                        .globs
+                        .iter()
+                        .filter(|(name, _)| wanted(name))
+                        .map(|(_, check)| check.pattern.clone())
+                        .collect(),
+                }
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+        paths
+    }
+
     /// Evaluate multiple detector references. Returns true if ALL pass.
     pub fn evaluate_all(&self, references: &[String], project_root: &Path) -> Result<bool> {
         for reference in references {
@@ -319,6 +591,115 @@ impl DetectorRegistry {
 
     // --- Private evaluation helpers ---
 
+    /// Evaluate a plural group with OR semantics. An empty group passes.
+    fn eval_group(
+        &self,
+        def: &DetectorDef,
+        group: &DetectorGroup,
+        project_root: &Path,
+    ) -> (bool, Vec<String>) {
+        match group {
+            DetectorGroup::Commands | DetectorGroup::Command => self.eval_commands_group(def),
+            DetectorGroup::Files | DetectorGroup::File => self.eval_files_group(def, project_root),
+            _ => {
+                let names = def.group_names(group);
+                if names.is_empty() {
+                    return (true, Vec::new());
+                }
+                let mut details = Vec::new();
+                let mut any_pass = false;
+                for name in &names {
+                    if let Some((pass, detail)) =
+                        self.eval_content_check(def, group, name, project_root)
+                    {
+                        any_pass |= pass;
+                        details.push(detail);
+                    }
+                }
+                (any_pass, details)
+            }
+        }
+    }
+
+    /// Evaluate one named content check. Returns `None` if it doesn't exist.
+    fn eval_content_check(
+        &self,
+        def: &DetectorDef,
+        group: &DetectorGroup,
+        name: &str,
+        project_root: &Path,
+    ) -> Option<(bool, String)> {
+        let (key, description) = match group {
+            DetectorGroup::Contains => {
+                let check = def.contains.get(name)?;
+                (
+                    format!("contains:{}:{}", check.file, check.text),
+                    format!("{} contains '{}'", check.file, check.text),
+                )
+            }
+            DetectorGroup::Regex => {
+                let check = def.regex.get(name)?;
+                (
+                    format!("regex:{}:{}", check.file, check.pattern),
+                    format!("{} matches /{}/", check.file, check.pattern),
+                )
+            }
+            DetectorGroup::JsonPath => {
+                let path = def.json_path.get(name)?;
+                (format!("json_path:{}", path), path.clone())
+            }
+            DetectorGroup::Globs => {
+                let check = def.globs.get(name)?;
+                (
+                    format!("globs:{}:{}", check.pattern, check.min),
+                    format!("{} matches at least {}", check.pattern, check.min),
+                )
+            }
+            _ => return None,
+        };
+
+        let cache_key = format!("{}:{}", project_root.display(), key);
+        let cached = self.cache.borrow().get(&cache_key).copied();
+        let pass = cached.unwrap_or_else(|| {
+            let pass = match group {
+                DetectorGroup::Contains => {
+                    let check = &def.contains[name];
+                    read_project_file(project_root, &check.file)
+                        .is_some_and(|content| content.contains(&check.text))
+                }
+                DetectorGroup::Regex => {
+                    let check = &def.regex[name];
+                    match Regex::new(&format!("(?m){}", check.pattern)) {
+                        Ok(re) => read_project_file(project_root, &check.file)
+                            .is_some_and(|content| re.is_match(&content)),
+                        Err(e) => {
+                            tracing::warn!("Invalid detector regex '{}': {}", check.pattern, e);
+                            false
+                        }
+                    }
+                }
+                DetectorGroup::JsonPath => key_path_exists(project_root, &def.json_path[name]),
+                DetectorGroup::Globs => {
+                    let check = &def.globs[name];
+                    glob_count(project_root, &check.pattern) >= check.min
+                }
+                _ => false,
+            };
+            self.cache.borrow_mut().insert(cache_key, pass);
+            pass
+        });
+
+        Some((
+            pass,
+            format!(
+                "{}: {} ({})",
+                name,
+                if pass { "matched" } else { "no match" },
+                description
+            ),
+        ))
+    }
+
     fn eval_commands_group(&self, def: &DetectorDef) -> (bool, Vec<String>) {
         let cmds = def.all_commands();
         if cmds.is_empty() {
@@ -481,6 +862,53 @@ impl DetectorRegistry {
     }
 }
 
+/// Read a file under the project root, or `None` if it can't be read.
+fn read_project_file(project_root: &Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(project_root.join(file)).ok()
+}
+
+/// Whether `file#dotted.key.path` resolves to a value.
+///
+/// The file is parsed as TOML or YAML by extension, otherwise as JSON.
+/// Numeric segments index into arrays. A path without `#` only checks that
+/// the file parses.
+fn key_path_exists(project_root: &Path, spec: &str) -> bool {
+    let (file, key) = spec.split_once('#').unwrap_or((spec, ""));
+    let Some(content) = read_project_file(project_root, file) else {
+        return false;
+    };
+    let doc: Option<serde_json::Value> = if file.ends_with(".toml") {
+        toml::from_str(&content).ok()
+    } else if file.ends_with(".yml") || file.ends_with(".yaml") {
+        serde_yaml::from_str(&content).ok()
+    } else {
+        serde_json::from_str(&content).ok()
+    };
+    let Some(mut current) = doc.as_ref() else {
+        return false;
+    };
+    for segment in key.split('.').filter(|s| !s.is_empty()) {
+        let next = match current {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(items) => {
+                segment.parse::<usize>().ok().and_then(|i| items.get(i))
+            }
+            _ => None,
+        };
+        match next {
+            Some(value) => current = value,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Count the paths under the project root matching a glob pattern.
+fn glob_count(project_root: &Path, pattern: &str) -> usize {
+    let full = format!("{}/{}", project_root.display(), pattern);
+    crate::sys::glob(&full).map(|m| m.len()).unwrap_or(0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -517,6 +945,38 @@ detectors:
         .unwrap()
     }
 
+    // --- watched_paths tests ---
+
+    #[test]
+    fn watched_paths_lists_files_for_bare_name() {
+        let registry = DetectorRegistry::new(sample_detectors());
+        assert_eq!(
+            registry.watched_paths("rails-project"),
+            vec!["config/application.rb", "config/database.yml"]
+        );
+    }
+
+    #[test]
+    fn watched_paths_narrows_to_specific_entry() {
+        let registry = DetectorRegistry::new(sample_detectors());
+        assert_eq!(
+            registry.watched_paths("rails-project.files.database"),
+            vec!["config/database.yml"]
+        );
+        assert_eq!(
+            registry.watched_paths("package-json-present.file"),
+            vec!["package.json"]
+        );
+    }
+
+    #[test]
+    fn watched_paths_skips_commands_and_unknown_refs() {
+        let registry = DetectorRegistry::new(sample_detectors());
+        assert!(registry.watched_paths("node-installed").is_empty());
+        assert!(registry.watched_paths("rails-project.commands").is_empty());
+        assert!(registry.watched_paths("no-such-detector").is_empty());
+    }
+
     // --- DetectorRef parsing tests ---
 
     #[test]
@@ -587,6 +1047,7 @@ detectors:
             commands: HashMap::new(),
             file: None,
             files: HashMap::new(),
+            ..Default::default()
         };
         let cmds = def.all_commands();
         assert_eq!(cmds.len(), 1);
@@ -602,6 +1063,7 @@ detectors:
             commands,
             file: None,
             files: HashMap::new(),
+            ..Default::default()
         };
         let cmds = def.all_commands();
         assert_eq!(cmds.len(), 1);
@@ -615,6 +1077,7 @@ detectors:
             commands: HashMap::new(),
             file: Some("package.json".to_string()),
             files: HashMap::new(),
+            ..Default::default()
         };
         let files = def.all_files();
         assert_eq!(files.len(), 1);
@@ -628,6 +1091,7 @@ detectors:
             commands: HashMap::new(),
             file: None,
             files: HashMap::new(),
+            ..Default::default()
         };
         assert!(def.has_checks());
     }
@@ -639,6 +1103,7 @@ detectors:
             commands: HashMap::new(),
             file: None,
             files: HashMap::new(),
+            ..Default::default()
         };
         assert!(!def.has_checks());
     }
@@ -834,6 +1299,18 @@ detectors:
         assert!(!registry.evaluate_all(&refs, temp.path()).unwrap());
     }
 
+    #[test]
+    fn check_resolves_without_evaluating() {
+        let registry = DetectorRegistry::new(sample_detectors());
+        assert!(registry.check("rails-project").is_ok());
+        assert!(registry.check("rails-project.files.application").is_ok());
+        assert!(registry.check("package-json-present.command").is_ok());
+        assert!(registry.check("rails-project.files.missing").is_err());
+        assert!(registry.check("cargo-toml-present.command").is_err());
+        assert!(registry.check("rails-project.regex").is_err());
+        assert!(registry.check("unknown").is_err());
+    }
+
     // --- Caching tests ---
 
     #[test]
@@ -869,6 +1346,214 @@ detectors:
         assert!(registry.cache.borrow().is_empty());
     }
 
+    // --- Content check tests ---
+
+    fn content_detectors() -> DetectorFile {
+        serde_yaml::from_str(
+            r#"
+detectors:
+  rails-app:
+    contains:
+      gemfile: { file: Gemfile, text: "rails" }
+    regex:
+      gem_line: { file: Gemfile, pattern: "^\\s*gem ['\"]rails['\"]" }
+  vite-app:
+    json_path:
+      dev: package.json#devDependencies.vite
+      dep: package.json#dependencies.vite
+  diesel-app:
+    json_path:
+      cargo: Cargo.toml#dependencies.diesel
+  migrations:
+    globs:
+      many: { pattern: "db/migrate/*.rb", min: 2 }
+  broken:
+    regex:
+      bad: { file: Gemfile, pattern: "(" }
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_content_groups() {
+        let r = DetectorRef::parse("vite-app.json_path.dev").unwrap();
+        assert_eq!(r.group, Some(DetectorGroup::JsonPath));
+        assert_eq!(r.specific, Some("dev".to_string()));
+        assert_eq!(
+            DetectorRef::parse("migrations.globs").unwrap().group,
+            Some(DetectorGroup::Globs)
+        );
+        assert_eq!(
+            DetectorRef::parse("rails-app.contains").unwrap().group,
+            Some(DetectorGroup::Contains)
+        );
+    }
+
+    #[test]
+    fn evaluate_contains_and_regex() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("Gemfile"),
+            "source \"https://rubygems.org\"\n  gem 'rails', '~> 7.1'\n",
+        )
+        .unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+
+        assert!(
+            registry
+                .evaluate("rails-app.contains.gemfile", temp.path())
+                .unwrap()
+                .passed
+        );
+        let result = registry.evaluate("rails-app.regex", temp.path()).unwrap();
+        assert!(result.passed);
+        assert_eq!(result.details.len(), 1);
+        assert!(result.details[0].starts_with("gem_line: matched"));
+    }
+
+    #[test]
+    fn evaluate_regex_does_not_match_commented_gem() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("Gemfile"),
+            "# gem 'rails'\ngem 'sinatra'\n",
+        )
+        .unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(
+            !registry
+                .evaluate("rails-app.regex", temp.path())
+                .unwrap()
+                .passed
+        );
+    }
+
+    #[test]
+    fn evaluate_json_path_in_json_and_toml() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"devDependencies": {"vite": "^5.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("Cargo.toml"),
+            "[dependencies]\ndiesel = { version = \"2\" }\n",
+        )
+        .unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+
+        assert!(registry.evaluate("vite-app", temp.path()).unwrap().passed);
+        assert!(
+            !registry
+                .evaluate("vite-app.json_path.dep", temp.path())
+                .unwrap()
+                .passed
+        );
+        assert!(
+            registry
+                .evaluate("diesel-app.json_path.cargo", temp.path())
+                .unwrap()
+                .passed
+        );
+    }
+
+    #[test]
+    fn evaluate_json_path_fails_on_invalid_file() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("package.json"), "not json").unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(!registry.evaluate("vite-app", temp.path()).unwrap().passed);
+    }
+
+    #[test]
+    fn evaluate_glob_min_count() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("db/migrate")).unwrap();
+        fs::write(temp.path().join("db/migrate/001_users.rb"), "").unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(!registry.evaluate("migrations", temp.path()).unwrap().passed);
+
+        fs::write(temp.path().join("db/migrate/002_posts.rb"), "").unwrap();
+        registry.clear_cache();
+        assert!(registry.evaluate("migrations", temp.path()).unwrap().passed);
+    }
+
+    #[test]
+    fn evaluate_invalid_regex_fails() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Gemfile"), "gem 'rails'").unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(!registry.evaluate("broken", temp.path()).unwrap().passed);
+    }
+
+    #[test]
+    fn evaluate_unknown_content_check_errors() {
+        let temp = TempDir::new().unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(registry
+            .evaluate("vite-app.json_path.missing", temp.path())
+            .is_err());
+    }
+
+    #[test]
+    fn content_checks_are_cached() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Gemfile"), "gem 'rails'").unwrap();
+        let registry = DetectorRegistry::new(content_detectors());
+        assert!(
+            registry
+                .evaluate("rails-app.contains", temp.path())
+                .unwrap()
+                .passed
+        );
+
+        // A changed file isn't re-read until the cache is cleared
+        fs::write(temp.path().join("Gemfile"), "gem 'sinatra'").unwrap();
+        assert!(
+            registry
+                .evaluate("rails-app.contains", temp.path())
+                .unwrap()
+                .passed
+        );
+        registry.clear_cache();
+        assert!(
+            !registry
+                .evaluate("rails-app.contains", temp.path())
+                .unwrap()
+                .passed
+        );
+    }
+
+    #[test]
+    fn evaluate_bare_name_ignores_empty_groups() {
+        let temp = TempDir::new().unwrap();
+        let registry = DetectorRegistry::new(sample_detectors());
+        let file: DetectorFile = serde_yaml::from_str(
+            r#"
+detectors:
+  absent-tool:
+    command: "false"
+"#,
+        )
+        .unwrap();
+        let only_command = DetectorRegistry::new(file);
+        // A failing command isn't rescued by the (empty) files group
+        assert!(
+            !only_command
+                .evaluate("absent-tool", temp.path())
+                .unwrap()
+                .passed
+        );
+        assert!(
+            registry
+                .evaluate("node-installed", temp.path())
+                .unwrap()
+                .passed
+        );
+    }
+
     // --- YAML deserialization tests ---
 
     #[test]
@@ -905,4 +1590,18 @@ detectors:
         assert_eq!(d.commands.len(), 2);
         assert_eq!(d.files.len(), 2);
     }
+
+    #[test]
+    fn deserialize_detector_file_with_categories() {
+        let yaml = r#"
+categories:
+  - name: platform
+    description: "Platform team tooling"
+    templates: [platform-lint]
+"#;
+        let file: DetectorFile = serde_yaml::from_str(yaml).unwrap();
+        assert!(file.detectors.is_empty());
+        assert_eq!(file.categories.len(), 1);
+        assert_eq!(file.categories[0].templates, vec!["platform-lint"]);
+    }
 }
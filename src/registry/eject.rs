@@ -0,0 +1,311 @@
+//! Rendering a template's step for `bivvy eject`.
+//!
+//! Ejecting replaces a step's `template:` reference with the fields the
+//! template provides, with the step's inputs substituted. The same
+//! rendering is used by `bivvy templates outdated` to compare ejected
+//! steps with the current version of their template.
+
+use std::collections::{BTreeMap, HashMap};
+
+use serde_yaml::{Mapping, Value};
+
+use super::resolver::Registry;
+use crate::config::{substitute_known, EjectedFrom, PromptConfig, StepConfig};
+use crate::error::Result;
+
+/// A template's step with its inputs substituted.
+#[derive(Debug, Clone)]
+pub struct EjectedStep {
+    /// Step title.
+    pub title: Option<String>,
+    /// Step description.
+    pub description: Option<String>,
+    /// Command to run.
+    pub command: Option<String>,
+    /// System-level prerequisites.
+    pub tools: Vec<String>,
+    /// Environment variables.
+    pub env: BTreeMap<String, String>,
+    /// Prompts for inputs that have no value, so their references still
+    /// resolve when the step runs.
+    pub prompts: Vec<PromptConfig>,
+    /// Where the fields came from.
+    pub ejected_from: EjectedFrom,
+}
+
+impl EjectedStep {
+    /// Render a template's step with the given inputs (plus input
+    /// defaults) substituted.
+    ///
+    /// Only input references are replaced; `vars`, environment variables
+    /// and other `${...}` references stay for the run to resolve.
+    pub fn render(
+        registry: &Registry,
+        template_name: &str,
+        inputs: &HashMap<String, Value>,
+    ) -> Result<Self> {
+        let (template, _source) = registry.resolve(template_name)?;
+        let values: HashMap<String, String> = registry
+            .effective_inputs(template_name, inputs)?
+            .iter()
+            .map(|(name, value)| (name.clone(), input_string(value)))
+            .collect();
+
+        let render = |text: &Option<String>| -> Result<Option<String>> {
+            text.as_deref()
+                .map(|t| substitute_known(t, &values))
+                .transpose()
+        };
+
+        let mut prompt_inputs: Vec<_> = template
+            .inputs
+            .iter()
+            .filter(|(name, _)| !values.contains_key(*name))
+            .filter_map(|(name, input)| input.prompt.as_ref().map(|p| (name, p)))
+            .collect();
+        prompt_inputs.sort_by_key(|(name, _)| *name);
+        let prompts = prompt_inputs
+            .into_iter()
+            .map(|(name, prompt)| PromptConfig {
+                key: name.clone(),
+                question: prompt.question.clone(),
+                prompt_type: prompt.prompt_type.clone(),
+                options: prompt.options.clone(),
+                default: None,
+                min: None,
+                max: None,
+                pattern: None,
+                pattern_message: None,
+                must_be: None,
+            })
+            .collect();
+
+        let step = &template.step;
+        let mut env = BTreeMap::new();
+        for (key, value) in &step.env {
+            env.insert(key.clone(), substitute_known(value, &values)?);
+        }
+
+        Ok(Self {
+            title: render(&step.title)?,
+            description: render(&step.description)?,
+            command: render(&step.command)?,
+            tools: step.requires.clone(),
+            env,
+            prompts,
+            ejected_from: EjectedFrom {
+                template: template_name.to_string(),
+                version: template.version.clone(),
+                inputs: inputs.clone(),
+            },
+        })
+    }
+
+    /// The rendered fields as a step config mapping, in the order they
+    /// are written.
+    pub fn fields(&self) -> Mapping {
+        fields(
+            self.title.as_deref(),
+            self.description.as_deref(),
+            self.command.as_deref(),
+            &self.tools,
+            self.env.iter(),
+            &self.prompts,
+        )
+    }
+}
+
+/// The fields of a step config that a template can provide, in the same
+/// shape as [`EjectedStep::fields`].
+pub fn step_fields(config: &StepConfig) -> Mapping {
+    let env: BTreeMap<&String, &String> = config.env_vars.env.iter().collect();
+    fields(
+        config.title.as_deref(),
+        config.description.as_deref(),
+        config.execution.command.as_deref(),
+        &config.requires,
+        env.into_iter(),
+        &config.output_settings.prompts,
+    )
+}
+
+fn fields<'a>(
+    title: Option<&str>,
+    description: Option<&str>,
+    command: Option<&str>,
+    tools: &[String],
+    env: impl Iterator<Item = (&'a String, &'a String)>,
+    prompts: &[PromptConfig],
+) -> Mapping {
+    let mut mapping = Mapping::new();
+    for (key, value) in [
+        ("title", title),
+        ("description", description),
+        ("command", command),
+    ] {
+        if let Some(value) = value {
+            mapping.insert(key.into(), value.into());
+        }
+    }
+    if !tools.is_empty() {
+        let tools = tools.iter().map(|t| t.as_str().into()).collect();
+        mapping.insert("tools".into(), Value::Sequence(tools));
+    }
+    let env: Mapping = env
+        .map(|(k, v)| (k.as_str().into(), v.as_str().into()))
+        .collect();
+    if !env.is_empty() {
+        mapping.insert("env".into(), Value::Mapping(env));
+    }
+    if !prompts.is_empty() {
+        let prompts = prompts.iter().map(prompt_value).collect();
+        mapping.insert("prompts".into(), Value::Sequence(prompts));
+    }
+    mapping
+}
+
+/// A prompt as written in config, without unset optional fields.
+fn prompt_value(prompt: &PromptConfig) -> Value {
+    let mut mapping = Mapping::new();
+    mapping.insert("key".into(), prompt.key.as_str().into());
+    mapping.insert("question".into(), prompt.question.as_str().into());
+    if let Ok(prompt_type) = serde_yaml::to_value(&prompt.prompt_type) {
+        mapping.insert("type".into(), prompt_type);
+    }
+    if !prompt.options.is_empty() {
+        if let Ok(options) = serde_yaml::to_value(&prompt.options) {
+            mapping.insert("options".into(), options);
+        }
+    }
+    if let Some(ref default) = prompt.default {
+        mapping.insert("default".into(), default.clone());
+    }
+    Value::Mapping(mapping)
+}
+
+/// The text an input value is substituted as.
+fn input_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim_end()
+            .to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn registry_with(template: &str) -> (TempDir, Registry) {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path().join(".bivvy/templates/steps");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("deploy.yml"), template).unwrap();
+        let registry = Registry::new(Some(temp.path())).unwrap();
+        (temp, registry)
+    }
+
+    #[test]
+    fn renders_builtin_template() {
+        let registry = Registry::new(None).unwrap();
+
+        let step = EjectedStep::render(&registry, "bundle-install", &HashMap::new()).unwrap();
+
+        assert_eq!(step.title.as_deref(), Some("Install Ruby dependencies"));
+        assert_eq!(step.command.as_deref(), Some("bundle install"));
+        assert_eq!(step.tools, vec!["ruby".to_string()]);
+        assert_eq!(step.ejected_from.template, "bundle-install");
+        assert_eq!(step.ejected_from.version, "1.0.0");
+    }
+
+    #[test]
+    fn substitutes_inputs_and_defaults() {
+        let (_temp, registry) = registry_with(
+            r#"
+name: deploy
+description: "Deploy"
+category: custom
+version: "2.1.0"
+inputs:
+  region:
+    description: "Region"
+    type: string
+    required: true
+  stage:
+    description: "Stage"
+    type: string
+    default: staging
+step:
+  title: "Deploy to ${region}"
+  command: "deploy --region ${region} --stage ${stage} --app ${APP}"
+  env:
+    DEPLOY_STAGE: "${stage | upper}"
+"#,
+        );
+        let inputs = HashMap::from([("region".to_string(), Value::from("eu-west-1"))]);
+
+        let step = EjectedStep::render(&registry, "deploy", &inputs).unwrap();
+
+        assert_eq!(step.title.as_deref(), Some("Deploy to eu-west-1"));
+        assert_eq!(
+            step.command.as_deref(),
+            Some("deploy --region eu-west-1 --stage staging --app ${APP}")
+        );
+        assert_eq!(step.env["DEPLOY_STAGE"], "STAGING");
+        assert_eq!(step.ejected_from.version, "2.1.0");
+        assert_eq!(step.ejected_from.inputs, inputs);
+    }
+
+    #[test]
+    fn inputs_without_values_become_prompts() {
+        let (_temp, registry) = registry_with(
+            r#"
+name: deploy
+description: "Deploy"
+category: custom
+inputs:
+  region:
+    description: "Region"
+    type: string
+    prompt:
+      question: "Which region?"
+      type: input
+step:
+  command: "deploy --region ${region}"
+"#,
+        );
+
+        let step = EjectedStep::render(&registry, "deploy", &HashMap::new()).unwrap();
+
+        assert_eq!(step.command.as_deref(), Some("deploy --region ${region}"));
+        assert_eq!(step.prompts.len(), 1);
+        assert_eq!(step.prompts[0].key, "region");
+
+        let config: StepConfig = serde_yaml::from_value(Value::Mapping(step.fields())).unwrap();
+        assert_eq!(config.output_settings.prompts[0].question, "Which region?");
+    }
+
+    #[test]
+    fn fields_match_step_config_shape() {
+        let registry = Registry::new(None).unwrap();
+        let step = EjectedStep::render(&registry, "bundle-install", &HashMap::new()).unwrap();
+
+        let config: StepConfig = serde_yaml::from_value(Value::Mapping(step.fields())).unwrap();
+
+        assert_eq!(step_fields(&config), step.fields());
+        assert_eq!(config.requires, vec!["ruby".to_string()]);
+    }
+
+    #[test]
+    fn unknown_template_fails() {
+        let registry = Registry::new(None).unwrap();
+
+        assert!(EjectedStep::render(&registry, "nonexistent", &HashMap::new()).is_err());
+    }
+}
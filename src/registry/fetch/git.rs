@@ -41,6 +41,8 @@ pub fn validate_git_url(url: &str) -> Result<()> {
 pub struct GitFetcher {
     /// Directory for cloned repositories.
     clone_dir: PathBuf,
+    /// Use existing clones only ([offline mode](crate::cache::offline)).
+    offline: bool,
 }
 
 /// Information about a git fetch.
@@ -57,9 +59,29 @@ impl GitFetcher {
     pub fn new(clone_dir: impl Into<PathBuf>) -> Self {
         Self {
             clone_dir: clone_dir.into(),
+            offline: crate::cache::is_offline(),
         }
     }
 
+    /// Use existing clones only, never touching the network.
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// An existing clone of a repository, without fetching.
+    pub fn cached(&self, url: &str) -> Option<GitFetchResult> {
+        let repo_path = self.repo_path(url);
+        if !repo_path.join(".git").exists() {
+            return None;
+        }
+        let commit_sha = self.get_head_sha(&repo_path).ok()?;
+        Some(GitFetchResult {
+            local_path: repo_path,
+            commit_sha,
+        })
+    }
+
     /// Get the clone directory.
     pub fn clone_dir(&self) -> &PathBuf {
         &self.clone_dir
@@ -82,6 +104,12 @@ impl GitFetcher {
         url: &str,
         git_ref: Option<&str>,
     ) -> Result<GitFetchResult> {
+        if self.offline {
+            return self
+                .cached(url)
+                .ok_or_else(|| crate::cache::offline::refuse(&format!("git clone of {}", url)));
+        }
+
         let repo_path = self.repo_path(url);
 
         if repo_path.exists() {
@@ -127,6 +155,9 @@ impl GitFetcher {
 
     /// Internal resolve_ref without URL validation.
     fn resolve_ref_unchecked(&self, url: &str, git_ref: Option<&str>) -> Result<String> {
+        if self.offline {
+            return Err(crate::cache::offline::refuse(url));
+        }
         let refspec = git_ref.unwrap_or("HEAD");
 
         // Try the refspec directly, then with refs/heads/ and refs/tags/ prefixes.
@@ -15,6 +15,7 @@ pub struct HttpFetcher {
     client: Client,
     timeout: Duration,
     max_response_size: u64,
+    offline: bool,
 }
 
 /// Response from fetching a template.
@@ -44,6 +45,7 @@ impl HttpFetcher {
                 .expect("Failed to build HTTP client"),
             timeout,
             max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+            offline: crate::cache::is_offline(),
         }
     }
 
@@ -58,16 +60,31 @@ impl HttpFetcher {
                 .expect("Failed to build HTTP client"),
             timeout,
             max_response_size,
+            offline: false,
         }
     }
 
+    /// Refuse every request ([offline mode](crate::cache::offline)).
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
     /// Get the configured timeout.
     pub fn timeout(&self) -> Duration {
         self.timeout
     }
 
+    /// Whether requests are refused because bivvy is offline.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
     /// Fetch a template from a URL.
     pub fn fetch(&self, url: &str) -> Result<FetchResponse> {
+        if self.offline {
+            return Err(crate::cache::offline::refuse(url));
+        }
         let response = self.client.get(url).send()?;
 
         if !response.status().is_success() {
@@ -121,6 +138,9 @@ impl HttpFetcher {
     ///
     /// Returns `None` if content unchanged (304 Not Modified).
     pub fn fetch_if_changed(&self, url: &str, etag: Option<&str>) -> Result<Option<FetchResponse>> {
+        if self.offline {
+            return Err(crate::cache::offline::refuse(url));
+        }
         let mut request = self.client.get(url);
 
         if let Some(etag) = etag {
@@ -510,6 +530,16 @@ mod tests {
         mock.assert();
     }
 
+    #[test]
+    fn offline_refuses_requests() {
+        let fetcher = HttpFetcher::new().with_offline(true);
+        let err = fetcher.fetch("https://example.com/t.yml").unwrap_err();
+        assert!(err.to_string().contains("Offline mode"));
+        assert!(fetcher
+            .fetch_if_changed("https://example.com/t.yml", None)
+            .is_err());
+    }
+
     #[test]
     fn empty_response_body_returned() {
         let server = MockServer::start();
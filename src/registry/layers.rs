@@ -0,0 +1,266 @@
+//! Merging detectors and categories from every template source.
+//!
+//! Built-in detectors come from `templates/detectors.yml` and built-in
+//! categories from `templates/registry.yml`. Project (`.bivvy/detectors.yml`),
+//! user (`~/.bivvy/detectors.yml`) and remote template sources can add
+//! their own. Layers are merged in template resolution order, so a project
+//! detector shadows a user one, which shadows a remote one, which shadows
+//! a built-in one. Every shadowed detector produces a warning.
+
+use std::collections::HashMap;
+
+use crate::registry::detector::{DetectorDef, DetectorFile, DetectorRegistry};
+use crate::registry::manifest::Category;
+use crate::registry::template::TemplateSource;
+
+/// Detectors and categories contributed by one non-built-in source.
+#[derive(Debug, Clone)]
+pub struct DetectorLayer {
+    /// Where the layer came from, for resolution priority.
+    pub source: TemplateSource,
+    /// Human-readable origin (a path or URL) used in warnings.
+    pub origin: String,
+    /// The parsed detector file.
+    pub file: DetectorFile,
+}
+
+/// The merged view of detectors and categories across all sources.
+#[derive(Debug, Clone, Default)]
+pub struct MergedDetectors {
+    detectors: HashMap<String, (DetectorDef, TemplateSource)>,
+    categories: Vec<Category>,
+    warnings: Vec<String>,
+}
+
+impl MergedDetectors {
+    /// Merge built-in definitions with the given layers.
+    ///
+    /// Detectors with the same name resolve to the highest-priority layer.
+    /// Categories with the same name are combined: the highest-priority
+    /// description wins and template lists are unioned. New categories are
+    /// appended after the built-in ones.
+    pub fn merge(
+        builtin: DetectorFile,
+        builtin_categories: &[Category],
+        mut layers: Vec<DetectorLayer>,
+    ) -> Self {
+        // Stable sort keeps declaration order among equal remote priorities.
+        layers.sort_by_key(|l| l.source);
+
+        let mut merged = Self::default();
+        let mut origins: HashMap<String, String> = HashMap::new();
+
+        for layer in &layers {
+            for (name, def) in &layer.file.detectors {
+                if let Some(winner) = origins.get(name) {
+                    merged.warnings.push(format!(
+                        "Detector '{}' in {} is shadowed by {}",
+                        name, layer.origin, winner
+                    ));
+                    continue;
+                }
+                origins.insert(name.clone(), layer.origin.clone());
+                merged
+                    .detectors
+                    .insert(name.clone(), (def.clone(), layer.source));
+            }
+        }
+
+        for (name, def) in builtin.detectors {
+            if let Some(winner) = origins.get(&name) {
+                merged.warnings.push(format!(
+                    "Built-in detector '{}' is overridden by {}",
+                    name, winner
+                ));
+                continue;
+            }
+            merged
+                .detectors
+                .insert(name, (def, TemplateSource::Builtin));
+        }
+
+        // Lowest priority first so higher layers can replace descriptions.
+        merged.categories = builtin_categories.to_vec();
+        for layer in layers.iter().rev() {
+            for category in &layer.file.categories {
+                merged.add_category(category);
+            }
+        }
+
+        merged.warnings.sort();
+        merged
+    }
+
+    fn add_category(&mut self, category: &Category) {
+        match self.categories.iter_mut().find(|c| c.name == category.name) {
+            Some(existing) => {
+                if !category.description.is_empty() {
+                    existing.description = category.description.clone();
+                }
+                for template in &category.templates {
+                    if !existing.templates.contains(template) {
+                        existing.templates.push(template.clone());
+                    }
+                }
+            }
+            None => self.categories.push(category.clone()),
+        }
+    }
+
+    /// Build a detector registry over the merged definitions.
+    pub fn registry(&self) -> DetectorRegistry {
+        DetectorRegistry::new(DetectorFile {
+            detectors: self
+                .detectors
+                .iter()
+                .map(|(name, (def, _))| (name.clone(), def.clone()))
+                .collect(),
+            categories: Vec::new(),
+        })
+    }
+
+    /// Where the winning definition of a detector came from.
+    pub fn source_of(&self, name: &str) -> Option<TemplateSource> {
+        self.detectors.get(name).map(|(_, source)| *source)
+    }
+
+    /// Merged categories, built-in ones first.
+    pub fn categories(&self) -> &[Category] {
+        &self.categories
+    }
+
+    /// One message per shadowed detector, sorted.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(yaml: &str) -> DetectorFile {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn layer(source: TemplateSource, origin: &str, yaml: &str) -> DetectorLayer {
+        DetectorLayer {
+            source,
+            origin: origin.to_string(),
+            file: file(yaml),
+        }
+    }
+
+    fn builtin() -> DetectorFile {
+        file("detectors:\n  gemfile:\n    file: Gemfile\n  cargo:\n    file: Cargo.toml\n")
+    }
+
+    fn categories() -> Vec<Category> {
+        vec![Category {
+            name: "ruby".to_string(),
+            description: "Ruby ecosystem".to_string(),
+            templates: vec!["bundle-install".to_string()],
+        }]
+    }
+
+    #[test]
+    fn layers_add_detectors() {
+        let merged = MergedDetectors::merge(
+            builtin(),
+            &categories(),
+            vec![layer(
+                TemplateSource::Project,
+                ".bivvy/detectors.yml",
+                "detectors:\n  platform:\n    file: platform.yml\n",
+            )],
+        );
+        let registry = merged.registry();
+        assert!(registry.get("platform").is_some());
+        assert!(registry.get("gemfile").is_some());
+        assert_eq!(merged.source_of("platform"), Some(TemplateSource::Project));
+        assert!(merged.warnings().is_empty());
+    }
+
+    #[test]
+    fn higher_priority_layer_wins_with_warning() {
+        let merged = MergedDetectors::merge(
+            builtin(),
+            &[],
+            vec![
+                layer(
+                    TemplateSource::Remote { priority: 10 },
+                    "https://example.com/templates",
+                    "detectors:\n  gemfile:\n    file: remote.rb\n",
+                ),
+                layer(
+                    TemplateSource::Project,
+                    ".bivvy/detectors.yml",
+                    "detectors:\n  gemfile:\n    file: project.rb\n",
+                ),
+            ],
+        );
+        let registry = merged.registry();
+        assert_eq!(
+            registry.get("gemfile").unwrap().file.as_deref(),
+            Some("project.rb")
+        );
+        assert_eq!(merged.source_of("gemfile"), Some(TemplateSource::Project));
+        assert_eq!(
+            merged.warnings(),
+            [
+                "Built-in detector 'gemfile' is overridden by .bivvy/detectors.yml",
+                "Detector 'gemfile' in https://example.com/templates is shadowed by .bivvy/detectors.yml",
+            ]
+        );
+    }
+
+    #[test]
+    fn remote_layers_merge_by_priority() {
+        let merged = MergedDetectors::merge(
+            DetectorFile::default(),
+            &[],
+            vec![
+                layer(
+                    TemplateSource::Remote { priority: 50 },
+                    "low",
+                    "detectors:\n  tool:\n    file: low\n",
+                ),
+                layer(
+                    TemplateSource::Remote { priority: 10 },
+                    "high",
+                    "detectors:\n  tool:\n    file: high\n",
+                ),
+            ],
+        );
+        assert_eq!(
+            merged.registry().get("tool").unwrap().file.as_deref(),
+            Some("high")
+        );
+    }
+
+    #[test]
+    fn categories_are_unioned() {
+        let merged = MergedDetectors::merge(
+            builtin(),
+            &categories(),
+            vec![
+                layer(
+                    TemplateSource::User,
+                    "~/.bivvy/detectors.yml",
+                    "categories:\n  - name: ruby\n    description: Ruby at work\n    templates: [rubocop]\n",
+                ),
+                layer(
+                    TemplateSource::Project,
+                    ".bivvy/detectors.yml",
+                    "categories:\n  - name: platform\n    description: Platform\n    templates: [platform-lint]\n",
+                ),
+            ],
+        );
+        let cats = merged.categories();
+        assert_eq!(cats.len(), 2);
+        assert_eq!(cats[0].name, "ruby");
+        assert_eq!(cats[0].description, "Ruby at work");
+        assert_eq!(cats[0].templates, vec!["bundle-install", "rubocop"]);
+        assert_eq!(cats[1].name, "platform");
+    }
+}
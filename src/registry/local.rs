@@ -1,39 +1,78 @@
 //! Local template loading from user and project directories.
 
 use crate::error::Result;
+use crate::registry::detector::DetectorFile;
+use crate::registry::layers::DetectorLayer;
 use crate::registry::template::{Template, TemplateSource};
 use std::collections::HashMap;
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::Path;
 
 /// Loader for local templates (user and project).
 #[derive(Debug, Clone)]
 pub struct LocalLoader {
     templates: HashMap<String, (Template, TemplateSource)>,
+    detector_layers: Vec<DetectorLayer>,
 }
 
 impl LocalLoader {
     /// Load templates from user and project directories.
     pub fn new(project_root: Option<&Path>) -> Result<Self> {
         let mut templates = HashMap::new();
+        let mut detector_layers = Vec::new();
 
-        // Load user templates (~/.bivvy/templates/)
-        if let Some(user_dir) = Self::user_templates_dir() {
-            Self::load_from_dir(&user_dir, TemplateSource::User, &mut templates)?;
+        // Load user templates (~/.bivvy/templates/) and detectors
+        if let Some(home) = crate::sys::home_dir() {
+            let user_dir = home.join(".bivvy");
+            Self::load_from_dir(
+                &user_dir.join("templates"),
+                TemplateSource::User,
+                &mut templates,
+            )?;
+            if let Some(file) = Self::load_detector_file(&user_dir.join("detectors.yml"))? {
+                detector_layers.push(DetectorLayer {
+                    source: TemplateSource::User,
+                    origin: "~/.bivvy/detectors.yml".to_string(),
+                    file,
+                });
+            }
         }
 
-        // Load project templates (.bivvy/templates/)
+        // Load project templates (.bivvy/templates/) and detectors
         if let Some(root) = project_root {
-            let project_dir = root.join(".bivvy").join("templates");
-            Self::load_from_dir(&project_dir, TemplateSource::Project, &mut templates)?;
+            let project_dir = root.join(".bivvy");
+            Self::load_from_dir(
+                &project_dir.join("templates"),
+                TemplateSource::Project,
+                &mut templates,
+            )?;
+            if let Some(file) = Self::load_detector_file(&project_dir.join("detectors.yml"))? {
+                detector_layers.push(DetectorLayer {
+                    source: TemplateSource::Project,
+                    origin: ".bivvy/detectors.yml".to_string(),
+                    file,
+                });
+            }
         }
 
-        Ok(Self { templates })
+        Ok(Self {
+            templates,
+            detector_layers,
+        })
     }
 
-    /// Get user templates directory.
-    fn user_templates_dir() -> Option<PathBuf> {
-        crate::sys::home_dir().map(|h| h.join(".bivvy").join("templates"))
+    /// Parse a `detectors.yml` file if it exists.
+    fn load_detector_file(path: &Path) -> Result<Option<DetectorFile>> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map(Some).map_err(|e| {
+            crate::error::BivvyError::ConfigParseError {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            }
+        })
     }
 
     /// Load templates from a directory (recursively).
@@ -139,6 +178,11 @@ impl LocalLoader {
     pub fn has(&self, name: &str) -> bool {
         self.templates.contains_key(name) || self.templates.values().any(|(t, _)| t.name == name)
     }
+
+    /// Detector files found in the user and project `.bivvy/` directories.
+    pub fn detector_layers(&self) -> &[DetectorLayer] {
+        &self.detector_layers
+    }
 }
 
 #[cfg(test)]
@@ -216,4 +260,34 @@ step:
         let loader = LocalLoader::new(Some(temp.path())).unwrap();
         assert!(loader.has("nested"));
     }
+
+    #[test]
+    fn local_loader_reads_project_detectors() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(
+            bivvy_dir.join("detectors.yml"),
+            "detectors:\n  platform:\n    file: platform.yml\n",
+        )
+        .unwrap();
+
+        let loader = LocalLoader::new(Some(temp.path())).unwrap();
+        let layer = loader
+            .detector_layers()
+            .iter()
+            .find(|l| l.source == TemplateSource::Project)
+            .unwrap();
+        assert!(layer.file.detectors.contains_key("platform"));
+    }
+
+    #[test]
+    fn local_loader_rejects_invalid_detectors() {
+        let temp = TempDir::new().unwrap();
+        let bivvy_dir = temp.path().join(".bivvy");
+        fs::create_dir_all(&bivvy_dir).unwrap();
+        fs::write(bivvy_dir.join("detectors.yml"), "detectors: [oops").unwrap();
+
+        assert!(LocalLoader::new(Some(temp.path())).is_err());
+    }
 }
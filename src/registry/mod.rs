@@ -4,7 +4,7 @@
 //! - Built-in templates (embedded in binary)
 //! - User templates (~/.bivvy/templates/)
 //! - Project templates (.bivvy/templates/)
-//! - Remote templates (coming in M11)
+//! - Remote templates (`template_sources`)
 //!
 //! # Resolution Order
 //!
@@ -30,7 +30,9 @@
 
 pub mod builtin;
 pub mod detector;
+pub mod eject;
 pub mod fetch;
+pub mod layers;
 pub mod local;
 pub mod manifest;
 pub mod remote;
@@ -42,7 +44,9 @@ pub mod template_name;
 // Re-exports
 pub use builtin::BuiltinLoader;
 pub use detector::{DetectorDef, DetectorFile, DetectorRef, DetectorRegistry, DetectorResult};
+pub use eject::EjectedStep;
 pub use fetch::{FetchResponse, GitFetchResult, GitFetcher, HttpFetcher};
+pub use layers::{DetectorLayer, MergedDetectors};
 pub use local::LocalLoader;
 pub use manifest::{Category, RegistryManifest};
 pub use remote::RemoteLoader;
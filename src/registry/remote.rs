@@ -3,14 +3,23 @@
 //! Fetches templates from URLs defined in `template_sources` and caches
 //! them using the shared cache infrastructure (HTTP) or the on-disk clone
 //! directory (Git).
+//!
+//! A source may also ship detectors and categories: Git sources through a
+//! `detectors.yml` file at the source root, HTTP sources by returning a
+//! document with `templates`, `detectors` and `categories` keys.
 
 use anyhow::{Context, Result};
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
+use super::detector::{DetectorDef, DetectorFile};
 use super::fetch::{GitFetcher, HttpFetcher};
-use super::template::Template;
+use super::layers::DetectorLayer;
+use super::manifest::Category;
+use super::template::{Template, TemplateSource};
+use crate::cache::offline::{self, RemoteResource};
 use crate::cache::{parse_ttl, CacheStore};
 use crate::config::schema::{TemplateSource as TemplateSourceConfig, TemplateSourceKind};
 
@@ -19,6 +28,27 @@ use crate::config::schema::{TemplateSource as TemplateSourceConfig, TemplateSour
 pub struct RemoteLoader {
     /// Fetched and parsed templates, keyed by `category/name`.
     templates: HashMap<String, (Template, u32)>,
+    /// Detector files shipped by the sources.
+    detector_layers: Vec<DetectorLayer>,
+}
+
+/// Everything a single source provided.
+#[derive(Debug, Default)]
+struct LoadedSource {
+    templates: Vec<Template>,
+    detectors: Option<DetectorFile>,
+}
+
+/// An HTTP source document that carries detectors alongside templates.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SourceBundle {
+    #[serde(default)]
+    templates: Vec<Template>,
+    #[serde(default)]
+    detectors: HashMap<String, DetectorDef>,
+    #[serde(default)]
+    categories: Vec<Category>,
 }
 
 impl RemoteLoader {
@@ -34,6 +64,7 @@ impl RemoteLoader {
         cache: &CacheStore,
     ) -> Result<Self> {
         let mut templates = HashMap::new();
+        let mut detector_layers = Vec::new();
 
         // Visit sources in priority order (lower `priority` number wins on
         // collision). Stable sort preserves declaration order among ties so
@@ -41,6 +72,19 @@ impl RemoteLoader {
         let mut ordered: Vec<&TemplateSourceConfig> = sources.iter().collect();
         ordered.sort_by_key(|s| s.priority);
 
+        // Offline, a source that isn't on disk is an error rather than a
+        // warning; every missing source is reported together.
+        if http_fetcher.is_offline() {
+            let missing: Vec<String> = ordered
+                .iter()
+                .filter(|s| !Self::is_cached(s, git_fetcher, cache))
+                .map(|s| RemoteResource::TemplateSource((*s).clone()).to_string())
+                .collect();
+            if !missing.is_empty() {
+                return Err(offline::unavailable(missing).into());
+            }
+        }
+
         for source in ordered {
             let result = match source.effective_kind() {
                 TemplateSourceKind::Http => Self::load_http_source(source, http_fetcher, cache),
@@ -49,11 +93,20 @@ impl RemoteLoader {
 
             match result {
                 Ok(loaded) => {
-                    for template in loaded {
+                    for template in loaded.templates {
                         // Higher-priority sources (lower number) win on collision.
                         let key = format!("{}/{}", template.category, template.name);
                         templates.entry(key).or_insert((template, source.priority));
                     }
+                    if let Some(file) = loaded.detectors {
+                        detector_layers.push(DetectorLayer {
+                            source: TemplateSource::Remote {
+                                priority: source.priority,
+                            },
+                            origin: source.url.clone(),
+                            file,
+                        });
+                    }
                 }
                 Err(e) => {
                     tracing::warn!("Failed to load templates from {}: {}", source.url, e);
@@ -61,29 +114,76 @@ impl RemoteLoader {
             }
         }
 
-        Ok(Self { templates })
+        Ok(Self {
+            templates,
+            detector_layers,
+        })
     }
 
     /// Create an empty remote loader (no sources).
     pub fn empty() -> Self {
         Self {
             templates: HashMap::new(),
+            detector_layers: Vec::new(),
         }
     }
 
+    /// Whether a source can be loaded without the network: an HTTP source
+    /// has a cache entry (expired or not), a Git source has a clone.
+    pub fn is_cached(source: &TemplateSourceConfig, git: &GitFetcher, cache: &CacheStore) -> bool {
+        match source.effective_kind() {
+            TemplateSourceKind::Http => {
+                matches!(
+                    cache.load(&http_source_id(&source.url), "_index"),
+                    Ok(Some(_))
+                )
+            }
+            TemplateSourceKind::Git => git.cached(&source.url).is_some(),
+        }
+    }
+
+    /// Fetch a source into the cache (HTTP) or clone directory (Git),
+    /// ignoring any cached copy.
+    pub fn refresh_source(
+        source: &TemplateSourceConfig,
+        http: &HttpFetcher,
+        git: &GitFetcher,
+        cache: &CacheStore,
+    ) -> Result<()> {
+        match source.effective_kind() {
+            TemplateSourceKind::Http => {
+                let response = http
+                    .fetch(&source.url)
+                    .with_context(|| format!("Failed to fetch template source: {}", source.url))?;
+                Self::parse_source(&response.content)?;
+                cache.store(
+                    &http_source_id(&source.url),
+                    "_index",
+                    &response.content,
+                    source_ttl(source),
+                )?;
+            }
+            TemplateSourceKind::Git => {
+                git.fetch(&source.url, source.git_ref.as_deref())
+                    .with_context(|| format!("Failed to clone git source: {}", source.url))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Load templates from a single HTTP source.
     fn load_http_source(
         source: &TemplateSourceConfig,
         fetcher: &HttpFetcher,
         cache: &CacheStore,
-    ) -> Result<Vec<Template>> {
-        let source_id = format!("http:{}", source.url);
+    ) -> Result<LoadedSource> {
+        let source_id = http_source_id(&source.url);
 
-        // Check cache first
+        // Check cache first; offline, an expired entry is still used
         if let Some(entry) = cache.load(&source_id, "_index")? {
-            if !entry.is_expired() {
+            if !entry.is_expired() || fetcher.is_offline() {
                 let content = cache.read_content(&entry)?;
-                return Self::parse_templates(&content);
+                return Self::parse_source(&content);
             }
         }
 
@@ -93,15 +193,9 @@ impl RemoteLoader {
             .with_context(|| format!("Failed to fetch template source: {}", source.url))?;
 
         // Cache the response
-        let ttl_seconds = source
-            .cache
-            .as_ref()
-            .and_then(|c| parse_ttl(&c.ttl).ok())
-            .map(|d| d.num_seconds() as u64)
-            .unwrap_or(604800);
-        cache.store(&source_id, "_index", &response.content, ttl_seconds)?;
-
-        Self::parse_templates(&response.content)
+        cache.store(&source_id, "_index", &response.content, source_ttl(source))?;
+
+        Self::parse_source(&response.content)
     }
 
     /// Load templates from a single Git source.
@@ -109,7 +203,8 @@ impl RemoteLoader {
     /// Clones (or updates) the repository, then walks `source.path` (or the
     /// repository root if `path` is unset) for `*.yml`/`*.yaml` files. Each
     /// file is parsed as a [`Template`]. Files that fail to parse are
-    /// reported via `tracing::warn!` and skipped.
+    /// reported via `tracing::warn!` and skipped. A `detectors.yml` at the
+    /// walk root is parsed as a [`DetectorFile`] instead.
     ///
     /// URL scheme validation is the caller's responsibility: production code
     /// goes through [`Registry::with_remote_sources`](crate::registry::Registry::with_remote_sources),
@@ -117,7 +212,7 @@ impl RemoteLoader {
     fn load_git_source(
         source: &TemplateSourceConfig,
         fetcher: &GitFetcher,
-    ) -> Result<Vec<Template>> {
+    ) -> Result<LoadedSource> {
         let result = fetcher
             .fetch_unchecked(&source.url, source.git_ref.as_deref())
             .with_context(|| format!("Failed to clone git source: {}", source.url))?;
@@ -135,9 +230,22 @@ impl RemoteLoader {
             );
         }
 
+        let detectors_path = walk_root.join("detectors.yml");
+        let detectors = if detectors_path.is_file() {
+            let content = fs::read_to_string(&detectors_path)?;
+            let file = serde_yaml::from_str(&content)
+                .with_context(|| format!("Invalid detectors.yml in {}", source.url))?;
+            Some(file)
+        } else {
+            None
+        };
+
         let mut templates = Vec::new();
         Self::walk_templates(&walk_root, &mut templates);
-        Ok(templates)
+        Ok(LoadedSource {
+            templates,
+            detectors,
+        })
     }
 
     /// Recursively walk `dir` collecting templates from `*.yml` / `*.yaml` files.
@@ -166,6 +274,11 @@ impl RemoteLoader {
                 continue;
             }
 
+            // Detector files hold detectors, not templates.
+            if path.file_name().is_some_and(|n| n == "detectors.yml") {
+                continue;
+            }
+
             let is_yaml = path
                 .extension()
                 .and_then(|e| e.to_str())
@@ -189,6 +302,34 @@ impl RemoteLoader {
         }
     }
 
+    /// Parse an HTTP source document.
+    ///
+    /// Accepts anything [`parse_templates`](Self::parse_templates) does, plus
+    /// a mapping with `templates`, `detectors` and `categories` keys.
+    fn parse_source(content: &str) -> Result<LoadedSource> {
+        if let Ok(templates) = Self::parse_templates(content) {
+            return Ok(LoadedSource {
+                templates,
+                detectors: None,
+            });
+        }
+
+        let bundle: SourceBundle = serde_yaml::from_str(content).map_err(|_| {
+            anyhow::anyhow!(
+                "Remote source returned content that is neither a template nor a list of templates"
+            )
+        })?;
+        let has_detectors = !bundle.detectors.is_empty() || !bundle.categories.is_empty();
+        let detectors = has_detectors.then_some(DetectorFile {
+            detectors: bundle.detectors,
+            categories: bundle.categories,
+        });
+        Ok(LoadedSource {
+            templates: bundle.templates,
+            detectors,
+        })
+    }
+
     /// Parse a YAML string containing one or more templates.
     ///
     /// Supports two formats:
@@ -251,6 +392,26 @@ impl RemoteLoader {
     pub fn template_names(&self) -> Vec<&str> {
         self.templates.keys().map(|s| s.as_str()).collect()
     }
+
+    /// Detector files shipped by the sources, in source order.
+    pub fn detector_layers(&self) -> &[DetectorLayer] {
+        &self.detector_layers
+    }
+}
+
+/// Cache key for an HTTP source.
+fn http_source_id(url: &str) -> String {
+    format!("http:{}", url)
+}
+
+/// How long an HTTP source stays fresh in the cache (default: one week).
+fn source_ttl(source: &TemplateSourceConfig) -> u64 {
+    source
+        .cache
+        .as_ref()
+        .and_then(|c| parse_ttl(&c.ttl).ok())
+        .map(|d| d.num_seconds() as u64)
+        .unwrap_or(604800)
 }
 
 #[cfg(test)]
@@ -313,6 +474,37 @@ step:
         assert!(result.is_err());
     }
 
+    #[test]
+    fn parse_source_bundle_with_detectors() {
+        let yaml = r#"
+templates:
+  - name: platform-lint
+    description: "Platform lint"
+    category: platform
+    detectors: [platform-config]
+    step:
+      command: platform lint
+detectors:
+  platform-config:
+    file: platform.yml
+categories:
+  - name: platform
+    description: "Platform team tooling"
+    templates: [platform-lint]
+"#;
+
+        let loaded = RemoteLoader::parse_source(yaml).unwrap();
+        assert_eq!(loaded.templates.len(), 1);
+        let detectors = loaded.detectors.unwrap();
+        assert!(detectors.detectors.contains_key("platform-config"));
+        assert_eq!(detectors.categories[0].name, "platform");
+    }
+
+    #[test]
+    fn parse_source_rejects_unknown_mapping() {
+        assert!(RemoteLoader::parse_source("something: else").is_err());
+    }
+
     fn make_source(url: String, kind: TemplateSourceKind) -> TemplateSourceConfig {
         TemplateSourceConfig {
             kind: Some(kind),
@@ -326,6 +518,68 @@ step:
         }
     }
 
+    #[test]
+    fn offline_uses_existing_clone_and_expired_cache() {
+        let _lock = GIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let temp = TempDir::new().unwrap();
+        let bare = create_bare_repo_with_templates(
+            temp.path(),
+            &[(
+                "git-tool.yml",
+                "name: git-tool\ndescription: Git tool\ncategory: tools\nstep:\n  command: x\n",
+            )],
+        );
+        let git_source = make_source(bare.to_string_lossy().to_string(), TemplateSourceKind::Git);
+        let http_source = make_source(
+            "https://templates.example.com/index.yml".to_string(),
+            TemplateSourceKind::Http,
+        );
+
+        // Clone while online, and cache the HTTP source with an expired TTL
+        let cache = CacheStore::new(temp.path().join("cache"));
+        GitFetcher::new(temp.path().join("clones"))
+            .fetch_unchecked(&git_source.url, Some("main"))
+            .unwrap();
+        cache
+            .store(
+                "http:https://templates.example.com/index.yml",
+                "_index",
+                "name: http-tool\ndescription: HTTP tool\ncategory: tools\nstep:\n  command: y\n",
+                0,
+            )
+            .unwrap();
+
+        let http = HttpFetcher::new().with_offline(true);
+        let git = GitFetcher::new(temp.path().join("clones")).with_offline(true);
+        let loader = RemoteLoader::new(&[git_source, http_source], &http, &git, &cache).unwrap();
+
+        assert!(loader.has("git-tool"));
+        assert!(loader.has("http-tool"));
+    }
+
+    #[test]
+    fn offline_reports_every_missing_source() {
+        let temp = TempDir::new().unwrap();
+        let cache = CacheStore::new(temp.path().join("cache"));
+        let http = HttpFetcher::new().with_offline(true);
+        let git = GitFetcher::new(temp.path().join("clones")).with_offline(true);
+        let sources = vec![
+            make_source(
+                "https://templates.example.com/index.yml".to_string(),
+                TemplateSourceKind::Http,
+            ),
+            make_source(
+                "https://github.com/example/templates.git".to_string(),
+                TemplateSourceKind::Git,
+            ),
+        ];
+
+        let err = RemoteLoader::new(&sources, &http, &git, &cache).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("template source https://templates.example.com/index.yml"));
+        assert!(msg.contains("git template source https://github.com/example/templates.git (main)"));
+    }
+
     #[test]
     fn loads_templates_from_git_source_root() {
         let _lock = GIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
@@ -465,4 +719,40 @@ step:
             "valid template should still be loaded even if a sibling fails to parse"
         );
     }
+
+    #[test]
+    fn git_source_ships_detectors() {
+        let _lock = GIT_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let temp = TempDir::new().unwrap();
+
+        let template_yaml = r#"
+name: platform-lint
+description: "Platform lint"
+category: platform
+detectors: [platform-config]
+step:
+  command: platform lint
+"#;
+        let detectors_yaml = "detectors:\n  platform-config:\n    file: platform.yml\n";
+        let bare = create_bare_repo_with_templates(
+            temp.path(),
+            &[
+                ("platform-lint.yml", template_yaml),
+                ("detectors.yml", detectors_yaml),
+            ],
+        );
+
+        let cache = CacheStore::new(temp.path().join("cache"));
+        let http = HttpFetcher::new();
+        let git = GitFetcher::new(temp.path().join("clones"));
+
+        let source = make_source(bare.to_string_lossy().to_string(), TemplateSourceKind::Git);
+
+        let loader = RemoteLoader::new(&[source], &http, &git, &cache).unwrap();
+        assert!(loader.has("platform-lint"));
+        let layers = loader.detector_layers();
+        assert_eq!(layers.len(), 1);
+        assert_eq!(layers[0].source, TemplateSource::Remote { priority: 50 });
+        assert!(layers[0].file.detectors.contains_key("platform-config"));
+    }
 }
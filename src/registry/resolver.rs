@@ -3,13 +3,19 @@
 //! Resolution order (first match wins):
 //! 1. Project-local (.bivvy/templates/)
 //! 2. User-local (~/.bivvy/templates/)
-//! 3. Remote (by priority) - TODO in M11
+//! 3. Remote (by priority)
 //! 4. Built-in
+//!
+//! Detectors and categories are merged across the same sources, see
+//! [`layers`](crate::registry::layers).
 
 use crate::config::schema::TemplateSource as TemplateSourceConfig;
 use crate::error::{BivvyError, Result};
 use crate::registry::builtin::BuiltinLoader;
+use crate::registry::detector::DetectorRegistry;
+use crate::registry::layers::MergedDetectors;
 use crate::registry::local::LocalLoader;
+use crate::registry::manifest::Category;
 use crate::registry::remote::RemoteLoader;
 use crate::registry::template::{Template, TemplateSource};
 use std::collections::HashMap;
@@ -52,15 +58,35 @@ pub struct Registry {
     builtin: BuiltinLoader,
     local: LocalLoader,
     remote: RemoteLoader,
+    detectors: MergedDetectors,
 }
 
 impl Registry {
     /// Create a new registry for a project (without remote sources).
     pub fn new(project_root: Option<&Path>) -> Result<Self> {
+        Self::assemble(project_root, RemoteLoader::empty())
+    }
+
+    /// Load local sources and merge detectors from every layer.
+    fn assemble(project_root: Option<&Path>, remote: RemoteLoader) -> Result<Self> {
+        let builtin = BuiltinLoader::new()?;
+        let local = LocalLoader::new(project_root)?;
+        let layers = local
+            .detector_layers()
+            .iter()
+            .chain(remote.detector_layers())
+            .cloned()
+            .collect();
+        let detectors = MergedDetectors::merge(
+            crate::registry::builtin::load_detectors()?,
+            &builtin.manifest().categories,
+            layers,
+        );
         Ok(Self {
-            builtin: BuiltinLoader::new()?,
-            local: LocalLoader::new(project_root)?,
-            remote: RemoteLoader::empty(),
+            builtin,
+            local,
+            remote,
+            detectors,
         })
     }
 
@@ -94,16 +120,18 @@ impl Registry {
 
         let remote =
             RemoteLoader::new(sources, &http_fetcher, &git_fetcher, &cache).map_err(|e| {
-                BivvyError::ConfigValidationError {
-                    message: format!("Failed to load remote templates: {}", e),
+                match e.downcast::<BivvyError>() {
+                    Ok(e @ BivvyError::OfflineUnavailable { .. }) => e,
+                    Ok(e) => BivvyError::ConfigValidationError {
+                        message: format!("Failed to load remote templates: {}", e),
+                    },
+                    Err(e) => BivvyError::ConfigValidationError {
+                        message: format!("Failed to load remote templates: {}", e),
+                    },
                 }
             })?;
 
-        Ok(Self {
-            builtin: BuiltinLoader::new()?,
-            local: LocalLoader::new(project_root)?,
-            remote,
-        })
+        Self::assemble(project_root, remote)
     }
 
     /// Create a registry with a pre-loaded remote loader (for testing).
@@ -112,11 +140,7 @@ impl Registry {
         project_root: Option<&Path>,
         remote: RemoteLoader,
     ) -> Result<Self> {
-        Ok(Self {
-            builtin: BuiltinLoader::new()?,
-            local: LocalLoader::new(project_root)?,
-            remote,
-        })
+        Self::assemble(project_root, remote)
     }
 
     /// Resolve a template by name, with optional `category/name` syntax.
@@ -234,6 +258,40 @@ impl Registry {
         &self.builtin
     }
 
+    /// Detectors merged from project, user, remote and built-in sources.
+    pub fn detectors(&self) -> DetectorRegistry {
+        self.detectors.registry()
+    }
+
+    /// Categories merged from the built-in manifest and every detector file.
+    pub fn categories(&self) -> &[Category] {
+        self.detectors.categories()
+    }
+
+    /// Warnings about detectors that shadow one another.
+    pub fn detector_warnings(&self) -> &[String] {
+        self.detectors.warnings()
+    }
+
+    /// Non-built-in templates whose detectors all pass in `project_root`.
+    ///
+    /// Templates without detectors are never suggested. Invalid references
+    /// are treated as a failed detection.
+    pub fn detected_custom_templates(&self, project_root: &Path) -> Vec<&Template> {
+        let detectors = self.detectors();
+        self.all_template_names()
+            .iter()
+            .filter_map(|n| self.resolve(n).ok())
+            .filter(|(t, source)| *source != TemplateSource::Builtin && !t.detectors.is_empty())
+            .map(|(t, _)| t)
+            .filter(|t| {
+                detectors
+                    .evaluate_all(&t.detectors, project_root)
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
     /// Validate inputs against a template's input contract.
     pub fn validate_inputs(
         &self,
@@ -123,13 +123,33 @@ pub struct TemplateInputPrompt {
     /// Question to display
     pub question: String,
 
-    /// Prompt type: select, input, confirm, multiselect
+    /// Prompt type: select, multiselect, confirm, input, secret, number, path
     #[serde(rename = "type")]
     pub prompt_type: crate::config::schema::PromptType,
 
     /// Options for select/multiselect
     #[serde(default)]
     pub options: Vec<crate::config::schema::PromptOption>,
+
+    /// Smallest accepted value (number prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+
+    /// Largest accepted value (number prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+
+    /// Regex the whole answer must match (input prompts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
+
+    /// Message shown when the answer doesn't match `pattern`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pattern_message: Option<String>,
+
+    /// What the answer must point at (path prompts): exists, file, dir
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub must_be: Option<crate::config::schema::PathRequirement>,
 }
 
 impl TemplateInput {
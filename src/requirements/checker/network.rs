@@ -6,8 +6,13 @@ use std::time::Duration;
 /// Check whether the network is reachable.
 ///
 /// Attempts a TCP connection to well-known hosts with a 2-second timeout.
-/// Returns `true` if any connection succeeds, `false` otherwise.
+/// Returns `true` if any connection succeeds, `false` otherwise. In
+/// [offline mode](crate::cache::offline) the network is reported
+/// unreachable without probing.
 pub fn check_network() -> bool {
+    if crate::cache::is_offline() {
+        return false;
+    }
     const TARGETS: &[(&str, u16)] = &[
         ("1.1.1.1", 443), // Cloudflare DNS
         ("8.8.8.8", 443), // Google DNS
@@ -112,6 +112,9 @@ pub fn default_context() -> InstallerContext<'static> {
         check_network: &|| {
             use std::net::TcpStream;
             use std::time::Duration;
+            if crate::cache::is_offline() {
+                return false;
+            }
             let timeout = Duration::from_secs(2);
             let addr: std::net::SocketAddr = "1.1.1.1:443".parse().unwrap();
             TcpStream::connect_timeout(&addr, timeout).is_ok()
@@ -0,0 +1,272 @@
+//! Change-driven step selection.
+//!
+//! Maps the files changed since a git ref onto the paths each step
+//! watches (change-check targets, deprecated `watches` globs and template
+//! detector files) and extends the result with every step that
+//! transitively depends on an affected step.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{BivvyError, Result};
+
+use super::DependencyGraph;
+
+/// A step whose watched paths matched changed files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedStep {
+    /// Step name.
+    pub name: String,
+    /// Changed files that matched one of the step's watched paths.
+    pub files: Vec<String>,
+}
+
+/// Steps invalidated by a set of changed files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Affected {
+    /// Steps that watch a changed file, by name.
+    pub direct: Vec<AffectedStep>,
+    /// Steps that depend on a directly affected step, by name.
+    pub dependents: Vec<String>,
+}
+
+impl Affected {
+    /// All affected step names: direct matches first, then dependents.
+    pub fn step_names(&self) -> Vec<String> {
+        self.direct
+            .iter()
+            .map(|s| s.name.clone())
+            .chain(self.dependents.iter().cloned())
+            .collect()
+    }
+
+    /// Whether no step is affected.
+    pub fn is_empty(&self) -> bool {
+        self.direct.is_empty() && self.dependents.is_empty()
+    }
+}
+
+/// Files changed since `since`, relative to `project_root`.
+///
+/// Compares the merge base of `since` and `HEAD` with the working tree,
+/// so commits on the current branch, uncommitted edits and untracked
+/// files all count, while commits that only landed on `since` don't.
+pub fn changed_files(project_root: &Path, since: &str) -> Result<Vec<String>> {
+    let base = git(project_root, &["merge-base", since, "HEAD"])
+        .map_err(|e| BivvyError::Other(anyhow::anyhow!("Cannot diff against '{since}': {e}")))?;
+    let base = base.trim();
+
+    let diff = git(project_root, &["diff", "--name-only", "--relative", base])
+        .map_err(|e| BivvyError::Other(anyhow::anyhow!("git diff failed: {e}")))?;
+    let untracked = git(
+        project_root,
+        &["ls-files", "--others", "--exclude-standard"],
+    )
+    .map_err(|e| BivvyError::Other(anyhow::anyhow!("git ls-files failed: {e}")))?;
+
+    let files: BTreeSet<String> = diff
+        .lines()
+        .chain(untracked.lines())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    Ok(files.into_iter().collect())
+}
+
+fn git(project_root: &Path, args: &[&str]) -> std::result::Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(project_root)
+        .output()
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// Whether a changed file matches a watched path.
+///
+/// Watched paths may be globs, exact files or directories; `./` prefixes
+/// are ignored on both sides.
+pub fn watch_matches(watched: &str, file: &str) -> bool {
+    let watched = watched.trim_start_matches("./").trim_end_matches('/');
+    let file = file.trim_start_matches("./");
+    if watched.contains(['*', '?', '[']) {
+        crate::sys::glob_match_path(watched, file)
+    } else {
+        file == watched
+            || file
+                .strip_prefix(watched)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+/// Select the steps invalidated by `changed` files.
+///
+/// `watches` maps each step to the paths it watches. Only steps in
+/// `graph` are considered.
+pub fn affected_steps(
+    watches: &BTreeMap<String, Vec<String>>,
+    changed: &[String],
+    graph: &DependencyGraph,
+) -> Affected {
+    let direct: Vec<AffectedStep> = watches
+        .iter()
+        .filter(|(name, _)| graph.contains(name))
+        .filter_map(|(name, paths)| {
+            let files: Vec<String> = changed
+                .iter()
+                .filter(|file| paths.iter().any(|p| watch_matches(p, file)))
+                .cloned()
+                .collect();
+            (!files.is_empty()).then(|| AffectedStep {
+                name: name.clone(),
+                files,
+            })
+        })
+        .collect();
+
+    let direct_names: BTreeSet<&str> = direct.iter().map(|s| s.name.as_str()).collect();
+    let dependents: BTreeSet<String> = direct
+        .iter()
+        .flat_map(|s| graph.transitive_dependents(&s.name))
+        .filter(|name| !direct_names.contains(name.as_str()))
+        .collect();
+
+    Affected {
+        direct,
+        dependents: dependents.into_iter().collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn watches(entries: &[(&str, &[&str])]) -> BTreeMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, paths)| {
+                (
+                    name.to_string(),
+                    paths.iter().map(|p| p.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    fn graph() -> DependencyGraph {
+        DependencyGraph::builder()
+            .add_step("deps", vec![])
+            .add_step("db", vec!["deps".to_string()])
+            .add_step("seed", vec!["db".to_string()])
+            .add_step("assets", vec!["deps".to_string()])
+            .build()
+            .unwrap()
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "-c",
+                "commit.gpgsign=false",
+            ])
+            .args(args)
+            .current_dir(dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn watch_matches_files_globs_and_directories() {
+        assert!(watch_matches("Gemfile.lock", "Gemfile.lock"));
+        assert!(watch_matches("./Gemfile.lock", "Gemfile.lock"));
+        assert!(!watch_matches("Gemfile", "Gemfile.lock"));
+        assert!(watch_matches("db/migrate/*.rb", "db/migrate/001_init.rb"));
+        assert!(!watch_matches("db/migrate/*.rb", "db/schema.rb"));
+        assert!(watch_matches("db/migrate", "db/migrate/001_init.rb"));
+        assert!(!watch_matches("db/migrate", "db/migrate_old.rb"));
+    }
+
+    #[test]
+    fn affected_steps_include_transitive_dependents() {
+        let watches = watches(&[
+            ("deps", &["package-lock.json"]),
+            ("db", &["db/migrate/*.rb"]),
+        ]);
+        let changed = vec!["db/migrate/002_users.rb".to_string()];
+
+        let affected = affected_steps(&watches, &changed, &graph());
+
+        assert_eq!(
+            affected.direct,
+            vec![AffectedStep {
+                name: "db".to_string(),
+                files: changed.clone(),
+            }]
+        );
+        assert_eq!(affected.dependents, vec!["seed"]);
+        assert_eq!(affected.step_names(), vec!["db", "seed"]);
+    }
+
+    #[test]
+    fn dependents_that_match_directly_are_not_repeated() {
+        let watches = watches(&[("deps", &["package-lock.json"]), ("seed", &["db/seeds.rb"])]);
+        let changed = vec!["package-lock.json".to_string(), "db/seeds.rb".to_string()];
+
+        let affected = affected_steps(&watches, &changed, &graph());
+
+        assert_eq!(affected.step_names(), vec!["deps", "seed", "assets", "db"]);
+    }
+
+    #[test]
+    fn steps_outside_the_graph_are_ignored() {
+        let watches = watches(&[("lint", &["package-lock.json"])]);
+        let changed = vec!["package-lock.json".to_string()];
+
+        assert!(affected_steps(&watches, &changed, &graph()).is_empty());
+    }
+
+    #[test]
+    fn changed_files_includes_commits_edits_and_untracked() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "-q", "-b", "main"]);
+        fs::write(dir.join("a.txt"), "a").unwrap();
+        fs::write(dir.join("b.txt"), "b").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "init"]);
+        git(dir, &["checkout", "-q", "-b", "feature"]);
+        fs::write(dir.join("a.txt"), "a2").unwrap();
+        git(dir, &["commit", "-q", "-am", "edit a"]);
+        fs::write(dir.join("b.txt"), "b2").unwrap();
+        fs::write(dir.join("c.txt"), "c").unwrap();
+
+        assert_eq!(
+            changed_files(dir, "main").unwrap(),
+            vec!["a.txt", "b.txt", "c.txt"]
+        );
+    }
+
+    #[test]
+    fn changed_files_rejects_unknown_ref() {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q"]);
+        fs::write(temp.path().join("a.txt"), "a").unwrap();
+        git(temp.path(), &["add", "."]);
+        git(temp.path(), &["commit", "-q", "-m", "init"]);
+
+        let err = changed_files(temp.path(), "no-such-ref").unwrap_err();
+        assert!(err.to_string().contains("no-such-ref"));
+    }
+}
@@ -38,15 +38,15 @@ struct RawCategoryMatch {
 // === Category signal patterns ===
 
 static RE_NOT_FOUND: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)(not found|could not find|can't find|does not exist|no such|missing|not installed|no module named|cannot find)").unwrap()
+    Regex::new(r"(?i)(not found|could not find|can't find|does not exist|no such|missing|not installed|no module named|cannot find|unknown database|initialization required|no application encryption key)").unwrap()
 });
 
 static RE_CONNECTION_REFUSED: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)(connection refused|cannot connect|could not connect|not running|server not available)").unwrap()
+    Regex::new(r"(?i)(connection refused|cannot connect|can't connect|could not connect|not running|server not available)").unwrap()
 });
 
 static RE_VERSION_MISMATCH: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)(version mismatch|incompatible|is not compatible|required version|expected version|engine.*incompatible)").unwrap()
+    Regex::new(r"(?i)(version mismatch|incompatible|is not compatible|required version|expected version|engine.*incompatible|does not satisfy|version solving failed|does not support \w+ version|tools version)").unwrap()
 });
 
 /// Structural signal: two different version numbers on the same line
@@ -75,7 +75,7 @@ static RE_RESOURCE_LIMIT: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"(?i)\b(limit|ENOSPC|too many|exceeded|quota)\b").unwrap());
 
 static RE_AUTH_FAILURE: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r"(?i)(certificate|publickey|authentication failed|unauthorized|\b401\b|\b403\b)")
+    Regex::new(r"(?i)(certificate|publickey|authentication failed|unauthorized|\b401\b|\b403\b|access denied for user|no valid credential)")
         .unwrap()
 });
 
@@ -90,14 +90,27 @@ static RE_EXTRACT_MODULE_NAME: LazyLock<Regex> = LazyLock::new(|| {
 });
 
 static RE_EXTRACT_NOT_FOUND_TARGET: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(r#"(?i)(?:not found|could not find|does not exist|no such|missing|not installed|cannot find)\s*:?\s*['"]?([^\s'",:]+)"#).unwrap()
+    Regex::new(r#"(?i)(?:not found|could not find|does not exist|no such|missing|not installed|cannot find)\b\s*:?\s*['"]?([^\s'",:]+)"#).unwrap()
 });
 
 static RE_EXTRACT_COMMAND_NOT_FOUND: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"command not found:\s*(\S+)").unwrap());
 
-static RE_EXTRACT_DB_NAME: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r#"database "([^"]+)" does not exist"#).unwrap());
+static RE_EXTRACT_DB_NAME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?:database "([^"]+)" does not exist|Unknown database '([^']+)')"#).unwrap()
+});
+
+/// A capitalized tool name followed by "not installed" (e.g., "CocoaPods not installed").
+static RE_EXTRACT_NOT_INSTALLED_TOOL: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b([A-Z]\w+) (?:is )?not installed").unwrap());
+
+/// Laravel's missing `APP_KEY`.
+static RE_EXTRACT_APP_KEY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"No application encryption key has been specified").unwrap());
+
+/// MySQL-family servers named in client errors (e.g., "local MySQL server", "Mysql2::Error").
+static RE_EXTRACT_MYSQL_SERVICE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\b(mysql|mariadb)").unwrap());
 
 static RE_EXTRACT_ROLE_NAME: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r#"role "([^"]+)" does not exist"#).unwrap());
@@ -288,13 +301,35 @@ fn extract_details(line: &str, details: &mut DiagnosticDetails) {
         }
     }
 
-    // Extract database name
+    // Extract database name (PostgreSQL or MySQL wording)
     if let Some(caps) = RE_EXTRACT_DB_NAME.captures(line) {
+        if details.target.is_none() {
+            details.target = caps
+                .get(1)
+                .or_else(|| caps.get(2))
+                .map(|m| m.as_str().to_string());
+        }
+    }
+
+    // Laravel's missing APP_KEY is reported without a quoted name
+    if RE_EXTRACT_APP_KEY.is_match(line) && details.target.is_none() {
+        details.target = Some("APP_KEY".to_string());
+    }
+
+    // Extract the tool in "<Tool> not installed"
+    if let Some(caps) = RE_EXTRACT_NOT_INSTALLED_TOOL.captures(line) {
         if details.target.is_none() {
             details.target = Some(caps[1].to_string());
         }
     }
 
+    // Extract MySQL-family service
+    if let Some(caps) = RE_EXTRACT_MYSQL_SERVICE.captures(line) {
+        if details.service.is_none() {
+            details.service = Some(caps[1].to_lowercase());
+        }
+    }
+
     // Extract role name
     if let Some(caps) = RE_EXTRACT_ROLE_NAME.captures(line) {
         if details.target.is_none() {
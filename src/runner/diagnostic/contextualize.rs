@@ -239,6 +239,10 @@ fn is_install_command(cmd: &str) -> bool {
         || cmd.contains("go mod")
         || cmd.contains("dotnet restore")
         || cmd.contains("mvn install")
+        || cmd.contains("pub get")
+        || cmd.contains("swift package resolve")
+        || cmd.contains("terraform init")
+        || cmd.contains("tofu init")
 }
 
 fn is_package_manager_command(cmd: &str) -> bool {
@@ -254,6 +258,8 @@ fn is_package_manager_command(cmd: &str) -> bool {
         || cmd.contains("maven")
         || cmd.contains("gradle")
         || cmd.contains("dotnet")
+        || cmd.contains("pub get")
+        || cmd.contains("swift package")
 }
 
 fn detect_ecosystem(cmd: &str) -> &str {
@@ -281,6 +287,12 @@ fn detect_ecosystem(cmd: &str) -> &str {
         "java"
     } else if cmd.contains("dotnet") || cmd.contains("nuget") {
         "dotnet"
+    } else if cmd.contains("flutter") || cmd.contains("dart") {
+        "dart"
+    } else if cmd.contains("swift") || cmd.contains("xcodebuild") {
+        "swift"
+    } else if cmd.contains("terraform") || cmd.contains("tofu") {
+        "terraform"
     } else if cmd.contains("docker") {
         "docker"
     } else if cmd.contains("composer") || cmd.contains("php") {
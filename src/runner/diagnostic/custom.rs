@@ -0,0 +1,233 @@
+//! Project-defined diagnostic rules.
+//!
+//! Rules declared under `diagnostics:` in config run alongside the built-in
+//! stages. A rule whose pattern matches the normalized output boosts its
+//! category and contributes a resolution candidate. The fix command may
+//! reference capture groups from the pattern as `{1}`, `{2}`, etc.
+
+use std::collections::HashMap;
+
+use regex::Regex;
+use tracing::warn;
+
+use crate::config::{DiagnosticCategory, DiagnosticPlatform, DiagnosticRule};
+use crate::runner::patterns::substitute;
+
+use super::{CategoryMatch, ErrorCategory, Platform, ResolutionCandidate, ResolutionSource};
+
+/// A diagnostic rule with its pattern compiled.
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    name: String,
+    regex: Regex,
+    rule: DiagnosticRule,
+}
+
+impl CustomRule {
+    /// Compile a config rule. Fails if the pattern is not a valid regex.
+    pub fn compile(name: &str, rule: &DiagnosticRule) -> Result<Self, regex::Error> {
+        Ok(Self {
+            name: name.to_string(),
+            regex: Regex::new(&rule.pattern)?,
+            rule: rule.clone(),
+        })
+    }
+
+    /// The rule's name as declared in config.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Compile every rule in config, sorted by name.
+///
+/// Rules with invalid patterns are skipped with a warning; `bivvy lint`
+/// reports them as errors.
+pub fn compile_rules(rules: &HashMap<String, DiagnosticRule>) -> Vec<CustomRule> {
+    let mut names: Vec<_> = rules.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| match CustomRule::compile(name, &rules[name]) {
+            Ok(rule) => Some(rule),
+            Err(e) => {
+                warn!("Skipping diagnostic rule '{}': {}", name, e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Match rules against the normalized output.
+///
+/// Categories of matching rules are merged into `categories` (keeping the
+/// higher confidence). Returns one resolution candidate per matching rule.
+pub(super) fn apply_rules(
+    rules: &[CustomRule],
+    output: &str,
+    categories: &mut Vec<CategoryMatch>,
+) -> Vec<ResolutionCandidate> {
+    let mut resolutions = Vec::new();
+
+    for custom in rules {
+        let Some(caps) = custom.regex.captures(output) else {
+            continue;
+        };
+        let rule = &custom.rule;
+        let confidence = rule.confidence.clamp(0.0, 1.0);
+
+        if let Some(category) = rule.category.map(to_error_category) {
+            match categories.iter_mut().find(|c| c.category == category) {
+                Some(existing) => existing.confidence = existing.confidence.max(confidence),
+                None => categories.push(CategoryMatch {
+                    category,
+                    confidence,
+                }),
+            }
+        }
+
+        // A fix referencing a group that did not participate is dropped
+        // rather than offered half-substituted.
+        let command = rule.fix.as_deref().and_then(|fix| substitute(fix, &caps));
+        let label = rule
+            .label
+            .as_deref()
+            .and_then(|label| substitute(label, &caps))
+            .or_else(|| command.clone())
+            .unwrap_or_else(|| custom.name.clone());
+
+        resolutions.push(ResolutionCandidate {
+            label,
+            command,
+            explanation: substitute(&rule.explanation, &caps)
+                .unwrap_or_else(|| rule.explanation.clone()),
+            confidence,
+            source: ResolutionSource::Project,
+            platform: rule.platform.map(to_platform),
+        });
+    }
+
+    categories.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    resolutions
+}
+
+fn to_error_category(category: DiagnosticCategory) -> ErrorCategory {
+    match category {
+        DiagnosticCategory::NotFound => ErrorCategory::NotFound,
+        DiagnosticCategory::ConnectionRefused => ErrorCategory::ConnectionRefused,
+        DiagnosticCategory::VersionMismatch => ErrorCategory::VersionMismatch,
+        DiagnosticCategory::SyncIssue => ErrorCategory::SyncIssue,
+        DiagnosticCategory::PermissionDenied => ErrorCategory::PermissionDenied,
+        DiagnosticCategory::PortConflict => ErrorCategory::PortConflict,
+        DiagnosticCategory::BuildFailure => ErrorCategory::BuildFailure,
+        DiagnosticCategory::ResourceLimit => ErrorCategory::ResourceLimit,
+        DiagnosticCategory::AuthFailure => ErrorCategory::AuthFailure,
+        DiagnosticCategory::SystemConstraint => ErrorCategory::SystemConstraint,
+    }
+}
+
+fn to_platform(platform: DiagnosticPlatform) -> Platform {
+    match platform {
+        DiagnosticPlatform::Macos => Platform::MacOS,
+        DiagnosticPlatform::Linux => Platform::Linux,
+        DiagnosticPlatform::Windows => Platform::Windows,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(yaml: &str) -> CustomRule {
+        let rule: DiagnosticRule = serde_yaml::from_str(yaml).unwrap();
+        CustomRule::compile("test-rule", &rule).unwrap()
+    }
+
+    #[test]
+    fn matching_rule_interpolates_captures_into_fix() {
+        let rules = vec![rule(
+            "pattern: 'profile \\(([\\w-]+)\\) could not be found'\n\
+             category: auth_failure\n\
+             explanation: The AWS profile is not configured\n\
+             fix: aws configure --profile {1}\n",
+        )];
+        let mut categories = Vec::new();
+
+        let resolutions = apply_rules(
+            &rules,
+            "The config profile (acme-dev) could not be found",
+            &mut categories,
+        );
+
+        assert_eq!(resolutions.len(), 1);
+        assert_eq!(
+            resolutions[0].command.as_deref(),
+            Some("aws configure --profile acme-dev")
+        );
+        assert_eq!(resolutions[0].label, "aws configure --profile acme-dev");
+        assert_eq!(resolutions[0].source, ResolutionSource::Project);
+        assert!((resolutions[0].confidence - 0.8).abs() < f32::EPSILON);
+        assert_eq!(categories[0].category, ErrorCategory::AuthFailure);
+    }
+
+    #[test]
+    fn non_matching_rule_contributes_nothing() {
+        let rules = vec![rule("pattern: 'VPN not connected'\nexplanation: Connect\n")];
+        let mut categories = Vec::new();
+
+        assert!(apply_rules(&rules, "everything is fine", &mut categories).is_empty());
+        assert!(categories.is_empty());
+    }
+
+    #[test]
+    fn rule_raises_existing_category_confidence() {
+        let rules = vec![rule(
+            "pattern: 'token expired'\ncategory: auth_failure\nexplanation: Refresh\nconfidence: 0.9\n",
+        )];
+        let mut categories = vec![CategoryMatch {
+            category: ErrorCategory::AuthFailure,
+            confidence: 0.4,
+        }];
+
+        apply_rules(&rules, "artifactory token expired", &mut categories);
+
+        assert_eq!(categories.len(), 1);
+        assert!((categories[0].confidence - 0.9).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn advisory_rule_uses_label_or_name() {
+        let rules = vec![rule(
+            "pattern: 'VPN not connected'\nexplanation: Connect to the corporate VPN first\n",
+        )];
+        let mut categories = Vec::new();
+
+        let resolutions = apply_rules(&rules, "error: VPN not connected", &mut categories);
+
+        assert!(resolutions[0].command.is_none());
+        assert_eq!(resolutions[0].label, "test-rule");
+    }
+
+    #[test]
+    fn compile_rules_skips_invalid_patterns() {
+        let mut rules = HashMap::new();
+        rules.insert(
+            "good".to_string(),
+            serde_yaml::from_str("pattern: 'ok'\nexplanation: x\n").unwrap(),
+        );
+        rules.insert(
+            "bad".to_string(),
+            serde_yaml::from_str("pattern: '[a-'\nexplanation: x\n").unwrap(),
+        );
+
+        let compiled = compile_rules(&rules);
+
+        assert_eq!(compiled.len(), 1);
+        assert_eq!(compiled[0].name(), "good");
+    }
+}
@@ -30,7 +30,7 @@ pub fn deduce_resolutions(
                 deduce_connection_refused(&mut resolutions, details, step_ctx, workflow_state);
             }
             ErrorCategory::VersionMismatch => {
-                deduce_version_mismatch(&mut resolutions, details);
+                deduce_version_mismatch(&mut resolutions, details, step_ctx);
             }
             ErrorCategory::SyncIssue => {
                 deduce_sync_issue(&mut resolutions, step_ctx);
@@ -62,7 +62,7 @@ pub fn deduce_resolutions(
                 deduce_system_constraint(&mut resolutions, step_ctx);
             }
             ErrorCategory::AuthFailure => {
-                deduce_auth_failure(&mut resolutions, step_ctx);
+                deduce_auth_failure(&mut resolutions, details, step_ctx);
             }
             ErrorCategory::ResourceLimit => {
                 resolutions.push(ResolutionCandidate {
@@ -88,6 +88,25 @@ fn deduce_not_found(
 ) {
     let target = details.target.as_deref().unwrap_or("unknown");
 
+    // Uninitialized Terraform working directory (missing backend, modules or providers)
+    if let Some(tf) = terraform_binary(step_ctx.command) {
+        resolutions.push(ResolutionCandidate {
+            label: format!("{} init", tf),
+            command: Some(format!("{} init", tf)),
+            explanation: "The working directory has not been initialized".to_string(),
+            confidence: 0.6,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+        return;
+    }
+
+    // Tool-level gaps with a well-known remedy
+    if let Some(candidate) = deduce_missing_tool(target, step_ctx) {
+        resolutions.push(candidate);
+        return;
+    }
+
     // Check if install step for this ecosystem already succeeded — the issue
     // is likely a wrong package name or missing entry in the dependency manifest.
     if contextualize::has_install_step_succeeded(step_ctx, workflow_state) && target != "unknown" {
@@ -141,9 +160,14 @@ fn deduce_not_found(
         || step_ctx.command.contains("migrate")
         || step_ctx.command.contains("createdb")
     {
+        let create = if is_mysql(details, step_ctx) {
+            format!("mysqladmin -u root create {}", target)
+        } else {
+            format!("createdb {}", target)
+        };
         resolutions.push(ResolutionCandidate {
-            label: format!("createdb {}", target),
-            command: Some(format!("createdb {}", target)),
+            label: create.clone(),
+            command: Some(create),
             explanation: format!("Database '{}' does not exist", target),
             confidence: 0.5,
             source: ResolutionSource::Deduced,
@@ -168,6 +192,37 @@ fn deduce_not_found(
     }
 }
 
+/// Remedies for tools reported missing by name (Xcode tooling, CocoaPods,
+/// Laravel's APP_KEY).
+fn deduce_missing_tool(target: &str, step_ctx: &StepContext<'_>) -> Option<ResolutionCandidate> {
+    let (command, explanation, platform) = match target {
+        "xcrun" | "xcodebuild" => (
+            "xcode-select --install",
+            "Xcode Command Line Tools are missing or broken",
+            Some(Platform::MacOS),
+        ),
+        "CocoaPods" => (
+            "brew install cocoapods",
+            "CocoaPods is required to build iOS and macOS targets",
+            Some(Platform::MacOS),
+        ),
+        "APP_KEY" if step_ctx.command.contains("artisan") || step_ctx.command.contains("php") => (
+            "php artisan key:generate",
+            "APP_KEY is not set in .env",
+            None,
+        ),
+        _ => return None,
+    };
+    Some(ResolutionCandidate {
+        label: command.to_string(),
+        command: Some(command.to_string()),
+        explanation: explanation.to_string(),
+        confidence: 0.6,
+        source: ResolutionSource::Deduced,
+        platform,
+    })
+}
+
 fn deduce_connection_refused(
     resolutions: &mut Vec<ResolutionCandidate>,
     details: &DiagnosticDetails,
@@ -205,6 +260,7 @@ fn deduce_connection_refused(
                 r.contains("postgres")
                     || r.contains("redis")
                     || r.contains("mysql")
+                    || r.contains("mariadb")
                     || r.contains("mongo")
             })
             .map(|s| s.as_str())
@@ -267,7 +323,14 @@ fn is_docker_daemon_error(step_ctx: &StepContext<'_>) -> bool {
 fn deduce_version_mismatch(
     resolutions: &mut Vec<ResolutionCandidate>,
     details: &DiagnosticDetails,
+    step_ctx: &StepContext<'_>,
 ) {
+    // Toolchain constraints in ecosystems that declare them in their manifest
+    if let Some(candidate) = deduce_toolchain_upgrade(step_ctx) {
+        resolutions.push(candidate);
+        return;
+    }
+
     if let (Some(have), Some(need)) = (&details.version_have, &details.version_need) {
         // Extract major version from need for brew formula
         let major = need.split('.').next().unwrap_or(need);
@@ -322,6 +385,59 @@ fn deduce_version_mismatch(
     }
 }
 
+/// Suggest a toolchain upgrade when a manifest's SDK or language constraint
+/// is not met (pubspec `environment.sdk`, composer `require.php`, Swift tools
+/// version, Terraform `required_version`).
+fn deduce_toolchain_upgrade(step_ctx: &StepContext<'_>) -> Option<ResolutionCandidate> {
+    let cmd = step_ctx.command;
+    let (label, command, explanation, confidence) = if cmd.contains("flutter") {
+        (
+            "flutter upgrade",
+            Some("flutter upgrade"),
+            "The Flutter SDK does not satisfy the pubspec SDK constraint",
+            0.6,
+        )
+    } else if cmd.contains("dart") {
+        (
+            "upgrade the Dart SDK",
+            None,
+            "The Dart SDK does not satisfy the pubspec SDK constraint",
+            0.4,
+        )
+    } else if cmd.contains("composer") || cmd.contains("php") {
+        (
+            "install a PHP version that satisfies composer.json",
+            None,
+            "The installed PHP version does not satisfy a package's requirement",
+            0.4,
+        )
+    } else if cmd.contains("swift") || cmd.contains("xcodebuild") {
+        (
+            "update Xcode or install a newer Swift toolchain",
+            None,
+            "The package needs a newer Swift tools version",
+            0.4,
+        )
+    } else if terraform_binary(cmd).is_some() {
+        (
+            "install a Terraform version allowed by required_version",
+            None,
+            "The installed Terraform does not satisfy required_version",
+            0.4,
+        )
+    } else {
+        return None;
+    };
+    Some(ResolutionCandidate {
+        label: label.to_string(),
+        command: command.map(str::to_string),
+        explanation: explanation.to_string(),
+        confidence,
+        source: ResolutionSource::Deduced,
+        platform: None,
+    })
+}
+
 fn deduce_sync_issue(resolutions: &mut Vec<ResolutionCandidate>, step_ctx: &StepContext<'_>) {
     let cmd = step_ctx.command;
     if cmd.contains("bundle") {
@@ -342,6 +458,48 @@ fn deduce_sync_issue(resolutions: &mut Vec<ResolutionCandidate>, step_ctx: &Step
             source: ResolutionSource::Deduced,
             platform: None,
         });
+    } else if cmd.contains("composer") {
+        resolutions.push(ResolutionCandidate {
+            label: "composer update --lock".to_string(),
+            command: Some("composer update --lock".to_string()),
+            explanation: "composer.lock may be out of sync with composer.json".to_string(),
+            confidence: 0.5,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+    } else if let Some(tf) = terraform_binary(cmd) {
+        resolutions.push(ResolutionCandidate {
+            label: format!("{} init -upgrade", tf),
+            command: Some(format!("{} init -upgrade", tf)),
+            explanation: ".terraform.lock.hcl may be out of sync with the configured providers"
+                .to_string(),
+            confidence: 0.5,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+    } else if cmd.contains("flutter") || cmd.contains("dart") {
+        let get_cmd = if cmd.contains("flutter") {
+            "flutter pub get"
+        } else {
+            "dart pub get"
+        };
+        resolutions.push(ResolutionCandidate {
+            label: get_cmd.to_string(),
+            command: Some(get_cmd.to_string()),
+            explanation: "pubspec.lock may be out of sync with pubspec.yaml".to_string(),
+            confidence: 0.5,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+    } else if cmd.contains("swift") {
+        resolutions.push(ResolutionCandidate {
+            label: "swift package resolve".to_string(),
+            command: Some("swift package resolve".to_string()),
+            explanation: "Package.resolved may be out of sync with Package.swift".to_string(),
+            confidence: 0.5,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
     } else if cmd.contains("npm") || cmd.contains("yarn") {
         let lock_cmd = if cmd.contains("yarn") {
             "yarn install"
@@ -474,7 +632,35 @@ fn deduce_system_constraint(
     }
 }
 
-fn deduce_auth_failure(resolutions: &mut Vec<ResolutionCandidate>, step_ctx: &StepContext<'_>) {
+fn deduce_auth_failure(
+    resolutions: &mut Vec<ResolutionCandidate>,
+    details: &DiagnosticDetails,
+    step_ctx: &StepContext<'_>,
+) {
+    if is_mysql(details, step_ctx) {
+        resolutions.push(ResolutionCandidate {
+            label: "check the MySQL username and password".to_string(),
+            command: None,
+            explanation:
+                "MySQL rejected the credentials — check DATABASE_URL or your database config"
+                    .to_string(),
+            confidence: 0.4,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+        return;
+    }
+    if terraform_binary(step_ctx.command).is_some() {
+        resolutions.push(ResolutionCandidate {
+            label: "configure cloud credentials (e.g., aws sso login or AWS_PROFILE)".to_string(),
+            command: None,
+            explanation: "The provider could not find valid credentials".to_string(),
+            confidence: 0.4,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+        return;
+    }
     if step_ctx.command.contains("git") || step_ctx.command.contains("ssh") {
         resolutions.push(ResolutionCandidate {
             label: "check SSH keys".to_string(),
@@ -488,12 +674,36 @@ fn deduce_auth_failure(resolutions: &mut Vec<ResolutionCandidate>, step_ctx: &St
     }
 }
 
+/// The Terraform-compatible binary the step invokes, if any.
+fn terraform_binary(cmd: &str) -> Option<&'static str> {
+    if cmd.contains("tofu") {
+        Some("tofu")
+    } else if cmd.contains("terraform") {
+        Some("terraform")
+    } else {
+        None
+    }
+}
+
+/// Whether the failure involves a MySQL-family server.
+fn is_mysql(details: &DiagnosticDetails, step_ctx: &StepContext<'_>) -> bool {
+    details.service.is_some()
+        || step_ctx.command.contains("mysql")
+        || step_ctx.command.contains("mariadb")
+        || step_ctx
+            .requires
+            .iter()
+            .any(|r| r.contains("mysql") || r.contains("mariadb"))
+}
+
 /// Extract a short service name from a requirement string.
 fn extract_service_short_name(service: &str) -> &str {
     if service.contains("postgres") {
         "postgresql"
     } else if service.contains("redis") {
         "redis"
+    } else if service.contains("mariadb") {
+        "mariadb"
     } else if service.contains("mysql") {
         "mysql"
     } else if service.contains("mongo") {
@@ -0,0 +1,668 @@
+//! Learned recoveries: fixes that worked before, keyed by diagnosis fingerprint.
+//!
+//! When a step fails, is recovered through the recovery menu, and then
+//! passes, the command that fixed it is recorded against the failure's
+//! fingerprint. Later diagnoses with the same fingerprint boost the matching
+//! candidates, or add the learned command as a new candidate.
+//!
+//! Entries are stored as YAML in `~/.bivvy/learned-fixes.yml`. Failure
+//! signatures are scrubbed the same way as failure reports. Commands must
+//! stay runnable, so commands containing secrets are never stored and paths
+//! are made relative to the project or home directory. The file can be
+//! exported and shared with a team.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::error::{BivvyError, Result};
+use crate::runner::telemetry::{contains_secret, scrub_for_telemetry};
+
+use super::segment::{LineTag, TaggedLine};
+use super::{CategoryMatch, Diagnosis, ResolutionCandidate, ResolutionSource};
+
+/// Maximum length of the error line kept in a signature.
+const MAX_SIGNATURE_LENGTH: usize = 120;
+
+/// Confidence added per recorded success to a matching built-in candidate.
+const BOOST_PER_SUCCESS: f32 = 0.1;
+
+/// Cap on the total boost from learned successes.
+const MAX_BOOST: f32 = 0.3;
+
+/// Highest confidence a learned fix can reach.
+const MAX_LEARNED_CONFIDENCE: f32 = 0.95;
+
+/// How a learned fix was originally applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LearnedSource {
+    /// A fix suggested in the recovery menu.
+    Suggested,
+    /// A command typed at the "Custom fix" prompt.
+    Custom,
+    /// A command run in the debug shell.
+    Shell,
+}
+
+impl LearnedSource {
+    /// Confidence for a learned fix with a single success.
+    ///
+    /// Shell commands start lower because a debug session may include
+    /// commands that did not contribute to the fix.
+    fn base_confidence(self) -> f32 {
+        match self {
+            LearnedSource::Suggested | LearnedSource::Custom => 0.6,
+            LearnedSource::Shell => 0.3,
+        }
+    }
+}
+
+/// A command that fixed a failure with a given fingerprint.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LearnedFix {
+    /// Fingerprint of the diagnosis the command fixed.
+    pub fingerprint: String,
+    /// Scrubbed, human-readable summary of the failure.
+    pub signature: String,
+    /// Scrubbed fix command.
+    pub command: String,
+    /// How the command was applied.
+    pub source: LearnedSource,
+    /// Number of times the command fixed this failure.
+    pub successes: u32,
+    /// When the command last fixed this failure.
+    pub last_success: DateTime<Utc>,
+}
+
+/// On-disk layout of the learned fixes file.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct LearnedFile {
+    #[serde(default)]
+    fixes: Vec<LearnedFix>,
+}
+
+/// Counts from merging an exported file into the store.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    /// Entries that were not in the store yet.
+    pub added: usize,
+    /// Existing entries whose success count or timestamp increased.
+    pub updated: usize,
+}
+
+/// Default path of the learned fixes file.
+pub fn default_learned_path() -> PathBuf {
+    crate::sys::home_dir()
+        .unwrap_or_else(|| PathBuf::from("~"))
+        .join(".bivvy")
+        .join("learned-fixes.yml")
+}
+
+/// File-backed store of learned fixes.
+#[derive(Debug, Clone)]
+pub struct LearnedStore {
+    path: PathBuf,
+}
+
+impl LearnedStore {
+    /// Create a store backed by `path`. The file is created on first write.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Path of the backing file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Load every learned fix, most successful first.
+    pub fn list(&self) -> Result<Vec<LearnedFix>> {
+        let mut fixes = read_fixes(&self.path)?;
+        sort_fixes(&mut fixes);
+        Ok(fixes)
+    }
+
+    /// Load the learned fixes for a fingerprint.
+    pub fn for_fingerprint(&self, fingerprint: &str) -> Result<Vec<LearnedFix>> {
+        Ok(self
+            .list()?
+            .into_iter()
+            .filter(|f| f.fingerprint == fingerprint)
+            .collect())
+    }
+
+    /// Record that `command` fixed the failure diagnosed as `diagnosis`.
+    ///
+    /// Commands containing a secret from `step_env` or a token are skipped.
+    /// `project_root` and the home directory are replaced with `.` and `~`.
+    /// Recording the same command again increments its count.
+    pub fn record(
+        &self,
+        diagnosis: &Diagnosis,
+        command: &str,
+        source: LearnedSource,
+        step_env: &HashMap<String, String>,
+        project_root: &Path,
+    ) -> Result<()> {
+        let command = command.trim();
+        if command.is_empty() || contains_secret(command, step_env) {
+            return Ok(());
+        }
+        let command = relativize(command, project_root);
+        let mut fixes = read_fixes(&self.path)?;
+        let now = Utc::now();
+        match fixes
+            .iter_mut()
+            .find(|f| f.fingerprint == diagnosis.fingerprint && f.command == command)
+        {
+            Some(existing) => {
+                existing.successes += 1;
+                existing.last_success = now;
+                existing.signature = diagnosis.signature.clone();
+            }
+            None => fixes.push(LearnedFix {
+                fingerprint: diagnosis.fingerprint.clone(),
+                signature: diagnosis.signature.clone(),
+                command,
+                source,
+                successes: 1,
+                last_success: now,
+            }),
+        }
+        write_fixes(&self.path, fixes)
+    }
+
+    /// Merge fixes from an exported file into the store.
+    ///
+    /// Entries are matched on fingerprint and command. For a match, the
+    /// higher success count and the later timestamp win, so importing the
+    /// same file twice does not inflate counts.
+    pub fn import(&self, from: &Path) -> Result<ImportSummary> {
+        let incoming = read_existing_fixes(from)?;
+        let mut fixes = read_fixes(&self.path)?;
+        let mut summary = ImportSummary::default();
+        for fix in incoming {
+            match fixes
+                .iter_mut()
+                .find(|f| f.fingerprint == fix.fingerprint && f.command == fix.command)
+            {
+                Some(existing) => {
+                    if fix.successes > existing.successes
+                        || fix.last_success > existing.last_success
+                    {
+                        existing.successes = existing.successes.max(fix.successes);
+                        existing.last_success = existing.last_success.max(fix.last_success);
+                        summary.updated += 1;
+                    }
+                }
+                None => {
+                    fixes.push(fix);
+                    summary.added += 1;
+                }
+            }
+        }
+        write_fixes(&self.path, fixes)?;
+        Ok(summary)
+    }
+
+    /// Write every learned fix to `to`, in the format [`import`](Self::import) reads.
+    pub fn export(&self, to: &Path) -> Result<usize> {
+        let fixes = self.list()?;
+        let count = fixes.len();
+        write_fixes(to, fixes)?;
+        Ok(count)
+    }
+
+    /// Remove every learned fix. Returns how many were removed.
+    pub fn clear(&self) -> Result<usize> {
+        let count = read_fixes(&self.path)?.len();
+        if self.path.exists() {
+            std::fs::remove_file(&self.path)?;
+        }
+        Ok(count)
+    }
+}
+
+/// Boost or add resolution candidates from learned fixes.
+///
+/// Only fixes whose fingerprint matches the diagnosis are considered. A
+/// candidate that runs a learned command gains confidence for each recorded
+/// success; a learned command with no matching candidate is added as a new
+/// [`ResolutionSource::Learned`] candidate. Resolutions are re-sorted.
+pub fn apply_learned(diagnosis: &mut Diagnosis, learned: &[LearnedFix]) {
+    for fix in learned
+        .iter()
+        .filter(|f| f.fingerprint == diagnosis.fingerprint)
+    {
+        let times = if fix.successes == 1 {
+            "once".to_string()
+        } else {
+            format!("{} times", fix.successes)
+        };
+        let boost = (fix.successes as f32 * BOOST_PER_SUCCESS).min(MAX_BOOST);
+        match diagnosis
+            .resolutions
+            .iter_mut()
+            .find(|r| r.command.as_deref() == Some(fix.command.as_str()))
+        {
+            Some(existing) => {
+                existing.confidence = (existing.confidence + boost).min(MAX_LEARNED_CONFIDENCE);
+                existing.explanation = format!(
+                    "{} (fixed this failure {} before)",
+                    existing.explanation, times
+                );
+            }
+            None => diagnosis.resolutions.push(ResolutionCandidate {
+                label: fix.command.clone(),
+                command: Some(fix.command.clone()),
+                explanation: format!("Fixed this failure {} before", times),
+                confidence: (fix.source.base_confidence() + boost - BOOST_PER_SUCCESS)
+                    .min(MAX_LEARNED_CONFIDENCE),
+                source: ResolutionSource::Learned,
+                platform: None,
+            }),
+        }
+    }
+
+    diagnosis.resolutions.sort_by(|a, b| {
+        b.confidence
+            .partial_cmp(&a.confidence)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+}
+
+/// Compute the fingerprint and signature of a failure.
+///
+/// The fingerprint hashes the step's program, the top error category and
+/// the first error line with paths, secrets and numbers normalized, so the
+/// same failure matches across runs, machines and tool versions.
+pub(super) fn fingerprint(
+    command: &str,
+    categories: &[CategoryMatch],
+    tagged_lines: &[TaggedLine],
+) -> (String, String) {
+    let program = program_name(command);
+    let category = categories
+        .first()
+        .map(|c| format!("{:?}", c.category))
+        .unwrap_or_default();
+    let line = tagged_lines
+        .iter()
+        .find(|l| l.tags.contains(&LineTag::ErrorSignal))
+        .or_else(|| tagged_lines.iter().find(|l| !l.text.trim().is_empty()))
+        .map(|l| normalize_line(&l.text))
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("program:{}\n", program));
+    hasher.update(format!("category:{}\n", category));
+    hasher.update(format!("line:{}\n", line));
+    let fingerprint = hex::encode(&hasher.finalize()[..8]);
+
+    let signature = if program.is_empty() {
+        line
+    } else {
+        format!("{}: {}", program, line)
+    };
+    (fingerprint, signature)
+}
+
+/// Replace the project root with `.` and the home directory with `~`.
+fn relativize(command: &str, project_root: &Path) -> String {
+    let mut command = command.replace(&format!("{}/", project_root.display()), "./");
+    command = command.replace(&project_root.display().to_string(), ".");
+    if let Some(home) = crate::sys::home_dir() {
+        command = command.replace(&home.display().to_string(), "~");
+    }
+    command
+}
+
+/// The program a step command runs, skipping leading `VAR=value` assignments.
+fn program_name(command: &str) -> String {
+    command
+        .split_whitespace()
+        .find(|word| !word.contains('='))
+        .map(|word| word.rsplit('/').next().unwrap_or(word).to_string())
+        .unwrap_or_default()
+}
+
+/// Scrub an error line and replace digit runs so versions, ports and line
+/// numbers don't split fingerprints.
+fn normalize_line(line: &str) -> String {
+    let scrubbed = scrub_for_telemetry(line.trim(), &HashMap::new());
+    let mut out = String::with_capacity(scrubbed.len());
+    let mut in_digits = false;
+    let mut in_space = false;
+    for ch in scrubbed.chars() {
+        if ch.is_ascii_digit() {
+            if !in_digits {
+                out.push('#');
+            }
+            in_digits = true;
+            in_space = false;
+        } else if ch.is_whitespace() {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+            in_digits = false;
+        } else {
+            out.push(ch);
+            in_digits = false;
+            in_space = false;
+        }
+    }
+    if let Some((idx, _)) = out.char_indices().nth(MAX_SIGNATURE_LENGTH) {
+        out.truncate(idx);
+    }
+    out
+}
+
+fn sort_fixes(fixes: &mut [LearnedFix]) {
+    fixes.sort_by(|a, b| {
+        b.successes
+            .cmp(&a.successes)
+            .then_with(|| b.last_success.cmp(&a.last_success))
+    });
+}
+
+/// Read fixes from `path`, treating a missing file as empty.
+fn read_fixes(path: &Path) -> Result<Vec<LearnedFix>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    read_existing_fixes(path)
+}
+
+fn read_existing_fixes(path: &Path) -> Result<Vec<LearnedFix>> {
+    let content = std::fs::read_to_string(path)?;
+    let file: LearnedFile =
+        serde_yaml::from_str(&content).map_err(|e| BivvyError::ConfigParseError {
+            path: path.to_path_buf(),
+            message: e.to_string(),
+        })?;
+    Ok(file.fixes)
+}
+
+fn write_fixes(path: &Path, mut fixes: Vec<LearnedFix>) -> Result<()> {
+    sort_fixes(&mut fixes);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let content = serde_yaml::to_string(&LearnedFile { fixes }).map_err(|e| {
+        BivvyError::ConfigValidationError {
+            message: format!("Failed to serialize learned fixes: {}", e),
+        }
+    })?;
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{diagnose, StepContext, WorkflowState};
+    use super::*;
+
+    fn diagnose_bundle(error: &str) -> Diagnosis {
+        let ctx = StepContext {
+            name: "deps",
+            command: "bundle install",
+            requires: &[],
+            template: None,
+        };
+        let outcomes = HashMap::new();
+        let ws = WorkflowState {
+            steps: &[],
+            outcomes: &outcomes,
+        };
+        diagnose(error, &ctx, &ws)
+    }
+
+    const ERROR: &str = "Installing pg 1.5.4 with native extensions\n\
+                         Gem::Ext::BuildError: ERROR: Failed to build gem native extension.\n\
+                         checking for pg_config... no\n\
+                         An error occurred while installing pg (1.5.4), and Bundler cannot continue.";
+
+    #[test]
+    fn fingerprint_ignores_versions_and_paths() {
+        let a = diagnose_bundle(ERROR);
+        let b = diagnose_bundle(
+            &ERROR
+                .replace("1.5.4", "1.6.0")
+                .replace("native extensions", "native extensions in /home/dev/app"),
+        );
+        assert_eq!(a.fingerprint, b.fingerprint);
+        assert!(a.signature.starts_with("bundle: "));
+        assert!(!a.signature.contains("1.5.4"));
+    }
+
+    #[test]
+    fn fingerprint_differs_for_different_failures() {
+        let a = diagnose_bundle(ERROR);
+        let b = diagnose_bundle("Could not find gem 'rails' in locally installed gems.");
+        assert_ne!(a.fingerprint, b.fingerprint);
+    }
+
+    #[test]
+    fn program_name_skips_env_assignments() {
+        assert_eq!(program_name("RAILS_ENV=test bin/rails db:prepare"), "rails");
+        assert_eq!(program_name(""), "");
+    }
+
+    #[test]
+    fn record_increments_and_skips_secrets() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LearnedStore::new(dir.path().join("learned.yml"));
+        let diag = diagnose_bundle(ERROR);
+        let mut env = HashMap::new();
+        env.insert("API_TOKEN".to_string(), "s3cr3t-value".to_string());
+
+        for _ in 0..2 {
+            store
+                .record(
+                    &diag,
+                    "brew install libpq",
+                    LearnedSource::Shell,
+                    &env,
+                    dir.path(),
+                )
+                .unwrap();
+        }
+        store
+            .record(
+                &diag,
+                "TOKEN=s3cr3t-value bundle config",
+                LearnedSource::Custom,
+                &env,
+                dir.path(),
+            )
+            .unwrap();
+
+        let fixes = store.for_fingerprint(&diag.fingerprint).unwrap();
+        assert_eq!(fixes.len(), 1);
+        assert_eq!(fixes[0].command, "brew install libpq");
+        assert_eq!(fixes[0].successes, 2);
+    }
+
+    #[test]
+    fn record_makes_project_paths_relative() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LearnedStore::new(dir.path().join("learned.yml"));
+        let diag = diagnose_bundle(ERROR);
+        let root = Path::new("/work/app");
+
+        store
+            .record(
+                &diag,
+                "cp /work/app/.env.example /work/app/.env",
+                LearnedSource::Custom,
+                &HashMap::new(),
+                root,
+            )
+            .unwrap();
+        assert_eq!(store.list().unwrap()[0].command, "cp ./.env.example ./.env");
+    }
+
+    #[test]
+    fn apply_learned_adds_new_candidate() {
+        let mut diag = diagnose_bundle(ERROR);
+        let fix = LearnedFix {
+            fingerprint: diag.fingerprint.clone(),
+            signature: diag.signature.clone(),
+            command: "brew install libpq".to_string(),
+            source: LearnedSource::Custom,
+            successes: 2,
+            last_success: Utc::now(),
+        };
+        apply_learned(&mut diag, &[fix]);
+
+        let learned = diag
+            .resolutions
+            .iter()
+            .find(|r| r.source == ResolutionSource::Learned)
+            .unwrap();
+        assert_eq!(learned.command.as_deref(), Some("brew install libpq"));
+        assert!((learned.confidence - 0.7).abs() < 0.001);
+        assert_eq!(learned.explanation, "Fixed this failure 2 times before");
+    }
+
+    #[test]
+    fn apply_learned_boosts_matching_candidate() {
+        let mut diag = diagnose_bundle(ERROR);
+        diag.resolutions.push(ResolutionCandidate {
+            label: "gem install pg".to_string(),
+            command: Some("gem install pg".to_string()),
+            explanation: "Reinstall pg".to_string(),
+            confidence: 0.2,
+            source: ResolutionSource::Deduced,
+            platform: None,
+        });
+        let fix = LearnedFix {
+            fingerprint: diag.fingerprint.clone(),
+            signature: diag.signature.clone(),
+            command: "gem install pg".to_string(),
+            source: LearnedSource::Suggested,
+            successes: 5,
+            last_success: Utc::now(),
+        };
+        apply_learned(&mut diag, &[fix]);
+
+        let boosted = diag
+            .resolutions
+            .iter()
+            .find(|r| r.command.as_deref() == Some("gem install pg"))
+            .unwrap();
+        assert!((boosted.confidence - 0.5).abs() < 0.001);
+        assert_eq!(boosted.source, ResolutionSource::Deduced);
+        assert!(boosted
+            .explanation
+            .ends_with("(fixed this failure 5 times before)"));
+    }
+
+    #[test]
+    fn apply_learned_ignores_other_fingerprints() {
+        let mut diag = diagnose_bundle(ERROR);
+        let before = diag.resolutions.len();
+        let fix = LearnedFix {
+            fingerprint: "0000000000000000".to_string(),
+            signature: String::new(),
+            command: "brew install libpq".to_string(),
+            source: LearnedSource::Custom,
+            successes: 1,
+            last_success: Utc::now(),
+        };
+        apply_learned(&mut diag, &[fix]);
+        assert_eq!(diag.resolutions.len(), before);
+    }
+
+    #[test]
+    fn import_merges_without_inflating_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let ours = LearnedStore::new(dir.path().join("ours.yml"));
+        let theirs = LearnedStore::new(dir.path().join("theirs.yml"));
+        let diag = diagnose_bundle(ERROR);
+        let env = HashMap::new();
+
+        let root = dir.path();
+        ours.record(
+            &diag,
+            "brew install libpq",
+            LearnedSource::Shell,
+            &env,
+            root,
+        )
+        .unwrap();
+        for _ in 0..3 {
+            theirs
+                .record(
+                    &diag,
+                    "brew install libpq",
+                    LearnedSource::Shell,
+                    &env,
+                    root,
+                )
+                .unwrap();
+        }
+        theirs
+            .record(
+                &diag,
+                "gem install pg",
+                LearnedSource::Suggested,
+                &env,
+                root,
+            )
+            .unwrap();
+
+        let export = dir.path().join("export.yml");
+        assert_eq!(theirs.export(&export).unwrap(), 2);
+
+        let summary = ours.import(&export).unwrap();
+        assert_eq!(
+            summary,
+            ImportSummary {
+                added: 1,
+                updated: 1
+            }
+        );
+        let again = ours.import(&export).unwrap();
+        assert_eq!(again, ImportSummary::default());
+
+        let fixes = ours.list().unwrap();
+        assert_eq!(fixes[0].command, "brew install libpq");
+        assert_eq!(fixes[0].successes, 3);
+    }
+
+    #[test]
+    fn missing_file_is_empty_and_clear_removes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = LearnedStore::new(dir.path().join("learned.yml"));
+        assert!(store.list().unwrap().is_empty());
+        assert_eq!(store.clear().unwrap(), 0);
+
+        let diag = diagnose_bundle(ERROR);
+        store
+            .record(
+                &diag,
+                "brew install libpq",
+                LearnedSource::Shell,
+                &HashMap::new(),
+                dir.path(),
+            )
+            .unwrap();
+        assert_eq!(store.clear().unwrap(), 1);
+        assert!(!store.path().exists());
+    }
+
+    #[test]
+    fn import_rejects_invalid_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let bad = dir.path().join("bad.yml");
+        std::fs::write(&bad, "fixes: [not, a, fix]").unwrap();
+        let store = LearnedStore::new(dir.path().join("learned.yml"));
+        assert!(store.import(&bad).is_err());
+    }
+}
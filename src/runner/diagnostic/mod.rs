@@ -14,11 +14,18 @@
 //! 4. **Contextualize** — refine using step context and workflow state
 //! 5. **Extract** — pull resolution candidates from tool output
 //! 6. **Deduce** — generate heuristic resolutions from diagnosis + context
+//!
+//! Project-defined rules from the `diagnostics:` config section are matched
+//! after classification and merged with the built-in resolutions. Each
+//! diagnosis carries a fingerprint that [`apply_learned`] uses to boost
+//! fixes that resolved the same failure before.
 
 mod classify;
 mod contextualize;
+mod custom;
 mod deduce;
 mod extract;
+mod learned;
 mod normalize;
 mod segment;
 
@@ -27,6 +34,10 @@ use std::collections::HashMap;
 use crate::steps::{ResolvedStep, StepStatus};
 
 pub use classify::ErrorCategory;
+pub use custom::{compile_rules, CustomRule};
+pub use learned::{
+    apply_learned, default_learned_path, ImportSummary, LearnedFix, LearnedSource, LearnedStore,
+};
 pub use segment::LineTag;
 
 /// Context about the step that failed, used for diagnostic filtering.
@@ -82,6 +93,10 @@ pub enum ResolutionSource {
     Extracted,
     /// Deduced from diagnosis + context.
     Deduced,
+    /// Declared by a project diagnostic rule.
+    Project,
+    /// Recorded after it fixed the same failure before.
+    Learned,
 }
 
 /// Platform constraint for a resolution.
@@ -121,6 +136,10 @@ pub struct Diagnosis {
     pub details: DiagnosticDetails,
     /// Ranked resolution candidates.
     pub resolutions: Vec<ResolutionCandidate>,
+    /// Stable hash identifying this failure across runs.
+    pub fingerprint: String,
+    /// Scrubbed summary of the failure (program and first error line).
+    pub signature: String,
 }
 
 /// Run the full diagnostic funnel pipeline.
@@ -132,6 +151,20 @@ pub fn diagnose(
     error_output: &str,
     step_context: &StepContext<'_>,
     workflow_state: &WorkflowState<'_>,
+) -> Diagnosis {
+    diagnose_with_rules(error_output, step_context, workflow_state, &[])
+}
+
+/// Run the diagnostic funnel with project-defined rules.
+///
+/// Like [`diagnose`], but also matches `rules` against the normalized
+/// output. Matching rules feed their category into classification and
+/// their resolution into the merged, ranked candidates.
+pub fn diagnose_with_rules(
+    error_output: &str,
+    step_context: &StepContext<'_>,
+    workflow_state: &WorkflowState<'_>,
+    rules: &[CustomRule],
 ) -> Diagnosis {
     // Stage 1: Normalize
     let normalized = normalize::normalize(error_output);
@@ -142,6 +175,9 @@ pub fn diagnose(
     // Stage 3: Classify
     let (mut categories, mut details) = classify::classify(&tagged_lines);
 
+    // Project rules share classification with the built-in signals
+    let project = custom::apply_rules(rules, &normalized, &mut categories);
+
     // Stage 4: Contextualize
     contextualize::contextualize(&mut categories, &mut details, step_context, workflow_state);
 
@@ -153,6 +189,7 @@ pub fn diagnose(
 
     // Merge and deduplicate
     deduce::merge_resolutions(&mut resolutions, deduced);
+    deduce::merge_resolutions(&mut resolutions, project);
 
     // Sort by confidence descending
     resolutions.sort_by(|a, b| {
@@ -164,11 +201,16 @@ pub fn diagnose(
     // Overall confidence is the max category confidence
     let confidence = categories.first().map(|c| c.confidence).unwrap_or(0.0);
 
+    let (fingerprint, signature) =
+        learned::fingerprint(step_context.command, &categories, &tagged_lines);
+
     Diagnosis {
         categories,
         confidence,
         details,
         resolutions,
+        fingerprint,
+        signature,
     }
 }
 
@@ -225,6 +267,46 @@ Tasks: TOP => db:prepare
         assert!(!diag.resolutions.is_empty());
     }
 
+    #[test]
+    fn diagnose_with_rules_ranks_project_resolution() {
+        let rule: crate::config::DiagnosticRule = serde_yaml::from_str(
+            "pattern: 'could not resolve host: (\\S+)\\.internal'\n\
+             category: connection_refused\n\
+             explanation: Internal hosts need the VPN\n\
+             fix: vpn connect {1}\n\
+             confidence: 0.95\n",
+        )
+        .unwrap();
+        let rules = vec![CustomRule::compile("vpn", &rule).unwrap()];
+
+        let ctx = StepContext {
+            name: "deps",
+            command: "bundle install",
+            requires: &[],
+            template: None,
+        };
+        let outcomes = HashMap::new();
+        let ws = WorkflowState {
+            steps: &[],
+            outcomes: &outcomes,
+        };
+
+        let diag = diagnose_with_rules(
+            "fatal: could not resolve host: gems.internal",
+            &ctx,
+            &ws,
+            &rules,
+        );
+
+        assert_eq!(
+            diag.categories[0].category,
+            ErrorCategory::ConnectionRefused
+        );
+        let top = &diag.resolutions[0];
+        assert_eq!(top.command.as_deref(), Some("vpn connect gems"));
+        assert_eq!(top.source, ResolutionSource::Project);
+    }
+
     #[test]
     fn diagnose_connection_refused() {
         let error = "PG::ConnectionBad: could not connect to server: Connection refused\nIs the server running on host \"localhost\" (::1) and accepting TCP/IP connections on port 5432?";
@@ -378,4 +460,210 @@ Tasks: TOP => db:prepare
         assert_eq!(diag.categories[0].category, ErrorCategory::PortConflict);
         assert_eq!(diag.details.port, Some(3000));
     }
+
+    fn diagnose_command(command: &str, error: &str) -> Diagnosis {
+        let ctx = StepContext {
+            name: "step",
+            command,
+            requires: &[],
+            template: None,
+        };
+        let outcomes = HashMap::new();
+        let ws = WorkflowState {
+            steps: &[],
+            outcomes: &outcomes,
+        };
+        diagnose(error, &ctx, &ws)
+    }
+
+    fn commands(diag: &Diagnosis) -> Vec<&str> {
+        diag.resolutions
+            .iter()
+            .filter_map(|r| r.command.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn diagnose_composer_lock_out_of_date() {
+        let error = "\
+Installing dependencies from lock file (including require-dev)
+Verifying lock file contents can be installed on current platform.
+Warning: The lock file is not up to date with the latest changes in composer.json. You may be getting outdated dependencies. It is recommended that you run `composer update` or `composer update <package name>`.
+Your lock file does not contain a compatible set of packages. Please run composer update.
+
+  Problem 1
+    - laravel/framework is locked to version v10.48.4 and an update of this package was not requested.
+    - laravel/framework v10.48.4 requires ext-mbstring * -> it is missing from your system. Install or enable PHP's mbstring extension.";
+
+        let diag = diagnose_command("composer install", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::SyncIssue);
+        assert!(commands(&diag).contains(&"composer update --lock"));
+    }
+
+    #[test]
+    fn diagnose_composer_php_version() {
+        let error = "\
+Your requirements could not be resolved to an installable set of packages.
+
+  Problem 1
+    - Root composer.json requires php ^8.2 but your php version (8.1.27) does not satisfy that requirement.";
+
+        let diag = diagnose_command("composer install", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::VersionMismatch);
+        assert_eq!(
+            diag.resolutions[0].label,
+            "install a PHP version that satisfies composer.json"
+        );
+        // PostgreSQL-specific version advice must not leak into PHP failures
+        assert!(!commands(&diag).iter().any(|c| c.contains("postgresql")));
+    }
+
+    #[test]
+    fn diagnose_laravel_missing_app_key() {
+        let error = "
+   Illuminate\\Encryption\\MissingAppKeyException
+
+  No application encryption key has been specified.
+
+  at vendor/laravel/framework/src/Illuminate/Encryption/EncryptionServiceProvider.php:83";
+
+        let diag = diagnose_command("php artisan migrate", error);
+
+        assert_eq!(diag.details.target.as_deref(), Some("APP_KEY"));
+        assert_eq!(
+            diag.resolutions[0].command.as_deref(),
+            Some("php artisan key:generate")
+        );
+    }
+
+    #[test]
+    fn diagnose_flutter_sdk_constraint() {
+        let error = "\
+Resolving dependencies...
+The current Dart SDK version is 2.19.6.
+
+Because my_app requires SDK version >=3.0.0 <4.0.0, version solving failed.";
+
+        let diag = diagnose_command("flutter pub get", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::VersionMismatch);
+        assert_eq!(
+            diag.resolutions[0].command.as_deref(),
+            Some("flutter upgrade")
+        );
+    }
+
+    #[test]
+    fn diagnose_flutter_cocoapods_missing() {
+        let error = "\
+Warning: CocoaPods not installed. Skipping pod install.
+  CocoaPods is used to retrieve the iOS and macOS platform side's plugin code.
+Error: CocoaPods not installed or not in valid state.";
+
+        let diag = diagnose_command("flutter build ios", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::NotFound);
+        assert_eq!(diag.details.target.as_deref(), Some("CocoaPods"));
+        if cfg!(target_os = "macos") {
+            assert!(commands(&diag).contains(&"brew install cocoapods"));
+        }
+    }
+
+    #[test]
+    fn diagnose_swift_tools_version() {
+        let error = "error: 'my-package': package 'my-package' is using Swift tools version 5.9.0 but the installed version is 5.7.1";
+
+        let diag = diagnose_command("swift package resolve", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::VersionMismatch);
+        assert_eq!(
+            diag.resolutions[0].label,
+            "update Xcode or install a newer Swift toolchain"
+        );
+    }
+
+    #[test]
+    fn diagnose_terraform_inconsistent_lock_file() {
+        let error = "\
+╷
+│ Error: Inconsistent dependency lock file
+│
+│ The following dependency selections recorded in the lock file are inconsistent with the current configuration:
+│   - provider registry.terraform.io/hashicorp/aws: required by this configuration but no version is selected
+│
+│ To make the initial dependency selections that will initialize the dependency lock file, run:
+│   terraform init
+╵";
+
+        let diag = diagnose_command("terraform plan", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::SyncIssue);
+        assert!(commands(&diag).contains(&"terraform init -upgrade"));
+    }
+
+    #[test]
+    fn diagnose_terraform_backend_not_initialized() {
+        let error = "\
+╷
+│ Error: Backend initialization required, please run \"terraform init\"
+│
+│ Reason: Initial configuration of the requested backend \"s3\"
+╵";
+
+        let diag = diagnose_command("tofu plan", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::NotFound);
+        assert_eq!(diag.resolutions[0].command.as_deref(), Some("tofu init"));
+    }
+
+    #[test]
+    fn diagnose_mysql_server_not_running() {
+        let error = "\
+ActiveRecord::ConnectionNotEstablished: Can't connect to local MySQL server through socket '/tmp/mysql.sock' (2)
+bin/rails aborted!";
+
+        let diag = diagnose_command("bin/rails db:prepare", error);
+
+        assert_eq!(
+            diag.categories[0].category,
+            ErrorCategory::ConnectionRefused
+        );
+        assert_eq!(diag.details.service.as_deref(), Some("mysql"));
+        assert!(commands(&diag).iter().any(|c| c.ends_with("start mysql")));
+    }
+
+    #[test]
+    fn diagnose_mysql_unknown_database() {
+        let error = "\
+ActiveRecord::NoDatabaseError: Unknown database 'myapp_development'
+Caused by:
+Mysql2::Error: Unknown database 'myapp_development'";
+
+        let diag = diagnose_command("bin/rails db:migrate", error);
+
+        assert_eq!(diag.categories[0].category, ErrorCategory::NotFound);
+        assert_eq!(
+            diag.resolutions[0].command.as_deref(),
+            Some("mysqladmin -u root create myapp_development")
+        );
+    }
+
+    #[test]
+    fn diagnose_mysql_access_denied() {
+        let error =
+            "ERROR 1045 (28000): Access denied for user 'root'@'localhost' (using password: YES)";
+
+        let diag = diagnose_command("mysql -u root myapp", error);
+
+        assert!(diag
+            .categories
+            .iter()
+            .any(|c| c.category == ErrorCategory::AuthFailure));
+        assert_eq!(
+            diag.resolutions[0].label,
+            "check the MySQL username and password"
+        );
+    }
 }
@@ -529,7 +529,7 @@ impl Prompter for NonInteractiveStepDisplay {
             crate::ui::PromptType::MultiSelect { .. }
         );
         if let Some(result) = crate::ui::prompts::env_override(prompt) {
-            return Ok(result);
+            return prompt.check(result);
         }
         if let Some(default) = &prompt.default {
             if is_multiselect {
@@ -537,7 +537,7 @@ impl Prompter for NonInteractiveStepDisplay {
                     default.split(',').map(|s| s.trim().to_string()).collect();
                 return Ok(PromptResult::Strings(values));
             }
-            return Ok(PromptResult::String(default.clone()));
+            return prompt.check(PromptResult::String(default.clone()));
         }
         Err(crate::error::BivvyError::ConfigValidationError {
             message: format!(
@@ -13,9 +13,10 @@ use tracing::warn;
 use crate::config::interpolation::InterpolationContext;
 use crate::error::Result;
 use crate::logging::{BivvyEvent, EventBus};
-use crate::shell::OutputCallback;
+use crate::secrets::OutputMasker;
+use crate::shell::{OutputCallback, OutputLine};
 use crate::steps::{execute_step, ExecutionOptions, ResolvedStep, StepResult, StepStatus};
-use crate::ui::{format_duration, OutputMode, Prompt, PromptOption, PromptType, UserInterface};
+use crate::ui::{format_duration, OutputMode, UserInterface};
 
 use super::diagnostic;
 use super::display::StepDisplay;
@@ -57,12 +58,18 @@ pub(super) fn execute_step_with_recovery(
     step_ctx: &StepContext<'_>,
     diagnostic_funnel: bool,
     workflow_state: &diagnostic::WorkflowState<'_>,
+    diagnostic_rules: &[diagnostic::CustomRule],
+    learned: Option<&diagnostic::LearnedStore>,
     ui: &mut dyn UserInterface,
     step_display: &mut dyn StepDisplay,
     event_bus: &mut EventBus,
 ) -> Result<StepExecutionResult> {
     let mut retry_count: u32 = 0;
     let mut fix_history: HashSet<String> = HashSet::new();
+    // The latest failure's diagnosis and the fixes applied since it, so a
+    // fix can be learned once the step passes
+    let mut last_diagnosis: Option<diagnostic::Diagnosis> = None;
+    let mut attempted_fixes: Vec<(String, diagnostic::LearnedSource)> = Vec::new();
     let mut skipped_by_user = false;
     let mut aborted = false;
     #[allow(unused_assignments)]
@@ -80,7 +87,9 @@ pub(super) fn execute_step_with_recovery(
         // owns the live-output ring buffer.
         step_display.start_running(&display_command);
         let output_mode = step_display.output_mode();
-        let output_callback: Option<OutputCallback> = step_display.live_output_callback();
+        let output_callback: Option<OutputCallback> = step_display
+            .live_output_callback()
+            .map(|sink| mask_output_callback(sink, context));
 
         let exec_options = ExecutionOptions {
             force: needs_force,
@@ -107,6 +116,13 @@ pub(super) fn execute_step_with_recovery(
             }
         };
 
+        // Secret prompt answers never leave this function unmasked
+        let mut result = result;
+        if !context.secrets.is_empty() {
+            result.output = result.output.map(|o| context.mask_secrets(&o));
+            result.error = result.error.map(|e| context.mask_secrets(&e));
+        }
+
         // Emit StepOutput events for captured output
         if let Some(ref output) = result.output {
             for line in output.lines() {
@@ -135,6 +151,15 @@ pub(super) fn execute_step_with_recovery(
                     Some(result.duration),
                     detail.as_deref(),
                 );
+                if let (Some(store), Some(diag)) = (learned, &last_diagnosis) {
+                    for (command, source) in &attempted_fixes {
+                        let recorded =
+                            store.record(diag, command, *source, &step.env_vars.env, project_root);
+                        if let Err(e) = recorded {
+                            warn!("Failed to record learned fix for '{}': {}", step_name, e);
+                        }
+                    }
+                }
                 let mut r = result;
                 r.recovery_detail = detail;
                 final_result = Some(r);
@@ -166,7 +191,18 @@ pub(super) fn execute_step_with_recovery(
                         requires: step_ctx.requires,
                         template: step_ctx.template,
                     };
-                    let diag = diagnostic::diagnose(&combined_output, &diag_ctx, workflow_state);
+                    let mut diag = diagnostic::diagnose_with_rules(
+                        &combined_output,
+                        &diag_ctx,
+                        workflow_state,
+                        diagnostic_rules,
+                    );
+                    if let Some(store) = learned {
+                        match store.for_fingerprint(&diag.fingerprint) {
+                            Ok(fixes) => diagnostic::apply_learned(&mut diag, &fixes),
+                            Err(e) => warn!("Failed to load learned fixes: {}", e),
+                        }
+                    }
                     // Collect all resolutions with confidence 0.1–0.29 as hint text
                     // (shown below error block, not in menu)
                     let hints: Vec<String> = diag
@@ -180,7 +216,9 @@ pub(super) fn execute_step_with_recovery(
                     } else {
                         Some(format!("You might try: {}", hints.join(", or ")))
                     };
-                    (None, hint, diag.resolutions)
+                    let resolutions = diag.resolutions.clone();
+                    last_diagnosis = Some(diag);
+                    (None, hint, resolutions)
                 } else {
                     let fix = patterns::find_fix(&combined_output, step_ctx);
                     let hint = patterns::find_hint(&combined_output, step_ctx);
@@ -236,6 +274,7 @@ pub(super) fn execute_step_with_recovery(
                 }
 
                 // Interactive recovery menu
+                attempted_fixes.clear();
                 handle_recovery_menu(
                     step,
                     step_name,
@@ -247,6 +286,7 @@ pub(super) fn execute_step_with_recovery(
                     &resolutions,
                     diagnostic_funnel,
                     &mut fix_history,
+                    &mut attempted_fixes,
                     &mut retry_count,
                     &mut skipped_by_user,
                     &mut aborted,
@@ -278,10 +318,28 @@ pub(super) fn execute_step_with_recovery(
     })
 }
 
+/// Wrap a live output sink so secret prompt answers are redacted.
+fn mask_output_callback(sink: OutputCallback, context: &InterpolationContext) -> OutputCallback {
+    if context.secrets.is_empty() {
+        return sink;
+    }
+    let mut masker = OutputMasker::new();
+    masker.add_secrets(context.secrets.iter().cloned());
+    Box::new(move |line: OutputLine| {
+        let line = match line {
+            OutputLine::Stdout(s) => OutputLine::Stdout(masker.mask(&s)),
+            OutputLine::Stderr(s) => OutputLine::Stderr(masker.mask(&s)),
+        };
+        sink.write_line(line);
+    })
+}
+
 /// Handle the interactive recovery menu after a step failure.
 ///
 /// Sets `final_result` if the user chose to skip or abort. Returns `Ok(())`
 /// to let the caller decide whether to continue or break the execution loop.
+/// Successful fix commands and debug-shell commands are appended to
+/// `attempted_fixes` so they can be learned if the retry passes.
 #[allow(clippy::too_many_arguments)]
 fn handle_recovery_menu(
     step: &ResolvedStep,
@@ -294,6 +352,7 @@ fn handle_recovery_menu(
     resolutions: &[diagnostic::ResolutionCandidate],
     diagnostic_funnel: bool,
     fix_history: &mut HashSet<String>,
+    attempted_fixes: &mut Vec<(String, diagnostic::LearnedSource)>,
     retry_count: &mut u32,
     skipped_by_user: &mut bool,
     aborted: &mut bool,
@@ -351,6 +410,12 @@ fn handle_recovery_menu(
                     let fix_ok = recovery::run_fix(&cmd, project_root, &step.env_vars.env)?;
                     fix_history.insert(cmd.clone());
                     if fix_ok {
+                        let source = if is_custom {
+                            diagnostic::LearnedSource::Custom
+                        } else {
+                            diagnostic::LearnedSource::Suggested
+                        };
+                        attempted_fixes.push((cmd.clone(), source));
                         step_display.message(&format!("{}Fix command succeeded.", pad));
                     } else {
                         step_display.message(&format!("{}Fix command failed.", pad));
@@ -372,7 +437,14 @@ fn handle_recovery_menu(
                 ));
                 let debug_env =
                     crate::steps::build_step_env(step, project_root, base_env, process_env)?;
-                crate::shell::debug::spawn_debug_shell(step_name, project_root, &debug_env)?;
+                let commands =
+                    crate::shell::debug::spawn_debug_shell(step_name, project_root, &debug_env)?;
+                attempted_fixes.extend(
+                    commands
+                        .into_iter()
+                        .filter(|c| c.trim() != step.execution.command.trim())
+                        .map(|c| (c, diagnostic::LearnedSource::Shell)),
+                );
                 // After shell exit, re-show recovery menu
             }
             RecoveryAction::Skip => {
@@ -402,47 +474,3 @@ fn handle_recovery_menu(
         }
     }
 }
-
-/// Convert a config-level PromptConfig into a UI Prompt.
-pub(super) fn config_prompt_to_ui_prompt(config: &crate::config::schema::PromptConfig) -> Prompt {
-    use crate::config::schema::PromptType as ConfigPromptType;
-
-    let default = config.default.as_ref().and_then(|v| match v {
-        serde_yaml::Value::String(s) => Some(s.clone()),
-        serde_yaml::Value::Bool(b) => Some(b.to_string()),
-        serde_yaml::Value::Number(n) => Some(n.to_string()),
-        _ => None,
-    });
-
-    let prompt_type = match &config.prompt_type {
-        ConfigPromptType::Select => PromptType::Select {
-            options: config
-                .options
-                .iter()
-                .map(|o| PromptOption {
-                    label: o.label.clone(),
-                    value: o.value.clone(),
-                })
-                .collect(),
-        },
-        ConfigPromptType::Multiselect => PromptType::MultiSelect {
-            options: config
-                .options
-                .iter()
-                .map(|o| PromptOption {
-                    label: o.label.clone(),
-                    value: o.value.clone(),
-                })
-                .collect(),
-        },
-        ConfigPromptType::Confirm => PromptType::Confirm,
-        ConfigPromptType::Input => PromptType::Input,
-    };
-
-    Prompt {
-        key: config.key.clone(),
-        question: config.question.clone(),
-        prompt_type,
-        default,
-    }
-}
@@ -1,5 +1,6 @@
 //! Step execution orchestration.
 
+pub mod affected;
 pub mod decision;
 pub mod dependency;
 pub mod diagnostic;
@@ -5,7 +5,7 @@
 //! recovery, error display) are delegated to [`super::step_manager::StepManager`].
 //!
 //! Step execution with recovery is in [`super::execution`]. Prompt conversion
-//! is in [`super::execution::config_prompt_to_ui_prompt`].
+//! is the `From<&PromptConfig>` impl on [`crate::ui::Prompt`].
 
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
@@ -22,6 +22,7 @@ use crate::state::StateStore;
 use crate::ui::theme::BivvyTheme;
 use crate::ui::UserInterface;
 
+use super::diagnostic;
 use super::display::WorkflowDisplay;
 use super::plan::build_execution_plan;
 use super::step_manager::{SkipCategory, StepAction, StepExecutionOptions, StepManager};
@@ -111,6 +112,7 @@ impl<'a> WorkflowRunner<'a> {
         }
 
         let interactive = ui.is_interactive() && !workflow_non_interactive;
+        let diagnostic_rules = diagnostic::compile_rules(&self.config.diagnostics);
 
         let mut results = Vec::new();
         let mut all_success = true;
@@ -152,6 +154,10 @@ impl<'a> WorkflowRunner<'a> {
                 force_steps: &options.force,
                 force_all: options.force_all,
                 provided_requirements: &options.provided_requirements,
+                answers: &options.answers,
+                artifacts: options.artifacts.as_ref(),
+                diagnostic_rules: &diagnostic_rules,
+                learned: options.learned.as_ref(),
             };
 
             let action = step_mgr.execute(
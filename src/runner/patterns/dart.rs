@@ -0,0 +1,209 @@
+use super::{Confidence, ErrorPattern, FixTemplate, PatternContext};
+
+// Matches a pubspec SDK constraint the installed Dart SDK does not satisfy.
+lazy_regex!(
+    RE_DART_SDK_CONSTRAINT,
+    r"requires SDK version ([^,]+), version solving failed"
+);
+// Matches an import of a package that has not been fetched, capturing the package name.
+lazy_regex!(
+    RE_DART_PACKAGE_UNRESOLVED,
+    r"Couldn't resolve the package '([^']+)'"
+);
+// Matches Flutter's CocoaPods check on macOS.
+lazy_regex!(
+    RE_FLUTTER_COCOAPODS_MISSING,
+    r"CocoaPods not installed or not in valid state"
+);
+// Matches a stale CocoaPods specs repo during `pod install`.
+lazy_regex!(
+    RE_FLUTTER_COCOAPODS_SPECS_OUTDATED,
+    r"CocoaPods's specs repository is too out-of-date"
+);
+// Matches unaccepted Android SDK licenses.
+lazy_regex!(
+    RE_FLUTTER_ANDROID_LICENSES,
+    r"(?:Some Android licenses not accepted|Android license status unknown)"
+);
+
+/// Return error patterns for the Dart and Flutter ecosystem.
+///
+/// Covers SDK constraint failures, unfetched packages, CocoaPods setup for
+/// iOS builds, and Android SDK licenses.
+pub fn patterns() -> Vec<ErrorPattern> {
+    vec![
+        ErrorPattern {
+            name: "flutter_sdk_constraint",
+            regex: RE_DART_SDK_CONSTRAINT.as_str(),
+            context: PatternContext::CommandContains("flutter"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Template {
+                label: "flutter upgrade",
+                command: "flutter upgrade",
+                explanation: "The installed Flutter SDK does not satisfy SDK version {1}",
+            },
+        },
+        ErrorPattern {
+            name: "dart_sdk_constraint",
+            regex: RE_DART_SDK_CONSTRAINT.as_str(),
+            context: PatternContext::CommandContains("dart"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "upgrade the Dart SDK to satisfy {1}",
+                explanation: "The installed Dart SDK does not satisfy SDK version {1}",
+            },
+        },
+        ErrorPattern {
+            name: "dart_package_unresolved",
+            regex: RE_DART_PACKAGE_UNRESOLVED.as_str(),
+            context: PatternContext::CommandContains("flutter|dart"),
+            confidence: Confidence::High,
+            fix: FixTemplate::ContextSwitch {
+                alternatives: &[
+                    ("flutter", "flutter pub get", "flutter pub get"),
+                    ("dart", "dart pub get", "dart pub get"),
+                ],
+                explanation: "Package dependencies have not been fetched",
+            },
+        },
+        ErrorPattern {
+            name: "flutter_cocoapods_missing",
+            regex: RE_FLUTTER_COCOAPODS_MISSING.as_str(),
+            context: PatternContext::CommandContains("flutter|pod"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "brew install cocoapods",
+                command: "brew install cocoapods",
+                explanation: "CocoaPods is required to build iOS and macOS targets",
+            },
+        },
+        ErrorPattern {
+            name: "flutter_cocoapods_specs_outdated",
+            regex: RE_FLUTTER_COCOAPODS_SPECS_OUTDATED.as_str(),
+            context: PatternContext::CommandContains("flutter|pod"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "pod repo update",
+                command: "pod repo update",
+                explanation: "The local CocoaPods specs repository is out of date",
+            },
+        },
+        ErrorPattern {
+            name: "flutter_android_licenses",
+            regex: RE_FLUTTER_ANDROID_LICENSES.as_str(),
+            context: PatternContext::CommandContains("flutter"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "flutter doctor --android-licenses",
+                command: "flutter doctor --android-licenses",
+                explanation: "Android SDK licenses have not been accepted",
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn flutter_context() -> StepContext<'static> {
+        StepContext {
+            name: "deps",
+            command: "flutter pub get",
+            requires: &[],
+            template: None,
+        }
+    }
+
+    fn dart_context() -> StepContext<'static> {
+        StepContext {
+            name: "deps",
+            command: "dart pub get",
+            requires: &[],
+            template: None,
+        }
+    }
+
+    const SDK_CONSTRAINT: &str = "Resolving dependencies...\n\
+        The current Dart SDK version is 2.19.6.\n\n\
+        Because my_app requires SDK version >=3.0.0 <4.0.0, version solving failed.";
+
+    #[test]
+    fn flutter_sdk_constraint_matches() {
+        let fix = find_fix(SDK_CONSTRAINT, &flutter_context()).unwrap();
+        assert_eq!(fix.command, "flutter upgrade");
+        assert_eq!(
+            fix.explanation,
+            "The installed Flutter SDK does not satisfy SDK version >=3.0.0 <4.0.0"
+        );
+    }
+
+    #[test]
+    fn dart_sdk_constraint_hint() {
+        assert!(find_fix(SDK_CONSTRAINT, &dart_context()).is_none());
+        let hint = find_hint(SDK_CONSTRAINT, &dart_context()).unwrap();
+        assert!(hint.contains("upgrade the Dart SDK to satisfy >=3.0.0 <4.0.0"));
+    }
+
+    #[test]
+    fn package_unresolved_uses_matching_tool() {
+        let error = "lib/main.dart:3:8: Error: Couldn't resolve the package 'provider' in \
+                     'package:provider/provider.dart'.";
+        assert_eq!(
+            find_fix(error, &flutter_context()).unwrap().command,
+            "flutter pub get"
+        );
+        let ctx = StepContext {
+            name: "test",
+            command: "dart test",
+            requires: &[],
+            template: None,
+        };
+        assert_eq!(find_fix(error, &ctx).unwrap().command, "dart pub get");
+    }
+
+    #[test]
+    fn cocoapods_missing_matches() {
+        let ctx = StepContext {
+            name: "ios",
+            command: "flutter build ios",
+            requires: &[],
+            template: None,
+        };
+        let error = "Warning: CocoaPods not installed. Skipping pod install.\n\
+                     Error: CocoaPods not installed or not in valid state.";
+        assert_eq!(
+            find_fix(error, &ctx).unwrap().command,
+            "brew install cocoapods"
+        );
+    }
+
+    #[test]
+    fn cocoapods_specs_outdated_matches() {
+        let ctx = StepContext {
+            name: "ios",
+            command: "flutter build ios",
+            requires: &[],
+            template: None,
+        };
+        let error = "[!] CocoaPods could not find compatible versions for pod \"Firebase/CoreOnly\"\n\
+                     Error: CocoaPods's specs repository is too out-of-date to satisfy dependencies.";
+        assert_eq!(find_fix(error, &ctx).unwrap().command, "pod repo update");
+    }
+
+    #[test]
+    fn android_licenses_matches() {
+        let ctx = StepContext {
+            name: "doctor",
+            command: "flutter doctor",
+            requires: &[],
+            template: None,
+        };
+        let error = "[!] Android toolchain - develop for Android devices (Android SDK version 34.0.0)\n    \
+                     ! Some Android licenses not accepted. To resolve this, run: flutter doctor --android-licenses";
+        assert_eq!(
+            find_fix(error, &ctx).unwrap().command,
+            "flutter doctor --android-licenses"
+        );
+    }
+}
@@ -19,19 +19,24 @@ macro_rules! lazy_regex {
     };
 }
 
+mod dart;
 mod docker;
 mod dotnet;
 mod elixir;
 mod general;
 mod go;
 mod java;
+mod mysql;
 mod node;
+mod php;
 mod postgres;
 mod python;
 mod rails;
 mod redis;
 mod ruby;
 mod rust_cargo;
+mod swift;
+mod terraform;
 
 /// Confidence level for a pattern match.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -135,7 +140,7 @@ pub enum FixTemplate {
 
 /// Replace `{1}`, `{2}`, etc. with capture groups. Returns `None` if a
 /// referenced group did not participate in the match.
-fn substitute(template: &str, caps: &regex::Captures) -> Option<String> {
+pub(crate) fn substitute(template: &str, caps: &regex::Captures) -> Option<String> {
     let mut result = template.to_string();
     let mut i = 1;
     while result.contains(&format!("{{{}}}", i)) {
@@ -246,6 +251,7 @@ pub fn built_in_patterns() -> Vec<ErrorPattern> {
     all.extend(node::patterns());
     all.extend(python::patterns());
     all.extend(postgres::patterns());
+    all.extend(mysql::patterns());
     all.extend(redis::patterns());
     all.extend(docker::patterns());
     all.extend(rails::patterns());
@@ -254,6 +260,10 @@ pub fn built_in_patterns() -> Vec<ErrorPattern> {
     all.extend(rust_cargo::patterns());
     all.extend(go::patterns());
     all.extend(elixir::patterns());
+    all.extend(php::patterns());
+    all.extend(dart::patterns());
+    all.extend(swift::patterns());
+    all.extend(terraform::patterns());
     all.extend(general::patterns());
     all
 }
@@ -0,0 +1,156 @@
+use super::{Confidence, ErrorPattern, FixTemplate, PatternContext};
+
+// Matches a MySQL/MariaDB client unable to reach the local server socket.
+lazy_regex!(
+    RE_MYSQL_SOCKET_CONN,
+    r"Can't connect to local (?:MySQL|MariaDB) server through socket"
+);
+// Matches `Unknown database '...'`, capturing the database name.
+lazy_regex!(RE_MYSQL_UNKNOWN_DATABASE, r"Unknown database '([^']+)'");
+// Matches rejected credentials, capturing the user.
+lazy_regex!(
+    RE_MYSQL_ACCESS_DENIED,
+    r"Access denied for user '([^']*)'@'[^']*'"
+);
+// Matches a client that cannot load the server's authentication plugin.
+lazy_regex!(
+    RE_MYSQL_AUTH_PLUGIN,
+    r"Authentication plugin '(\w+)' cannot be loaded"
+);
+// Matches the mysql2 gem or mysqlclient wheel failing to find client headers.
+lazy_regex!(
+    RE_MYSQL_CLIENT_MISSING,
+    r"(?:mysql client is missing|mysql_config: not found|mysql_config not found)"
+);
+
+/// Return error patterns for MySQL and MariaDB.
+///
+/// Covers a stopped server, missing databases, rejected credentials,
+/// authentication plugin mismatches, and missing client libraries.
+pub fn patterns() -> Vec<ErrorPattern> {
+    vec![
+        ErrorPattern {
+            name: "mysql_socket_conn",
+            regex: RE_MYSQL_SOCKET_CONN.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::High,
+            fix: FixTemplate::PlatformAware {
+                macos_label: "brew services start mysql",
+                macos_command: "brew services start mysql",
+                linux_label: "systemctl start mysql",
+                linux_command: "systemctl start mysql",
+                explanation: "MySQL server is not running",
+            },
+        },
+        ErrorPattern {
+            name: "mysql_unknown_database",
+            regex: RE_MYSQL_UNKNOWN_DATABASE.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::High,
+            fix: FixTemplate::Template {
+                label: "mysqladmin -u root create {1}",
+                command: "mysqladmin -u root create {1}",
+                explanation: "Database '{1}' does not exist",
+            },
+        },
+        ErrorPattern {
+            name: "mysql_access_denied",
+            regex: RE_MYSQL_ACCESS_DENIED.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label:
+                    "check the password for MySQL user '{1}' (DATABASE_URL or config/database.yml)",
+                explanation: "MySQL rejected the credentials for '{1}'",
+            },
+        },
+        ErrorPattern {
+            name: "mysql_auth_plugin",
+            regex: RE_MYSQL_AUTH_PLUGIN.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label:
+                    "upgrade the MySQL client library, or switch the user to mysql_native_password",
+                explanation: "The client cannot load the '{1}' authentication plugin",
+            },
+        },
+        ErrorPattern {
+            name: "mysql_client_missing",
+            regex: RE_MYSQL_CLIENT_MISSING.as_str(),
+            context: PatternContext::CommandContains("bundle|gem|pip|poetry"),
+            confidence: Confidence::High,
+            fix: FixTemplate::PlatformAware {
+                macos_label: "brew install mysql-client",
+                macos_command: "brew install mysql-client",
+                linux_label: "apt-get install libmysqlclient-dev",
+                linux_command: "sudo apt-get install -y libmysqlclient-dev",
+                explanation: "MySQL client headers are needed to build the database driver",
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn rails_context() -> StepContext<'static> {
+        StepContext {
+            name: "db",
+            command: "bin/rails db:prepare",
+            requires: &[],
+            template: None,
+        }
+    }
+
+    #[test]
+    fn socket_conn_matches() {
+        let error = "ERROR 2002 (HY000): Can't connect to local MySQL server through socket \
+                     '/tmp/mysql.sock' (2)";
+        let fix = find_fix(error, &rails_context()).unwrap();
+        assert!(fix.command.contains("start mysql"));
+        assert_eq!(fix.explanation, "MySQL server is not running");
+    }
+
+    #[test]
+    fn unknown_database_matches() {
+        let error = "ActiveRecord::NoDatabaseError: Unknown database 'myapp_development'\n\
+                     Caused by:\nMysql2::Error: Unknown database 'myapp_development'";
+        let fix = find_fix(error, &rails_context()).unwrap();
+        assert_eq!(fix.command, "mysqladmin -u root create myapp_development");
+    }
+
+    #[test]
+    fn access_denied_hint() {
+        let error =
+            "ERROR 1045 (28000): Access denied for user 'root'@'localhost' (using password: YES)";
+        let hint = find_hint(error, &rails_context()).unwrap();
+        assert!(hint.contains("MySQL user 'root'"));
+    }
+
+    #[test]
+    fn auth_plugin_hint() {
+        let error =
+            "Mysql2::Error: Authentication plugin 'caching_sha2_password' cannot be loaded: \
+                     dlopen(/usr/local/lib/plugin/caching_sha2_password.so, 2): image not found";
+        let hint = find_hint(error, &rails_context()).unwrap();
+        assert!(hint.contains("mysql_native_password"));
+    }
+
+    #[test]
+    fn client_missing_matches_for_bundler() {
+        let ctx = StepContext {
+            name: "bundler",
+            command: "bundle install",
+            requires: &[],
+            template: None,
+        };
+        let error = "checking for mysql_query() in -lmysqlclient... no\n\
+                     -----\n\
+                     mysql client is missing. You may need to 'sudo apt-get install libmariadb-dev', \
+                     'sudo apt-get install libmysqlclient-dev' or 'sudo yum install mysql-devel', and try again.";
+        let fix = find_fix(error, &ctx).unwrap();
+        assert!(fix.command.contains("mysql"));
+    }
+}
@@ -0,0 +1,173 @@
+use super::{Confidence, ErrorPattern, FixTemplate, PatternContext};
+
+// Matches Composer's warning when composer.lock was not regenerated after editing composer.json.
+lazy_regex!(
+    RE_COMPOSER_LOCK_OUTDATED,
+    r"lock file is not up to date with the latest changes in composer\.json"
+);
+// Matches Composer running out of PHP memory while resolving dependencies.
+lazy_regex!(
+    RE_COMPOSER_MEMORY_EXHAUSTED,
+    r"Allowed memory size of \d+ bytes exhausted"
+);
+// Matches a missing PHP extension required by a package, capturing the extension name.
+lazy_regex!(
+    RE_COMPOSER_MISSING_EXTENSION,
+    r"requires ext-(\w+) .*(?:it is missing from your system|to be missing)"
+);
+// Matches a PHP version constraint that the installed PHP does not satisfy.
+lazy_regex!(
+    RE_COMPOSER_PHP_VERSION,
+    r"requires php (\S+) (?:->|but) your php version \(([^)]+)\) does not satisfy"
+);
+// Matches Laravel's missing APP_KEY error.
+lazy_regex!(
+    RE_LARAVEL_MISSING_KEY,
+    r"No application encryption key has been specified"
+);
+
+/// Return error patterns for the PHP ecosystem.
+///
+/// Covers Composer lock drift, memory exhaustion, missing extensions, PHP
+/// version constraints, and Laravel's missing application key.
+pub fn patterns() -> Vec<ErrorPattern> {
+    vec![
+        ErrorPattern {
+            name: "composer_lock_outdated",
+            regex: RE_COMPOSER_LOCK_OUTDATED.as_str(),
+            context: PatternContext::CommandContains("composer"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "composer update --lock",
+                command: "composer update --lock",
+                explanation: "composer.lock is out of date with composer.json",
+            },
+        },
+        ErrorPattern {
+            name: "composer_memory_exhausted",
+            regex: RE_COMPOSER_MEMORY_EXHAUSTED.as_str(),
+            context: PatternContext::CommandContains("composer"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "COMPOSER_MEMORY_LIMIT=-1 composer install",
+                command: "COMPOSER_MEMORY_LIMIT=-1 composer install",
+                explanation: "Composer ran out of PHP memory while resolving dependencies",
+            },
+        },
+        ErrorPattern {
+            name: "composer_missing_extension",
+            regex: RE_COMPOSER_MISSING_EXTENSION.as_str(),
+            context: PatternContext::CommandContains("composer"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "install or enable the PHP {1} extension (check php --ini)",
+                explanation: "A dependency requires the PHP '{1}' extension",
+            },
+        },
+        ErrorPattern {
+            name: "composer_php_version",
+            regex: RE_COMPOSER_PHP_VERSION.as_str(),
+            context: PatternContext::CommandContains("composer"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "install PHP {1} (found {2})",
+                explanation: "The installed PHP version {2} does not satisfy {1}",
+            },
+        },
+        ErrorPattern {
+            name: "laravel_missing_key",
+            regex: RE_LARAVEL_MISSING_KEY.as_str(),
+            context: PatternContext::CommandContains("artisan|php|composer"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "php artisan key:generate",
+                command: "php artisan key:generate",
+                explanation: "APP_KEY is not set in .env",
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn composer_context() -> StepContext<'static> {
+        StepContext {
+            name: "composer",
+            command: "composer install",
+            requires: &[],
+            template: None,
+        }
+    }
+
+    #[test]
+    fn composer_lock_outdated_matches() {
+        let ctx = composer_context();
+        let error = "Installing dependencies from lock file (including require-dev)\n\
+                     Verifying lock file contents can be installed on current platform.\n\
+                     Warning: The lock file is not up to date with the latest changes in composer.json. \
+                     You may be getting outdated dependencies. It is recommended that you run \
+                     `composer update` or `composer update <package name>`.";
+        let fix = find_fix(error, &ctx).unwrap();
+        assert_eq!(fix.command, "composer update --lock");
+    }
+
+    #[test]
+    fn composer_memory_exhausted_matches() {
+        let ctx = composer_context();
+        let error = "PHP Fatal error:  Allowed memory size of 1610612736 bytes exhausted \
+                     (tried to allocate 4096 bytes) in phar:///usr/local/bin/composer/src/Composer/DependencyResolver/Solver.php on line 223";
+        let fix = find_fix(error, &ctx).unwrap();
+        assert_eq!(fix.command, "COMPOSER_MEMORY_LIMIT=-1 composer install");
+    }
+
+    #[test]
+    fn composer_missing_extension_hint() {
+        let ctx = composer_context();
+        let error = "Your lock file does not contain a compatible set of packages. Please run composer update.\n\n\
+                     Problem 1\n    \
+                     - laravel/framework is locked to version v10.48.4 and an update of this package was not requested.\n    \
+                     - laravel/framework v10.48.4 requires ext-mbstring * -> it is missing from your system. \
+                     Install or enable PHP's mbstring extension.";
+        let hint = find_hint(error, &ctx).unwrap();
+        assert!(hint.contains("PHP mbstring extension"));
+    }
+
+    #[test]
+    fn composer_php_version_hint() {
+        let ctx = composer_context();
+        let error = "Your requirements could not be resolved to an installable set of packages.\n\n\
+                     Problem 1\n    \
+                     - Root composer.json requires php ^8.2 but your php version (8.1.27) does not satisfy that requirement.";
+        let hint = find_hint(error, &ctx).unwrap();
+        assert!(hint.contains("install PHP ^8.2 (found 8.1.27)"));
+    }
+
+    #[test]
+    fn laravel_missing_key_matches() {
+        let ctx = StepContext {
+            name: "laravel",
+            command: "php artisan migrate",
+            requires: &[],
+            template: None,
+        };
+        let error = "Illuminate\\Encryption\\MissingAppKeyException\n\n  \
+                     No application encryption key has been specified.";
+        let fix = find_fix(error, &ctx).unwrap();
+        assert_eq!(fix.command, "php artisan key:generate");
+    }
+
+    #[test]
+    fn composer_pattern_requires_composer_context() {
+        let ctx = StepContext {
+            name: "build",
+            command: "make build",
+            requires: &[],
+            template: None,
+        };
+        let error =
+            "Warning: The lock file is not up to date with the latest changes in composer.json.";
+        assert!(find_fix(error, &ctx).is_none());
+    }
+}
@@ -0,0 +1,156 @@
+use super::{Confidence, ErrorPattern, FixTemplate, PatternContext};
+
+// Matches a missing or broken Command Line Tools install after a macOS upgrade.
+lazy_regex!(
+    RE_XCRUN_INVALID_DEVELOPER_PATH,
+    r"xcrun: error: invalid active developer path"
+);
+// Matches xcode-select pointing at the Command Line Tools when full Xcode is needed.
+lazy_regex!(
+    RE_XCODE_SELECT_CLT_ONLY,
+    r"requires Xcode, but active developer directory '[^']+' is a command line tools instance"
+);
+// Matches the Xcode license prompt blocking command-line builds.
+lazy_regex!(
+    RE_XCODE_LICENSE,
+    r"You have not agreed to the Xcode license agreements?"
+);
+// Matches a package manifest that needs a newer Swift toolchain, capturing both versions.
+lazy_regex!(
+    RE_SWIFT_TOOLS_VERSION,
+    r"is using Swift tools version (\d+(?:\.\d+)*) but the installed version is (\d+(?:\.\d+)*)"
+);
+// Matches a corrupted Package.resolved file.
+lazy_regex!(
+    RE_SWIFT_RESOLVED_CORRUPT,
+    r"Package\.resolved file is corrupted or malformed"
+);
+
+/// Return error patterns for the Swift ecosystem.
+///
+/// Covers Xcode and Command Line Tools setup, the Xcode license, Swift
+/// tools version mismatches, and corrupted `Package.resolved` files.
+pub fn patterns() -> Vec<ErrorPattern> {
+    vec![
+        ErrorPattern {
+            name: "xcrun_invalid_developer_path",
+            regex: RE_XCRUN_INVALID_DEVELOPER_PATH.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "xcode-select --install",
+                command: "xcode-select --install",
+                explanation: "Xcode Command Line Tools are missing or broken",
+            },
+        },
+        ErrorPattern {
+            name: "xcode_select_clt_only",
+            regex: RE_XCODE_SELECT_CLT_ONLY.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "sudo xcode-select -s /Applications/Xcode.app/Contents/Developer",
+                command: "sudo xcode-select -s /Applications/Xcode.app/Contents/Developer",
+                explanation: "The active developer directory is the Command Line Tools, not Xcode",
+            },
+        },
+        ErrorPattern {
+            name: "xcode_license",
+            regex: RE_XCODE_LICENSE.as_str(),
+            context: PatternContext::Always,
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "sudo xcodebuild -license accept",
+                command: "sudo xcodebuild -license accept",
+                explanation: "The Xcode license has not been accepted",
+            },
+        },
+        ErrorPattern {
+            name: "swift_tools_version",
+            regex: RE_SWIFT_TOOLS_VERSION.as_str(),
+            context: PatternContext::CommandContains("swift|xcodebuild"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "install Swift {1} or newer (found {2}), e.g. by updating Xcode",
+                explanation: "The package needs Swift tools version {1}",
+            },
+        },
+        ErrorPattern {
+            name: "swift_resolved_corrupt",
+            regex: RE_SWIFT_RESOLVED_CORRUPT.as_str(),
+            context: PatternContext::CommandContains("swift"),
+            confidence: Confidence::High,
+            fix: FixTemplate::Static {
+                label: "rm Package.resolved && swift package resolve",
+                command: "rm Package.resolved && swift package resolve",
+                explanation: "Package.resolved is corrupted and must be regenerated",
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn swift_context() -> StepContext<'static> {
+        StepContext {
+            name: "resolve",
+            command: "swift package resolve",
+            requires: &[],
+            template: None,
+        }
+    }
+
+    #[test]
+    fn xcrun_invalid_developer_path_matches() {
+        let error =
+            "xcrun: error: invalid active developer path (/Library/Developer/CommandLineTools), \
+                     missing xcrun at: /Library/Developer/CommandLineTools/usr/bin/xcrun";
+        let fix = find_fix(error, &swift_context()).unwrap();
+        assert_eq!(fix.command, "xcode-select --install");
+    }
+
+    #[test]
+    fn xcode_select_clt_only_matches() {
+        let ctx = StepContext {
+            name: "build",
+            command: "xcodebuild -scheme App build",
+            requires: &[],
+            template: None,
+        };
+        let error = "xcode-select: error: tool 'xcodebuild' requires Xcode, but active developer \
+                     directory '/Library/Developer/CommandLineTools' is a command line tools instance";
+        let fix = find_fix(error, &ctx).unwrap();
+        assert_eq!(
+            fix.command,
+            "sudo xcode-select -s /Applications/Xcode.app/Contents/Developer"
+        );
+    }
+
+    #[test]
+    fn xcode_license_matches() {
+        let error = "You have not agreed to the Xcode license agreements. You must agree to both \
+                     license agreements below in order to use Xcode.";
+        let fix = find_fix(error, &swift_context()).unwrap();
+        assert_eq!(fix.command, "sudo xcodebuild -license accept");
+    }
+
+    #[test]
+    fn swift_tools_version_hint() {
+        let error = "error: 'my-package': package 'my-package' is using Swift tools version 5.9.0 \
+                     but the installed version is 5.7.1";
+        assert!(find_fix(error, &swift_context()).is_none());
+        let hint = find_hint(error, &swift_context()).unwrap();
+        assert!(hint.contains("install Swift 5.9.0 or newer (found 5.7.1)"));
+    }
+
+    #[test]
+    fn swift_resolved_corrupt_matches() {
+        let error =
+            "error: Package.resolved file is corrupted or malformed; fix or delete the file \
+                     to continue: unsupported schema version 3";
+        let fix = find_fix(error, &swift_context()).unwrap();
+        assert_eq!(fix.command, "rm Package.resolved && swift package resolve");
+    }
+}
@@ -0,0 +1,193 @@
+use super::{Confidence, ErrorPattern, FixTemplate, PatternContext};
+
+// Matches a backend whose configuration changed since the last init.
+lazy_regex!(
+    RE_TERRAFORM_BACKEND_CHANGED,
+    r"Error: Backend configuration changed"
+);
+// Matches a working directory that has not been initialized yet.
+lazy_regex!(
+    RE_TERRAFORM_INIT_REQUIRED,
+    r#"(?:Backend initialization required|Module not installed|Required plugins are not installed|please run "terraform init")"#
+);
+// Matches `.terraform.lock.hcl` disagreeing with the configured providers.
+lazy_regex!(
+    RE_TERRAFORM_LOCK_INCONSISTENT,
+    r"Error: Inconsistent dependency lock file"
+);
+// Matches a held state lock, capturing the lock ID.
+lazy_regex!(
+    RE_TERRAFORM_STATE_LOCK,
+    r"(?s)Error acquiring the state lock.*?ID:\s+(\S+)"
+);
+// Matches a `required_version` constraint that the installed Terraform does not satisfy.
+lazy_regex!(
+    RE_TERRAFORM_VERSION,
+    r"does not support Terraform version (\d+(?:\.\d+)*)"
+);
+// Matches the AWS provider failing to find credentials.
+lazy_regex!(
+    RE_TERRAFORM_AWS_CREDENTIALS,
+    r"No valid credential sources found"
+);
+
+/// Return error patterns for Terraform.
+///
+/// Covers uninitialized working directories, backend and lock file drift,
+/// held state locks, `required_version` mismatches, and missing AWS
+/// credentials.
+pub fn patterns() -> Vec<ErrorPattern> {
+    vec![
+        ErrorPattern {
+            name: "terraform_backend_changed",
+            regex: RE_TERRAFORM_BACKEND_CHANGED.as_str(),
+            context: PatternContext::CommandContains("terraform|tofu"),
+            confidence: Confidence::High,
+            fix: FixTemplate::ContextSwitch {
+                alternatives: &[
+                    ("tofu", "tofu init -reconfigure", "tofu init -reconfigure"),
+                    (
+                        "terraform",
+                        "terraform init -reconfigure",
+                        "terraform init -reconfigure",
+                    ),
+                ],
+                explanation: "The backend configuration changed since the last init",
+            },
+        },
+        ErrorPattern {
+            name: "terraform_init_required",
+            regex: RE_TERRAFORM_INIT_REQUIRED.as_str(),
+            context: PatternContext::CommandContains("terraform|tofu"),
+            confidence: Confidence::High,
+            fix: FixTemplate::ContextSwitch {
+                alternatives: &[
+                    ("tofu", "tofu init", "tofu init"),
+                    ("terraform", "terraform init", "terraform init"),
+                ],
+                explanation: "The working directory has not been initialized",
+            },
+        },
+        ErrorPattern {
+            name: "terraform_lock_inconsistent",
+            regex: RE_TERRAFORM_LOCK_INCONSISTENT.as_str(),
+            context: PatternContext::CommandContains("terraform|tofu"),
+            confidence: Confidence::High,
+            fix: FixTemplate::ContextSwitch {
+                alternatives: &[
+                    ("tofu", "tofu init -upgrade", "tofu init -upgrade"),
+                    ("terraform", "terraform init -upgrade", "terraform init -upgrade"),
+                ],
+                explanation: ".terraform.lock.hcl does not match the configured providers",
+            },
+        },
+        ErrorPattern {
+            name: "terraform_state_lock",
+            regex: RE_TERRAFORM_STATE_LOCK.as_str(),
+            context: PatternContext::CommandContains("terraform|tofu"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "wait for the other run to finish, or run terraform force-unlock {1} if it crashed",
+                explanation: "The state is locked by another operation (lock {1})",
+            },
+        },
+        ErrorPattern {
+            name: "terraform_version",
+            regex: RE_TERRAFORM_VERSION.as_str(),
+            context: PatternContext::CommandContains("terraform"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "install a Terraform version allowed by required_version (found {1})",
+                explanation: "Terraform {1} does not satisfy the configured required_version",
+            },
+        },
+        ErrorPattern {
+            name: "terraform_aws_credentials",
+            regex: RE_TERRAFORM_AWS_CREDENTIALS.as_str(),
+            context: PatternContext::CommandContains("terraform|tofu"),
+            confidence: Confidence::Low,
+            fix: FixTemplate::Hint {
+                label: "configure AWS credentials (aws configure, aws sso login, or set AWS_PROFILE)",
+                explanation: "The AWS provider could not find credentials",
+            },
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::*;
+
+    fn terraform_context(command: &'static str) -> StepContext<'static> {
+        StepContext {
+            name: "infra",
+            command,
+            requires: &[],
+            template: None,
+        }
+    }
+
+    #[test]
+    fn backend_changed_matches() {
+        let error = "Initializing the backend...\n\
+                     ╷\n│ Error: Backend configuration changed\n│\n\
+                     │ A change in the backend configuration has been detected, which may require \
+                     migrating existing state.";
+        let fix = find_fix(error, &terraform_context("terraform init")).unwrap();
+        assert_eq!(fix.command, "terraform init -reconfigure");
+    }
+
+    #[test]
+    fn init_required_matches() {
+        let error =
+            "╷\n│ Error: Backend initialization required, please run \"terraform init\"\n│\n\
+                     │ Reason: Initial configuration of the requested backend \"s3\"";
+        let fix = find_fix(error, &terraform_context("terraform plan")).unwrap();
+        assert_eq!(fix.command, "terraform init");
+    }
+
+    #[test]
+    fn init_required_uses_tofu_for_tofu_steps() {
+        let error = "│ Error: Module not installed\n│\n│   on main.tf line 1:";
+        let fix = find_fix(error, &terraform_context("tofu plan")).unwrap();
+        assert_eq!(fix.command, "tofu init");
+    }
+
+    #[test]
+    fn lock_inconsistent_matches() {
+        let error = "╷\n│ Error: Inconsistent dependency lock file\n│\n\
+                     │ The following dependency selections recorded in the lock file are inconsistent \
+                     with the current configuration:\n\
+                     │   - provider registry.terraform.io/hashicorp/aws: required by this configuration \
+                     but no version is selected";
+        let fix = find_fix(error, &terraform_context("terraform plan")).unwrap();
+        assert_eq!(fix.command, "terraform init -upgrade");
+    }
+
+    #[test]
+    fn state_lock_hint_includes_lock_id() {
+        let error = "╷\n│ Error: Error acquiring the state lock\n│\n\
+                     │ Error message: ConditionalCheckFailedException: The conditional request failed\n\
+                     │ Lock Info:\n\
+                     │   ID:        6f1b0c1e-21a6-8d3f-7b3c-2c7e1d5e9a10\n\
+                     │   Path:      acme-tf-state/dev/terraform.tfstate\n\
+                     │   Operation: OperationTypeApply";
+        let hint = find_hint(error, &terraform_context("terraform apply")).unwrap();
+        assert!(hint.contains("terraform force-unlock 6f1b0c1e-21a6-8d3f-7b3c-2c7e1d5e9a10"));
+    }
+
+    #[test]
+    fn version_hint() {
+        let error = "│ Error: Unsupported Terraform Core version\n│\n\
+                     │ This configuration does not support Terraform version 1.3.9. To proceed, either \
+                     choose another supported Terraform version or update this version constraint.";
+        let hint = find_hint(error, &terraform_context("terraform init")).unwrap();
+        assert!(hint.contains("(found 1.3.9)"));
+    }
+
+    #[test]
+    fn terraform_pattern_requires_terraform_context() {
+        let error = "Error: Inconsistent dependency lock file";
+        assert!(find_fix(error, &terraform_context("make plan")).is_none());
+    }
+}
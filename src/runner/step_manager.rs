@@ -6,11 +6,16 @@
 
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
+use std::time::Instant;
 
+use tracing::{debug, warn};
+
+use crate::cache::{compute_key, ArtifactStore};
 use crate::checks::evaluator::CheckEvaluator;
 use crate::checks::CheckResult;
 use crate::config::interpolation::InterpolationContext;
 use crate::config::schema::StepOverride;
+use crate::config::PromptAnswers;
 use crate::error::{BivvyError, Result};
 use crate::logging::{BehaviorFlags, BivvyEvent, DecisionTrace, EventBus, StepOutcomeKind};
 use crate::requirements::checker::GapChecker;
@@ -20,13 +25,13 @@ use crate::state::satisfaction::SatisfactionCache;
 use crate::state::StateStore;
 use crate::steps::{ResolvedStep, StepResult, StepStatus};
 use crate::ui::theme::BivvyTheme;
-use crate::ui::{Prompt, PromptOption, PromptType, StatusKind, UserInterface};
+use crate::ui::{Prompt, PromptOption, PromptResult, PromptType, StatusKind, UserInterface};
 
 use super::decision::{BlockReason, SkipReason, StepDecision};
 use super::diagnostic;
 use super::display::StepDisplay;
 use super::engine::{self, EngineContext, EvaluationResult};
-use super::execution::{config_prompt_to_ui_prompt, execute_step_with_recovery};
+use super::execution::execute_step_with_recovery;
 use super::patterns::StepContext;
 use super::satisfaction;
 
@@ -44,6 +49,16 @@ pub(super) struct StepExecutionOptions<'a> {
     pub force_steps: &'a HashSet<String>,
     pub force_all: bool,
     pub provided_requirements: &'a HashSet<String>,
+    /// Pre-supplied prompt answers (`--answers` / `--set`).
+    pub answers: &'a PromptAnswers,
+    /// Artifact store for steps that declare `cache:`. `None` disables
+    /// artifact caching.
+    pub artifacts: Option<&'a ArtifactStore>,
+    /// Compiled project diagnostic rules from the `diagnostics:` section.
+    pub diagnostic_rules: &'a [diagnostic::CustomRule],
+    /// Learned fixes store for boosting and recording recoveries. `None`
+    /// disables learning.
+    pub learned: Option<&'a diagnostic::LearnedStore>,
 }
 
 impl StepExecutionOptions<'_> {
@@ -355,11 +370,39 @@ impl<'a> StepManager<'a> {
         // ── Step-level prompts (template inputs) ──
         if !self.step.output.prompts.is_empty() {
             for prompt_config in &self.step.output.prompts {
-                if context.resolve(&prompt_config.key).is_some() {
+                let ui_prompt = Prompt::from(prompt_config);
+                let is_secret = ui_prompt.prompt_type.is_secret();
+
+                // Explicit answers win over everything else, including
+                // values already resolvable from vars or the environment.
+                if let Some(answer) = opts.answers.get(self.step_name, &prompt_config.key) {
+                    if is_secret {
+                        context.add_secret(answer);
+                    }
+                    context
+                        .prompts
+                        .insert(prompt_config.key.clone(), answer.to_string());
+                    continue;
+                }
+
+                if let Some(value) = context.resolve(&prompt_config.key) {
+                    if is_secret {
+                        context.add_secret(value);
+                    }
                     continue;
                 }
 
                 if !opts.interactive {
+                    // The same `KEY=value` override the prompt UIs honour,
+                    // so an exported variable answers the prompt here too.
+                    if let Some(result) = crate::ui::prompts::env_override(&ui_prompt) {
+                        let value = ui_prompt.check(result)?.as_string();
+                        if is_secret {
+                            context.add_secret(&value);
+                        }
+                        context.prompts.insert(prompt_config.key.clone(), value);
+                        continue;
+                    }
                     if let Some(default) = &prompt_config.default {
                         let default_str = match default {
                             serde_yaml::Value::String(s) => s.clone(),
@@ -367,6 +410,10 @@ impl<'a> StepManager<'a> {
                             serde_yaml::Value::Number(n) => n.to_string(),
                             _ => format!("{:?}", default),
                         };
+                        ui_prompt.check(PromptResult::String(default_str.clone()))?;
+                        if is_secret {
+                            context.add_secret(&default_str);
+                        }
                         context
                             .prompts
                             .insert(prompt_config.key.clone(), default_str);
@@ -383,11 +430,64 @@ impl<'a> StepManager<'a> {
                     continue;
                 }
 
-                let ui_prompt = config_prompt_to_ui_prompt(prompt_config);
-                let result = ui.prompt(&ui_prompt)?;
-                context
-                    .prompts
-                    .insert(prompt_config.key.clone(), result.as_string());
+                event_bus.emit(&BivvyEvent::UserPrompted {
+                    step: Some(self.step_name.to_string()),
+                    prompt: ui_prompt.question.clone(),
+                    options: prompt_config
+                        .options
+                        .iter()
+                        .map(|o| o.label.clone())
+                        .collect(),
+                });
+                let value = ui.prompt(&ui_prompt)?.as_string();
+                if is_secret {
+                    context.add_secret(&value);
+                }
+                // Secret answers never reach the event log.
+                let logged = if is_secret {
+                    "[REDACTED]".to_string()
+                } else {
+                    value.clone()
+                };
+                let method = match ui_prompt.prompt_type {
+                    PromptType::Confirm
+                    | PromptType::Select { .. }
+                    | PromptType::MultiSelect { .. } => crate::logging::InputMethod::ArrowSelect,
+                    _ => crate::logging::InputMethod::TypedInput(logged.clone()),
+                };
+                event_bus.emit(&BivvyEvent::UserResponded {
+                    step: Some(self.step_name.to_string()),
+                    input: logged,
+                    method,
+                });
+                context.prompts.insert(prompt_config.key.clone(), value);
+            }
+        }
+
+        // ── Artifact cache: restore outputs instead of running ──
+        let artifacts = match (opts.artifacts, &self.step.execution.cache) {
+            (Some(store), Some(cache)) if !opts.dry_run => Some((store, cache)),
+            _ => None,
+        };
+        if let Some((store, cache)) = artifacts.filter(|_| !needs_force) {
+            let start = Instant::now();
+            let key = compute_key(
+                self.step_name,
+                &self.step.execution.command,
+                cache,
+                opts.project_root,
+            );
+            match store.restore(&key, opts.project_root) {
+                Ok(Some(_)) => {
+                    return Ok(self.handle_artifact_restored(
+                        start,
+                        &step_pad,
+                        event_bus,
+                        step_display,
+                    ));
+                }
+                Ok(None) => debug!("No artifact for step '{}' (key {})", self.step_name, key),
+                Err(e) => warn!("Failed to restore artifact for '{}': {}", self.step_name, e),
             }
         }
 
@@ -456,11 +556,37 @@ impl<'a> StepManager<'a> {
             &step_ctx,
             opts.diagnostic_funnel,
             &ws,
+            opts.diagnostic_rules,
+            opts.learned,
             ui,
             step_display,
             event_bus,
         )?;
 
+        // ── Artifact cache: save outputs after a successful run ──
+        // The key is recomputed because the command may rewrite key files
+        // (e.g. `yarn install` updating `yarn.lock`).
+        if let Some((store, cache)) = artifacts {
+            if exec_result.result.status() == StepStatus::Completed {
+                let key = compute_key(
+                    self.step_name,
+                    &self.step.execution.command,
+                    cache,
+                    opts.project_root,
+                );
+                let project = context.resolve("project_name").unwrap_or_default();
+                if let Err(e) = store.save(
+                    &key,
+                    &project,
+                    self.step_name,
+                    &cache.paths,
+                    opts.project_root,
+                ) {
+                    warn!("Failed to save artifact for '{}': {}", self.step_name, e);
+                }
+            }
+        }
+
         // ── Emit step completion event ──
         let duration_ms = exec_result.result.duration.as_millis() as u64;
         match exec_result.result.status() {
@@ -641,6 +767,48 @@ impl<'a> StepManager<'a> {
         ))
     }
 
+    /// Handle a step whose outputs were restored from the artifact cache.
+    fn handle_artifact_restored(
+        &self,
+        start: Instant,
+        step_pad: &str,
+        event_bus: &mut EventBus,
+        step_display: &mut dyn StepDisplay,
+    ) -> StepAction {
+        const DETAIL: &str = "restored from artifact cache";
+
+        let duration = start.elapsed();
+        let duration_ms = duration.as_millis() as u64;
+        event_bus.emit(&BivvyEvent::StepDecided {
+            name: self.step_name.to_string(),
+            decision: "restore".to_string(),
+            reason: Some("artifact_cache".to_string()),
+            trace: None,
+        });
+        event_bus.emit(&BivvyEvent::StepCompleted {
+            name: self.step_name.to_string(),
+            success: true,
+            exit_code: None,
+            duration_ms,
+            error: None,
+        });
+        event_bus.emit(&BivvyEvent::StepOutcome {
+            name: self.step_name.to_string(),
+            outcome: StepOutcomeKind::Completed,
+            detail: Some(DETAIL.to_string()),
+            duration_ms: Some(duration_ms),
+        });
+        step_display.message(&format!(
+            "{}{}",
+            step_pad,
+            StatusKind::Success.format(self.theme, "Restored from artifact cache")
+        ));
+
+        let mut result = StepResult::success(&self.step.name, duration, None, None);
+        result.recovery_detail = Some(DETAIL.to_string());
+        StepAction::Completed(result)
+    }
+
     /// Handle a prompt decision. Returns `Some(StepAction)` if the step should
     /// not proceed to execution (user declined), or `None` to fall through.
     #[allow(clippy::too_many_arguments)]
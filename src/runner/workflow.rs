@@ -6,9 +6,11 @@ use std::time::{Duration, Instant};
 
 use tracing::warn;
 
+use crate::cache::ArtifactStore;
 use crate::checks::evaluator::CheckEvaluator;
+use crate::config::answers::UnansweredPrompt;
 use crate::config::interpolation::InterpolationContext;
-use crate::config::BivvyConfig;
+use crate::config::{BivvyConfig, PromptAnswers};
 use crate::error::{BivvyError, Result};
 use crate::logging::EventBus;
 use crate::requirements::checker::GapChecker;
@@ -18,6 +20,7 @@ use crate::steps::{execute_step, ExecutionOptions, ResolvedStep, StepResult, Ste
 
 use super::decision;
 use super::dependency::{DependencyGraph, SkipBehavior};
+use super::diagnostic::LearnedStore;
 use super::plan::build_execution_plan;
 
 /// Progress events emitted during workflow execution.
@@ -87,6 +90,14 @@ pub struct RunOptions {
     pub diagnostic_funnel: bool,
     /// Discard all persisted satisfaction records and evaluate everything fresh.
     pub fresh: bool,
+    /// Pre-supplied prompt answers from `--answers` and `--set`.
+    pub answers: PromptAnswers,
+    /// Artifact store for steps with `cache:`. `None` disables restoring
+    /// and saving artifacts.
+    pub artifacts: Option<ArtifactStore>,
+    /// Learned fixes from earlier recoveries. `None` disables boosting
+    /// and recording learned fixes.
+    pub learned: Option<LearnedStore>,
 }
 
 impl RunOptions {
@@ -358,6 +369,42 @@ impl<'a> WorkflowRunner<'a> {
 
         builder.build()
     }
+
+    /// Steps a run with `options` will execute, in execution order.
+    pub fn planned_steps(&self, options: &RunOptions) -> Result<Vec<String>> {
+        let workflow_name = options.workflow.as_deref().unwrap_or("default");
+        let graph = self.build_graph(workflow_name)?;
+        let workflow_steps = &self.config.workflows[workflow_name].steps;
+        let plan = build_execution_plan(&graph, workflow_steps, options, &self.steps)?;
+        Ok(plan.steps_to_run)
+    }
+
+    /// Variable names referenced by the planned steps.
+    ///
+    /// Lets the caller evaluate only the `vars:` this run needs.
+    pub fn referenced_variables(&self, options: &RunOptions) -> Result<HashSet<String>> {
+        Ok(self
+            .planned_steps(options)?
+            .iter()
+            .filter_map(|name| self.steps.get(name))
+            .flat_map(|step| step.referenced_variables())
+            .collect())
+    }
+
+    /// Prompts in the planned steps that nothing will answer.
+    ///
+    /// Used before a non-interactive run so every missing answer is
+    /// reported at once instead of failing on the first prompt reached.
+    pub fn unanswered_prompts(
+        &self,
+        options: &RunOptions,
+        context: &InterpolationContext,
+    ) -> Result<Vec<UnansweredPrompt>> {
+        let planned = self.planned_steps(options)?;
+        Ok(options
+            .answers
+            .unanswered(&planned, &self.steps, |key| context.resolve(key).is_some()))
+    }
 }
 
 /// Record a step's result in the state store.
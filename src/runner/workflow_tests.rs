@@ -3719,6 +3719,94 @@ fn recovery_fix_confirmed_retries() {
     assert!(marker.exists());
 }
 
+#[test]
+fn recovery_fix_is_learned_and_suggested_next_time() {
+    // A custom fix that makes the retry pass is recorded, and offered as a
+    // learned fix when the same failure happens again.
+    let temp = TempDir::new().unwrap();
+    let learned_dir = TempDir::new().unwrap();
+    let marker = temp.path().join("seeded");
+    let cmd = format!(
+        "if [ -f {m} ]; then exit 0; else echo 'Error: database is not seeded'; exit 1; fi",
+        m = marker.display()
+    );
+
+    let config: BivvyConfig = serde_yaml::from_str(
+        r#"
+            workflows:
+              default:
+                steps: [seed]
+        "#,
+    )
+    .unwrap();
+    let mut steps = HashMap::new();
+    steps.insert("seed".to_string(), make_step("seed", &cmd, vec![]));
+    let store = crate::runner::diagnostic::LearnedStore::new(learned_dir.path().join("l.yml"));
+    let options = RunOptions {
+        diagnostic_funnel: true,
+        learned: Some(store.clone()),
+        ..Default::default()
+    };
+    let fix = "touch seeded";
+
+    let mut ui = MockUI::new();
+    ui.set_interactive(true);
+    ui.queue_prompt_responses("recovery_seed", vec!["custom_fix"]);
+    ui.set_prompt_response("custom_fix_seed", fix);
+    ui.set_prompt_response("confirm_fix_seed", "yes");
+    let mut runner = WorkflowRunner::new(&config, steps.clone());
+    let result = runner
+        .run_with_ui(
+            &options,
+            &InterpolationContext::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            temp.path(),
+            false,
+            &HashMap::new(),
+            None,
+            None,
+            &mut SatisfactionCache::empty(temp.path().join("satisfaction.json")),
+            &mut ui,
+            &mut MockWorkflowDisplay::new(),
+            &mut EventBus::new(),
+        )
+        .unwrap();
+    assert!(result.success);
+
+    let learned = store.list().unwrap();
+    assert_eq!(learned.len(), 1);
+    assert_eq!(learned[0].command, fix);
+    assert_eq!(learned[0].successes, 1);
+
+    // Same failure again: the learned fix is offered as the first option
+    fs::remove_file(&marker).unwrap();
+    let mut ui = MockUI::new();
+    ui.set_interactive(true);
+    ui.queue_prompt_responses("recovery_seed", vec!["fix_0"]);
+    ui.set_prompt_response("confirm_fix_seed", "yes");
+    let mut runner = WorkflowRunner::new(&config, steps);
+    let result = runner
+        .run_with_ui(
+            &options,
+            &InterpolationContext::new(),
+            &HashMap::new(),
+            &HashMap::new(),
+            temp.path(),
+            false,
+            &HashMap::new(),
+            None,
+            None,
+            &mut SatisfactionCache::empty(temp.path().join("satisfaction.json")),
+            &mut ui,
+            &mut MockWorkflowDisplay::new(),
+            &mut EventBus::new(),
+        )
+        .unwrap();
+    assert!(result.success);
+    assert_eq!(store.list().unwrap()[0].successes, 2);
+}
+
 #[test]
 fn recovery_fix_declined_reprompts() {
     // User picks Fix, declines confirm, menu re-appears, then aborts.
@@ -3807,3 +3895,237 @@ fn runner_env_override_depends_on_cycle_detected() {
     let cycle = graph.find_cycle();
     assert!(cycle.is_some());
 }
+
+#[test]
+fn run_with_ui_masks_secret_prompt_answers() {
+    use crate::config::schema::{PromptConfig, PromptType};
+    use crate::logging::EventConsumer;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+    impl EventConsumer for Recorder {
+        fn on_event(&mut self, event: &crate::logging::BivvyEvent) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(serde_json::to_string(event).unwrap());
+        }
+    }
+
+    let temp = TempDir::new().unwrap();
+    let config: BivvyConfig = serde_yaml::from_str(
+        r#"
+            workflows:
+              default:
+                steps: [login]
+        "#,
+    )
+    .unwrap();
+
+    let mut step = make_step("login", "echo token=${token}", vec![]);
+    step.output.prompts = vec![PromptConfig {
+        key: "token".to_string(),
+        question: "API token".to_string(),
+        prompt_type: PromptType::Secret,
+        options: vec![],
+        default: None,
+        min: None,
+        max: None,
+        pattern: None,
+        pattern_message: None,
+        must_be: None,
+    }];
+    let mut steps = HashMap::new();
+    steps.insert("login".to_string(), step);
+
+    let mut runner = WorkflowRunner::new(&config, steps);
+    let options = RunOptions::default();
+    let ctx = InterpolationContext::new();
+
+    let mut ui = MockUI::new();
+    ui.set_interactive(true);
+    ui.set_default_prompt_response("yes");
+    ui.set_prompt_response("token", "hunter2");
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut bus = EventBus::new();
+    bus.add_consumer(Box::new(Recorder(events.clone())));
+
+    let mut workflow_display = MockWorkflowDisplay::new();
+    let result = runner
+        .run_with_ui(
+            &options,
+            &ctx,
+            &HashMap::new(),
+            &HashMap::new(),
+            temp.path(),
+            false,
+            &HashMap::new(),
+            None,
+            None,
+            &mut SatisfactionCache::empty(temp.path().join("satisfaction.json")),
+            &mut ui,
+            &mut workflow_display,
+            &mut bus,
+        )
+        .unwrap();
+
+    assert!(result.success);
+    let output = result.steps[0].output.clone().unwrap_or_default();
+    assert_eq!(output.trim(), "token=[REDACTED]");
+
+    let events = events.lock().unwrap();
+    assert!(events.iter().all(|e| !e.contains("hunter2")));
+    assert!(events
+        .iter()
+        .any(|e| e.contains("user_responded") && e.contains("[REDACTED]")));
+}
+
+#[test]
+fn run_with_ui_non_interactive_uses_env_override_for_prompt() {
+    use crate::config::schema::{PromptConfig, PromptType};
+
+    std::env::set_var("BIVVY_WORKFLOW_TEST_REGION", "eu");
+    let temp = TempDir::new().unwrap();
+    let config: BivvyConfig = serde_yaml::from_str(
+        r#"
+            workflows:
+              default:
+                steps: [deploy]
+        "#,
+    )
+    .unwrap();
+
+    let mut step = make_step(
+        "deploy",
+        "echo region=${bivvy_workflow_test_region}",
+        vec![],
+    );
+    step.output.prompts = vec![PromptConfig {
+        key: "bivvy_workflow_test_region".to_string(),
+        question: "Which region?".to_string(),
+        prompt_type: PromptType::Input,
+        options: vec![],
+        default: None,
+        min: None,
+        max: None,
+        pattern: None,
+        pattern_message: None,
+        must_be: None,
+    }];
+    let mut steps = HashMap::new();
+    steps.insert("deploy".to_string(), step);
+
+    let mut runner = WorkflowRunner::new(&config, steps);
+    let options = RunOptions::default();
+    let ctx = InterpolationContext::new();
+    assert!(runner
+        .unanswered_prompts(&options, &ctx)
+        .unwrap()
+        .is_empty());
+
+    let mut ui = MockUI::new();
+    let mut workflow_display = MockWorkflowDisplay::new();
+    let result = runner
+        .run_with_ui(
+            &options,
+            &ctx,
+            &HashMap::new(),
+            &HashMap::new(),
+            temp.path(),
+            false,
+            &HashMap::new(),
+            None,
+            None,
+            &mut SatisfactionCache::empty(temp.path().join("satisfaction.json")),
+            &mut ui,
+            &mut workflow_display,
+            &mut EventBus::new(),
+        )
+        .unwrap();
+    std::env::remove_var("BIVVY_WORKFLOW_TEST_REGION");
+
+    assert!(result.success);
+    let output = result.steps[0].output.clone().unwrap_or_default();
+    assert_eq!(output.trim(), "region=eu");
+}
+
+#[test]
+fn run_with_ui_restores_cached_artifacts_on_key_hit() {
+    use crate::cache::ArtifactStore;
+    use crate::config::ArtifactCacheConfig;
+
+    let temp = TempDir::new().unwrap();
+    let store_dir = TempDir::new().unwrap();
+    let config: BivvyConfig = serde_yaml::from_str(
+        r#"
+            workflows:
+              default:
+                steps: [install]
+        "#,
+    )
+    .unwrap();
+
+    let mut step = make_step(
+        "install",
+        "mkdir -p out && echo built >> out/log && echo ran >> runs.txt",
+        vec![],
+    );
+    step.execution.cache = Some(ArtifactCacheConfig {
+        key: vec!["app.lock".to_string()],
+        paths: vec!["out".to_string()],
+    });
+
+    let run = |lock: &str| {
+        fs::write(temp.path().join("app.lock"), lock).unwrap();
+        let mut steps = HashMap::new();
+        steps.insert("install".to_string(), step.clone());
+        let mut runner = WorkflowRunner::new(&config, steps);
+        let options = RunOptions {
+            artifacts: Some(ArtifactStore::new(store_dir.path(), 100)),
+            ..Default::default()
+        };
+        let mut ui = MockUI::new();
+        let mut workflow_display = MockWorkflowDisplay::new();
+        runner
+            .run_with_ui(
+                &options,
+                &InterpolationContext::new(),
+                &HashMap::new(),
+                &HashMap::new(),
+                temp.path(),
+                false,
+                &HashMap::new(),
+                None,
+                None,
+                &mut SatisfactionCache::empty(temp.path().join("satisfaction.json")),
+                &mut ui,
+                &mut workflow_display,
+                &mut EventBus::new(),
+            )
+            .unwrap()
+    };
+    let runs = || {
+        fs::read_to_string(temp.path().join("runs.txt"))
+            .unwrap()
+            .lines()
+            .count()
+    };
+
+    assert!(run("a").success);
+    assert!(run("b").success);
+    assert_eq!(runs(), 2);
+
+    // Back to the first lockfile: outputs come from the cache.
+    let result = run("a");
+    assert!(result.success);
+    assert_eq!(runs(), 2);
+    assert_eq!(
+        result.steps[0].recovery_detail.as_deref(),
+        Some("restored from artifact cache")
+    );
+    assert_eq!(
+        fs::read_to_string(temp.path().join("out/log")).unwrap(),
+        "built\n"
+    );
+}
@@ -1,14 +1,23 @@
 //! Debug shell for step failure investigation.
 //!
 //! Spawns an interactive shell with the step's environment for debugging
-//! when a step fails during `bivvy run`.
+//! when a step fails during `bivvy run`. The commands typed in the session
+//! are read back from a per-session history file so a fix made by hand can
+//! be learned.
 
 use std::collections::HashMap;
-use std::path::Path;
+use std::fs::{self, OpenOptions};
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use crate::error::Result;
-use crate::shell::platform::detect_shell;
+use crate::shell::platform::{detect_shell, ShellType};
+
+/// Commands that only inspect or navigate, never worth learning as a fix.
+const INSPECTION_COMMANDS: &[&str] = &[
+    "cat", "cd", "clear", "echo", "env", "exit", "head", "history", "less", "ll", "ls", "man",
+    "more", "printenv", "pwd", "tail", "type", "which",
+];
 
 /// Spawn an interactive debug shell for investigating a step failure.
 ///
@@ -19,11 +28,16 @@ use crate::shell::platform::detect_shell;
 /// `env` is the fully-merged environment the step ran with — see
 /// [`crate::steps::build_step_env`] for the layering rules. The debug shell
 /// itself only adds the `BIVVY_DEBUG` markers on top.
+///
+/// Returns the commands run in the session, excluding navigation and
+/// inspection commands. Bash and zsh write them to a private temporary
+/// `HISTFILE`, removed when the session ends; other shells, or dotfiles
+/// that override `HISTFILE`, yield no commands.
 pub fn spawn_debug_shell(
     step_name: &str,
     project_root: &Path,
     env: &HashMap<String, String>,
-) -> Result<()> {
+) -> Result<Vec<String>> {
     let shell_info = detect_shell();
     let shell_exe = shell_info.executable;
 
@@ -39,6 +53,19 @@ pub fn spawn_debug_shell(
     env.insert("BIVVY_DEBUG".to_string(), "1".to_string());
     env.insert("BIVVY_DEBUG_STEP".to_string(), step_name.to_string());
 
+    // Record the session's commands in a history file of our own
+    let history_file = matches!(shell_info.name, ShellType::Bash | ShellType::Zsh)
+        .then(HistoryFile::create)
+        .and_then(|file| file.ok());
+    if let Some(ref history_file) = history_file {
+        env.insert(
+            "HISTFILE".to_string(),
+            history_file.path.to_string_lossy().into_owned(),
+        );
+        env.insert("HISTSIZE".to_string(), "1000".to_string());
+        env.insert("SAVEHIST".to_string(), "1000".to_string());
+    }
+
     cmd.envs(&env);
 
     // Inherit stdio (takes over terminal)
@@ -69,13 +96,121 @@ pub fn spawn_debug_shell(
     #[cfg(unix)]
     crate::shell::command::claim_foreground();
 
-    Ok(())
+    let history = history_file
+        .as_ref()
+        .and_then(|file| fs::read_to_string(&file.path).ok())
+        .unwrap_or_default();
+    Ok(parse_history(&history))
+}
+
+/// A randomly named history file readable only by the user.
+///
+/// Created exclusively so an existing file or symlink at the path is never
+/// reused, and deleted when dropped, whichever way the session ends.
+struct HistoryFile {
+    path: PathBuf,
+}
+
+impl HistoryFile {
+    fn create() -> std::io::Result<Self> {
+        let mut suffix = [0u8; 16];
+        crate::sys::random_bytes(&mut suffix);
+        let path =
+            std::env::temp_dir().join(format!("bivvy-debug-history-{}", hex::encode(suffix)));
+
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        options.open(&path)?;
+        Ok(Self { path })
+    }
+}
+
+impl Drop for HistoryFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Extract fix-worthy commands from bash or zsh history file content.
+///
+/// Handles zsh extended history (`: <time>:<elapsed>;<command>`) and skips
+/// bash timestamp lines, duplicates, and [`INSPECTION_COMMANDS`].
+fn parse_history(content: &str) -> Vec<String> {
+    let mut commands: Vec<String> = Vec::new();
+    for line in content.lines() {
+        let line = match line.strip_prefix(": ") {
+            Some(rest) => rest.split_once(';').map(|(_, cmd)| cmd).unwrap_or(rest),
+            None => line,
+        }
+        .trim();
+        if line.is_empty()
+            || (line.starts_with('#') && line[1..].chars().all(|c| c.is_ascii_digit()))
+        {
+            continue;
+        }
+        let program = line.split_whitespace().next().unwrap_or_default();
+        if INSPECTION_COMMANDS.contains(&program) || program == "bivvy" {
+            continue;
+        }
+        if !commands.iter().any(|c| c == line) {
+            commands.push(line.to_string());
+        }
+    }
+    commands
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn history_file_is_private_and_removed_on_drop() {
+        let first = HistoryFile::create().unwrap();
+        let second = HistoryFile::create().unwrap();
+        assert_ne!(first.path, second.path);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&first.path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let path = first.path.clone();
+        drop(first);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn parse_history_skips_inspection_and_duplicates() {
+        let history = "ls -la\ncd db\nbrew services start postgresql@16\n\
+                       cat config/database.yml\nbrew services start postgresql@16\n\
+                       createdb myapp_development\nexit\n";
+        assert_eq!(
+            parse_history(history),
+            vec![
+                "brew services start postgresql@16".to_string(),
+                "createdb myapp_development".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_history_reads_zsh_extended_and_bash_timestamps() {
+        let history =
+            ": 1700000000:0;pwd\n: 1700000003:2;bundle config set force_ruby_platform true\n\
+                       #1700000010\nbivvy run --only deps\n";
+        assert_eq!(
+            parse_history(history),
+            vec!["bundle config set force_ruby_platform true".to_string()]
+        );
+    }
+
     #[test]
     fn debug_shell_env_vars() {
         // We can't actually spawn an interactive shell in tests,
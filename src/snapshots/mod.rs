@@ -289,6 +289,28 @@ impl SnapshotStore {
         found
     }
 
+    /// Delete every baseline and named snapshot recorded for a step.
+    ///
+    /// Removes the step's snapshot files from disk. Returns the number of
+    /// files removed.
+    pub fn delete_step(&mut self, step: &str) -> std::io::Result<usize> {
+        self.load_all();
+        let filenames: Vec<String> = self
+            .cache
+            .iter()
+            .filter(|(_, data)| data.step == step)
+            .map(|(filename, _)| filename.clone())
+            .collect();
+        for filename in &filenames {
+            self.cache.remove(filename);
+            let path = self.dir.join(filename);
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        Ok(filenames.len())
+    }
+
     /// Save all modified data to disk.
     pub fn save(&self) -> std::io::Result<()> {
         std::fs::create_dir_all(&self.dir)?;
@@ -499,6 +521,24 @@ mod tests {
         assert!(store.get_baseline(&key, "v1.0").is_none());
     }
 
+    #[test]
+    fn delete_step_removes_its_snapshot_files() {
+        let temp = TempDir::new().unwrap();
+        let mut store = SnapshotStore::new(temp.path());
+        store.record_baseline(&test_key(), "_last_run", "sha256:a".into(), "x".into());
+        store.record_baseline(&workflow_key(), "_last_run", "sha256:b".into(), "x".into());
+        let other = SnapshotKey::project("yarn_install", "abc12345");
+        store.record_baseline(&other, "_last_run", "sha256:c".into(), "x".into());
+        store.save().unwrap();
+
+        let mut store = SnapshotStore::new(temp.path());
+        assert_eq!(store.delete_step("bundle_install").unwrap(), 2);
+
+        assert!(!temp.path().join(test_key().filename()).exists());
+        assert!(!temp.path().join(workflow_key().filename()).exists());
+        assert!(temp.path().join(other.filename()).exists());
+    }
+
     #[test]
     fn delete_nonexistent_returns_false() {
         let temp = TempDir::new().unwrap();
@@ -0,0 +1,283 @@
+//! Cached change-check hashes for fast staleness reports.
+//!
+//! After a run, every step that finished records the hash of each file
+//! and glob change-check target it watches, along with a fingerprint of
+//! the matched files' paths, sizes and modification times. Staleness is
+//! then decided by comparing fingerprints, rehashing only targets whose
+//! files were touched, which keeps `bivvy status --stale` cheap enough to
+//! run from a shell prompt.
+//!
+//! Command targets are not recorded: running commands is too slow for a
+//! prompt hook.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::checks::change::hash_target;
+use crate::checks::ChangeKind;
+
+/// A change-check target as it was when its step last finished.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchedTarget {
+    /// File path or glob pattern, relative to the project root.
+    pub target: String,
+    /// Whether `target` is a file or a glob.
+    pub kind: ChangeKind,
+    /// Content hash, or `None` if the target was missing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Hash of the matched files' paths, sizes and modification times.
+    pub fingerprint: String,
+}
+
+impl WatchedTarget {
+    /// Capture a target's current hash and fingerprint.
+    pub fn capture(target: &str, kind: ChangeKind, project_root: &Path) -> Self {
+        Self {
+            target: target.to_string(),
+            kind,
+            hash: hash_target(target, &kind, project_root).ok(),
+            fingerprint: fingerprint(target, kind, project_root),
+        }
+    }
+
+    /// Whether the target's content differs from when it was captured.
+    ///
+    /// Unchanged fingerprints are trusted; otherwise the target is
+    /// rehashed so touching a file without editing it isn't a change.
+    pub fn has_changed(&self, project_root: &Path) -> bool {
+        if fingerprint(&self.target, self.kind, project_root) == self.fingerprint {
+            return false;
+        }
+        hash_target(&self.target, &self.kind, project_root).ok() != self.hash
+    }
+}
+
+/// A step whose watched targets changed since it last finished.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleStep {
+    /// Step name.
+    pub name: String,
+    /// Targets that changed.
+    pub changed: Vec<String>,
+}
+
+/// Watched targets per step, persisted as `freshness.json`.
+#[derive(Debug, Default)]
+pub struct FreshnessIndex {
+    steps: BTreeMap<String, Vec<WatchedTarget>>,
+    path: PathBuf,
+}
+
+impl FreshnessIndex {
+    /// Load the index from a `freshness.json` file.
+    ///
+    /// If the file doesn't exist or is invalid, returns an empty index.
+    pub fn load(path: PathBuf) -> Self {
+        let steps = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { steps, path }
+    }
+
+    /// Record the current state of a step's watched targets.
+    ///
+    /// Command targets are ignored. A step with no file or glob targets
+    /// is removed from the index.
+    pub fn record(&mut self, step: &str, targets: &[(&str, ChangeKind)], project_root: &Path) {
+        let watched: Vec<WatchedTarget> = targets
+            .iter()
+            .filter(|(_, kind)| *kind != ChangeKind::Command)
+            .map(|(target, kind)| WatchedTarget::capture(target, *kind, project_root))
+            .collect();
+        if watched.is_empty() {
+            self.steps.remove(step);
+        } else {
+            self.steps.insert(step.to_string(), watched);
+        }
+    }
+
+    /// Drop steps that no longer exist in the configuration.
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.steps.retain(|name, _| keep(name));
+    }
+
+    /// Steps with at least one watched target that changed, by name.
+    pub fn stale_steps(&self, project_root: &Path) -> Vec<StaleStep> {
+        self.steps
+            .iter()
+            .filter_map(|(name, targets)| {
+                let changed: Vec<String> = targets
+                    .iter()
+                    .filter(|t| t.has_changed(project_root))
+                    .map(|t| t.target.clone())
+                    .collect();
+                (!changed.is_empty()).then(|| StaleStep {
+                    name: name.clone(),
+                    changed,
+                })
+            })
+            .collect()
+    }
+
+    /// Whether any step is tracked.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Write the index to disk using atomic write.
+    pub fn save(&self) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&self.steps).map_err(std::io::Error::other)?;
+        let temp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&temp_path, &content)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Hash the paths, sizes and modification times of a target's files.
+///
+/// Reads metadata only, never file contents. Missing files hash to a
+/// fixed value so a target that stays missing is unchanged.
+pub fn fingerprint(target: &str, kind: ChangeKind, project_root: &Path) -> String {
+    let resolved = if Path::new(target).is_absolute() {
+        PathBuf::from(target)
+    } else {
+        project_root.join(target)
+    };
+    let mut paths = match kind {
+        ChangeKind::Glob => crate::sys::glob(&resolved.to_string_lossy()).unwrap_or_default(),
+        _ => vec![resolved],
+    };
+    paths.sort();
+
+    let mut hasher = Sha256::new();
+    for path in &paths {
+        let Ok(meta) = std::fs::metadata(path) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let mtime = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_nanos())
+            .unwrap_or_default();
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(meta.len().to_le_bytes());
+        hasher.update(mtime.to_le_bytes());
+    }
+    hex::encode(&hasher.finalize()[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    fn set_mtime(path: &Path, secs_ago: u64) {
+        let file = fs::File::options().write(true).open(path).unwrap();
+        file.set_modified(SystemTime::now() - Duration::from_secs(secs_ago))
+            .unwrap();
+    }
+
+    #[test]
+    fn unchanged_target_is_fresh() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Gemfile.lock"), "gems").unwrap();
+
+        let mut index = FreshnessIndex::load(temp.path().join("freshness.json"));
+        index.record("bundle", &[("Gemfile.lock", ChangeKind::File)], temp.path());
+
+        assert!(index.stale_steps(temp.path()).is_empty());
+    }
+
+    #[test]
+    fn edited_target_is_stale() {
+        let temp = TempDir::new().unwrap();
+        let lock = temp.path().join("Gemfile.lock");
+        fs::write(&lock, "gems").unwrap();
+        set_mtime(&lock, 60);
+
+        let mut index = FreshnessIndex::load(temp.path().join("freshness.json"));
+        index.record("bundle", &[("Gemfile.lock", ChangeKind::File)], temp.path());
+        fs::write(&lock, "more gems").unwrap();
+
+        assert_eq!(
+            index.stale_steps(temp.path()),
+            vec![StaleStep {
+                name: "bundle".to_string(),
+                changed: vec!["Gemfile.lock".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn touched_but_identical_target_is_fresh() {
+        let temp = TempDir::new().unwrap();
+        let lock = temp.path().join("Gemfile.lock");
+        fs::write(&lock, "gems").unwrap();
+        set_mtime(&lock, 60);
+
+        let mut index = FreshnessIndex::load(temp.path().join("freshness.json"));
+        index.record("bundle", &[("Gemfile.lock", ChangeKind::File)], temp.path());
+        set_mtime(&lock, 0);
+
+        assert!(index.stale_steps(temp.path()).is_empty());
+    }
+
+    #[test]
+    fn glob_target_detects_new_files() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir(temp.path().join("migrations")).unwrap();
+        fs::write(temp.path().join("migrations/001.sql"), "create").unwrap();
+
+        let mut index = FreshnessIndex::load(temp.path().join("freshness.json"));
+        index.record("db", &[("migrations/*.sql", ChangeKind::Glob)], temp.path());
+        fs::write(temp.path().join("migrations/002.sql"), "alter").unwrap();
+
+        assert_eq!(index.stale_steps(temp.path())[0].name, "db");
+    }
+
+    #[test]
+    fn command_targets_are_not_recorded() {
+        let temp = TempDir::new().unwrap();
+        let mut index = FreshnessIndex::load(temp.path().join("freshness.json"));
+        index.record(
+            "node",
+            &[("node --version", ChangeKind::Command)],
+            temp.path(),
+        );
+
+        assert!(index.is_empty());
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("Gemfile.lock"), "gems").unwrap();
+        let path = temp.path().join("state/freshness.json");
+
+        let mut index = FreshnessIndex::load(path.clone());
+        index.record("bundle", &[("Gemfile.lock", ChangeKind::File)], temp.path());
+        index.record("gone", &[("Gemfile.lock", ChangeKind::File)], temp.path());
+        index.retain(|name| name != "gone");
+        index.save().unwrap();
+
+        let loaded = FreshnessIndex::load(path);
+        assert_eq!(loaded.steps.len(), 1);
+        assert_eq!(loaded.steps["bundle"], index.steps["bundle"]);
+    }
+}
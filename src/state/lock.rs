@@ -0,0 +1,491 @@
+//! Per-project advisory lock for commands that write state.
+//!
+//! `StateStore`, `SatisfactionCache` and `SnapshotStore` each write their
+//! files atomically, but two `bivvy run` processes in the same project
+//! would still interleave and overwrite each other's records. Commands that
+//! write state take this lock first; read-only commands (`status`, `last`,
+//! `list`) never do.
+//!
+//! The lock is a `run.lock` file in the project's state directory holding
+//! the owner's PID, host and start time. A lock whose owner is gone (same
+//! host, process no longer running) or that is older than [`STALE_AFTER`]
+//! is treated as stale and taken over. Takeovers go through a
+//! `run.lock.takeover` file so only one process replaces a given stale
+//! lock.
+
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{ProjectId, StateStore};
+use crate::error::{BivvyError, Result};
+
+/// File name of the lock inside the project state directory.
+pub const LOCK_FILE: &str = "run.lock";
+
+/// Age after which a lock is considered stale regardless of its owner.
+pub const STALE_AFTER: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// How long an unreadable lock file is respected before being treated as
+/// stale. Covers the window between creating the file and writing it.
+const UNREADABLE_GRACE: Duration = Duration::from_secs(5);
+
+/// Interval between attempts while waiting for a lock.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// What to do when another process holds the lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitPolicy {
+    /// Block until the lock is released.
+    Wait,
+    /// Fail immediately with [`BivvyError::ProjectLocked`].
+    NoWait,
+}
+
+impl WaitPolicy {
+    /// Resolve `--wait`/`--no-wait` flags.
+    ///
+    /// Without either flag, interactive sessions wait and non-interactive
+    /// ones (CI, editor integrations) fail fast.
+    pub fn from_flags(wait: bool, no_wait: bool, interactive: bool) -> Self {
+        if wait || (!no_wait && interactive) {
+            Self::Wait
+        } else {
+            Self::NoWait
+        }
+    }
+}
+
+/// Metadata written into the lock file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockInfo {
+    /// PID of the owning process.
+    pub pid: u32,
+    /// Host the owning process runs on.
+    pub host: String,
+    /// The bivvy command holding the lock (e.g. `run`).
+    pub command: String,
+    /// When the lock was taken.
+    pub acquired_at: DateTime<Utc>,
+    /// Random token identifying this acquisition.
+    pub token: String,
+}
+
+impl LockInfo {
+    fn current(command: &str) -> Self {
+        let mut bytes = [0u8; 8];
+        crate::sys::random_bytes(&mut bytes);
+        Self {
+            pid: std::process::id(),
+            host: crate::sys::hostname(),
+            command: command.to_string(),
+            acquired_at: Utc::now(),
+            token: hex::encode(bytes),
+        }
+    }
+
+    /// Whether the owner of this lock can no longer be holding it.
+    pub fn is_stale(&self) -> bool {
+        let age = Utc::now()
+            .signed_duration_since(self.acquired_at)
+            .to_std()
+            .unwrap_or_default();
+        if age > STALE_AFTER {
+            return true;
+        }
+        self.host == crate::sys::hostname() && !crate::sys::process_alive(self.pid)
+    }
+
+    /// Human-readable description of the owner.
+    pub fn describe(&self) -> String {
+        format!(
+            "bivvy {}, pid {} on {}, started {}",
+            self.command,
+            self.pid,
+            self.host,
+            self.acquired_at
+                .with_timezone(&chrono::Local)
+                .format("%H:%M:%S")
+        )
+    }
+}
+
+/// A held project lock. The lock file is removed on drop.
+#[derive(Debug)]
+pub struct ProjectLock {
+    path: PathBuf,
+    info: LockInfo,
+}
+
+impl ProjectLock {
+    /// Path of the lock file for a project.
+    pub fn path_for(project_id: &ProjectId) -> PathBuf {
+        StateStore::state_dir(project_id).join(LOCK_FILE)
+    }
+
+    /// Acquire the lock for a project.
+    ///
+    /// `on_wait` is called once with the current owner when the policy is
+    /// [`WaitPolicy::Wait`] and the lock is busy.
+    pub fn acquire(
+        project_id: &ProjectId,
+        command: &str,
+        policy: WaitPolicy,
+        on_wait: impl FnMut(&LockInfo),
+    ) -> Result<Self> {
+        Self::acquire_in(&StateStore::state_dir(project_id), command, policy, on_wait)
+    }
+
+    /// Acquire the lock in the given directory.
+    pub fn acquire_in(
+        dir: &Path,
+        command: &str,
+        policy: WaitPolicy,
+        mut on_wait: impl FnMut(&LockInfo),
+    ) -> Result<Self> {
+        fs::create_dir_all(dir)?;
+        let path = dir.join(LOCK_FILE);
+        let info = LockInfo::current(command);
+        let mut notified = false;
+
+        loop {
+            match Self::try_create(&path, &info) {
+                Ok(()) => return Ok(Self { path, info }),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.into()),
+            }
+
+            match read_holder(&path) {
+                Holder::Released => continue,
+                Holder::Stale(content) => {
+                    tracing::warn!("Removing stale project lock {}", path.display());
+                    if !remove_stale(&path, &content)? {
+                        // Another process is taking it over
+                        std::thread::sleep(POLL_INTERVAL);
+                    }
+                    continue;
+                }
+                Holder::Unreadable => {}
+                Holder::Held(holder) => match policy {
+                    WaitPolicy::NoWait => {
+                        return Err(BivvyError::ProjectLocked {
+                            holder: holder.describe(),
+                        });
+                    }
+                    WaitPolicy::Wait if !notified => {
+                        on_wait(&holder);
+                        notified = true;
+                    }
+                    WaitPolicy::Wait => {}
+                },
+            }
+
+            if policy == WaitPolicy::NoWait {
+                return Err(BivvyError::ProjectLocked {
+                    holder: format!("lock file {}", path.display()),
+                });
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    fn try_create(path: &Path, info: &LockInfo) -> std::io::Result<()> {
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)?;
+        let content = serde_json::to_string(info).map_err(std::io::Error::other)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    }
+
+    /// Read the current owner of a project lock, if any.
+    ///
+    /// Stale and unreadable locks are reported as `None`.
+    pub fn holder(project_id: &ProjectId) -> Option<LockInfo> {
+        match read_holder(&Self::path_for(project_id)) {
+            Holder::Held(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Metadata of this lock.
+    pub fn info(&self) -> &LockInfo {
+        &self.info
+    }
+}
+
+impl Drop for ProjectLock {
+    fn drop(&mut self) {
+        // Only remove the file if it is still ours; a stale-lock takeover
+        // by another process must not be undone.
+        if let Ok(content) = fs::read_to_string(&self.path) {
+            if let Ok(info) = serde_json::from_str::<LockInfo>(&content) {
+                if info.token == self.info.token {
+                    let _ = fs::remove_file(&self.path);
+                }
+            }
+        }
+    }
+}
+
+enum Holder {
+    Released,
+    /// A stale lock, with the file content it was judged on.
+    Stale(String),
+    Unreadable,
+    Held(LockInfo),
+}
+
+fn read_holder(path: &Path) -> Holder {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Holder::Released,
+        Err(_) => return Holder::Unreadable,
+    };
+    match serde_json::from_str::<LockInfo>(&content) {
+        Ok(info) if info.is_stale() => Holder::Stale(content),
+        Ok(info) => Holder::Held(info),
+        Err(_) if older_than_grace(path) => Holder::Stale(content),
+        Err(_) => Holder::Unreadable,
+    }
+}
+
+/// Whether a file was last modified more than [`UNREADABLE_GRACE`] ago.
+fn older_than_grace(path: &Path) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|age| age > UNREADABLE_GRACE)
+}
+
+/// Remove a stale lock if it still holds the content it was judged stale
+/// on. Returns `false` if another process is mid-takeover.
+///
+/// Takeovers are serialized through a `run.lock.takeover` file created
+/// exclusively, and the lock is re-read while holding it. Two processes
+/// that both saw the same stale lock therefore can't delete the fresh lock
+/// the first one creates after removing it.
+fn remove_stale(path: &Path, stale: &str) -> Result<bool> {
+    let guard = path.with_file_name(format!("{}.takeover", LOCK_FILE));
+    match fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&guard)
+    {
+        Ok(_) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+            // Left behind by a process that died mid-takeover
+            if older_than_grace(&guard) {
+                let _ = fs::remove_file(&guard);
+            }
+            return Ok(false);
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    let result = match fs::read_to_string(path) {
+        Ok(current) if current == stale => match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(true),
+        },
+        // Released or replaced since it was read
+        _ => Ok(true),
+    };
+    let _ = fs::remove_file(&guard);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_lock(dir: &Path, info: &LockInfo) {
+        fs::write(dir.join(LOCK_FILE), serde_json::to_string(info).unwrap()).unwrap();
+    }
+
+    fn foreign_lock(pid: u32, host: &str) -> LockInfo {
+        LockInfo {
+            pid,
+            host: host.to_string(),
+            command: "run".to_string(),
+            acquired_at: Utc::now(),
+            token: "other".to_string(),
+        }
+    }
+
+    #[test]
+    fn acquire_writes_metadata_and_releases_on_drop() {
+        let temp = TempDir::new().unwrap();
+        let lock = ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap();
+
+        let content = fs::read_to_string(temp.path().join(LOCK_FILE)).unwrap();
+        let info: LockInfo = serde_json::from_str(&content).unwrap();
+        assert_eq!(info.pid, std::process::id());
+        assert_eq!(info.host, crate::sys::hostname());
+        assert_eq!(info.command, "run");
+
+        drop(lock);
+        assert!(!temp.path().join(LOCK_FILE).exists());
+    }
+
+    #[test]
+    fn second_acquire_fails_without_wait() {
+        let temp = TempDir::new().unwrap();
+        let _lock =
+            ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap();
+
+        let err =
+            ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap_err();
+        match err {
+            BivvyError::ProjectLocked { holder } => {
+                assert!(holder.contains(&format!("pid {}", std::process::id())));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn wait_blocks_until_released() {
+        let temp = TempDir::new().unwrap();
+        let lock = ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap();
+
+        let dir = temp.path().to_path_buf();
+        let waiter = std::thread::spawn(move || {
+            let mut waited = 0;
+            let lock = ProjectLock::acquire_in(&dir, "snapshot", WaitPolicy::Wait, |_| waited += 1)
+                .unwrap();
+            (waited, lock.info().command.clone())
+        });
+
+        std::thread::sleep(Duration::from_millis(400));
+        drop(lock);
+        let (waited, command) = waiter.join().unwrap();
+        assert_eq!(waited, 1);
+        assert_eq!(command, "snapshot");
+    }
+
+    #[test]
+    fn lock_of_dead_process_is_taken_over() {
+        let temp = TempDir::new().unwrap();
+        let mut child = std::process::Command::new(if cfg!(windows) { "cmd" } else { "true" })
+            .args(if cfg!(windows) {
+                &["/C", "exit"][..]
+            } else {
+                &[][..]
+            })
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        write_lock(temp.path(), &foreign_lock(pid, &crate::sys::hostname()));
+
+        let lock = ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap();
+        assert_eq!(lock.info().pid, std::process::id());
+    }
+
+    #[test]
+    fn lock_on_other_host_is_respected() {
+        let temp = TempDir::new().unwrap();
+        write_lock(temp.path(), &foreign_lock(1, "some-other-host"));
+
+        assert!(matches!(
+            ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}),
+            Err(BivvyError::ProjectLocked { .. })
+        ));
+    }
+
+    #[test]
+    fn old_lock_is_stale() {
+        let mut info = foreign_lock(1, "some-other-host");
+        info.acquired_at = Utc::now() - chrono::Duration::hours(25);
+        assert!(info.is_stale());
+    }
+
+    #[test]
+    fn drop_keeps_lock_taken_over_by_another_process() {
+        let temp = TempDir::new().unwrap();
+        let lock = ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {}).unwrap();
+        write_lock(temp.path(), &foreign_lock(1, "some-other-host"));
+
+        drop(lock);
+        assert!(temp.path().join(LOCK_FILE).exists());
+    }
+
+    #[test]
+    fn stale_removal_keeps_a_lock_replaced_since_it_was_read() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        let mut stale = foreign_lock(1, "some-other-host");
+        stale.acquired_at = Utc::now() - chrono::Duration::hours(25);
+        write_lock(temp.path(), &stale);
+        let Holder::Stale(content) = read_holder(&path) else {
+            panic!("expected a stale lock");
+        };
+
+        // Another process takes over between the read and the removal
+        let fresh = ProjectLock::acquire_in(temp.path(), "run", WaitPolicy::NoWait, |_| {});
+        let fresh = fresh.unwrap();
+
+        assert!(remove_stale(&path, &content).unwrap());
+        let holder: LockInfo = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(holder.token, fresh.info().token);
+    }
+
+    #[test]
+    fn stale_removal_waits_for_a_takeover_in_progress() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(LOCK_FILE);
+        fs::write(&path, "stale").unwrap();
+        fs::write(temp.path().join("run.lock.takeover"), "").unwrap();
+
+        assert!(!remove_stale(&path, "stale").unwrap());
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn concurrent_takeovers_of_a_stale_lock_have_one_winner() {
+        let temp = TempDir::new().unwrap();
+        let mut stale = foreign_lock(1, "some-other-host");
+        stale.acquired_at = Utc::now() - chrono::Duration::hours(25);
+        write_lock(temp.path(), &stale);
+
+        let barrier = std::sync::Arc::new(std::sync::Barrier::new(8));
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let dir = temp.path().to_path_buf();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    ProjectLock::acquire_in(&dir, "run", WaitPolicy::NoWait, |_| {}).ok()
+                })
+            })
+            .collect();
+        let locks: Vec<_> = handles
+            .into_iter()
+            .filter_map(|h| h.join().unwrap())
+            .collect();
+
+        assert_eq!(locks.len(), 1);
+    }
+
+    #[test]
+    fn wait_policy_from_flags() {
+        assert_eq!(WaitPolicy::from_flags(false, false, true), WaitPolicy::Wait);
+        assert_eq!(
+            WaitPolicy::from_flags(false, false, false),
+            WaitPolicy::NoWait
+        );
+        assert_eq!(WaitPolicy::from_flags(true, false, false), WaitPolicy::Wait);
+        assert_eq!(
+            WaitPolicy::from_flags(false, true, true),
+            WaitPolicy::NoWait
+        );
+    }
+}
@@ -3,16 +3,20 @@
 //! This module provides persistent state storage for Bivvy projects,
 //! tracking execution history, step states, and user preferences.
 
+pub mod freshness;
 pub mod history;
 pub mod index;
+pub mod lock;
 pub mod preferences;
 pub mod project;
 pub mod recorder;
 pub mod satisfaction;
 pub mod store;
 
+pub use freshness::{FreshnessIndex, StaleStep, WatchedTarget};
 pub use history::{RunHistoryBuilder, RunRecord, RunStatus};
 pub use index::{ProjectEntry, ProjectIndex};
+pub use lock::{LockInfo, ProjectLock, WaitPolicy};
 pub use preferences::Preferences;
 pub use project::ProjectId;
 pub use recorder::StateRecorder;
@@ -99,6 +99,13 @@ impl ProjectId {
         crate::state::StateStore::state_dir(self).join("satisfaction.json")
     }
 
+    /// Get the path to the change-check freshness index for a project.
+    ///
+    /// Located alongside the state file in `~/.bivvy/projects/{hash}/freshness.json`.
+    pub fn freshness_path(&self) -> PathBuf {
+        crate::state::StateStore::state_dir(self).join("freshness.json")
+    }
+
     fn compute_hash(path: &Path, git_remote: Option<&str>) -> String {
         let mut hasher = Sha256::new();
 
@@ -4,10 +4,11 @@
 //! producing a fully-specified step that can be executed.
 
 use crate::checks::{Check, SatisfactionCondition};
-use crate::config::schema::{PromptConfig, StepEnvironmentOverride};
+use crate::config::interpolation::extract_variables;
+use crate::config::schema::{ArtifactCacheConfig, PromptConfig, StepEnvironmentOverride};
 use crate::config::StepConfig;
 use crate::registry::template::Template;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Resolved execution fields (command, checks, retry, sudo).
@@ -30,6 +31,9 @@ pub struct ResolvedExecution {
 
     /// Requires sudo.
     pub requires_sudo: bool,
+
+    /// Artifact cache keyed by input files.
+    pub cache: Option<ArtifactCacheConfig>,
 }
 
 impl ResolvedExecution {
@@ -228,6 +232,7 @@ impl ResolvedStep {
                 precondition: config.execution.precondition.clone(),
                 retry: config.execution.retry,
                 requires_sudo: config.execution.requires_sudo,
+                cache: config.execution.cache.clone(),
             },
             env_vars: ResolvedEnvironmentVars {
                 env: merge_env(&step.env, &config.env_vars.env),
@@ -290,6 +295,7 @@ impl ResolvedStep {
                 precondition: config.execution.precondition.clone(),
                 retry: config.execution.retry,
                 requires_sudo: config.execution.requires_sudo,
+                cache: config.execution.cache.clone(),
             },
             env_vars: ResolvedEnvironmentVars {
                 env: config.env_vars.env.clone(),
@@ -395,6 +401,58 @@ impl ResolvedStep {
             self.behavior.rerun_window = resolve_rerun_window(Some(w));
         }
     }
+
+    /// Names this step may look up in the interpolation context.
+    ///
+    /// Covers `${...}` references in the command, hooks, env values and
+    /// checks, plus the keys of the step's prompts (a var with the same
+    /// name answers the prompt). Used to evaluate only the `vars:` a run
+    /// actually needs.
+    pub fn referenced_variables(&self) -> HashSet<String> {
+        let mut names: HashSet<String> = std::iter::once(&self.execution.command)
+            .chain(&self.hooks.before)
+            .chain(&self.hooks.after)
+            .chain(self.env_vars.env.values())
+            .flat_map(|s| extract_variables(s))
+            .collect();
+
+        let checks = self
+            .execution
+            .check
+            .iter()
+            .chain(&self.execution.checks)
+            .chain(&self.execution.precondition)
+            .filter_map(|c| serde_yaml::to_value(c).ok())
+            .chain(
+                self.satisfied_when
+                    .iter()
+                    .filter_map(|c| serde_yaml::to_value(c).ok()),
+            );
+        for value in checks {
+            collect_yaml_variables(&value, &mut names);
+        }
+
+        names.extend(self.output.prompts.iter().map(|p| p.key.clone()));
+        names
+    }
+}
+
+/// Collect `${...}` references from every string in a YAML value.
+fn collect_yaml_variables(value: &serde_yaml::Value, names: &mut HashSet<String>) {
+    match value {
+        serde_yaml::Value::String(s) => names.extend(extract_variables(s)),
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                collect_yaml_variables(item, names);
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for item in map.values() {
+                collect_yaml_variables(item, names);
+            }
+        }
+        _ => {}
+    }
 }
 
 /// Resolve a rerun window string to a `RerunWindow`, falling back to the default.
@@ -476,6 +534,11 @@ fn merge_template_prompts(
             prompt_type: template_prompt.prompt_type.clone(),
             options: template_prompt.options.clone(),
             default: input_def.default.clone(),
+            min: template_prompt.min,
+            max: template_prompt.max,
+            pattern: template_prompt.pattern.clone(),
+            pattern_message: template_prompt.pattern_message.clone(),
+            must_be: template_prompt.must_be,
         });
     }
 
@@ -1381,6 +1444,66 @@ mod tests {
         assert!(!resolved.inputs.contains_key("opt"));
     }
 
+    #[test]
+    fn from_template_input_prompt_keeps_constraints() {
+        use crate::config::schema::PathRequirement;
+        use crate::registry::template::TemplateInput;
+
+        let mut template = make_template();
+        let inputs: HashMap<String, TemplateInput> = serde_yaml::from_str(
+            r#"
+replicas:
+  description: Replica count
+  type: number
+  prompt:
+    question: How many replicas?
+    type: number
+    min: 1
+    max: 5
+slug:
+  description: App slug
+  type: string
+  prompt:
+    question: App slug?
+    type: input
+    pattern: "[a-z-]+"
+    pattern_message: use lowercase letters and dashes
+seed_file:
+  description: Seed file
+  type: string
+  prompt:
+    question: Seed file?
+    type: path
+    must_be: file
+"#,
+        )
+        .unwrap();
+        template.inputs = inputs;
+
+        let config = StepConfig::default();
+        let resolved =
+            ResolvedStep::from_template("test", &template, &config, &HashMap::new(), None);
+        let prompt = |key: &str| {
+            resolved
+                .output
+                .prompts
+                .iter()
+                .find(|p| p.key == key)
+                .unwrap()
+                .clone()
+        };
+
+        let replicas = prompt("replicas");
+        assert_eq!((replicas.min, replicas.max), (Some(1.0), Some(5.0)));
+        let slug = prompt("slug");
+        assert_eq!(slug.pattern.as_deref(), Some("[a-z-]+"));
+        assert_eq!(
+            slug.pattern_message.as_deref(),
+            Some("use lowercase letters and dashes")
+        );
+        assert_eq!(prompt("seed_file").must_be, Some(PathRequirement::File));
+    }
+
     #[test]
     fn from_config_has_empty_inputs() {
         let config = StepConfig::default();
@@ -1401,6 +1524,11 @@ mod tests {
                     prompt_type: PromptType::Input,
                     options: vec![],
                     default: None,
+                    min: None,
+                    max: None,
+                    pattern: None,
+                    pattern_message: None,
+                    must_be: None,
                 }],
                 ..Default::default()
             },
@@ -1429,6 +1557,11 @@ mod tests {
                     prompt_type: PromptType::Input,
                     options: vec![],
                     default: None,
+                    min: None,
+                    max: None,
+                    pattern: None,
+                    pattern_message: None,
+                    must_be: None,
                 }],
                 ..Default::default()
             },
@@ -1487,4 +1620,38 @@ mod tests {
             ResolvedStep::from_template("test", &template, &config, &HashMap::new(), None);
         assert_eq!(resolved.satisfied_when.len(), 1);
     }
+
+    #[test]
+    fn referenced_variables_covers_command_hooks_env_and_checks() {
+        let config: StepConfig = serde_yaml::from_str(
+            r#"
+            command: "deploy --region ${region}"
+            env:
+              VERSION: "${version}"
+            before: ["echo ${greeting}"]
+            check:
+              type: presence
+              target: "${output_dir}/app"
+            prompts:
+              - key: confirm_deploy
+                question: Deploy?
+                type: confirm
+            "#,
+        )
+        .unwrap();
+
+        let resolved = ResolvedStep::from_config("deploy", &config, None);
+        let mut names: Vec<_> = resolved.referenced_variables().into_iter().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                "confirm_deploy",
+                "greeting",
+                "output_dir",
+                "region",
+                "version"
+            ]
+        );
+    }
 }
@@ -6,6 +6,7 @@
 //! - Opening URLs in the default browser (replaces `open`)
 //! - Finding executables on PATH (replaces `which`)
 //! - Cryptographic random byte generation (replaces `getrandom`)
+//! - Host name and process liveness lookup (replaces `hostname`/`sysinfo`)
 //! - File glob pattern matching (replaces `glob`)
 
 use std::path::{Path, PathBuf};
@@ -211,6 +212,61 @@ pub fn random_bytes(buf: &mut [u8]) {
     assert!(status >= 0, "BCryptGenRandom failed with status {status}");
 }
 
+// === Processes ===
+
+/// Returns the name of this machine, or `"unknown"` if it cannot be read.
+#[cfg(unix)]
+pub fn hostname() -> String {
+    let mut buf = [0u8; 256];
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len()) };
+    if rc != 0 {
+        return "unknown".to_string();
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8_lossy(&buf[..len]).into_owned()
+}
+
+/// Returns the name of this machine, or `"unknown"` if it cannot be read.
+#[cfg(windows)]
+pub fn hostname() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Check whether a process with the given PID is running on this machine.
+#[cfg(unix)]
+pub fn process_alive(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    if unsafe { libc::kill(pid, 0) } == 0 {
+        return true;
+    }
+    // EPERM means the process exists but belongs to another user.
+    std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+/// Check whether a process with the given PID is running on this machine.
+#[cfg(windows)]
+pub fn process_alive(pid: u32) -> bool {
+    extern "system" {
+        fn OpenProcess(access: u32, inherit: i32, pid: u32) -> *mut std::ffi::c_void;
+        fn GetExitCodeProcess(handle: *mut std::ffi::c_void, code: *mut u32) -> i32;
+        fn CloseHandle(handle: *mut std::ffi::c_void) -> i32;
+    }
+    const PROCESS_QUERY_LIMITED_INFORMATION: u32 = 0x1000;
+    const STILL_ACTIVE: u32 = 259;
+    unsafe {
+        let handle = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+        if handle.is_null() {
+            return false;
+        }
+        let mut code = 0u32;
+        let ok = GetExitCodeProcess(handle, &mut code);
+        CloseHandle(handle);
+        ok != 0 && code == STILL_ACTIVE
+    }
+}
+
 // === Glob pattern matching ===
 
 /// Match files against a glob pattern and return matching paths.
@@ -279,7 +335,7 @@ fn walk_dir_recursive(dir: &Path, results: &mut Vec<PathBuf>) {
 /// Both pattern and path use `/` as separator. The pattern may contain
 /// `*` (matches non-separator chars), `**` (matches across separators),
 /// and `?` (matches single non-separator char).
-fn glob_match_path(pattern: &str, path: &str) -> bool {
+pub fn glob_match_path(pattern: &str, path: &str) -> bool {
     let pat_parts: Vec<&str> = pattern.split('/').collect();
     let path_parts: Vec<&str> = path.split('/').collect();
     match_components(&pat_parts, &path_parts)
@@ -422,6 +478,31 @@ mod tests {
         assert!(buf.iter().any(|&b| b != 0));
     }
 
+    #[test]
+    fn hostname_is_not_empty() {
+        assert!(!hostname().is_empty());
+    }
+
+    #[test]
+    fn process_alive_for_current_process() {
+        assert!(process_alive(std::process::id()));
+    }
+
+    #[test]
+    fn process_alive_false_for_exited_child() {
+        let mut child = std::process::Command::new(if cfg!(windows) { "cmd" } else { "true" })
+            .args(if cfg!(windows) {
+                &["/C", "exit"][..]
+            } else {
+                &[][..]
+            })
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        assert!(!process_alive(pid));
+    }
+
     #[test]
     fn random_bytes_produces_different_values() {
         let mut buf1 = [0u8; 16];
@@ -0,0 +1,132 @@
+//! Line diffs for reviewing file changes before they're written.
+
+use super::BivvyTheme;
+
+/// Lines of unchanged context shown around each change.
+const CONTEXT: usize = 3;
+
+/// One line of a diff.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// Line in both versions.
+    Same(&'a str),
+    /// Line only in the old version.
+    Removed(&'a str),
+    /// Line only in the new version.
+    Added(&'a str),
+}
+
+/// Diff two texts line by line (longest common subsequence).
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the LCS of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|l| DiffLine::Removed(l)));
+    lines.extend(new[j..].iter().map(|l| DiffLine::Added(l)));
+    lines
+}
+
+/// Render a diff with `+`/`-` markers, showing a few lines of context
+/// around each change and eliding the rest.
+pub fn render_diff(lines: &[DiffLine], theme: &BivvyTheme) -> String {
+    let changed: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+    let near_change = |i: usize| {
+        changed
+            .iter()
+            .any(|&c| i + CONTEXT >= c && i <= c + CONTEXT)
+    };
+
+    let mut out = String::new();
+    let mut elided = false;
+    for (i, line) in lines.iter().enumerate() {
+        let rendered = match line {
+            DiffLine::Same(text) if near_change(i) => format!("  {text}"),
+            DiffLine::Same(_) => {
+                if !elided {
+                    out.push_str(&format!("{}\n", theme.dim.apply_to("  ...")));
+                    elided = true;
+                }
+                continue;
+            }
+            DiffLine::Removed(text) => theme.error.apply_to(format!("- {text}")).to_string(),
+            DiffLine::Added(text) => theme.success.apply_to(format!("+ {text}")).to_string(),
+        };
+        elided = false;
+        out.push_str(rendered.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_of_identical_text_is_all_same() {
+        let diff = line_diff("a\nb\n", "a\nb\n");
+        assert_eq!(diff, vec![DiffLine::Same("a"), DiffLine::Same("b")]);
+    }
+
+    #[test]
+    fn diff_marks_added_and_removed_lines() {
+        let diff = line_diff("a\nb\nc\n", "a\nx\nc\nd\n");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a"),
+                DiffLine::Removed("b"),
+                DiffLine::Added("x"),
+                DiffLine::Same("c"),
+                DiffLine::Added("d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_against_empty_adds_everything() {
+        let diff = line_diff("", "a\nb");
+        assert_eq!(diff, vec![DiffLine::Added("a"), DiffLine::Added("b")]);
+    }
+
+    #[test]
+    fn render_elides_distant_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\n3\n4\n5\n6\n7\n8\n9\nten\n";
+        let rendered = render_diff(&line_diff(old, new), &BivvyTheme::plain());
+
+        assert_eq!(rendered, "  ...\n  7\n  8\n  9\n- 10\n+ ten\n");
+    }
+}
@@ -255,7 +255,7 @@ impl Prompter for MockUI {
                         response.split(',').map(|s| s.trim().to_string()).collect();
                     return Ok(PromptResult::Strings(values));
                 }
-                return Ok(PromptResult::String(response));
+                return prompt.check(PromptResult::String(response));
             }
         }
 
@@ -270,7 +270,7 @@ impl Prompter for MockUI {
                     response.split(',').map(|s| s.trim().to_string()).collect();
                 return Ok(PromptResult::Strings(values));
             }
-            return Ok(PromptResult::String(response.clone()));
+            return prompt.check(PromptResult::String(response.clone()));
         }
 
         // Fall back to default_prompt_response if set (before prompt.default)
@@ -284,7 +284,7 @@ impl Prompter for MockUI {
                     response.split(',').map(|s| s.trim().to_string()).collect();
                 return Ok(PromptResult::Strings(values));
             }
-            return Ok(PromptResult::String(response.clone()));
+            return prompt.check(PromptResult::String(response.clone()));
         }
 
         // Fall back to default if available
@@ -298,7 +298,7 @@ impl Prompter for MockUI {
                     default.split(',').map(|s| s.trim().to_string()).collect();
                 return Ok(PromptResult::Strings(values));
             }
-            return Ok(PromptResult::String(default.clone()));
+            return prompt.check(PromptResult::String(default.clone()));
         }
 
         // Return type-appropriate empty for last resort (for testing)
@@ -841,4 +841,26 @@ mod tests {
         assert_eq!(ui.prompt(&prompt).unwrap().as_bool(), Some(true));
         assert_eq!(ui.prompt(&prompt).unwrap().as_bool(), Some(false));
     }
+
+    #[test]
+    fn mock_ui_rejects_invalid_number_response() {
+        let mut ui = MockUI::new();
+        ui.set_prompt_response("port", "http");
+
+        let prompt = Prompt {
+            key: "port".to_string(),
+            question: "Port?".to_string(),
+            prompt_type: PromptType::Number {
+                min: None,
+                max: None,
+            },
+            default: None,
+        };
+
+        let err = ui.prompt(&prompt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid value for prompt 'port': 'http' is not a number"
+        );
+    }
 }
@@ -4,7 +4,7 @@
 //! - [`UserInterface`] trait for UI abstraction
 //! - [`TerminalUI`] for interactive terminal usage
 //! - [`NonInteractiveUI`] for CI/headless environments
-//! - Prompts, spinners, progress indicators, and tables
+//! - Prompts, spinners, progress indicators, tables and diffs
 //!
 //! # Example
 //!
@@ -17,6 +17,7 @@
 //! ui.success("Setup complete!");
 //! ```
 
+pub mod diff;
 pub mod hints;
 pub mod icons;
 pub mod mock;
@@ -31,7 +32,9 @@ pub mod surface;
 pub mod table;
 pub mod terminal;
 pub mod theme;
+pub mod tui;
 
+pub use diff::{line_diff, render_diff, DiffLine};
 pub use icons::StatusKind;
 pub use mock::{MockSpinner, MockUI};
 pub use non_interactive::NonInteractiveUI;
@@ -58,7 +61,8 @@ pub(crate) fn is_dumb_term() -> bool {
     std::env::var("TERM").map(|v| v == "dumb").unwrap_or(false)
 }
 
-use crate::error::Result;
+pub use crate::config::schema::PathRequirement;
+use crate::error::{BivvyError, Result};
 use indicatif::ProgressBar;
 
 /// Output methods for displaying messages, warnings, errors, and hints.
@@ -135,6 +139,14 @@ pub trait UiState {
     /// Change the output mode at runtime.
     fn set_output_mode(&mut self, mode: OutputMode);
 
+    /// Whether full-screen [`tui`] screens can take over the terminal.
+    ///
+    /// Only `TerminalUI` on a real, capable terminal returns true; every
+    /// other UI keeps commands on their prompt flow.
+    fn supports_fullscreen(&self) -> bool {
+        false
+    }
+
     /// Attach a [`crate::ui::surface::TerminalSurface`] for the
     /// duration of a run. Default impl is a no-op for UIs that have no
     /// surface concept (mock, non-interactive). Only `TerminalUI`
@@ -212,6 +224,152 @@ pub enum PromptType {
     Select { options: Vec<PromptOption> },
     /// Select multiple from a list of options.
     MultiSelect { options: Vec<PromptOption> },
+    /// Masked text input whose value is never echoed or logged.
+    Secret,
+    /// Numeric input, optionally bounded.
+    Number { min: Option<f64>, max: Option<f64> },
+    /// Filesystem path input with tab completion.
+    Path { must_be: Option<PathRequirement> },
+    /// Free-form text input that must fully match a regex.
+    ValidatedInput {
+        pattern: String,
+        message: Option<String>,
+    },
+}
+
+impl PromptType {
+    /// Whether answers to this prompt must be masked in output and events.
+    pub fn is_secret(&self) -> bool {
+        matches!(self, Self::Secret)
+    }
+
+    /// Check a text answer against this prompt type's constraints.
+    ///
+    /// Returns a human-readable reason when the value is rejected. Types
+    /// without constraints accept any value.
+    pub fn validate(&self, value: &str) -> std::result::Result<(), String> {
+        match self {
+            Self::Number { min, max } => {
+                let n: f64 = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("'{}' is not a number", value))?;
+                if let Some(min) = min {
+                    if n < *min {
+                        return Err(format!("must be at least {}", min));
+                    }
+                }
+                if let Some(max) = max {
+                    if n > *max {
+                        return Err(format!("must be at most {}", max));
+                    }
+                }
+                Ok(())
+            }
+            Self::Path { must_be } => {
+                let Some(requirement) = must_be else {
+                    return Ok(());
+                };
+                let path = prompts::expand_home(value);
+                let ok = match requirement {
+                    PathRequirement::Exists => path.exists(),
+                    PathRequirement::File => path.is_file(),
+                    PathRequirement::Dir => path.is_dir(),
+                };
+                if ok {
+                    Ok(())
+                } else {
+                    let kind = match requirement {
+                        PathRequirement::Exists => "path",
+                        PathRequirement::File => "file",
+                        PathRequirement::Dir => "directory",
+                    };
+                    Err(format!("{} '{}' does not exist", kind, value))
+                }
+            }
+            Self::ValidatedInput { pattern, message } => {
+                let re = regex::Regex::new(&format!("^(?:{})$", pattern))
+                    .map_err(|e| format!("invalid pattern '{}': {}", pattern, e))?;
+                if re.is_match(value) {
+                    Ok(())
+                } else {
+                    Err(message
+                        .clone()
+                        .unwrap_or_else(|| format!("must match pattern '{}'", pattern)))
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Prompt {
+    /// Validate a prompt result against this prompt's constraints.
+    ///
+    /// Used for answers that bypass the interactive validator (env
+    /// overrides, defaults, pre-configured responses).
+    pub fn check(&self, result: PromptResult) -> Result<PromptResult> {
+        if let PromptResult::String(value) = &result {
+            self.prompt_type.validate(value).map_err(|reason| {
+                BivvyError::ConfigValidationError {
+                    message: format!("Invalid value for prompt '{}': {}", self.key, reason),
+                }
+            })?;
+        }
+        Ok(result)
+    }
+}
+
+impl From<&crate::config::schema::PromptConfig> for Prompt {
+    fn from(config: &crate::config::schema::PromptConfig) -> Self {
+        use crate::config::schema::PromptType as ConfigPromptType;
+
+        let default = config.default.as_ref().and_then(|v| match v {
+            serde_yaml::Value::String(s) => Some(s.clone()),
+            serde_yaml::Value::Bool(b) => Some(b.to_string()),
+            serde_yaml::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        });
+
+        let options = || {
+            config
+                .options
+                .iter()
+                .map(|o| PromptOption {
+                    label: o.label.clone(),
+                    value: o.value.clone(),
+                })
+                .collect()
+        };
+
+        let prompt_type = match &config.prompt_type {
+            ConfigPromptType::Select => PromptType::Select { options: options() },
+            ConfigPromptType::Multiselect => PromptType::MultiSelect { options: options() },
+            ConfigPromptType::Confirm => PromptType::Confirm,
+            ConfigPromptType::Input => match &config.pattern {
+                Some(pattern) => PromptType::ValidatedInput {
+                    pattern: pattern.clone(),
+                    message: config.pattern_message.clone(),
+                },
+                None => PromptType::Input,
+            },
+            ConfigPromptType::Secret => PromptType::Secret,
+            ConfigPromptType::Number => PromptType::Number {
+                min: config.min,
+                max: config.max,
+            },
+            ConfigPromptType::Path => PromptType::Path {
+                must_be: config.must_be,
+            },
+        };
+
+        Self {
+            key: config.key.clone(),
+            question: config.question.clone(),
+            prompt_type,
+            default,
+        }
+    }
 }
 
 /// An option in a select prompt.
@@ -387,4 +545,115 @@ mod tests {
         let result = PromptResult::Strings(vec!["true".to_string()]);
         assert_eq!(result.as_bool(), None);
     }
+
+    #[test]
+    fn number_validation_checks_bounds() {
+        let number = PromptType::Number {
+            min: Some(1.0),
+            max: Some(10.0),
+        };
+        assert_eq!(number.validate("5"), Ok(()));
+        assert_eq!(number.validate("2.5"), Ok(()));
+        assert_eq!(number.validate("0"), Err("must be at least 1".to_string()));
+        assert_eq!(number.validate("11"), Err("must be at most 10".to_string()));
+        assert_eq!(
+            number.validate("lots"),
+            Err("'lots' is not a number".to_string())
+        );
+    }
+
+    #[test]
+    fn validated_input_must_match_whole_value() {
+        let slug = PromptType::ValidatedInput {
+            pattern: "[a-z]+".to_string(),
+            message: None,
+        };
+        assert_eq!(slug.validate("myapp"), Ok(()));
+        assert_eq!(
+            slug.validate("myapp2"),
+            Err("must match pattern '[a-z]+'".to_string())
+        );
+
+        let with_message = PromptType::ValidatedInput {
+            pattern: "[a-z]+".to_string(),
+            message: Some("lowercase only".to_string()),
+        };
+        assert_eq!(
+            with_message.validate("MyApp"),
+            Err("lowercase only".to_string())
+        );
+    }
+
+    #[test]
+    fn path_validation_checks_requirement() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let file = temp.path().join("a.txt");
+        std::fs::write(&file, "").unwrap();
+        let dir = temp.path().display().to_string();
+        let file = file.display().to_string();
+
+        let must_be = |r| PromptType::Path { must_be: Some(r) };
+        assert_eq!(must_be(PathRequirement::File).validate(&file), Ok(()));
+        assert_eq!(must_be(PathRequirement::Dir).validate(&dir), Ok(()));
+        assert_eq!(must_be(PathRequirement::Exists).validate(&dir), Ok(()));
+        assert_eq!(
+            must_be(PathRequirement::Dir).validate(&file),
+            Err(format!("directory '{}' does not exist", file))
+        );
+        assert_eq!(
+            PromptType::Path { must_be: None }.validate("/no/such/path"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn check_reports_prompt_key() {
+        let prompt = Prompt {
+            key: "port".to_string(),
+            question: "Port?".to_string(),
+            prompt_type: PromptType::Number {
+                min: None,
+                max: Some(65535.0),
+            },
+            default: None,
+        };
+        let err = prompt
+            .check(PromptResult::String("70000".to_string()))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid value for prompt 'port': must be at most 65535"
+        );
+    }
+
+    #[test]
+    fn from_prompt_config_maps_new_types() {
+        let config: crate::config::schema::PromptConfig = serde_yaml::from_str(
+            "key: slug\nquestion: Slug?\ntype: input\npattern: '[a-z-]+'\npattern_message: lowercase\n",
+        )
+        .unwrap();
+        let prompt = Prompt::from(&config);
+        assert!(matches!(
+            prompt.prompt_type,
+            PromptType::ValidatedInput { ref pattern, message: Some(_) } if pattern == "[a-z-]+"
+        ));
+
+        let config: crate::config::schema::PromptConfig = serde_yaml::from_str(
+            "key: port\nquestion: Port?\ntype: number\nmin: 1\ndefault: 3000\n",
+        )
+        .unwrap();
+        let prompt = Prompt::from(&config);
+        assert!(matches!(
+            prompt.prompt_type,
+            PromptType::Number {
+                min: Some(_),
+                max: None
+            }
+        ));
+        assert_eq!(prompt.default.as_deref(), Some("3000"));
+
+        let config: crate::config::schema::PromptConfig =
+            serde_yaml::from_str("key: token\nquestion: Token?\ntype: secret\n").unwrap();
+        assert!(Prompt::from(&config).prompt_type.is_secret());
+    }
 }
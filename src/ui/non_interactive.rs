@@ -91,7 +91,7 @@ impl Prompter for NonInteractiveUI {
         // This is handled by prompt_user for TerminalUI, but
         // NonInteractiveUI doesn't call prompt_user, so check here too.
         if let Some(result) = super::prompts::env_override(prompt) {
-            return Ok(result);
+            return prompt.check(result);
         }
 
         // Use default
@@ -101,7 +101,7 @@ impl Prompter for NonInteractiveUI {
                     default.split(',').map(|s| s.trim().to_string()).collect();
                 return Ok(PromptResult::Strings(values));
             }
-            return Ok(PromptResult::String(default.clone()));
+            return prompt.check(PromptResult::String(default.clone()));
         }
 
         Err(BivvyError::ConfigValidationError {
@@ -316,4 +316,24 @@ mod tests {
         let ui = NonInteractiveUI::with_ci(OutputMode::Normal, false);
         assert!(!ui.is_ci);
     }
+
+    #[test]
+    fn prompt_rejects_default_failing_validation() {
+        let mut ui = NonInteractiveUI::new(OutputMode::Normal);
+        let prompt = Prompt {
+            key: "noninteractive_slug".to_string(),
+            question: "Slug?".to_string(),
+            prompt_type: PromptType::ValidatedInput {
+                pattern: "[a-z]+".to_string(),
+                message: Some("lowercase letters only".to_string()),
+            },
+            default: Some("My App".to_string()),
+        };
+
+        let err = ui.prompt(&prompt).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid configuration: Invalid value for prompt 'noninteractive_slug': lowercase letters only"
+        );
+    }
 }
@@ -2,7 +2,7 @@
 
 use console::{style, Key, Style, Term};
 use dialoguer::theme::ColorfulTheme;
-use dialoguer::{Confirm, Input, MultiSelect, Select};
+use dialoguer::{Completion, Confirm, Input, MultiSelect, Password, Select};
 
 use crate::error::{BivvyError, Result};
 
@@ -48,7 +48,7 @@ pub(crate) fn env_override(prompt: &Prompt) -> Option<PromptResult> {
 /// This works in both interactive and non-interactive modes.
 pub fn prompt_user(prompt: &Prompt, term: &Term) -> Result<PromptResult> {
     if let Some(result) = env_override(prompt) {
-        return Ok(result);
+        return prompt.check(result);
     }
 
     match &prompt.prompt_type {
@@ -56,6 +56,21 @@ pub fn prompt_user(prompt: &Prompt, term: &Term) -> Result<PromptResult> {
         PromptType::Input => prompt_input(prompt, term),
         PromptType::Select { options } => prompt_select(prompt, options, term),
         PromptType::MultiSelect { options } => prompt_multiselect(prompt, options, term),
+        PromptType::Secret => prompt_secret(prompt, term),
+        PromptType::Number { .. } | PromptType::ValidatedInput { .. } => {
+            prompt_validated(prompt, term, None)
+        }
+        PromptType::Path { .. } => prompt_validated(prompt, term, Some(&PathCompletion)),
+    }
+}
+
+/// Expand a leading `~/` to the user's home directory.
+pub(crate) fn expand_home(value: &str) -> std::path::PathBuf {
+    match value.strip_prefix("~/") {
+        Some(rest) => crate::sys::home_dir()
+            .map(|home| home.join(rest))
+            .unwrap_or_else(|| std::path::PathBuf::from(value)),
+        None => std::path::PathBuf::from(value),
     }
 }
 
@@ -90,6 +105,93 @@ fn prompt_input(prompt: &Prompt, term: &Term) -> Result<PromptResult> {
     Ok(PromptResult::String(result))
 }
 
+/// Masked input. An empty answer falls back to the default, if any.
+fn prompt_secret(prompt: &Prompt, term: &Term) -> Result<PromptResult> {
+    let value = Password::new()
+        .with_prompt(&prompt.question)
+        .allow_empty_password(prompt.default.is_some())
+        .interact_on(term)
+        .map_err(map_dialoguer_err)?;
+
+    if value.is_empty() {
+        if let Some(default) = &prompt.default {
+            return Ok(PromptResult::String(default.clone()));
+        }
+    }
+    Ok(PromptResult::String(value))
+}
+
+/// Text input that re-asks until the answer passes the prompt's constraints.
+fn prompt_validated(
+    prompt: &Prompt,
+    term: &Term,
+    completion: Option<&PathCompletion>,
+) -> Result<PromptResult> {
+    let mut input = Input::<String>::new()
+        .with_prompt(&prompt.question)
+        .validate_with(|value: &String| prompt.prompt_type.validate(value));
+    if let Some(completion) = completion {
+        input = input.completion_with(completion);
+    }
+    if let Some(default) = &prompt.default {
+        input = input.default(default.clone());
+    }
+
+    let result = input.interact_on(term).map_err(map_dialoguer_err)?;
+    Ok(PromptResult::String(result))
+}
+
+/// Tab completion for path prompts.
+///
+/// Completes the last path component against directory entries. When several
+/// entries match, completes up to their longest common prefix.
+struct PathCompletion;
+
+impl Completion for PathCompletion {
+    fn get(&self, input: &str) -> Option<String> {
+        let (dir_part, file_part) = match input.rfind('/') {
+            Some(idx) => (&input[..=idx], &input[idx + 1..]),
+            None => ("", input),
+        };
+        let dir = if dir_part.is_empty() {
+            std::path::PathBuf::from(".")
+        } else {
+            expand_home(dir_part)
+        };
+
+        let mut matches: Vec<(String, bool)> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let name = entry.file_name().into_string().ok()?;
+                let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                name.starts_with(file_part).then_some((name, is_dir))
+            })
+            .filter(|(name, _)| !name.starts_with('.') || file_part.starts_with('.'))
+            .collect();
+        matches.sort();
+
+        let completed = match matches.as_slice() {
+            [] => return None,
+            [(name, true)] => format!("{}/", name),
+            [(name, false)] => name.clone(),
+            [(first, _), rest @ ..] => rest.iter().fold(first.clone(), |prefix, (name, _)| {
+                prefix
+                    .chars()
+                    .zip(name.chars())
+                    .take_while(|(a, b)| a == b)
+                    .map(|(a, _)| a)
+                    .collect()
+            }),
+        };
+
+        if completed.len() <= file_part.len() {
+            return None;
+        }
+        Some(format!("{}{}", dir_part, completed))
+    }
+}
+
 /// Dialoguer theme for general select prompts with `›`/`·` indicators.
 fn select_theme() -> ColorfulTheme {
     ColorfulTheme {
@@ -792,4 +894,49 @@ mod tests {
         assert!(result_yn.is_err(), "yes/no should error on non-TTY");
         assert!(result_multi.is_err(), "multi should error on non-TTY");
     }
+
+    #[test]
+    fn path_completion_completes_unique_entry() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("config")).unwrap();
+        std::fs::write(temp.path().join("README.md"), "").unwrap();
+        let base = format!("{}/", temp.path().display());
+
+        assert_eq!(
+            PathCompletion.get(&format!("{}con", base)),
+            Some(format!("{}config/", base))
+        );
+        assert_eq!(
+            PathCompletion.get(&format!("{}REA", base)),
+            Some(format!("{}README.md", base))
+        );
+        assert_eq!(PathCompletion.get(&format!("{}zzz", base)), None);
+    }
+
+    #[test]
+    fn path_completion_uses_common_prefix() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("setup-db.sh"), "").unwrap();
+        std::fs::write(temp.path().join("setup-web.sh"), "").unwrap();
+        let base = format!("{}/", temp.path().display());
+
+        assert_eq!(
+            PathCompletion.get(&format!("{}se", base)),
+            Some(format!("{}setup-", base))
+        );
+        // Already at the common prefix: nothing more to complete
+        assert_eq!(PathCompletion.get(&format!("{}setup-", base)), None);
+    }
+
+    #[test]
+    fn expand_home_leaves_other_paths_alone() {
+        assert_eq!(
+            expand_home("/etc/hosts"),
+            std::path::PathBuf::from("/etc/hosts")
+        );
+        assert_eq!(
+            expand_home("relative"),
+            std::path::PathBuf::from("relative")
+        );
+    }
 }
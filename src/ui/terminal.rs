@@ -213,6 +213,12 @@ impl UiState for TerminalUI {
         self.mode = mode;
     }
 
+    fn supports_fullscreen(&self) -> bool {
+        self.term.is_term()
+            && std::io::IsTerminal::is_terminal(&std::io::stdin())
+            && !super::is_dumb_term()
+    }
+
     fn attach_surface(&mut self, surface: Arc<TerminalSurface>) {
         self.surface = Some(surface);
     }
@@ -0,0 +1,16 @@
+//! State shared between the runner and the active screen.
+
+/// State that outlives a single frame.
+#[derive(Debug, Clone, Default)]
+pub struct AppState {
+    /// One-line status shown in the footer until the next key press,
+    /// e.g. a validation error.
+    pub status: Option<String>,
+}
+
+impl AppState {
+    /// Show a status message until the next key press.
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status = Some(message.into());
+    }
+}
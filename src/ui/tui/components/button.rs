@@ -0,0 +1,73 @@
+//! Clickable header buttons.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Span;
+
+use crate::ui::tui::theme::Palette;
+
+/// Button emphasis.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonColor {
+    /// The primary action.
+    Accent,
+    /// Secondary actions.
+    Muted,
+}
+
+/// A one-row button.
+#[derive(Debug, Clone)]
+pub struct Button {
+    label: String,
+    icon: Option<String>,
+    color: ButtonColor,
+}
+
+impl Button {
+    /// Create a muted button.
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            icon: None,
+            color: ButtonColor::Muted,
+        }
+    }
+
+    /// Show an icon after the label, usually the key that fires it.
+    pub fn icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Set the emphasis.
+    pub fn color(mut self, color: ButtonColor) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn text(&self) -> String {
+        match self.icon {
+            Some(ref icon) => format!(" {} {} ", self.label, icon),
+            None => format!(" {} ", self.label),
+        }
+    }
+
+    /// Width in cells.
+    pub fn width(&self) -> u16 {
+        Span::raw(self.text()).width() as u16
+    }
+
+    /// Draw at `(x, y)` and return the button's rect.
+    pub fn render(&self, buf: &mut Buffer, x: u16, y: u16) -> Rect {
+        let style = match self.color {
+            ButtonColor::Accent => Style::default()
+                .fg(Color::Black)
+                .bg(Palette::LIME)
+                .add_modifier(Modifier::BOLD),
+            ButtonColor::Muted => Style::default().fg(Palette::TEXT).bg(Palette::BG_ELEVATED),
+        };
+        buf.set_string(x, y, self.text(), style);
+        Rect::new(x, y, self.width(), 1)
+    }
+}
@@ -0,0 +1,172 @@
+//! Vertical list of selectable cards.
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui::Frame;
+
+use crate::ui::tui::theme::Palette;
+
+/// Rows every card takes: border, title, description, border.
+const CARD_HEIGHT: u16 = 4;
+
+/// Space between cards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardListSpacing {
+    /// Cards touch.
+    Compact,
+    /// One blank row between cards.
+    Relaxed,
+}
+
+impl CardListSpacing {
+    /// Rows between cards.
+    pub fn gap(&self) -> u16 {
+        match self {
+            CardListSpacing::Compact => 0,
+            CardListSpacing::Relaxed => 1,
+        }
+    }
+}
+
+/// A card with a checkbox, title and description.
+#[derive(Debug, Clone)]
+pub struct CardListItem {
+    title: String,
+    description: String,
+    checked: bool,
+    focused: bool,
+    badge: Option<String>,
+}
+
+impl CardListItem {
+    /// Create a card.
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        checked: bool,
+        focused: bool,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            description: description.into(),
+            checked,
+            focused,
+            badge: None,
+        }
+    }
+
+    /// Show a short note at the right of the title row.
+    pub fn badge(mut self, badge: impl Into<String>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    fn render(&self, frame: &mut Frame, area: Rect) {
+        // Focus changes the border shape as well as its color so it stays
+        // visible without colors.
+        let (border, border_type) = if self.focused {
+            (Palette::LIME, BorderType::Thick)
+        } else {
+            (Palette::BORDER, BorderType::Rounded)
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(border_type)
+            .border_style(Style::default().fg(border));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        if inner.height == 0 || inner.width < 2 {
+            return;
+        }
+
+        let (checkbox, checkbox_style) = if self.checked {
+            ("[✓] ", Style::default().fg(Palette::LIME))
+        } else {
+            ("[ ] ", Style::default().fg(Palette::TEXT_MUTED))
+        };
+        let title = Line::from(vec![
+            Span::styled(checkbox, checkbox_style),
+            Span::styled(
+                self.title.clone(),
+                Style::default()
+                    .fg(Palette::TEXT)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]);
+        let width = inner.width - 1;
+        frame
+            .buffer_mut()
+            .set_line(inner.x + 1, inner.y, &title, width);
+
+        if let Some(ref badge) = self.badge {
+            let badge_width = Span::raw(badge.as_str()).width() as u16;
+            if title.width() as u16 + badge_width + 2 <= width {
+                frame.buffer_mut().set_string(
+                    inner.x + inner.width - badge_width - 1,
+                    inner.y,
+                    badge,
+                    Style::default().fg(Palette::TEAL),
+                );
+            }
+        }
+
+        if inner.height > 1 {
+            frame.buffer_mut().set_stringn(
+                inner.x + 5,
+                inner.y + 1,
+                &self.description,
+                width.saturating_sub(4) as usize,
+                Style::default().fg(Palette::TEXT_DIM),
+            );
+        }
+    }
+}
+
+/// Cards stacked vertically, scrolled to an offset.
+#[derive(Debug, Clone)]
+pub struct CardList {
+    items: Vec<CardListItem>,
+    spacing: CardListSpacing,
+    scroll_offset: usize,
+}
+
+impl CardList {
+    /// Create a list.
+    pub fn new(items: Vec<CardListItem>) -> Self {
+        Self {
+            items,
+            spacing: CardListSpacing::Relaxed,
+            scroll_offset: 0,
+        }
+    }
+
+    /// Set the space between cards.
+    pub fn spacing(mut self, spacing: CardListSpacing) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Index of the first visible card.
+    pub fn scroll_offset(mut self, offset: usize) -> Self {
+        self.scroll_offset = offset;
+        self
+    }
+
+    /// How many whole cards fit in `height` rows.
+    pub fn items_that_fit(&self, height: u16) -> usize {
+        let gap = self.spacing.gap();
+        ((height + gap) / (CARD_HEIGHT + gap)) as usize
+    }
+
+    /// Draw the visible cards.
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let visible = self.items_that_fit(area.height);
+        let mut y = area.y;
+        for item in self.items.iter().skip(self.scroll_offset).take(visible) {
+            item.render(frame, Rect::new(area.x, y, area.width, CARD_HEIGHT));
+            y += CARD_HEIGHT + self.spacing.gap();
+        }
+    }
+}
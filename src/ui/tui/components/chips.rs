@@ -0,0 +1,104 @@
+//! Inline chips for short labelled facts.
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+use crate::ui::tui::layouts::DegradeMode;
+use crate::ui::tui::theme::Palette;
+
+/// A chip: a label with optional detail.
+#[derive(Debug, Clone)]
+pub struct ChipGroupItem {
+    label: String,
+    detail: Option<String>,
+}
+
+impl ChipGroupItem {
+    /// Create a chip.
+    pub fn new(label: impl Into<String>, detail: Option<&str>) -> Self {
+        Self {
+            label: label.into(),
+            detail: detail.map(String::from),
+        }
+    }
+
+    fn spans(&self, with_detail: bool) -> Vec<Span<'static>> {
+        let chip = Style::default().bg(Palette::BG_ELEVATED);
+        let mut spans = vec![Span::styled(
+            format!(" {}", self.label),
+            chip.fg(Palette::TEXT).add_modifier(Modifier::BOLD),
+        )];
+        if let (true, Some(detail)) = (with_detail, &self.detail) {
+            spans.push(Span::styled(" · ", chip.fg(Palette::TEAL)));
+            spans.push(Span::styled(detail.clone(), chip.fg(Palette::TEXT_DIM)));
+        }
+        spans.push(Span::styled(" ", chip));
+        spans
+    }
+}
+
+/// Chips laid out left to right, wrapping onto new rows.
+#[derive(Debug, Clone)]
+pub struct ChipGroup {
+    items: Vec<ChipGroupItem>,
+    mode: DegradeMode,
+}
+
+impl ChipGroup {
+    /// Create a group.
+    pub fn new(items: Vec<ChipGroupItem>) -> Self {
+        Self {
+            items,
+            mode: DegradeMode::Full,
+        }
+    }
+
+    /// Set how much to show. Minimal drops details and chip styling.
+    pub fn degrade_mode(mut self, mode: DegradeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Draw the chips and return the number of rows used.
+    pub fn render(&self, frame: &mut Frame, area: Rect) -> u16 {
+        if area.height == 0 || area.width == 0 || self.items.is_empty() {
+            return 0;
+        }
+
+        if self.mode == DegradeMode::Minimal {
+            let names: Vec<&str> = self.items.iter().map(|i| i.label.as_str()).collect();
+            frame.buffer_mut().set_stringn(
+                area.x,
+                area.y,
+                names.join(", "),
+                area.width as usize,
+                Style::default().fg(Palette::TEXT),
+            );
+            return 1;
+        }
+
+        let mut row = 0u16;
+        let mut x = area.x;
+        for item in &self.items {
+            let mut line = Line::from(item.spans(true));
+            if line.width() > area.width as usize {
+                line = Line::from(item.spans(false));
+            }
+            let width = line.width() as u16;
+            if x > area.x && x + width > area.x + area.width {
+                row += 1;
+                x = area.x;
+            }
+            if row >= area.height {
+                return row;
+            }
+            frame
+                .buffer_mut()
+                .set_line(x, area.y + row, &line, area.x + area.width - x);
+            x += width + 1;
+        }
+        row + 1
+    }
+}
@@ -0,0 +1,71 @@
+//! Key hints along the bottom of a screen.
+
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+
+use crate::ui::tui::screen::KeyHint;
+use crate::ui::tui::theme::{Palette, Theme};
+
+/// Key hints, with a status message or separator above them.
+#[derive(Debug, Clone)]
+pub struct FooterWidget {
+    hints: Vec<KeyHint>,
+    status: Option<String>,
+}
+
+impl FooterWidget {
+    /// Create a footer.
+    pub fn new(hints: Vec<KeyHint>) -> Self {
+        Self {
+            hints,
+            status: None,
+        }
+    }
+
+    /// Show a status message in place of the separator.
+    pub fn status(mut self, status: Option<&str>) -> Self {
+        self.status = status.map(String::from);
+        self
+    }
+
+    /// Draw the footer. Hints go on the last row.
+    pub fn render(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+
+        if area.height >= 2 {
+            match self.status {
+                Some(ref status) => {
+                    buf.set_stringn(area.x, area.y, status, area.width as usize, theme.error);
+                }
+                None => {
+                    buf.set_string(
+                        area.x,
+                        area.y,
+                        "─".repeat(area.width as usize),
+                        Style::default().fg(Palette::BORDER_SUBTLE),
+                    );
+                }
+            }
+        }
+
+        let mut spans = Vec::new();
+        for (i, hint) in self.hints.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw("  "));
+            }
+            spans.push(Span::styled(hint.key, theme.key));
+            spans.push(Span::raw(" "));
+            spans.push(Span::styled(hint.description, theme.description));
+        }
+        buf.set_line(
+            area.x,
+            area.y + area.height - 1,
+            &Line::from(spans),
+            area.width,
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Screen header: branding, a centered widget and action buttons.
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::Frame;
+
+use crate::ui::tui::theme::{Palette, Theme};
+
+use super::Button;
+
+/// What the header identifies.
+#[derive(Debug, Clone)]
+pub struct HeaderData {
+    project: String,
+    version: Option<String>,
+    subtitle: Option<String>,
+}
+
+impl HeaderData {
+    /// Header for a project, optionally showing the bivvy version.
+    pub fn new(project: impl Into<String>, version: Option<impl Into<String>>) -> Self {
+        Self {
+            project: project.into(),
+            version: version.map(Into::into),
+            subtitle: None,
+        }
+    }
+
+    /// Name the command or view, e.g. `init`.
+    pub fn with_subtitle(mut self, subtitle: impl Into<String>) -> Self {
+        self.subtitle = Some(subtitle.into());
+        self
+    }
+}
+
+/// The header row with a separator below it.
+pub struct HeaderWidget<'a> {
+    data: &'a HeaderData,
+    theme: &'a Theme,
+    center: Option<Line<'static>>,
+    actions: Vec<Button>,
+}
+
+impl<'a> HeaderWidget<'a> {
+    /// Create a header.
+    pub fn new(data: &'a HeaderData, theme: &'a Theme) -> Self {
+        Self {
+            data,
+            theme,
+            center: None,
+            actions: Vec::new(),
+        }
+    }
+
+    /// Content centered in the row when it fits.
+    pub fn center(mut self, line: Line<'static>) -> Self {
+        self.center = Some(line);
+        self
+    }
+
+    /// Buttons right-aligned in the row.
+    pub fn actions(mut self, buttons: Vec<Button>) -> Self {
+        self.actions = buttons;
+        self
+    }
+
+    fn branding(&self) -> Line<'static> {
+        let separator = || Span::styled(" · ", Style::default().fg(Palette::TEAL));
+        let mut spans = vec![
+            Span::styled("⛺ ", Style::default().fg(Palette::FUCHSIA)),
+            Span::styled("bivvy", self.theme.brand),
+        ];
+        if let Some(ref version) = self.data.version {
+            spans.push(Span::styled(format!(" v{}", version), self.theme.subtitle));
+        }
+        spans.push(separator());
+        spans.push(Span::styled(
+            self.data.project.clone(),
+            Style::default()
+                .fg(Palette::TEXT)
+                .add_modifier(Modifier::BOLD),
+        ));
+        if let Some(ref subtitle) = self.data.subtitle {
+            spans.push(separator());
+            spans.push(Span::styled(subtitle.clone(), self.theme.subtitle));
+        }
+        Line::from(spans)
+    }
+
+    /// Draw the header and return the rect of each action button.
+    pub fn render_to_frame(&self, frame: &mut Frame, area: Rect) -> Vec<Rect> {
+        if area.height == 0 || area.width == 0 {
+            return Vec::new();
+        }
+        let row = if area.height >= 3 { area.y + 1 } else { area.y };
+        let right = area.x + area.width;
+
+        let branding = self.branding();
+        let left_end = area.x + (branding.width() as u16).min(area.width);
+        frame
+            .buffer_mut()
+            .set_line(area.x, row, &branding, area.width);
+
+        // Buttons are right-aligned, and dropped together when they would
+        // cover the branding so callers can rely on their order.
+        let buttons_width: u16 = self.actions.iter().map(|b| b.width() + 1).sum();
+        let mut rects = Vec::new();
+        let mut x = right;
+        if !self.actions.is_empty() && left_end + buttons_width < right {
+            x = right + 1 - buttons_width;
+            let mut bx = x;
+            for button in &self.actions {
+                rects.push(button.render(frame.buffer_mut(), bx, row));
+                bx += button.width() + 1;
+            }
+        }
+
+        if let Some(ref center) = self.center {
+            let width = center.width() as u16;
+            let start = area.x + area.width.saturating_sub(width) / 2;
+            if start > left_end + 1 && start + width + 1 < x {
+                frame.buffer_mut().set_line(start, row, center, width);
+            }
+        }
+
+        if area.height >= 3 {
+            frame.buffer_mut().set_string(
+                area.x,
+                area.y + 2,
+                "─".repeat(area.width as usize),
+                Style::default().fg(Palette::BORDER_SUBTLE),
+            );
+        }
+
+        rects
+    }
+}
@@ -0,0 +1,22 @@
+//! Reusable widgets for full-screen screens.
+//!
+//! Components draw into a frame or buffer and, where they are clickable,
+//! return the rects they used so screens can register hit regions.
+//! Components that sit in a flexible layout region take the region's
+//! [`DegradeMode`](super::layouts::DegradeMode).
+
+mod button;
+mod card_list;
+mod chips;
+mod footer;
+mod header;
+mod progress;
+mod section;
+
+pub use button::{Button, ButtonColor};
+pub use card_list::{CardList, CardListItem, CardListSpacing};
+pub use chips::{ChipGroup, ChipGroupItem};
+pub use footer::FooterWidget;
+pub use header::{HeaderData, HeaderWidget};
+pub use progress::ProgressSteps;
+pub use section::SectionCard;
@@ -0,0 +1,55 @@
+//! Step indicator for multi-stage flows.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::ui::tui::theme::Palette;
+
+/// A row of stages with the current one highlighted.
+#[derive(Debug, Clone)]
+pub struct ProgressSteps {
+    labels: Vec<String>,
+    current: usize,
+}
+
+impl ProgressSteps {
+    /// Create an indicator with `current` as the active stage.
+    pub fn new(labels: Vec<String>, current: usize) -> Self {
+        Self { labels, current }
+    }
+
+    /// Render as a line: an indicator and a label per stage, with
+    /// separators between stages.
+    pub fn to_line(&self) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (i, label) in self.labels.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" ─ ", Style::default().fg(Palette::TEAL)));
+            }
+            let (indicator, indicator_style, label_style) = if i < self.current {
+                (
+                    "✓ ",
+                    Style::default().fg(Palette::GREEN),
+                    Style::default().fg(Palette::TEXT_DIM),
+                )
+            } else if i == self.current {
+                (
+                    "● ",
+                    Style::default().fg(Palette::FUCHSIA),
+                    Style::default()
+                        .fg(Palette::TEXT)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (
+                    "○ ",
+                    Style::default().fg(Palette::TEXT_MUTED),
+                    Style::default().fg(Palette::TEXT_MUTED),
+                )
+            };
+            spans.push(Span::styled(indicator, indicator_style));
+            spans.push(Span::styled(label.clone(), label_style));
+        }
+        Line::from(spans)
+    }
+}
@@ -0,0 +1,103 @@
+//! Titled sections.
+
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use ratatui::widgets::{Block, BorderType, Borders};
+use ratatui::Frame;
+
+use crate::ui::tui::layouts::DegradeMode;
+use crate::ui::tui::theme::Palette;
+
+/// A section with a title, bordered when there is room.
+#[derive(Debug, Clone)]
+pub struct SectionCard {
+    title: Option<String>,
+    mode: DegradeMode,
+    focused: bool,
+}
+
+impl Default for SectionCard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SectionCard {
+    /// Create an untitled, full section.
+    pub fn new() -> Self {
+        Self {
+            title: None,
+            mode: DegradeMode::Full,
+            focused: false,
+        }
+    }
+
+    /// Set the title.
+    pub fn title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set how much chrome to draw.
+    pub fn degrade_mode(mut self, mode: DegradeMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Highlight the border.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+
+    /// Draw the chrome and return the content area.
+    ///
+    /// Full draws a rounded border with the title in it, Compact a title
+    /// row and Minimal nothing.
+    pub fn render(&self, frame: &mut Frame, area: Rect) -> Rect {
+        let title_style = Style::default()
+            .fg(Palette::TEXT_DIM)
+            .add_modifier(Modifier::BOLD);
+        match self.mode {
+            DegradeMode::Full => {
+                let border = if self.focused {
+                    Palette::LIME
+                } else {
+                    Palette::BORDER
+                };
+                let mut block = Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(border));
+                if let Some(ref title) = self.title {
+                    block = block.title(format!(" {} ", title)).title_style(title_style);
+                }
+                let inner = block.inner(area);
+                frame.render_widget(block, area);
+                Rect {
+                    x: inner.x + 1,
+                    width: inner.width.saturating_sub(2),
+                    ..inner
+                }
+            }
+            DegradeMode::Compact => match self.title {
+                Some(ref title) if area.height > 0 => {
+                    frame.buffer_mut().set_stringn(
+                        area.x,
+                        area.y,
+                        title,
+                        area.width as usize,
+                        title_style,
+                    );
+                    Rect {
+                        y: area.y + 1,
+                        height: area.height - 1,
+                        ..area
+                    }
+                }
+                _ => area,
+            },
+            DegradeMode::Minimal => area,
+        }
+    }
+}
@@ -0,0 +1,281 @@
+//! Semantic actions, key bindings and mouse hit regions.
+//!
+//! Screens never see raw key events for navigation: the runner resolves
+//! keys through the screen's [`BindingSet`] into [`Action`]s, and the same
+//! set renders the footer and help overlay, so what is shown always
+//! matches what works. Mouse clicks are resolved through the [`HitMap`]
+//! the screen fills while rendering.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::layout::{Position, Rect};
+
+/// Something the user asked a screen to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    /// Move focus up.
+    Up,
+    /// Move focus down.
+    Down,
+    /// Move left or select the previous value.
+    Left,
+    /// Move right or select the next value.
+    Right,
+    /// Toggle the focused item.
+    Toggle,
+    /// Accept the current state.
+    Confirm,
+    /// Back out of the current view.
+    Cancel,
+    /// Leave the screen.
+    Quit,
+    /// Show or hide the help overlay.
+    Help,
+    /// Mouse wheel up.
+    ScrollUp,
+    /// Mouse wheel down.
+    ScrollDown,
+    /// Expand the focused item.
+    Expand,
+    /// Edit the focused item.
+    Edit,
+    /// Show or hide the preview pane.
+    Preview,
+}
+
+/// A group of keys shown as one footer hint.
+#[derive(Debug, Clone, Copy)]
+pub struct Binding {
+    /// Key label shown in the footer and help overlay.
+    pub key_label: &'static str,
+    /// What the keys do.
+    pub description: &'static str,
+    /// Keys and the action each one triggers.
+    pub keys: &'static [(KeyCode, Action)],
+}
+
+/// Standard bindings shared by screens.
+pub mod bindings {
+    use super::{Action, Binding, KeyCode};
+
+    /// Arrow keys and `j`/`k`.
+    pub const NAVIGATE: Binding = Binding {
+        key_label: "↑↓",
+        description: "Navigate",
+        keys: &[
+            (KeyCode::Up, Action::Up),
+            (KeyCode::Down, Action::Down),
+            (KeyCode::Char('k'), Action::Up),
+            (KeyCode::Char('j'), Action::Down),
+        ],
+    };
+
+    /// Left and right arrows.
+    pub const CHANGE: Binding = Binding {
+        key_label: "←→",
+        description: "Change value",
+        keys: &[
+            (KeyCode::Left, Action::Left),
+            (KeyCode::Right, Action::Right),
+        ],
+    };
+
+    /// Space toggles.
+    pub const TOGGLE: Binding = Binding {
+        key_label: "Space",
+        description: "Toggle",
+        keys: &[(KeyCode::Char(' '), Action::Toggle)],
+    };
+
+    /// Enter confirms.
+    pub const CONFIRM: Binding = Binding {
+        key_label: "Enter",
+        description: "Confirm",
+        keys: &[(KeyCode::Enter, Action::Confirm)],
+    };
+
+    /// Escape cancels.
+    pub const CANCEL: Binding = Binding {
+        key_label: "Esc",
+        description: "Cancel",
+        keys: &[(KeyCode::Esc, Action::Cancel)],
+    };
+
+    /// `q` quits.
+    pub const QUIT: Binding = Binding {
+        key_label: "q",
+        description: "Quit",
+        keys: &[(KeyCode::Char('q'), Action::Quit)],
+    };
+
+    /// `?` shows help.
+    pub const HELP: Binding = Binding {
+        key_label: "?",
+        description: "Help",
+        keys: &[(KeyCode::Char('?'), Action::Help)],
+    };
+
+    /// `e` edits.
+    pub const EDIT: Binding = Binding {
+        key_label: "e",
+        description: "Edit inputs",
+        keys: &[(KeyCode::Char('e'), Action::Edit)],
+    };
+
+    /// `p` shows or hides the preview.
+    pub const PREVIEW: Binding = Binding {
+        key_label: "p",
+        description: "Preview",
+        keys: &[(KeyCode::Char('p'), Action::Preview)],
+    };
+}
+
+/// The bindings active on a screen, in footer order.
+#[derive(Debug, Clone, Default)]
+pub struct BindingSet {
+    bindings: Vec<Binding>,
+}
+
+impl BindingSet {
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a binding.
+    pub fn add(&mut self, binding: Binding) {
+        self.bindings.push(binding);
+    }
+
+    /// Resolve a key press to an action.
+    ///
+    /// Shift is ignored so shifted characters like `?` match; keys held
+    /// with Ctrl or Alt never match.
+    pub fn resolve(&self, key: &KeyEvent) -> Option<Action> {
+        if key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return None;
+        }
+        self.bindings
+            .iter()
+            .flat_map(|b| b.keys.iter())
+            .find(|(code, _)| *code == key.code)
+            .map(|(_, action)| *action)
+    }
+
+    /// Bindings to show in the footer and help overlay.
+    pub fn footer_bindings(&self) -> Vec<Binding> {
+        self.bindings.clone()
+    }
+}
+
+/// How a click on a hit region is handled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickBehavior {
+    /// Fire the action immediately.
+    Fire,
+    /// Focus the item; fire only if it was already focused.
+    FocusThenFire,
+    /// Only move focus to the item.
+    FocusOnly,
+}
+
+/// A clickable area registered during rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HitRegion {
+    /// Screen area that responds to clicks.
+    pub area: Rect,
+    /// Action the click triggers.
+    pub action: Action,
+    /// Index of the item in a list, if any.
+    pub index: Option<usize>,
+    /// How the click is handled.
+    pub click: ClickBehavior,
+}
+
+/// Clickable areas for the most recently rendered frame.
+#[derive(Debug, Clone, Default)]
+pub struct HitMap {
+    regions: Vec<HitRegion>,
+}
+
+impl HitMap {
+    /// Create an empty map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a clickable area. Later registrations sit on top.
+    pub fn register(&mut self, region: HitRegion) {
+        self.regions.push(region);
+    }
+
+    /// Remove all regions before a new frame is rendered.
+    pub fn clear(&mut self) {
+        self.regions.clear();
+    }
+
+    /// The topmost region containing a cell.
+    pub fn hit(&self, column: u16, row: u16) -> Option<HitRegion> {
+        self.regions
+            .iter()
+            .rev()
+            .find(|r| r.area.contains(Position::new(column, row)))
+            .copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn resolve_maps_keys_to_actions() {
+        let mut set = BindingSet::new();
+        set.add(bindings::NAVIGATE);
+        set.add(bindings::HELP);
+
+        assert_eq!(
+            set.resolve(&key(KeyCode::Char('j'), KeyModifiers::NONE)),
+            Some(Action::Down)
+        );
+        assert_eq!(
+            set.resolve(&key(KeyCode::Char('?'), KeyModifiers::SHIFT)),
+            Some(Action::Help)
+        );
+        assert_eq!(
+            set.resolve(&key(KeyCode::Char('j'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(set.resolve(&key(KeyCode::Enter, KeyModifiers::NONE)), None);
+    }
+
+    #[test]
+    fn hit_returns_topmost_region() {
+        let mut hits = HitMap::new();
+        hits.register(HitRegion {
+            area: Rect::new(0, 0, 10, 10),
+            action: Action::Toggle,
+            index: Some(0),
+            click: ClickBehavior::FocusThenFire,
+        });
+        hits.register(HitRegion {
+            area: Rect::new(2, 2, 2, 1),
+            action: Action::Confirm,
+            index: None,
+            click: ClickBehavior::Fire,
+        });
+
+        assert_eq!(hits.hit(3, 2).unwrap().action, Action::Confirm);
+        assert_eq!(hits.hit(5, 5).unwrap().action, Action::Toggle);
+        assert!(hits.hit(10, 0).is_none());
+
+        hits.clear();
+        assert!(hits.hit(3, 2).is_none());
+    }
+}
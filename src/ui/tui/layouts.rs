@@ -0,0 +1,196 @@
+//! Vertical region allocation for screens.
+//!
+//! A screen is a stack of named regions. Pinned regions (header, footer)
+//! get a fixed height; flexible regions share what remains, growing to
+//! their ideal height when there is room and degrading when there isn't.
+//! Each allocation carries a [`DegradeMode`] so components can drop
+//! borders and secondary text on small terminals.
+
+/// How a region is sized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegionSize {
+    /// Always this many rows (if the terminal has them).
+    Pin(u16),
+    /// Prefers `ideal` rows and needs `min` to render without degrading
+    /// to [`DegradeMode::Minimal`]. The flexible region with the largest
+    /// ideal absorbs spare rows.
+    Flex {
+        /// Rows for the full rendering.
+        ideal: u16,
+        /// Rows for the compact rendering.
+        min: u16,
+    },
+}
+
+/// A named vertical region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Region {
+    /// Name the screen matches on when rendering.
+    pub name: &'static str,
+    /// How the region is sized.
+    pub size: RegionSize,
+}
+
+impl Region {
+    /// A region with a fixed height.
+    pub const fn pin(name: &'static str, height: u16) -> Self {
+        Self {
+            name,
+            size: RegionSize::Pin(height),
+        }
+    }
+
+    /// A region that grows and shrinks with the terminal.
+    pub const fn flex(name: &'static str, ideal: u16, min: u16) -> Self {
+        Self {
+            name,
+            size: RegionSize::Flex { ideal, min },
+        }
+    }
+}
+
+/// How much of a component fits in its allocation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DegradeMode {
+    /// Borders, titles and helper text.
+    Full,
+    /// Title line, no border or helper text.
+    Compact,
+    /// Content only.
+    Minimal,
+}
+
+/// Height assigned to a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Allocation {
+    /// Region name.
+    pub name: &'static str,
+    /// Rows assigned.
+    pub height: u16,
+    /// How the region should render in those rows.
+    pub mode: DegradeMode,
+}
+
+/// Split `height` rows between `regions`, top to bottom.
+pub fn allocate_regions(height: u16, regions: &[Region]) -> Vec<Allocation> {
+    let mut heights = vec![0u16; regions.len()];
+    let mut remaining = height;
+
+    for (i, region) in regions.iter().enumerate() {
+        if let RegionSize::Pin(rows) = region.size {
+            heights[i] = rows.min(remaining);
+            remaining -= heights[i];
+        }
+    }
+
+    // Flexible regions, largest ideal first: they get their minimum, then
+    // their ideal, then the main region takes whatever is left.
+    let mut flex: Vec<(usize, u16, u16)> = regions
+        .iter()
+        .enumerate()
+        .filter_map(|(i, r)| match r.size {
+            RegionSize::Flex { ideal, min } => Some((i, ideal, min)),
+            RegionSize::Pin(_) => None,
+        })
+        .collect();
+    flex.sort_by_key(|&(_, ideal, _)| std::cmp::Reverse(ideal));
+
+    for &(i, _, min) in &flex {
+        heights[i] = min.min(remaining);
+        remaining -= heights[i];
+    }
+    for &(i, ideal, _) in &flex {
+        let extra = ideal.saturating_sub(heights[i]).min(remaining);
+        heights[i] += extra;
+        remaining -= extra;
+    }
+    if let Some(&(main, _, _)) = flex.first() {
+        heights[main] += remaining;
+    }
+
+    regions
+        .iter()
+        .zip(heights)
+        .map(|(region, height)| Allocation {
+            name: region.name,
+            height,
+            mode: match region.size {
+                RegionSize::Pin(_) => DegradeMode::Full,
+                RegionSize::Flex { ideal, .. } if height >= ideal => DegradeMode::Full,
+                RegionSize::Flex { min, .. } if height >= min => DegradeMode::Compact,
+                RegionSize::Flex { .. } => DegradeMode::Minimal,
+            },
+        })
+        .collect()
+}
+
+/// Region stacks for each screen.
+pub mod screen_layouts {
+    use super::Region;
+
+    /// `bivvy init`: header, detected technologies, template picker,
+    /// selection summary and key hints.
+    pub fn init() -> Vec<Region> {
+        vec![
+            Region::pin("header", 3),
+            Region::flex("detection", 5, 3),
+            Region::flex("steps", 14, 5),
+            Region::flex("summary", 4, 2),
+            Region::pin("footer", 2),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn heights(allocations: &[Allocation]) -> Vec<(u16, DegradeMode)> {
+        allocations.iter().map(|a| (a.height, a.mode)).collect()
+    }
+
+    #[test]
+    fn tall_terminal_gives_spare_rows_to_main_region() {
+        let allocations = allocate_regions(40, &screen_layouts::init());
+        assert_eq!(
+            heights(&allocations),
+            [
+                (3, DegradeMode::Full),
+                (5, DegradeMode::Full),
+                (26, DegradeMode::Full),
+                (4, DegradeMode::Full),
+                (2, DegradeMode::Full),
+            ]
+        );
+    }
+
+    #[test]
+    fn short_terminal_degrades_regions() {
+        let allocations = allocate_regions(18, &screen_layouts::init());
+        assert_eq!(
+            heights(&allocations),
+            [
+                (3, DegradeMode::Full),
+                (3, DegradeMode::Compact),
+                (8, DegradeMode::Compact),
+                (2, DegradeMode::Compact),
+                (2, DegradeMode::Full),
+            ]
+        );
+    }
+
+    #[test]
+    fn tiny_terminal_starves_smaller_regions_first() {
+        let allocations = allocate_regions(9, &screen_layouts::init());
+        assert_eq!(
+            heights(&allocations),
+            [
+                (3, DegradeMode::Full),
+                (0, DegradeMode::Minimal),
+                (4, DegradeMode::Minimal),
+                (0, DegradeMode::Minimal),
+                (2, DegradeMode::Full),
+            ]
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Full-screen terminal UI.
+//!
+//! Screens implement [`Screen`] and are driven by [`run`], which owns the
+//! terminal: it switches to the alternate screen with mouse capture,
+//! redraws after every event, resolves keys through the screen's bindings
+//! and clicks through the hit regions registered while rendering, and
+//! restores the terminal on exit, error or panic.
+//!
+//! Commands only use a screen when [`UiState::supports_fullscreen`]
+//! is true and keep their prompt flow for every other case.
+//!
+//! [`UiState::supports_fullscreen`]: crate::ui::UiState::supports_fullscreen
+
+pub mod app;
+pub mod components;
+pub mod interaction;
+pub mod layouts;
+pub mod screen;
+pub mod screens;
+pub mod theme;
+
+use std::io;
+
+use crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+    KeyModifiers, MouseButton, MouseEventKind,
+};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::buffer::Buffer;
+use ratatui::style::Color;
+use ratatui::Terminal;
+
+pub use app::AppState;
+pub use interaction::{Action, HitMap};
+pub use screen::{Screen, ScreenResult};
+
+/// Show `screen` full-screen until it quits.
+pub fn run<S: Screen>(screen: &mut S) -> io::Result<()> {
+    let _guard = TerminalGuard::enter()?;
+    let mut terminal = Terminal::new(CrosstermBackend::new(io::stdout()))?;
+    let colors = crate::ui::should_use_colors();
+    run_with(&mut terminal, screen, colors, event::read)
+}
+
+/// Drive `screen` on `terminal` with events from `next_event`.
+///
+/// Used by [`run`] with the real terminal, and by tests with a scripted
+/// event source.
+pub fn run_with<B, S, E>(
+    terminal: &mut Terminal<B>,
+    screen: &mut S,
+    colors: bool,
+    mut next_event: E,
+) -> io::Result<()>
+where
+    B: Backend,
+    S: Screen,
+    E: FnMut() -> io::Result<Event>,
+{
+    let mut state = AppState::default();
+    let mut hits = HitMap::new();
+
+    loop {
+        hits.clear();
+        terminal.draw(|frame| {
+            let area = frame.area();
+            screen.render(frame, area, &state, &mut hits);
+            if !colors {
+                strip_colors(frame.buffer_mut());
+            }
+        })?;
+
+        let result = match next_event()? {
+            Event::Key(key) if key.kind != KeyEventKind::Release => {
+                handle_key(screen, &key, &mut state)
+            }
+            Event::Mouse(mouse) => match mouse.kind {
+                MouseEventKind::Down(MouseButton::Left) => {
+                    match hits.hit(mouse.column, mouse.row) {
+                        Some(region) => {
+                            state.status = None;
+                            screen.handle_click(
+                                region.action,
+                                region.index,
+                                region.click,
+                                &mut state,
+                            )
+                        }
+                        None => ScreenResult::Continue,
+                    }
+                }
+                MouseEventKind::ScrollUp => screen.handle_action(Action::ScrollUp, &mut state),
+                MouseEventKind::ScrollDown => screen.handle_action(Action::ScrollDown, &mut state),
+                _ => ScreenResult::Continue,
+            },
+            _ => ScreenResult::Continue,
+        };
+
+        if result == ScreenResult::Quit {
+            return Ok(());
+        }
+    }
+}
+
+fn handle_key<S: Screen>(screen: &mut S, key: &KeyEvent, state: &mut AppState) -> ScreenResult {
+    state.status = None;
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return screen.handle_action(Action::Quit, state);
+    }
+    if let Some(result) = screen.handle_key(key, state) {
+        return result;
+    }
+    match screen.bindings().resolve(key) {
+        Some(action) => screen.handle_action(action, state),
+        None => ScreenResult::Continue,
+    }
+}
+
+/// Drop colors but keep modifiers, for `NO_COLOR` and plain terminals.
+fn strip_colors(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.set_fg(Color::Reset);
+        cell.set_bg(Color::Reset);
+    }
+}
+
+/// Raw mode, alternate screen and mouse capture, undone on drop.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        enable_raw_mode()?;
+        let guard = TerminalGuard;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        Ok(guard)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            DisableMouseCapture,
+            LeaveAlternateScreen,
+            crossterm::cursor::Show
+        );
+        let _ = disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testing {
+    //! Render screens through the crossterm backend into a `vt100` parser,
+    //! so tests see what a real terminal would show.
+
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+    };
+    use ratatui::backend::CrosstermBackend;
+    use ratatui::layout::Rect;
+    use ratatui::{Terminal, TerminalOptions, Viewport};
+
+    use super::{run_with, AppState, HitMap, Screen};
+
+    /// Bytes the backend writes, shared with the test.
+    #[derive(Clone, Default)]
+    struct Output(Rc<RefCell<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// A fixed-size terminal backed by a vt100 emulator.
+    pub struct Vt100Terminal {
+        terminal: Terminal<CrosstermBackend<Output>>,
+        output: Output,
+        parser: vt100::Parser,
+    }
+
+    impl Vt100Terminal {
+        pub fn new(width: u16, height: u16) -> Self {
+            let output = Output::default();
+            let terminal = Terminal::with_options(
+                CrosstermBackend::new(output.clone()),
+                TerminalOptions {
+                    viewport: Viewport::Fixed(Rect::new(0, 0, width, height)),
+                },
+            )
+            .unwrap();
+            Self {
+                terminal,
+                output,
+                parser: vt100::Parser::new(height, width, 0),
+            }
+        }
+
+        fn sync(&mut self) {
+            let bytes = std::mem::take(&mut *self.output.0.borrow_mut());
+            self.parser.process(&bytes);
+        }
+
+        /// Render one frame and return the hit regions it registered.
+        pub fn draw<S: Screen>(&mut self, screen: &S) -> HitMap {
+            let mut hits = HitMap::new();
+            let state = AppState::default();
+            self.terminal
+                .draw(|frame| {
+                    let area = frame.area();
+                    screen.render(frame, area, &state, &mut hits);
+                })
+                .unwrap();
+            self.sync();
+            hits
+        }
+
+        /// Run `screen` with scripted events until it quits.
+        ///
+        /// Fails if the events run out first.
+        pub fn run<S: Screen>(&mut self, screen: &mut S, events: Vec<Event>) -> io::Result<()> {
+            let mut events = events.into_iter();
+            let result = run_with(&mut self.terminal, screen, true, || {
+                events
+                    .next()
+                    .ok_or_else(|| io::Error::new(io::ErrorKind::UnexpectedEof, "out of events"))
+            });
+            self.sync();
+            result
+        }
+
+        /// Screen contents as text, one line per row.
+        pub fn contents(&self) -> String {
+            self.parser.screen().contents()
+        }
+    }
+
+    /// A key press without modifiers.
+    pub fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    /// Typed characters.
+    pub fn text(s: &str) -> Vec<Event> {
+        s.chars().map(|c| key(KeyCode::Char(c))).collect()
+    }
+
+    /// A left click.
+    pub fn click(column: u16, row: u16) -> Event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::testing::{click, key, Vt100Terminal};
+    use super::*;
+    use crate::ui::tui::interaction::{bindings, BindingSet, ClickBehavior, HitRegion};
+    use ratatui::layout::Rect;
+    use ratatui::Frame;
+
+    /// Counts presses; the counter is clickable.
+    #[derive(Default)]
+    struct Counter {
+        count: usize,
+        typed: String,
+    }
+
+    impl Screen for Counter {
+        fn render(&self, frame: &mut Frame, _area: Rect, _state: &AppState, hits: &mut HitMap) {
+            let label = format!("count={} typed={}", self.count, self.typed);
+            frame
+                .buffer_mut()
+                .set_string(0, 0, &label, ratatui::style::Style::default());
+            hits.register(HitRegion {
+                area: Rect::new(0, 0, label.len() as u16, 1),
+                action: Action::Toggle,
+                index: None,
+                click: ClickBehavior::Fire,
+            });
+        }
+
+        fn bindings(&self) -> BindingSet {
+            let mut set = BindingSet::new();
+            set.add(bindings::TOGGLE);
+            set.add(bindings::QUIT);
+            set
+        }
+
+        fn handle_action(&mut self, action: Action, _state: &mut AppState) -> ScreenResult {
+            match action {
+                Action::Toggle => {
+                    self.count += 1;
+                    ScreenResult::Continue
+                }
+                Action::Quit => ScreenResult::Quit,
+                _ => ScreenResult::Continue,
+            }
+        }
+
+        fn handle_key(&mut self, key: &KeyEvent, _state: &mut AppState) -> Option<ScreenResult> {
+            match key.code {
+                KeyCode::Char(c) if c.is_ascii_digit() => {
+                    self.typed.push(c);
+                    Some(ScreenResult::Continue)
+                }
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn run_dispatches_keys_and_clicks() {
+        let mut term = Vt100Terminal::new(40, 3);
+        let mut screen = Counter::default();
+
+        term.run(
+            &mut screen,
+            vec![
+                key(KeyCode::Char(' ')),
+                key(KeyCode::Char('7')),
+                click(2, 0),
+                click(2, 2),
+                key(KeyCode::Char('q')),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(screen.count, 2);
+        assert_eq!(screen.typed, "7");
+        assert!(term.contents().starts_with("count=2 typed=7"));
+    }
+
+    #[test]
+    fn ctrl_c_quits() {
+        let mut term = Vt100Terminal::new(40, 3);
+        let mut screen = Counter::default();
+
+        term.run(
+            &mut screen,
+            vec![Event::Key(KeyEvent::new(
+                KeyCode::Char('c'),
+                KeyModifiers::CONTROL,
+            ))],
+        )
+        .unwrap();
+
+        assert_eq!(screen.count, 0);
+    }
+
+    #[test]
+    fn strip_colors_keeps_modifiers() {
+        use ratatui::style::{Modifier, Style};
+
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf.set_string(
+            0,
+            0,
+            "ok",
+            Style::default()
+                .fg(Color::Green)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+        strip_colors(&mut buf);
+
+        let cell = &buf.content[0];
+        assert_eq!(cell.fg, Color::Reset);
+        assert_eq!(cell.bg, Color::Reset);
+        assert!(cell.modifier.contains(Modifier::BOLD));
+    }
+}
@@ -0,0 +1,66 @@
+//! The trait every full-screen screen implements.
+
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use ratatui::Frame;
+
+use super::app::AppState;
+use super::interaction::{Action, BindingSet, ClickBehavior, HitMap};
+
+/// What the runner should do after a screen handles input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScreenResult {
+    /// Keep the screen open.
+    Continue,
+    /// Close the screen and return to the caller.
+    Quit,
+}
+
+/// A key and what it does, as shown in the footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyHint {
+    /// Key label, e.g. `Enter`.
+    pub key: &'static str,
+    /// What the key does.
+    pub description: &'static str,
+}
+
+impl KeyHint {
+    /// Create a hint.
+    pub fn new(key: &'static str, description: &'static str) -> Self {
+        Self { key, description }
+    }
+}
+
+/// A full-screen view driven by [`crate::ui::tui::run`].
+pub trait Screen {
+    /// Draw the screen, registering clickable areas in `hits`.
+    fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, hits: &mut HitMap);
+
+    /// Key bindings currently active.
+    fn bindings(&self) -> BindingSet;
+
+    /// Handle a resolved action.
+    fn handle_action(&mut self, action: Action, state: &mut AppState) -> ScreenResult;
+
+    /// Handle a click on a region registered during rendering.
+    fn handle_click(
+        &mut self,
+        action: Action,
+        index: Option<usize>,
+        click: ClickBehavior,
+        state: &mut AppState,
+    ) -> ScreenResult {
+        let _ = (index, click);
+        self.handle_action(action, state)
+    }
+
+    /// Handle a key before bindings are resolved.
+    ///
+    /// Screens with a focused text field return `Some` to consume typed
+    /// characters; the default passes every key on to the bindings.
+    fn handle_key(&mut self, key: &KeyEvent, state: &mut AppState) -> Option<ScreenResult> {
+        let _ = (key, state);
+        None
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! This screen displays detected technologies and provides a selectable
 //! list of suggested step templates based on the detection results.
+//! Templates with `inputs` can be edited in place, and a preview pane
+//! shows the `.bivvy/config.yml` the current selection would produce.
 //!
 //! ## Bivvy Components Used
 //!
@@ -28,19 +30,25 @@
 //! │ Header — branding+progress+btns Pin(3) │
 //! ├────────────────────────────────────────┤
 //! │ Detection — chips (Flex 5/3)           │
-//! ├────────────────────────────────────────┤
-//! │ Steps — selection list (Flex 14/5)     │
-//! ├────────────────────────────────────────┤
+//! ├───────────────────┬────────────────────┤
+//! │ Steps — selection │ YAML preview       │
+//! │ list (Flex 14/5)  │ (≥ 80 columns)     │
+//! ├───────────────────┴────────────────────┤
 //! │ Summary (Flex 4/2)                     │
 //! ├────────────────────────────────────────┤
 //! │ Footer (Pin 2)                         │
 //! └────────────────────────────────────────┘
 //! ```
+//!
+//! Below 80 columns the preview replaces the list while toggled with `p`.
+//! The input editor is an overlay like the help overlay.
 
 use std::cell::Cell;
 
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::prelude::*;
 
+use crate::registry::template::{InputType, TemplateInput};
 use crate::ui::tui::app::AppState;
 use ratatui::widgets::{Block, Borders, Clear};
 
@@ -119,6 +127,16 @@ impl DetectionResult {
     }
 }
 
+impl From<&crate::detection::DetectionResult> for DetectionResult {
+    fn from(result: &crate::detection::DetectionResult) -> Self {
+        Self {
+            name: result.name.clone(),
+            detail: (!result.details.is_empty()).then(|| result.details.join(", ")),
+            found: result.detected,
+        }
+    }
+}
+
 /// A suggested step template detected for the project.
 #[derive(Debug, Clone)]
 pub struct StepTemplate {
@@ -128,6 +146,8 @@ pub struct StepTemplate {
     pub reason: String,
     /// Whether this step is selected for inclusion.
     pub selected: bool,
+    /// Editable inputs declared by the template.
+    pub inputs: Vec<InputField>,
 }
 
 impl StepTemplate {
@@ -137,17 +157,147 @@ impl StepTemplate {
             name: name.into(),
             reason: reason.into(),
             selected: false,
+            inputs: Vec::new(),
         }
     }
 
     /// Create a new step template option that is pre-selected.
     pub fn new_selected(name: impl Into<String>, reason: impl Into<String>) -> Self {
         Self {
-            name: name.into(),
-            reason: reason.into(),
             selected: true,
+            ..Self::new(name, reason)
         }
     }
+
+    /// Attach the template's inputs, sorted by name.
+    pub fn with_inputs<'a>(
+        mut self,
+        inputs: impl IntoIterator<Item = (&'a String, &'a TemplateInput)>,
+    ) -> Self {
+        self.inputs = inputs
+            .into_iter()
+            .map(|(name, input)| InputField::new(name, input.clone()))
+            .collect();
+        self.inputs.sort_by(|a, b| a.name.cmp(&b.name));
+        self
+    }
+
+    fn badge(&self) -> Option<String> {
+        match self.inputs.len() {
+            0 => None,
+            1 => Some("1 input".to_string()),
+            n => Some(format!("{} inputs", n)),
+        }
+    }
+}
+
+/// A template input being edited, held as text until confirmed.
+#[derive(Debug, Clone)]
+pub struct InputField {
+    /// Input name.
+    pub name: String,
+    /// The template's contract for the input.
+    pub input: TemplateInput,
+    /// Current value as typed; empty means unset.
+    pub value: String,
+}
+
+impl InputField {
+    /// Create a field holding the input's default.
+    pub fn new(name: impl Into<String>, input: TemplateInput) -> Self {
+        let value = input.default.as_ref().map(yaml_scalar).unwrap_or_default();
+        Self {
+            name: name.into(),
+            input,
+            value,
+        }
+    }
+
+    /// Whether the value is typed rather than chosen.
+    fn is_text(&self) -> bool {
+        matches!(self.input.input_type, InputType::String | InputType::Number)
+    }
+
+    /// Values to cycle through for enum and boolean inputs.
+    fn choices(&self) -> Vec<String> {
+        match self.input.input_type {
+            InputType::Enum => self.input.values.clone(),
+            InputType::Boolean => vec!["true".to_string(), "false".to_string()],
+            InputType::String | InputType::Number => Vec::new(),
+        }
+    }
+
+    /// Move to the next (or previous) choice.
+    fn cycle(&mut self, forward: bool) {
+        let choices = self.choices();
+        if choices.is_empty() {
+            return;
+        }
+        let next = match choices.iter().position(|c| *c == self.value) {
+            Some(i) if forward => (i + 1) % choices.len(),
+            Some(i) => (i + choices.len() - 1) % choices.len(),
+            None if forward => 0,
+            None => choices.len() - 1,
+        };
+        self.value = choices[next].clone();
+    }
+
+    /// The typed value, checked against the input contract.
+    ///
+    /// Returns `None` when unset, so the template's default or prompt
+    /// applies.
+    pub fn to_value(&self) -> Result<Option<serde_yaml::Value>, String> {
+        let text = self.value.trim();
+        if text.is_empty() {
+            return self.input.validate(&self.name, None).map(|_| None);
+        }
+        let value = match self.input.input_type {
+            InputType::String | InputType::Enum => serde_yaml::Value::String(text.to_string()),
+            InputType::Number => serde_yaml::from_str::<serde_yaml::Value>(text)
+                .ok()
+                .filter(serde_yaml::Value::is_number)
+                .ok_or_else(|| format!("Input '{}' must be a number", self.name))?,
+            InputType::Boolean => serde_yaml::Value::Bool(text == "true"),
+        };
+        self.input.validate(&self.name, Some(&value))?;
+        Ok(Some(value))
+    }
+
+    /// Whether the value differs from the template's default.
+    fn is_overridden(&self) -> bool {
+        let default = self.input.default.as_ref().map(yaml_scalar);
+        !self.value.trim().is_empty() && default.as_deref() != Some(self.value.trim())
+    }
+}
+
+/// Render a scalar the way a user would type it.
+fn yaml_scalar(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// A selected template with the inputs the user set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selection {
+    /// Template name.
+    pub name: String,
+    /// Inputs that differ from the template's defaults.
+    pub inputs: Vec<(String, serde_yaml::Value)>,
+}
+
+/// State of the input editor overlay.
+#[derive(Debug, Clone)]
+struct InputEditor {
+    /// Index of the template being edited.
+    template: usize,
+    /// Focused field.
+    field: usize,
+    /// Values before editing, restored on cancel.
+    original: Vec<String>,
 }
 
 /// Result of the init screen interaction.
@@ -159,8 +309,14 @@ pub enum InitResult {
     Cancelled,
 }
 
+/// Renders the config a selection would produce, for the preview pane.
+pub type PreviewFn<'a> = Box<dyn Fn(&[Selection]) -> String + 'a>;
+
+/// Minimum width for showing the list and preview side by side.
+const SPLIT_MIN_WIDTH: u16 = 80;
+
 /// The init screen.
-pub struct InitScreen {
+pub struct InitScreen<'a> {
     /// Detected technologies.
     detections: Vec<DetectionResult>,
     /// Suggested step templates.
@@ -181,11 +337,18 @@ pub struct InitScreen {
     cancelled: bool,
     /// Whether the help overlay is showing.
     show_help: bool,
+    /// Input editor overlay, when open.
+    editing: Option<InputEditor>,
+    /// Renders the preview pane; no pane without it.
+    preview: Option<PreviewFn<'a>>,
+    /// Whether `p` flipped the preview: hidden on wide terminals, shown
+    /// in place of the list on narrow ones.
+    preview_toggled: bool,
     /// Theme for styling.
     theme: Theme,
 }
 
-impl InitScreen {
+impl<'a> InitScreen<'a> {
     pub fn new() -> Self {
         Self {
             detections: Vec::new(),
@@ -198,10 +361,19 @@ impl InitScreen {
             completed: false,
             cancelled: false,
             show_help: false,
+            editing: None,
+            preview: None,
+            preview_toggled: false,
             theme: Theme::default(),
         }
     }
 
+    /// Show a preview pane rendered by `render` from the current selection.
+    pub fn preview(mut self, render: impl Fn(&[Selection]) -> String + 'a) -> Self {
+        self.preview = Some(Box::new(render));
+        self
+    }
+
     /// Set the project name.
     pub fn project_name(mut self, name: impl Into<String>) -> Self {
         self.project_name = name.into();
@@ -269,6 +441,117 @@ impl InitScreen {
             .collect()
     }
 
+    /// Selected templates with the inputs that differ from their defaults.
+    ///
+    /// Inputs that fail validation are left out; the editor doesn't close
+    /// while any are invalid.
+    pub fn selections(&self) -> Vec<Selection> {
+        self.step_templates
+            .iter()
+            .filter(|t| t.selected)
+            .map(|t| Selection {
+                name: t.name.clone(),
+                inputs: t
+                    .inputs
+                    .iter()
+                    .filter(|f| f.is_overridden())
+                    .filter_map(|f| Some((f.name.clone(), f.to_value().ok()??)))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Open the input editor for the focused template, if it has inputs.
+    fn open_editor(&mut self) {
+        if let Some(template) = self.step_templates.get(self.selected_index) {
+            if !template.inputs.is_empty() {
+                self.editing = Some(InputEditor {
+                    template: self.selected_index,
+                    field: 0,
+                    original: template.inputs.iter().map(|f| f.value.clone()).collect(),
+                });
+            }
+        }
+    }
+
+    /// The field focused in the editor.
+    fn editing_field(&mut self) -> Option<&mut InputField> {
+        let editor = self.editing.as_ref()?;
+        self.step_templates
+            .get_mut(editor.template)?
+            .inputs
+            .get_mut(editor.field)
+    }
+
+    /// Close the editor, keeping the edits if they're valid.
+    ///
+    /// Keeping edits also selects the template. Invalid values leave the
+    /// editor open with the error in the status line.
+    fn apply_editor(&mut self, state: &mut AppState) {
+        let Some(editor) = self.editing.take() else {
+            return;
+        };
+        let template = &mut self.step_templates[editor.template];
+        for (i, field) in template.inputs.iter().enumerate() {
+            if let Err(e) = field.to_value() {
+                state.set_status(e);
+                self.editing = Some(InputEditor { field: i, ..editor });
+                return;
+            }
+        }
+        template.selected = true;
+    }
+
+    /// Close the editor, restoring the values it opened with.
+    fn cancel_editor(&mut self) {
+        if let Some(editor) = self.editing.take() {
+            let template = &mut self.step_templates[editor.template];
+            for (field, value) in template.inputs.iter_mut().zip(editor.original) {
+                field.value = value;
+            }
+        }
+    }
+
+    fn handle_editor_action(&mut self, action: Action, state: &mut AppState) -> ScreenResult {
+        let Some(editor) = self.editing.as_mut() else {
+            return ScreenResult::Continue;
+        };
+        let count = self.step_templates[editor.template].inputs.len();
+        match action {
+            Action::Up | Action::ScrollUp => {
+                editor.field = editor.field.saturating_sub(1);
+            }
+            Action::Down | Action::ScrollDown => {
+                editor.field = (editor.field + 1).min(count - 1);
+            }
+            Action::Right | Action::Toggle => {
+                if let Some(field) = self.editing_field() {
+                    field.cycle(true);
+                }
+            }
+            Action::Left => {
+                if let Some(field) = self.editing_field() {
+                    field.cycle(false);
+                }
+            }
+            Action::Confirm => self.apply_editor(state),
+            Action::Cancel | Action::Quit => self.cancel_editor(),
+            _ => {}
+        }
+        ScreenResult::Continue
+    }
+
+    /// Whether the list and the preview pane are shown at `width`.
+    fn panes(&self, width: u16) -> (bool, bool) {
+        if self.preview.is_none() {
+            (true, false)
+        } else if width >= SPLIT_MIN_WIDTH {
+            (true, !self.preview_toggled)
+        } else {
+            (!self.preview_toggled, self.preview_toggled)
+        }
+    }
+
     pub fn is_complete(&self) -> bool {
         self.completed || self.cancelled
     }
@@ -305,9 +588,7 @@ impl InitScreen {
         let progress_line = self.build_progress_line();
 
         let cancel_btn = Button::new("Cancel").color(ButtonColor::Muted);
-        let confirm_btn = Button::new("Confirm & Run")
-            .icon("⏎")
-            .color(ButtonColor::Accent);
+        let confirm_btn = Button::new("Confirm").icon("⏎").color(ButtonColor::Accent);
 
         let widget = HeaderWidget::new(&header_data, &self.theme)
             .center(progress_line)
@@ -411,8 +692,8 @@ impl InitScreen {
         }
 
         // Render count badge in top-right of card (only when Full mode has a border)
-        if mode == DegradeMode::Full && inner.height > 0 {
-            let count = format!("{} items", found.len());
+        let count = format!("{} items", found.len());
+        if mode == DegradeMode::Full && inner.width as usize > count.len() + 1 {
             let badge_x = inner.x + inner.width - count.len() as u16 - 1;
             frame.buffer_mut().set_string(
                 badge_x,
@@ -556,7 +837,12 @@ impl InitScreen {
             .iter()
             .enumerate()
             .map(|(i, t)| {
-                CardListItem::new(&t.name, &t.reason, t.selected, i == self.selected_index)
+                let item =
+                    CardListItem::new(&t.name, &t.reason, t.selected, i == self.selected_index);
+                match t.badge() {
+                    Some(badge) => item.badge(badge),
+                    None => item,
+                }
             })
             .collect();
 
@@ -589,6 +875,171 @@ impl InitScreen {
         }
     }
 
+    fn render_preview(&self, frame: &mut Frame, area: Rect, mode: DegradeMode) {
+        let Some(ref preview) = self.preview else {
+            return;
+        };
+        let card = SectionCard::new()
+            .title("PREVIEW · .bivvy/config.yml")
+            .degrade_mode(mode);
+        let inner = card.render(frame, area);
+        if inner.height == 0 {
+            return;
+        }
+
+        let yaml = preview(&self.selections());
+        let lines: Vec<&str> = yaml.lines().collect();
+
+        // Highlight the focused template's step and scroll it into view.
+        let focused = self
+            .step_templates
+            .get(self.selected_index)
+            .filter(|t| t.selected)
+            .map(|t| format!("  {}:", t.name));
+        let block_start = focused
+            .as_ref()
+            .and_then(|key| lines.iter().position(|l| l == key));
+        let block_end = block_start.map(|start| {
+            lines[start + 1..]
+                .iter()
+                .position(|l| !l.starts_with("    "))
+                .map_or(lines.len(), |n| start + 1 + n)
+        });
+        let height = inner.height as usize;
+        let offset = match (block_start, block_end) {
+            (Some(start), Some(end)) if end > height => start
+                .saturating_sub(1)
+                .min(lines.len().saturating_sub(height)),
+            _ => 0,
+        };
+
+        for (row, (i, line)) in lines
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(height)
+            .enumerate()
+        {
+            let in_block =
+                matches!((block_start, block_end), (Some(s), Some(e)) if i >= s && i < e);
+            let style = if in_block {
+                Style::default().fg(Palette::LIME)
+            } else if line.trim_start().starts_with('#') {
+                Style::default().fg(Palette::TEXT_MUTED)
+            } else {
+                Style::default().fg(Palette::TEXT)
+            };
+            frame.buffer_mut().set_stringn(
+                inner.x,
+                inner.y + row as u16,
+                line,
+                inner.width as usize,
+                style,
+            );
+        }
+    }
+
+    fn render_editor_overlay(&self, frame: &mut Frame, area: Rect, hits: &mut HitMap) {
+        let Some(ref editor) = self.editing else {
+            return;
+        };
+        let template = &self.step_templates[editor.template];
+
+        // Two rows per field, a blank row and the hint, inside the border.
+        let content_height = template.inputs.len() as u16 * 2 + 2;
+        let overlay_width = 64u16.min(area.width.saturating_sub(4));
+        let overlay_height = (content_height + 2).min(area.height.saturating_sub(2));
+        let x = area.x + (area.width.saturating_sub(overlay_width)) / 2;
+        let y = area.y + (area.height.saturating_sub(overlay_height)) / 2;
+        let overlay_area = Rect::new(x, y, overlay_width, overlay_height);
+
+        frame.render_widget(Clear, overlay_area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .border_style(Style::default().fg(Palette::LIME))
+            .title(format!(" Inputs · {} ", template.name))
+            .title_style(
+                Style::default()
+                    .fg(Palette::LIME)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .style(Style::default().bg(Palette::BG_ELEVATED));
+        let inner = block.inner(overlay_area);
+        frame.render_widget(block, overlay_area);
+        if inner.width < 4 {
+            return;
+        }
+
+        let width = inner.width - 2;
+        let bottom = inner.y + inner.height;
+        let mut row = inner.y;
+        for (i, field) in template.inputs.iter().enumerate() {
+            if row + 1 >= bottom {
+                break;
+            }
+            let focused = i == editor.field;
+            let name_style = if focused {
+                Style::default()
+                    .fg(Palette::LIME)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+                    .fg(Palette::TEXT)
+                    .add_modifier(Modifier::BOLD)
+            };
+            let marker = if focused { "› " } else { "  " };
+            let required = if field.input.required { "*" } else { "" };
+            let value = if field.is_text() {
+                let cursor = if focused { "▏" } else { "" };
+                format!("{}{}", field.value, cursor)
+            } else {
+                format!("‹ {} ›", field.value)
+            };
+            let value_style = if field.value.is_empty() && !focused {
+                Style::default().fg(Palette::TEXT_MUTED)
+            } else {
+                Style::default().fg(Palette::TEXT)
+            };
+            let line = Line::from(vec![
+                Span::styled(marker, name_style),
+                Span::styled(format!("{}{}", field.name, required), name_style),
+                Span::raw("  "),
+                Span::styled(value, value_style),
+            ]);
+            frame.buffer_mut().set_line(inner.x + 1, row, &line, width);
+            frame.buffer_mut().set_stringn(
+                inner.x + 3,
+                row + 1,
+                &field.input.description,
+                width.saturating_sub(2) as usize,
+                Style::default().fg(Palette::TEXT_DIM),
+            );
+            hits.register(HitRegion {
+                area: Rect::new(inner.x, row, inner.width, 2),
+                action: Action::Edit,
+                index: Some(i),
+                click: ClickBehavior::FocusOnly,
+            });
+            row += 2;
+        }
+
+        if inner.height > 0 {
+            let hint = if template.inputs[editor.field].is_text() {
+                "Type to edit · Enter to save · Esc to discard"
+            } else {
+                "←→ to choose · Enter to save · Esc to discard"
+            };
+            frame.buffer_mut().set_stringn(
+                inner.x + 1,
+                bottom - 1,
+                hint,
+                width as usize,
+                Style::default().fg(Palette::TEXT_DIM),
+            );
+        }
+    }
+
     fn render_summary(&self, frame: &mut Frame, area: Rect, mode: DegradeMode) {
         let card = SectionCard::new().title("SUMMARY").degrade_mode(mode);
         let inner = card.render(frame, area);
@@ -644,8 +1095,9 @@ impl InitScreen {
         };
         // Truncate if too long
         let max_len = (col_width as usize).saturating_sub(1);
-        let names_display = if names.len() > max_len {
-            format!("{}…", &names[..max_len.saturating_sub(1)])
+        let names_display = if names.chars().count() > max_len {
+            let kept: String = names.chars().take(max_len.saturating_sub(1)).collect();
+            format!("{}…", kept)
         } else {
             names
         };
@@ -658,14 +1110,14 @@ impl InitScreen {
     }
 }
 
-impl Default for InitScreen {
+impl Default for InitScreen<'_> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl Screen for InitScreen {
-    fn render(&self, frame: &mut Frame, area: Rect, _app_state: &AppState, hits: &mut HitMap) {
+impl Screen for InitScreen<'_> {
+    fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, hits: &mut HitMap) {
         // Clear with main background
         frame
             .buffer_mut()
@@ -707,7 +1159,29 @@ impl Screen for InitScreen {
                     self.render_detections(frame, rect, mode);
                 }
                 "steps" => {
-                    self.render_step_templates(frame, rect, hits, mode);
+                    let (list, preview) = self.panes(rect.width);
+                    let (list_rect, preview_rect) = if list && preview {
+                        let list_width = rect.width / 2;
+                        (
+                            Rect {
+                                width: list_width - 1,
+                                ..rect
+                            },
+                            Rect {
+                                x: rect.x + list_width,
+                                width: rect.width - list_width,
+                                ..rect
+                            },
+                        )
+                    } else {
+                        (rect, rect)
+                    };
+                    if list {
+                        self.render_step_templates(frame, list_rect, hits, mode);
+                    }
+                    if preview {
+                        self.render_preview(frame, preview_rect, mode);
+                    }
                 }
                 "summary" => {
                     self.render_summary(frame, rect, mode);
@@ -719,14 +1193,16 @@ impl Screen for InitScreen {
                         .iter()
                         .map(|b| KeyHint::new(b.key_label, b.description))
                         .collect();
-                    let footer = FooterWidget::new(footer_hints);
+                    let footer =
+                        FooterWidget::new(footer_hints).status(app_state.status.as_deref());
                     footer.render(rect, frame.buffer_mut(), &self.theme);
                 }
                 _ => {}
             }
         }
 
-        // 5. Render help overlay on top of everything
+        // 5. Render overlays on top of everything
+        self.render_editor_overlay(frame, padded, hits);
         if self.show_help {
             self.render_help_overlay(frame, area);
         }
@@ -734,8 +1210,25 @@ impl Screen for InitScreen {
 
     fn bindings(&self) -> BindingSet {
         let mut set = BindingSet::new();
+        if self.editing.is_some() {
+            set.add(bindings::NAVIGATE);
+            set.add(bindings::CHANGE);
+            set.add(bindings::CONFIRM);
+            set.add(bindings::CANCEL);
+            return set;
+        }
         set.add(bindings::NAVIGATE);
         set.add(bindings::TOGGLE);
+        if self
+            .step_templates
+            .get(self.selected_index)
+            .is_some_and(|t| !t.inputs.is_empty())
+        {
+            set.add(bindings::EDIT);
+        }
+        if self.preview.is_some() {
+            set.add(bindings::PREVIEW);
+        }
         set.add(bindings::CONFIRM);
         set.add(bindings::CANCEL);
         set.add(bindings::QUIT);
@@ -743,12 +1236,15 @@ impl Screen for InitScreen {
         set
     }
 
-    fn handle_action(&mut self, action: Action, _state: &mut AppState) -> ScreenResult {
+    fn handle_action(&mut self, action: Action, state: &mut AppState) -> ScreenResult {
         // When help is showing, Help toggles it off; any other action dismisses it
         if self.show_help {
             self.show_help = false;
             return ScreenResult::Continue;
         }
+        if self.editing.is_some() {
+            return self.handle_editor_action(action, state);
+        }
 
         match action {
             Action::Quit | Action::Cancel => {
@@ -783,10 +1279,37 @@ impl Screen for InitScreen {
                 self.show_help = true;
                 ScreenResult::Continue
             }
+            Action::Edit => {
+                self.open_editor();
+                ScreenResult::Continue
+            }
+            Action::Preview => {
+                self.preview_toggled = !self.preview_toggled;
+                ScreenResult::Continue
+            }
             _ => ScreenResult::Continue,
         }
     }
 
+    fn handle_key(&mut self, key: &KeyEvent, _state: &mut AppState) -> Option<ScreenResult> {
+        if self.show_help
+            || key
+                .modifiers
+                .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
+        {
+            return None;
+        }
+        let field = self.editing_field().filter(|f| f.is_text())?;
+        match key.code {
+            KeyCode::Char(c) => field.value.push(c),
+            KeyCode::Backspace => {
+                field.value.pop();
+            }
+            _ => return None,
+        }
+        Some(ScreenResult::Continue)
+    }
+
     fn handle_click(
         &mut self,
         action: Action,
@@ -794,6 +1317,19 @@ impl Screen for InitScreen {
         click: ClickBehavior,
         state: &mut AppState,
     ) -> ScreenResult {
+        // While editing, only the editor's fields and the header buttons
+        // respond; the list underneath is inert.
+        if let Some(ref mut editor) = self.editing {
+            return match (click, index) {
+                (ClickBehavior::FocusOnly, Some(idx)) => {
+                    editor.field = idx;
+                    ScreenResult::Continue
+                }
+                (ClickBehavior::Fire, _) => self.handle_action(action, state),
+                _ => ScreenResult::Continue,
+            };
+        }
+
         match click {
             ClickBehavior::Fire => self.handle_action(action, state),
             ClickBehavior::FocusThenFire => {
@@ -1242,4 +1778,248 @@ mod tests {
         screen.handle_action(Action::Help, &mut app_state);
         assert!(!screen.show_help);
     }
+
+    // ========================================================================
+    // Terminal rendering, inputs and preview
+    // ========================================================================
+
+    use crate::ui::tui::testing::{click, key, text, Vt100Terminal};
+
+    fn input(yaml: &str) -> TemplateInput {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn version_bump() -> StepTemplate {
+        let inputs: std::collections::HashMap<String, TemplateInput> = [
+            (
+                "bump".to_string(),
+                input("description: Bump type\ntype: enum\nvalues: [patch, minor, major]\ndefault: patch\n"),
+            ),
+            (
+                "retries".to_string(),
+                input("description: How many retries\ntype: number\n"),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        StepTemplate::new("version-bump", "Detected package.json").with_inputs(&inputs)
+    }
+
+    fn preview_yaml(selections: &[Selection]) -> String {
+        let mut yaml = String::from("# preview\nsteps:\n");
+        for s in selections {
+            yaml.push_str(&format!("  {}:\n    template: {}\n", s.name, s.name));
+            for (name, value) in &s.inputs {
+                let value = serde_yaml::to_string(value).unwrap();
+                yaml.push_str(&format!("    {}: {}\n", name, value.trim_end()));
+            }
+        }
+        yaml
+    }
+
+    fn sample_screen<'a>() -> InitScreen<'a> {
+        let mut screen = InitScreen::new().project_name("demo").preview(preview_yaml);
+        screen.add_detection(DetectionResult::new("Node", "package.json"));
+        screen.add_detection(DetectionResult::not_found("Python"));
+        screen.add_step_template(StepTemplate::new_selected(
+            "npm-install",
+            "Detected package.json",
+        ));
+        screen.add_step_template(version_bump());
+        screen
+    }
+
+    #[test]
+    fn renders_detections_templates_and_preview() {
+        let mut term = Vt100Terminal::new(100, 40);
+        let screen = sample_screen();
+        term.draw(&screen);
+        let contents = term.contents();
+
+        assert!(contents.contains("bivvy · demo · init"));
+        assert!(contents.contains("Node · package.json"));
+        assert!(!contents.contains("Python"));
+        assert!(contents.contains("[✓] npm-install"));
+        assert!(contents.contains("[ ] version-bump"));
+        assert!(contents.contains("2 inputs"));
+        assert!(contents.contains("PREVIEW · .bivvy/config.yml"));
+        assert!(contents.contains("template: npm-install"));
+        assert!(!contents.contains("template: version-bump"));
+        assert!(contents.contains("p Preview"));
+        assert!(!contents.contains("Edit inputs"));
+    }
+
+    #[test]
+    fn detection_result_from_runner_result() {
+        let found = crate::detection::DetectionResult::found("Rust")
+            .with_detail("Cargo.toml")
+            .with_detail("Cargo.lock");
+        let chip = DetectionResult::from(&found);
+        assert_eq!(chip.name, "Rust");
+        assert_eq!(chip.detail.as_deref(), Some("Cargo.toml, Cargo.lock"));
+        assert!(chip.found);
+
+        let missing = DetectionResult::from(&crate::detection::DetectionResult::not_found("Go"));
+        assert!(missing.detail.is_none());
+        assert!(!missing.found);
+    }
+
+    #[test]
+    fn editing_inputs_updates_selection_and_preview() {
+        let mut term = Vt100Terminal::new(100, 40);
+        let mut screen = sample_screen();
+
+        let mut events = vec![
+            key(KeyCode::Down),
+            key(KeyCode::Char('e')),
+            // bump: patch -> minor
+            key(KeyCode::Right),
+            key(KeyCode::Down),
+        ];
+        events.extend(text("3"));
+        events.push(key(KeyCode::Enter));
+        events.push(key(KeyCode::Char('?')));
+        events.push(key(KeyCode::Char('?')));
+        events.push(key(KeyCode::Enter));
+        term.run(&mut screen, events).unwrap();
+
+        assert!(matches!(screen.result(), InitResult::Confirmed(_)));
+        let selections = screen.selections();
+        assert_eq!(selections.len(), 2);
+        assert_eq!(selections[1].name, "version-bump");
+        assert_eq!(
+            selections[1].inputs,
+            vec![
+                ("bump".to_string(), serde_yaml::Value::from("minor")),
+                ("retries".to_string(), serde_yaml::Value::from(3)),
+            ]
+        );
+
+        let contents = term.contents();
+        assert!(contents.contains("template: version-bump"));
+        assert!(contents.contains("bump: minor"));
+    }
+
+    #[test]
+    fn editor_overlay_shows_fields() {
+        let mut term = Vt100Terminal::new(100, 40);
+        let mut screen = sample_screen();
+        let mut state = AppState::default();
+        screen.handle_action(Action::Down, &mut state);
+        screen.handle_action(Action::Edit, &mut state);
+        term.draw(&screen);
+        let contents = term.contents();
+
+        assert!(contents.contains("Inputs · version-bump"));
+        assert!(contents.contains("› bump  ‹ patch ›"));
+        assert!(contents.contains("How many retries"));
+        assert!(contents.contains("Change value"));
+    }
+
+    #[test]
+    fn invalid_input_keeps_editor_open() {
+        let mut screen = sample_screen();
+        let mut state = AppState::default();
+        screen.move_selection(1);
+        screen.handle_action(Action::Edit, &mut state);
+        screen.handle_action(Action::Down, &mut state);
+        for c in "many".chars() {
+            let event = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            assert!(screen.handle_key(&event, &mut state).is_some());
+        }
+
+        screen.handle_action(Action::Confirm, &mut state);
+        assert!(screen.editing.is_some());
+        assert_eq!(
+            state.status.as_deref(),
+            Some("Input 'retries' must be a number")
+        );
+
+        let backspace = KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE);
+        for _ in 0..4 {
+            screen.handle_key(&backspace, &mut state);
+        }
+        screen.handle_action(Action::Confirm, &mut state);
+        assert!(screen.editing.is_none());
+        assert!(screen.step_templates[1].selected);
+    }
+
+    #[test]
+    fn cancel_discards_edits() {
+        let mut screen = sample_screen();
+        let mut state = AppState::default();
+        screen.move_selection(1);
+        screen.handle_action(Action::Edit, &mut state);
+        screen.handle_action(Action::Right, &mut state);
+        assert_eq!(screen.step_templates[1].inputs[0].value, "minor");
+
+        let result = screen.handle_action(Action::Cancel, &mut state);
+        assert!(matches!(result, ScreenResult::Continue));
+        assert!(screen.editing.is_none());
+        assert!(!screen.cancelled);
+        assert_eq!(screen.step_templates[1].inputs[0].value, "patch");
+        assert!(!screen.step_templates[1].selected);
+    }
+
+    #[test]
+    fn edit_ignored_for_templates_without_inputs() {
+        let mut screen = sample_screen();
+        let mut state = AppState::default();
+        assert_eq!(screen.bindings().footer_bindings().len(), 7);
+
+        screen.handle_action(Action::Edit, &mut state);
+        assert!(screen.editing.is_none());
+    }
+
+    #[test]
+    fn narrow_terminal_toggles_preview() {
+        let mut term = Vt100Terminal::new(60, 40);
+        let mut screen = sample_screen();
+        term.draw(&screen);
+        assert!(term.contents().contains("npm-install"));
+        assert!(!term.contents().contains("PREVIEW"));
+
+        screen.handle_action(Action::Preview, &mut AppState::default());
+        term.draw(&screen);
+        assert!(term.contents().contains("PREVIEW"));
+        assert!(!term.contents().contains("[✓] npm-install"));
+    }
+
+    #[test]
+    fn clicking_cards_and_confirm_button() {
+        let mut term = Vt100Terminal::new(100, 40);
+        let mut screen = sample_screen();
+        let hits = term.draw(&screen);
+
+        let card = (0..40)
+            .find_map(|row| hits.hit(4, row).filter(|r| r.index == Some(1)))
+            .expect("second card is clickable");
+        let confirm = (0..100)
+            .find_map(|col| hits.hit(col, 2).filter(|r| r.action == Action::Confirm))
+            .expect("confirm button is clickable");
+
+        term.run(
+            &mut screen,
+            vec![
+                click(card.area.x + 1, card.area.y + 1),
+                click(card.area.x + 1, card.area.y + 1),
+                click(confirm.area.x, confirm.area.y),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            screen.get_selected_steps(),
+            vec!["npm-install".to_string(), "version-bump".to_string()]
+        );
+        assert!(screen.completed);
+    }
+
+    #[test]
+    fn tiny_terminal_renders_without_panicking() {
+        let screen = sample_screen();
+        for (width, height) in [(20, 6), (40, 12), (8, 3)] {
+            Vt100Terminal::new(width, height).draw(&screen);
+        }
+    }
 }
@@ -0,0 +1,5 @@
+//! Full-screen screens.
+
+pub mod init;
+
+pub use init::{InitResult, InitScreen};
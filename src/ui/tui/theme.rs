@@ -0,0 +1,84 @@
+//! Colors and styles for full-screen screens.
+//!
+//! Follows the semantic color norms in `src/ui/README.md`: fuchsia for
+//! info and progress, green for success, orange for warnings, dim for
+//! secondary text. [`Palette`] holds the raw colors; [`Theme`] groups the
+//! styles components share.
+
+use ratatui::style::{Color, Modifier, Style};
+
+/// Raw colors used by screens and components.
+pub struct Palette;
+
+impl Palette {
+    /// Screen background.
+    pub const BG: Color = Color::Reset;
+    /// Background for overlays drawn above the screen.
+    pub const BG_ELEVATED: Color = Color::Indexed(235);
+    /// Primary text.
+    pub const TEXT: Color = Color::Reset;
+    /// Secondary text.
+    pub const TEXT_DIM: Color = Color::Indexed(245);
+    /// Placeholder and disabled text.
+    pub const TEXT_MUTED: Color = Color::Indexed(240);
+    /// Focus, keys and selection.
+    pub const LIME: Color = Color::Indexed(149);
+    /// Separators and counts.
+    pub const TEAL: Color = Color::Indexed(73);
+    /// Info, progress and branding.
+    pub const FUCHSIA: Color = Color::Magenta;
+    /// Success.
+    pub const GREEN: Color = Color::Green;
+    /// Warnings.
+    pub const ORANGE: Color = Color::Indexed(208);
+    /// Errors.
+    pub const RED: Color = Color::Red;
+    /// Card and section borders.
+    pub const BORDER: Color = Color::Indexed(240);
+    /// Hairline separators inside sections.
+    pub const BORDER_SUBTLE: Color = Color::Indexed(237);
+}
+
+/// Shared styles for components.
+#[derive(Debug, Clone)]
+pub struct Theme {
+    /// App name in the header.
+    pub brand: Style,
+    /// Supporting header text.
+    pub subtitle: Style,
+    /// Section titles.
+    pub title: Style,
+    /// Section and card borders.
+    pub border: Style,
+    /// Border of the focused card.
+    pub border_focused: Style,
+    /// Key labels in the footer.
+    pub key: Style,
+    /// Descriptions in the footer.
+    pub description: Style,
+    /// Validation errors.
+    pub error: Style,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            brand: Style::default()
+                .fg(Palette::FUCHSIA)
+                .add_modifier(Modifier::BOLD),
+            subtitle: Style::default().fg(Palette::TEXT_DIM),
+            title: Style::default()
+                .fg(Palette::TEXT)
+                .add_modifier(Modifier::BOLD),
+            border: Style::default().fg(Palette::BORDER),
+            border_focused: Style::default().fg(Palette::LIME),
+            key: Style::default()
+                .fg(Palette::LIME)
+                .add_modifier(Modifier::BOLD),
+            description: Style::default().fg(Palette::TEXT_DIM),
+            error: Style::default()
+                .fg(Palette::RED)
+                .add_modifier(Modifier::BOLD),
+        }
+    }
+}
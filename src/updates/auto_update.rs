@@ -8,15 +8,23 @@
 //! For manual installs, the staged binary is swapped in on the next startup.
 //! For package-manager installs, the update is applied immediately by the
 //! package manager and takes effect on the next invocation.
+//!
+//! Downloaded binaries are verified against the release's checksum manifest
+//! and, when a release key is embedded, its signature (see
+//! [`super::verify`]) both when staged and again before they are swapped in. The replaced binary is kept so `bivvy update --rollback`
+//! can restore it.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-use super::version::{check_for_updates, UpdateInfo};
+use super::verify::{
+    release_public_key, verify_release_asset, CHECKSUM_MANIFEST, CHECKSUM_SIGNATURE,
+};
+use super::version::{check_for_updates, UpdateInfo, UpdatePreferences, RELEASES_API_URL, VERSION};
 use super::{detect_install_method, InstallMethod};
 
 /// Metadata for a staged binary update (manual installs only).
@@ -26,6 +34,29 @@ pub struct StagedUpdate {
     pub version: String,
     /// When the binary was staged.
     pub staged_at: DateTime<Utc>,
+    /// Release asset the binary was downloaded from, used to look up its
+    /// checksum. Updates staged before verification existed have none and
+    /// are refused.
+    #[serde(default)]
+    pub asset: Option<String>,
+}
+
+/// Metadata for the binary replaced by the last update.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PreviousBinary {
+    /// The version of the replaced binary.
+    pub version: String,
+    /// When it was replaced.
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// File name of the bivvy binary on this platform.
+fn binary_name() -> &'static str {
+    if cfg!(windows) {
+        "bivvy.exe"
+    } else {
+        "bivvy"
+    }
 }
 
 /// Get the staging directory for downloaded binaries.
@@ -33,15 +64,9 @@ fn staging_dir() -> Option<PathBuf> {
     crate::sys::data_dir().map(|d| d.join("bivvy").join("staged-update"))
 }
 
-/// Get the path to the staged binary.
-fn staged_binary_path() -> Option<PathBuf> {
-    staging_dir().map(|d| {
-        if cfg!(windows) {
-            d.join("bivvy.exe")
-        } else {
-            d.join("bivvy")
-        }
-    })
+/// Get the directory holding the binary replaced by the last update.
+fn previous_dir() -> Option<PathBuf> {
+    crate::sys::data_dir().map(|d| d.join("bivvy").join("previous-binary"))
 }
 
 /// Get the path to the staging metadata file.
@@ -64,12 +89,13 @@ pub fn check_staged_update() -> Option<StagedUpdate> {
 /// Apply a staged update by replacing the current binary.
 ///
 /// Only applies to manual installs — for cargo/homebrew, the package manager
-/// already placed the new binary. Returns the new version string if applied.
+/// already placed the new binary. The staged binary is verified again before
+/// it is swapped in; one that fails verification is deleted and an error is
+/// returned. Returns the new version string if applied.
 pub fn apply_staged_update() -> Result<Option<String>> {
-    let staged = match check_staged_update() {
-        Some(s) => s,
-        None => return Ok(None),
-    };
+    if check_staged_update().is_none() {
+        return Ok(None);
+    }
 
     let method = detect_install_method();
 
@@ -80,23 +106,135 @@ pub fn apply_staged_update() -> Result<Option<String>> {
         return Ok(None);
     }
 
-    let staged_binary = match staged_binary_path() {
-        Some(p) if p.exists() => p,
-        _ => return Ok(None),
+    let (Some(staging), Some(previous)) = (staging_dir(), previous_dir()) else {
+        return Ok(None);
     };
 
     let current_exe = std::env::current_exe().context("Failed to locate current executable")?;
 
-    replace_binary(&staged_binary, &current_exe)?;
+    apply_staged_in(
+        &staging,
+        &previous,
+        &current_exe,
+        release_public_key()?.as_deref(),
+    )
+}
 
-    let _ = clear_staged_update();
+/// Verify the update staged in `staging`, keep `current` in `previous`,
+/// and swap the staged binary in.
+fn apply_staged_in(
+    staging: &Path,
+    previous: &Path,
+    current: &Path,
+    public_key: Option<&[u8]>,
+) -> Result<Option<String>> {
+    if !staging.join(binary_name()).exists() {
+        return Ok(None);
+    }
+
+    let staged = match verify_staged(staging, public_key) {
+        Ok(staged) => staged,
+        Err(e) => {
+            let _ = fs::remove_dir_all(staging);
+            return Err(e.context("Refusing to apply staged update"));
+        }
+    };
+
+    backup_binary(current, previous)?;
+    replace_binary(&staging.join(binary_name()), current)?;
+
+    let _ = fs::remove_dir_all(staging);
 
     Ok(Some(staged.version))
 }
 
+/// Re-verify a staged binary against the manifest staged with it.
+fn verify_staged(staging: &Path, public_key: Option<&[u8]>) -> Result<StagedUpdate> {
+    let staged: StagedUpdate = serde_json::from_str(
+        &fs::read_to_string(staging.join("metadata.json"))
+            .context("Failed to read staged update metadata")?,
+    )?;
+    let asset = staged
+        .asset
+        .as_deref()
+        .context("Staged update has no checksum information")?;
+
+    let manifest = fs::read(staging.join(CHECKSUM_MANIFEST))
+        .with_context(|| format!("Staged update has no {}", CHECKSUM_MANIFEST))?;
+    let signature = match public_key {
+        Some(_) => Some(
+            fs::read(staging.join(CHECKSUM_SIGNATURE))
+                .with_context(|| format!("Staged update has no {}", CHECKSUM_SIGNATURE))?,
+        ),
+        None => None,
+    };
+    let binary = fs::read(staging.join(binary_name()))?;
+
+    verify_release_asset(&manifest, signature.as_deref(), public_key, asset, &binary)?;
+    Ok(staged)
+}
+
+/// Copy the running binary aside so the update can be rolled back.
+fn backup_binary(current: &Path, previous: &Path) -> Result<()> {
+    if previous.exists() {
+        fs::remove_dir_all(previous)?;
+    }
+    fs::create_dir_all(previous)?;
+    fs::copy(current, previous.join(binary_name()))
+        .context("Failed to keep a copy of the current binary")?;
+
+    let metadata = PreviousBinary {
+        version: VERSION.to_string(),
+        replaced_at: Utc::now(),
+    };
+    fs::write(
+        previous.join("metadata.json"),
+        serde_json::to_string_pretty(&metadata)?,
+    )?;
+    Ok(())
+}
+
+/// The binary replaced by the last update, if one was kept.
+pub fn previous_binary() -> Option<PreviousBinary> {
+    let dir = previous_dir()?;
+    if !dir.join(binary_name()).exists() {
+        return None;
+    }
+    let content = fs::read_to_string(dir.join("metadata.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Restore the binary replaced by the last update.
+///
+/// Returns the restored version, or `None` if no previous binary was kept.
+pub fn rollback() -> Result<Option<String>> {
+    let Some(previous) = previous_dir() else {
+        return Ok(None);
+    };
+    let current_exe = std::env::current_exe().context("Failed to locate current executable")?;
+    rollback_in(&previous, &current_exe)
+}
+
+fn rollback_in(previous: &Path, current: &Path) -> Result<Option<String>> {
+    let binary = previous.join(binary_name());
+    if !binary.exists() {
+        return Ok(None);
+    }
+    let version = fs::read_to_string(previous.join("metadata.json"))
+        .ok()
+        .and_then(|c| serde_json::from_str::<PreviousBinary>(&c).ok())
+        .map(|p| p.version)
+        .unwrap_or_else(|| "the previous version".to_string());
+
+    replace_binary(&binary, current)?;
+    fs::remove_dir_all(previous)?;
+
+    Ok(Some(version))
+}
+
 /// Replace the current binary with the staged one (Unix).
 #[cfg(unix)]
-fn replace_binary(staged: &PathBuf, current: &PathBuf) -> Result<()> {
+fn replace_binary(staged: &Path, current: &Path) -> Result<()> {
     use std::os::unix::fs::PermissionsExt;
 
     // Copy to a temp file in the same directory (ensures same-filesystem rename)
@@ -121,7 +259,7 @@ fn replace_binary(staged: &PathBuf, current: &PathBuf) -> Result<()> {
 
 /// Replace the current binary with the staged one (Windows).
 #[cfg(windows)]
-fn replace_binary(staged: &PathBuf, current: &PathBuf) -> Result<()> {
+fn replace_binary(staged: &Path, current: &Path) -> Result<()> {
     let old = current.with_extension("old.exe");
 
     // Clean up any leftover from a previous update
@@ -225,6 +363,11 @@ pub fn should_spawn_background_update(is_interactive: bool, is_ci: bool) -> bool
         return false;
     }
 
+    // Offline mode never touches the network
+    if crate::cache::is_offline() {
+        return false;
+    }
+
     // Respect the user's config setting
     if !is_auto_update_enabled() {
         return false;
@@ -307,13 +450,16 @@ fn do_background_update() -> Result<()> {
     };
 
     let method = detect_install_method();
+    let latest_stable = UpdatePreferences::load().follows_latest_stable();
 
     match method {
-        InstallMethod::Cargo => {
-            run_package_manager_update("cargo", &["install", "bivvy", "--force"])?;
-        }
-        InstallMethod::Homebrew => {
-            run_package_manager_update("brew", &["upgrade", "bivvy"])?;
+        InstallMethod::Cargo | InstallMethod::Homebrew => {
+            // Homebrew can't install betas or pins — skip rather than
+            // move the user somewhere they didn't choose
+            if let Some(cmd) = method.update_command_for(&info.latest, latest_stable) {
+                let parts: Vec<&str> = cmd.split_whitespace().collect();
+                run_package_manager_update(parts[0], &parts[1..])?;
+            }
         }
         InstallMethod::Manual { .. } => {
             stage_binary_update(&info)?;
@@ -349,69 +495,122 @@ fn run_package_manager_update(program: &str, args: &[&str]) -> Result<()> {
 }
 
 /// Download the appropriate binary from GitHub releases and stage it.
-fn stage_binary_update(info: &UpdateInfo) -> Result<()> {
-    let asset_url = find_platform_asset_url(info)?;
-
+fn stage_binary_update(info: &UpdateInfo) -> Result<StagedUpdate> {
+    if crate::cache::is_offline() {
+        return Err(crate::cache::offline::refuse(&format!(
+            "bivvy v{} release",
+            info.latest
+        )));
+    }
     let dir = staging_dir().context("No data directory for staging")?;
-    fs::create_dir_all(&dir)?;
-
-    let binary_path = if cfg!(windows) {
-        dir.join("bivvy.exe")
-    } else {
-        dir.join("bivvy")
-    };
-
-    download_asset(&asset_url, &binary_path)?;
-
-    // Write metadata so apply_staged_update knows the version
-    let metadata = StagedUpdate {
-        version: info.latest.clone(),
-        staged_at: Utc::now(),
-    };
-    let meta_path = dir.join("metadata.json");
-    fs::write(meta_path, serde_json::to_string_pretty(&metadata)?)?;
-
-    Ok(())
+    stage_release(
+        RELEASES_API_URL,
+        &info.latest,
+        &platform_target(),
+        &dir,
+        release_public_key()?.as_deref(),
+    )
 }
 
-/// Query the GitHub releases API to find the download URL for this platform.
-fn find_platform_asset_url(info: &UpdateInfo) -> Result<String> {
-    let target = platform_target();
+/// Download, verify and immediately install an update (manual installs).
+///
+/// Returns the installed version.
+pub fn install_verified_update(info: &UpdateInfo) -> Result<Option<String>> {
+    stage_binary_update(info)?;
+    apply_staged_update()
+}
 
+/// Download the release asset for `target` plus the release's checksum
+/// manifest (and its signature when `public_key` is given), verify the
+/// asset, and stage it in `dir`.
+///
+/// Nothing is written unless verification succeeds.
+fn stage_release(
+    api_url: &str,
+    version: &str,
+    target: &str,
+    dir: &Path,
+    public_key: Option<&[u8]>,
+) -> Result<StagedUpdate> {
     let client = reqwest::blocking::Client::builder()
         .user_agent("bivvy")
-        .timeout(std::time::Duration::from_secs(30))
+        .timeout(std::time::Duration::from_secs(120))
         .build()?;
 
-    let api_url = format!(
-        "https://api.github.com/repos/bivvy-dev/bivvy/releases/tags/v{}",
-        info.latest
-    );
-
-    let response: serde_json::Value = client
-        .get(&api_url)
+    let release: serde_json::Value = client
+        .get(format!("{}/tags/v{}", api_url, version))
         .send()?
+        .error_for_status()?
         .json()
         .context("Failed to parse GitHub releases API response")?;
 
-    let assets = response["assets"]
+    let assets: Vec<(&str, &str)> = release["assets"]
         .as_array()
-        .context("No assets array in release")?;
+        .context("No assets array in release")?
+        .iter()
+        .filter_map(|a| Some((a["name"].as_str()?, a["browser_download_url"].as_str()?)))
+        .collect();
+    let find = |name: &str| assets.iter().find(|(n, _)| *n == name).map(|(_, u)| *u);
 
-    for asset in assets {
-        let name = asset["name"].as_str().unwrap_or("");
-        if name.contains(&target) {
-            if let Some(url) = asset["browser_download_url"].as_str() {
-                return Ok(url.to_string());
-            }
+    let Some(&(asset_name, asset_url)) = assets.iter().find(|(n, _)| n.contains(target)) else {
+        anyhow::bail!(
+            "No release asset found for platform '{}' in v{}",
+            target,
+            version
+        );
+    };
+    let manifest_url = find(CHECKSUM_MANIFEST).with_context(|| {
+        format!(
+            "Release v{} has no {} checksum manifest",
+            version, CHECKSUM_MANIFEST
+        )
+    })?;
+    let signature = match public_key {
+        Some(_) => {
+            let signature_url = find(CHECKSUM_SIGNATURE).with_context(|| {
+                format!(
+                    "Release v{} has no {} signature",
+                    version, CHECKSUM_SIGNATURE
+                )
+            })?;
+            Some(download_bytes(&client, signature_url)?)
         }
+        None => None,
+    };
+
+    let manifest = download_bytes(&client, manifest_url)?;
+    let binary = download_bytes(&client, asset_url)?;
+
+    verify_release_asset(
+        &manifest,
+        signature.as_deref(),
+        public_key,
+        asset_name,
+        &binary,
+    )?;
+
+    if dir.exists() {
+        fs::remove_dir_all(dir)?;
+    }
+    fs::create_dir_all(dir)?;
+    write_executable(&dir.join(binary_name()), &binary)?;
+    fs::write(dir.join(CHECKSUM_MANIFEST), &manifest)?;
+    if let Some(signature) = &signature {
+        fs::write(dir.join(CHECKSUM_SIGNATURE), signature)?;
     }
 
-    anyhow::bail!(
-        "No release asset found for platform '{}' in v{}",
-        target,
-        info.latest
-    )
+    // Metadata last: its presence marks the staged update as complete
+    let metadata = StagedUpdate {
+        version: version.to_string(),
+        staged_at: Utc::now(),
+        asset: Some(asset_name.to_string()),
+    };
+    fs::write(
+        dir.join("metadata.json"),
+        serde_json::to_string_pretty(&metadata)?,
+    )?;
+
+    Ok(metadata)
 }
 
 /// Return the Rust target triple fragment for this platform.
@@ -431,23 +630,22 @@ pub fn platform_target() -> String {
     format!("{}-{}", arch, os)
 }
 
-/// Download a file from a URL to a local path.
-fn download_asset(url: &str, dest: &PathBuf) -> Result<()> {
-    let client = reqwest::blocking::Client::builder()
-        .user_agent("bivvy")
-        .timeout(std::time::Duration::from_secs(120))
-        .build()?;
-
+/// Download a URL into memory.
+fn download_bytes(client: &reqwest::blocking::Client, url: &str) -> Result<Vec<u8>> {
     let bytes = client
         .get(url)
         .send()
-        .context("Failed to download update")?
+        .and_then(|r| r.error_for_status())
+        .with_context(|| format!("Failed to download {}", url))?
         .bytes()
         .context("Failed to read download body")?;
+    Ok(bytes.to_vec())
+}
 
-    fs::write(dest, &bytes)?;
+/// Write a file and make it executable on Unix.
+fn write_executable(dest: &Path, bytes: &[u8]) -> Result<()> {
+    fs::write(dest, bytes)?;
 
-    // Make executable on Unix
     #[cfg(unix)]
     {
         use std::os::unix::fs::PermissionsExt;
@@ -462,8 +660,50 @@ fn download_asset(url: &str, dest: &PathBuf) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::updates::verify::tests::{signed_manifest, test_key};
+    use httpmock::prelude::*;
     use tempfile::TempDir;
 
+    const ASSET: &str = "bivvy-x86_64-unknown-linux-gnu";
+
+    /// Serve release v2.0.0 with one binary asset and its signed manifest.
+    fn release_server(binary: &[u8], manifest: &[u8], signature: &[u8]) -> MockServer {
+        let server = MockServer::start();
+        let asset = |name: &str| {
+            serde_json::json!({
+                "name": name,
+                "browser_download_url": server.url(format!("/download/{}", name)),
+            })
+        };
+        let release = serde_json::json!({
+            "tag_name": "v2.0.0",
+            "assets": [asset(ASSET), asset(CHECKSUM_MANIFEST), asset(CHECKSUM_SIGNATURE)],
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/releases/tags/v2.0.0");
+            then.status(200).json_body(release);
+        });
+        for (name, body) in [
+            (ASSET, binary),
+            (CHECKSUM_MANIFEST, manifest),
+            (CHECKSUM_SIGNATURE, signature),
+        ] {
+            let body = body.to_vec();
+            server.mock(|when, then| {
+                when.method(GET).path(format!("/download/{}", name));
+                then.status(200).body(body);
+            });
+        }
+        server
+    }
+
+    fn current_binary(temp: &TempDir) -> PathBuf {
+        let current = temp.path().join("bin").join(binary_name());
+        fs::create_dir_all(current.parent().unwrap()).unwrap();
+        write_executable(&current, b"old version").unwrap();
+        current
+    }
+
     #[test]
     fn platform_target_contains_arch_and_os() {
         let target = platform_target();
@@ -491,6 +731,7 @@ mod tests {
         let staged = StagedUpdate {
             version: "2.0.0".to_string(),
             staged_at: Utc::now(),
+            asset: Some("bivvy-linux-x64".to_string()),
         };
 
         let json = serde_json::to_string(&staged).unwrap();
@@ -509,7 +750,7 @@ mod tests {
 
     #[test]
     fn staged_binary_path_has_correct_name() {
-        if let Some(path) = staged_binary_path() {
+        if let Some(path) = staging_dir().map(|d| d.join(binary_name())) {
             let name = path.file_name().unwrap().to_string_lossy();
             if cfg!(windows) {
                 assert_eq!(name, "bivvy.exe");
@@ -624,4 +865,203 @@ mod tests {
             assert_eq!(path.file_name().unwrap().to_string_lossy(), "update.lock");
         }
     }
+
+    #[test]
+    fn verified_update_stages_applies_and_rolls_back() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"new version", &manifest, &signature);
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+        let previous = temp.path().join("previous-binary");
+        let current = current_binary(&temp);
+
+        let staged = stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            Some(public.as_slice()),
+        )
+        .unwrap();
+        assert_eq!(staged.asset.as_deref(), Some(ASSET));
+        assert!(staging.join(CHECKSUM_MANIFEST).exists());
+
+        let applied =
+            apply_staged_in(&staging, &previous, &current, Some(public.as_slice())).unwrap();
+        assert_eq!(applied.as_deref(), Some("2.0.0"));
+        assert_eq!(fs::read(&current).unwrap(), b"new version");
+        assert_eq!(
+            fs::read(previous.join(binary_name())).unwrap(),
+            b"old version"
+        );
+        assert!(!staging.exists());
+
+        let restored = rollback_in(&previous, &current).unwrap();
+        assert_eq!(restored.as_deref(), Some(VERSION));
+        assert_eq!(fs::read(&current).unwrap(), b"old version");
+        assert!(rollback_in(&previous, &current).unwrap().is_none());
+    }
+
+    #[test]
+    fn stage_release_refuses_checksum_mismatch() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"tampered", &manifest, &signature);
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+
+        let err = stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            Some(public.as_slice()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn stage_release_refuses_bad_signature() {
+        let (pair, _) = test_key();
+        let (_, other_public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"new version", &manifest, &signature);
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+
+        let err = stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            Some(other_public.as_slice()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("signature"));
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn unsigned_release_is_checked_against_manifest_without_key() {
+        let (pair, _) = test_key();
+        let (manifest, _) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"new version", &manifest, b"");
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+        let previous = temp.path().join("previous-binary");
+        let current = current_binary(&temp);
+
+        stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            None,
+        )
+        .unwrap();
+        assert!(!staging.join(CHECKSUM_SIGNATURE).exists());
+
+        let applied = apply_staged_in(&staging, &previous, &current, None).unwrap();
+        assert_eq!(applied.as_deref(), Some("2.0.0"));
+        assert_eq!(fs::read(&current).unwrap(), b"new version");
+    }
+
+    #[test]
+    fn stage_release_without_key_refuses_checksum_mismatch() {
+        let (pair, _) = test_key();
+        let (manifest, _) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"tampered", &manifest, b"");
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+
+        let err = stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+        assert!(!staging.exists());
+    }
+
+    #[test]
+    fn stage_release_refuses_release_without_manifest() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/releases/tags/v2.0.0");
+            then.status(200).json_body(serde_json::json!({
+                "assets": [{
+                    "name": ASSET,
+                    "browser_download_url": server.url("/download/bin"),
+                }],
+            }));
+        });
+        let temp = TempDir::new().unwrap();
+
+        let err = stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &temp.path().join("staged-update"),
+            Some(test_key().1.as_slice()),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("SHA256SUMS"));
+    }
+
+    #[test]
+    fn apply_refuses_tampered_staged_binary() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, ASSET, b"new version");
+        let server = release_server(b"new version", &manifest, &signature);
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+        let previous = temp.path().join("previous-binary");
+        let current = current_binary(&temp);
+
+        stage_release(
+            &server.url("/releases"),
+            "2.0.0",
+            "x86_64-unknown-linux-gnu",
+            &staging,
+            Some(public.as_slice()),
+        )
+        .unwrap();
+        fs::write(staging.join(binary_name()), b"swapped after staging").unwrap();
+
+        let err =
+            apply_staged_in(&staging, &previous, &current, Some(public.as_slice())).unwrap_err();
+        assert!(err.to_string().contains("Refusing"));
+        assert_eq!(fs::read(&current).unwrap(), b"old version");
+        assert!(!staging.exists());
+        assert!(!previous.exists());
+    }
+
+    #[test]
+    fn apply_refuses_unverified_legacy_staging() {
+        let temp = TempDir::new().unwrap();
+        let staging = temp.path().join("staged-update");
+        fs::create_dir_all(&staging).unwrap();
+        fs::write(staging.join(binary_name()), b"unverified").unwrap();
+        fs::write(
+            staging.join("metadata.json"),
+            r#"{"version":"2.0.0","staged_at":"2026-01-01T00:00:00Z"}"#,
+        )
+        .unwrap();
+        let current = current_binary(&temp);
+
+        assert!(apply_staged_in(
+            &staging,
+            &temp.path().join("previous-binary"),
+            &current,
+            Some(test_key().1.as_slice())
+        )
+        .is_err());
+        assert_eq!(fs::read(&current).unwrap(), b"old version");
+    }
 }
@@ -30,6 +30,22 @@ impl InstallMethod {
         }
     }
 
+    /// Get the command that installs a specific `version`.
+    ///
+    /// Used when following the beta channel or a pin. Homebrew only ships
+    /// the latest stable release, so it has no command for other versions.
+    pub fn update_command_for(&self, version: &str, latest_stable: bool) -> Option<String> {
+        if latest_stable {
+            return self.update_command();
+        }
+        match self {
+            InstallMethod::Cargo => {
+                Some(format!("cargo install bivvy --version {} --force", version))
+            }
+            _ => None,
+        }
+    }
+
     /// Check if this method supports automatic updates.
     pub fn supports_auto_update(&self) -> bool {
         matches!(self, InstallMethod::Cargo | InstallMethod::Homebrew)
@@ -164,6 +180,21 @@ mod tests {
         assert!(InstallMethod::Unknown.update_command().is_none());
     }
 
+    #[test]
+    fn install_method_update_command_for_version() {
+        assert_eq!(
+            InstallMethod::Cargo.update_command_for("1.10.0", true),
+            InstallMethod::Cargo.update_command()
+        );
+        assert_eq!(
+            InstallMethod::Cargo.update_command_for("1.10.0-beta.1", false),
+            Some("cargo install bivvy --version 1.10.0-beta.1 --force".to_string())
+        );
+        assert!(InstallMethod::Homebrew
+            .update_command_for("1.10.0-beta.1", false)
+            .is_none());
+    }
+
     #[test]
     fn install_method_supports_auto_update() {
         assert!(InstallMethod::Cargo.supports_auto_update());
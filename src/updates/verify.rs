@@ -0,0 +1,293 @@
+//! Verification of downloaded release binaries.
+//!
+//! Every release publishes a `SHA256SUMS` manifest (the output of
+//! `sha256sum` over the release assets) and, once a signing key exists,
+//! `SHA256SUMS.sig`, an Ed25519 signature of that manifest. A downloaded
+//! asset is trusted only if its SHA-256 matches its manifest entry and,
+//! when a release public key is embedded, the manifest's signature
+//! verifies against it.
+//!
+//! No release public key is embedded yet (see
+//! `docs/guides/release-signing.md`), so [`release_public_key`] returns
+//! `None` and downloads are checked against the manifest alone.
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use ring::signature::{UnparsedPublicKey, ED25519};
+use sha2::{Digest, Sha256};
+
+/// Release asset name of the checksum manifest.
+pub const CHECKSUM_MANIFEST: &str = "SHA256SUMS";
+
+/// Release asset name of the manifest's detached signature.
+pub const CHECKSUM_SIGNATURE: &str = "SHA256SUMS.sig";
+
+/// Hex-encoded Ed25519 public key that signs release checksum manifests.
+///
+/// The release workflow refuses to publish an unsigned manifest once this
+/// is set.
+const RELEASE_PUBLIC_KEY_HEX: Option<&str> = None;
+
+/// The embedded Ed25519 public key that signs release checksum manifests.
+///
+/// Returns `None` while no key is embedded; downloads are then checked
+/// against the checksum manifest without a signature.
+pub fn release_public_key() -> Result<Option<Vec<u8>>> {
+    RELEASE_PUBLIC_KEY_HEX
+        .map(|key| hex::decode(key).context("Embedded release public key is not valid hex"))
+        .transpose()
+}
+
+/// A parsed `SHA256SUMS` manifest.
+#[derive(Debug, Clone, Default)]
+pub struct ChecksumManifest {
+    checksums: HashMap<String, String>,
+}
+
+impl ChecksumManifest {
+    /// Parse `sha256sum` output: `<hex>  <name>` or `<hex> *<name>` per line.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut checksums = HashMap::new();
+        for (index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (hash, name) = line
+                .split_once(char::is_whitespace)
+                .with_context(|| format!("Malformed checksum line {}", index + 1))?;
+            let name = name.trim_start().trim_start_matches('*');
+            if hash.len() != 64 || !hash.bytes().all(|b| b.is_ascii_hexdigit()) {
+                bail!("Invalid SHA-256 on checksum line {}", index + 1);
+            }
+            // sha256sum may be run from a parent directory; match on file name
+            let name = name.rsplit('/').next().unwrap_or(name);
+            checksums.insert(name.to_string(), hash.to_ascii_lowercase());
+        }
+        Ok(Self { checksums })
+    }
+
+    /// The expected SHA-256 of an asset, lowercase hex.
+    pub fn checksum_for(&self, asset: &str) -> Option<&str> {
+        self.checksums.get(asset).map(String::as_str)
+    }
+}
+
+/// Lowercase hex SHA-256 of some bytes.
+pub fn sha256_hex(bytes: &[u8]) -> String {
+    hex::encode(Sha256::digest(bytes))
+}
+
+/// Verify a detached Ed25519 signature over a manifest.
+///
+/// The signature may be the raw 64 bytes or their hex encoding.
+pub fn verify_signature(manifest: &[u8], signature: &[u8], public_key: &[u8]) -> Result<()> {
+    let signature = if signature.len() == 64 {
+        signature.to_vec()
+    } else {
+        let text = std::str::from_utf8(signature)
+            .ok()
+            .map(str::trim)
+            .context("Checksum signature is neither raw nor hex-encoded")?;
+        hex::decode(text).context("Checksum signature is not valid hex")?
+    };
+
+    UnparsedPublicKey::new(&ED25519, public_key)
+        .verify(manifest, &signature)
+        .map_err(|_| anyhow::anyhow!("Checksum manifest signature verification failed"))
+}
+
+/// Verify an asset against a signed manifest. Returns its SHA-256.
+pub fn verify_asset(
+    manifest: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+    asset_name: &str,
+    asset: &[u8],
+) -> Result<String> {
+    verify_signature(manifest, signature, public_key)?;
+    verify_checksum(manifest, asset_name, asset)
+}
+
+/// Verify an asset against a release manifest, checking the manifest's
+/// signature only when a public key is embedded. Returns its SHA-256.
+///
+/// A missing signature fails when a key is given.
+pub fn verify_release_asset(
+    manifest: &[u8],
+    signature: Option<&[u8]>,
+    public_key: Option<&[u8]>,
+    asset_name: &str,
+    asset: &[u8],
+) -> Result<String> {
+    match public_key {
+        Some(public_key) => {
+            let signature = signature
+                .with_context(|| format!("Release has no {} signature", CHECKSUM_SIGNATURE))?;
+            verify_asset(manifest, signature, public_key, asset_name, asset)
+        }
+        None => verify_checksum(manifest, asset_name, asset),
+    }
+}
+
+/// Check an asset's SHA-256 against its manifest entry, without verifying
+/// the manifest itself. Returns its SHA-256.
+pub fn verify_checksum(manifest: &[u8], asset_name: &str, asset: &[u8]) -> Result<String> {
+    let manifest = ChecksumManifest::parse(
+        std::str::from_utf8(manifest).context("Checksum manifest is not UTF-8")?,
+    )?;
+    let expected = manifest
+        .checksum_for(asset_name)
+        .with_context(|| format!("'{}' is not listed in {}", asset_name, CHECKSUM_MANIFEST))?;
+
+    let actual = sha256_hex(asset);
+    if actual != expected {
+        bail!(
+            "Checksum mismatch for '{}': expected {}, got {}",
+            asset_name,
+            expected,
+            actual
+        );
+    }
+    Ok(actual)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+    use ring::signature::{Ed25519KeyPair, KeyPair};
+
+    /// A freshly generated signing key and its public half.
+    pub(crate) fn test_key() -> (Ed25519KeyPair, Vec<u8>) {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        let pair = Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let public = pair.public_key().as_ref().to_vec();
+        (pair, public)
+    }
+
+    /// A manifest listing one asset, and its signature.
+    pub(crate) fn signed_manifest(
+        pair: &Ed25519KeyPair,
+        asset_name: &str,
+        asset: &[u8],
+    ) -> (Vec<u8>, Vec<u8>) {
+        let manifest = format!(
+            "{}  {}\n{}  other.tar.gz\n",
+            sha256_hex(asset),
+            asset_name,
+            "0".repeat(64)
+        )
+        .into_bytes();
+        let signature = pair.sign(&manifest).as_ref().to_vec();
+        (manifest, signature)
+    }
+
+    #[test]
+    fn no_release_key_is_embedded_yet() {
+        assert!(release_public_key().unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_manifest_formats() {
+        let hash = "a".repeat(64);
+        let manifest = ChecksumManifest::parse(&format!(
+            "{hash}  bivvy-linux-x64.tar.gz\n{hash} *bivvy-windows.zip\n\n{hash}  artifacts/x/bivvy-darwin-arm64.tar.gz\n"
+        ))
+        .unwrap();
+        assert_eq!(
+            manifest.checksum_for("bivvy-linux-x64.tar.gz"),
+            Some(&*hash)
+        );
+        assert_eq!(manifest.checksum_for("bivvy-windows.zip"), Some(&*hash));
+        assert_eq!(
+            manifest.checksum_for("bivvy-darwin-arm64.tar.gz"),
+            Some(&*hash)
+        );
+        assert!(manifest.checksum_for("missing").is_none());
+    }
+
+    #[test]
+    fn parse_manifest_rejects_bad_hash() {
+        assert!(ChecksumManifest::parse("abc  bivvy\n").is_err());
+    }
+
+    #[test]
+    fn verify_asset_accepts_signed_match() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, "bivvy", b"binary");
+
+        let sha = verify_asset(&manifest, &signature, &public, "bivvy", b"binary").unwrap();
+        assert_eq!(sha, sha256_hex(b"binary"));
+
+        let hex_signature = hex::encode(&signature).into_bytes();
+        assert!(verify_asset(&manifest, &hex_signature, &public, "bivvy", b"binary").is_ok());
+    }
+
+    #[test]
+    fn verify_asset_rejects_tampered_binary() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, "bivvy", b"binary");
+
+        let err = verify_asset(&manifest, &signature, &public, "bivvy", b"evil").unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn verify_asset_rejects_tampered_manifest() {
+        let (pair, public) = test_key();
+        let (_, signature) = signed_manifest(&pair, "bivvy", b"binary");
+        let forged = format!("{}  bivvy\n", sha256_hex(b"evil")).into_bytes();
+
+        let err = verify_asset(&forged, &signature, &public, "bivvy", b"evil").unwrap_err();
+        assert!(err.to_string().contains("signature"));
+    }
+
+    #[test]
+    fn verify_asset_rejects_other_key() {
+        let (pair, _) = test_key();
+        let (_, other_public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, "bivvy", b"binary");
+
+        assert!(verify_asset(&manifest, &signature, &other_public, "bivvy", b"binary").is_err());
+    }
+
+    #[test]
+    fn verify_release_asset_without_key_checks_checksum_only() {
+        let (pair, _) = test_key();
+        let (manifest, _) = signed_manifest(&pair, "bivvy", b"binary");
+
+        assert!(verify_release_asset(&manifest, None, None, "bivvy", b"binary").is_ok());
+        let err = verify_release_asset(&manifest, None, None, "bivvy", b"evil").unwrap_err();
+        assert!(err.to_string().contains("Checksum mismatch"));
+    }
+
+    #[test]
+    fn verify_release_asset_with_key_requires_signature() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, "bivvy", b"binary");
+
+        let err =
+            verify_release_asset(&manifest, None, Some(public.as_slice()), "bivvy", b"binary")
+                .unwrap_err();
+        assert!(err.to_string().contains("SHA256SUMS.sig"));
+        assert!(verify_release_asset(
+            &manifest,
+            Some(signature.as_slice()),
+            Some(public.as_slice()),
+            "bivvy",
+            b"binary"
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn verify_asset_rejects_unlisted_asset() {
+        let (pair, public) = test_key();
+        let (manifest, signature) = signed_manifest(&pair, "bivvy", b"binary");
+
+        let err = verify_asset(&manifest, &signature, &public, "bivvy-x", b"binary").unwrap_err();
+        assert!(err.to_string().contains("not listed"));
+    }
+}
@@ -3,14 +3,20 @@
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use crate::config::UpdateChannel;
 
 /// Current version of bivvy.
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 /// GitHub API URL for releases.
-const GITHUB_API_URL: &str = "https://api.github.com/repos/bivvy-dev/bivvy/releases/latest";
+pub(crate) const RELEASES_API_URL: &str = "https://api.github.com/repos/bivvy-dev/bivvy/releases";
+
+/// Number of recent releases considered when looking for the newest beta.
+const BETA_RELEASE_WINDOW: u32 = 30;
 
 /// How often to check for updates (1 day).
 const CHECK_INTERVAL_SECS: i64 = 86400;
@@ -37,14 +43,86 @@ struct UpdateCache {
     info: UpdateInfo,
     /// When the cache was written.
     cached_at: DateTime<Utc>,
+    /// Channel or pin the check was made for. A cache written for a
+    /// different selection is ignored.
+    #[serde(default)]
+    selection: String,
+}
+
+/// Which releases `bivvy update` and auto-update follow.
+///
+/// Read from `settings.update_channel` and `settings.update_pin` in the
+/// user config (`~/.bivvy/config.yml`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UpdatePreferences {
+    /// Release channel to follow.
+    pub channel: UpdateChannel,
+    /// Exact version to stay on, overriding the channel.
+    pub pin: Option<String>,
+}
+
+impl UpdatePreferences {
+    /// Load preferences from the user config, falling back to defaults.
+    pub fn load() -> Self {
+        crate::sys::home_dir()
+            .map(|h| Self::from_config_file(&h.join(".bivvy").join("config.yml")))
+            .unwrap_or_default()
+    }
+
+    /// Read preferences from a config file's `settings` block.
+    pub fn from_config_file(path: &Path) -> Self {
+        let Some(value) = fs::read_to_string(path)
+            .ok()
+            .and_then(|c| serde_yaml::from_str::<serde_yaml::Value>(&c).ok())
+        else {
+            return Self::default();
+        };
+        let settings = value.get("settings");
+        let channel = settings
+            .and_then(|s| s.get("update_channel"))
+            .and_then(|v| serde_yaml::from_value(v.clone()).ok())
+            .unwrap_or_default();
+        let pin = settings
+            .and_then(|s| s.get("update_pin"))
+            .and_then(|v| match v {
+                serde_yaml::Value::String(s) => Some(s.clone()),
+                serde_yaml::Value::Number(n) => Some(n.to_string()),
+                _ => None,
+            })
+            .map(|v| v.trim_start_matches('v').to_string());
+        Self { channel, pin }
+    }
+
+    /// Whether these are the defaults (latest stable release).
+    pub fn follows_latest_stable(&self) -> bool {
+        self.pin.is_none() && self.channel == UpdateChannel::Stable
+    }
+
+    /// Short description, e.g. `stable`, `beta` or `pinned to 1.9.2`.
+    pub fn describe(&self) -> String {
+        match self.pin {
+            Some(ref pin) => format!("pinned to {}", pin),
+            None => self.channel.to_string(),
+        }
+    }
 }
 
 /// Check for available updates.
 ///
 /// Returns cached result if within check interval.
 pub fn check_for_updates() -> Option<UpdateInfo> {
+    let prefs = UpdatePreferences::load();
+    let selection = prefs.describe();
+
+    // Offline, the last result is all there is
+    if crate::cache::is_offline() {
+        return load_cache()
+            .filter(|c| c.selection == selection)
+            .map(|c| c.info);
+    }
+
     // Check cache first
-    if let Some(cached) = load_cache() {
+    if let Some(cached) = load_cache().filter(|c| c.selection == selection) {
         let age = Utc::now()
             .signed_duration_since(cached.cached_at)
             .num_seconds();
@@ -54,48 +132,89 @@ pub fn check_for_updates() -> Option<UpdateInfo> {
     }
 
     // Fetch latest version
-    match fetch_latest_version() {
+    match fetch_update_info(RELEASES_API_URL, &prefs, VERSION) {
         Ok(info) => {
             // Cache the result
-            let _ = save_cache(&info);
+            let _ = save_cache(&info, &selection);
             Some(info)
         }
         Err(_) => {
             // Return cached result even if expired
-            load_cache().map(|c| c.info)
+            load_cache()
+                .filter(|c| c.selection == selection)
+                .map(|c| c.info)
         }
     }
 }
 
 /// Check for updates without using cache.
 pub fn check_for_updates_fresh() -> Result<UpdateInfo> {
-    fetch_latest_version()
+    if crate::cache::is_offline() {
+        return Err(crate::cache::offline::refuse("GitHub releases"));
+    }
+    fetch_update_info(RELEASES_API_URL, &UpdatePreferences::load(), VERSION)
 }
 
-/// Fetch the latest version from GitHub.
-fn fetch_latest_version() -> Result<UpdateInfo> {
+/// Find the release the preferences select and compare it to `current`.
+///
+/// - A pin selects that exact release; an update is "available" whenever
+///   the running version differs, so pinning can also downgrade.
+/// - `stable` selects GitHub's latest release (never a pre-release).
+/// - `beta` selects the highest version among recent releases, including
+///   pre-releases.
+fn fetch_update_info(
+    api_url: &str,
+    prefs: &UpdatePreferences,
+    current: &str,
+) -> Result<UpdateInfo> {
     let client = reqwest::blocking::Client::builder()
         .user_agent("bivvy")
         .timeout(std::time::Duration::from_secs(10))
         .build()?;
 
-    let response: serde_json::Value = client
-        .get(GITHUB_API_URL)
-        .send()?
-        .json()
-        .context("Failed to parse GitHub API response")?;
+    let get = |url: String| -> Result<serde_json::Value> {
+        client
+            .get(url)
+            .send()?
+            .error_for_status()?
+            .json()
+            .context("Failed to parse GitHub API response")
+    };
 
-    let tag = response["tag_name"]
-        .as_str()
-        .context("No tag_name in response")?
-        .trim_start_matches('v');
+    let release = match (&prefs.pin, prefs.channel) {
+        (Some(pin), _) => get(format!("{}/tags/v{}", api_url, pin))
+            .with_context(|| format!("Pinned version {} was not found", pin))?,
+        (None, UpdateChannel::Stable) => get(format!("{}/latest", api_url))?,
+        (None, UpdateChannel::Beta) => {
+            let releases = get(format!("{}?per_page={}", api_url, BETA_RELEASE_WINDOW))?;
+            releases
+                .as_array()
+                .context("Expected a list of releases")?
+                .iter()
+                .filter(|r| !r["draft"].as_bool().unwrap_or(false))
+                .filter(|r| r["tag_name"].is_string())
+                .max_by(|a, b| {
+                    compare_versions(
+                        release_version(a).unwrap_or_default(),
+                        release_version(b).unwrap_or_default(),
+                    )
+                })
+                .cloned()
+                .context("No releases found")?
+        }
+    };
 
-    let release_url = response["html_url"].as_str().map(String::from);
+    let tag = release_version(&release).context("No tag_name in response")?;
+    let release_url = release["html_url"].as_str().map(String::from);
 
-    let update_available = is_newer_version(tag, VERSION);
+    let update_available = match (&prefs.pin, prefs.channel) {
+        (Some(_), _) => compare_versions(tag, current) != Ordering::Equal,
+        (None, UpdateChannel::Stable) => is_newer_version(tag, current),
+        (None, UpdateChannel::Beta) => compare_versions(tag, current) == Ordering::Greater,
+    };
 
     Ok(UpdateInfo {
-        current: VERSION.to_string(),
+        current: current.to_string(),
         latest: tag.to_string(),
         update_available,
         release_url,
@@ -103,6 +222,141 @@ fn fetch_latest_version() -> Result<UpdateInfo> {
     })
 }
 
+/// The version named by a release's tag, without a leading `v`.
+fn release_version(release: &serde_json::Value) -> Option<&str> {
+    release["tag_name"]
+        .as_str()
+        .map(|t| t.trim_start_matches('v'))
+}
+
+/// Compare two versions, semver-style.
+///
+/// Missing components count as zero (`1.10` == `1.10.0`), and a
+/// pre-release sorts before its release (`1.10.0-rc.1` < `1.10.0`).
+/// Build metadata after `+` is ignored.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    fn split(v: &str) -> (Vec<u64>, Option<&str>) {
+        let v = v.trim().trim_start_matches('v');
+        let v = v.split('+').next().unwrap_or(v);
+        let (release, pre) = match v.split_once('-') {
+            Some((release, pre)) => (release, Some(pre)),
+            None => (v, None),
+        };
+        let parts = release.split('.').map(|p| p.parse().unwrap_or(0)).collect();
+        (parts, pre)
+    }
+
+    let (a_parts, a_pre) = split(a);
+    let (b_parts, b_pre) = split(b);
+    let len = a_parts.len().max(b_parts.len());
+    for i in 0..len {
+        let x = a_parts.get(i).copied().unwrap_or(0);
+        let y = b_parts.get(i).copied().unwrap_or(0);
+        match x.cmp(&y) {
+            Ordering::Equal => {}
+            other => return other,
+        }
+    }
+
+    match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(x), Some(y)) => {
+            let mut xs = x.split('.');
+            let mut ys = y.split('.');
+            loop {
+                match (xs.next(), ys.next()) {
+                    (None, None) => return Ordering::Equal,
+                    (None, Some(_)) => return Ordering::Less,
+                    (Some(_), None) => return Ordering::Greater,
+                    (Some(p), Some(q)) => {
+                        let ord = match (p.parse::<u64>(), q.parse::<u64>()) {
+                            (Ok(m), Ok(n)) => m.cmp(&n),
+                            _ => p.cmp(q),
+                        };
+                        if ord != Ordering::Equal {
+                            return ord;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A `bivvy_version` requirement such as `>=1.10` or `>=1.10, <2`.
+///
+/// Comma-separated comparators must all match. Supported operators are
+/// `>=`, `>`, `<=`, `<` and `=`; a bare version means `>=`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionConstraint {
+    raw: String,
+    comparators: Vec<(Ordering, bool, String)>,
+}
+
+impl VersionConstraint {
+    /// Parse a constraint string.
+    pub fn parse(raw: &str) -> std::result::Result<Self, String> {
+        let mut comparators = Vec::new();
+        for part in raw.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                continue;
+            }
+            // (ordering the version must have relative to the bound, or-equal)
+            let (ordering, or_equal, version) = if let Some(v) = part.strip_prefix(">=") {
+                (Ordering::Greater, true, v)
+            } else if let Some(v) = part.strip_prefix("<=") {
+                (Ordering::Less, true, v)
+            } else if let Some(v) = part.strip_prefix('>') {
+                (Ordering::Greater, false, v)
+            } else if let Some(v) = part.strip_prefix('<') {
+                (Ordering::Less, false, v)
+            } else if let Some(v) = part.strip_prefix('=') {
+                (Ordering::Equal, true, v)
+            } else {
+                (Ordering::Greater, true, part)
+            };
+            let version = version.trim().trim_start_matches('v');
+            let valid = !version.is_empty()
+                && version
+                    .split(['-', '+'])
+                    .next()
+                    .unwrap_or("")
+                    .split('.')
+                    .all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()));
+            if !valid {
+                return Err(format!("'{}' is not a version comparison", part));
+            }
+            comparators.push((ordering, or_equal, version.to_string()));
+        }
+        if comparators.is_empty() {
+            return Err("empty version constraint".to_string());
+        }
+        Ok(Self {
+            raw: raw.trim().to_string(),
+            comparators,
+        })
+    }
+
+    /// Whether `version` satisfies every comparator.
+    pub fn matches(&self, version: &str) -> bool {
+        self.comparators.iter().all(|(ordering, or_equal, bound)| {
+            match compare_versions(version, bound) {
+                Ordering::Equal => *or_equal,
+                actual => actual == *ordering,
+            }
+        })
+    }
+}
+
+impl std::fmt::Display for VersionConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.raw)
+    }
+}
+
 /// Compare versions to check if `latest` is newer than `current`.
 fn is_newer_version(latest: &str, current: &str) -> bool {
     let parse_version = |v: &str| -> Vec<u32> {
@@ -142,7 +396,7 @@ fn load_cache() -> Option<UpdateCache> {
 }
 
 /// Save update info to cache.
-fn save_cache(info: &UpdateInfo) -> Result<()> {
+fn save_cache(info: &UpdateInfo, selection: &str) -> Result<()> {
     let path = cache_path().context("No cache directory")?;
 
     if let Some(parent) = path.parent() {
@@ -152,6 +406,7 @@ fn save_cache(info: &UpdateInfo) -> Result<()> {
     let cache = UpdateCache {
         info: info.clone(),
         cached_at: Utc::now(),
+        selection: selection.to_string(),
     };
 
     let content = serde_json::to_string_pretty(&cache)?;
@@ -307,6 +562,155 @@ mod tests {
         assert!(is_newer_version("0.1.0", ""));
     }
 
+    #[test]
+    fn compare_versions_orders_prereleases() {
+        assert_eq!(compare_versions("1.10", "1.10.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10.0", "1.9.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.10.0-rc.1", "1.10.0"), Ordering::Less);
+        assert_eq!(
+            compare_versions("1.10.0-rc.2", "1.10.0-rc.1"),
+            Ordering::Greater
+        );
+        assert_eq!(
+            compare_versions("1.10.0-rc.10", "1.10.0-rc.9"),
+            Ordering::Greater
+        );
+        assert_eq!(compare_versions("v1.2.3+build", "1.2.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn version_constraint_operators() {
+        let c = VersionConstraint::parse(">=1.10").unwrap();
+        assert!(c.matches("1.10.0"));
+        assert!(c.matches("2.0.0"));
+        assert!(!c.matches("1.9.5"));
+
+        let c = VersionConstraint::parse(">=1.10, <2").unwrap();
+        assert!(c.matches("1.12.3"));
+        assert!(!c.matches("2.0.0"));
+
+        assert!(VersionConstraint::parse(">1.0").unwrap().matches("1.0.1"));
+        assert!(!VersionConstraint::parse(">1.0").unwrap().matches("1.0.0"));
+        assert!(VersionConstraint::parse("<=1.0").unwrap().matches("1.0.0"));
+        assert!(VersionConstraint::parse("=1.9").unwrap().matches("1.9.0"));
+        assert!(!VersionConstraint::parse("=1.9").unwrap().matches("1.9.1"));
+    }
+
+    #[test]
+    fn version_constraint_bare_version_means_at_least() {
+        let c = VersionConstraint::parse("1.10").unwrap();
+        assert!(c.matches("1.10.0"));
+        assert!(c.matches("1.11.0"));
+        assert!(!c.matches("1.9.0"));
+        assert_eq!(c.to_string(), "1.10");
+    }
+
+    #[test]
+    fn version_constraint_rejects_garbage() {
+        assert!(VersionConstraint::parse("").is_err());
+        assert!(VersionConstraint::parse("latest").is_err());
+        assert!(VersionConstraint::parse(">=1.x").is_err());
+        assert!(VersionConstraint::parse("~>1.0").is_err());
+    }
+
+    #[test]
+    fn update_preferences_from_config_file() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("config.yml");
+
+        assert!(UpdatePreferences::from_config_file(&path).follows_latest_stable());
+
+        fs::write(&path, "settings:\n  update_channel: beta\n").unwrap();
+        let prefs = UpdatePreferences::from_config_file(&path);
+        assert_eq!(prefs.channel, UpdateChannel::Beta);
+        assert_eq!(prefs.describe(), "beta");
+
+        fs::write(&path, "settings:\n  update_pin: v1.9.2\n").unwrap();
+        let prefs = UpdatePreferences::from_config_file(&path);
+        assert_eq!(prefs.pin.as_deref(), Some("1.9.2"));
+        assert!(!prefs.follows_latest_stable());
+        assert_eq!(prefs.describe(), "pinned to 1.9.2");
+    }
+
+    #[test]
+    fn fetch_update_info_stable_uses_latest_release() {
+        use httpmock::prelude::*;
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/releases/latest");
+            then.status(200).json_body(
+                serde_json::json!({"tag_name": "v1.10.0", "html_url": "https://x/1.10.0"}),
+            );
+        });
+
+        let info = fetch_update_info(
+            &server.url("/releases"),
+            &UpdatePreferences::default(),
+            "1.9.0",
+        )
+        .unwrap();
+        assert_eq!(info.latest, "1.10.0");
+        assert!(info.update_available);
+        assert_eq!(info.release_url.as_deref(), Some("https://x/1.10.0"));
+    }
+
+    #[test]
+    fn fetch_update_info_beta_picks_highest_prerelease() {
+        use httpmock::prelude::*;
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/releases");
+            then.status(200).json_body(serde_json::json!([
+                {"tag_name": "v1.10.0", "prerelease": false},
+                {"tag_name": "v1.11.0-beta.2", "prerelease": true},
+                {"tag_name": "v1.11.0-beta.10", "prerelease": true},
+                {"tag_name": "v1.12.0", "draft": true},
+            ]));
+        });
+
+        let prefs = UpdatePreferences {
+            channel: UpdateChannel::Beta,
+            pin: None,
+        };
+        let info = fetch_update_info(&server.url("/releases"), &prefs, "1.11.0-beta.2").unwrap();
+        assert_eq!(info.latest, "1.11.0-beta.10");
+        assert!(info.update_available);
+    }
+
+    #[test]
+    fn fetch_update_info_pin_can_downgrade() {
+        use httpmock::prelude::*;
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/releases/tags/v1.8.0");
+            then.status(200)
+                .json_body(serde_json::json!({"tag_name": "v1.8.0"}));
+        });
+
+        let prefs = UpdatePreferences {
+            channel: UpdateChannel::Stable,
+            pin: Some("1.8.0".to_string()),
+        };
+        let url = server.url("/releases");
+        assert!(
+            fetch_update_info(&url, &prefs, "1.9.0")
+                .unwrap()
+                .update_available
+        );
+        assert!(
+            !fetch_update_info(&url, &prefs, "1.8.0")
+                .unwrap()
+                .update_available
+        );
+
+        let missing = UpdatePreferences {
+            pin: Some("0.0.1".to_string()),
+            ..prefs
+        };
+        let err = fetch_update_info(&url, &missing, "1.9.0").unwrap_err();
+        assert!(err.to_string().contains("Pinned version 0.0.1"));
+    }
+
     #[test]
     fn is_newer_version_invalid_format() {
         // Edge case: non-numeric components are filtered out
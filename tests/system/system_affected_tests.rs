@@ -0,0 +1,120 @@
+//! System tests for `bivvy affected`.
+//!
+//! Builds a git history in an isolated project and checks which steps
+//! `affected --since` reports for the files changed since a ref.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use system::helpers::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"
+app_name: "AffectedTest"
+steps:
+  bundle:
+    command: "cargo --version"
+    check:
+      type: change
+      target: Gemfile.lock
+  yarn:
+    command: "rustc --version"
+    check:
+      type: change
+      target: yarn.lock
+workflows:
+  default:
+    steps: [bundle, yarn]
+"#;
+
+/// Run `git` in the project with HOME pinned to its test home.
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", project_test_home(dir))
+        .output()
+        .expect("Failed to run git");
+    assert!(output.status.success(), "git {args:?} failed");
+}
+
+/// A project whose last commit changes `Gemfile.lock` and `README.md`.
+fn setup_affected_project() -> TempDir {
+    let temp = setup_project_with_git(CONFIG);
+    fs::write(temp.path().join("Gemfile.lock"), "gems\n").unwrap();
+    fs::write(temp.path().join("yarn.lock"), "packages\n").unwrap();
+    fs::write(temp.path().join("README.md"), "Demo\n").unwrap();
+    git(temp.path(), &["add", "."]);
+    git(temp.path(), &["commit", "-m", "Add lockfiles"]);
+    fs::write(temp.path().join("Gemfile.lock"), "more gems\n").unwrap();
+    fs::write(temp.path().join("README.md"), "Demo app\n").unwrap();
+    git(temp.path(), &["commit", "-am", "Update gems"]);
+    temp
+}
+
+// =====================================================================
+// HAPPY PATH
+// =====================================================================
+
+/// Only steps whose change check target differs from the ref are listed.
+#[test]
+fn affected_lists_steps_changed_since_ref() {
+    let temp = setup_affected_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["affected", "--since", "HEAD~1"])
+        .assert()
+        .code(0)
+        .stdout("1 step affected by 2 changed files since HEAD~1\n\n  bundle  Gemfile.lock\n")
+        .stderr("");
+}
+
+/// Nothing changed since HEAD, so no steps are affected.
+#[test]
+fn affected_since_head_reports_nothing() {
+    let temp = setup_affected_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["affected", "--since", "HEAD"])
+        .assert()
+        .code(0)
+        .stdout("No steps affected by 0 changed files since HEAD\n")
+        .stderr("");
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy affected --help`.
+#[test]
+fn affected_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["affected", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("affected_help", String::from_utf8_lossy(&output.stdout));
+}
+
+// =====================================================================
+// SAD PATH
+// =====================================================================
+
+/// A ref git can't resolve exits 1 with git's own explanation.
+#[test]
+fn affected_unknown_ref_fails_with_exit_1() {
+    let temp = setup_affected_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["affected", "--since", "missing-ref"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Cannot diff against 'missing-ref': fatal: Not a valid object name missing-ref\n");
+}
@@ -1,7 +1,7 @@
 //! Comprehensive system tests for `bivvy cache`.
 //!
-//! Tests cache management subcommands: `list`, `stats`, `clear`, and their
-//! flag variants.  Each test is isolated by setting `HOME` and
+//! Tests cache management subcommands: `list`, `stats`, `clear`, `warm`,
+//! and their flag variants.  Each test is isolated by setting `HOME` and
 //! `XDG_CACHE_HOME` to a temp directory so the cache is deterministic.
 //!
 //! When a test needs a non-empty cache, it seeds entries directly through
@@ -14,6 +14,7 @@ mod system;
 use assert_cmd::cargo::cargo_bin;
 use bivvy::cache::CacheStore;
 use expectrl::Session;
+use httpmock::prelude::*;
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::time::Duration;
@@ -463,6 +464,87 @@ fn cache_full_lifecycle_on_seeded_cache() {
     assert!(store.list().unwrap().is_empty());
 }
 
+// =====================================================================
+// HAPPY PATH — cache warm
+// =====================================================================
+
+/// `cache warm` fetches every template source the project references, so
+/// a later `--offline` command resolves templates without the network.
+#[test]
+fn cache_warm_fetches_sources_for_offline_use() {
+    let server = MockServer::start();
+    let source = server.mock(|when, then| {
+        when.method(GET).path("/templates.yml");
+        then.status(200).body(
+            r#"name: company-bootstrap
+description: "Company-wide bootstrap step"
+category: company
+step:
+  command: cargo fetch
+"#,
+        );
+    });
+    let url = server.url("/templates.yml");
+    let temp = setup_project(&format!(
+        r#"app_name: "WarmTest"
+template_sources:
+  - url: "{url}"
+steps:
+  tools:
+    command: "cargo --version"
+workflows:
+  default:
+    steps: [tools]
+"#
+    ));
+
+    bivvy_assert_cmd(temp.path())
+        .args(["cache", "warm"])
+        .assert()
+        .code(0)
+        .stdout(format!(
+            "✓ Cached template source {url}\nFetched 1 remote sources; `--offline` runs can use them\n"
+        ))
+        .stderr("");
+    source.assert_calls(1);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["--offline", "add", "company-bootstrap"])
+        .assert()
+        .code(0)
+        .stdout(
+            "\
+✓ Added 'company-bootstrap' step using template 'company-bootstrap'
+  Added to 'default' workflow
+  💡 Run `bivvy run --only=company-bootstrap` to run it, or `bivvy list` to see all steps.
+",
+        )
+        .stderr("");
+    source.assert_calls(1);
+}
+
+/// `cache warm` on a config without remote sources has nothing to fetch.
+#[test]
+fn cache_warm_without_remote_sources() {
+    let temp = setup_project(
+        r#"app_name: "WarmTest"
+steps:
+  tools:
+    command: "cargo --version"
+workflows:
+  default:
+    steps: [tools]
+"#,
+    );
+
+    bivvy_assert_cmd(temp.path())
+        .args(["cache", "warm"])
+        .assert()
+        .code(0)
+        .stdout("No remote sources to fetch\n")
+        .stderr("");
+}
+
 // =====================================================================
 // HELP — snapshot-based regression protection
 // =====================================================================
@@ -492,6 +574,16 @@ fn cache_clear_help_snapshot() {
     insta::assert_snapshot!("cache_clear_help", stdout);
 }
 
+/// Snapshot of `bivvy cache warm --help`.
+#[test]
+fn cache_warm_help_snapshot() {
+    let home = TempDir::new().unwrap();
+    let (stdout, _stderr, code) =
+        run_cache_isolated(&["cache", "warm", "--help"], home.path());
+    assert_eq!(code, 0, "cache warm --help should exit 0");
+    insta::assert_snapshot!("cache_warm_help", stdout);
+}
+
 /// Snapshot of `bivvy cache stats --help`.
 #[test]
 fn cache_stats_help_snapshot() {
@@ -562,3 +654,30 @@ fn cache_list_unknown_flag_is_usage_error_with_exit_2() {
     );
     insta::assert_snapshot!("cache_list_unknown_flag_stderr", stderr);
 }
+
+/// `cache warm` needs the network, so it refuses to run offline and
+/// exits 1 without fetching anything.
+#[test]
+fn cache_warm_offline_fails_with_exit_1() {
+    let temp = setup_project("app_name: \"WarmTest\"\nsteps: {}\n");
+
+    bivvy_assert_cmd(temp.path())
+        .args(["--offline", "cache", "warm"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Cannot warm the cache in offline mode\n");
+}
+
+/// `cache warm` outside a project exits 1 with the documented message.
+#[test]
+fn cache_warm_without_config_fails_with_exit_1() {
+    let temp = TempDir::new().unwrap();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["cache", "warm"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ No configuration found. Run 'bivvy init' first.\n");
+}
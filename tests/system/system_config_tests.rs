@@ -158,6 +158,24 @@ const LOCAL_OVERRIDE_SETTINGS: &str = "settings:\n  defaults:\n    output: quiet
 const LOCAL_OVERRIDE_ADD_STEP: &str =
     "steps:\n  local-step:\n    command: \"git --version\"\n";
 
+/// Hand-written config with comments and a flow map, used by the
+/// `config set` / `config unset` tests to check edits keep the rest of
+/// the file as written.
+const EDIT_CONFIG: &str = r#"app_name: "EditConfig"
+
+# Steps
+steps:
+  deps:
+    command: "cargo --version"  # installs
+  build:
+    command: "rustc --version"
+    depends_on: [deps]
+    env: { PORT: "3000" }
+workflows:
+  default:
+    steps: [deps, build]
+"#;
+
 // ─────────────────────────────────────────────────────────────────────
 // Helpers
 // ─────────────────────────────────────────────────────────────────────
@@ -517,6 +535,76 @@ fn config_tests_merged_local_override_changes_settings() {
     assert_exit_code(&s, 0);
 }
 
+// =====================================================================
+// HAPPY PATH — config set / unset
+// =====================================================================
+
+/// `config set` and `config unset` edit values in place, keeping
+/// comments and the rest of the file as written.
+#[test]
+fn config_tests_set_and_unset_edit_in_place() {
+    let temp = setup_project(EDIT_CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "set", "steps.build.env.PORT", "4000"])
+        .assert()
+        .code(0)
+        .stdout("✓ Set steps.build.env.PORT in .bivvy/config.yml\n")
+        .stderr("");
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "set", "steps.deps.title", "Install deps"])
+        .assert()
+        .code(0)
+        .stdout("✓ Set steps.deps.title in .bivvy/config.yml\n")
+        .stderr("");
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "unset", "steps.build.depends_on"])
+        .assert()
+        .code(0)
+        .stdout("✓ Removed steps.build.depends_on from .bivvy/config.yml\n")
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        r#"app_name: "EditConfig"
+
+# Steps
+steps:
+  deps:
+    command: "cargo --version"  # installs
+    title: Install deps
+  build:
+    command: "rustc --version"
+    env: {PORT: '4000'}
+workflows:
+  default:
+    steps: [deps, build]
+"#
+    );
+}
+
+/// A value that reads as a YAML mapping is stored as a string when the
+/// field only takes strings, so `FOO "a: b"` doesn't fail as a map.
+#[test]
+fn config_tests_set_string_containing_colon_space() {
+    let temp = setup_project(EDIT_CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "set", "steps.deps.env.FOO", "a: b"])
+        .assert()
+        .code(0)
+        .stdout("✓ Set steps.deps.env.FOO in .bivvy/config.yml\n")
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        EDIT_CONFIG.replace(
+            "    command: \"cargo --version\"  # installs\n",
+            "    command: \"cargo --version\"  # installs\n    env:\n      FOO: 'a: b'\n",
+        )
+    );
+}
+
 // =====================================================================
 // HELP
 // =====================================================================
@@ -640,3 +728,43 @@ fn config_tests_malformed_yaml_fails() {
     // through `main` and produces exit code 1.
     assert_exit_code(&s, 1);
 }
+
+/// `config set` refuses a value that would make the config invalid and
+/// exits 1 without changing the file.
+#[test]
+fn config_tests_set_invalid_value_fails_with_exit_1() {
+    let temp = setup_project(EDIT_CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "set", "steps.build.depends_on", "deps"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(
+            "✗ The change would make the config invalid: invalid type: string \"deps\", expected a sequence\n",
+        );
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        EDIT_CONFIG
+    );
+}
+
+/// `config unset` of a key that isn't set exits 1 without changing the
+/// file.
+#[test]
+fn config_tests_unset_missing_key_fails_with_exit_1() {
+    let temp = setup_project(EDIT_CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["config", "unset", "steps.test"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ No value at 'steps.test' in .bivvy/config.yml\n");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        EDIT_CONFIG
+    );
+}
@@ -0,0 +1,73 @@
+//! System tests for `bivvy detect`.
+//!
+//! Runs the binary against projects with known marker files and checks
+//! the exit code and the complete report of technologies, package
+//! managers and proposed templates.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use system::helpers::*;
+use tempfile::TempDir;
+
+/// Create a project directory containing `files`, without a config.
+fn setup_detection_project(files: &[(&str, &str)]) -> TempDir {
+    let temp = TempDir::new().unwrap();
+    for (path, content) in files {
+        fs::write(temp.path().join(path), content).unwrap();
+    }
+    temp
+}
+
+/// A Ruby and Node project lists both stacks, their package managers and
+/// the templates `init` would propose, without needing a config.
+#[test]
+fn detect_reports_technologies_and_proposed_templates() {
+    let temp = setup_detection_project(&[
+        ("Gemfile", "source \"https://rubygems.org\"\n"),
+        ("Gemfile.lock", ""),
+        ("package.json", "{\"name\": \"app\"}\n"),
+        ("yarn.lock", ""),
+    ]);
+
+    bivvy_assert_cmd(temp.path())
+        .arg("detect")
+        .assert()
+        .code(0)
+        .stdout(
+            "\
+Detected technologies:
+✓ Ruby (100% confidence)
+    Files:      Gemfile
+    Template:   bundle-install
+✓ Node.js (100% confidence)
+    Files:      package.json, yarn.lock
+    Template:   yarn-install
+
+Package managers:
+✓ Bundler (100% confidence)
+    Files:      Gemfile, Gemfile.lock
+✓ Yarn (100% confidence)
+    Files:      yarn.lock
+
+Proposed templates:
+  bundle-install — Gemfile found (builtin)
+  yarn-install — package.json found (builtin)
+",
+        )
+        .stderr("");
+}
+
+/// Snapshot of `bivvy detect --help`.
+#[test]
+fn detect_help_snapshot() {
+    let temp = TempDir::new().unwrap();
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["detect", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("detect_help", String::from_utf8_lossy(&output.stdout));
+}
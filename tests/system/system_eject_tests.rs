@@ -0,0 +1,157 @@
+//! System tests for `bivvy eject` and `bivvy templates outdated`.
+//!
+//! Ejects a step from a project-local template, then changes the
+//! template and checks that `templates outdated` reports the change.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use system::helpers::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"app_name: "EjectTest"
+steps:
+  build:
+    template: build
+  tools:
+    command: "cargo --version"
+workflows:
+  default:
+    steps: [build, tools]
+"#;
+
+const BUILD_TEMPLATE: &str = r#"name: build
+description: "Build the project"
+category: custom
+version: "1.0.0"
+step:
+  title: "Build"
+  command: "cargo build"
+"#;
+
+/// A project whose `build` step uses the project-local `build` template.
+fn setup_eject_project() -> TempDir {
+    let temp = setup_project(CONFIG);
+    write_template(&temp, BUILD_TEMPLATE);
+    temp
+}
+
+/// Write the project-local `build` template.
+fn write_template(temp: &TempDir, content: &str) {
+    let dir = temp.path().join(".bivvy/templates/steps");
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("build.yml"), content).unwrap();
+}
+
+// =====================================================================
+// HAPPY PATH
+// =====================================================================
+
+/// `eject` replaces the template reference with the template's fields
+/// and records where they came from.
+#[test]
+fn eject_inlines_template_fields() {
+    let temp = setup_eject_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["eject", "build"])
+        .assert()
+        .code(0)
+        .stdout(
+            "\
+✓ Ejected 'build' from template 'build' (1.0.0)
+  Run 'bivvy templates outdated' to see later changes to the template
+",
+        )
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        r#"app_name: "EjectTest"
+steps:
+  build:
+    title: Build
+    command: cargo build
+    ejected_from:
+      template: build
+      version: 1.0.0
+  tools:
+    command: "cargo --version"
+workflows:
+  default:
+    steps: [build, tools]
+"#
+    );
+}
+
+/// `templates outdated` is quiet until the template changes, then shows
+/// the new version and what changed in it.
+#[test]
+fn templates_outdated_shows_template_changes() {
+    let temp = setup_eject_project();
+    run_bivvy_silently(temp.path(), &["eject", "build"]);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["templates", "outdated"])
+        .assert()
+        .code(0)
+        .stdout("✓ All 1 ejected step(s) are up to date\n")
+        .stderr("");
+
+    write_template(
+        &temp,
+        &BUILD_TEMPLATE
+            .replace("1.0.0", "1.1.0")
+            .replace("cargo build", "cargo build --locked"),
+    );
+
+    bivvy_assert_cmd(temp.path())
+        .args(["templates", "outdated"])
+        .assert()
+        .code(0)
+        .stdout(
+            "  build  build 1.0.0 → 1.1.0
+  title: Build
+- command: cargo build
++ command: cargo build --locked
+
+  1 ejected step(s) behind their template. Copy over the changes you want, then set `ejected_from.version` to the new version.
+",
+        )
+        .stderr("");
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy eject --help`.
+#[test]
+fn eject_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["eject", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("eject_help", String::from_utf8_lossy(&output.stdout));
+}
+
+// =====================================================================
+// SAD PATH
+// =====================================================================
+
+/// Ejecting a step that doesn't use a template exits 1.
+#[test]
+fn eject_step_without_template_fails_with_exit_1() {
+    let temp = setup_eject_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["eject", "tools"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Step 'tools' doesn't use a template.\n");
+}
@@ -0,0 +1,109 @@
+//! System tests for `bivvy env`.
+//!
+//! Checks the listing of effective variables with their sources, the
+//! `--export` shell output and error handling for unknown steps.
+#![cfg(unix)]
+
+mod system;
+
+use system::helpers::*;
+
+const CONFIG: &str = r#"
+app_name: "EnvTest"
+settings:
+  env:
+    APP_ENV: development
+steps:
+  deps:
+    command: "cargo --version"
+    env:
+      DEPS_DIR: vendor
+  build:
+    command: "rustc --version"
+    depends_on: [deps]
+    env:
+      GREETING: "it's ready"
+workflows:
+  default:
+    steps: [deps, build]
+"#;
+
+// =====================================================================
+// HAPPY PATH
+// =====================================================================
+
+/// `env` lists the effective variables and where each one is set.
+#[test]
+fn env_shows_variables_and_sources() {
+    let temp = setup_project(CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["env", "--env", "development", "--step", "build"])
+        .assert()
+        .code(0)
+        .stdout(
+            "  Environment: development (--env flag)
+  Workflow: default
+  Step: build
+
+  APP_ENV=development  (settings.env_vars.env)
+  GREETING=it's ready  (steps.build.env)
+",
+        )
+        .stderr("");
+}
+
+/// `env --export` prints lines a shell can `eval`, quoting each value.
+#[test]
+fn env_export_prints_quoted_shell_lines() {
+    let temp = setup_project(CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args([
+            "env",
+            "--env",
+            "development",
+            "--step",
+            "build",
+            "--export",
+            "bash",
+        ])
+        .assert()
+        .code(0)
+        .stdout("export APP_ENV='development'\nexport GREETING='it'\\''s ready'\n")
+        .stderr("");
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy env --help`.
+#[test]
+fn env_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["env", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("env_help", String::from_utf8_lossy(&output.stdout));
+}
+
+// =====================================================================
+// SAD PATH
+// =====================================================================
+
+/// `env --step` with a step that isn't configured exits 1.
+#[test]
+fn env_unknown_step_fails_with_exit_1() {
+    let temp = setup_project(CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["env", "--env", "development", "--step", "nope"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Unknown step: nope\n");
+}
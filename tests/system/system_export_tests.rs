@@ -0,0 +1,232 @@
+//! System tests for `bivvy export`.
+//!
+//! Exports a Ruby project's workflow as a CI pipeline, a Dockerfile and a
+//! devcontainer, and checks the complete generated output.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use system::helpers::*;
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"
+app_name: "ExportTest"
+steps:
+  gems:
+    template: bundle-install
+    checks:
+      - type: change
+        target: Gemfile
+      - type: change
+        target: Gemfile.lock
+  build:
+    command: make build
+    depends_on: [gems]
+workflows:
+  default:
+    steps: [gems, build]
+"#;
+
+/// A Ruby project with the export config.
+fn setup_export_project() -> TempDir {
+    let temp = setup_project(CONFIG);
+    fs::write(
+        temp.path().join("Gemfile"),
+        "source \"https://rubygems.org\"\n",
+    )
+    .unwrap();
+    fs::write(temp.path().join("Gemfile.lock"), "gems\n").unwrap();
+    temp
+}
+
+// =====================================================================
+// HAPPY PATH — export ci
+// =====================================================================
+
+/// `export ci` prints a pipeline that sets up the workflow's tools and
+/// runs it with bivvy.
+#[test]
+fn export_ci_prints_github_workflow() {
+    let temp = setup_export_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["export", "ci", "--provider", "github"])
+        .assert()
+        .code(0)
+        .stdout(
+            r#"# Generated by `bivvy export ci` from the 'default' workflow.
+# Regenerate it after changing the bivvy configuration.
+
+name: bivvy
+on:
+- push
+- pull_request
+jobs:
+  setup:
+    runs-on: ubuntu-latest
+    steps:
+    - uses: actions/checkout@v4
+    - uses: ruby/setup-ruby@v1
+      with:
+        ruby-version: '3.3'
+    - name: Install bivvy
+      run: |
+        curl -fsSL https://bivvy.dev/install | sh
+        echo "$HOME/.local/bin" >> "$GITHUB_PATH"
+    - name: Run bivvy
+      run: bivvy run --non-interactive --env ci
+"#,
+        )
+        .stderr("");
+}
+
+// =====================================================================
+// HAPPY PATH — export dockerfile / devcontainer
+// =====================================================================
+
+/// `export dockerfile` builds steps whose change checks cover every file
+/// their command reads in their own layer, and leaves the rest for
+/// `bivvy run` in the container.
+#[test]
+fn export_dockerfile_builds_steps_with_complete_inputs() {
+    let temp = setup_export_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["export", "dockerfile"])
+        .assert()
+        .code(0)
+        .stdout(
+            r#"# syntax=docker/dockerfile:1
+# Generated by `bivvy export` from the 'default' workflow.
+# Regenerate it after changing the bivvy configuration.
+FROM buildpack-deps:bookworm
+
+ENV PATH="/root/.local/bin:/root/.local/share/mise/shims:/root/.cargo/bin:$PATH"
+WORKDIR /app
+
+# mise
+RUN curl https://mise.run | sh
+
+# ruby
+RUN mise install ruby
+
+# bivvy
+RUN curl -fsSL https://bivvy.dev/install | sh
+COPY .bivvy/ .bivvy/
+
+# gems
+COPY Gemfile Gemfile.lock ./
+RUN bivvy run --non-interactive --env docker --only gems
+
+COPY . .
+
+# Run in the container with `bivvy run --non-interactive --env docker`:
+#   build: has no file inputs to build on
+"#,
+        )
+        .stderr("");
+}
+
+/// `export devcontainer` writes devcontainer.json and the codespace
+/// Dockerfile.
+#[test]
+fn export_devcontainer_writes_files() {
+    let temp = setup_export_project();
+    let project_name = temp.path().file_name().unwrap().to_string_lossy();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["export", "devcontainer"])
+        .assert()
+        .code(0)
+        .stdout(format!(
+            "✓ Wrote {}/.devcontainer/devcontainer.json and Dockerfile\n",
+            temp.path().display()
+        ))
+        .stderr("");
+
+    let devcontainer = temp.path().join(".devcontainer");
+    assert_eq!(
+        fs::read_to_string(devcontainer.join("devcontainer.json")).unwrap(),
+        format!(
+            r#"// Generated by `bivvy export devcontainer` from the 'default' workflow.
+// Regenerate it after changing the bivvy configuration.
+{{
+  "build": {{
+    "context": "..",
+    "dockerfile": "Dockerfile"
+  }},
+  "name": "{project_name}",
+  "postCreateCommand": "bivvy run --non-interactive --env codespace"
+}}
+"#
+        )
+    );
+    let dockerfile = bivvy_assert_cmd(temp.path())
+        .args(["export", "dockerfile", "--env", "codespace"])
+        .output()
+        .unwrap();
+    assert_eq!(
+        fs::read_to_string(devcontainer.join("Dockerfile")).unwrap(),
+        String::from_utf8_lossy(&dockerfile.stdout)
+    );
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy export --help`.
+#[test]
+fn export_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["export", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("export_help", String::from_utf8_lossy(&output.stdout));
+}
+
+// =====================================================================
+// SAD PATH
+// =====================================================================
+
+/// `export ci` for a workflow that isn't configured exits 1.
+#[test]
+fn export_ci_unknown_workflow_fails_with_exit_1() {
+    let temp = setup_export_project();
+
+    bivvy_assert_cmd(temp.path())
+        .args([
+            "export",
+            "ci",
+            "--provider",
+            "github",
+            "--workflow",
+            "missing",
+        ])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Unknown workflow: missing\n");
+}
+
+/// `export devcontainer` won't overwrite existing files without
+/// `--force`, and exits 1.
+#[test]
+fn export_devcontainer_existing_files_fails_with_exit_1() {
+    let temp = setup_export_project();
+    run_bivvy_silently(temp.path(), &["export", "devcontainer"]);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["export", "devcontainer"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr(format!(
+            "✗ {}/.devcontainer/devcontainer.json already exists. Use --force to overwrite it.\n",
+            temp.path().display()
+        ));
+}
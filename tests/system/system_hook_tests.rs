@@ -0,0 +1,115 @@
+//! System tests for `bivvy hook`.
+//!
+//! Installs the git and shell hooks into isolated projects and homes and
+//! checks the exact files written and the messages printed, including
+//! re-running an install that is already in place.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use system::helpers::*;
+
+const CONFIG: &str = r#"
+app_name: "HookTest"
+steps:
+  tools:
+    command: "cargo --version"
+workflows:
+  default:
+    steps: [tools]
+"#;
+
+/// post-checkout only reports on branch switches (`$3` is 1), not on
+/// checkouts of individual files.
+const POST_CHECKOUT_HOOK: &str = r#"#!/bin/sh
+
+# >>> bivvy hook >>>
+if [ "$3" = "1" ] && command -v bivvy >/dev/null 2>&1; then
+  bivvy status --stale --quiet || true
+fi
+# <<< bivvy hook <<<
+"#;
+
+const POST_MERGE_HOOK: &str = r#"#!/bin/sh
+
+# >>> bivvy hook >>>
+if command -v bivvy >/dev/null 2>&1; then
+  bivvy status --stale --quiet || true
+fi
+# <<< bivvy hook <<<
+"#;
+
+// =====================================================================
+// HAPPY PATH
+// =====================================================================
+
+/// `hook install git` adds post-checkout and post-merge hooks that report
+/// stale steps, and is a no-op when they are already installed.
+#[test]
+fn hook_install_git_writes_hooks_once() {
+    let temp = setup_project_with_git(CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["hook", "install", "git"])
+        .assert()
+        .code(0)
+        .stdout("✓ Installed post-checkout hook\n✓ Installed post-merge hook\n")
+        .stderr("");
+
+    let hooks = temp.path().join(".git/hooks");
+    assert_eq!(
+        fs::read_to_string(hooks.join("post-checkout")).unwrap(),
+        POST_CHECKOUT_HOOK
+    );
+    assert_eq!(
+        fs::read_to_string(hooks.join("post-merge")).unwrap(),
+        POST_MERGE_HOOK
+    );
+
+    bivvy_assert_cmd(temp.path())
+        .args(["hook", "install", "git"])
+        .assert()
+        .code(0)
+        .stdout("post-checkout hook is already installed\npost-merge hook is already installed\n")
+        .stderr("");
+}
+
+/// `hook install bash` appends the prompt hook to `~/.bashrc`.
+#[test]
+fn hook_install_bash_appends_to_rc_file() {
+    let temp = setup_project(CONFIG);
+    let home = project_test_home(temp.path());
+
+    bivvy_assert_cmd(temp.path())
+        .args(["hook", "install", "bash"])
+        .assert()
+        .code(0)
+        .stdout(format!(
+            "✓ Installed prompt hook in {}/.bashrc\n  Open a new shell to start seeing stale step notices.\n",
+            home.display()
+        ))
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(home.join(".bashrc")).unwrap(),
+        "# >>> bivvy hook >>>\neval \"$(bivvy hook print bash)\"\n# <<< bivvy hook <<<\n"
+    );
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy hook --help`.
+#[test]
+fn hook_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["hook", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("hook_help", String::from_utf8_lossy(&output.stdout));
+}
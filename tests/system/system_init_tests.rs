@@ -3,7 +3,8 @@
 //! Tests the full interactive initialization experience including
 //! technology detection, template selection via MultiSelect prompts,
 //! the "Run setup now?" follow-up, all flag combinations, conflict
-//! detection, .gitignore updates, and generated config content verification.
+//! detection, .gitignore updates, setup script imports, and generated
+//! config content verification.
 #![cfg(unix)]
 
 mod system;
@@ -51,6 +52,16 @@ fn read_generated_config(temp: &TempDir) -> String {
     fs::read_to_string(config_path).unwrap()
 }
 
+/// Replace the bivvy version, the test home and the project directory
+/// name in `text` with placeholders so snapshots are stable.
+fn redact_project(temp: &TempDir, text: &str) -> String {
+    let home = project_test_home(temp.path());
+    let name = temp.path().file_name().unwrap().to_string_lossy();
+    text.replace(&format!("v{}", env!("CARGO_PKG_VERSION")), "v[VERSION]")
+        .replace(&home.display().to_string(), "[HOME]")
+        .replace(name.as_ref(), "[NAME]")
+}
+
 // =====================================================================
 // HAPPY PATH — Interactive init with technology detection
 // =====================================================================
@@ -1848,6 +1859,51 @@ fn init_generated_config_has_template_comments() {
     );
 }
 
+// =====================================================================
+// IMPORT
+// =====================================================================
+
+/// --import turns a setup script into template and custom steps, shows
+/// the config it writes and lists the lines it couldn't import.
+#[test]
+fn init_import_builds_steps_from_setup_script() {
+    let temp = setup_detection_project(&[(
+        "bin/setup",
+        r#"#!/bin/sh
+set -e
+cd "$(dirname "$0")/.."
+export RAILS_ENV=development
+
+# Install dependencies
+bundle install
+yarn
+
+echo "== Preparing database =="
+bin/rails db:prepare
+mkdir -p tmp/cache
+"#,
+    )]);
+
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["init", "--import", "bin/setup"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!(
+        "init_import_output",
+        redact_project(&temp, &String::from_utf8_lossy(&output.stdout))
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "⚠ 1 line(s) weren't imported; move them into steps by hand:\n"
+    );
+    insta::assert_snapshot!(
+        "init_import_config",
+        redact_project(&temp, &read_generated_config(&temp))
+    );
+}
+
 // =====================================================================
 // SAD PATH
 // =====================================================================
@@ -1985,6 +2041,28 @@ fn init_from_missing_config_fails() {
     );
 }
 
+/// --import of a missing file exits 1 without writing a config.
+#[test]
+fn init_import_missing_file_fails() {
+    let temp = TempDir::new().unwrap();
+
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["init", "--import", "bin/setup"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        redact_project(&temp, &String::from_utf8_lossy(&output.stdout)),
+        "\n⛺ bivvy v[VERSION] · init\n\n"
+    );
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "✗ Failed to read bin/setup: No such file or directory (os error 2)\n"
+    );
+    assert!(!temp.path().join(".bivvy/config.yml").exists());
+}
+
 // =====================================================================
 // EDGE CASES
 // =====================================================================
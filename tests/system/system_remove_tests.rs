@@ -0,0 +1,108 @@
+//! System tests for `bivvy remove`.
+//!
+//! Removes steps from a project that has already run and checks the
+//! rewritten config, the reported reference updates and the cleared
+//! state.
+#![cfg(unix)]
+
+mod system;
+
+use std::fs;
+use system::helpers::*;
+
+const CONFIG: &str = r#"app_name: "RemoveTest"
+steps:
+  deps:
+    command: "cargo --version"
+  build:
+    command: "rustc --version"
+    depends_on: [deps]
+  test:
+    command: "git --version"
+    depends_on: [build]
+workflows:
+  default:
+    steps: [deps, build, test]
+"#;
+
+// =====================================================================
+// HAPPY PATH
+// =====================================================================
+
+/// `remove` deletes a step, reconnects the steps that depended on it,
+/// drops it from workflows and clears its saved state.
+#[test]
+fn remove_deletes_step_and_references() {
+    let temp = setup_project(CONFIG);
+    run_workflow_silently(temp.path());
+
+    bivvy_assert_cmd(temp.path())
+        .args(["remove", "build"])
+        .assert()
+        .code(0)
+        .stdout(
+            "\
+✓ Removed step 'build'
+  Updated steps.test.depends_on
+  Updated workflows.default.steps
+  test now depend(s) on deps
+  Cleared saved state for 'build'
+",
+        )
+        .stderr("");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        r#"app_name: "RemoveTest"
+steps:
+  deps:
+    command: "cargo --version"
+  test:
+    command: "git --version"
+    depends_on: [deps]
+workflows:
+  default:
+    steps: [deps, test]
+"#
+    );
+}
+
+// =====================================================================
+// HELP
+// =====================================================================
+
+/// Snapshot of `bivvy remove --help`.
+#[test]
+fn remove_help_snapshot() {
+    let temp = setup_project(CONFIG);
+    let output = bivvy_assert_cmd(temp.path())
+        .args(["remove", "--help"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    insta::assert_snapshot!("remove_help", String::from_utf8_lossy(&output.stdout));
+}
+
+// =====================================================================
+// SAD PATH
+// =====================================================================
+
+/// Removing a step that isn't configured exits 1 and leaves the config
+/// untouched.
+#[test]
+fn remove_unknown_step_fails_with_exit_1() {
+    let temp = setup_project(CONFIG);
+
+    bivvy_assert_cmd(temp.path())
+        .args(["remove", "lint"])
+        .assert()
+        .code(1)
+        .stdout("")
+        .stderr("✗ Step 'lint' not found in configuration.\n");
+
+    assert_eq!(
+        fs::read_to_string(temp.path().join(".bivvy/config.yml")).unwrap(),
+        CONFIG
+    );
+}
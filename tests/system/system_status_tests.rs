@@ -8,6 +8,7 @@
 mod system;
 
 use expectrl::WaitStatus;
+use std::fs;
 use system::helpers::*;
 
 // ─────────────────────────────────────────────────────────────────────
@@ -716,6 +717,51 @@ fn status_json_shows_skipped_in_wrong_env() {
     );
 }
 
+/// --stale reports steps whose change check target changed since the
+/// step last completed, and --quiet reduces that to the summary line.
+#[test]
+fn status_stale_reports_changed_targets() {
+    let temp = setup_project(
+        r#"
+app_name: "StaleTest"
+steps:
+  bundle:
+    command: "cargo --version"
+    check:
+      type: change
+      target: Gemfile.lock
+workflows:
+  default:
+    steps: [bundle]
+"#,
+    );
+    fs::write(temp.path().join("Gemfile.lock"), "gems\n").unwrap();
+    run_workflow_silently(temp.path());
+
+    bivvy_assert_cmd(temp.path())
+        .args(["status", "--stale"])
+        .assert()
+        .code(0)
+        .stdout("  ✓ All steps are up to date\n")
+        .stderr("");
+
+    fs::write(temp.path().join("Gemfile.lock"), "more gems\n").unwrap();
+
+    bivvy_assert_cmd(temp.path())
+        .args(["status", "--stale"])
+        .assert()
+        .code(0)
+        .stdout("  ⚠ bundle (Gemfile.lock changed)\n\n1 setup step is stale, run `bivvy run`\n")
+        .stderr("");
+
+    bivvy_assert_cmd(temp.path())
+        .args(["status", "--stale", "--quiet"])
+        .assert()
+        .code(0)
+        .stdout("1 setup step is stale, run `bivvy run`\n")
+        .stderr("");
+}
+
 // =====================================================================
 // HELP
 // =====================================================================